@@ -7,23 +7,29 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use arawn_llm::{
-    CompletionRequest, CompletionResponse, ContentBlock, LlmBackend, Message, SharedBackend,
-    SharedEmbedder, ToolResultBlock,
+    CompletionRequest, CompletionResponse, ContentBlock, LlmBackend, Message, RoutingContext,
+    SharedBackend, SharedEmbedder, SharedRouter, ToolResultBlock,
     interaction_log::{InteractionLogger, InteractionRecord},
 };
 use arawn_memory::store::{MemoryStore, RecallQuery};
-use arawn_types::{FsGateResolver, HookOutcome, SharedHookDispatcher, SharedSecretResolver};
+use arawn_types::{
+    AgentSettings, BudgetStatus, ExpandedSkill, FsGateResolver, HookOutcome, MemoryScope,
+    SharedHookDispatcher, SharedSecretResolver, SharedSkillExpander,
+};
 use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, field};
 
 use crate::stream::{AgentStream, create_turn_stream};
 
 use crate::context::estimate_tokens;
 use crate::error::{AgentError, Result};
-use crate::prompt::SystemPromptBuilder;
+use crate::metrics::TurnMeter;
+use crate::prompt::{SharedPluginPrompts, SystemPromptBuilder};
 use crate::tool::{ToolContext, ToolRegistry, ToolResult};
 use crate::types::{
     AgentConfig, AgentResponse, ResponseUsage, Session, ToolCall, ToolResultRecord,
 };
+use crate::usage::{SharedUsageMeter, UsageMeter, spend_limit_message};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Recall Configuration
@@ -50,6 +56,10 @@ impl Default for RecallConfig {
     }
 }
 
+/// Recall configuration shared with whatever keeps it current (e.g. a
+/// config reload), read at the start of every turn.
+pub type SharedRecallConfig = Arc<std::sync::RwLock<RecallConfig>>;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Agent
 // ─────────────────────────────────────────────────────────────────────────────
@@ -66,14 +76,20 @@ pub struct Agent {
     prompt_builder: Option<SystemPromptBuilder>,
     /// Optional interaction logger for structured JSONL capture.
     interaction_logger: Option<Arc<InteractionLogger>>,
+    /// Optional router selecting an LLM profile per request.
+    router: Option<SharedRouter>,
+    /// Optional usage meter for cost accounting and spend limits.
+    usage_meter: Option<SharedUsageMeter>,
     /// Optional memory store for active recall.
     memory_store: Option<Arc<MemoryStore>>,
     /// Optional embedder for computing query embeddings.
     embedder: Option<SharedEmbedder>,
     /// Active recall configuration.
-    recall_config: RecallConfig,
+    recall_config: SharedRecallConfig,
     /// Optional hook dispatcher for plugin lifecycle events.
     hook_dispatcher: Option<SharedHookDispatcher>,
+    /// Optional expander for `/skill` invocations in user messages.
+    skill_expander: Option<SharedSkillExpander>,
     /// Optional resolver for filesystem access gates.
     fs_gate_resolver: Option<FsGateResolver>,
     /// Optional secret resolver for `${{secrets.*}}` handle resolution.
@@ -89,10 +105,13 @@ impl Agent {
             config,
             prompt_builder: None,
             interaction_logger: None,
+            router: None,
+            usage_meter: None,
             memory_store: None,
             embedder: None,
-            recall_config: RecallConfig::default(),
+            recall_config: SharedRecallConfig::default(),
             hook_dispatcher: None,
+            skill_expander: None,
             fs_gate_resolver: None,
             secret_resolver: None,
         }
@@ -118,20 +137,68 @@ impl Agent {
         self.backend.clone()
     }
 
+    /// Get the LLM router, if configured.
+    pub fn router(&self) -> Option<SharedRouter> {
+        self.router.clone()
+    }
+
+    /// Get the usage meter, if configured.
+    pub fn usage_meter(&self) -> Option<SharedUsageMeter> {
+        self.usage_meter.clone()
+    }
+
+    /// Resolve the routing hint for a session.
+    ///
+    /// An explicit session hint wins, then the profile from the session's
+    /// workstream settings, then the configured hint.
+    fn routing_hint(&self, session: &Session, settings: &AgentSettings) -> Option<String> {
+        session
+            .routing_hint()
+            .map(str::to_string)
+            .or_else(|| settings.profile.clone())
+            .or_else(|| self.config.routing_hint.clone())
+    }
+
+    /// Expand a `/skill` invocation, if the message is one.
+    fn expand_skill(&self, user_message: &str) -> Option<ExpandedSkill> {
+        let skill = self.skill_expander.as_ref()?.expand(user_message)?;
+        tracing::debug!(skill = %skill.name, "Expanded skill invocation");
+        Some(skill)
+    }
+
+    /// The tool set a session may use under its settings.
+    fn tools_for(&self, settings: &AgentSettings) -> Arc<ToolRegistry> {
+        if !settings.restricts_tools() {
+            return self.tools.clone();
+        }
+        let names: Vec<&str> = self
+            .tools
+            .names()
+            .into_iter()
+            .filter(|name| settings.allows_tool(name))
+            .collect();
+        Arc::new(self.tools.filtered_by_names(&names))
+    }
+
     /// Get the current system prompt (built dynamically if a builder is present).
     ///
     /// This is the prompt that would be sent to the LLM on the next turn,
     /// without any session context preamble.
     pub fn system_prompt(&self) -> Option<String> {
-        self.build_system_prompt(None)
+        self.build_system_prompt(None, None)
     }
 
     /// Build the system prompt dynamically.
     ///
     /// If a `SystemPromptBuilder` is stored, rebuilds the prompt fresh
     /// (giving current datetime, etc.). Falls back to the static
-    /// `config.system_prompt` if no builder is present.
-    fn build_system_prompt(&self, context_preamble: Option<&str>) -> Option<String> {
+    /// `config.system_prompt` if no builder is present. Workstream
+    /// instructions, when given, are appended as their own section.
+    fn build_system_prompt(
+        &self,
+        context_preamble: Option<&str>,
+        instructions: Option<&str>,
+    ) -> Option<String> {
         // Build from dynamic builder if present
         let base_prompt = if let Some(ref builder) = self.prompt_builder {
             let prompt = builder.build();
@@ -144,6 +211,16 @@ impl Agent {
             self.config.system_prompt.clone()
         };
 
+        // Append workstream instructions
+        let base_prompt = match (base_prompt, instructions) {
+            (Some(prompt), Some(extra)) => Some(format!(
+                "{}\n\n# Workstream Instructions\n\n{}",
+                prompt, extra
+            )),
+            (None, Some(extra)) => Some(format!("# Workstream Instructions\n\n{}", extra)),
+            (prompt, None) => prompt,
+        };
+
         // Merge with context preamble
         match (base_prompt, context_preamble) {
             (Some(prompt), Some(preamble)) => Some(format!(
@@ -166,6 +243,35 @@ impl Agent {
         user_message: &str,
         workstream_id: Option<&str>,
     ) -> Result<AgentResponse> {
+        let span = tracing::info_span!(
+            "agent_turn",
+            session_id = %session.id,
+            mode = "sync",
+            iterations = field::Empty,
+            truncated = field::Empty,
+        );
+        let mut meter = TurnMeter::start("sync");
+        let result = self
+            .run_turn(session, user_message, workstream_id)
+            .instrument(span.clone())
+            .await;
+        if let Ok(response) = &result {
+            span.record("iterations", response.iterations);
+            span.record("truncated", response.truncated);
+            meter.finish(response.iterations, response.truncated);
+        }
+        result
+    }
+
+    async fn run_turn(
+        &self,
+        session: &mut Session,
+        user_message: &str,
+        workstream_id: Option<&str>,
+    ) -> Result<AgentResponse> {
+        let skill = self.expand_skill(user_message);
+        let user_message = skill.as_ref().map_or(user_message, |s| s.prompt.as_str());
+
         // Start a new turn
         let turn = session.start_turn(user_message);
         let turn_id = turn.id;
@@ -180,6 +286,12 @@ impl Agent {
 
         // Build initial messages from session history
         let mut messages = self.build_messages(session);
+        let settings = session.agent_settings().unwrap_or_default();
+        let routing_hint = skill
+            .as_ref()
+            .and_then(|s| s.routing_hint.clone())
+            .or_else(|| self.routing_hint(session, &settings));
+        let tools = self.tools_for(&settings);
 
         // Log initial context size
         let initial_context_tokens = self.estimate_messages_tokens(&messages);
@@ -192,7 +304,10 @@ impl Agent {
         );
 
         // Active recall: inject relevant memories before first LLM call
-        if let Some(context_msg) = self.perform_recall(user_message).await {
+        if let Some(context_msg) = self
+            .perform_recall(user_message, session_id, settings.memory_scope)
+            .await
+        {
             // Insert as second message (after first user message, or at start)
             let insert_pos = 1.min(messages.len());
             messages.insert(insert_pos, context_msg);
@@ -227,28 +342,74 @@ impl Agent {
                 });
             }
 
+            // Stop before the next call once the workstream's hard spend limit is hit
+            if let (Some(meter), Some(ws_id)) = (&self.usage_meter, workstream_id) {
+                let status = meter.budget_status(Some(ws_id));
+                if status.is_exhausted() {
+                    tracing::warn!(%session_id, %turn_id, workstream_id = ws_id, %status, "Spend limit reached — stopping turn");
+                    let text = spend_limit_message(ws_id, &status);
+                    let turn = session.current_turn_mut().unwrap();
+                    turn.complete(&text);
+                    turn.tool_calls = all_tool_calls.clone();
+                    turn.tool_results = all_tool_results.clone();
+
+                    return Ok(AgentResponse {
+                        text,
+                        tool_calls: all_tool_calls,
+                        tool_results: all_tool_results,
+                        iterations,
+                        usage: ResponseUsage::new(total_input_tokens, total_output_tokens),
+                        truncated: true,
+                    });
+                }
+                if iterations == 1 && matches!(status, BudgetStatus::SoftLimitExceeded { .. }) {
+                    tracing::warn!(%session_id, workstream_id = ws_id, %status, "Workstream soft spend limit exceeded");
+                }
+            }
+
             // Build completion request
-            let request = self.build_request(&messages, session.context_preamble());
+            let request =
+                self.build_request(&messages, session.context_preamble(), &settings, &tools);
+
+            // Pick a profile for this call when a router is configured
+            let (backend, request, routing) = match self.router {
+                Some(ref router) => {
+                    let ctx = RoutingContext::from_request(&request)
+                        .with_iteration(iterations)
+                        .with_hint(routing_hint.clone());
+                    let decision = router.route(&request, &ctx).await;
+                    tracing::debug!(
+                        %session_id,
+                        iteration = iterations,
+                        profile = %decision.profile,
+                        reason = %decision.reason,
+                        "LLM route selected"
+                    );
+                    let request = decision.apply(request);
+                    (decision.backend.clone(), request, Some(decision.metadata()))
+                }
+                None => (self.backend.clone(), request, None),
+            };
 
             tracing::debug!(
                 %session_id,
                 iteration = iterations,
                 messages = messages.len(),
-                tools = self.tools.names().len(),
+                tools = tools.names().len(),
                 model = %request.model,
                 "Calling LLM"
             );
 
             // Call LLM with timing
             let call_start = Instant::now();
-            let response = match self.backend.complete(request.clone()).await {
+            let response = match backend.complete(request.clone()).await {
                 Ok(r) => r,
                 Err(e) => {
                     // Check if this is a tool validation error (LLM hallucinated a tool name)
                     // If so, inject feedback and retry instead of failing
                     if e.is_tool_validation_error() {
                         let invalid_tool = e.invalid_tool_name().unwrap_or("unknown");
-                        let available_tools = self.tools.names().join(", ");
+                        let available_tools = tools.names().join(", ");
 
                         tracing::warn!(
                             %session_id,
@@ -278,6 +439,15 @@ impl Agent {
             // Update usage
             total_input_tokens += response.usage.input_tokens;
             total_output_tokens += response.usage.output_tokens;
+            if let Some(ref meter) = self.usage_meter {
+                meter.record(
+                    session_id,
+                    workstream_id,
+                    routing.as_ref().map(|r| r.profile.as_str()),
+                    &request.model,
+                    &response.usage,
+                );
+            }
 
             // Check token budget
             if let Some(max) = self.config.max_total_tokens {
@@ -317,7 +487,10 @@ impl Agent {
 
             // Write structured interaction record
             if let Some(ref logger) = self.interaction_logger {
-                let record = InteractionRecord::from_exchange(&request, &response, duration_ms);
+                let mut record = InteractionRecord::from_exchange(&request, &response, duration_ms);
+                if let Some(routing) = routing {
+                    record = record.with_routing(routing);
+                }
                 if let Err(e) = logger.log(&record) {
                     tracing::warn!(error = %e, "Failed to write interaction log");
                 }
@@ -336,7 +509,7 @@ impl Agent {
 
                 // Execute tools
                 let (tool_calls, tool_results) = self
-                    .execute_tools(&response, session_id, turn_id, workstream_id)
+                    .execute_tools(&tools, &response, session_id, turn_id, workstream_id)
                     .await?;
 
                 // Record tool calls and results
@@ -424,6 +597,9 @@ impl Agent {
         cancellation: CancellationToken,
         workstream_id: Option<&str>,
     ) -> AgentStream {
+        let skill = self.expand_skill(user_message);
+        let user_message = skill.as_ref().map_or(user_message, |s| s.prompt.as_str());
+
         // Start a new turn
         let turn = session.start_turn(user_message);
         let turn_id = turn.id;
@@ -439,16 +615,33 @@ impl Agent {
         let messages = self.build_messages(session);
 
         // Build a config snapshot with a fresh system prompt for this turn
+        let settings = session.agent_settings().unwrap_or_default();
         let mut config = self.config.clone();
-        config.system_prompt = self.build_system_prompt(session.context_preamble());
+        config.system_prompt = self.build_system_prompt(
+            session.context_preamble(),
+            settings.system_prompt.as_deref(),
+        );
+        if let Some(ref model) = settings.model {
+            config.model = model.clone();
+        }
+
+        // A skill's routing hint wins over the session-level one, which
+        // overrides the configured one
+        config.routing_hint = skill
+            .as_ref()
+            .and_then(|s| s.routing_hint.clone())
+            .or_else(|| self.routing_hint(session, &settings));
 
         create_turn_stream(
             self.backend.clone(),
-            self.tools.clone(),
+            self.router.clone(),
+            self.usage_meter.clone(),
+            self.tools_for(&settings),
             config,
             messages,
             session_id,
             turn_id,
+            workstream_id.map(String::from),
             cancellation,
             fs_gate,
             self.secret_resolver.clone(),
@@ -561,19 +754,22 @@ impl Agent {
     /// # Arguments
     /// * `messages` - The conversation messages
     /// * `context_preamble` - Optional session context to prepend to the system prompt
+    /// * `settings` - Workstream settings (model override, extra instructions)
+    /// * `tools` - The tool set offered to the model
     fn build_request(
         &self,
         messages: &[Message],
         context_preamble: Option<&str>,
+        settings: &AgentSettings,
+        tools: &ToolRegistry,
     ) -> CompletionRequest {
-        let mut request = CompletionRequest::new(
-            &self.config.model,
-            messages.to_vec(),
-            self.config.max_tokens,
-        );
+        let model = settings.model.as_deref().unwrap_or(&self.config.model);
+        let mut request = CompletionRequest::new(model, messages.to_vec(), self.config.max_tokens);
 
         // Build system prompt dynamically (fresh datetime, etc.)
-        if let Some(ref prompt) = self.build_system_prompt(context_preamble) {
+        if let Some(ref prompt) =
+            self.build_system_prompt(context_preamble, settings.system_prompt.as_deref())
+        {
             request = request.with_system(prompt);
         }
 
@@ -583,7 +779,7 @@ impl Agent {
         }
 
         // Add tools
-        let tool_defs = self.tools.to_llm_definitions();
+        let tool_defs = tools.to_llm_definitions();
         if !tool_defs.is_empty() {
             request = request.with_tools(tool_defs);
         }
@@ -594,6 +790,7 @@ impl Agent {
     /// Execute tool calls from an LLM response.
     async fn execute_tools(
         &self,
+        tools: &ToolRegistry,
         response: &CompletionResponse,
         session_id: crate::types::SessionId,
         turn_id: crate::types::TurnId,
@@ -661,9 +858,8 @@ impl Agent {
             );
 
             // Execute the tool with per-tool output limits
-            let output_config = self.tools.output_config_for(&tool_use.name);
-            let result = match self
-                .tools
+            let output_config = tools.output_config_for(&tool_use.name);
+            let result = match tools
                 .execute_with_config(&tool_use.name, tool_use.input.clone(), &ctx, &output_config)
                 .await
             {
@@ -714,9 +910,20 @@ impl Agent {
     /// Embeds the user message, queries the memory store, and returns
     /// a system message with relevant context if any matches are found.
     /// Returns `None` if recall is disabled, not configured, or finds nothing.
-    async fn perform_recall(&self, user_message: &str) -> Option<Message> {
+    /// `scope` narrows recall to the current session or turns it off.
+    async fn perform_recall(
+        &self,
+        user_message: &str,
+        session_id: crate::types::SessionId,
+        scope: MemoryScope,
+    ) -> Option<Message> {
         // Guard: recall must be enabled
-        if !self.recall_config.enabled {
+        let recall_config = self
+            .recall_config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if !recall_config.enabled || scope == MemoryScope::None {
             return None;
         }
 
@@ -744,9 +951,12 @@ impl Agent {
         };
 
         // Build recall query
-        let query = RecallQuery::new(embedding)
-            .with_limit(self.recall_config.limit)
-            .with_min_score(self.recall_config.threshold);
+        let mut query = RecallQuery::new(embedding)
+            .with_limit(recall_config.limit)
+            .with_min_score(recall_config.threshold);
+        if scope == MemoryScope::Session {
+            query = query.with_session(session_id.to_string());
+        }
 
         // Execute recall
         let result = match store.recall(query) {
@@ -803,11 +1013,15 @@ pub struct AgentBuilder {
     prompt_builder: Option<SystemPromptBuilder>,
     bootstrap_context: Option<crate::prompt::BootstrapContext>,
     interaction_logger: Option<Arc<InteractionLogger>>,
+    router: Option<SharedRouter>,
+    usage_meter: Option<SharedUsageMeter>,
     memory_store: Option<Arc<MemoryStore>>,
     embedder: Option<SharedEmbedder>,
-    recall_config: RecallConfig,
+    recall_config: SharedRecallConfig,
     plugin_prompts: Vec<(String, String)>,
+    live_plugin_prompts: Option<SharedPluginPrompts>,
     hook_dispatcher: Option<SharedHookDispatcher>,
+    skill_expander: Option<SharedSkillExpander>,
     fs_gate_resolver: Option<FsGateResolver>,
     secret_resolver: Option<SharedSecretResolver>,
 }
@@ -822,11 +1036,15 @@ impl AgentBuilder {
             prompt_builder: None,
             bootstrap_context: None,
             interaction_logger: None,
+            router: None,
+            usage_meter: None,
             memory_store: None,
             embedder: None,
-            recall_config: RecallConfig::default(),
+            recall_config: SharedRecallConfig::default(),
             plugin_prompts: Vec::new(),
+            live_plugin_prompts: None,
             hook_dispatcher: None,
+            skill_expander: None,
             fs_gate_resolver: None,
             secret_resolver: None,
         }
@@ -1013,6 +1231,14 @@ impl AgentBuilder {
 
     /// Set the recall configuration.
     pub fn with_recall_config(mut self, config: RecallConfig) -> Self {
+        self.recall_config = Arc::new(std::sync::RwLock::new(config));
+        self
+    }
+
+    /// Set a recall configuration that can change while the agent runs.
+    ///
+    /// Read at the start of every turn, so updates apply to the next turn.
+    pub fn with_live_recall_config(mut self, config: SharedRecallConfig) -> Self {
         self.recall_config = config;
         self
     }
@@ -1023,6 +1249,31 @@ impl AgentBuilder {
         self
     }
 
+    /// Set the LLM router.
+    ///
+    /// When set, each LLM call is sent to the profile the router selects
+    /// instead of the agent's backend and model, and the decision is
+    /// recorded in the interaction log.
+    pub fn with_router(mut self, router: SharedRouter) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// Set a routing hint for the LLM router.
+    pub fn with_routing_hint(mut self, hint: impl Into<String>) -> Self {
+        self.config.routing_hint = Some(hint.into());
+        self
+    }
+
+    /// Set the usage meter.
+    ///
+    /// When set, every LLM call is priced and recorded, and turns in a
+    /// workstream stop before the next call once its hard spend limit is hit.
+    pub fn with_usage_meter(mut self, meter: UsageMeter) -> Self {
+        self.usage_meter = Some(Arc::new(meter));
+        self
+    }
+
     /// Add plugin prompt fragments to the system prompt.
     ///
     /// Each fragment is a `(plugin_name, prompt_text)` pair that will be
@@ -1032,6 +1283,15 @@ impl AgentBuilder {
         self
     }
 
+    /// Add plugin prompt fragments that are re-read on every turn.
+    ///
+    /// Used for plugins that can be enabled, disabled or reloaded while the
+    /// agent is running.
+    pub fn with_live_plugin_prompts(mut self, prompts: SharedPluginPrompts) -> Self {
+        self.live_plugin_prompts = Some(prompts);
+        self
+    }
+
     /// Set the hook dispatcher for plugin lifecycle events.
     ///
     /// The hook dispatcher fires hooks at lifecycle events like PreToolUse,
@@ -1044,6 +1304,16 @@ impl AgentBuilder {
         self
     }
 
+    /// Set the expander for `/skill args` invocations.
+    ///
+    /// A message that invokes a known skill is replaced by the rendered
+    /// skill before the turn starts, and the skill's routing hint applies
+    /// to that turn.
+    pub fn with_skill_expander(mut self, expander: SharedSkillExpander) -> Self {
+        self.skill_expander = Some(expander);
+        self
+    }
+
     /// Build the agent.
     pub fn build(mut self) -> Result<Agent> {
         let backend = self
@@ -1055,6 +1325,7 @@ impl AgentBuilder {
         let prompt_builder = if self.prompt_builder.is_some()
             || self.bootstrap_context.is_some()
             || !self.plugin_prompts.is_empty()
+            || self.live_plugin_prompts.is_some()
         {
             let builder = self.prompt_builder.take().unwrap_or_default();
 
@@ -1079,6 +1350,10 @@ impl AgentBuilder {
             } else {
                 builder
             };
+            let builder = match self.live_plugin_prompts {
+                Some(prompts) => builder.with_live_plugin_prompts(prompts),
+                None => builder,
+            };
 
             Some(builder)
         } else {
@@ -1088,10 +1363,13 @@ impl AgentBuilder {
         let mut agent = Agent::new(backend, self.tools, self.config);
         agent.prompt_builder = prompt_builder;
         agent.interaction_logger = self.interaction_logger;
+        agent.router = self.router;
+        agent.usage_meter = self.usage_meter;
         agent.memory_store = self.memory_store;
         agent.embedder = self.embedder;
         agent.recall_config = self.recall_config;
         agent.hook_dispatcher = self.hook_dispatcher;
+        agent.skill_expander = self.skill_expander;
         agent.fs_gate_resolver = self.fs_gate_resolver;
         agent.secret_resolver = self.secret_resolver;
         Ok(agent)
@@ -1228,6 +1506,224 @@ mod tests {
         assert_eq!(response.iterations, 2);
     }
 
+    #[tokio::test]
+    async fn test_turn_with_prompt_based_tool_calls() {
+        use arawn_llm::{PromptToolBackend, ToolPromptFormat};
+
+        let mock = Arc::new(MockBackend::new(vec![
+            mock_text_response(
+                "<tool_call>\n{\"name\": \"test_tool\", \"arguments\": {\"arg\": \"value\"}}\n</tool_call>",
+            ),
+            mock_text_response("Done! I used the tool."),
+        ]));
+        let backend = PromptToolBackend::new(mock.clone(), ToolPromptFormat::Hermes);
+
+        let mut tools = ToolRegistry::new();
+        tools.register(MockTool::new("test_tool").with_response(ToolResult::text("tool output")));
+
+        let agent = Agent::builder()
+            .with_backend(backend)
+            .with_tools(tools)
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        let response = agent
+            .turn(&mut session, "Use the tool", None)
+            .await
+            .unwrap();
+
+        assert_eq!(response.text, "Done! I used the tool.");
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].arguments["arg"], "value");
+        assert!(response.tool_results[0].success);
+
+        // The model never saw native tools; the result went back as text
+        let requests = mock.requests();
+        assert!(requests.iter().all(|r| r.tools.is_empty()));
+        let followup = requests[1].messages.last().unwrap().content.to_text();
+        assert!(followup.contains("<tool_response>"));
+        assert!(followup.contains("tool output"));
+    }
+
+    #[tokio::test]
+    async fn test_turn_routes_iterations_and_logs_decision() {
+        use arawn_llm::interaction_log::InteractionLogConfig;
+        use arawn_llm::{LlmRouter, RouteCondition, RouteProfile, RoutingRule};
+
+        // Tool-calling first iteration goes to "quality", follow-ups to "fast"
+        let quality = Arc::new(MockBackend::new(vec![mock_tool_use_response(
+            "call_1",
+            "test_tool",
+            serde_json::json!({}),
+        )]));
+        let fast = Arc::new(MockBackend::with_text("Done."));
+        let router = LlmRouter::new(RouteProfile::new("quality", quality.clone(), "big-model"))
+            .with_profile(RouteProfile::new("fast", fast.clone(), "small-model"))
+            .with_rule(RoutingRule::new(
+                "follow-up",
+                RouteCondition::default().min_iteration(2),
+                "fast",
+            ));
+
+        let mut tools = ToolRegistry::new();
+        tools.register(MockTool::new("test_tool"));
+
+        let log_dir = tempfile::tempdir().unwrap();
+        let logger = InteractionLogger::new(InteractionLogConfig {
+            enabled: true,
+            path: Some(log_dir.path().to_path_buf()),
+            retention_days: 90,
+        })
+        .unwrap();
+
+        let agent = Agent::builder()
+            .with_backend(MockBackend::new(vec![]))
+            .with_tools(tools)
+            .with_router(Arc::new(router))
+            .with_interaction_logger(Arc::new(logger))
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        let response = agent
+            .turn(&mut session, "Use the tool", None)
+            .await
+            .unwrap();
+        assert_eq!(response.text, "Done.");
+        assert_eq!(quality.requests()[0].model, "big-model");
+        assert_eq!(fast.requests()[0].model, "small-model");
+
+        let log_file = std::fs::read_dir(log_dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let records: Vec<InteractionRecord> = std::fs::read_to_string(log_file)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        let routing: Vec<_> = records
+            .iter()
+            .map(|r| r.routing.as_ref().unwrap())
+            .collect();
+        assert_eq!(routing[0].profile, "quality");
+        assert_eq!(routing[0].reason, "default profile");
+        assert_eq!(routing[1].profile, "fast");
+        assert!(routing[1].reason.contains("follow-up"));
+    }
+
+    #[tokio::test]
+    async fn test_session_routing_hint_selects_profile() {
+        use arawn_llm::{LlmRouter, RouteProfile};
+
+        let quality = Arc::new(MockBackend::with_text("slow"));
+        let fast = Arc::new(MockBackend::with_text("quick"));
+        let router = LlmRouter::new(RouteProfile::new("quality", quality, "big-model"))
+            .with_profile(RouteProfile::new("fast", fast.clone(), "small-model"));
+
+        let agent = Agent::builder()
+            .with_backend(MockBackend::new(vec![]))
+            .with_router(Arc::new(router))
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        session.set_metadata(
+            crate::types::ROUTING_HINT_METADATA_KEY,
+            serde_json::json!("fast"),
+        );
+        let response = agent.turn(&mut session, "Hi", None).await.unwrap();
+        assert_eq!(response.text, "quick");
+        assert_eq!(fast.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skill_invocation_expands_message_and_routes() {
+        use arawn_llm::{LlmRouter, RouteProfile};
+        use arawn_types::SkillExpander;
+
+        struct Review;
+        impl SkillExpander for Review {
+            fn expand(&self, message: &str) -> Option<ExpandedSkill> {
+                let pr = message.strip_prefix("/review ")?;
+                Some(ExpandedSkill {
+                    name: "git:review".into(),
+                    prompt: format!("Review PR {pr} carefully."),
+                    routing_hint: Some("fast".into()),
+                })
+            }
+        }
+
+        let quality = Arc::new(MockBackend::with_text("slow"));
+        let fast = Arc::new(MockBackend::new(vec![
+            mock_text_response("Reviewed."),
+            mock_text_response("Hello."),
+        ]));
+        let router = LlmRouter::new(RouteProfile::new("quality", quality.clone(), "big-model"))
+            .with_profile(RouteProfile::new("fast", fast.clone(), "small-model"));
+        let agent = Agent::builder()
+            .with_backend(MockBackend::new(vec![]))
+            .with_router(Arc::new(router))
+            .with_skill_expander(Arc::new(Review))
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        let response = agent.turn(&mut session, "/review 42", None).await.unwrap();
+        assert_eq!(response.text, "Reviewed.");
+        assert_eq!(
+            session.current_turn().unwrap().user_message,
+            "Review PR 42 carefully."
+        );
+
+        // Ordinary messages are left alone and use the default route
+        agent.turn(&mut session, "Hi", None).await.unwrap();
+        assert_eq!(session.current_turn().unwrap().user_message, "Hi");
+        assert_eq!(fast.request_count(), 1);
+        assert_eq!(quality.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_agent_settings_shape_request() {
+        let backend = Arc::new(MockBackend::new(vec![
+            mock_tool_use_response("call_1", "shell", serde_json::json!({})),
+            mock_text_response("Done."),
+        ]));
+
+        let mut tools = ToolRegistry::new();
+        tools.register(MockTool::new("shell"));
+        tools.register(MockTool::new("file_read"));
+
+        let agent = Agent::builder()
+            .with_shared_backend(backend.clone())
+            .with_tools(tools)
+            .with_system_prompt("You are helpful.")
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        session.set_agent_settings(&AgentSettings {
+            model: Some("ws-model".into()),
+            system_prompt: Some("Answer in French.".into()),
+            denied_tools: vec!["shell".into()],
+            ..Default::default()
+        });
+        let response = agent.turn(&mut session, "Hi", None).await.unwrap();
+
+        // The denied tool is neither offered nor executed
+        assert!(!response.tool_results[0].success);
+        let requests = backend.requests();
+        assert_eq!(requests[0].model, "ws-model");
+        let names: Vec<_> = requests[0].tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["file_read"]);
+        let system = requests[0].system.as_ref().unwrap().to_text();
+        assert!(system.starts_with("You are helpful."));
+        assert!(system.contains("# Workstream Instructions\n\nAnswer in French."));
+    }
+
     #[tokio::test]
     async fn test_turn_max_iterations() {
         // Keep returning tool calls to hit max iterations
@@ -1259,6 +1755,36 @@ mod tests {
         assert_eq!(response.iterations, 6); // 5 + 1 that exceeded
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_turn_records_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let responses: Vec<CompletionResponse> = (0..3)
+            .map(|i| {
+                mock_tool_use_response(&format!("call_{}", i), "test_tool", serde_json::json!({}))
+            })
+            .collect();
+        let mut tools = ToolRegistry::new();
+        tools.register(MockTool::new("test_tool"));
+        let agent = Agent::builder()
+            .with_backend(MockBackend::new(responses))
+            .with_tools(tools)
+            .with_max_iterations(2)
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        agent.turn(&mut session, "Use tools", None).await.unwrap();
+
+        let output = handle.render();
+        assert!(output.contains(r#"arawn_agent_turns_total{mode="sync",outcome="ok"} 1"#));
+        assert!(output.contains(r#"arawn_agent_turns_truncated_total{mode="sync"} 1"#));
+        assert!(output.contains(r#"arawn_agent_turn_iterations_sum{mode="sync"} 3"#));
+        assert!(output.contains(r#"arawn_tool_executions_total{tool="test_tool",outcome="ok"} 2"#));
+    }
+
     #[tokio::test]
     async fn test_turn_token_budget_exceeded() {
         // Each mock response uses Usage::new(10, 20) = 30 tokens per iteration.
@@ -1290,6 +1816,108 @@ mod tests {
         assert!(response.usage.total() > 50);
     }
 
+    /// Recorder that sums recorded cost and enforces a fixed hard limit.
+    struct LimitedRecorder {
+        hard_limit_usd: f64,
+        entries: std::sync::Mutex<Vec<arawn_types::UsageEntry>>,
+    }
+
+    impl arawn_types::UsageRecorder for LimitedRecorder {
+        fn record(
+            &self,
+            entry: &arawn_types::UsageEntry,
+        ) -> std::result::Result<(), arawn_types::UsageError> {
+            self.entries.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+
+        fn budget_status(&self, _workstream_id: &str) -> BudgetStatus {
+            let spent_usd: f64 = self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|e| e.cost_usd)
+                .sum();
+            if spent_usd >= self.hard_limit_usd {
+                BudgetStatus::HardLimitExceeded {
+                    spent_usd,
+                    limit_usd: self.hard_limit_usd,
+                }
+            } else {
+                BudgetStatus::WithinLimit
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_turn_records_usage_and_stops_at_spend_limit() {
+        use arawn_llm::{ModelPricing, PricingTable};
+
+        let responses: Vec<CompletionResponse> = (0..10)
+            .map(|i| {
+                mock_tool_use_response(&format!("call_{}", i), "test_tool", serde_json::json!({}))
+            })
+            .collect();
+        let mut tools = ToolRegistry::new();
+        tools.register(MockTool::new("test_tool"));
+
+        // Each call costs $1 (10 input tokens at $0.1M/Mtok); limit is hit after two calls
+        let recorder = Arc::new(LimitedRecorder {
+            hard_limit_usd: 2.0,
+            entries: std::sync::Mutex::new(Vec::new()),
+        });
+        let pricing = PricingTable::new().with_model("mock", ModelPricing::new(100_000.0, 0.0));
+        let agent = Agent::builder()
+            .with_backend(MockBackend::new(responses))
+            .with_tools(tools)
+            .with_model("mock")
+            .with_usage_meter(
+                UsageMeter::new(recorder.clone())
+                    .with_pricing(pricing)
+                    .with_profile("main"),
+            )
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        let response = agent
+            .turn(&mut session, "Use tools", Some("ws-1"))
+            .await
+            .unwrap();
+
+        assert!(response.truncated);
+        assert_eq!(response.iterations, 3);
+        assert!(response.text.contains("Spend limit reached"));
+        assert!(response.text.contains("ws-1"));
+
+        let entries = recorder.entries.lock().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].profile, "main");
+        assert_eq!(entries[0].workstream_id.as_deref(), Some("ws-1"));
+        assert_eq!(entries[0].session_id, session.id.to_string());
+        assert!((entries[0].cost_usd - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_turn_without_workstream_ignores_spend_limit() {
+        let recorder = Arc::new(LimitedRecorder {
+            hard_limit_usd: 0.0,
+            entries: std::sync::Mutex::new(Vec::new()),
+        });
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Hi"))
+            .with_usage_meter(UsageMeter::new(recorder.clone()))
+            .build()
+            .unwrap();
+
+        let mut session = Session::new();
+        let response = agent.turn(&mut session, "Hello", None).await.unwrap();
+
+        assert!(!response.truncated);
+        assert_eq!(recorder.entries.lock().unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_turn_no_token_budget() {
         // Without a budget, token usage is not limited (only iterations)
@@ -2066,6 +2694,51 @@ mod tests {
             assert_eq!(response.text, "No memories found.");
         }
 
+        #[tokio::test]
+        #[serial]
+        async fn test_live_recall_config_applies_next_turn() {
+            let store = create_recall_store(4);
+            let mem = Memory::new(ContentType::Note, "The deploy key lives in the vault");
+            store
+                .insert_memory_with_embedding(&mem, &[0.5, 0.5, 0.5, 0.5])
+                .unwrap();
+
+            let recall = super::super::SharedRecallConfig::default();
+            recall.write().unwrap().enabled = false;
+
+            let backend = Arc::new(MockBackend::new(vec![
+                super::mock_text_response("first"),
+                super::mock_text_response("second"),
+            ]));
+            let agent = Agent::builder()
+                .with_shared_backend(backend.clone())
+                .with_memory_store(store)
+                .with_embedder(Arc::new(FixedEmbedder::new(4)))
+                .with_live_recall_config(recall.clone())
+                .build()
+                .unwrap();
+
+            let recalled = |request: &arawn_llm::CompletionRequest| {
+                request
+                    .messages
+                    .iter()
+                    .any(|m| m.content.to_text().contains("Relevant memories recalled"))
+            };
+
+            let mut session = Session::new();
+            agent.turn(&mut session, "Where is the key?", None).await.unwrap();
+            assert!(!recalled(&backend.requests()[0]));
+
+            *recall.write().unwrap() = super::super::RecallConfig {
+                enabled: true,
+                threshold: 0.0,
+                limit: 5,
+            };
+            let mut session = Session::new();
+            agent.turn(&mut session, "Where is the key?", None).await.unwrap();
+            assert!(recalled(&backend.requests()[1]));
+        }
+
         #[tokio::test]
         async fn test_recall_disabled_config() {
             let backend = MockBackend::with_text("Recall disabled.");
@@ -1,5 +1,7 @@
 //! LLM extraction prompt and JSON parser.
 
+use arawn_llm::ResponseSchema;
+use serde_json::json;
 use tracing::warn;
 
 use super::types::ExtractionResult;
@@ -146,6 +148,60 @@ Rules:
 - Omit trivially obvious or ephemeral information
 - Keep `entities` and `relationships` as empty arrays"#;
 
+/// JSON Schema for [`ExtractionResult`], used to request structured output.
+///
+/// Shared by the full and facts-only prompts; the facts-only prompt asks for
+/// empty `entities` and `relationships` arrays.
+pub fn extraction_schema() -> ResponseSchema {
+    ResponseSchema::new(
+        "extraction",
+        json!({
+            "type": "object",
+            "properties": {
+                "entities": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "entity_type": { "type": "string" },
+                            "context": { "type": ["string", "null"] }
+                        },
+                        "required": ["name", "entity_type"]
+                    }
+                },
+                "facts": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "subject": { "type": "string" },
+                            "predicate": { "type": "string" },
+                            "object": { "type": "string" },
+                            "confidence": { "enum": ["stated", "observed", "inferred"] }
+                        },
+                        "required": ["subject", "predicate", "object"]
+                    }
+                },
+                "relationships": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "from": { "type": "string" },
+                            "relation": { "type": "string" },
+                            "to": { "type": "string" }
+                        },
+                        "required": ["from", "relation", "to"]
+                    }
+                }
+            },
+            "required": ["entities", "facts", "relationships"]
+        }),
+    )
+    .with_description("Entities, facts and relationships extracted from a conversation")
+}
+
 /// Parse LLM output into an ExtractionResult.
 ///
 /// Handles common failure modes:
@@ -218,6 +274,14 @@ mod tests {
         assert!(prompt.contains("Example:"));
     }
 
+    #[test]
+    fn test_extraction_schema_accepts_few_shot_example() {
+        let example = FEW_SHOT_EXAMPLE.split("```json").nth(1).unwrap();
+        let example = example.split("```").next().unwrap();
+        let value: serde_json::Value = serde_json::from_str(example).unwrap();
+        assert!(arawn_llm::structured::validate(&extraction_schema().schema, &value).is_ok());
+    }
+
     #[test]
     fn test_parse_valid_json() {
         let json = r#"{
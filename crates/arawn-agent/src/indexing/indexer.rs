@@ -14,15 +14,15 @@ use tracing::{info, warn};
 
 use arawn_llm::backend::SharedBackend;
 use arawn_llm::embeddings::SharedEmbedder;
-use std::collections::HashMap;
 
-use arawn_llm::types::{CompletionRequest, Message};
+use arawn_llm::types::{CompletionRequest, Message, ResponseSchema};
+use arawn_llm::{DEFAULT_MAX_REPAIRS, StructuredCompletion};
 use arawn_memory::{
     Citation, ConfidenceSource, ContentType, GraphNode, GraphRelationship, Memory,
     MemoryConfidence, MemoryStore, Metadata, RelationshipType, StoreFactResult, StoreOptions,
 };
 
-use super::extraction::{ExtractionPrompt, FactsOnlyPrompt, parse_extraction};
+use super::extraction::{ExtractionPrompt, FactsOnlyPrompt, extraction_schema, parse_extraction};
 use super::ner::{ENTITY_LABELS, NerEngine, RELATION_LABELS, ner_output_to_extracted};
 use super::report::IndexReport;
 use super::summarization::{SummarizationPrompt, clean_summary};
@@ -53,6 +53,19 @@ impl Default for IndexerConfig {
 #[async_trait]
 pub trait Completer: Send + Sync {
     async fn complete(&self, model: &str, prompt: &str, max_tokens: u32) -> Result<String, String>;
+
+    /// Complete a prompt whose response must conform to `schema`.
+    ///
+    /// Default: a plain completion; callers still parse the output leniently.
+    async fn complete_json(
+        &self,
+        model: &str,
+        prompt: &str,
+        max_tokens: u32,
+        _schema: &ResponseSchema,
+    ) -> Result<String, String> {
+        self.complete(model, prompt, max_tokens).await
+    }
 }
 
 /// Production completer that uses the real LLM backend.
@@ -69,20 +82,8 @@ impl BackendCompleter {
 #[async_trait]
 impl Completer for BackendCompleter {
     async fn complete(&self, model: &str, prompt: &str, max_tokens: u32) -> Result<String, String> {
-        let request = CompletionRequest {
-            model: model.to_string(),
-            messages: vec![Message::user(prompt)],
-            max_tokens,
-            system: None,
-            tools: vec![],
-            tool_choice: None,
-            stream: false,
-            temperature: Some(0.3),
-            top_p: None,
-            top_k: None,
-            stop_sequences: vec![],
-            metadata: HashMap::new(),
-        };
+        let request = CompletionRequest::new(model, vec![Message::user(prompt)], max_tokens)
+            .with_temperature(0.3);
         let response = self
             .backend
             .complete(request)
@@ -90,6 +91,24 @@ impl Completer for BackendCompleter {
             .map_err(|e| e.to_string())?;
         Ok(response.text())
     }
+
+    async fn complete_json(
+        &self,
+        model: &str,
+        prompt: &str,
+        max_tokens: u32,
+        schema: &ResponseSchema,
+    ) -> Result<String, String> {
+        let request = CompletionRequest::new(model, vec![Message::user(prompt)], max_tokens)
+            .with_temperature(0.3)
+            .with_response_schema(schema.clone());
+        let structured = self
+            .backend
+            .complete_structured(request, DEFAULT_MAX_REPAIRS)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(structured.value.to_string())
+    }
 }
 
 /// Orchestrates post-session indexing: extraction, graph storage, and summarization.
@@ -207,10 +226,11 @@ impl SessionIndexer {
         let prompt = ExtractionPrompt::build(messages);
         let raw = self
             .completer
-            .complete(
+            .complete_json(
                 &self.config.model,
                 &prompt,
                 self.config.max_extraction_tokens,
+                &extraction_schema(),
             )
             .await?;
         Ok(parse_extraction(&raw))
@@ -261,10 +281,11 @@ impl SessionIndexer {
 
         let facts = match self
             .completer
-            .complete(
+            .complete_json(
                 &self.config.model,
                 &facts_prompt,
                 self.config.max_extraction_tokens,
+                &extraction_schema(),
             )
             .await
         {
@@ -972,4 +993,39 @@ mod tests {
             RelationshipType::RelatedTo
         ));
     }
+
+    #[tokio::test]
+    async fn test_backend_completer_repairs_extraction_output() {
+        use arawn_llm::{CompletionResponse, ContentBlock, MockBackend, StopReason, Usage};
+
+        let text = |t: &str| {
+            CompletionResponse::new(
+                "msg",
+                "mock",
+                vec![ContentBlock::Text {
+                    text: t.to_string(),
+                    cache_control: None,
+                }],
+                StopReason::EndTurn,
+                Usage::new(1, 1),
+            )
+        };
+        // First output omits required arrays; the repair prompt fixes it.
+        let backend = Arc::new(MockBackend::new(vec![
+            text(r#"{"facts": []}"#),
+            text(
+                r#"{"entities": [{"name": "Rust", "entity_type": "language"}], "facts": [], "relationships": []}"#,
+            ),
+        ]));
+        let completer = BackendCompleter::new(backend.clone());
+
+        let raw = completer
+            .complete_json("m", "extract", 256, &extraction_schema())
+            .await
+            .unwrap();
+        let result = parse_extraction(&raw);
+        assert_eq!(result.entities.len(), 1);
+        assert_eq!(result.entities[0].name, "Rust");
+        assert_eq!(backend.request_count(), 2);
+    }
 }
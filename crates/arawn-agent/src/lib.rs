@@ -34,6 +34,7 @@ pub mod context;
 pub mod error;
 pub mod indexing;
 pub mod mcp;
+pub mod metrics;
 pub mod orchestrator;
 pub mod prompt;
 pub mod rlm;
@@ -41,21 +42,25 @@ pub mod stream;
 pub mod tool;
 pub mod tools;
 pub mod types;
+pub mod usage;
 
 // Re-export filesystem gate (defined in arawn-types)
 pub use arawn_types::{
     FsGate, FsGateError, FsGateResolver, GATED_TOOLS, SandboxOutput, SharedFsGate, is_gated_tool,
 };
 
+// Re-export per-workstream agent settings (defined in arawn-types)
+pub use arawn_types::{AgentSettings, MemoryScope};
+
 // Re-export core types
 pub use error::{AgentError, Result};
 pub use types::{
-    AgentConfig, AgentResponse, ResponseUsage, Session, SessionId, ToolCall, ToolResultRecord,
-    Turn, TurnId,
+    AGENT_SETTINGS_METADATA_KEY, AgentConfig, AgentResponse, ResponseUsage, Session, SessionId,
+    ToolCall, ToolResultRecord, Turn, TurnId,
 };
 
 // Re-export tool types
-pub use tool::{Tool, ToolContext, ToolRegistry, ToolResult};
+pub use tool::{SharedOutputOverrides, Tool, ToolContext, ToolRegistry, ToolResult};
 
 // Re-export parameter validation types
 pub use tool::{
@@ -73,7 +78,7 @@ pub use tool::{
 pub use tool::{CommandValidation, CommandValidator};
 
 // Re-export agent
-pub use agent::{Agent, AgentBuilder, RecallConfig};
+pub use agent::{Agent, AgentBuilder, RecallConfig, SharedRecallConfig};
 
 // Re-export compaction types
 pub use compaction::{
@@ -90,11 +95,16 @@ pub use orchestrator::{
 pub use context::{ContextBuilder, ContextStatus, ContextTracker};
 
 // Re-export prompt builder
-pub use prompt::{BootstrapContext, BootstrapFile, PromptMode, SystemPromptBuilder};
+pub use prompt::{
+    BootstrapContext, BootstrapFile, PromptMode, SharedPluginPrompts, SystemPromptBuilder,
+};
 
 // Re-export streaming types
 pub use stream::{AgentStream, StreamChunk, create_turn_stream};
 
+// Re-export usage accounting
+pub use usage::{SharedUsageMeter, UsageMeter, spend_limit_message};
+
 // Re-export indexing types
 #[cfg(feature = "gliner")]
 pub use indexing::GlinerEngine;
@@ -116,6 +126,8 @@ pub use mcp::{
 
 // Re-export built-in tools
 pub use tools::{
+    // Conversation search tool
+    ConversationSearchTool,
     // Explore tool
     ExploreTool,
     // File tools
@@ -259,9 +259,12 @@ fn convert_mcp_result(mcp_result: CallToolResult) -> ToolResult {
 /// );
 /// ```
 pub fn parse_namespaced_name(name: &str) -> Option<(&str, &str, &str)> {
-    let parts: Vec<&str> = name.split(NAMESPACE_DELIMITER).collect();
-    if parts.len() == 3 && parts[0] == MCP_PREFIX {
-        Some((parts[0], parts[1], parts[2]))
+    // Server names may themselves be namespaced (plugin servers are
+    // `plugin:server`), so the tool name is everything after the last delimiter.
+    let (prefix, rest) = name.split_once(NAMESPACE_DELIMITER)?;
+    let (server, tool) = rest.rsplit_once(NAMESPACE_DELIMITER)?;
+    if prefix == MCP_PREFIX && !server.is_empty() && !tool.is_empty() {
+        Some((prefix, server, tool))
     } else {
         None
     }
@@ -310,6 +313,12 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_parse_namespaced_name_plugin_server() {
+        let result = parse_namespaced_name("mcp:journal:db:query");
+        assert_eq!(result, Some(("mcp", "journal:db", "query")));
+    }
+
     #[test]
     fn test_parse_namespaced_name_no_delimiter() {
         let result = parse_namespaced_name("mcp_sqlite_query");
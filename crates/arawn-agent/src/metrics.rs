@@ -0,0 +1,85 @@
+//! Metrics for agent turns and tool executions.
+//!
+//! Recorded through the [`metrics`] facade, so nothing is collected until a
+//! recorder is installed. Turns run inside an `agent_turn` tracing span and
+//! tool executions inside a `tool` span, so exported traces nest LLM calls
+//! and tool runs under the turn that made them.
+
+use std::time::Instant;
+
+/// Completed agent turns, by `mode` (`sync`/`stream`) and `outcome`.
+pub const AGENT_TURNS_TOTAL: &str = "arawn_agent_turns_total";
+/// Agent turn latency, by `mode`.
+pub const AGENT_TURN_DURATION_SECONDS: &str = "arawn_agent_turn_duration_seconds";
+/// Tool-loop iterations per turn, by `mode`.
+pub const AGENT_TURN_ITERATIONS: &str = "arawn_agent_turn_iterations";
+/// Turns cut short by the iteration or token limit, by `mode`.
+pub const AGENT_TURNS_TRUNCATED_TOTAL: &str = "arawn_agent_turns_truncated_total";
+/// Tool executions, by `tool` and `outcome` (`ok`, `error` for an error
+/// result, `failed` when the tool itself failed).
+pub const TOOL_EXECUTIONS_TOTAL: &str = "arawn_tool_executions_total";
+/// Tool execution latency, by `tool`.
+pub const TOOL_DURATION_SECONDS: &str = "arawn_tool_duration_seconds";
+
+/// Records a turn's metrics when dropped, so every exit path is counted.
+///
+/// A turn counts as failed unless [`TurnMeter::finish`] is called before the drop.
+pub(crate) struct TurnMeter {
+    mode: &'static str,
+    started: Instant,
+    iterations: u32,
+    truncated: bool,
+    succeeded: bool,
+}
+
+impl TurnMeter {
+    pub(crate) fn start(mode: &'static str) -> Self {
+        Self {
+            mode,
+            started: Instant::now(),
+            iterations: 0,
+            truncated: false,
+            succeeded: false,
+        }
+    }
+
+    /// Mark the turn as finished successfully.
+    pub(crate) fn finish(&mut self, iterations: u32, truncated: bool) {
+        self.iterations = iterations;
+        self.truncated = truncated;
+        self.succeeded = true;
+    }
+}
+
+impl Drop for TurnMeter {
+    fn drop(&mut self) {
+        let mode = self.mode;
+        metrics::counter!(
+            AGENT_TURNS_TOTAL,
+            "mode" => mode,
+            "outcome" => if self.succeeded { "ok" } else { "error" },
+        )
+        .increment(1);
+        metrics::histogram!(AGENT_TURN_DURATION_SECONDS, "mode" => mode)
+            .record(self.started.elapsed().as_secs_f64());
+        if self.succeeded {
+            metrics::histogram!(AGENT_TURN_ITERATIONS, "mode" => mode)
+                .record(f64::from(self.iterations));
+        }
+        if self.truncated {
+            metrics::counter!(AGENT_TURNS_TRUNCATED_TOTAL, "mode" => mode).increment(1);
+        }
+    }
+}
+
+/// Record a finished tool execution.
+pub(crate) fn record_tool(tool: &str, outcome: &'static str, started: Instant) {
+    metrics::counter!(
+        TOOL_EXECUTIONS_TOTAL,
+        "tool" => tool.to_string(),
+        "outcome" => outcome,
+    )
+    .increment(1);
+    metrics::histogram!(TOOL_DURATION_SECONDS, "tool" => tool.to_string())
+        .record(started.elapsed().as_secs_f64());
+}
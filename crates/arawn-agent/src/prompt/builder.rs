@@ -3,6 +3,7 @@
 //! Provides a fluent builder for assembling system prompts from modular sections.
 
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 use chrono::{Local, Utc};
 
@@ -11,6 +12,11 @@ use crate::tool::ToolRegistry;
 use super::bootstrap::BootstrapContext;
 use super::mode::PromptMode;
 
+/// Plugin prompt fragments shared with whatever keeps them current.
+///
+/// Each entry is a `(plugin_name, prompt_text)` pair.
+pub type SharedPluginPrompts = Arc<RwLock<Vec<(String, String)>>>;
+
 /// A tool summary for prompt generation.
 #[derive(Debug, Clone)]
 pub struct ToolSummary {
@@ -46,6 +52,7 @@ pub struct SystemPromptBuilder {
     think_enabled: bool,
     bootstrap_context: Option<BootstrapContext>,
     plugin_prompts: Vec<(String, String)>,
+    live_plugin_prompts: Option<SharedPluginPrompts>,
 }
 
 impl Default for SystemPromptBuilder {
@@ -68,6 +75,7 @@ impl SystemPromptBuilder {
             think_enabled: false,
             bootstrap_context: None,
             plugin_prompts: Vec::new(),
+            live_plugin_prompts: None,
         }
     }
 
@@ -164,6 +172,15 @@ impl SystemPromptBuilder {
         self
     }
 
+    /// Add plugin prompt fragments that can change between builds.
+    ///
+    /// Read on every [`build`](Self::build), after the fixed fragments, so
+    /// plugins enabled or reloaded at runtime show up on the next turn.
+    pub fn with_live_plugin_prompts(mut self, fragments: SharedPluginPrompts) -> Self {
+        self.live_plugin_prompts = Some(fragments);
+        self
+    }
+
     /// Build the final system prompt string.
     ///
     /// Sections are assembled based on the configured mode and
@@ -220,7 +237,12 @@ impl SystemPromptBuilder {
         }
 
         // 9. Plugins — plugin prompt fragments
-        for (plugin_name, text) in &self.plugin_prompts {
+        let live = self
+            .live_plugin_prompts
+            .as_ref()
+            .map(|fragments| fragments.read().unwrap_or_else(|e| e.into_inner()).clone())
+            .unwrap_or_default();
+        for (plugin_name, text) in self.plugin_prompts.iter().chain(&live) {
             if !text.is_empty() {
                 sections.push(format!("## Plugin: {}\n\n{}", plugin_name, text));
             }
@@ -576,6 +598,23 @@ mod tests {
         assert!(prompt.contains("## Plugin: notempty"));
     }
 
+    #[test]
+    fn test_live_plugin_prompts_read_per_build() {
+        let live = SharedPluginPrompts::default();
+        let builder = SystemPromptBuilder::new()
+            .with_plugin_prompts(vec![("fixed".to_string(), "Always here.".to_string())])
+            .with_live_plugin_prompts(live.clone());
+
+        assert!(!builder.build().contains("Plugin: journal"));
+
+        live.write()
+            .unwrap()
+            .push(("journal".to_string(), "Journal skills.".to_string()));
+        let prompt = builder.build();
+        assert!(prompt.contains("## Plugin: fixed"));
+        assert!(prompt.contains("## Plugin: journal\n\nJournal skills."));
+    }
+
     #[test]
     fn test_plugin_prompts_none() {
         let prompt = SystemPromptBuilder::new()
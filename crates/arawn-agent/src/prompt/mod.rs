@@ -23,5 +23,5 @@ mod builder;
 mod mode;
 
 pub use bootstrap::{BootstrapContext, BootstrapFile};
-pub use builder::SystemPromptBuilder;
+pub use builder::{SharedPluginPrompts, SystemPromptBuilder};
 pub use mode::PromptMode;
@@ -8,15 +8,19 @@ use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Span, field};
 
 use arawn_llm::{
-    CompletionRequest, ContentDelta, Message, SharedBackend, StreamEvent, ToolResultBlock,
+    CompletionRequest, ContentDelta, Message, RoutingContext, SharedBackend, SharedRouter,
+    StreamEvent, ToolResultBlock,
 };
 
 use arawn_types::{SharedFsGate, SharedSecretResolver};
 
+use crate::metrics::TurnMeter;
 use crate::tool::{ToolContext, ToolRegistry, ToolResult};
 use crate::types::{AgentConfig, SessionId, ToolCall, ToolResultRecord, TurnId};
+use crate::usage::{SharedUsageMeter, spend_limit_message};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Stream Chunk
@@ -139,17 +143,32 @@ pub type AgentStream = Pin<Box<dyn Stream<Item = StreamChunk> + Send + 'static>>
 /// State for streaming agent responses.
 struct StreamState {
     backend: SharedBackend,
+    router: Option<SharedRouter>,
+    usage_meter: Option<SharedUsageMeter>,
     tools: Arc<ToolRegistry>,
     config: AgentConfig,
     messages: Vec<Message>,
     session_id: SessionId,
     turn_id: TurnId,
+    workstream_id: Option<String>,
     cancellation: CancellationToken,
     iterations: u32,
     tool_calls: Vec<ToolCall>,
     tool_results: Vec<ToolResultRecord>,
     fs_gate: Option<SharedFsGate>,
     secret_resolver: Option<SharedSecretResolver>,
+    /// `agent_turn` span that LLM calls and tool runs are nested under.
+    span: Span,
+    meter: TurnMeter,
+}
+
+impl StreamState {
+    /// Record a successful end of the turn.
+    fn finish(&mut self, truncated: bool) {
+        self.span.record("iterations", self.iterations);
+        self.span.record("truncated", truncated);
+        self.meter.finish(self.iterations, truncated);
+    }
 }
 
 /// Create a streaming response for an agent turn.
@@ -159,28 +178,43 @@ struct StreamState {
 #[allow(clippy::too_many_arguments)]
 pub fn create_turn_stream(
     backend: SharedBackend,
+    router: Option<SharedRouter>,
+    usage_meter: Option<SharedUsageMeter>,
     tools: Arc<ToolRegistry>,
     config: AgentConfig,
     messages: Vec<Message>,
     session_id: SessionId,
     turn_id: TurnId,
+    workstream_id: Option<String>,
     cancellation: CancellationToken,
     fs_gate: Option<SharedFsGate>,
     secret_resolver: Option<SharedSecretResolver>,
 ) -> AgentStream {
     let state = StreamState {
         backend,
+        router,
+        usage_meter,
         tools,
         config,
         messages,
         session_id,
         turn_id,
+        workstream_id,
         cancellation,
         iterations: 0,
         tool_calls: Vec::new(),
         tool_results: Vec::new(),
         fs_gate,
         secret_resolver,
+        span: tracing::info_span!(
+            "agent_turn",
+            %session_id,
+            %turn_id,
+            mode = "stream",
+            iterations = field::Empty,
+            truncated = field::Empty,
+        ),
+        meter: TurnMeter::start("stream"),
     };
 
     Box::pin(async_stream::stream! {
@@ -200,16 +234,52 @@ pub fn create_turn_stream(
                     max = state.config.max_iterations,
                     "Max iterations reached — truncating streaming turn"
                 );
+                state.finish(true);
                 yield StreamChunk::error("Max iterations exceeded");
                 yield StreamChunk::done(state.iterations);
                 return;
             }
 
+            // Stop before the next call once the workstream's hard spend limit is hit
+            if let (Some(meter), Some(ws_id)) = (&state.usage_meter, state.workstream_id.as_deref()) {
+                let status = meter.budget_status(Some(ws_id));
+                if status.is_exhausted() {
+                    tracing::warn!(workstream_id = ws_id, %status, "Spend limit reached — stopping streaming turn");
+                    let message = spend_limit_message(ws_id, &status);
+                    state.finish(true);
+                    yield StreamChunk::error(message);
+                    yield StreamChunk::done(state.iterations);
+                    return;
+                }
+            }
+
             // Build request
-            let request = build_stream_request(&state);
+            let mut request = build_stream_request(&state);
+
+            // Pick a profile for this iteration when a router is configured
+            let mut backend = state.backend.clone();
+            let mut routed_model = None;
+            let mut routed_profile = None;
+            if let Some(ref router) = state.router {
+                let ctx = RoutingContext::from_request(&request)
+                    .with_iteration(state.iterations)
+                    .with_hint(state.config.routing_hint.clone());
+                let decision = router.route(&request, &ctx).await;
+                tracing::debug!(
+                    iteration = state.iterations,
+                    profile = %decision.profile,
+                    reason = %decision.reason,
+                    "LLM route selected"
+                );
+                backend = decision.backend.clone();
+                routed_model = Some(decision.model.clone());
+                routed_profile = Some(decision.profile.clone());
+                request = decision.apply(request);
+            }
+            let stream_model = request.model.clone();
 
             // Start streaming from LLM
-            let stream_result = state.backend.complete_stream(request).await;
+            let stream_result = backend.complete_stream(request).instrument(state.span.clone()).await;
 
             let mut llm_stream = match stream_result {
                 Ok(s) => s,
@@ -263,8 +333,17 @@ pub fn create_turn_stream(
                             // This is simplified - real impl would track tool_use starts
                         }
                     }
-                    StreamEvent::MessageDelta { stop_reason: _, .. } => {
-                        // Message is finishing
+                    StreamEvent::MessageDelta { usage, .. } => {
+                        // Message is finishing with final usage stats
+                        if let Some(ref meter) = state.usage_meter {
+                            meter.record(
+                                state.session_id,
+                                state.workstream_id.as_deref(),
+                                routed_profile.as_deref(),
+                                &stream_model,
+                                &usage,
+                            );
+                        }
                     }
                     StreamEvent::MessageStop => {
                         break;
@@ -278,14 +357,27 @@ pub fn create_turn_stream(
             }
 
             // Get the full response to check for tool calls
-            let request = build_sync_request(&state);
-            let response = match state.backend.complete(request).await {
+            let mut request = build_sync_request(&state);
+            if let Some(ref model) = routed_model {
+                request.model = model.clone();
+            }
+            let sync_model = request.model.clone();
+            let response = match backend.complete(request).instrument(state.span.clone()).await {
                 Ok(r) => r,
                 Err(e) => {
                     yield StreamChunk::error(e.to_string());
                     return;
                 }
             };
+            if let Some(ref meter) = state.usage_meter {
+                meter.record(
+                    state.session_id,
+                    state.workstream_id.as_deref(),
+                    routed_profile.as_deref(),
+                    &sync_model,
+                    &response.usage,
+                );
+            }
 
             // Check for tool use
             if response.has_tool_use() {
@@ -312,7 +404,7 @@ pub fn create_turn_stream(
 
                     yield StreamChunk::tool_start(&tool_use.id, &tool_use.name, tool_use.input.clone());
 
-                    let result = match state.tools.execute(&tool_use.name, tool_use.input.clone(), &ctx).await {
+                    let result = match state.tools.execute(&tool_use.name, tool_use.input.clone(), &ctx).instrument(state.span.clone()).await {
                         Ok(r) => r,
                         Err(e) => {
                             tracing::warn!(tool = %tool_use.name, error = %e, "Tool execution failed");
@@ -358,6 +450,7 @@ pub fn create_turn_stream(
             }
 
             // No tool use - we're done
+            state.finish(false);
             yield StreamChunk::done(state.iterations);
             return;
         }
@@ -513,11 +606,14 @@ mod tests {
 
         let stream = create_turn_stream(
             backend as SharedBackend,
+            None,
+            None,
             tools,
             config,
             messages,
             session_id,
             turn_id,
+            None,
             cancel,
             None,
             None,
@@ -565,11 +661,14 @@ mod tests {
 
         let stream = create_turn_stream(
             backend as SharedBackend,
+            None,
+            None,
             tools,
             config,
             messages,
             session_id,
             turn_id,
+            None,
             cancel,
             None,
             None,
@@ -602,11 +701,14 @@ mod tests {
 
         let stream = create_turn_stream(
             backend as SharedBackend,
+            None,
+            None,
             tools,
             config,
             messages,
             session_id,
             turn_id,
+            None,
             cancel,
             None,
             None,
@@ -638,11 +740,14 @@ mod tests {
 
         let stream = create_turn_stream(
             backend as SharedBackend,
+            None,
+            None,
             tools,
             config,
             messages,
             session_id,
             turn_id,
+            None,
             cancel,
             None,
             None,
@@ -678,11 +783,14 @@ mod tests {
 
         let stream = create_turn_stream(
             backend as SharedBackend,
+            None,
+            None,
             tools,
             config,
             messages,
             session_id,
             turn_id,
+            None,
             cancel,
             None,
             None,
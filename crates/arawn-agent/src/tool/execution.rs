@@ -2,7 +2,11 @@
 //!
 //! Implements execute, execute_with_config, execute_raw, and secret handle resolution.
 
+use std::future::Future;
+use std::time::Instant;
+
 use arawn_types::{contains_secret_handle, is_gated_tool, resolve_handles_in_json};
+use tracing::{Instrument, field};
 
 use super::context::{ToolContext, ToolResult};
 use super::output::OutputConfig;
@@ -48,6 +52,20 @@ impl ToolRegistry {
         params: serde_json::Value,
         ctx: &ToolContext,
         output_config: &OutputConfig,
+    ) -> Result<ToolResult> {
+        metered(
+            name,
+            self.execute_sanitized(name, params, ctx, output_config),
+        )
+        .await
+    }
+
+    async fn execute_sanitized(
+        &self,
+        name: &str,
+        params: serde_json::Value,
+        ctx: &ToolContext,
+        output_config: &OutputConfig,
     ) -> Result<ToolResult> {
         let tool = self
             .get(name)
@@ -97,6 +115,15 @@ impl ToolRegistry {
         name: &str,
         params: serde_json::Value,
         ctx: &ToolContext,
+    ) -> Result<ToolResult> {
+        metered(name, self.execute_unsanitized(name, params, ctx)).await
+    }
+
+    async fn execute_unsanitized(
+        &self,
+        name: &str,
+        params: serde_json::Value,
+        ctx: &ToolContext,
     ) -> Result<ToolResult> {
         let tool = self
             .get(name)
@@ -156,6 +183,25 @@ impl ToolRegistry {
     }
 }
 
+/// Run a tool execution inside a `tool` span and record its metrics.
+async fn metered(
+    name: &str,
+    execution: impl Future<Output = Result<ToolResult>>,
+) -> Result<ToolResult> {
+    let span = tracing::info_span!("tool", tool = %name, outcome = field::Empty);
+    let started = Instant::now();
+    let result = execution.instrument(span.clone()).await;
+
+    let outcome = match &result {
+        Ok(r) if r.is_success() => "ok",
+        Ok(_) => "error",
+        Err(_) => "failed",
+    };
+    span.record("outcome", outcome);
+    crate::metrics::record_tool(name, outcome, started);
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -328,6 +374,43 @@ mod tests {
         assert!(result.is_success());
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_execution_records_tool_metrics() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let mut registry = ToolRegistry::new();
+        registry.register(MockTool::new("ok_tool"));
+        registry.register(MockTool::new("bad_tool").with_response(ToolResult::error("nope")));
+        let ctx = ToolContext::default();
+
+        registry
+            .execute("ok_tool", serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+        registry
+            .execute_raw("bad_tool", serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+        assert!(
+            registry
+                .execute("missing", serde_json::json!({}), &ctx)
+                .await
+                .is_err()
+        );
+
+        let output = handle.render();
+        assert!(output.contains(r#"arawn_tool_executions_total{tool="ok_tool",outcome="ok"} 1"#));
+        assert!(
+            output.contains(r#"arawn_tool_executions_total{tool="bad_tool",outcome="error"} 1"#)
+        );
+        assert!(
+            output.contains(r#"arawn_tool_executions_total{tool="missing",outcome="failed"} 1"#)
+        );
+        assert!(output.contains("arawn_tool_duration_seconds"));
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Command Validator Tests
     // ─────────────────────────────────────────────────────────────────────────
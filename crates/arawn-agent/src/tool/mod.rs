@@ -53,7 +53,7 @@ pub use output::{
 };
 
 // Re-export registry
-pub use registry::ToolRegistry;
+pub use registry::{SharedOutputOverrides, ToolRegistry};
 
 // Re-export command validation types
 pub use command_validator::{CommandValidation, CommandValidator};
@@ -1,7 +1,7 @@
 //! Tool registry for managing available tools.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use super::context::Tool;
 #[cfg(test)]
@@ -16,6 +16,10 @@ use async_trait::async_trait;
 // Tool Registry
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Per-tool output config overrides, shared so they can change while the
+/// agent runs (e.g. when `[tools.output]` is reloaded).
+pub type SharedOutputOverrides = Arc<RwLock<HashMap<String, OutputConfig>>>;
+
 /// Registry for managing available tools.
 ///
 /// The registry maintains a collection of tools that can be used by the agent.
@@ -24,7 +28,7 @@ use async_trait::async_trait;
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
     /// Per-tool output config overrides from user configuration.
-    output_overrides: HashMap<String, OutputConfig>,
+    output_overrides: SharedOutputOverrides,
 }
 
 impl ToolRegistry {
@@ -41,17 +45,34 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
-            output_overrides: HashMap::new(),
+            output_overrides: SharedOutputOverrides::default(),
         }
     }
 
+    /// Use an existing set of output config overrides.
+    ///
+    /// Registries sharing overrides see each other's changes, so a registry
+    /// rebuilt from another keeps following live config updates.
+    pub fn with_output_overrides(mut self, overrides: SharedOutputOverrides) -> Self {
+        self.output_overrides = overrides;
+        self
+    }
+
+    /// The output config overrides, for replacing them at runtime.
+    pub fn output_overrides(&self) -> SharedOutputOverrides {
+        self.output_overrides.clone()
+    }
+
     /// Set a per-tool output config override.
     ///
     /// This override takes precedence over the hardcoded defaults in
     /// `output_config_for()`. Multiple tool names can map to the same
     /// config (e.g., "shell" and "bash" share a limit).
     pub fn set_output_config(&mut self, name: impl Into<String>, config: OutputConfig) {
-        self.output_overrides.insert(name.into(), config);
+        self.output_overrides
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.into(), config);
     }
 
     /// Register a tool.
@@ -117,7 +138,7 @@ impl ToolRegistry {
     ///
     /// Returns a new `ToolRegistry` with cloned `Arc` refs for matching tools.
     /// Names not matching any registered tool are silently ignored.
-    /// Output config overrides for matching tools are also carried over.
+    /// Output config overrides are shared with this registry.
     pub fn filtered_by_names(&self, names: &[&str]) -> ToolRegistry {
         let tools: HashMap<String, Arc<dyn Tool>> = names
             .iter()
@@ -128,18 +149,9 @@ impl ToolRegistry {
             })
             .collect();
 
-        let output_overrides: HashMap<String, OutputConfig> = names
-            .iter()
-            .filter_map(|&name| {
-                self.output_overrides
-                    .get(name)
-                    .map(|config| (name.to_string(), config.clone()))
-            })
-            .collect();
-
         ToolRegistry {
             tools,
-            output_overrides,
+            output_overrides: self.output_overrides.clone(),
         }
     }
 
@@ -149,7 +161,12 @@ impl ToolRegistry {
     /// hardcoded per-tool defaults.
     pub fn output_config_for(&self, name: &str) -> OutputConfig {
         // Check overrides first
-        if let Some(config) = self.output_overrides.get(name) {
+        if let Some(config) = self
+            .output_overrides
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+        {
             return config.clone();
         }
 
@@ -492,6 +509,23 @@ mod tests {
         assert_eq!(config.max_size_bytes, 999);
     }
 
+    #[test]
+    fn test_shared_output_overrides_follow_updates() {
+        let mut registry = ToolRegistry::new();
+        registry.register(MockTool::new("shell"));
+        let filtered = registry.filtered_by_names(&["shell"]);
+        let rebuilt = ToolRegistry::new().with_output_overrides(registry.output_overrides());
+
+        registry
+            .output_overrides()
+            .write()
+            .unwrap()
+            .insert("shell".to_string(), OutputConfig::with_max_size(1234));
+
+        assert_eq!(filtered.output_config_for("shell").max_size_bytes, 1234);
+        assert_eq!(rebuilt.output_config_for("shell").max_size_bytes, 1234);
+    }
+
     #[test]
     fn test_filtered_by_names_llm_definitions() {
         let mut registry = ToolRegistry::new();
@@ -0,0 +1,272 @@
+//! Conversation search tool.
+//!
+//! Lets the agent recall past conversations by searching message history
+//! across all workstreams.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+
+use arawn_types::{MessageSearchError, MessageSearchQuery, SharedMessageSearcher};
+
+use crate::error::Result;
+use crate::tool::{Tool, ToolContext, ToolResult};
+
+/// Maximum results the tool returns in one call.
+const MAX_LIMIT: usize = 50;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Conversation Search Tool
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tool for finding where something was discussed in earlier conversations.
+///
+/// Unlike `memory_search`, which covers facts extracted from conversations,
+/// this searches the raw message history itself.
+#[derive(Clone)]
+pub struct ConversationSearchTool {
+    searcher: SharedMessageSearcher,
+}
+
+impl std::fmt::Debug for ConversationSearchTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConversationSearchTool")
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConversationSearchTool {
+    /// Create a conversation search tool over the given message index.
+    pub fn new(searcher: SharedMessageSearcher) -> Self {
+        Self { searcher }
+    }
+}
+
+#[async_trait]
+impl Tool for ConversationSearchTool {
+    fn name(&self) -> &str {
+        "conversation_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the full message history of past conversations across all workstreams. Use this to find where a topic was discussed, recall what was decided, or locate an earlier answer."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Words to search for. All words must match; end a word with * to match a prefix."
+                },
+                "workstream": {
+                    "type": "string",
+                    "description": "Only search this workstream ID."
+                },
+                "role": {
+                    "type": "string",
+                    "enum": ["any", "user", "assistant"],
+                    "description": "Only match messages from this role. Defaults to 'any'.",
+                    "default": "any"
+                },
+                "time_range": {
+                    "type": "string",
+                    "enum": ["all", "today", "week", "month"],
+                    "description": "Time range to search within. Defaults to 'all'.",
+                    "default": "all"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of results to return. Defaults to 10.",
+                    "default": 10
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, params: Value, ctx: &ToolContext) -> Result<ToolResult> {
+        if ctx.is_cancelled() {
+            return Ok(ToolResult::error("Operation cancelled"));
+        }
+
+        let text = params
+            .get("query")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                crate::error::AgentError::Tool("Missing 'query' parameter".to_string())
+            })?;
+        let limit = params
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| (l as usize).clamp(1, MAX_LIMIT))
+            .unwrap_or(10);
+        let role = params.get("role").and_then(|v| v.as_str()).unwrap_or("any");
+        let time_range = params
+            .get("time_range")
+            .and_then(|v| v.as_str())
+            .unwrap_or("all");
+
+        let mut query = MessageSearchQuery::new(text)
+            .with_time_range(since(time_range), None)
+            .with_page(limit, 0);
+        if let Some(ws) = params.get("workstream").and_then(|v| v.as_str()) {
+            query = query.with_workstream(ws);
+        }
+        if matches!(role, "user" | "assistant") {
+            query = query.with_role(role);
+        }
+
+        let results = match self.searcher.search_messages(&query) {
+            Ok(results) => results,
+            Err(MessageSearchError::EmptyQuery) => {
+                return Ok(ToolResult::error(
+                    "The query has no searchable words. Provide one or more keywords.",
+                ));
+            }
+            Err(e) => {
+                return Ok(ToolResult::error(format!(
+                    "Conversation search failed: {e}"
+                )));
+            }
+        };
+
+        let hits: Vec<Value> = results
+            .hits
+            .iter()
+            .map(|hit| {
+                json!({
+                    "workstream_id": hit.workstream_id,
+                    "workstream": hit.workstream_title,
+                    "session_id": hit.session_id,
+                    "message_id": hit.message_id,
+                    "role": hit.role,
+                    "timestamp": hit.timestamp.to_rfc3339(),
+                    "snippet": hit.snippet,
+                })
+            })
+            .collect();
+
+        Ok(ToolResult::json(json!({
+            "status": "ok",
+            "query": text,
+            "total": results.total,
+            "count": hits.len(),
+            "results": hits
+        })))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn since(time_range: &str) -> Option<chrono::DateTime<Utc>> {
+    let now = Utc::now();
+    match time_range {
+        "today" => now.date_naive().and_hms_opt(0, 0, 0).map(|t| t.and_utc()),
+        "week" => Some(now - Duration::days(7)),
+        "month" => Some(now - Duration::days(30)),
+        _ => None,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_types::{MessageSearchHit, MessageSearchResults, MessageSearcher};
+    use std::sync::{Arc, Mutex};
+
+    /// Records the last query and returns one canned hit.
+    #[derive(Default)]
+    struct FakeSearcher {
+        last: Mutex<Option<MessageSearchQuery>>,
+    }
+
+    impl MessageSearcher for FakeSearcher {
+        fn search_messages(
+            &self,
+            query: &MessageSearchQuery,
+        ) -> std::result::Result<MessageSearchResults, MessageSearchError> {
+            if query.text.trim().is_empty() {
+                return Err(MessageSearchError::EmptyQuery);
+            }
+            *self.last.lock().unwrap() = Some(query.clone());
+            Ok(MessageSearchResults {
+                hits: vec![MessageSearchHit {
+                    message_id: "m1".to_string(),
+                    workstream_id: "ws-1".to_string(),
+                    workstream_title: "Infra".to_string(),
+                    session_id: Some("s1".to_string()),
+                    role: "assistant".to_string(),
+                    timestamp: Utc::now(),
+                    snippet: "rotate the **certificates** weekly".to_string(),
+                    score: 1.0,
+                }],
+                total: 1,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn test_conversation_search_metadata() {
+        let tool = ConversationSearchTool::new(Arc::new(FakeSearcher::default()));
+        assert_eq!(tool.name(), "conversation_search");
+        let params = tool.parameters();
+        assert!(params["properties"].get("query").is_some());
+        assert_eq!(params["required"], json!(["query"]));
+    }
+
+    #[tokio::test]
+    async fn test_conversation_search_builds_query() {
+        let searcher = Arc::new(FakeSearcher::default());
+        let tool = ConversationSearchTool::new(searcher.clone());
+
+        let result = tool
+            .execute(
+                json!({
+                    "query": "certificates",
+                    "workstream": "ws-1",
+                    "role": "assistant",
+                    "time_range": "week",
+                    "limit": 500
+                }),
+                &ToolContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.is_success());
+        let content = result.to_llm_content();
+        assert!(content.contains("**certificates**"));
+        assert!(content.contains("Infra"));
+
+        let query = searcher.last.lock().unwrap().clone().unwrap();
+        assert_eq!(query.workstream_id.as_deref(), Some("ws-1"));
+        assert_eq!(query.roles, vec!["assistant"]);
+        assert_eq!(query.limit, MAX_LIMIT);
+        assert!(query.since.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_conversation_search_empty_query() {
+        let tool = ConversationSearchTool::new(Arc::new(FakeSearcher::default()));
+        let result = tool
+            .execute(json!({"query": "  "}), &ToolContext::default())
+            .await
+            .unwrap();
+        assert!(!result.is_success());
+
+        assert!(
+            tool.execute(json!({}), &ToolContext::default())
+                .await
+                .is_err()
+        );
+    }
+}
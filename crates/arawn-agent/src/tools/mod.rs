@@ -7,9 +7,11 @@
 //! - Web search and fetching
 //! - File search (glob/grep)
 //! - Memory/knowledge search
+//! - Conversation history search
 //! - Subagent delegation
 
 mod catalog;
+mod conversation;
 mod delegate;
 mod explore;
 mod file;
@@ -41,6 +43,9 @@ pub use search::{GlobTool, GrepTool};
 // Memory tool
 pub use memory::MemorySearchTool;
 
+// Conversation search tool
+pub use conversation::ConversationSearchTool;
+
 // Workflow tool
 pub use workflow::WorkflowTool;
 
@@ -6,6 +6,7 @@
 //! - [`AgentConfig`]: Runtime configuration
 //! - [`AgentResponse`]: Agent output from a turn
 
+use arawn_types::AgentSettings;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -215,6 +216,13 @@ impl Turn {
 // Session
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Session metadata key holding a routing hint for the LLM router.
+pub const ROUTING_HINT_METADATA_KEY: &str = "routing_hint";
+
+/// Session metadata key holding the [`AgentSettings`] inherited from the
+/// session's workstream.
+pub const AGENT_SETTINGS_METADATA_KEY: &str = "agent_settings";
+
 /// A conversation session containing multiple turns.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -369,6 +377,33 @@ impl Session {
         self.metadata.get(key)
     }
 
+    /// Routing hint stored under the [`ROUTING_HINT_METADATA_KEY`] metadata key.
+    ///
+    /// Lets callers (e.g. skill invocation) steer LLM routing for a session.
+    pub fn routing_hint(&self) -> Option<&str> {
+        self.metadata
+            .get(ROUTING_HINT_METADATA_KEY)
+            .and_then(|v| v.as_str())
+    }
+
+    /// Agent settings stored under the [`AGENT_SETTINGS_METADATA_KEY`] metadata key.
+    ///
+    /// Returns `None` when no settings are set or the stored value is malformed.
+    pub fn agent_settings(&self) -> Option<AgentSettings> {
+        self.metadata
+            .get(AGENT_SETTINGS_METADATA_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Store agent settings for this session, clearing the key for defaults.
+    pub fn set_agent_settings(&mut self, settings: &AgentSettings) {
+        if settings.is_default() {
+            self.remove_metadata(AGENT_SETTINGS_METADATA_KEY);
+        } else if let Ok(value) = serde_json::to_value(settings) {
+            self.set_metadata(AGENT_SETTINGS_METADATA_KEY, value);
+        }
+    }
+
     /// Remove a metadata value.
     pub fn remove_metadata(&mut self, key: &str) -> Option<serde_json::Value> {
         let value = self.metadata.remove(key);
@@ -427,6 +462,10 @@ pub struct AgentConfig {
     /// Workspace path for file operations.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace_path: Option<PathBuf>,
+    /// Routing hint passed to the LLM router (a profile name or a hint
+    /// matched by routing rules). Ignored when no router is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing_hint: Option<String>,
 }
 
 impl AgentConfig {
@@ -441,6 +480,7 @@ impl AgentConfig {
             timeout: Duration::from_secs(300),
             system_prompt: None,
             workspace_path: None,
+            routing_hint: None,
         }
     }
 
@@ -485,6 +525,12 @@ impl AgentConfig {
         self.workspace_path = Some(path.into());
         self
     }
+
+    /// Set the routing hint.
+    pub fn with_routing_hint(mut self, hint: impl Into<String>) -> Self {
+        self.routing_hint = Some(hint.into());
+        self
+    }
 }
 
 impl Default for AgentConfig {
@@ -675,6 +721,24 @@ mod tests {
         assert_eq!(session.get_metadata("key1"), None);
     }
 
+    #[test]
+    fn test_session_agent_settings() {
+        let mut session = Session::new();
+        assert!(session.agent_settings().is_none());
+
+        let settings = AgentSettings {
+            profile: Some("fast".to_string()),
+            denied_tools: vec!["shell".to_string()],
+            ..Default::default()
+        };
+        session.set_agent_settings(&settings);
+        assert_eq!(session.agent_settings(), Some(settings));
+
+        // Defaults clear the key instead of storing an empty object
+        session.set_agent_settings(&AgentSettings::default());
+        assert!(session.get_metadata(AGENT_SETTINGS_METADATA_KEY).is_none());
+    }
+
     #[test]
     fn test_agent_config() {
         let config = AgentConfig::new("claude-sonnet-4-20250514")
@@ -0,0 +1,166 @@
+//! Per-call usage accounting for the agent loop.
+//!
+//! A [`UsageMeter`] prices each LLM call with a [`PricingTable`], reports it to
+//! a [`UsageRecorder`], and answers whether a workstream has reached its spend
+//! limit before the next call is made.
+
+use std::sync::Arc;
+
+use arawn_llm::{PricingTable, Usage};
+use arawn_types::{BudgetStatus, SharedUsageRecorder, UsageEntry};
+
+use crate::types::SessionId;
+
+/// Prices LLM calls, records them, and checks spend limits.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use arawn_agent::UsageMeter;
+///
+/// let meter = UsageMeter::new(ledger)
+///     .with_pricing(pricing)
+///     .with_profile("default");
+/// let agent = Agent::builder().with_usage_meter(meter).build()?;
+/// ```
+#[derive(Clone)]
+pub struct UsageMeter {
+    recorder: SharedUsageRecorder,
+    pricing: Arc<PricingTable>,
+    profile: String,
+}
+
+impl UsageMeter {
+    /// Create a meter reporting to the given recorder, with no prices configured.
+    pub fn new(recorder: SharedUsageRecorder) -> Self {
+        Self {
+            recorder,
+            pricing: Arc::new(PricingTable::new()),
+            profile: "default".to_string(),
+        }
+    }
+
+    /// Set the pricing table used to compute call cost.
+    pub fn with_pricing(mut self, pricing: PricingTable) -> Self {
+        self.pricing = Arc::new(pricing);
+        self
+    }
+
+    /// Set the profile name recorded for calls that were not routed.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    /// Profile name recorded for calls that were not routed.
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Price and record one LLM call, returning its cost in USD.
+    ///
+    /// `profile` is the routed profile, if any; recording failures are logged
+    /// rather than failing the turn.
+    pub fn record(
+        &self,
+        session_id: SessionId,
+        workstream_id: Option<&str>,
+        profile: Option<&str>,
+        model: &str,
+        usage: &Usage,
+    ) -> f64 {
+        let cost_usd = self.pricing.cost(model, usage).unwrap_or_else(|| {
+            tracing::debug!(model, "No price configured for model — recording zero cost");
+            0.0
+        });
+        let entry = UsageEntry {
+            session_id: session_id.to_string(),
+            workstream_id: workstream_id.map(String::from),
+            profile: profile.unwrap_or(&self.profile).to_string(),
+            model: model.to_string(),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cache_creation_input_tokens: usage.cache_creation_input_tokens,
+            cache_read_input_tokens: usage.cache_read_input_tokens,
+            cost_usd,
+        };
+        if let Err(e) = self.recorder.record(&entry) {
+            tracing::warn!(%session_id, error = %e, "Failed to record LLM usage");
+        }
+        cost_usd
+    }
+
+    /// Spend state of the workstream (always within limit outside a workstream).
+    pub fn budget_status(&self, workstream_id: Option<&str>) -> BudgetStatus {
+        match workstream_id {
+            Some(ws) => self.recorder.budget_status(ws),
+            None => BudgetStatus::WithinLimit,
+        }
+    }
+}
+
+/// Message returned to the user when a hard spend limit stops the turn.
+pub fn spend_limit_message(workstream_id: &str, status: &BudgetStatus) -> String {
+    format!(
+        "[Spend limit reached: workstream '{}' has {}. No further LLM calls will be made until the limit is raised or the period resets.]",
+        workstream_id, status
+    )
+}
+
+/// Type alias for a shared usage meter.
+pub type SharedUsageMeter = Arc<UsageMeter>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_llm::ModelPricing;
+    use arawn_types::{UsageError, UsageRecorder};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CapturingRecorder {
+        entries: Mutex<Vec<UsageEntry>>,
+    }
+
+    impl UsageRecorder for CapturingRecorder {
+        fn record(&self, entry: &UsageEntry) -> Result<(), UsageError> {
+            self.entries.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+
+        fn budget_status(&self, _workstream_id: &str) -> BudgetStatus {
+            BudgetStatus::HardLimitExceeded {
+                spent_usd: 3.0,
+                limit_usd: 2.0,
+            }
+        }
+    }
+
+    #[test]
+    fn test_record_prices_and_attributes_call() {
+        let recorder = Arc::new(CapturingRecorder::default());
+        let meter = UsageMeter::new(recorder.clone())
+            .with_pricing(PricingTable::new().with_model("m", ModelPricing::new(1.0, 2.0)))
+            .with_profile("main");
+
+        let session = SessionId::new();
+        let cost = meter.record(session, Some("ws"), None, "m", &Usage::new(1_000_000, 0));
+        assert!((cost - 1.0).abs() < 1e-9);
+        meter.record(session, None, Some("fast"), "unpriced", &Usage::new(10, 10));
+
+        let entries = recorder.entries.lock().unwrap();
+        assert_eq!(entries[0].profile, "main");
+        assert_eq!(entries[0].workstream_id.as_deref(), Some("ws"));
+        assert_eq!(entries[1].profile, "fast");
+        assert_eq!(entries[1].cost_usd, 0.0);
+    }
+
+    #[test]
+    fn test_budget_status_requires_workstream() {
+        let meter = UsageMeter::new(Arc::new(CapturingRecorder::default()));
+        assert_eq!(meter.budget_status(None), BudgetStatus::WithinLimit);
+        let status = meter.budget_status(Some("ws"));
+        assert!(status.is_exhausted());
+        assert!(spend_limit_message("ws", &status).contains("$3.00 of $2.00 hard limit"));
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::client::ArawnClient;
 use crate::error::Result;
-use crate::types::ConfigResponse;
+use crate::types::{ConfigResponse, ConfigUpdateResponse};
 
 /// Config API client.
 pub struct ConfigApi {
@@ -18,4 +18,14 @@ impl ConfigApi {
     pub async fn get(&self) -> Result<ConfigResponse> {
         self.client.get("config").await
     }
+
+    /// Replace the whole configuration (the `arawn.toml` structure as JSON).
+    pub async fn replace(&self, config: &serde_json::Value) -> Result<ConfigUpdateResponse> {
+        self.client.put("config", config).await
+    }
+
+    /// Update part of the configuration with a JSON merge patch.
+    pub async fn patch(&self, patch: &serde_json::Value) -> Result<ConfigUpdateResponse> {
+        self.client.patch("config", patch).await
+    }
 }
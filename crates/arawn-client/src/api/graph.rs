@@ -0,0 +1,104 @@
+//! Knowledge graph API.
+
+use crate::client::ArawnClient;
+use crate::error::Result;
+use crate::types::{
+    CypherQueryRequest, CypherQueryResponse, EntityDetail, GraphExportFormat, GraphStats,
+    ListEntitiesResponse, Subgraph,
+};
+
+/// Query parameters for listing entities.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct ListEntitiesQuery {
+    /// Only entities with this label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Case-insensitive substring of the ID, name or context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// Maximum results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Offset for pagination.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Knowledge graph API client.
+pub struct GraphApi {
+    client: ArawnClient,
+}
+
+impl GraphApi {
+    pub(crate) fn new(client: ArawnClient) -> Self {
+        Self { client }
+    }
+
+    /// Get node and relationship counts.
+    pub async fn stats(&self) -> Result<GraphStats> {
+        self.client.get("memory/graph").await
+    }
+
+    /// List entities.
+    pub async fn entities(&self) -> Result<ListEntitiesResponse> {
+        self.entities_with_options(ListEntitiesQuery::default())
+            .await
+    }
+
+    /// List entities with filters and pagination.
+    pub async fn entities_with_options(
+        &self,
+        query: ListEntitiesQuery,
+    ) -> Result<ListEntitiesResponse> {
+        self.client
+            .get_with_query("memory/graph/entities", &query)
+            .await
+    }
+
+    /// Search entities by ID, name or context.
+    pub async fn search(&self, q: &str) -> Result<ListEntitiesResponse> {
+        self.entities_with_options(ListEntitiesQuery {
+            q: Some(q.to_string()),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Get an entity with its relationships and linked memories.
+    pub async fn entity(&self, id: &str) -> Result<EntityDetail> {
+        self.client
+            .get(&format!("memory/graph/entities/{}", id))
+            .await
+    }
+
+    /// Get the entities within `hops` relationships of an entity.
+    pub async fn neighbors(&self, id: &str, hops: usize) -> Result<Subgraph> {
+        self.client
+            .get_with_query(
+                &format!("memory/graph/entities/{}/neighbors", id),
+                &[("hops", hops)],
+            )
+            .await
+    }
+
+    /// Run a read-only Cypher query (requires the `admin` scope).
+    pub async fn query(&self, cypher: &str) -> Result<CypherQueryResponse> {
+        self.client
+            .post(
+                "memory/graph/query",
+                &CypherQueryRequest {
+                    query: cypher.to_string(),
+                    timeout_ms: None,
+                },
+            )
+            .await
+    }
+
+    /// Export the whole graph as JSON, GraphML or DOT text (requires the
+    /// `admin` scope).
+    pub async fn export(&self, format: GraphExportFormat) -> Result<String> {
+        self.client
+            .get_text("memory/graph/export", &[("format", format)])
+            .await
+    }
+}
@@ -3,21 +3,31 @@
 mod agents;
 mod chat;
 mod config;
+mod graph;
 mod health;
 mod mcp;
 mod memory;
 mod notes;
+mod plugins;
+mod search;
 mod sessions;
 mod tasks;
+mod templates;
+mod workflows;
 mod workstreams;
 
 pub use agents::AgentsApi;
 pub use chat::ChatApi;
 pub use config::ConfigApi;
+pub use graph::{GraphApi, ListEntitiesQuery};
 pub use health::HealthApi;
 pub use mcp::McpApi;
 pub use memory::{MemoryApi, MemorySearchQuery};
 pub use notes::{ListNotesQuery, NotesApi};
+pub use plugins::PluginsApi;
+pub use search::{MessageSearchQuery, SearchApi};
 pub use sessions::SessionsApi;
 pub use tasks::{ListTasksQuery, TasksApi};
+pub use templates::TemplatesApi;
+pub use workflows::WorkflowsApi;
 pub use workstreams::{ListMessagesQuery, WorkstreamsApi};
@@ -0,0 +1,83 @@
+//! Plugins API.
+
+use crate::client::ArawnClient;
+use crate::error::Result;
+use crate::types::{
+    InstallPluginRequest, ListPluginsResponse, PluginInfo, SyncPluginResponse, SyncPluginsResponse,
+};
+
+/// Plugins API client.
+///
+/// Plugin IDs may be a plugin name, a subscription ID such as
+/// `github/owner-repo`, or a GitHub `owner/repo`; they are percent-encoded
+/// into the request path.
+pub struct PluginsApi {
+    client: ArawnClient,
+}
+
+impl PluginsApi {
+    pub(crate) fn new(client: ArawnClient) -> Self {
+        Self { client }
+    }
+
+    /// List plugins with their capabilities and load errors.
+    pub async fn list(&self) -> Result<ListPluginsResponse> {
+        self.client.get("plugins").await
+    }
+
+    /// Get a plugin.
+    pub async fn get(&self, id: &str) -> Result<PluginInfo> {
+        self.client.get(&plugin_path(id, "")).await
+    }
+
+    /// Subscribe to a plugin from a GitHub `owner/repo`, git URL or server
+    /// path (requires the `admin` scope).
+    pub async fn install(&self, request: InstallPluginRequest) -> Result<PluginInfo> {
+        self.client.post("plugins", &request).await
+    }
+
+    /// Enable a plugin and load it into the running agent.
+    pub async fn enable(&self, id: &str) -> Result<PluginInfo> {
+        self.client
+            .post(&plugin_path(id, "/enable"), &serde_json::json!({}))
+            .await
+    }
+
+    /// Disable a plugin and unload it from the running agent.
+    pub async fn disable(&self, id: &str) -> Result<PluginInfo> {
+        self.client
+            .post(&plugin_path(id, "/disable"), &serde_json::json!({}))
+            .await
+    }
+
+    /// Update a subscription to the latest commit of its ref and reload it.
+    pub async fn sync(&self, id: &str) -> Result<SyncPluginResponse> {
+        self.client
+            .post(&plugin_path(id, "/sync"), &serde_json::json!({}))
+            .await
+    }
+
+    /// Update all enabled subscriptions.
+    pub async fn sync_all(&self) -> Result<SyncPluginsResponse> {
+        self.client
+            .post("plugins/sync", &serde_json::json!({}))
+            .await
+    }
+
+    /// Unsubscribe from a plugin, keeping its git checkout.
+    pub async fn remove(&self, id: &str) -> Result<()> {
+        self.client.delete(&plugin_path(id, "")).await
+    }
+
+    /// Unsubscribe from a plugin and delete its git checkout.
+    pub async fn remove_and_delete_cache(&self, id: &str) -> Result<()> {
+        self.client
+            .delete(&plugin_path(id, "?delete_cache=true"))
+            .await
+    }
+}
+
+fn plugin_path(id: &str, suffix: &str) -> String {
+    let id: String = url::form_urlencoded::byte_serialize(id.as_bytes()).collect();
+    format!("plugins/{}{}", id, suffix)
+}
@@ -0,0 +1,64 @@
+//! Search API.
+
+use crate::client::ArawnClient;
+use crate::error::Result;
+use crate::types::MessageSearchResponse;
+
+/// Query parameters for message history search.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct MessageSearchQuery {
+    /// Words to search for.
+    pub q: String,
+    /// Only search this workstream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workstream: Option<String>,
+    /// Only search this session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    /// Comma-separated roles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Comma-separated workstream tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Earliest message time (RFC 3339 or YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Latest message time (RFC 3339 or YYYY-MM-DD).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    /// Maximum hits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Hits to skip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Search API client.
+pub struct SearchApi {
+    client: ArawnClient,
+}
+
+impl SearchApi {
+    pub(crate) fn new(client: ArawnClient) -> Self {
+        Self { client }
+    }
+
+    /// Search message history across all workstreams.
+    pub async fn messages(&self, query: &str) -> Result<MessageSearchResponse> {
+        self.messages_with_options(MessageSearchQuery {
+            q: query.to_string(),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Search message history with filters.
+    pub async fn messages_with_options(
+        &self,
+        query: MessageSearchQuery,
+    ) -> Result<MessageSearchResponse> {
+        self.client.get_with_query("search/messages", &query).await
+    }
+}
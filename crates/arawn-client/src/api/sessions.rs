@@ -3,8 +3,8 @@
 use crate::client::ArawnClient;
 use crate::error::Result;
 use crate::types::{
-    CreateSessionRequest, ListSessionsResponse, SessionDetail, SessionMessagesResponse,
-    UpdateSessionRequest,
+    CreateSessionRequest, ForkSessionRequest, ForkSessionResponse, ListSessionsResponse,
+    SessionDetail, SessionMessagesResponse, UpdateSessionRequest,
 };
 
 /// Sessions API client.
@@ -48,4 +48,11 @@ impl SessionsApi {
     pub async fn messages(&self, id: &str) -> Result<SessionMessagesResponse> {
         self.client.get(&format!("sessions/{}/messages", id)).await
     }
+
+    /// Fork a session into a new one holding a prefix of its history.
+    pub async fn fork(&self, id: &str, request: ForkSessionRequest) -> Result<ForkSessionResponse> {
+        self.client
+            .post(&format!("sessions/{}/fork", id), &request)
+            .await
+    }
 }
@@ -0,0 +1,24 @@
+//! Workstream templates API.
+
+use crate::client::ArawnClient;
+use crate::error::Result;
+use crate::types::ListTemplatesResponse;
+
+/// Workstream templates API client.
+///
+/// Templates are applied by setting `template` on
+/// [`CreateWorkstreamRequest`](crate::types::CreateWorkstreamRequest).
+pub struct TemplatesApi {
+    client: ArawnClient,
+}
+
+impl TemplatesApi {
+    pub(crate) fn new(client: ArawnClient) -> Self {
+        Self { client }
+    }
+
+    /// List available workstream templates.
+    pub async fn list(&self) -> Result<ListTemplatesResponse> {
+        self.client.get("templates").await
+    }
+}
@@ -0,0 +1,145 @@
+//! Workflows and runtime catalog API.
+
+use crate::client::ArawnClient;
+use crate::error::Result;
+use crate::types::{
+    ListRuntimesResponse, ListWorkflowsResponse, RunWorkflowAcceptedResponse, RunWorkflowRequest,
+    RunWorkflowResponse, RuntimeInfo, SaveRuntimeRequest, SaveWorkflowRequest,
+    ScheduleWorkflowRequest, WorkflowDetail, WorkflowSchedule, WorkflowSummary,
+};
+
+/// Workflows API client.
+///
+/// Invalid definitions are rejected with
+/// [`Error::Validation`](crate::Error::Validation), whose `details.issues`
+/// lists every problem found.
+pub struct WorkflowsApi {
+    client: ArawnClient,
+}
+
+impl WorkflowsApi {
+    pub(crate) fn new(client: ArawnClient) -> Self {
+        Self { client }
+    }
+
+    /// List workflows and their schedules.
+    pub async fn list(&self) -> Result<ListWorkflowsResponse> {
+        self.client.get("workflows").await
+    }
+
+    /// Get a workflow by name.
+    pub async fn get(&self, name: &str) -> Result<WorkflowDetail> {
+        self.client.get(&format!("workflows/{}", name)).await
+    }
+
+    /// Create a new workflow from a TOML definition.
+    pub async fn create(&self, definition: &str) -> Result<WorkflowSummary> {
+        self.client
+            .post(
+                "workflows",
+                &SaveWorkflowRequest {
+                    definition: definition.to_string(),
+                },
+            )
+            .await
+    }
+
+    /// Create or replace a workflow.
+    pub async fn put(&self, name: &str, definition: &str) -> Result<WorkflowSummary> {
+        self.client
+            .put(
+                &format!("workflows/{}", name),
+                &SaveWorkflowRequest {
+                    definition: definition.to_string(),
+                },
+            )
+            .await
+    }
+
+    /// Delete a workflow.
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        self.client.delete(&format!("workflows/{}", name)).await
+    }
+
+    /// Run a workflow and wait for it to finish.
+    pub async fn run(&self, name: &str, input: serde_json::Value) -> Result<RunWorkflowResponse> {
+        self.client
+            .post(
+                &format!("workflows/{}/run", name),
+                &RunWorkflowRequest {
+                    input,
+                    run_async: false,
+                },
+            )
+            .await
+    }
+
+    /// Start a workflow in the background; poll the returned task ID via
+    /// the tasks API.
+    pub async fn run_async(
+        &self,
+        name: &str,
+        input: serde_json::Value,
+    ) -> Result<RunWorkflowAcceptedResponse> {
+        self.client
+            .post(
+                &format!("workflows/{}/run", name),
+                &RunWorkflowRequest {
+                    input,
+                    run_async: true,
+                },
+            )
+            .await
+    }
+
+    /// Schedule a workflow with a cron expression.
+    pub async fn schedule(
+        &self,
+        name: &str,
+        cron: &str,
+        timezone: &str,
+    ) -> Result<WorkflowSchedule> {
+        self.client
+            .post(
+                &format!("workflows/{}/schedules", name),
+                &ScheduleWorkflowRequest {
+                    cron: cron.to_string(),
+                    timezone: timezone.to_string(),
+                },
+            )
+            .await
+    }
+
+    /// Cancel a workflow schedule.
+    pub async fn cancel_schedule(&self, name: &str, schedule_id: &str) -> Result<()> {
+        self.client
+            .delete(&format!("workflows/{}/schedules/{}", name, schedule_id))
+            .await
+    }
+
+    /// List runtime catalog entries.
+    pub async fn runtimes(&self) -> Result<ListRuntimesResponse> {
+        self.client.get("runtimes").await
+    }
+
+    /// Get a runtime catalog entry.
+    pub async fn runtime(&self, name: &str) -> Result<RuntimeInfo> {
+        self.client.get(&format!("runtimes/{}", name)).await
+    }
+
+    /// Create or replace a custom runtime.
+    pub async fn put_runtime(
+        &self,
+        name: &str,
+        request: SaveRuntimeRequest,
+    ) -> Result<RuntimeInfo> {
+        self.client
+            .put(&format!("runtimes/{}", name), &request)
+            .await
+    }
+
+    /// Remove a custom runtime.
+    pub async fn delete_runtime(&self, name: &str) -> Result<()> {
+        self.client.delete(&format!("runtimes/{}", name)).await
+    }
+}
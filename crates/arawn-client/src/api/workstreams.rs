@@ -4,7 +4,8 @@ use crate::client::ArawnClient;
 use crate::error::Result;
 use crate::types::{
     CreateWorkstreamRequest, ListMessagesResponse, ListWorkstreamSessionsResponse,
-    ListWorkstreamsResponse, PromoteRequest, SendMessageRequest, UpdateWorkstreamRequest,
+    ListWorkstreamsResponse, MergeWorkstreamRequest, MergeWorkstreamResponse, PromoteRequest,
+    SendMessageRequest, SplitWorkstreamRequest, SplitWorkstreamResponse, UpdateWorkstreamRequest,
     Workstream, WorkstreamMessage,
 };
 
@@ -114,4 +115,26 @@ impl WorkstreamsApi {
             .post("workstreams/scratch/promote", &request)
             .await
     }
+
+    /// Merge a workstream into another, archiving the source.
+    pub async fn merge(
+        &self,
+        source_id: &str,
+        request: MergeWorkstreamRequest,
+    ) -> Result<MergeWorkstreamResponse> {
+        self.client
+            .post(&format!("workstreams/{}/merge", source_id), &request)
+            .await
+    }
+
+    /// Move sessions out of a workstream into a new one.
+    pub async fn split(
+        &self,
+        workstream_id: &str,
+        request: SplitWorkstreamRequest,
+    ) -> Result<SplitWorkstreamResponse> {
+        self.client
+            .post(&format!("workstreams/{}/split", workstream_id), &request)
+            .await
+    }
 }
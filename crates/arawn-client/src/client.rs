@@ -7,8 +7,8 @@ use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use url::Url;
 
 use crate::api::{
-    AgentsApi, ChatApi, ConfigApi, HealthApi, McpApi, MemoryApi, NotesApi, SessionsApi, TasksApi,
-    WorkstreamsApi,
+    AgentsApi, ChatApi, ConfigApi, GraphApi, HealthApi, McpApi, MemoryApi, NotesApi, PluginsApi,
+    SearchApi, SessionsApi, TasksApi, TemplatesApi, WorkflowsApi, WorkstreamsApi,
 };
 use crate::error::{Error, ErrorResponse, Result};
 
@@ -117,16 +117,41 @@ impl ArawnClient {
         MemoryApi::new(self.clone())
     }
 
+    /// Access the knowledge graph API.
+    pub fn graph(&self) -> GraphApi {
+        GraphApi::new(self.clone())
+    }
+
+    /// Access the search API.
+    pub fn search(&self) -> SearchApi {
+        SearchApi::new(self.clone())
+    }
+
     /// Access the tasks API.
     pub fn tasks(&self) -> TasksApi {
         TasksApi::new(self.clone())
     }
 
+    /// Access the workstream templates API.
+    pub fn templates(&self) -> TemplatesApi {
+        TemplatesApi::new(self.clone())
+    }
+
     /// Access the MCP API.
     pub fn mcp(&self) -> McpApi {
         McpApi::new(self.clone())
     }
 
+    /// Access the plugins API.
+    pub fn plugins(&self) -> PluginsApi {
+        PluginsApi::new(self.clone())
+    }
+
+    /// Access the workflows API.
+    pub fn workflows(&self) -> WorkflowsApi {
+        WorkflowsApi::new(self.clone())
+    }
+
     /// Access the health API.
     pub fn health(&self) -> HealthApi {
         HealthApi::new(self.clone())
@@ -176,6 +201,28 @@ impl ArawnClient {
         self.handle_response(response).await
     }
 
+    /// Make a GET request with query parameters, returning the body as text.
+    pub(crate) async fn get_text<Q>(&self, path: &str, query: &Q) -> Result<String>
+    where
+        Q: serde::Serialize + ?Sized,
+    {
+        let url = self.url(path)?;
+        let response = self
+            .inner
+            .http
+            .get(url)
+            .query(query)
+            .timeout(self.inner.timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(self.extract_error(response).await);
+        }
+
+        Ok(response.text().await?)
+    }
+
     /// Make a POST request.
     pub(crate) async fn post<T, B>(&self, path: &str, body: &B) -> Result<T>
     where
@@ -293,6 +340,11 @@ impl ArawnClient {
                     Error::NotFound(err.message)
                 } else if status == 401 {
                     Error::Auth(err.message)
+                } else if let (422, Some(details)) = (status, err.details) {
+                    Error::Validation {
+                        message: err.message,
+                        details,
+                    }
                 } else {
                     Error::Api {
                         status,
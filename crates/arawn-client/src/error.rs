@@ -32,6 +32,15 @@ pub enum Error {
     #[error("Authentication failed: {0}")]
     Auth(String),
 
+    /// Request failed validation; `details` carries the structured problems.
+    #[error("Validation failed: {message}")]
+    Validation {
+        /// Error message from server.
+        message: String,
+        /// Structured validation details.
+        details: serde_json::Value,
+    },
+
     /// Resource not found.
     #[error("Not found: {0}")]
     NotFound(String),
@@ -75,4 +84,6 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub(crate) struct ErrorResponse {
     pub code: String,
     pub message: String,
+    #[serde(default)]
+    pub details: Option<serde_json::Value>,
 }
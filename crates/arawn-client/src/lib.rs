@@ -54,7 +54,11 @@
 //! - **Agents**: List agents and their tools
 //! - **Notes**: CRUD operations for notes
 //! - **Memory**: Search and store memories
+//! - **Graph**: Browse, traverse, query and export the knowledge graph
+//! - **Search**: Full-text search over message history
 //! - **Tasks**: List and cancel background tasks
+//! - **Workflows**: Manage, run and schedule workflows and WASM runtimes
+//! - **Plugins**: Install, enable, disable, sync and remove plugins
 //! - **MCP**: Manage Model Context Protocol servers
 //! - **Health**: Server health checks
 
@@ -68,4 +72,7 @@ pub use error::{Error, Result};
 pub use types::*;
 
 // Re-export API types that are commonly used with query methods
-pub use api::{ListMessagesQuery, ListNotesQuery, ListTasksQuery, MemorySearchQuery};
+pub use api::{
+    ListEntitiesQuery, ListMessagesQuery, ListNotesQuery, ListTasksQuery, MemorySearchQuery,
+    MessageSearchQuery,
+};
@@ -3,7 +3,7 @@
 //! These types mirror the server's API contract.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Sessions
@@ -34,6 +34,36 @@ pub struct UpdateSessionRequest {
     pub workstream_id: Option<String>,
 }
 
+/// Request to fork a session.
+///
+/// Set at most one of `message_id` and `turns`; with neither, the whole
+/// session is copied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForkSessionRequest {
+    /// Keep history up to and including this message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Keep only the first N turns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turns: Option<usize>,
+}
+
+/// Response from forking a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkSessionResponse {
+    /// ID of the new session.
+    pub session_id: String,
+    /// Workstream both sessions belong to.
+    pub workstream_id: String,
+    /// Session the fork was copied from.
+    pub parent_session_id: String,
+    /// Last message copied from the parent.
+    #[serde(default)]
+    pub forked_from_message_id: Option<String>,
+    /// Number of messages copied.
+    pub messages_copied: usize,
+}
+
 /// Summary info for a session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionSummary {
@@ -122,7 +152,7 @@ pub struct ListSessionsResponse {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Request to create a workstream.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateWorkstreamRequest {
     /// Workstream title.
     pub title: String,
@@ -132,6 +162,12 @@ pub struct CreateWorkstreamRequest {
     /// Tags for categorization.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Template to scaffold the workstream from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Values for the template's parameters.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, String>,
 }
 
 /// Request to update a workstream.
@@ -149,6 +185,38 @@ pub struct UpdateWorkstreamRequest {
     /// New tags.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// Replacement agent settings (replaces the whole record).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<WorkstreamSettings>,
+}
+
+/// Agent settings applied to a workstream's sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkstreamSettings {
+    /// Model override.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// LLM profile used as the routing hint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Extra system prompt instructions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Files under `production/` appended to the system prompt.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap_files: Vec<String>,
+    /// Tools the agent may use (empty allows all).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Tools the agent may never use.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_tools: Vec<String>,
+    /// Recall scope (all, session, none).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_scope: Option<String>,
+    /// Domains sandboxed shell commands may reach.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_domains: Vec<String>,
 }
 
 /// Workstream details.
@@ -175,6 +243,12 @@ pub struct Workstream {
     /// Tags.
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// Agent settings.
+    #[serde(default)]
+    pub settings: WorkstreamSettings,
+    /// What a template applied, when the workstream was just created from one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateApplied>,
 }
 
 /// Response for list workstreams.
@@ -261,6 +335,130 @@ pub struct PromoteRequest {
     pub default_model: Option<String>,
 }
 
+/// Request to merge a workstream into another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeWorkstreamRequest {
+    /// ID of the workstream to merge into.
+    pub target: String,
+    /// File conflict policy: `rename` (server default), `overwrite` or `skip`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_conflict: Option<String>,
+}
+
+/// Response from merging workstreams.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeWorkstreamResponse {
+    /// The target workstream after the merge.
+    pub workstream: Workstream,
+    /// IDs of the sessions moved from the source.
+    #[serde(default)]
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    #[serde(default)]
+    pub messages_moved: usize,
+    /// Files moved without a conflict.
+    #[serde(default)]
+    pub files_moved: usize,
+    /// Conflicting files moved under a new name.
+    #[serde(default)]
+    pub files_renamed: usize,
+    /// Conflicting files that replaced the target's copy.
+    #[serde(default)]
+    pub files_overwritten: usize,
+    /// Conflicting files left in the source.
+    #[serde(default)]
+    pub files_skipped: usize,
+    /// Notes retagged from the source to the target.
+    #[serde(default)]
+    pub notes_retagged: usize,
+}
+
+/// Request to split sessions out into a new workstream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitWorkstreamRequest {
+    /// IDs of the sessions to move.
+    pub session_ids: Vec<String>,
+    /// Title for the new workstream.
+    pub title: String,
+    /// Tags for the new workstream.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Response from splitting a workstream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitWorkstreamResponse {
+    /// The newly created workstream.
+    pub workstream: Workstream,
+    /// IDs of the sessions moved into it.
+    #[serde(default)]
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    #[serde(default)]
+    pub messages_moved: usize,
+}
+
+/// What a template applied to a new workstream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateApplied {
+    /// Template name.
+    pub name: String,
+    /// Number of files copied into `production/` and `work/`.
+    #[serde(default)]
+    pub files: usize,
+    /// Number of notes created.
+    #[serde(default)]
+    pub notes: usize,
+    /// Workflow files registered.
+    #[serde(default)]
+    pub workflows: Vec<String>,
+    /// Cloned repositories, relative to `production/`.
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Parts of the template that could not be applied.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// A parameter a template asks for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateParamInfo {
+    /// Parameter name.
+    pub name: String,
+    /// What the value is for.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Value used when none is given.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Whether a value must be given.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// An available workstream template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateInfo {
+    /// Template name (`<plugin>:<template>` for plugin templates).
+    pub name: String,
+    /// What the template is for.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Plugin that provides the template, if any.
+    #[serde(default)]
+    pub plugin: Option<String>,
+    /// Parameters the template asks for.
+    #[serde(default)]
+    pub params: Vec<TemplateParamInfo>,
+}
+
+/// Response for list templates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTemplatesResponse {
+    /// Available templates.
+    pub templates: Vec<TemplateInfo>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Chat
 // ─────────────────────────────────────────────────────────────────────────────
@@ -430,6 +628,23 @@ pub struct ConfigLimits {
     pub max_concurrent_requests: Option<u32>,
 }
 
+/// Result of updating the configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigUpdateResponse {
+    /// Every changed key (e.g. `llm_profiles.fast.model`).
+    pub changed: Vec<String>,
+    /// Changed keys that took effect immediately.
+    pub applied: Vec<String>,
+    /// Changed keys that need a server restart.
+    pub restart_required: Vec<String>,
+    /// Config files written.
+    #[serde(default)]
+    pub persisted: Vec<String>,
+    /// Problems applying accepted changes.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Agents
 // ─────────────────────────────────────────────────────────────────────────────
@@ -621,6 +836,188 @@ pub struct MemorySearchResponse {
     pub count: usize,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Knowledge graph
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Knowledge graph size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphStats {
+    /// Number of nodes, including memory nodes.
+    pub node_count: usize,
+    /// Number of relationships.
+    pub relationship_count: usize,
+}
+
+/// An entity in the knowledge graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEntity {
+    /// Entity ID.
+    pub id: String,
+    /// Entity label, e.g. `Person` or `Concept`.
+    pub label: String,
+    /// Entity properties.
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+/// A directed relationship between two entities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    /// Source entity ID.
+    pub source: String,
+    /// Target entity ID.
+    pub target: String,
+    /// Relationship type, e.g. `RELATED_TO`.
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    /// Relationship properties.
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+/// A page of entities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListEntitiesResponse {
+    /// Entities ordered by ID.
+    pub entities: Vec<GraphEntity>,
+    /// Total number of matching entities.
+    pub total: usize,
+    /// Page size.
+    pub limit: usize,
+    /// Offset of this page.
+    pub offset: usize,
+}
+
+/// A memory that mentions an entity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedMemory {
+    /// Memory ID.
+    pub id: String,
+    /// Content type.
+    pub content_type: String,
+    /// Content text.
+    pub content: String,
+    /// Session the memory belongs to.
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// An entity with its relationships and linked memories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityDetail {
+    /// The entity.
+    pub entity: GraphEntity,
+    /// Relationships in both directions.
+    pub relationships: Vec<GraphEdge>,
+    /// Memories linked to the entity.
+    pub memories: Vec<LinkedMemory>,
+}
+
+/// Nodes and the relationships between them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subgraph {
+    /// Nodes.
+    pub nodes: Vec<GraphEntity>,
+    /// Relationships between the nodes.
+    pub relationships: Vec<GraphEdge>,
+    /// Whether nodes were left out to stay within the limit.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// A read-only Cypher query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CypherQueryRequest {
+    /// The query.
+    pub query: String,
+    /// Timeout in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Rows returned by a Cypher query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CypherQueryResponse {
+    /// Column names.
+    pub columns: Vec<String>,
+    /// One object per row, keyed by column name.
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// Number of rows returned.
+    pub row_count: usize,
+    /// Whether rows beyond the server's limit were dropped.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// Graph export format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphExportFormat {
+    /// JSON with `nodes` and `relationships`.
+    #[default]
+    Json,
+    /// GraphML XML.
+    Graphml,
+    /// Graphviz DOT.
+    Dot,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Search
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A message matching a history search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchHit {
+    /// Message ID.
+    pub message_id: String,
+    /// Workstream ID.
+    pub workstream_id: String,
+    /// Workstream title.
+    pub workstream_title: String,
+    /// Session ID.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Message role.
+    pub role: String,
+    /// Message timestamp.
+    pub timestamp: String,
+    /// Excerpt with matched terms wrapped in `**`.
+    pub snippet: String,
+    /// Relevance score; higher is better.
+    pub score: f64,
+}
+
+/// Match counts per facet value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageSearchFacets {
+    /// Matches per role.
+    #[serde(default)]
+    pub roles: std::collections::BTreeMap<String, usize>,
+    /// Matches per workstream ID.
+    #[serde(default)]
+    pub workstreams: std::collections::BTreeMap<String, usize>,
+}
+
+/// Response for message history search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchResponse {
+    /// Query executed.
+    pub query: String,
+    /// Hits ordered by relevance.
+    pub hits: Vec<MessageSearchHit>,
+    /// Total matches.
+    pub total: usize,
+    /// Page size.
+    pub limit: usize,
+    /// Hits skipped.
+    pub offset: usize,
+    /// Match counts per role and workstream.
+    #[serde(default)]
+    pub facets: MessageSearchFacets,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tasks
 // ─────────────────────────────────────────────────────────────────────────────
@@ -686,6 +1083,9 @@ pub struct TaskDetail {
     /// Error message.
     #[serde(default)]
     pub error: Option<String>,
+    /// Result payload (e.g. workflow run output).
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
 }
 
 /// Response for list tasks.
@@ -697,6 +1097,158 @@ pub struct ListTasksResponse {
     pub total: usize,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Workflows
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request to create or replace a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveWorkflowRequest {
+    /// Workflow definition as TOML.
+    pub definition: String,
+}
+
+/// Workflow summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSummary {
+    /// Workflow name.
+    pub name: String,
+    /// Workflow description.
+    #[serde(default)]
+    pub description: String,
+    /// Whether the workflow is registered with the engine.
+    pub registered: bool,
+    /// Path to the TOML file, if the workflow is backed by one.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Number of tasks.
+    pub task_count: usize,
+}
+
+/// Cron schedule for a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSchedule {
+    /// Schedule ID.
+    pub id: String,
+    /// Scheduled workflow.
+    pub workflow_name: String,
+    /// Cron expression.
+    pub cron_expr: String,
+    /// Whether the schedule is enabled.
+    pub enabled: bool,
+}
+
+/// Workflow details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDetail {
+    /// Summary fields.
+    #[serde(flatten)]
+    pub workflow: WorkflowSummary,
+    /// Parsed definition.
+    #[serde(default)]
+    pub definition: Option<serde_json::Value>,
+    /// Raw TOML source.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Schedules for this workflow.
+    #[serde(default)]
+    pub schedules: Vec<WorkflowSchedule>,
+}
+
+/// Response for list workflows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListWorkflowsResponse {
+    /// Known workflows.
+    pub workflows: Vec<WorkflowSummary>,
+    /// All cron schedules.
+    #[serde(default)]
+    pub schedules: Vec<WorkflowSchedule>,
+}
+
+/// Request to run a workflow.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunWorkflowRequest {
+    /// Input exposed to tasks as the `input` context key.
+    #[serde(default)]
+    pub input: serde_json::Value,
+    /// Run in the background and return a task ID.
+    #[serde(default, rename = "async")]
+    pub run_async: bool,
+}
+
+/// Result of a synchronous workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunWorkflowResponse {
+    /// Execution ID.
+    pub execution_id: String,
+    /// Final status.
+    pub status: String,
+    /// Error message, if the run failed.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Final context output.
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+}
+
+/// Response for an asynchronous workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunWorkflowAcceptedResponse {
+    /// Task ID to poll via the tasks API.
+    pub task_id: String,
+    /// Workflow name.
+    pub workflow: String,
+}
+
+/// Request to schedule a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleWorkflowRequest {
+    /// Cron expression.
+    pub cron: String,
+    /// IANA timezone.
+    pub timezone: String,
+}
+
+/// Request to create or replace a runtime catalog entry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SaveRuntimeRequest {
+    /// Runtime description.
+    #[serde(default)]
+    pub description: String,
+    /// Rust source to compile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Path to a pre-compiled `.wasm` module on the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_path: Option<String>,
+}
+
+/// Runtime catalog entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeInfo {
+    /// Runtime name.
+    pub name: String,
+    /// Runtime description.
+    #[serde(default)]
+    pub description: String,
+    /// Catalog path.
+    pub path: String,
+    /// Category (builtin, custom).
+    pub category: String,
+    /// Absolute path to the module.
+    #[serde(default)]
+    pub resolved_path: Option<String>,
+    /// Whether the compiled module exists.
+    pub wasm_exists: bool,
+}
+
+/// Response for list runtimes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRuntimesResponse {
+    /// Catalog entries.
+    pub runtimes: Vec<RuntimeInfo>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // MCP
 // ─────────────────────────────────────────────────────────────────────────────
@@ -775,6 +1327,137 @@ pub struct ListToolsResponse {
     pub tools: Vec<McpToolInfo>,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Plugins
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request to subscribe to a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallPluginRequest {
+    /// GitHub `owner/repo` shorthand, a git URL, or an absolute path on the server.
+    pub source: String,
+    /// Git branch, tag or commit (default: `main`).
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Save to the project's `.arawn/plugins.json` instead of the global one.
+    #[serde(default)]
+    pub project: bool,
+}
+
+/// Declared vs discovered capabilities of a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCapabilities {
+    /// Whether the manifest declares skills.
+    pub skills_declared: bool,
+    /// Skills found on disk.
+    pub skills_found: usize,
+    /// Whether the manifest declares agents.
+    pub agents_declared: bool,
+    /// Agents found on disk.
+    pub agents_found: usize,
+    /// Whether the manifest declares hooks.
+    pub hooks_declared: bool,
+    /// Hook config files found.
+    pub hooks_found: usize,
+    /// Whether the manifest declares commands.
+    pub commands_declared: bool,
+    /// Commands found on disk.
+    pub commands_found: usize,
+    /// Capabilities found but not declared, or declared but missing.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Plugin info.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// Subscription ID, or `name@local` for plugins in plugin directories.
+    pub id: String,
+    /// Plugin name.
+    pub name: String,
+    /// Plugin version.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Plugin description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Where the plugin comes from (directory, github, url, local).
+    pub source: String,
+    /// GitHub `owner/repo` of a GitHub subscription.
+    #[serde(default)]
+    pub repo: Option<String>,
+    /// Clone URL of a URL subscription.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Requested git ref of a subscription.
+    #[serde(rename = "ref", default)]
+    pub git_ref: Option<String>,
+    /// Locked commit of the subscription checkout.
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// Plugin directory on the server.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Whether the plugin is enabled.
+    pub enabled: bool,
+    /// Whether the plugin is loaded into the running agent.
+    pub loaded: bool,
+    /// Declared vs discovered capabilities.
+    #[serde(default)]
+    pub capabilities: Option<PluginCapabilities>,
+    /// Skill names (invoked as `/plugin:skill`).
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Subagent names.
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Number of hooks.
+    #[serde(default)]
+    pub hooks: usize,
+    /// MCP servers, namespaced as `plugin:server`.
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    /// Why the plugin could not be loaded or synced.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Response for list plugins.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListPluginsResponse {
+    /// Plugins, sorted by name.
+    pub plugins: Vec<PluginInfo>,
+}
+
+/// Outcome of syncing one subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginSyncResult {
+    /// Subscription ID.
+    pub id: String,
+    /// What happened (cloned, updated, skipped, clone_failed, update_failed,
+    /// integrity_failed).
+    pub action: String,
+    /// Error message for failed syncs.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Response for syncing one plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPluginResponse {
+    /// What the sync did.
+    pub result: PluginSyncResult,
+    /// The plugin after reloading.
+    pub plugin: PluginInfo,
+}
+
+/// Response for syncing all subscriptions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPluginsResponse {
+    /// One result per enabled subscription.
+    pub results: Vec<PluginSyncResult>,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Health
 // ─────────────────────────────────────────────────────────────────────────────
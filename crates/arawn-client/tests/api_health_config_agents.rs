@@ -140,6 +140,34 @@ async fn test_config_get() {
     assert!(resp.auth_required);
 }
 
+#[tokio::test]
+async fn test_config_patch() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/api/v1/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "changed": ["server.api_rpm", "server.port"],
+            "applied": ["server.api_rpm"],
+            "restart_required": ["server.port"],
+            "persisted": ["/etc/arawn/arawn.toml"],
+            "warnings": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let resp = client
+        .config()
+        .patch(&serde_json::json!({ "server": { "api_rpm": 30, "port": 9000 } }))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.applied, vec!["server.api_rpm"]);
+    assert_eq!(resp.restart_required, vec!["server.port"]);
+}
+
 #[tokio::test]
 async fn test_config_get_auth_header() {
     let server = MockServer::start().await;
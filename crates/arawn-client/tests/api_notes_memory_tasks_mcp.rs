@@ -1,5 +1,5 @@
 use arawn_client::ArawnClient;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{body_partial_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn test_client(uri: &str) -> ArawnClient {
@@ -445,6 +445,149 @@ async fn test_memory_search_empty() {
     assert!(resp.results.is_empty());
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Graph API
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_graph_entity() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/memory/graph/entities/rust"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "entity": {"id": "rust", "label": "Language", "properties": {"name": "Rust"}},
+            "relationships": [
+                {"source": "rust", "target": "mozilla", "type": "CREATED_BY", "properties": {}}
+            ],
+            "memories": [
+                {"id": "mem-1", "content_type": "fact", "content": "Rust was created at Mozilla"}
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let detail = client.graph().entity("rust").await.unwrap();
+
+    assert_eq!(detail.entity.properties["name"], "Rust");
+    assert_eq!(detail.relationships[0].rel_type, "CREATED_BY");
+    assert_eq!(detail.memories[0].id, "mem-1");
+}
+
+#[tokio::test]
+async fn test_graph_neighbors_and_query() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/memory/graph/entities/rust/neighbors"))
+        .and(query_param("hops", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "nodes": [
+                {"id": "rust", "label": "Language"},
+                {"id": "mozilla", "label": "Organization"}
+            ],
+            "relationships": [{"source": "rust", "target": "mozilla", "type": "CREATED_BY"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/memory/graph/query"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "columns": ["id"],
+            "rows": [{"id": "rust"}],
+            "row_count": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let subgraph = client.graph().neighbors("rust", 2).await.unwrap();
+    assert_eq!(subgraph.nodes.len(), 2);
+    assert!(!subgraph.truncated);
+
+    let result = client
+        .graph()
+        .query("MATCH (n:Language) RETURN n.id AS id")
+        .await
+        .unwrap();
+    assert_eq!(result.rows[0]["id"], "rust");
+}
+
+#[tokio::test]
+async fn test_graph_export() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/memory/graph/export"))
+        .and(query_param("format", "dot"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("digraph arawn {\n}\n"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let dot = client
+        .graph()
+        .export(arawn_client::GraphExportFormat::Dot)
+        .await
+        .unwrap();
+    assert!(dot.starts_with("digraph arawn"));
+}
+
+#[tokio::test]
+async fn test_search_messages_with_options() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/search/messages"))
+        .and(query_param("q", "certificates"))
+        .and(query_param("role", "user,assistant"))
+        .and(query_param("limit", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "query": "certificates",
+            "hits": [
+                {
+                    "message_id": "msg-1",
+                    "workstream_id": "ws-1",
+                    "workstream_title": "Ops",
+                    "session_id": "sess-1",
+                    "role": "user",
+                    "timestamp": "2026-03-01T10:00:00Z",
+                    "snippet": "rotate the **certificates**",
+                    "score": 2.5
+                }
+            ],
+            "total": 1,
+            "limit": 5,
+            "offset": 0,
+            "facets": { "roles": { "user": 1 }, "workstreams": { "ws-1": 1 } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let resp = client
+        .search()
+        .messages_with_options(arawn_client::MessageSearchQuery {
+            q: "certificates".to_string(),
+            role: Some("user,assistant".to_string()),
+            limit: Some(5),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.hits[0].workstream_title, "Ops");
+    assert_eq!(resp.hits[0].session_id.as_deref(), Some("sess-1"));
+    assert_eq!(resp.facets.roles["user"], 1);
+}
+
 #[tokio::test]
 async fn test_memory_store() {
     let server = MockServer::start().await;
@@ -1052,3 +1195,114 @@ async fn test_mcp_disconnect() {
 
     assert!(result.is_ok());
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Plugins API
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn plugin_json(enabled: bool) -> serde_json::Value {
+    serde_json::json!({
+        "id": "github/acme-journal",
+        "name": "journal",
+        "source": "github",
+        "repo": "acme/journal",
+        "ref": "main",
+        "enabled": enabled,
+        "loaded": enabled,
+        "capabilities": {
+            "skills_declared": true, "skills_found": 1,
+            "agents_declared": false, "agents_found": 0,
+            "hooks_declared": false, "hooks_found": 0,
+            "commands_declared": false, "commands_found": 0,
+            "warnings": []
+        },
+        "skills": ["entry"],
+        "agents": [],
+        "hooks": 0,
+        "mcp_servers": []
+    })
+}
+
+#[tokio::test]
+async fn test_plugins_list_and_install() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/plugins"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "plugins": [plugin_json(true), {
+                "id": "broken@local",
+                "name": "broken",
+                "source": "directory",
+                "enabled": true,
+                "loaded": false,
+                "skills": [], "agents": [], "hooks": 0, "mcp_servers": [],
+                "error": "invalid manifest"
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/plugins"))
+        .and(body_partial_json(
+            serde_json::json!({"source": "acme/journal", "ref": "v1"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(plugin_json(true)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let list = client.plugins().list().await.unwrap();
+    assert_eq!(list.plugins.len(), 2);
+    assert_eq!(list.plugins[0].git_ref.as_deref(), Some("main"));
+    assert_eq!(
+        list.plugins[0].capabilities.as_ref().unwrap().skills_found,
+        1
+    );
+    assert_eq!(list.plugins[1].error.as_deref(), Some("invalid manifest"));
+
+    let plugin = client
+        .plugins()
+        .install(arawn_client::InstallPluginRequest {
+            source: "acme/journal".to_string(),
+            git_ref: Some("v1".to_string()),
+            project: false,
+        })
+        .await
+        .unwrap();
+    assert_eq!(plugin.skills, vec!["entry"]);
+}
+
+#[tokio::test]
+async fn test_plugins_encode_ids() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/plugins/github%2Facme-journal/disable"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(plugin_json(false)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v1/plugins/github%2Facme-journal"))
+        .and(query_param("delete_cache", "true"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let plugin = client
+        .plugins()
+        .disable("github/acme-journal")
+        .await
+        .unwrap();
+    assert!(!plugin.enabled);
+    client
+        .plugins()
+        .remove_and_delete_cache("github/acme-journal")
+        .await
+        .unwrap();
+}
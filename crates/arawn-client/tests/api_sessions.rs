@@ -1,6 +1,6 @@
 use arawn_client::{
-    ArawnClient, CreateSessionRequest, Error, ListSessionsResponse, SessionDetail,
-    SessionMessagesResponse, UpdateSessionRequest,
+    ArawnClient, CreateSessionRequest, Error, ForkSessionRequest, ListSessionsResponse,
+    SessionDetail, SessionMessagesResponse, UpdateSessionRequest,
 };
 use serde_json::json;
 use wiremock::matchers::{body_json, header, method, path};
@@ -507,6 +507,46 @@ async fn test_sessions_messages_empty() {
     assert!(resp.messages.is_empty());
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Fork session
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_sessions_fork() {
+    let server = MockServer::start().await;
+    let client = test_client(&server.uri());
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/sessions/sess-1/fork"))
+        .and(body_json(json!({ "turns": 2 })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "session_id": "sess-2",
+            "workstream_id": "ws-1",
+            "parent_session_id": "sess-1",
+            "forked_from_message_id": "msg-4",
+            "messages_copied": 4
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = client
+        .sessions()
+        .fork(
+            "sess-1",
+            ForkSessionRequest {
+                turns: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.session_id, "sess-2");
+    assert_eq!(resp.parent_session_id, "sess-1");
+    assert_eq!(resp.forked_from_message_id.as_deref(), Some("msg-4"));
+    assert_eq!(resp.messages_copied, 4);
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Auth / unauthorized
 // ─────────────────────────────────────────────────────────────────────────────
@@ -181,6 +181,7 @@ async fn test_workstreams_create() {
             title: "New Project".to_string(),
             default_model: Some("claude-sonnet-4-20250514".to_string()),
             tags: vec!["rust".to_string()],
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -231,6 +232,49 @@ async fn test_workstreams_update() {
     assert_eq!(ws.summary.as_deref(), Some("New summary"));
 }
 
+#[tokio::test]
+async fn test_workstreams_update_settings() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/api/v1/workstreams/ws-42"))
+        .and(body_json(json!({
+            "settings": {"profile": "fast", "denied_tools": ["shell"]}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "ws-42",
+            "title": "Docs",
+            "state": "active",
+            "is_scratch": false,
+            "created_at": "2026-03-01T00:00:00Z",
+            "updated_at": "2026-03-08T00:00:00Z",
+            "settings": {"profile": "fast", "denied_tools": ["shell"], "memory_scope": "all"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let ws = client
+        .workstreams()
+        .update(
+            "ws-42",
+            arawn_client::UpdateWorkstreamRequest {
+                settings: Some(arawn_client::WorkstreamSettings {
+                    profile: Some("fast".to_string()),
+                    denied_tools: vec!["shell".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(ws.settings.profile.as_deref(), Some("fast"));
+    assert_eq!(ws.settings.memory_scope.as_deref(), Some("all"));
+}
+
 #[tokio::test]
 async fn test_workstreams_delete() {
     let server = MockServer::start().await;
@@ -441,6 +485,183 @@ async fn test_workstreams_promote_scratch() {
     assert!(!ws.is_scratch);
 }
 
+#[tokio::test]
+async fn test_workstreams_merge() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/workstreams/ws-a/merge"))
+        .and(body_json(
+            json!({ "target": "ws-b", "on_conflict": "skip" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "workstream": {
+                "id": "ws-b",
+                "title": "Target",
+                "summary": null,
+                "state": "active",
+                "is_scratch": false,
+                "created_at": "2026-03-08T00:00:00Z",
+                "updated_at": "2026-03-08T00:00:00Z"
+            },
+            "session_ids": ["s1", "s2"],
+            "messages_moved": 12,
+            "files_moved": 3,
+            "files_renamed": 0,
+            "files_overwritten": 0,
+            "files_skipped": 1,
+            "notes_retagged": 2
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let result = client
+        .workstreams()
+        .merge(
+            "ws-a",
+            arawn_client::MergeWorkstreamRequest {
+                target: "ws-b".to_string(),
+                on_conflict: Some("skip".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.workstream.id, "ws-b");
+    assert_eq!(result.session_ids.len(), 2);
+    assert_eq!(result.messages_moved, 12);
+    assert_eq!(result.files_skipped, 1);
+}
+
+#[tokio::test]
+async fn test_workstreams_split() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/workstreams/ws-a/split"))
+        .and(body_json(
+            json!({ "session_ids": ["s2"], "title": "Spun off" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "workstream": {
+                "id": "ws-new",
+                "title": "Spun off",
+                "summary": null,
+                "state": "active",
+                "is_scratch": false,
+                "created_at": "2026-03-08T00:00:00Z",
+                "updated_at": "2026-03-08T00:00:00Z"
+            },
+            "session_ids": ["s2"],
+            "messages_moved": 4
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let result = client
+        .workstreams()
+        .split(
+            "ws-a",
+            arawn_client::SplitWorkstreamRequest {
+                session_ids: vec!["s2".to_string()],
+                title: "Spun off".to_string(),
+                tags: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.workstream.id, "ws-new");
+    assert_eq!(result.messages_moved, 4);
+}
+
+#[tokio::test]
+async fn test_workstreams_create_from_template() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/workstreams"))
+        .and(body_json(json!({
+            "title": "Fusion",
+            "template": "research",
+            "params": { "topic": "tokamaks" }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": "ws-fusion",
+            "title": "Fusion",
+            "summary": null,
+            "state": "active",
+            "is_scratch": false,
+            "created_at": "2026-03-08T00:00:00Z",
+            "updated_at": "2026-03-08T00:00:00Z",
+            "tags": ["research"],
+            "template": {
+                "name": "research",
+                "files": 2,
+                "notes": 1,
+                "workflows": ["fusion-digest"],
+                "repos": []
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let ws = client
+        .workstreams()
+        .create(arawn_client::CreateWorkstreamRequest {
+            title: "Fusion".to_string(),
+            template: Some("research".to_string()),
+            params: [("topic".to_string(), "tokamaks".to_string())].into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let applied = ws.template.unwrap();
+    assert_eq!(applied.name, "research");
+    assert_eq!(applied.files, 2);
+    assert_eq!(applied.workflows, vec!["fusion-digest"]);
+    assert!(applied.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn test_templates_list() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/templates"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "templates": [
+                {
+                    "name": "research",
+                    "description": "Research project",
+                    "params": [{ "name": "topic", "required": true }]
+                },
+                {
+                    "name": "github:repo-study",
+                    "plugin": "github",
+                    "params": []
+                }
+            ]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = test_client(&server.uri());
+    let result = client.templates().list().await.unwrap();
+
+    assert_eq!(result.templates.len(), 2);
+    assert!(result.templates[0].params[0].required);
+    assert_eq!(result.templates[1].plugin.as_deref(), Some("github"));
+}
+
 #[tokio::test]
 async fn test_workstreams_list_server_error() {
     let server = MockServer::start().await;
@@ -495,6 +716,7 @@ async fn test_workstreams_create_auth_header() {
             title: "Auth Test".to_string(),
             default_model: None,
             tags: vec![],
+            ..Default::default()
         })
         .await
         .unwrap();
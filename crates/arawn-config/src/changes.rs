@@ -0,0 +1,576 @@
+//! Validating, comparing and saving config changes.
+//!
+//! Used when the config changes while the server runs: the new config is
+//! [validated](ArawnConfig::validate), compared with the running one as a
+//! list of dotted keys ([`ConfigDiff`]), and written back with
+//! [`save_layered`], which puts each changed section in the config file
+//! layer that defines it.
+//!
+//! Keys use the JSON field names of [`ArawnConfig`], so named LLM configs
+//! appear as `llm_profiles.<name>` (the `[llm.<name>]` tables in TOML).
+
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+use crate::discovery::{ConfigSource, load_config_file, save_config};
+use crate::{ArawnConfig, ConfigError, McpTransportType, Result};
+
+/// Sections whose top-level key holds one entry per name. Layers are merged
+/// entry by entry for these, so changes are saved per entry too.
+const KEYED_SECTIONS: &[&str] = &["llm_profiles", "agent"];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Validation
+// ─────────────────────────────────────────────────────────────────────────────
+
+impl ArawnConfig {
+    /// Check the config for mistakes that would only surface at runtime.
+    ///
+    /// Reports every problem found, as `key: message`, in a
+    /// [`ConfigError::Invalid`].
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        for (name, llm) in &self.llm_profiles {
+            if llm.backend.is_none() {
+                errors.push(format!("llm_profiles.{name}: missing 'backend'"));
+            }
+            if llm.model.is_none() {
+                errors.push(format!("llm_profiles.{name}: missing 'model'"));
+            }
+        }
+
+        for (agent, profile) in &self.agent {
+            if let Some(ref llm) = profile.llm
+                && !self.llm_profiles.contains_key(llm)
+            {
+                errors.push(format!("agent.{agent}.llm: unknown LLM profile '{llm}'"));
+            }
+        }
+
+        if let Some(routing) = self.routing.as_ref().filter(|r| r.enabled) {
+            let known = |name: &str| name == "default" || self.llm_profiles.contains_key(name);
+            let mut check = |key: String, name: &str| {
+                if !known(name) {
+                    errors.push(format!("{key}: unknown LLM profile '{name}'"));
+                }
+            };
+            if let Some(ref default) = routing.default {
+                check("routing.default".to_string(), default);
+            }
+            for (i, rule) in routing.rules.iter().enumerate() {
+                check(format!("routing.rules[{i}].profile"), &rule.profile);
+            }
+            if let Some(ref classifier) = routing.classifier {
+                check("routing.classifier.profile".to_string(), &classifier.profile);
+                for label in &classifier.labels {
+                    check(
+                        format!("routing.classifier.labels.{}", label.name),
+                        &label.profile,
+                    );
+                }
+            }
+        }
+
+        if let Some(ref server) = self.server {
+            if format!("{}:{}", server.bind, server.port)
+                .parse::<SocketAddr>()
+                .is_err()
+            {
+                errors.push(format!("server.bind: '{}' is not an IP address", server.bind));
+            }
+            if server.rate_limiting && server.api_rpm == 0 {
+                errors.push("server.api_rpm: must be greater than 0".to_string());
+            }
+        }
+
+        if let Some(ref memory) = self.memory
+            && !(0.0..=1.0).contains(&memory.recall.threshold)
+        {
+            errors.push("memory.recall.threshold: must be between 0.0 and 1.0".to_string());
+        }
+
+        if let Some(ref tools) = self.tools {
+            let output = &tools.output;
+            let limits = [
+                ("max_size_bytes", Some(output.max_size_bytes)),
+                ("shell", output.shell),
+                ("file_read", output.file_read),
+                ("web_fetch", output.web_fetch),
+                ("search", output.search),
+            ];
+            for (key, limit) in limits {
+                if limit == Some(0) {
+                    errors.push(format!("tools.output.{key}: must be greater than 0"));
+                }
+            }
+        }
+
+        if let Some(ref mcp) = self.mcp {
+            let mut names = HashSet::new();
+            for server in &mcp.servers {
+                if server.name.is_empty() {
+                    errors.push("mcp.servers: server without a name".to_string());
+                    continue;
+                }
+                if !names.insert(server.name.as_str()) {
+                    errors.push(format!("mcp.servers.{}: duplicate name", server.name));
+                }
+                match server.transport {
+                    McpTransportType::Http if server.url.is_none() => {
+                        errors.push(format!("mcp.servers.{}: http server needs a url", server.name))
+                    }
+                    McpTransportType::Stdio if server.command.is_empty() => errors.push(format!(
+                        "mcp.servers.{}: stdio server needs a command",
+                        server.name
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Diff
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The keys that differ between two configs.
+///
+/// Keys are dotted paths to the values that changed (`server.api_rpm`,
+/// `llm_profiles.fast.model`). Added or removed entries are reported at the
+/// entry (`llm_profiles.fast`), and lists are compared as a whole
+/// (`mcp.servers`). Sections that are absent compare equal to their
+/// defaults where the server treats them that way, so adding `[server]`
+/// with one changed value reports only that value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    keys: Vec<String>,
+}
+
+impl ConfigDiff {
+    /// Compare `old` with `new`.
+    pub fn between(old: &ArawnConfig, new: &ArawnConfig) -> Self {
+        let mut keys = Vec::new();
+        diff_values(
+            &mut String::new(),
+            &to_json(&with_default_sections(old)),
+            &to_json(&with_default_sections(new)),
+            &mut keys,
+        );
+        keys.sort();
+        Self { keys }
+    }
+
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The changed keys, sorted.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Whether `key` or anything below it changed.
+    pub fn touches(&self, key: &str) -> bool {
+        self.keys.iter().any(|k| is_under(k, key))
+    }
+
+    /// The units changes are saved in: top-level sections, or single
+    /// entries of `llm_profiles` and `agent`.
+    fn units(&self) -> Vec<String> {
+        let mut units: Vec<String> = self.keys.iter().map(|k| unit_of(k)).collect();
+        units.dedup();
+        units
+    }
+}
+
+/// Whether `key` is `parent` or a key below it.
+pub fn is_under(key: &str, parent: &str) -> bool {
+    key == parent
+        || key
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+fn unit_of(key: &str) -> String {
+    let mut parts = key.splitn(3, '.');
+    let section = parts.next().unwrap_or_default();
+    match parts.next() {
+        Some(entry) if KEYED_SECTIONS.contains(&section) => format!("{section}.{entry}"),
+        _ => section.to_string(),
+    }
+}
+
+/// Fill in sections the server reads with `unwrap_or_default()`, so a
+/// missing section and an explicit default one compare equal.
+fn with_default_sections(config: &ArawnConfig) -> ArawnConfig {
+    let mut full = ArawnConfig {
+        usage: Some(Default::default()),
+        server: Some(Default::default()),
+        telemetry: Some(Default::default()),
+        webhooks: Some(Default::default()),
+        embedding: Some(Default::default()),
+        pipeline: Some(Default::default()),
+        memory: Some(Default::default()),
+        plugins: Some(Default::default()),
+        mcp: Some(Default::default()),
+        workstream: Some(Default::default()),
+        session: Some(Default::default()),
+        tools: Some(Default::default()),
+        paths: Some(Default::default()),
+        ..Default::default()
+    };
+    full.merge(config.clone());
+    full
+}
+
+fn to_json(config: &ArawnConfig) -> Value {
+    // Every map in the config is keyed by strings, so this can't fail
+    serde_json::to_value(config).unwrap_or(Value::Null)
+}
+
+fn diff_values(path: &mut String, old: &Value, new: &Value, keys: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let names: HashSet<&String> = old.keys().chain(new.keys()).collect();
+            for name in names {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(name);
+                diff_values(
+                    path,
+                    old.get(name).unwrap_or(&Value::Null),
+                    new.get(name).unwrap_or(&Value::Null),
+                    keys,
+                );
+                path.truncate(len);
+            }
+        }
+        (old, new) if old != new => keys.push(path.clone()),
+        _ => {}
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Layered save
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Load the config layers in `sources` again, lowest precedence first.
+///
+/// Unlike discovery, a layer that fails to parse is an error rather than a
+/// warning, so a half-edited file doesn't silently drop its settings.
+pub fn reload_layers(sources: &[ConfigSource]) -> Result<ArawnConfig> {
+    let mut config = ArawnConfig::new();
+    for source in sources {
+        if source.path.is_file() {
+            config.merge(load_config_file(&source.path)?);
+        }
+    }
+    Ok(config)
+}
+
+/// Write the changes in `diff` to the layered config files.
+///
+/// `config` is the new merged config and `sources` the layers it was loaded
+/// from, lowest precedence first. Each changed section (or `llm_profiles` /
+/// `agent` entry) is written to the highest layer that already defines it,
+/// so it still wins when the layers are merged again; new sections go to
+/// the first layer that exists (or the first candidate when none does).
+/// Removed sections are removed from every layer. Returns the files written.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let diff = ConfigDiff::between(&loaded.config, &new_config);
+/// let written = save_layered(&loaded.sources, &diff, &new_config)?;
+/// ```
+pub fn save_layered(
+    sources: &[ConfigSource],
+    diff: &ConfigDiff,
+    config: &ArawnConfig,
+) -> Result<Vec<PathBuf>> {
+    if sources.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut layers = Vec::with_capacity(sources.len());
+    for source in sources {
+        let value = if source.path.is_file() {
+            Some(to_json(&load_config_file(&source.path)?))
+        } else {
+            None
+        };
+        layers.push(value);
+    }
+    let primary = layers.iter().position(Option::is_some).unwrap_or(0);
+
+    let new = to_json(config);
+    let mut dirty = vec![false; layers.len()];
+    for unit in diff.units() {
+        let value = lookup(&new, &unit).filter(|v| !v.is_null()).cloned();
+        let defined_in: Vec<usize> = layers
+            .iter()
+            .enumerate()
+            .filter(|(_, layer)| {
+                layer
+                    .as_ref()
+                    .and_then(|l| lookup(l, &unit))
+                    .is_some_and(|v| !v.is_null())
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        match value {
+            Some(value) => {
+                let target = defined_in.last().copied().unwrap_or(primary);
+                let layer = layers[target].get_or_insert_with(|| to_json(&ArawnConfig::new()));
+                set(layer, &unit, Some(value));
+                dirty[target] = true;
+            }
+            None => {
+                for i in defined_in {
+                    if let Some(layer) = layers[i].as_mut() {
+                        set(layer, &unit, None);
+                        dirty[i] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut written = Vec::new();
+    for ((source, layer), dirty) in sources.iter().zip(layers).zip(dirty) {
+        let Some(layer) = layer.filter(|_| dirty) else {
+            continue;
+        };
+        let layer: ArawnConfig = serde_json::from_value(layer)
+            .map_err(|e| ConfigError::Other(format!("failed to rebuild config layer: {e}")))?;
+        save_config(&layer, &source.path)?;
+        written.push(source.path.clone());
+    }
+    Ok(written)
+}
+
+fn lookup<'a>(value: &'a Value, unit: &str) -> Option<&'a Value> {
+    unit.split('.').try_fold(value, |v, part| v.get(part))
+}
+
+/// Set (or with `None`, remove) a unit in a layer's JSON.
+fn set(layer: &mut Value, unit: &str, value: Option<Value>) {
+    let Some((section, entry)) = unit.split_once('.') else {
+        if let Some(obj) = layer.as_object_mut() {
+            obj.insert(unit.to_string(), value.unwrap_or(Value::Null));
+        }
+        return;
+    };
+    let Some(obj) = layer.as_object_mut() else {
+        return;
+    };
+    let map = obj
+        .entry(section.to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !map.is_object() {
+        *map = Value::Object(Map::new());
+    }
+    if let Some(map) = map.as_object_mut() {
+        match value {
+            Some(value) => {
+                map.insert(entry.to_string(), value);
+            }
+            None => {
+                map.remove(entry);
+            }
+        }
+    }
+}
+
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn parse(toml: &str) -> ArawnConfig {
+        ArawnConfig::from_toml(toml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let config = parse(
+            r#"
+            [llm.fast]
+            model = "small"
+
+            [agent.default]
+            llm = "missing"
+
+            [routing]
+            enabled = true
+            default = "nope"
+
+            [server]
+            bind = "not an ip"
+            api_rpm = 0
+
+            [[mcp.servers]]
+            name = "remote"
+            transport = "http"
+            "#,
+        );
+        let Err(ConfigError::Invalid(errors)) = config.validate() else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(errors.len(), 6, "{errors:?}");
+        assert!(errors.contains(&"llm_profiles.fast: missing 'backend'".to_string()));
+        assert!(errors.iter().any(|e| e.starts_with("agent.default.llm")));
+        assert!(errors.iter().any(|e| e.starts_with("routing.default")));
+        assert!(errors.iter().any(|e| e.starts_with("server.bind")));
+        assert!(errors.iter().any(|e| e.starts_with("server.api_rpm")));
+        assert!(errors.iter().any(|e| e.starts_with("mcp.servers.remote")));
+
+        assert!(ArawnConfig::new().validate().is_ok());
+    }
+
+    #[test]
+    fn test_diff_reports_changed_keys() {
+        let old = parse(
+            r#"
+            [llm]
+            backend = "groq"
+            model = "a"
+
+            [llm.fast]
+            backend = "groq"
+            model = "small"
+            "#,
+        );
+        let new = parse(
+            r#"
+            [llm]
+            backend = "groq"
+            model = "b"
+
+            [llm.quality]
+            backend = "anthropic"
+            model = "big"
+
+            [server]
+            api_rpm = 30
+            "#,
+        );
+
+        let diff = ConfigDiff::between(&old, &new);
+        assert_eq!(
+            diff.keys(),
+            [
+                "llm.model",
+                "llm_profiles.fast",
+                "llm_profiles.quality",
+                "server.api_rpm"
+            ]
+        );
+        assert!(diff.touches("server"));
+        assert!(!diff.touches("serve"));
+        assert!(ConfigDiff::between(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_treats_missing_sections_as_defaults() {
+        let explicit = parse(
+            r#"
+            [tools.output]
+            max_size_bytes = 102400
+            "#,
+        );
+        assert!(ConfigDiff::between(&ArawnConfig::new(), &explicit).is_empty());
+    }
+
+    #[test]
+    fn test_save_layered_writes_to_defining_layer() {
+        let dir = TempDir::new().unwrap();
+        let user = dir.path().join("config.toml");
+        let project = dir.path().join("arawn.toml");
+        fs::write(
+            &user,
+            "[llm]\nbackend = \"groq\"\nmodel = \"a\"\n\n[server]\nport = 9000\n",
+        )
+        .unwrap();
+        fs::write(
+            &project,
+            "[server]\nport = 9100\n\n[llm.fast]\nbackend = \"groq\"\nmodel = \"small\"\n",
+        )
+        .unwrap();
+        let sources = vec![
+            ConfigSource {
+                path: user.clone(),
+                loaded: true,
+            },
+            ConfigSource {
+                path: project.clone(),
+                loaded: true,
+            },
+        ];
+
+        let old = reload_layers(&sources).unwrap();
+        assert_eq!(old.server.as_ref().unwrap().port, 9100);
+
+        let mut new = old.clone();
+        new.server.as_mut().unwrap().api_rpm = 30;
+        new.llm.as_mut().unwrap().model = Some("b".to_string());
+        new.llm_profiles.remove("fast");
+        new.memory = Some(Default::default());
+        new.memory.as_mut().unwrap().recall.limit = 9;
+
+        let diff = ConfigDiff::between(&old, &new);
+        let written = save_layered(&sources, &diff, &new).unwrap();
+        assert_eq!(written, vec![user.clone(), project.clone()]);
+
+        let user_layer = load_config_file(&user).unwrap();
+        let project_layer = load_config_file(&project).unwrap();
+        // [server] is defined by the project layer, which overrides the user one
+        assert_eq!(project_layer.server.as_ref().unwrap().api_rpm, 30);
+        assert_eq!(user_layer.server.as_ref().unwrap().port, 9000);
+        // [llm] only exists in the user layer; new sections go to the first layer
+        assert_eq!(user_layer.llm.unwrap().model.as_deref(), Some("b"));
+        assert_eq!(user_layer.memory.unwrap().recall.limit, 9);
+        assert!(project_layer.llm_profiles.is_empty());
+
+        let reloaded = reload_layers(&sources).unwrap();
+        assert!(ConfigDiff::between(&new, &reloaded).is_empty());
+    }
+
+    #[test]
+    fn test_save_layered_creates_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+        let sources = vec![ConfigSource {
+            path: path.clone(),
+            loaded: false,
+        }];
+        let mut new = ArawnConfig::new();
+        new.server = Some(Default::default());
+        new.server.as_mut().unwrap().rate_limiting = false;
+
+        let diff = ConfigDiff::between(&ArawnConfig::new(), &new);
+        assert_eq!(save_layered(&sources, &diff, &new).unwrap(), vec![path.clone()]);
+        assert!(!load_config_file(&path).unwrap().server.unwrap().rate_limiting);
+    }
+}
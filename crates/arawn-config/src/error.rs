@@ -56,6 +56,10 @@ pub enum ConfigError {
     #[error("failed to parse YAML config: {0}")]
     ParseYaml(String),
 
+    /// The config failed validation.
+    #[error("invalid config: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+
     /// Context not found.
     #[error("context '{0}' not found")]
     ContextNotFound(String),
@@ -14,6 +14,7 @@
 //! See ADR ARAWN-A-0001 for architectural decisions.
 
 pub mod age_crypto;
+pub mod changes;
 pub mod client;
 pub mod discovery;
 pub mod error;
@@ -23,6 +24,7 @@ pub mod secret_store;
 pub mod secrets;
 pub mod types;
 
+pub use changes::{ConfigDiff, reload_layers, save_layered};
 pub use client::{
     AuthConfig, ClientConfig, ClientDefaults, Context, client_config_path, load_client_config,
     load_client_config_from, save_client_config, save_client_config_to,
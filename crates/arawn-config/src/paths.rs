@@ -138,6 +138,18 @@ pub struct CleanupConfig {
     /// Default: 7 days
     pub scratch_cleanup_days: u32,
 
+    /// Number of production snapshots kept per workstream.
+    /// Default: 20
+    pub snapshot_keep_last: usize,
+
+    /// Snapshots older than this many days are pruned (the newest is kept).
+    /// Default: 30 days
+    pub snapshot_max_age_days: u32,
+
+    /// Snapshots kept for a workstream over its disk usage threshold.
+    /// Default: 3
+    pub snapshot_keep_under_pressure: usize,
+
     /// Dry run mode - log cleanup actions but don't actually delete.
     /// Default: false
     pub dry_run: bool,
@@ -147,6 +159,9 @@ impl Default for CleanupConfig {
     fn default() -> Self {
         Self {
             scratch_cleanup_days: 7,
+            snapshot_keep_last: 20,
+            snapshot_max_age_days: 30,
+            snapshot_keep_under_pressure: 3,
             dry_run: false,
         }
     }
@@ -213,6 +228,9 @@ mod tests {
     fn test_cleanup_config_defaults() {
         let config = CleanupConfig::default();
         assert_eq!(config.scratch_cleanup_days, 7);
+        assert_eq!(config.snapshot_keep_last, 20);
+        assert_eq!(config.snapshot_max_age_days, 30);
+        assert_eq!(config.snapshot_keep_under_pressure, 3);
         assert!(!config.dry_run);
     }
 
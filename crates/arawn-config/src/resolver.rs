@@ -4,7 +4,7 @@
 //! a given agent, handling cascading defaults and API key lookup.
 
 use crate::secrets::{self, SecretSource};
-use crate::{ArawnConfig, Backend, ConfigError, LlmConfig, Result};
+use crate::{ArawnConfig, Backend, ConfigError, LlmConfig, Result, ToolFormat};
 
 /// A fully resolved LLM configuration ready to construct a backend.
 ///
@@ -41,6 +41,8 @@ pub struct ResolvedLlm {
     pub retry_max: Option<u32>,
     /// Backoff delay between retries in milliseconds.
     pub retry_backoff_ms: Option<u64>,
+    /// Prompt-based tool calling format, if native tools are bypassed.
+    pub tool_format: Option<ToolFormat>,
 }
 
 impl std::fmt::Debug for ResolvedLlm {
@@ -54,6 +56,7 @@ impl std::fmt::Debug for ResolvedLlm {
             .field("resolved_from", &self.resolved_from)
             .field("retry_max", &self.retry_max)
             .field("retry_backoff_ms", &self.retry_backoff_ms)
+            .field("tool_format", &self.tool_format)
             .finish()
     }
 }
@@ -145,6 +148,7 @@ pub fn resolve_for_agent(config: &ArawnConfig, agent_name: &str) -> Result<Resol
         resolved_from,
         retry_max: llm_config.retry_max,
         retry_backoff_ms: llm_config.retry_backoff_ms,
+        tool_format: llm_config.tool_format,
     })
 }
 
@@ -429,6 +433,7 @@ api_key = "config-key"
             resolved_from: ResolvedFrom::GlobalDefault,
             retry_max: None,
             retry_backoff_ms: None,
+            tool_format: None,
         };
         let debug = format!("{:?}", resolved);
         assert!(
@@ -451,6 +456,7 @@ api_key = "config-key"
             resolved_from: ResolvedFrom::GlobalDefault,
             retry_max: None,
             retry_backoff_ms: None,
+            tool_format: None,
         };
         let debug = format!("{:?}", resolved);
         assert!(debug.contains("None"));
@@ -37,12 +37,24 @@ pub struct ArawnConfig {
     #[serde(default)]
     pub agent: HashMap<String, AgentProfileConfig>,
 
+    /// Per-request LLM routing across `llm_profiles`.
+    pub routing: Option<RoutingConfig>,
+
+    /// Token usage accounting, pricing and spend limits.
+    pub usage: Option<UsageConfig>,
+
     /// Server configuration.
     pub server: Option<ServerConfig>,
 
     /// Interaction logging configuration.
     pub logging: Option<LoggingConfig>,
 
+    /// Prometheus metrics and OpenTelemetry trace export.
+    pub telemetry: Option<TelemetryConfig>,
+
+    /// Outbound webhook delivery.
+    pub webhooks: Option<WebhooksConfig>,
+
     /// Embedding provider configuration.
     pub embedding: Option<EmbeddingConfig>,
 
@@ -141,6 +153,14 @@ impl ArawnConfig {
             self.agent.insert(name, config);
         }
 
+        if other.routing.is_some() {
+            self.routing = other.routing;
+        }
+
+        if other.usage.is_some() {
+            self.usage = other.usage;
+        }
+
         if other.server.is_some() {
             self.server = other.server;
         }
@@ -149,6 +169,14 @@ impl ArawnConfig {
             self.logging = other.logging;
         }
 
+        if other.telemetry.is_some() {
+            self.telemetry = other.telemetry;
+        }
+
+        if other.webhooks.is_some() {
+            self.webhooks = other.webhooks;
+        }
+
         if other.embedding.is_some() {
             self.embedding = other.embedding;
         }
@@ -278,8 +306,12 @@ struct RawConfig {
     llm: Option<RawLlmSection>,
     #[serde(default)]
     agent: HashMap<String, AgentProfileConfig>,
+    routing: Option<RoutingConfig>,
+    usage: Option<UsageConfig>,
     server: Option<ServerConfig>,
     logging: Option<LoggingConfig>,
+    telemetry: Option<TelemetryConfig>,
+    webhooks: Option<WebhooksConfig>,
     embedding: Option<EmbeddingConfig>,
     pipeline: Option<PipelineSection>,
     memory: Option<MemoryConfig>,
@@ -312,6 +344,8 @@ struct RawLlmSection {
     retry_backoff_ms: Option<u64>,
     /// Maximum context window size in tokens.
     max_context_tokens: Option<usize>,
+    /// Prompt-based tool calling format.
+    tool_format: Option<ToolFormat>,
 
     /// Named profiles are captured via flatten.
     #[serde(flatten)]
@@ -331,6 +365,7 @@ impl From<RawConfig> for ArawnConfig {
                         retry_max: section.retry_max,
                         retry_backoff_ms: section.retry_backoff_ms,
                         max_context_tokens: section.max_context_tokens,
+                        tool_format: section.tool_format,
                     })
                 } else {
                     None
@@ -344,8 +379,12 @@ impl From<RawConfig> for ArawnConfig {
             llm,
             llm_profiles,
             agent: raw.agent,
+            routing: raw.routing,
+            usage: raw.usage,
             server: raw.server,
             logging: raw.logging,
+            telemetry: raw.telemetry,
+            webhooks: raw.webhooks,
             embedding: raw.embedding,
             pipeline: raw.pipeline,
             memory: raw.memory,
@@ -374,6 +413,7 @@ impl From<ArawnConfig> for RawConfig {
                 retry_max: default.retry_max,
                 retry_backoff_ms: default.retry_backoff_ms,
                 max_context_tokens: default.max_context_tokens,
+                tool_format: default.tool_format,
                 profiles: config.llm_profiles,
             })
         } else {
@@ -383,8 +423,12 @@ impl From<ArawnConfig> for RawConfig {
         RawConfig {
             llm,
             agent: config.agent,
+            routing: config.routing,
+            usage: config.usage,
             server: config.server,
             logging: config.logging,
+            telemetry: config.telemetry,
+            webhooks: config.webhooks,
             embedding: config.embedding,
             pipeline: config.pipeline,
             memory: config.memory,
@@ -435,6 +479,9 @@ pub struct LlmConfig {
     /// Maximum context window size in tokens.
     /// If not specified, uses default for the model (see `effective_max_context_tokens`).
     pub max_context_tokens: Option<usize>,
+    /// Call tools through the prompt in this format instead of the provider's
+    /// native function calling (for models without tool support).
+    pub tool_format: Option<ToolFormat>,
 }
 
 impl LlmConfig {
@@ -465,6 +512,7 @@ impl LlmConfig {
 pub enum Backend {
     Anthropic,
     Openai,
+    Gemini,
     Groq,
     Ollama,
     Custom,
@@ -478,6 +526,7 @@ impl Backend {
         match self {
             Backend::Anthropic => "ANTHROPIC_API_KEY",
             Backend::Openai => "OPENAI_API_KEY",
+            Backend::Gemini => "GEMINI_API_KEY",
             Backend::Groq => "GROQ_API_KEY",
             Backend::Ollama => "OLLAMA_API_KEY",
             Backend::Custom => "LLM_API_KEY",
@@ -490,6 +539,7 @@ impl Backend {
         match self {
             Backend::Anthropic => "Anthropic",
             Backend::Openai => "OpenAI",
+            Backend::Gemini => "Gemini",
             Backend::Groq => "Groq",
             Backend::Ollama => "Ollama",
             Backend::Custom => "Custom",
@@ -504,6 +554,25 @@ impl std::fmt::Display for Backend {
     }
 }
 
+/// Prompt-based tool calling formats.
+///
+/// ```toml
+/// [llm.local]
+/// backend = "ollama"
+/// model = "hermes3"
+/// tool_format = "hermes"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolFormat {
+    /// Fenced ```` ```tool_call ```` blocks containing JSON.
+    Json,
+    /// `<tool_call><name>…</name><arguments>…</arguments></tool_call>` elements.
+    Xml,
+    /// Hermes / Qwen `<tool_call>{…}</tool_call>` with `<tools>` signatures.
+    Hermes,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Agent Configuration
 // ─────────────────────────────────────────────────────────────────────────────
@@ -537,6 +606,207 @@ pub struct AgentProfileConfig {
     pub max_iterations: Option<u32>,
     /// Maximum tokens per LLM response.
     pub max_tokens: Option<u32>,
+    /// Routing hint for this agent (a profile name or a hint matched by
+    /// `[routing]` rules). Only used when routing is enabled.
+    pub routing_hint: Option<String>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Routing Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Per-request routing across LLM profiles.
+///
+/// Rules are checked in order; the first match picks the profile. If none
+/// match, the optional classifier is asked, then `default` is used.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let config = ArawnConfig::from_toml(r#"
+///     [routing]
+///     enabled = true
+///     default = "quality"
+///
+///     [[routing.rules]]
+///     name = "short-chat"
+///     profile = "fast"
+///     max_prompt_tokens = 2000
+///     has_tools = false
+///
+///     [[routing.rules]]
+///     name = "tool-follow-up"
+///     profile = "fast"
+///     min_iteration = 2
+/// "#)?;
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingConfig {
+    /// Whether routing is active.
+    pub enabled: bool,
+    /// Profile used when nothing else matches. Defaults to the agent's
+    /// own LLM.
+    pub default: Option<String>,
+    /// Ordered routing rules.
+    pub rules: Vec<RoutingRuleConfig>,
+    /// Optional LLM classifier consulted when no rule matches.
+    pub classifier: Option<RoutingClassifierConfig>,
+}
+
+/// A single routing rule. Unset conditions match anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingRuleConfig {
+    /// Rule name, recorded in the routing reason.
+    pub name: Option<String>,
+    /// Profile to route to (key in `llm_profiles`).
+    pub profile: String,
+    /// Minimum estimated prompt tokens.
+    pub min_prompt_tokens: Option<usize>,
+    /// Maximum estimated prompt tokens.
+    pub max_prompt_tokens: Option<usize>,
+    /// Require tools to be present (`true`) or absent (`false`).
+    pub has_tools: Option<bool>,
+    /// Minimum agent loop iteration (1 = first call of a turn).
+    pub min_iteration: Option<u32>,
+    /// Maximum agent loop iteration.
+    pub max_iteration: Option<u32>,
+    /// Require this routing hint (from a skill, agent or session).
+    pub hint: Option<String>,
+}
+
+/// LLM classifier settings for routing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingClassifierConfig {
+    /// Profile used to run the classifier (should be cheap).
+    pub profile: String,
+    /// Minimum confidence (0.0–1.0) required to follow the classifier.
+    pub min_confidence: f64,
+    /// Labels the classifier chooses between.
+    pub labels: Vec<RoutingLabelConfig>,
+}
+
+impl Default for RoutingClassifierConfig {
+    fn default() -> Self {
+        Self {
+            profile: String::new(),
+            min_confidence: 0.6,
+            labels: Vec::new(),
+        }
+    }
+}
+
+/// A classifier label and the profile it routes to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RoutingLabelConfig {
+    /// Label name (e.g. "simple").
+    pub name: String,
+    /// Description shown to the classifier model.
+    pub description: String,
+    /// Profile to route to.
+    pub profile: String,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Usage Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Token usage accounting, model pricing and workstream spend limits.
+///
+/// Prices are USD per million tokens and are matched by exact model name,
+/// then by longest prefix. Limits are summed over `period` per workstream.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let config = ArawnConfig::from_toml(r#"
+///     [usage]
+///     period = "month"
+///     soft_limit_usd = 20.0
+///     hard_limit_usd = 50.0
+///
+///     [usage.pricing.claude-sonnet-4]
+///     input = 3.0
+///     output = 15.0
+///     cache_write = 3.75
+///     cache_read = 0.30
+///
+///     [usage.workstreams.research]
+///     hard_limit_usd = 200.0
+/// "#)?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Whether usage is recorded to the ledger.
+    pub enabled: bool,
+    /// Ledger database path (relative paths resolve against the data dir).
+    /// Defaults to `usage.db`.
+    pub database: Option<PathBuf>,
+    /// Window spend limits are summed over.
+    pub period: SpendPeriod,
+    /// Default soft limit (USD) — exceeding it logs a warning.
+    pub soft_limit_usd: Option<f64>,
+    /// Default hard limit (USD) — exceeding it stops the agent loop.
+    pub hard_limit_usd: Option<f64>,
+    /// Per-workstream limit overrides, keyed by workstream ID.
+    pub workstreams: HashMap<String, SpendLimitConfig>,
+    /// Model prices, keyed by model name or prefix.
+    pub pricing: HashMap<String, ModelPriceConfig>,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            database: None,
+            period: SpendPeriod::default(),
+            soft_limit_usd: None,
+            hard_limit_usd: None,
+            workstreams: HashMap::new(),
+            pricing: HashMap::new(),
+        }
+    }
+}
+
+/// Window over which workstream spend is summed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpendPeriod {
+    /// The current UTC day.
+    Day,
+    /// The current UTC calendar month.
+    #[default]
+    Month,
+    /// All recorded usage.
+    Total,
+}
+
+/// Spend limits for one workstream. Unset fields inherit the `[usage]` defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpendLimitConfig {
+    /// Soft limit (USD).
+    pub soft_limit_usd: Option<f64>,
+    /// Hard limit (USD).
+    pub hard_limit_usd: Option<f64>,
+}
+
+/// Price of a model in USD per million tokens.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelPriceConfig {
+    /// Uncached input tokens.
+    pub input: f64,
+    /// Output tokens.
+    pub output: f64,
+    /// Prompt-cache writes (defaults to `input`).
+    pub cache_write: Option<f64>,
+    /// Prompt-cache reads (defaults to `input`).
+    pub cache_read: Option<f64>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -606,6 +876,8 @@ impl Default for ServerConfig {
 pub struct LoggingConfig {
     /// Interaction log settings.
     pub interactions: InteractionLogConfig,
+    /// Full request/response capture settings.
+    pub capture: CaptureConfig,
 }
 
 /// Settings for structured interaction logging (JSONL).
@@ -630,6 +902,195 @@ impl Default for InteractionLogConfig {
     }
 }
 
+/// Settings for full LLM request/response capture (JSONL).
+///
+/// Off by default. Captures contain complete prompts and responses, so
+/// secrets are redacted before writing; `redact_patterns` adds regexes on
+/// top of the built-in credential patterns.
+///
+/// ```toml
+/// [logging.capture]
+/// enabled = true
+/// retention_days = 14
+/// redact_patterns = ["internal-[0-9a-f]{32}"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureConfig {
+    /// Whether full capture is enabled.
+    pub enabled: bool,
+    /// Directory for capture files (default `~/.config/arawn/captures`).
+    pub path: Option<PathBuf>,
+    /// Days to retain capture files before cleanup.
+    pub retention_days: u32,
+    /// Additional regex patterns to redact.
+    pub redact_patterns: Vec<String>,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            retention_days: 14,
+            redact_patterns: Vec::new(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Telemetry Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Prometheus metrics and OpenTelemetry trace export.
+///
+/// Metrics are served at `/metrics` unless disabled. Traces are exported over
+/// OTLP/HTTP when an endpoint is set here or in `OTEL_EXPORTER_OTLP_ENDPOINT`.
+///
+/// ```toml
+/// [telemetry]
+/// otlp_endpoint = "http://localhost:4318"
+/// service_name = "arawn-laptop"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Whether Prometheus metrics are recorded and served at `/metrics`.
+    pub metrics: bool,
+    /// OTLP/HTTP collector base URL (traces are posted to `/v1/traces`).
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` resource attribute on exported traces.
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            metrics: true,
+            otlp_endpoint: None,
+            service_name: "arawn".to_string(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Webhooks Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Outbound webhook delivery and inbound webhook triggers.
+///
+/// Subscriptions are managed through `/api/v1/webhooks`; this section
+/// tunes delivery. Failed deliveries are retried with exponential backoff
+/// (doubling from `initial_backoff_secs`, capped at `max_backoff_secs`).
+/// `[webhooks.inbound.<name>]` tables define `/hooks/<name>` endpoints.
+///
+/// ```toml
+/// [webhooks]
+/// max_attempts = 8
+/// disk_check_interval_secs = 300
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    /// Whether webhooks are delivered and the API is available.
+    pub enabled: bool,
+    /// Delivery attempts per event, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff_secs: u64,
+    /// Upper bound on the delay between retries.
+    pub max_backoff_secs: u64,
+    /// Per-request timeout.
+    pub timeout_secs: u64,
+    /// How often disk usage is checked for `disk_pressure` events (0 disables).
+    pub disk_check_interval_secs: u64,
+    /// Inbound triggers served at `/hooks/<name>`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub inbound: HashMap<String, InboundHookConfig>,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_attempts: 5,
+            initial_backoff_secs: 1,
+            max_backoff_secs: 60,
+            timeout_secs: 10,
+            disk_check_interval_secs: 900,
+            inbound: HashMap::new(),
+        }
+    }
+}
+
+/// An inbound webhook that starts a workflow or an agent turn.
+///
+/// Exactly one of `workflow` and `workstream` must be set. `context` maps
+/// names to JSON pointers into the request body; workflows receive them as
+/// context keys (alongside `payload`), and chat turns substitute them into
+/// `message` as `{{name}}`.
+///
+/// ```toml
+/// [webhooks.inbound.ci-failure]
+/// workflow = "ci-triage"
+/// secret_env = "CI_HOOK_SECRET"
+///
+/// [webhooks.inbound.ci-failure.context]
+/// repo = "/repository/full_name"
+/// run_url = "/workflow_run/html_url"
+///
+/// [webhooks.inbound.email]
+/// workstream = "inbox"
+/// auth = "token"
+/// secret_env = "EMAIL_HOOK_TOKEN"
+/// message = "New email from {{from}}: {{subject}}"
+/// context = { from = "/from", subject = "/subject" }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InboundHookConfig {
+    /// How requests are authenticated.
+    pub auth: InboundHookAuth,
+    /// Shared secret (plaintext — prefer `secret_env`).
+    pub secret: Option<String>,
+    /// Environment variable holding the shared secret.
+    pub secret_env: Option<String>,
+    /// Header carrying the signature or token. Defaults to
+    /// `X-Arawn-Signature` for `hmac` and `X-Arawn-Token` for `token`.
+    pub header: Option<String>,
+    /// Workflow to trigger.
+    pub workflow: Option<String>,
+    /// Workstream (ID or title) to run a chat turn in.
+    pub workstream: Option<String>,
+    /// Message template for chat turns. Defaults to the pretty-printed payload.
+    pub message: Option<String>,
+    /// Context name → JSON pointer into the payload.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub context: HashMap<String, String>,
+}
+
+impl InboundHookConfig {
+    /// Resolve the shared secret, preferring `secret_env` over `secret`.
+    pub fn resolve_secret(&self) -> Option<String> {
+        self.secret_env
+            .as_deref()
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|value| !value.is_empty())
+            .or_else(|| self.secret.clone())
+    }
+}
+
+/// Authentication scheme for an inbound webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InboundHookAuth {
+    /// `sha256=<hex>` HMAC-SHA256 of the raw body, keyed with the secret.
+    #[default]
+    Hmac,
+    /// The secret itself, sent in the header or as a bearer token.
+    Token,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Embedding Configuration
 // ─────────────────────────────────────────────────────────────────────────────
@@ -641,21 +1102,26 @@ impl Default for InteractionLogConfig {
 ///
 /// ```toml
 /// [embedding]
-/// provider = "local"        # "local", "openai", or "mock"
+/// provider = "local"        # "local", "openai", "gemini", or "mock"
 /// dimensions = 384
 ///
 /// [embedding.openai]
 /// model = "text-embedding-3-small"
+///
+/// [embedding.gemini]
+/// model = "text-embedding-004"
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct EmbeddingConfig {
-    /// Provider: "local" (ONNX), "openai", or "mock".
+    /// Provider: "local" (ONNX), "openai", "gemini", or "mock".
     pub provider: EmbeddingProvider,
     /// Output embedding dimensions. Default depends on provider.
     pub dimensions: Option<usize>,
     /// OpenAI-specific embedding settings.
     pub openai: Option<EmbeddingOpenAiConfig>,
+    /// Gemini-specific embedding settings.
+    pub gemini: Option<EmbeddingGeminiConfig>,
     /// Local ONNX-specific settings.
     pub local: Option<EmbeddingLocalConfig>,
 }
@@ -666,6 +1132,7 @@ impl Default for EmbeddingConfig {
             provider: EmbeddingProvider::Local,
             dimensions: None,
             openai: None,
+            gemini: None,
             local: None,
         }
     }
@@ -686,6 +1153,13 @@ impl EmbeddingConfig {
                     .and_then(|c| c.dimensions)
                     .unwrap_or(1536)
             }
+            EmbeddingProvider::Gemini => {
+                let config = self.gemini.clone().unwrap_or_default();
+                config.dimensions.unwrap_or(match config.model.as_str() {
+                    "gemini-embedding-001" => 3072,
+                    _ => 768,
+                })
+            }
             EmbeddingProvider::Mock => 384,
         }
     }
@@ -699,6 +1173,8 @@ pub enum EmbeddingProvider {
     Local,
     /// OpenAI embeddings API.
     OpenAi,
+    /// Google Gemini embeddings API.
+    Gemini,
     /// Mock embedder for testing.
     Mock,
 }
@@ -728,6 +1204,31 @@ impl Default for EmbeddingOpenAiConfig {
     }
 }
 
+/// Gemini embedding provider settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingGeminiConfig {
+    /// Model name. Default: "text-embedding-004".
+    pub model: String,
+    /// Override dimensions (sent as `outputDimensionality`).
+    pub dimensions: Option<usize>,
+    /// Custom base URL (for proxies).
+    pub base_url: Option<String>,
+    /// API key (prefer keyring or env var).
+    pub api_key: Option<String>,
+}
+
+impl Default for EmbeddingGeminiConfig {
+    fn default() -> Self {
+        Self {
+            model: "text-embedding-004".to_string(),
+            dimensions: None,
+            base_url: None,
+            api_key: None,
+        }
+    }
+}
+
 /// Local ONNX embedding settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -962,6 +1463,7 @@ impl Default for CompactionConfig {
 /// enabled = true
 /// dirs = ["~/.config/arawn/plugins", "./plugins"]
 /// auto_update = true
+/// lock = "warn"
 ///
 /// [[plugins.subscriptions]]
 /// source = "github"
@@ -983,6 +1485,8 @@ pub struct PluginsConfig {
     /// Plugin subscriptions (sources to fetch plugins from).
     #[serde(default)]
     pub subscriptions: Vec<PluginSubscription>,
+    /// How `arawn-plugins.lock` is enforced for subscribed plugins.
+    pub lock: PluginLockMode,
 }
 
 impl Default for PluginsConfig {
@@ -993,10 +1497,25 @@ impl Default for PluginsConfig {
             hot_reload: true,
             auto_update: true,
             subscriptions: Vec::new(),
+            lock: PluginLockMode::default(),
         }
     }
 }
 
+/// Enforcement policy for the plugin lockfile (`arawn-plugins.lock`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginLockMode {
+    /// Ignore the lockfile.
+    Off,
+    /// Sync to the latest ref, but warn when content differs from the lockfile.
+    #[default]
+    Warn,
+    /// Check out locked commits only and refuse to load plugins whose
+    /// content does not match the lockfile.
+    Strict,
+}
+
 /// A plugin subscription defining where to fetch a plugin from.
 ///
 /// Supports multiple source types:
@@ -1401,6 +1920,9 @@ pub struct WorkstreamConfig {
     pub session_timeout_minutes: i64,
     /// Session/workstream compression configuration.
     pub compression: Option<CompressionConfig>,
+    /// Directory of workstream templates (default: `templates`).
+    /// Relative paths are resolved from the data directory.
+    pub templates_dir: Option<PathBuf>,
 }
 
 impl Default for WorkstreamConfig {
@@ -1410,6 +1932,7 @@ impl Default for WorkstreamConfig {
             data_dir: None,
             session_timeout_minutes: 60,
             compression: None,
+            templates_dir: None,
         }
     }
 }
@@ -2134,6 +2657,27 @@ dimensions = 1536
         assert_eq!(emb.effective_dimensions(), 1536);
     }
 
+    #[test]
+    fn test_parse_gemini_embedding_config() {
+        let toml = r#"
+[embedding]
+provider = "gemini"
+
+[embedding.gemini]
+model = "gemini-embedding-001"
+"#;
+        let config = ArawnConfig::from_toml(toml).unwrap();
+        let emb = config.embedding.as_ref().unwrap();
+        assert_eq!(emb.provider, EmbeddingProvider::Gemini);
+        assert_eq!(emb.effective_dimensions(), 3072);
+
+        let default = EmbeddingConfig {
+            provider: EmbeddingProvider::Gemini,
+            ..Default::default()
+        };
+        assert_eq!(default.effective_dimensions(), 768);
+    }
+
     // ── Pipeline Config Tests ──────────────────────────────────────────
 
     #[test]
@@ -2569,6 +3113,192 @@ target_len = 2000
         assert_eq!(delegation.compaction.target_len, 2000);
     }
 
+    #[test]
+    fn test_parse_capture_config() {
+        let toml = r#"
+[logging.capture]
+enabled = true
+redact_patterns = ["internal-[0-9a-f]{8}"]
+"#;
+        let config = ArawnConfig::from_toml(toml).unwrap();
+        let logging = config.logging.unwrap();
+        assert!(logging.capture.enabled);
+        assert_eq!(logging.capture.retention_days, 14);
+        assert_eq!(logging.capture.redact_patterns.len(), 1);
+        // Interaction logging keeps its own defaults
+        assert!(logging.interactions.enabled);
+        assert!(!CaptureConfig::default().enabled);
+    }
+
+    #[test]
+    fn test_parse_usage_config() {
+        let toml = r#"
+[usage]
+period = "day"
+hard_limit_usd = 5.0
+
+[usage.pricing.claude-sonnet-4]
+input = 3.0
+output = 15.0
+cache_read = 0.3
+
+[usage.pricing."llama3.1"]
+input = 0.0
+output = 0.0
+
+[usage.workstreams.research]
+soft_limit_usd = 20.0
+"#;
+        let config = ArawnConfig::from_toml(toml).unwrap();
+        let usage = config.usage.as_ref().unwrap();
+        assert!(usage.enabled);
+        assert_eq!(usage.period, SpendPeriod::Day);
+        assert_eq!(usage.hard_limit_usd, Some(5.0));
+        assert!(usage.soft_limit_usd.is_none());
+        let sonnet = &usage.pricing["claude-sonnet-4"];
+        assert_eq!(sonnet.output, 15.0);
+        assert_eq!(sonnet.cache_read, Some(0.3));
+        assert!(sonnet.cache_write.is_none());
+        assert!(usage.pricing.contains_key("llama3.1"));
+        assert_eq!(usage.workstreams["research"].soft_limit_usd, Some(20.0));
+
+        // Round-trips through the raw TOML form
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.usage.unwrap().period, SpendPeriod::Day);
+    }
+
+    #[test]
+    fn test_parse_telemetry_config() {
+        let config = ArawnConfig::from_toml(
+            r#"
+[telemetry]
+otlp_endpoint = "http://collector:4318"
+"#,
+        )
+        .unwrap();
+        let telemetry = config.telemetry.as_ref().unwrap();
+        assert!(telemetry.metrics);
+        assert_eq!(
+            telemetry.otlp_endpoint.as_deref(),
+            Some("http://collector:4318")
+        );
+        assert_eq!(telemetry.service_name, "arawn");
+
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert!(reparsed.telemetry.is_some());
+    }
+
+    #[test]
+    fn test_parse_webhooks_config() {
+        let config = ArawnConfig::from_toml(
+            r#"
+[webhooks]
+max_attempts = 8
+disk_check_interval_secs = 0
+"#,
+        )
+        .unwrap();
+        let webhooks = config.webhooks.as_ref().unwrap();
+        assert!(webhooks.enabled);
+        assert_eq!(webhooks.max_attempts, 8);
+        assert_eq!(webhooks.initial_backoff_secs, 1);
+        assert_eq!(webhooks.disk_check_interval_secs, 0);
+
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.webhooks.unwrap().max_attempts, 8);
+    }
+
+    #[test]
+    fn test_parse_inbound_hooks() {
+        let config = ArawnConfig::from_toml(
+            r#"
+[webhooks.inbound.ci-failure]
+workflow = "ci-triage"
+secret = "s3cret"
+
+[webhooks.inbound.ci-failure.context]
+repo = "/repository/full_name"
+
+[webhooks.inbound.email]
+workstream = "inbox"
+auth = "token"
+message = "Mail from {{from}}"
+context = { from = "/from" }
+"#,
+        )
+        .unwrap();
+        let inbound = &config.webhooks.as_ref().unwrap().inbound;
+        assert_eq!(inbound.len(), 2);
+
+        let ci = &inbound["ci-failure"];
+        assert_eq!(ci.auth, InboundHookAuth::Hmac);
+        assert_eq!(ci.workflow.as_deref(), Some("ci-triage"));
+        assert_eq!(ci.resolve_secret().as_deref(), Some("s3cret"));
+        assert_eq!(ci.context["repo"], "/repository/full_name");
+
+        let email = &inbound["email"];
+        assert_eq!(email.auth, InboundHookAuth::Token);
+        assert_eq!(email.workstream.as_deref(), Some("inbox"));
+        assert!(email.resolve_secret().is_none());
+
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(
+            reparsed.webhooks.unwrap().inbound["email"].context["from"],
+            "/from"
+        );
+    }
+
+    #[test]
+    fn test_parse_routing_config() {
+        let toml = r#"
+[llm]
+backend = "anthropic"
+model = "claude-sonnet-4-20250514"
+
+[llm.fast]
+backend = "groq"
+model = "llama-3.1-8b-instant"
+
+[routing]
+enabled = true
+default = "claude"
+
+[[routing.rules]]
+name = "short-chat"
+profile = "fast"
+max_prompt_tokens = 2000
+has_tools = false
+
+[[routing.rules]]
+profile = "fast"
+min_iteration = 2
+
+[routing.classifier]
+profile = "fast"
+
+[[routing.classifier.labels]]
+name = "simple"
+description = "quick questions"
+profile = "fast"
+"#;
+        let config = ArawnConfig::from_toml(toml).unwrap();
+        let routing = config.routing.as_ref().unwrap();
+        assert!(routing.enabled);
+        assert_eq!(routing.default.as_deref(), Some("claude"));
+        assert_eq!(routing.rules.len(), 2);
+        assert_eq!(routing.rules[0].name.as_deref(), Some("short-chat"));
+        assert_eq!(routing.rules[0].has_tools, Some(false));
+        assert_eq!(routing.rules[1].min_iteration, Some(2));
+        let classifier = routing.classifier.as_ref().unwrap();
+        assert_eq!(classifier.min_confidence, 0.6);
+        assert_eq!(classifier.labels[0].profile, "fast");
+
+        // Routing survives a serialization roundtrip alongside llm profiles
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.routing.unwrap().rules.len(), 2);
+        assert!(reparsed.llm_profiles.contains_key("fast"));
+    }
+
     #[test]
     fn test_parse_delegation_compaction_disabled() {
         let toml = r#"
@@ -2806,6 +3536,34 @@ max_context_tokens = 32000
         assert_eq!(fast.max_context_tokens, Some(32_000));
     }
 
+    #[test]
+    fn test_parse_tool_format() {
+        let toml = r#"
+[llm]
+backend = "ollama"
+model = "qwen2.5"
+tool_format = "xml"
+
+[llm.hermes]
+backend = "ollama"
+model = "hermes3"
+tool_format = "hermes"
+"#;
+        let config = ArawnConfig::from_toml(toml).unwrap();
+        assert_eq!(
+            config.llm.as_ref().unwrap().tool_format,
+            Some(ToolFormat::Xml)
+        );
+        assert_eq!(
+            config.llm_profiles["hermes"].tool_format,
+            Some(ToolFormat::Hermes)
+        );
+
+        let reparsed = ArawnConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.llm.unwrap().tool_format, Some(ToolFormat::Xml));
+        assert!(ArawnConfig::from_toml("[llm]\ntool_format = \"yaml\"").is_err());
+    }
+
     #[test]
     fn test_require_max_context_tokens_success() {
         let llm = LlmConfig {
@@ -2862,6 +3620,7 @@ session_warning_mb = 500
 
 [paths.cleanup]
 scratch_cleanup_days = 14
+snapshot_keep_last = 5
 dry_run = true
 
 [paths.monitoring]
@@ -2879,6 +3638,8 @@ polling_interval_secs = 60
         assert_eq!(paths.usage.workstream_warning_gb, 2);
         assert_eq!(paths.usage.session_warning_mb, 500);
         assert_eq!(paths.cleanup.scratch_cleanup_days, 14);
+        assert_eq!(paths.cleanup.snapshot_keep_last, 5);
+        assert_eq!(paths.cleanup.snapshot_max_age_days, 30);
         assert!(paths.cleanup.dry_run);
         assert!(!paths.monitoring.enabled);
         assert_eq!(paths.monitoring.debounce_ms, 1000);
@@ -25,9 +25,26 @@ pub mod services;
 
 pub use error::{DomainError, Result};
 pub use services::DomainServices;
+pub use services::archive::{
+    ARCHIVE_SCHEMA_VERSION, ArchiveCounts, ArchiveError, ArchiveManifest, EmbedderInfo,
+    ImportOptions, ImportReport, WorkstreamArchiver,
+};
 pub use services::chat::{ChatResponse, ChatService, ToolCallSummary, TurnOptions};
+pub use services::config::{
+    BackendFactory, BackendFuture, ConfigService, ConfigServiceError, ConfigServiceResult,
+    ConfigUpdate,
+};
 pub use services::mcp::{McpServerInfo, McpService, McpToolInfo, SharedMcpManager};
 pub use services::memory::MemoryService;
+pub use services::plugin::{PluginInfo, PluginService, PluginServiceError, PluginServiceResult};
+pub use services::template::{
+    TEMPLATE_MANIFEST, TemplateError, TemplateOutcome, TemplateParam, TemplateRegistry,
+    TemplateRequest, TemplateResult, TemplateSource, WorkstreamTemplate,
+};
+pub use services::workflow::{
+    RuntimeInfo, RuntimeSource, WorkflowDetail, WorkflowError, WorkflowResult, WorkflowService,
+    WorkflowSummary,
+};
 
 // Re-export key types from infrastructure crates for convenience.
 // The domain facade aggregates these so transport layers (server, CLI) can depend
@@ -37,10 +54,12 @@ pub use services::memory::MemoryService;
 pub use arawn_agent::{
     Agent, AgentError, CompactionResult, CompactorConfig, Session, SessionCompactor, SessionId,
     SessionIndexer, StreamChunk, ToolCall, ToolRegistry, ToolResultRecord, Turn, TurnId,
+    context::estimate_tokens,
 };
 
-// Config: configuration errors
-pub use arawn_config::ConfigError;
+// Config: configuration, errors and plugin sources
+pub use arawn_config::discovery::ConfigSource;
+pub use arawn_config::{ArawnConfig, ConfigError, PluginSource};
 
 // MCP: server management and configuration
 pub use arawn_mcp::{McpManager, McpServerConfig};
@@ -49,6 +68,22 @@ pub use arawn_mcp::{McpManager, McpServerConfig};
 pub use arawn_memory::MemoryId;
 pub use arawn_memory::MemoryStore;
 pub use arawn_memory::types::{ContentType, Memory, Note as MemoryNote, NoteId};
+pub use arawn_memory::{
+    CypherRows, GraphNode, GraphRelationship, GraphStore, MEMORY_LABEL, MemoryError,
+    RelationshipType, Subgraph,
+};
+
+// Pipeline: workflow definitions, runs, schedules and runtimes
+pub use arawn_pipeline::{
+    CatalogEntry, ExecutionResult, ExecutionStatus, RuntimeCategory, ScheduleInfo, ValidationIssue,
+    WorkflowDefinition,
+};
+
+// Plugin: runtime capabilities, watcher and subscriptions
+pub use arawn_plugin::{
+    CapabilitySummary, PluginEvent, PluginRuntime, PluginWatcher, SubscriptionManager, SyncAction,
+    SyncResult,
+};
 
 // Sandbox: OS-level sandboxing for shell commands
 pub use arawn_sandbox::SandboxManager;
@@ -62,8 +97,17 @@ pub use arawn_session::{Error as SessionStoreError, Result as SessionStoreResult
 pub use arawn_workstream::cleanup::{DiskPressureEvent, PressureLevel};
 pub use arawn_workstream::directory::DirectoryError;
 pub use arawn_workstream::store::Workstream;
+pub use arawn_workstream::{AccessStore, ApiToken, ResourceKind, TokenScope, scopes_allow};
+pub use arawn_workstream::{
+    AttachResult, Compressor, DirectoryManager, FileConflictPolicy, FileMergeResult, ForkPoint,
+    FsAction, FsChangeEvent, MergeResult, MessageRole, PathValidator, ReconstructedSession,
+    SCRATCH_ID, SessionLoader, Snapshot, SnapshotTrigger, SplitResult, WatcherHandle,
+    WorkstreamError, WorkstreamManager, WorkstreamMessage,
+};
+pub use arawn_workstream::{
+    DeadLetter, NewWebhook, WebhookEventKind, WebhookStore, WebhookSubscription, WebhookUpdate,
+};
 pub use arawn_workstream::{
-    AttachResult, Compressor, DirectoryManager, FsAction, FsChangeEvent, MessageRole,
-    PathValidator, ReconstructedSession, SCRATCH_ID, SessionLoader, WatcherHandle, WorkstreamError,
-    WorkstreamManager, WorkstreamMessage,
+    LimitPeriod, SpendLimit, SpendLimits, UsageGroupBy, UsageLedger, UsageQuery, UsageReport,
+    UsageRow,
 };
@@ -0,0 +1,1115 @@
+//! Workstream export and import.
+//!
+//! Bundles everything that makes up a workstream into a single
+//! zstd-compressed tarball so it can be moved to another machine or shared:
+//!
+//! ```text
+//! manifest.json         schema version, source embedder, counts
+//! workstream.json       workstream record and tags
+//! sessions.json         session records
+//! messages.jsonl        conversation history
+//! memories.jsonl        memories extracted from the sessions, with embeddings
+//! notes.jsonl           notes tagged with the workstream ID or one of its tags
+//! files/production/...  production directory
+//! files/work/...        work directory
+//! ```
+//!
+//! Production snapshots are not included.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use arawn_llm::SharedEmbedder;
+use arawn_memory::MemoryStore;
+use arawn_memory::types::{Citation, Memory, MemoryId, Note, NoteId};
+use arawn_workstream::store::{Session, Workstream};
+use arawn_workstream::{
+    DirectoryManager, SCRATCH_ID, WorkstreamError, WorkstreamManager, WorkstreamMessage,
+};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Current archive schema version. Archives with a newer version are rejected.
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const WORKSTREAM_ENTRY: &str = "workstream.json";
+const SESSIONS_ENTRY: &str = "sessions.json";
+const MESSAGES_ENTRY: &str = "messages.jsonl";
+const MEMORIES_ENTRY: &str = "memories.jsonl";
+const NOTES_ENTRY: &str = "notes.jsonl";
+const FILES_PREFIX: &str = "files/";
+
+/// Maximum notes exported per tag.
+const NOTES_PER_TAG: usize = 10_000;
+
+/// Memories embedded per request when re-embedding on import.
+const REEMBED_BATCH: usize = 32;
+
+/// Errors from exporting or importing a workstream archive.
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    /// The workstream to export does not exist.
+    #[error("Workstream not found: {0}")]
+    WorkstreamNotFound(String),
+
+    /// The scratch workstream is per-session and has no single identity.
+    #[error("The scratch workstream cannot be exported; promote it first")]
+    ScratchNotExportable,
+
+    /// The archive was written by a newer version of Arawn.
+    #[error("Unsupported archive schema version {found} (this build reads up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+
+    /// The archive is missing required entries or contains unsafe paths.
+    #[error("Invalid archive: {0}")]
+    Invalid(String),
+
+    /// The archive's IDs are already in use. Import with new IDs instead.
+    #[error("Import conflicts with existing data: {}", .0.join(", "))]
+    Conflict(Vec<String>),
+
+    /// Workstream storage error.
+    #[error("Workstream error: {0}")]
+    Workstream(#[from] WorkstreamError),
+
+    /// Memory storage error.
+    #[error("Memory error: {0}")]
+    Memory(#[from] arawn_memory::MemoryError),
+
+    /// Filesystem or compression error.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Malformed JSON in the archive.
+    #[error("Serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type for archive operations.
+pub type ArchiveResult<T> = std::result::Result<T, ArchiveError>;
+
+/// Identity of the embedder that produced a set of memory vectors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbedderInfo {
+    /// Provider name as recorded by the memory store.
+    pub provider: String,
+    /// Vector dimensions.
+    pub dimensions: usize,
+}
+
+impl EmbedderInfo {
+    /// Reads the embedder recorded in a memory store's metadata.
+    pub fn from_store(store: &MemoryStore) -> ArchiveResult<Option<Self>> {
+        let provider = store.get_meta("embedding.provider")?;
+        let dimensions = store
+            .get_meta("embedding.dimensions")?
+            .and_then(|d| d.parse().ok());
+        Ok(match (provider, dimensions) {
+            (Some(provider), Some(dimensions)) => Some(Self {
+                provider,
+                dimensions,
+            }),
+            _ => None,
+        })
+    }
+}
+
+/// Number of records of each kind in an archive or import.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveCounts {
+    pub sessions: usize,
+    pub messages: usize,
+    pub memories: usize,
+    /// Memories that carry an embedding.
+    pub embeddings: usize,
+    pub notes: usize,
+    pub files: usize,
+}
+
+/// Describes an archive. Always the first entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub schema_version: u32,
+    /// Version of Arawn that wrote the archive.
+    pub arawn_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub workstream_id: String,
+    pub title: String,
+    /// Embedder behind the archived memory vectors, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedder: Option<EmbedderInfo>,
+    pub counts: ArchiveCounts,
+}
+
+/// Options for importing an archive.
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Give the workstream, sessions, messages, memories and notes fresh IDs
+    /// instead of failing when the archived IDs are already in use.
+    pub new_ids: bool,
+    /// Title to use instead of the archived one.
+    pub title: Option<String>,
+    /// Re-embed memories whose vectors came from a different embedder.
+    pub reembed: bool,
+}
+
+/// Outcome of an import.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    /// ID of the imported workstream.
+    pub workstream_id: String,
+    pub title: String,
+    /// Whether IDs were remapped.
+    pub ids_remapped: bool,
+    /// Records written.
+    pub imported: ArchiveCounts,
+    /// Memories that already existed, or that had nowhere to go.
+    pub memories_skipped: usize,
+    /// Notes that already existed, or that had nowhere to go.
+    pub notes_skipped: usize,
+    /// Memories given a fresh embedding from the configured embedder.
+    pub memories_reembedded: usize,
+    /// Memories stored without a vector; run `arawn memory reindex` to fix.
+    pub memories_without_embedding: usize,
+}
+
+/// Workstream record and tags, as stored in `workstream.json`.
+#[derive(Serialize, Deserialize)]
+struct WorkstreamRecord {
+    workstream: Workstream,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// A memory and its vector, as stored in `memories.jsonl`.
+#[derive(Serialize, Deserialize)]
+struct MemoryRecord {
+    memory: Memory,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embedding: Option<Vec<f32>>,
+}
+
+/// Records read from an archive, before any files are extracted.
+#[derive(Default)]
+struct ArchiveParts {
+    manifest: Option<ArchiveManifest>,
+    workstream: Option<WorkstreamRecord>,
+    sessions: Vec<Session>,
+    messages: Vec<WorkstreamMessage>,
+    memories: Vec<MemoryRecord>,
+    notes: Vec<Note>,
+}
+
+/// Validated records ready to be written.
+struct ImportPlan {
+    manifest: ArchiveManifest,
+    workstream: Workstream,
+    tags: Vec<String>,
+    sessions: Vec<Session>,
+    messages: Vec<WorkstreamMessage>,
+    memories: Vec<MemoryRecord>,
+    notes: Vec<Note>,
+    remapped: bool,
+}
+
+/// Exports and imports workstreams as portable `.tar.zst` archives.
+///
+/// Memories and notes are included only when a memory store is configured.
+/// Files are included only when the workstream manager has a directory
+/// manager.
+pub struct WorkstreamArchiver {
+    workstreams: Arc<WorkstreamManager>,
+    memory: Option<Arc<MemoryStore>>,
+    embedder: Option<SharedEmbedder>,
+}
+
+impl WorkstreamArchiver {
+    /// Create an archiver over the given workstream manager.
+    pub fn new(workstreams: Arc<WorkstreamManager>) -> Self {
+        Self {
+            workstreams,
+            memory: None,
+            embedder: None,
+        }
+    }
+
+    /// Include related memories and notes.
+    pub fn with_memory_store(mut self, store: Arc<MemoryStore>) -> Self {
+        self.memory = Some(store);
+        self
+    }
+
+    /// Embedder used to re-embed imported memories.
+    pub fn with_embedder(mut self, embedder: SharedEmbedder) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    // ── Export ──────────────────────────────────────────────────────
+
+    /// Write a workstream archive to `writer`.
+    pub fn export<W: Write>(
+        &self,
+        workstream_id: &str,
+        writer: W,
+    ) -> ArchiveResult<ArchiveManifest> {
+        if workstream_id == SCRATCH_ID {
+            return Err(ArchiveError::ScratchNotExportable);
+        }
+        let workstream = match self.workstreams.get_workstream(workstream_id) {
+            Ok(ws) => ws,
+            Err(WorkstreamError::NotFound(_)) => {
+                return Err(ArchiveError::WorkstreamNotFound(workstream_id.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let tags = self.workstreams.get_tags(workstream_id)?;
+        let mut sessions = self.workstreams.list_sessions(workstream_id)?;
+        sessions.reverse();
+        let messages = self.workstreams.get_messages(workstream_id)?;
+
+        let (memories, notes, embedder) = match &self.memory {
+            Some(store) => (
+                collect_memories(store, &sessions)?,
+                collect_notes(store, workstream_id, &tags)?,
+                EmbedderInfo::from_store(store)?,
+            ),
+            None => (Vec::new(), Vec::new(), None),
+        };
+        let files = match self.workstreams.directory_manager() {
+            Some(dm) => collect_files(dm, workstream_id)?,
+            None => Vec::new(),
+        };
+
+        let manifest = ArchiveManifest {
+            schema_version: ARCHIVE_SCHEMA_VERSION,
+            arawn_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: Utc::now(),
+            workstream_id: workstream.id.clone(),
+            title: workstream.title.clone(),
+            embedder,
+            counts: ArchiveCounts {
+                sessions: sessions.len(),
+                messages: messages.len(),
+                memories: memories.len(),
+                embeddings: memories.iter().filter(|m| m.embedding.is_some()).count(),
+                notes: notes.len(),
+                files: files.len(),
+            },
+        };
+
+        let mut tar = tar::Builder::new(zstd::Encoder::new(writer, 0)?);
+        append_bytes(
+            &mut tar,
+            MANIFEST_ENTRY,
+            &serde_json::to_vec_pretty(&manifest)?,
+        )?;
+        append_bytes(
+            &mut tar,
+            WORKSTREAM_ENTRY,
+            &serde_json::to_vec_pretty(&WorkstreamRecord { workstream, tags })?,
+        )?;
+        append_bytes(
+            &mut tar,
+            SESSIONS_ENTRY,
+            &serde_json::to_vec_pretty(&sessions)?,
+        )?;
+        append_bytes(&mut tar, MESSAGES_ENTRY, &to_jsonl(&messages)?)?;
+        append_bytes(&mut tar, MEMORIES_ENTRY, &to_jsonl(&memories)?)?;
+        append_bytes(&mut tar, NOTES_ENTRY, &to_jsonl(&notes)?)?;
+        for (name, path) in &files {
+            tar.append_path_with_name(path, name)?;
+        }
+        tar.into_inner()?.finish()?;
+
+        Ok(manifest)
+    }
+
+    // ── Import ──────────────────────────────────────────────────────
+
+    /// Read a workstream archive from `reader` and recreate it.
+    ///
+    /// Fails with [`ArchiveError::Conflict`] if the archived workstream or
+    /// session IDs already exist, unless `options.new_ids` is set. Memories
+    /// and notes that already exist are skipped rather than duplicated.
+    pub async fn import<R: Read>(
+        &self,
+        reader: R,
+        options: &ImportOptions,
+    ) -> ArchiveResult<ImportReport> {
+        let mut target = None;
+        let result = self.import_records(reader, options, &mut target);
+        if result.is_err()
+            && let Some(id) = target
+        {
+            self.remove_partial_import(&id);
+        }
+        let (mut report, pending) = result?;
+
+        self.reembed(pending, &mut report).await;
+        Ok(report)
+    }
+
+    /// Reads the archive and writes everything except new embeddings.
+    ///
+    /// Sets `target` to the new workstream ID once its directories exist, and
+    /// clears it again once the workstream record is committed, so the
+    /// caller knows whether there is anything to clean up on failure.
+    fn import_records<R: Read>(
+        &self,
+        reader: R,
+        options: &ImportOptions,
+        target: &mut Option<String>,
+    ) -> ArchiveResult<(ImportReport, Vec<(MemoryId, String)>)> {
+        let mut archive = tar::Archive::new(zstd::Decoder::new(reader)?);
+        let mut parts = ArchiveParts::default();
+        let mut plan: Option<ImportPlan> = None;
+        let mut files = 0;
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let name = entry.path()?.to_string_lossy().replace('\\', "/");
+
+            if parts.manifest.is_none() && plan.is_none() {
+                if name != MANIFEST_ENTRY {
+                    return Err(ArchiveError::Invalid(format!(
+                        "expected {MANIFEST_ENTRY} as the first entry, found {name}"
+                    )));
+                }
+                let manifest: ArchiveManifest = read_json(&mut entry)?;
+                if manifest.schema_version > ARCHIVE_SCHEMA_VERSION {
+                    return Err(ArchiveError::UnsupportedVersion {
+                        found: manifest.schema_version,
+                        supported: ARCHIVE_SCHEMA_VERSION,
+                    });
+                }
+                parts.manifest = Some(manifest);
+                continue;
+            }
+
+            if let Some(rel) = name.strip_prefix(FILES_PREFIX) {
+                if plan.is_none() {
+                    let p = self.plan_import(std::mem::take(&mut parts), options)?;
+                    *target = Some(p.workstream.id.clone());
+                    plan = Some(p);
+                }
+                let ws_id = target.as_deref().unwrap_or_default();
+                if self.extract_file(&mut entry, rel, ws_id)? {
+                    files += 1;
+                }
+                continue;
+            }
+
+            match name.as_str() {
+                WORKSTREAM_ENTRY => parts.workstream = Some(read_json(&mut entry)?),
+                SESSIONS_ENTRY => parts.sessions = read_json(&mut entry)?,
+                MESSAGES_ENTRY => parts.messages = read_jsonl(&mut entry)?,
+                MEMORIES_ENTRY => parts.memories = read_jsonl(&mut entry)?,
+                NOTES_ENTRY => parts.notes = read_jsonl(&mut entry)?,
+                other => warn!(entry = %other, "Ignoring unknown archive entry"),
+            }
+        }
+
+        let plan = match plan {
+            Some(plan) => plan,
+            None => {
+                let p = self.plan_import(parts, options)?;
+                *target = Some(p.workstream.id.clone());
+                p
+            }
+        };
+
+        let mut report = ImportReport {
+            workstream_id: plan.workstream.id.clone(),
+            title: plan.workstream.title.clone(),
+            ids_remapped: plan.remapped,
+            ..Default::default()
+        };
+        report.imported.files = files;
+
+        let ws_id = plan.workstream.id.clone();
+        report.imported.messages = self
+            .workstreams
+            .message_store()
+            .append_messages(&ws_id, &plan.messages)?;
+        self.workstreams
+            .store()
+            .import_workstream(&plan.workstream, &plan.tags, &plan.sessions)?;
+        report.imported.sessions = plan.sessions.len();
+        *target = None;
+
+        let pending = self.import_knowledge(plan, options, &mut report)?;
+        Ok((report, pending))
+    }
+
+    /// Validates the archived records, remaps or checks IDs, and creates the
+    /// workstream's directories.
+    fn plan_import(
+        &self,
+        parts: ArchiveParts,
+        options: &ImportOptions,
+    ) -> ArchiveResult<ImportPlan> {
+        let manifest = parts
+            .manifest
+            .ok_or_else(|| ArchiveError::Invalid(format!("missing {MANIFEST_ENTRY}")))?;
+        let record = parts
+            .workstream
+            .ok_or_else(|| ArchiveError::Invalid(format!("missing {WORKSTREAM_ENTRY}")))?;
+        if record.workstream.is_scratch || record.workstream.id == SCRATCH_ID {
+            return Err(ArchiveError::Invalid(
+                "archive contains the scratch workstream".to_string(),
+            ));
+        }
+
+        let mut plan = ImportPlan {
+            manifest,
+            workstream: record.workstream,
+            tags: record.tags,
+            sessions: parts.sessions,
+            messages: parts.messages,
+            memories: parts.memories,
+            notes: parts.notes,
+            remapped: false,
+        };
+
+        if options.new_ids {
+            remap_ids(&mut plan);
+        } else {
+            let conflicts = self.find_conflicts(&plan);
+            if !conflicts.is_empty() {
+                return Err(ArchiveError::Conflict(conflicts));
+            }
+        }
+        if let Some(title) = &options.title {
+            plan.workstream.title = title.clone();
+        }
+
+        if let Some(dm) = self.workstreams.directory_manager() {
+            dm.create_workstream(&plan.workstream.id)
+                .map_err(|e| ArchiveError::Invalid(e.to_string()))?;
+        }
+        Ok(plan)
+    }
+
+    /// Lists archived IDs that already exist locally.
+    fn find_conflicts(&self, plan: &ImportPlan) -> Vec<String> {
+        let store = self.workstreams.store();
+        let ws_id = &plan.workstream.id;
+        let mut conflicts = Vec::new();
+
+        if store.get_workstream(ws_id).is_ok() {
+            conflicts.push(format!("workstream {ws_id}"));
+        } else if self
+            .workstreams
+            .message_store()
+            .workstream_dir(ws_id)
+            .exists()
+        {
+            conflicts.push(format!("data directory for workstream {ws_id}"));
+        }
+        for session in &plan.sessions {
+            if store.get_session(&session.id).is_ok() {
+                conflicts.push(format!("session {}", session.id));
+            }
+        }
+        conflicts
+    }
+
+    /// Writes one `files/` entry into the workstream's directories.
+    ///
+    /// Returns whether a file was written.
+    fn extract_file<R: Read>(
+        &self,
+        entry: &mut tar::Entry<'_, R>,
+        rel: &str,
+        workstream_id: &str,
+    ) -> ArchiveResult<bool> {
+        let Some(dm) = self.workstreams.directory_manager() else {
+            return Ok(false);
+        };
+        if !entry.header().entry_type().is_file() {
+            return Ok(false);
+        }
+
+        let (area, path) = rel
+            .split_once('/')
+            .ok_or_else(|| ArchiveError::Invalid(format!("unexpected file entry: {rel}")))?;
+        let root = match area {
+            "production" => dm.production_path(workstream_id),
+            "work" => dm.work_path(workstream_id),
+            other => {
+                return Err(ArchiveError::Invalid(format!(
+                    "unexpected file area: {other}"
+                )));
+            }
+        };
+        let path = Path::new(path);
+        if path.as_os_str().is_empty()
+            || !path.components().all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(ArchiveError::Invalid(format!("unsafe file path: {rel}")));
+        }
+
+        let dest = root.join(path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out = fs::File::create(&dest)?;
+        io::copy(entry, &mut out)?;
+        Ok(true)
+    }
+
+    /// Writes memories and notes. Returns memories that still need embedding.
+    fn import_knowledge(
+        &self,
+        plan: ImportPlan,
+        options: &ImportOptions,
+        report: &mut ImportReport,
+    ) -> ArchiveResult<Vec<(MemoryId, String)>> {
+        let Some(store) = &self.memory else {
+            if !plan.memories.is_empty() || !plan.notes.is_empty() {
+                warn!("No memory store configured; skipping archived memories and notes");
+            }
+            report.memories_skipped = plan.memories.len();
+            report.notes_skipped = plan.notes.len();
+            return Ok(Vec::new());
+        };
+
+        let target = EmbedderInfo::from_store(store)?;
+        let same_embedder = target.is_some() && target == plan.manifest.embedder;
+        let can_reembed = options.reembed
+            && match (&self.embedder, &target) {
+                (Some(embedder), Some(info)) => embedder.dimensions() == info.dimensions,
+                _ => false,
+            };
+        if options.reembed && !can_reembed {
+            warn!("Re-embedding requested but no embedder matching the memory store is configured");
+        }
+
+        let mut pending = Vec::new();
+        for record in plan.memories {
+            let memory = record.memory;
+            if store.get_memory(memory.id)?.is_some() {
+                report.memories_skipped += 1;
+                continue;
+            }
+            match record.embedding {
+                Some(vector) if same_embedder && store.has_vectors() => {
+                    store.insert_memory_with_embedding(&memory, &vector)?;
+                    report.imported.embeddings += 1;
+                }
+                _ => {
+                    store.insert_memory(&memory)?;
+                    if can_reembed {
+                        pending.push((memory.id, memory.content.clone()));
+                    } else if store.has_vectors() {
+                        report.memories_without_embedding += 1;
+                    }
+                }
+            }
+            report.imported.memories += 1;
+        }
+
+        for note in plan.notes {
+            if store.get_note(note.id)?.is_some() {
+                report.notes_skipped += 1;
+                continue;
+            }
+            store.insert_note(&note)?;
+            report.imported.notes += 1;
+        }
+
+        Ok(pending)
+    }
+
+    /// Embeds imported memories whose archived vectors couldn't be reused.
+    async fn reembed(&self, pending: Vec<(MemoryId, String)>, report: &mut ImportReport) {
+        let (Some(store), Some(embedder)) = (&self.memory, &self.embedder) else {
+            return;
+        };
+        for batch in pending.chunks(REEMBED_BATCH) {
+            let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+            match embedder.embed_batch(&texts).await {
+                Ok(vectors) => {
+                    for ((id, _), vector) in batch.iter().zip(vectors) {
+                        match store.store_embedding(*id, &vector) {
+                            Ok(()) => {
+                                report.memories_reembedded += 1;
+                                report.imported.embeddings += 1;
+                            }
+                            Err(e) => {
+                                warn!(memory_id = %id, error = %e, "Failed to store embedding");
+                                report.memories_without_embedding += 1;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to re-embed imported memories");
+                    report.memories_without_embedding += batch.len();
+                }
+            }
+        }
+    }
+
+    /// Removes directories left behind by an import that failed part-way.
+    fn remove_partial_import(&self, workstream_id: &str) {
+        let mut dirs = vec![
+            self.workstreams
+                .message_store()
+                .workstream_dir(workstream_id),
+        ];
+        if let Some(dm) = self.workstreams.directory_manager() {
+            dirs.push(dm.workstream_path(workstream_id));
+        }
+        for dir in dirs {
+            if dir.exists()
+                && let Err(e) = fs::remove_dir_all(&dir)
+            {
+                warn!(path = %dir.display(), error = %e, "Failed to clean up partial import");
+            }
+        }
+    }
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────
+
+/// Memories extracted from any of the sessions, with their embeddings.
+fn collect_memories(store: &MemoryStore, sessions: &[Session]) -> ArchiveResult<Vec<MemoryRecord>> {
+    let mut records = Vec::new();
+    for session in sessions {
+        for memory in store.list_memories_for_session(&session.id)? {
+            let embedding = store.get_embedding(memory.id)?;
+            records.push(MemoryRecord { memory, embedding });
+        }
+    }
+    Ok(records)
+}
+
+/// Notes tagged with the workstream ID or one of its tags.
+fn collect_notes(
+    store: &MemoryStore,
+    workstream_id: &str,
+    tags: &[String],
+) -> ArchiveResult<Vec<Note>> {
+    let mut seen = HashSet::new();
+    let mut notes = Vec::new();
+    for tag in std::iter::once(workstream_id).chain(tags.iter().map(String::as_str)) {
+        for note in store.list_notes_by_tag(tag, NOTES_PER_TAG)? {
+            if seen.insert(note.id) {
+                notes.push(note);
+            }
+        }
+    }
+    Ok(notes)
+}
+
+/// Regular files under `production/` and `work/`, as (archive name, path).
+fn collect_files(
+    dm: &DirectoryManager,
+    workstream_id: &str,
+) -> ArchiveResult<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for (area, root) in [
+        ("production", dm.production_path(workstream_id)),
+        ("work", dm.work_path(workstream_id)),
+    ] {
+        if root.is_dir() {
+            walk_files(&root, &root, &format!("{FILES_PREFIX}{area}"), &mut files)?;
+        }
+    }
+    Ok(files)
+}
+
+fn walk_files(
+    root: &Path,
+    dir: &Path,
+    prefix: &str,
+    out: &mut Vec<(String, PathBuf)>,
+) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk_files(root, &path, prefix, out)?;
+        } else if file_type.is_file() {
+            let rel = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push((format!("{prefix}/{rel}"), path));
+        }
+    }
+    Ok(())
+}
+
+/// Gives every record a fresh ID and rewrites the references between them.
+fn remap_ids(plan: &mut ImportPlan) {
+    let ws_id = Uuid::new_v4().to_string();
+    let sessions: HashMap<String, String> = plan
+        .sessions
+        .iter()
+        .map(|s| (s.id.clone(), Uuid::new_v4().to_string()))
+        .collect();
+    let messages: HashMap<String, String> = plan
+        .messages
+        .iter()
+        .map(|m| (m.id.clone(), Uuid::new_v4().to_string()))
+        .collect();
+    let memories: HashMap<MemoryId, MemoryId> = plan
+        .memories
+        .iter()
+        .map(|r| (r.memory.id, MemoryId::new()))
+        .collect();
+    let remap = |map: &HashMap<String, String>, id: &str| {
+        map.get(id).cloned().unwrap_or_else(|| id.to_string())
+    };
+
+    plan.workstream.id = ws_id.clone();
+    for session in &mut plan.sessions {
+        session.id = remap(&sessions, &session.id);
+        session.workstream_id = ws_id.clone();
+        session.parent_session_id = session
+            .parent_session_id
+            .as_deref()
+            .map(|id| remap(&sessions, id));
+        session.forked_from_message_id = session
+            .forked_from_message_id
+            .as_deref()
+            .map(|id| remap(&messages, id));
+    }
+    for message in &mut plan.messages {
+        message.id = remap(&messages, &message.id);
+        message.workstream_id = ws_id.clone();
+        message.session_id = message.session_id.as_deref().map(|id| remap(&sessions, id));
+    }
+    for record in &mut plan.memories {
+        let memory = &mut record.memory;
+        memory.id = memories.get(&memory.id).copied().unwrap_or(memory.id);
+        memory.session_id = memory.session_id.as_deref().map(|id| remap(&sessions, id));
+        memory.metadata.session_id = memory
+            .metadata
+            .session_id
+            .as_deref()
+            .map(|id| remap(&sessions, id));
+        if let Some(Citation::Session { session_id, .. }) = &mut memory.citation {
+            *session_id = remap(&sessions, session_id);
+        }
+        if let Some(old) = memory.confidence.superseded_by {
+            memory.confidence.superseded_by = Some(memories.get(&old).copied().unwrap_or(old));
+        }
+    }
+    for note in &mut plan.notes {
+        note.id = NoteId::new();
+    }
+    plan.remapped = true;
+}
+
+fn append_bytes<W: Write>(tar: &mut tar::Builder<W>, name: &str, data: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    tar.append_data(&mut header, name, data)
+}
+
+fn to_jsonl<T: Serialize>(items: &[T]) -> serde_json::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for item in items {
+        serde_json::to_writer(&mut out, item)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn read_json<T: DeserializeOwned>(reader: impl Read) -> ArchiveResult<T> {
+    Ok(serde_json::from_reader(reader)?)
+}
+
+fn read_jsonl<T: DeserializeOwned>(reader: impl Read) -> ArchiveResult<Vec<T>> {
+    let mut items = Vec::new();
+    for line in io::BufReader::new(reader).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            items.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_llm::MockEmbedder;
+    use arawn_memory::types::ContentType;
+    use arawn_workstream::{MessageRole, WorkstreamConfig};
+    use tempfile::TempDir;
+
+    struct Env {
+        _dir: TempDir,
+        manager: Arc<WorkstreamManager>,
+        memory: Arc<MemoryStore>,
+    }
+
+    fn create_env(embedder: (usize, &str)) -> Env {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path().join("workstreams");
+        let config = WorkstreamConfig {
+            db_path: dir.path().join("workstreams.db"),
+            data_dir: data_dir.clone(),
+            session_timeout_minutes: 60,
+        };
+        let manager = WorkstreamManager::new(&config)
+            .unwrap()
+            .with_directory_manager(DirectoryManager::new(&data_dir));
+
+        arawn_memory::init_vector_extension();
+        let memory = MemoryStore::open_in_memory().unwrap();
+        memory.init_vectors(embedder.0, embedder.1).unwrap();
+
+        Env {
+            _dir: dir,
+            manager: Arc::new(manager),
+            memory: Arc::new(memory),
+        }
+    }
+
+    /// Creates a workstream with a session, messages, a memory, a note and
+    /// a production file.
+    fn populate(env: &Env) -> (String, String) {
+        let ws = env
+            .manager
+            .create_workstream("Research", None, &["rust".to_string()])
+            .unwrap();
+        let msg = env
+            .manager
+            .send_message(Some(&ws.id), None, MessageRole::User, "hello", None)
+            .unwrap();
+        let session_id = msg.session_id.clone().unwrap();
+        env.manager
+            .send_message(
+                Some(&ws.id),
+                Some(&session_id),
+                MessageRole::Assistant,
+                "hi there",
+                None,
+            )
+            .unwrap();
+
+        let memory =
+            Memory::new(ContentType::Fact, "The user likes Rust").with_session(&session_id);
+        env.memory
+            .insert_memory_with_embedding(&memory, &[0.1, 0.2, 0.3, 0.4])
+            .unwrap();
+        env.memory
+            .insert_note(&Note::new("Project notes").with_tag("rust"))
+            .unwrap();
+
+        let dm = env.manager.directory_manager().unwrap();
+        let report = dm.production_path(&ws.id).join("docs");
+        fs::create_dir_all(&report).unwrap();
+        fs::write(report.join("report.md"), "# Report").unwrap();
+
+        (ws.id, session_id)
+    }
+
+    fn export(env: &Env, id: &str) -> Vec<u8> {
+        let archiver =
+            WorkstreamArchiver::new(env.manager.clone()).with_memory_store(env.memory.clone());
+        let mut buf = Vec::new();
+        archiver.export(id, &mut buf).unwrap();
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let source = create_env((4, "mock"));
+        let (ws_id, session_id) = populate(&source);
+        let archive = export(&source, &ws_id);
+
+        let target = create_env((4, "mock"));
+        let archiver = WorkstreamArchiver::new(target.manager.clone())
+            .with_memory_store(target.memory.clone());
+        let report = archiver
+            .import(archive.as_slice(), &ImportOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.workstream_id, ws_id);
+        assert!(!report.ids_remapped);
+        assert_eq!(report.imported.sessions, 1);
+        assert_eq!(report.imported.messages, 2);
+        assert_eq!(report.imported.memories, 1);
+        assert_eq!(report.imported.embeddings, 1);
+        assert_eq!(report.imported.notes, 1);
+        assert_eq!(report.imported.files, 1);
+
+        let ws = target.manager.get_workstream(&ws_id).unwrap();
+        assert_eq!(ws.title, "Research");
+        assert_eq!(target.manager.get_tags(&ws_id).unwrap(), vec!["rust"]);
+        let messages = target.manager.get_messages(&ws_id).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].content, "hi there");
+
+        let memories = target
+            .memory
+            .list_memories_for_session(&session_id)
+            .unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(
+            target.memory.get_embedding(memories[0].id).unwrap(),
+            Some(vec![0.1, 0.2, 0.3, 0.4])
+        );
+
+        let dm = target.manager.directory_manager().unwrap();
+        let file = dm.production_path(&ws_id).join("docs/report.md");
+        assert_eq!(fs::read_to_string(file).unwrap(), "# Report");
+    }
+
+    #[tokio::test]
+    async fn test_import_conflict_and_new_ids() {
+        let env = create_env((4, "mock"));
+        let (ws_id, session_id) = populate(&env);
+        let archive = export(&env, &ws_id);
+        let archiver =
+            WorkstreamArchiver::new(env.manager.clone()).with_memory_store(env.memory.clone());
+
+        let err = archiver
+            .import(archive.as_slice(), &ImportOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ArchiveError::Conflict(_)));
+        // The existing workstream's files are untouched.
+        let dm = env.manager.directory_manager().unwrap();
+        assert!(dm.production_path(&ws_id).join("docs/report.md").exists());
+
+        let options = ImportOptions {
+            new_ids: true,
+            title: Some("Research (copy)".to_string()),
+            ..Default::default()
+        };
+        let report = archiver.import(archive.as_slice(), &options).await.unwrap();
+        assert!(report.ids_remapped);
+        assert_ne!(report.workstream_id, ws_id);
+        assert_eq!(report.title, "Research (copy)");
+        assert_eq!(report.imported.memories, 1);
+
+        let sessions = env.manager.list_sessions(&report.workstream_id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_ne!(sessions[0].id, session_id);
+        let messages = env.manager.get_messages(&report.workstream_id).unwrap();
+        assert!(
+            messages
+                .iter()
+                .all(|m| m.session_id.as_deref() == Some(sessions[0].id.as_str()))
+        );
+        let memories = env
+            .memory
+            .list_memories_for_session(&sessions[0].id)
+            .unwrap();
+        assert_eq!(memories.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_reembeds_with_different_embedder() {
+        let source = create_env((4, "mock"));
+        let (ws_id, session_id) = populate(&source);
+        let archive = export(&source, &ws_id);
+
+        let target = create_env((8, "other"));
+        let archiver = WorkstreamArchiver::new(target.manager.clone())
+            .with_memory_store(target.memory.clone())
+            .with_embedder(Arc::new(MockEmbedder::new(8)));
+
+        let report = archiver
+            .import(archive.as_slice(), &ImportOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(report.memories_without_embedding, 1);
+        assert_eq!(report.memories_reembedded, 0);
+
+        let target = create_env((8, "other"));
+        let archiver = WorkstreamArchiver::new(target.manager.clone())
+            .with_memory_store(target.memory.clone())
+            .with_embedder(Arc::new(MockEmbedder::new(8)));
+        let options = ImportOptions {
+            reembed: true,
+            ..Default::default()
+        };
+        let report = archiver.import(archive.as_slice(), &options).await.unwrap();
+        assert_eq!(report.memories_reembedded, 1);
+        assert_eq!(report.memories_without_embedding, 0);
+
+        let memories = target
+            .memory
+            .list_memories_for_session(&session_id)
+            .unwrap();
+        let vector = target
+            .memory
+            .get_embedding(memories[0].id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(vector.len(), 8);
+    }
+
+    #[test]
+    fn test_export_rejects_scratch_and_unknown() {
+        let env = create_env((4, "mock"));
+        let archiver = WorkstreamArchiver::new(env.manager.clone());
+        assert!(matches!(
+            archiver.export(SCRATCH_ID, Vec::new()),
+            Err(ArchiveError::ScratchNotExportable)
+        ));
+        assert!(matches!(
+            archiver.export("missing", Vec::new()),
+            Err(ArchiveError::WorkstreamNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_newer_schema() {
+        let manifest = ArchiveManifest {
+            schema_version: ARCHIVE_SCHEMA_VERSION + 1,
+            arawn_version: "99.0.0".to_string(),
+            exported_at: Utc::now(),
+            workstream_id: "ws".to_string(),
+            title: "Future".to_string(),
+            embedder: None,
+            counts: ArchiveCounts::default(),
+        };
+        let mut buf = Vec::new();
+        {
+            let mut tar = tar::Builder::new(zstd::Encoder::new(&mut buf, 0).unwrap());
+            append_bytes(
+                &mut tar,
+                MANIFEST_ENTRY,
+                &serde_json::to_vec(&manifest).unwrap(),
+            )
+            .unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+        }
+
+        let env = create_env((4, "mock"));
+        let err = WorkstreamArchiver::new(env.manager.clone())
+            .import(buf.as_slice(), &ImportOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ArchiveError::UnsupportedVersion { found: 2, .. }
+        ));
+    }
+}
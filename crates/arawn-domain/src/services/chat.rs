@@ -3,9 +3,10 @@
 //! The chat service coordinates agent execution with session management
 //! and workstream persistence.
 
+use std::path::{Component, Path};
 use std::sync::Arc;
 
-use arawn_agent::{Agent, AgentResponse, Session, SessionId, SessionIndexer};
+use arawn_agent::{Agent, AgentResponse, AgentSettings, Session, SessionId, SessionIndexer};
 use arawn_workstream::{DirectoryManager, WorkstreamManager};
 use tracing::{debug, info, warn};
 
@@ -110,8 +111,9 @@ impl ChatService {
     /// Execute a chat turn with an existing session.
     ///
     /// This is the core chat operation that:
-    /// 1. Executes the agent turn
-    /// 2. Returns the response
+    /// 1. Applies the workstream's agent settings to the session
+    /// 2. Executes the agent turn
+    /// 3. Returns the response
     ///
     /// Note: Session persistence is handled separately via the workstream manager.
     pub async fn turn(
@@ -124,6 +126,10 @@ impl ChatService {
 
         debug!(session_id = %session_id, message_len = message.len(), "Executing chat turn");
 
+        if let Some(ws_id) = workstream_id {
+            self.apply_workstream_settings(session, ws_id);
+        }
+
         // Execute the agent turn
         let response = self.agent.turn(session, message, workstream_id).await?;
 
@@ -140,6 +146,39 @@ impl ChatService {
         Ok(chat_response)
     }
 
+    /// Copy a workstream's agent settings into the session's metadata.
+    ///
+    /// Bootstrap files are read from the workstream's `production/`
+    /// directory and appended to the settings' system prompt. The session
+    /// keeps the resolved record, so it follows the workstream's settings as
+    /// of its latest turn. Lookup failures are logged and leave the session
+    /// unchanged.
+    pub fn apply_workstream_settings(&self, session: &mut Session, workstream_id: &str) {
+        let Some(ref manager) = self.workstreams else {
+            return;
+        };
+        let mut settings = match manager.get_workstream(workstream_id) {
+            Ok(ws) => ws.agent_settings(),
+            Err(e) => {
+                warn!(workstream_id, error = %e, "Failed to load workstream settings");
+                return;
+            }
+        };
+
+        if !settings.bootstrap_files.is_empty() {
+            let dm = self
+                .directory_manager
+                .as_deref()
+                .or_else(|| manager.directory_manager());
+            if let Some(dm) = dm {
+                let production = dm.production_path(workstream_id);
+                append_bootstrap_files(&mut settings, &production);
+            }
+        }
+
+        session.set_agent_settings(&settings);
+    }
+
     /// Create a scratch session directory.
     pub fn create_scratch_session(&self, session_id: &str) -> Result<()> {
         if let Some(ref dm) = self.directory_manager {
@@ -214,6 +253,31 @@ impl ChatService {
     }
 }
 
+/// Append the contents of the settings' bootstrap files to its system prompt.
+///
+/// Paths must stay inside `production`; absolute paths, `..` components and
+/// unreadable files are skipped with a warning.
+fn append_bootstrap_files(settings: &mut AgentSettings, production: &Path) {
+    let mut sections: Vec<String> = settings.system_prompt.take().into_iter().collect();
+
+    for file in &settings.bootstrap_files {
+        let relative = Path::new(file);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            warn!(file = %file, "Skipping bootstrap file outside production/");
+            continue;
+        }
+        match std::fs::read_to_string(production.join(relative)) {
+            Ok(content) => sections.push(format!("## {}\n\n{}", file, content.trim_end())),
+            Err(e) => warn!(file = %file, error = %e, "Failed to read bootstrap file"),
+        }
+    }
+
+    settings.system_prompt = (!sections.is_empty()).then(|| sections.join("\n\n"));
+}
+
 /// Convert a session's turns into owned `(role, content)` pairs.
 fn session_to_messages(session: &Session) -> Vec<(String, String)> {
     let mut messages = Vec::new();
@@ -263,6 +327,65 @@ mod tests {
         assert!(!response.response.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_chat_turn_applies_workstream_settings() {
+        use arawn_workstream::{DirectoryManager, MessageStore, WorkstreamStore};
+
+        let dir = tempfile::tempdir().unwrap();
+        let dm = DirectoryManager::new(dir.path().join("workstreams"));
+        let manager = WorkstreamManager::from_parts(
+            WorkstreamStore::open_in_memory().unwrap(),
+            MessageStore::new(dir.path()),
+            30,
+        )
+        .with_directory_manager(dm.clone());
+        let ws = manager.create_workstream("Docs", None, &[]).unwrap();
+        let production = dm.production_path(&ws.id);
+        std::fs::create_dir_all(&production).unwrap();
+        std::fs::write(production.join("STYLE.md"), "Use British spelling.").unwrap();
+        manager
+            .update_settings(
+                &ws.id,
+                &AgentSettings {
+                    model: Some("ws-model".into()),
+                    system_prompt: Some("Be terse.".into()),
+                    bootstrap_files: vec!["STYLE.md".into(), "../escape.md".into()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let backend = Arc::new(MockBackend::with_text("Done."));
+        let agent = Arc::new(
+            Agent::builder()
+                .with_shared_backend(backend.clone())
+                .build()
+                .unwrap(),
+        );
+        let chat = ChatService::new(agent, Some(Arc::new(manager)), Some(Arc::new(dm)), None);
+
+        let mut session = Session::new();
+        chat.turn(&mut session, "Hi", Some(&ws.id)).await.unwrap();
+
+        let settings = session.agent_settings().unwrap();
+        assert_eq!(settings.model.as_deref(), Some("ws-model"));
+        assert_eq!(
+            settings.system_prompt.as_deref(),
+            Some("Be terse.\n\n## STYLE.md\n\nUse British spelling.")
+        );
+
+        let request = &backend.requests()[0];
+        assert_eq!(request.model, "ws-model");
+        assert!(
+            request
+                .system
+                .as_ref()
+                .unwrap()
+                .to_text()
+                .contains("Use British spelling.")
+        );
+    }
+
     #[tokio::test]
     async fn test_chat_turn_token_counts() {
         let agent = create_test_agent();
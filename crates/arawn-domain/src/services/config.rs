@@ -0,0 +1,640 @@
+//! Live configuration.
+//!
+//! Validates a new [`ArawnConfig`] (a full replacement, a JSON merge patch
+//! or the config files read again), works out what changed, saves it back
+//! to the layered config files and applies what can be applied while the
+//! server runs:
+//!
+//! - LLM profiles (`[llm]`, `[llm_profiles.*]`, `agent.default.llm`) — the
+//!   backend is rebuilt and swapped in as long as the provider stays the same
+//! - tool output limits (`[tools.output]`)
+//! - active recall (`[memory.recall]`)
+//! - MCP servers (`[mcp.servers]`)
+//! - whatever the transport registers with [`ConfigService::on_change`]
+//!   (the server's rate limits)
+//!
+//! Every other changed key is reported as needing a restart.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arawn_agent::{OutputConfig, SharedOutputOverrides, SharedRecallConfig};
+use arawn_config::changes::is_under;
+use arawn_config::discovery::ConfigSource;
+use arawn_config::{
+    ArawnConfig, ConfigDiff, ConfigError, McpServerEntry, ToolOutputConfig, reload_layers,
+    save_layered,
+};
+use arawn_llm::{ReloadableBackend, SharedBackend};
+use arawn_mcp::{McpManager, McpServerConfig};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use super::mcp::SharedMcpManager;
+
+/// Name of the backend entry resolved from `[llm]` / `agent.default.llm`.
+const DEFAULT_BACKEND: &str = "default";
+
+/// Errors from changing the configuration.
+#[derive(Debug, Error)]
+pub enum ConfigServiceError {
+    /// The new configuration failed validation.
+    #[error("invalid config: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+
+    /// The request can't be carried out (malformed patch, ...).
+    #[error("{0}")]
+    Rejected(String),
+
+    /// Reading or writing the config files failed.
+    #[error(transparent)]
+    Config(ConfigError),
+}
+
+impl From<ConfigError> for ConfigServiceError {
+    fn from(e: ConfigError) -> Self {
+        match e {
+            ConfigError::Invalid(problems) => Self::Invalid(problems),
+            other => Self::Config(other),
+        }
+    }
+}
+
+/// Result type for config operations.
+pub type ConfigServiceResult<T> = std::result::Result<T, ConfigServiceError>;
+
+/// Future returned by a [`BackendFactory`]: the new backend and its model.
+pub type BackendFuture =
+    Pin<Box<dyn Future<Output = std::result::Result<(SharedBackend, String), String>> + Send>>;
+
+/// Builds the backend for a named profile (`"default"` for the default one)
+/// from a new config.
+pub type BackendFactory = Arc<dyn Fn(String, ArawnConfig) -> BackendFuture + Send + Sync>;
+
+/// Called with the new config when one of its keys changed.
+pub type ConfigListener = Arc<dyn Fn(&ArawnConfig) + Send + Sync>;
+
+/// Listeners and the keys they watch.
+type Listeners = Vec<(Vec<String>, ConfigListener)>;
+
+/// What a config change did.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigUpdate {
+    /// Every changed key, sorted.
+    pub changed: Vec<String>,
+    /// Changed keys that took effect.
+    pub applied: Vec<String>,
+    /// Changed keys that take effect after a restart.
+    pub restart_required: Vec<String>,
+    /// Config files written.
+    pub persisted: Vec<PathBuf>,
+    /// Problems applying changes that were otherwise accepted.
+    pub warnings: Vec<String>,
+}
+
+struct State {
+    config: ArawnConfig,
+    sources: Vec<ConfigSource>,
+}
+
+/// Configuration service.
+#[derive(Clone)]
+pub struct ConfigService {
+    state: Arc<Mutex<State>>,
+    backends: HashMap<String, Arc<ReloadableBackend>>,
+    factory: Option<BackendFactory>,
+    tool_output: Option<SharedOutputOverrides>,
+    recall: Option<SharedRecallConfig>,
+    mcp: Option<SharedMcpManager>,
+    listeners: Arc<std::sync::RwLock<Listeners>>,
+}
+
+impl ConfigService {
+    /// Create a config service for the config the server started with and
+    /// the layers it was loaded from, lowest precedence first.
+    pub fn new(config: ArawnConfig, sources: Vec<ConfigSource>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State { config, sources })),
+            backends: HashMap::new(),
+            factory: None,
+            tool_output: None,
+            recall: None,
+            mcp: None,
+            listeners: Arc::default(),
+        }
+    }
+
+    /// Swap these backends, keyed by profile name, when their profile changes.
+    pub fn with_backends(
+        mut self,
+        backends: HashMap<String, Arc<ReloadableBackend>>,
+        factory: BackendFactory,
+    ) -> Self {
+        self.backends = backends;
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Keep the tool registry's output overrides in line with `[tools.output]`.
+    pub fn with_tool_output(mut self, overrides: SharedOutputOverrides) -> Self {
+        self.tool_output = Some(overrides);
+        self
+    }
+
+    /// Keep the agent's recall config in line with `[memory.recall]`.
+    pub fn with_recall(mut self, recall: SharedRecallConfig) -> Self {
+        self.recall = Some(recall);
+        self
+    }
+
+    /// Keep the MCP manager's servers in line with `[mcp.servers]`.
+    pub fn with_mcp_manager(mut self, mcp: SharedMcpManager) -> Self {
+        self.mcp = Some(mcp);
+        self
+    }
+
+    /// Call `listener` with the new config whenever one of `keys` (or a key
+    /// below one of them) changes. Those keys count as applied live.
+    pub fn on_change(
+        &self,
+        keys: &[&str],
+        listener: impl Fn(&ArawnConfig) + Send + Sync + 'static,
+    ) {
+        let keys = keys.iter().map(|k| k.to_string()).collect();
+        self.listeners
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((keys, Arc::new(listener)));
+    }
+
+    /// The current configuration.
+    pub async fn config(&self) -> ArawnConfig {
+        self.state.lock().await.config.clone()
+    }
+
+    /// Replace the configuration, save it and apply it.
+    pub async fn replace(&self, config: ArawnConfig) -> ConfigServiceResult<ConfigUpdate> {
+        self.update(|_| Ok(config), true).await
+    }
+
+    /// Apply a JSON merge patch (RFC 7386) to the configuration, save it
+    /// and apply it.
+    pub async fn patch(&self, patch: Value) -> ConfigServiceResult<ConfigUpdate> {
+        self.update(
+            move |state| {
+                let mut value = serde_json::to_value(&state.config)
+                    .map_err(|e| ConfigServiceError::Rejected(e.to_string()))?;
+                merge_patch(&mut value, &patch);
+                serde_json::from_value(value)
+                    .map_err(|e| ConfigServiceError::Rejected(format!("invalid config: {e}")))
+            },
+            true,
+        )
+        .await
+    }
+
+    /// Read the config files again and apply what changed in them.
+    pub async fn reload(&self) -> ConfigServiceResult<ConfigUpdate> {
+        self.update(|state| Ok(reload_layers(&state.sources)?), false)
+            .await
+    }
+
+    async fn update(
+        &self,
+        build: impl FnOnce(&State) -> ConfigServiceResult<ArawnConfig>,
+        persist: bool,
+    ) -> ConfigServiceResult<ConfigUpdate> {
+        let mut state = self.state.lock().await;
+        let new = build(&state)?;
+        new.validate()?;
+
+        let diff = ConfigDiff::between(&state.config, &new);
+        let mut update = ConfigUpdate {
+            changed: diff.keys().to_vec(),
+            ..Default::default()
+        };
+        if diff.is_empty() {
+            return Ok(update);
+        }
+        if persist {
+            update.persisted = save_layered(&state.sources, &diff, &new)?;
+        }
+
+        let live = self
+            .apply(&state.config, &new, &diff, &mut update.warnings)
+            .await;
+        for key in diff.keys() {
+            if live.iter().any(|prefix| is_under(key, prefix)) {
+                update.applied.push(key.clone());
+            } else {
+                update.restart_required.push(key.clone());
+            }
+        }
+        info!(
+            applied = update.applied.len(),
+            restart_required = update.restart_required.len(),
+            "config updated"
+        );
+
+        state.config = new;
+        Ok(update)
+    }
+
+    /// Apply `new` and return the key prefixes that are now live.
+    async fn apply(
+        &self,
+        old: &ArawnConfig,
+        new: &ArawnConfig,
+        diff: &ConfigDiff,
+        warnings: &mut Vec<String>,
+    ) -> Vec<String> {
+        let mut live = Vec::new();
+
+        self.apply_llm(old, new, &mut live, warnings).await;
+
+        if let Some(ref overrides) = self.tool_output
+            && diff.touches("tools.output")
+        {
+            let output = new.tools.clone().unwrap_or_default().output;
+            *overrides.write().unwrap_or_else(|e| e.into_inner()) = tool_output_overrides(&output);
+            live.push("tools.output".to_string());
+        }
+
+        if let Some(ref recall) = self.recall
+            && diff.touches("memory.recall")
+        {
+            let config = new.memory.clone().unwrap_or_default().recall;
+            *recall.write().unwrap_or_else(|e| e.into_inner()) = recall_config(&config);
+            live.push("memory.recall".to_string());
+        }
+
+        if let Some(ref mcp) = self.mcp
+            && diff.touches("mcp.servers")
+        {
+            self.apply_mcp(mcp, old, new, warnings).await;
+            live.push("mcp.servers".to_string());
+        }
+
+        let listeners = self
+            .listeners
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for (keys, listener) in listeners {
+            if keys.iter().any(|k| diff.touches(k)) {
+                listener(new);
+                live.extend(keys);
+            }
+        }
+
+        live
+    }
+
+    async fn apply_llm(
+        &self,
+        old: &ArawnConfig,
+        new: &ArawnConfig,
+        live: &mut Vec<String>,
+        warnings: &mut Vec<String>,
+    ) {
+        let Some(ref factory) = self.factory else {
+            return;
+        };
+        for (name, backend) in &self.backends {
+            let keys = if name == DEFAULT_BACKEND {
+                vec!["llm".to_string(), "agent.default.llm".to_string()]
+            } else {
+                vec![format!("llm_profiles.{name}")]
+            };
+            let (before, after) = (llm_entry(old, name), llm_entry(new, name));
+            if after.is_none() {
+                // A removed profile keeps serving until the restart
+                continue;
+            }
+            if before == after {
+                live.extend(keys);
+                continue;
+            }
+
+            let swapped = match factory(name.clone(), new.clone()).await {
+                Ok((next, model)) => backend.swap(next, model),
+                Err(e) => Err(e),
+            };
+            match swapped {
+                Ok(()) => {
+                    info!(profile = %name, model = %backend.model(), "reloaded LLM backend");
+                    live.extend(keys);
+                }
+                Err(e) => {
+                    warn!(profile = %name, error = %e, "failed to reload LLM backend");
+                    warnings.push(format!("LLM profile '{name}' not reloaded: {e}"));
+                }
+            }
+        }
+    }
+
+    async fn apply_mcp(
+        &self,
+        mcp: &SharedMcpManager,
+        old: &ArawnConfig,
+        new: &ArawnConfig,
+        warnings: &mut Vec<String>,
+    ) {
+        let previous: Vec<String> = old
+            .mcp
+            .as_ref()
+            .map(|m| m.servers.iter().map(|s| s.name.clone()).collect())
+            .unwrap_or_default();
+        let configs: Vec<McpServerConfig> = new
+            .mcp
+            .as_ref()
+            .map(|m| {
+                m.servers
+                    .iter()
+                    .filter(|s| s.enabled)
+                    .filter_map(mcp_server_config)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let manager = mcp.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let mut mcp = manager.blocking_write();
+            sync_config_servers(&mut mcp, &previous, &configs)
+        })
+        .await;
+
+        match result {
+            Ok((started, errors)) => {
+                warnings.extend(errors);
+                if !started.is_empty() {
+                    warnings.push(format!(
+                        "MCP servers started: {}; the agent picks up their tools after a restart",
+                        started.join(", ")
+                    ));
+                }
+            }
+            Err(e) => warnings.push(format!("MCP sync failed: {e}")),
+        }
+    }
+}
+
+/// The LLM config a backend entry is built from, as JSON for comparison.
+fn llm_entry(config: &ArawnConfig, name: &str) -> Option<Value> {
+    let llm = if name == DEFAULT_BACKEND {
+        config.resolve_llm(DEFAULT_BACKEND).ok()
+    } else {
+        config.llm_profiles.get(name)
+    };
+    llm.and_then(|l| serde_json::to_value(l).ok())
+}
+
+/// Bring the config-declared servers in `mcp` in line with `configs`.
+///
+/// `previous` are the names the old config declared, so servers from other
+/// sources (plugins) are left alone. Returns the servers (re)started and
+/// the errors.
+fn sync_config_servers(
+    mcp: &mut McpManager,
+    previous: &[String],
+    configs: &[McpServerConfig],
+) -> (Vec<String>, Vec<String>) {
+    let declared: HashSet<&str> = configs.iter().map(|c| c.name.as_str()).collect();
+    for name in previous {
+        if !declared.contains(name.as_str()) && mcp.remove_server(name) {
+            info!(server = %name, "stopped removed MCP server");
+        }
+    }
+
+    let mut started = Vec::new();
+    let mut errors = Vec::new();
+    for config in configs {
+        let unchanged = mcp.server_config(&config.name) == Some(config);
+        if unchanged && mcp.is_connected(&config.name) {
+            continue;
+        }
+        if !unchanged {
+            mcp.remove_server(&config.name);
+            mcp.add_server(config.clone());
+        }
+        match mcp.connect_server_by_name(&config.name) {
+            Ok(()) => {
+                info!(server = %config.name, "started MCP server");
+                started.push(config.name.clone());
+            }
+            Err(e) => {
+                warn!(server = %config.name, error = %e, "failed to start MCP server");
+                errors.push(format!("MCP server '{}' failed to start: {e}", config.name));
+            }
+        }
+    }
+    (started, errors)
+}
+
+/// Apply an RFC 7386 JSON merge patch: objects merge, `null` removes, and
+/// anything else replaces.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Per-tool output overrides for `[tools.output]`, keyed by tool name.
+pub fn tool_output_overrides(output: &ToolOutputConfig) -> HashMap<String, OutputConfig> {
+    let groups: [(Option<usize>, &[&str]); 4] = [
+        (output.shell, &["shell", "bash"]),
+        (output.file_read, &["file_read", "read_file"]),
+        (output.web_fetch, &["web_fetch", "fetch"]),
+        (output.search, &["grep", "glob", "search", "memory_search"]),
+    ];
+    let mut overrides = HashMap::new();
+    for (size, tools) in groups {
+        if let Some(size) = size {
+            for tool in tools {
+                overrides.insert(tool.to_string(), OutputConfig::with_max_size(size));
+            }
+        }
+    }
+    overrides
+}
+
+/// The agent's recall config for `[memory.recall]`.
+pub fn recall_config(config: &arawn_config::RecallConfig) -> arawn_agent::RecallConfig {
+    arawn_agent::RecallConfig {
+        enabled: config.enabled,
+        threshold: config.threshold,
+        limit: config.limit,
+    }
+}
+
+/// The MCP server config for a `[[mcp.servers]]` entry, or `None` (with a
+/// warning) if it can't be started.
+pub fn mcp_server_config(entry: &McpServerEntry) -> Option<McpServerConfig> {
+    if !entry.is_http() {
+        return Some(
+            McpServerConfig::new(&entry.name, &entry.command)
+                .with_args(entry.args.clone())
+                .with_env(entry.env_tuples()),
+        );
+    }
+    let Some(ref url) = entry.url else {
+        warn!(server = %entry.name, "MCP server is HTTP but has no URL, skipping");
+        return None;
+    };
+    let mut config = McpServerConfig::http(&entry.name, url);
+    for (k, v) in entry.header_tuples() {
+        config = config.with_header(k, v);
+    }
+    if let Some(timeout) = entry.timeout_secs {
+        config = config.with_timeout(std::time::Duration::from_secs(timeout));
+    }
+    if let Some(retries) = entry.retries {
+        config = config.with_retries(retries);
+    }
+    Some(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_llm::MockBackend;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn parse(toml: &str) -> ArawnConfig {
+        ArawnConfig::from_toml(toml).unwrap()
+    }
+
+    fn service(dir: &TempDir, toml: &str) -> ConfigService {
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, toml).unwrap();
+        let source = ConfigSource { path, loaded: true };
+        ConfigService::new(parse(toml), vec![source])
+    }
+
+    const BASE: &str = r#"
+[llm]
+backend = "anthropic"
+model = "model-a"
+
+[memory.recall]
+limit = 5
+"#;
+
+    #[tokio::test]
+    async fn test_patch_applies_live_sections_and_persists() {
+        let dir = TempDir::new().unwrap();
+        let recall: SharedRecallConfig = Arc::default();
+        let overrides: SharedOutputOverrides = Arc::default();
+        let service = service(&dir, BASE)
+            .with_recall(recall.clone())
+            .with_tool_output(overrides.clone());
+
+        let update = service
+            .patch(json!({
+                "memory": {"recall": {"limit": 9}},
+                "tools": {"output": {"shell": 2048}},
+                "server": {"port": 9000}
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(recall.read().unwrap().limit, 9);
+        assert_eq!(overrides.read().unwrap()["bash"].max_size_bytes, 2048);
+        assert!(update.applied.contains(&"memory.recall.limit".to_string()));
+        assert_eq!(update.restart_required, vec!["server.port"]);
+        assert_eq!(update.persisted.len(), 1);
+
+        let saved = std::fs::read_to_string(dir.path().join("config.toml")).unwrap();
+        assert!(saved.contains("limit = 9"));
+        assert_eq!(service.config().await.memory.unwrap().recall.limit, 9);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_is_rejected_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let service = service(&dir, BASE);
+
+        let err = service
+            .patch(json!({"memory": {"recall": {"threshold": 3.0}}}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ConfigServiceError::Invalid(_)));
+        let recall = service.config().await.memory.unwrap().recall;
+        assert_eq!(recall.threshold, 0.6);
+    }
+
+    #[tokio::test]
+    async fn test_llm_change_swaps_backend() {
+        let dir = TempDir::new().unwrap();
+        let backend = Arc::new(ReloadableBackend::new(
+            Arc::new(MockBackend::with_text("a")),
+            "model-a",
+        ));
+        let factory: BackendFactory = Arc::new(|_, config: ArawnConfig| {
+            let model = config.llm.and_then(|l| l.model).unwrap_or_default();
+            Box::pin(async move {
+                let backend: SharedBackend = Arc::new(MockBackend::with_text("b"));
+                Ok((backend, model))
+            })
+        });
+        let service = service(&dir, BASE).with_backends(
+            HashMap::from([(DEFAULT_BACKEND.to_string(), backend.clone())]),
+            factory,
+        );
+
+        let update = service
+            .patch(json!({"llm": {"model": "model-b"}}))
+            .await
+            .unwrap();
+        assert_eq!(update.applied, vec!["llm.model"]);
+        assert_eq!(backend.model(), "model-b");
+    }
+
+    #[tokio::test]
+    async fn test_reload_reads_files_and_runs_listeners() {
+        let dir = TempDir::new().unwrap();
+        let service = service(&dir, BASE);
+        let seen = Arc::new(std::sync::Mutex::new(None));
+        let sink = seen.clone();
+        service.on_change(&["server.api_rpm"], move |config| {
+            *sink.lock().unwrap() = config.server.as_ref().map(|s| s.api_rpm);
+        });
+
+        std::fs::write(
+            dir.path().join("config.toml"),
+            format!("{BASE}\n[server]\napi_rpm = 7\n"),
+        )
+        .unwrap();
+        let update = service.reload().await.unwrap();
+
+        assert_eq!(update.applied, vec!["server.api_rpm"]);
+        assert!(update.persisted.is_empty());
+        assert_eq!(*seen.lock().unwrap(), Some(7));
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let mut target = json!({"a": {"b": 1, "c": 2}, "d": 3});
+        merge_patch(&mut target, &json!({"a": {"b": null, "e": 4}, "d": [1]}));
+        assert_eq!(target, json!({"a": {"c": 2, "e": 4}, "d": [1]}));
+    }
+}
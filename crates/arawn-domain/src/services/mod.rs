@@ -3,9 +3,14 @@
 //! This module contains the core domain services that orchestrate
 //! Arawn's functionality.
 
+pub mod archive;
 pub mod chat;
+pub mod config;
 pub mod mcp;
 pub mod memory;
+pub mod plugin;
+pub mod template;
+pub mod workflow;
 
 use std::sync::Arc;
 
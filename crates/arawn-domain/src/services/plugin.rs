@@ -0,0 +1,734 @@
+//! Plugin management.
+//!
+//! Lists, installs, enables, disables, syncs and removes plugins while the
+//! server runs. Enabled state and subscriptions are persisted to the runtime
+//! `plugins.json` files (the same ones `arawn plugin` edits), and every change
+//! is applied to the [`PluginRuntime`] the agent was built against, so skills,
+//! hooks, subagents and prompt fragments follow without a restart.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arawn_config::{PluginSource, PluginSubscription};
+use arawn_plugin::manager::MANIFEST_PATH;
+use arawn_plugin::{
+    CapabilitySummary, LoadedPlugin, PluginError, PluginEvent, PluginRuntime, PluginWatcher,
+    SubscriptionManager, SyncAction, SyncResult, parse_source,
+};
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Errors from managing plugins.
+#[derive(Debug, Error)]
+pub enum PluginServiceError {
+    /// No plugin or subscription with this ID or name.
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The request can't be carried out (bad source, not removable, ...).
+    #[error("{0}")]
+    Rejected(String),
+
+    /// Cloning or updating a subscription failed.
+    #[error("Sync failed: {0}")]
+    Sync(String),
+
+    /// Loading a plugin or saving plugin configuration failed.
+    #[error(transparent)]
+    Plugin(#[from] PluginError),
+
+    /// Removing a plugin checkout failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type for plugin operations.
+pub type PluginServiceResult<T> = std::result::Result<T, PluginServiceError>;
+
+/// A plugin found in a plugin directory or subscribed to.
+#[derive(Debug, Clone)]
+pub struct PluginInfo {
+    /// Key used for the enabled state: the subscription ID, or `name@local`
+    /// for plugins found in plugin directories.
+    pub id: String,
+    /// Plugin name from the manifest (the subscription ID if the manifest
+    /// can't be read).
+    pub name: String,
+    /// Version from the manifest.
+    pub version: Option<String>,
+    /// Description from the manifest.
+    pub description: Option<String>,
+    /// The subscription this plugin comes from, if any.
+    pub subscription: Option<PluginSubscription>,
+    /// Locked commit of the subscription checkout.
+    pub commit: Option<String>,
+    /// Plugin root directory (None for subscriptions that were never synced).
+    pub path: Option<PathBuf>,
+    /// Whether the plugin is enabled.
+    pub enabled: bool,
+    /// Whether the plugin is currently loaded.
+    pub loaded: bool,
+    /// Declared vs discovered capabilities.
+    pub capabilities: Option<CapabilitySummary>,
+    /// Skill names.
+    pub skills: Vec<String>,
+    /// Subagent names.
+    pub agents: Vec<String>,
+    /// Number of hooks.
+    pub hooks: usize,
+    /// Namespaced MCP server names.
+    pub mcp_servers: Vec<String>,
+    /// Why the plugin could not be loaded or synced.
+    pub error: Option<String>,
+}
+
+impl PluginInfo {
+    fn matches(&self, id: &str) -> bool {
+        self.id == id
+            || self.name == id
+            || self
+                .subscription
+                .as_ref()
+                .is_some_and(|s| s.repo.as_deref() == Some(id))
+    }
+}
+
+/// Plugin management service.
+#[derive(Clone)]
+pub struct PluginService {
+    watcher: Arc<PluginWatcher>,
+    runtime: PluginRuntime,
+    subscriptions: Arc<Mutex<SubscriptionManager>>,
+    /// Load errors by plugin directory.
+    errors: Arc<RwLock<HashMap<PathBuf, String>>>,
+}
+
+impl PluginService {
+    /// Create a plugin service over a watcher, the runtime the agent uses,
+    /// and the subscription manager.
+    ///
+    /// The watcher's plugin directories should include disabled
+    /// subscriptions (see [`SubscriptionManager::known_plugin_dirs`]) so they
+    /// can be enabled later.
+    pub fn new(
+        watcher: Arc<PluginWatcher>,
+        runtime: PluginRuntime,
+        subscriptions: SubscriptionManager,
+    ) -> Self {
+        Self {
+            watcher,
+            runtime,
+            subscriptions: Arc::new(Mutex::new(subscriptions)),
+            errors: Arc::default(),
+        }
+    }
+
+    /// The plugin watcher.
+    pub fn watcher(&self) -> &Arc<PluginWatcher> {
+        &self.watcher
+    }
+
+    /// The runtime that enabled plugins are applied to.
+    pub fn runtime(&self) -> &PluginRuntime {
+        &self.runtime
+    }
+
+    /// Load all plugins, drop the disabled ones and apply the rest.
+    pub async fn load_initial(&self) -> Vec<PluginEvent> {
+        let events = self.watcher.load_initial().await;
+        let subs = self.subscriptions.lock().await;
+        for event in &events {
+            self.record(event).await;
+            if let PluginEvent::Reloaded { name, plugin_dir } = event
+                && !is_enabled(&subs, &plugin_id(&subs, plugin_dir, name).0)
+            {
+                self.watcher.remove_plugin(plugin_dir).await;
+            }
+        }
+        drop(subs);
+        self.apply().await;
+        events
+    }
+
+    /// React to a hot-reload event from the watcher.
+    ///
+    /// Plugins that are disabled are dropped again, and the runtime is
+    /// re-applied.
+    pub async fn handle_event(&self, event: &PluginEvent) {
+        self.record(event).await;
+        if let PluginEvent::Reloaded { name, plugin_dir } = event {
+            let subs = self.subscriptions.lock().await;
+            if !is_enabled(&subs, &plugin_id(&subs, plugin_dir, name).0) {
+                self.watcher.remove_plugin(plugin_dir).await;
+            }
+        }
+        self.apply().await;
+    }
+
+    // ── Queries ─────────────────────────────────────────────────────────
+
+    /// List plugins from the plugin directories and all subscriptions,
+    /// sorted by name.
+    pub async fn list(&self) -> Vec<PluginInfo> {
+        let subs = self.subscriptions.lock().await;
+        self.describe_all(&subs).await
+    }
+
+    /// Get a plugin by ID, name or GitHub repo.
+    pub async fn get(&self, id: &str) -> PluginServiceResult<PluginInfo> {
+        let subs = self.subscriptions.lock().await;
+        self.find(&subs, id).await
+    }
+
+    // ── Changes ─────────────────────────────────────────────────────────
+
+    /// Subscribe to a plugin and load it.
+    ///
+    /// `source` is GitHub `owner/repo` shorthand, a git URL, or a local
+    /// path. With `project`, the subscription is saved to the project's
+    /// `plugins.json` instead of the global one.
+    pub async fn install(
+        &self,
+        source: &str,
+        git_ref: Option<String>,
+        project: bool,
+    ) -> PluginServiceResult<PluginInfo> {
+        let subscription = parse_source(source.trim(), git_ref);
+        let id = subscription.id();
+        if subscription.source == PluginSource::Local {
+            let path = subscription.path.as_deref().unwrap_or(Path::new(""));
+            if !path.join(MANIFEST_PATH).exists() {
+                return Err(PluginServiceError::Rejected(format!(
+                    "No {MANIFEST_PATH} in {}",
+                    path.display()
+                )));
+            }
+        }
+
+        let mut subs = self.subscriptions.lock().await;
+        if subs.subscriptions().iter().any(|(s, _)| s.id() == id) {
+            return Err(PluginServiceError::Rejected(format!(
+                "Already subscribed to '{id}'"
+            )));
+        }
+        if project {
+            if subs.project_config_path().is_none() {
+                return Err(PluginServiceError::Rejected(
+                    "No project directory to save the subscription to".to_string(),
+                ));
+            }
+            subs.add_project_subscription(subscription.clone());
+        } else {
+            subs.add_global_subscription(subscription.clone());
+        }
+
+        if let Err(e) = sync_and_lock(&mut subs, &subscription).await {
+            // Don't keep a subscription that never synced
+            subs.global_config_mut().remove_subscription(&id);
+            subs.project_config_mut().remove_subscription(&id);
+            return Err(e);
+        }
+        if project {
+            subs.save_project_config()?;
+        } else {
+            subs.save_global_config()?;
+        }
+        if subs.is_enabled(&id) == Some(false) {
+            subs.persist_enabled(&id, true)?;
+        }
+        info!(subscription = %id, "plugin installed");
+
+        if let Some(dir) = subs.plugin_dir_for(&subscription) {
+            self.load(&dir).await;
+        }
+        self.apply().await;
+        self.find(&subs, &id).await
+    }
+
+    /// Enable or disable a plugin, persist the change and apply it.
+    pub async fn set_enabled(&self, id: &str, enabled: bool) -> PluginServiceResult<PluginInfo> {
+        let mut subs = self.subscriptions.lock().await;
+        let plugin = self.find(&subs, id).await?;
+        subs.persist_enabled(&plugin.id, enabled)?;
+
+        if let Some(ref dir) = plugin.path {
+            if enabled {
+                self.load(dir).await;
+            } else {
+                self.watcher.remove_plugin(dir).await;
+            }
+        }
+        self.apply().await;
+        info!(plugin = %plugin.id, enabled, "plugin enabled state changed");
+        self.find(&subs, &plugin.id).await
+    }
+
+    /// Pull a subscription's latest commit (or re-read a local plugin),
+    /// relock it and reload it if enabled.
+    pub async fn sync(&self, id: &str) -> PluginServiceResult<(SyncResult, PluginInfo)> {
+        let mut subs = self.subscriptions.lock().await;
+        let plugin = self.find(&subs, id).await?;
+
+        let result = match plugin.subscription {
+            Some(ref subscription) => sync_and_lock(&mut subs, subscription).await?,
+            None => SyncResult {
+                subscription_id: plugin.id.clone(),
+                action: SyncAction::Skipped,
+                path: plugin.path.clone(),
+                error: None,
+            },
+        };
+
+        let dir = plugin.path.clone().or_else(|| {
+            plugin
+                .subscription
+                .as_ref()
+                .and_then(|s| subs.plugin_dir_for(s))
+        });
+        if plugin.enabled
+            && let Some(ref dir) = dir
+        {
+            self.load(dir).await;
+        }
+        self.apply().await;
+        let plugin = self.find(&subs, &plugin.id).await?;
+        Ok((result, plugin))
+    }
+
+    /// Sync every enabled subscription and reload the plugins.
+    ///
+    /// Failures are reported per subscription rather than as an error.
+    pub async fn sync_all(&self) -> Vec<SyncResult> {
+        let mut subs = self.subscriptions.lock().await;
+        let mut results = Vec::new();
+        let mut reloaded = Vec::new();
+        for subscription in subs.all_subscriptions() {
+            match sync_and_lock(&mut subs, &subscription).await {
+                Ok(result) => {
+                    if let Some(dir) = subs.plugin_dir_for(&subscription) {
+                        self.load(&dir).await;
+                        reloaded.push(dir);
+                    }
+                    results.push(result);
+                }
+                Err(e) => results.push(SyncResult {
+                    subscription_id: subscription.id(),
+                    action: SyncAction::UpdateFailed,
+                    path: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+        // Re-read the enabled plugins in plugin directories too
+        for dir in self.watcher.manager().discover() {
+            let loaded = self.watcher.state().read().await.get_by_dir(&dir).is_some();
+            if loaded && !reloaded.contains(&dir) {
+                self.load(&dir).await;
+            }
+        }
+        self.apply().await;
+        results
+    }
+
+    /// Unsubscribe from a plugin and unload it.
+    ///
+    /// Only subscriptions added at runtime can be removed; plugins in plugin
+    /// directories and `arawn.toml` subscriptions can only be disabled.
+    /// With `delete_cache`, the git checkout is deleted too.
+    pub async fn remove(&self, id: &str, delete_cache: bool) -> PluginServiceResult<PluginInfo> {
+        let mut subs = self.subscriptions.lock().await;
+        let plugin = self.find(&subs, id).await?;
+        let Some(ref subscription) = plugin.subscription else {
+            return Err(PluginServiceError::Rejected(format!(
+                "'{}' is in a plugin directory; disable it or delete the directory",
+                plugin.name
+            )));
+        };
+        if !subs.remove_subscription(&plugin.id)? {
+            return Err(PluginServiceError::Rejected(format!(
+                "'{}' is configured in arawn.toml; disable it instead",
+                plugin.id
+            )));
+        }
+
+        if let Some(ref dir) = plugin.path {
+            self.watcher.remove_plugin(dir).await;
+            self.errors.write().await.remove(dir);
+        }
+        if delete_cache && subscription.source != PluginSource::Local {
+            let cache = subs.cache_dir_for(subscription);
+            if cache.exists() {
+                tokio::fs::remove_dir_all(&cache).await?;
+            }
+        }
+        self.apply().await;
+        info!(subscription = %plugin.id, "plugin removed");
+        Ok(PluginInfo {
+            enabled: false,
+            loaded: false,
+            ..plugin
+        })
+    }
+
+    // ── Internals ───────────────────────────────────────────────────────
+
+    /// Apply the loaded plugins to the runtime.
+    async fn apply(&self) {
+        let state = self.watcher.state();
+        let state = state.read().await;
+        self.runtime.apply(&state.plugins());
+    }
+
+    /// Load or reload a plugin, recording any error.
+    async fn load(&self, dir: &Path) {
+        let event = self.watcher.reload_plugin(dir).await;
+        self.record(&event).await;
+    }
+
+    async fn record(&self, event: &PluginEvent) {
+        let mut errors = self.errors.write().await;
+        match event {
+            PluginEvent::Reloaded { plugin_dir, .. } | PluginEvent::Removed { plugin_dir, .. } => {
+                errors.remove(plugin_dir);
+            }
+            PluginEvent::Error { plugin_dir, error } => {
+                errors.insert(plugin_dir.clone(), error.clone());
+            }
+        }
+    }
+
+    async fn find(&self, subs: &SubscriptionManager, id: &str) -> PluginServiceResult<PluginInfo> {
+        self.describe_all(subs)
+            .await
+            .into_iter()
+            .find(|p| p.matches(id))
+            .ok_or_else(|| PluginServiceError::NotFound(format!("Plugin '{id}'")))
+    }
+
+    async fn describe_all(&self, subs: &SubscriptionManager) -> Vec<PluginInfo> {
+        let state = self.watcher.state();
+        let state = state.read().await;
+        let errors = self.errors.read().await;
+        let manager = self.watcher.manager();
+
+        let mut plugins = Vec::new();
+        let mut seen: Vec<PathBuf> = Vec::new();
+
+        let describe = |dir: &Path, id: String, subscription: Option<PluginSubscription>| {
+            let loaded = state.get_by_dir(dir).cloned();
+            let is_loaded = loaded.is_some();
+            let (plugin, load_error) = match loaded {
+                Some(p) => (Some(p), None),
+                None => match manager.load_single(dir) {
+                    Ok(p) => (Some(p), None),
+                    Err(e) => (None, Some(e.to_string())),
+                },
+            };
+            let enabled = is_enabled_with(subs, &id, subscription.as_ref());
+            let error = errors.get(dir).cloned().or(load_error);
+            describe_plugin(
+                id,
+                subscription,
+                Some(dir),
+                plugin.as_ref(),
+                enabled,
+                is_loaded,
+                error,
+            )
+        };
+
+        for (subscription, _) in subs.subscriptions() {
+            let id = subscription.id();
+            let commit = subs.lockfile().get(&id).map(|l| l.commit.clone());
+            let mut info = match subs.plugin_dir_for(&subscription) {
+                Some(dir) => {
+                    seen.push(dir.clone());
+                    describe(&dir, id, Some(subscription))
+                }
+                None => {
+                    let enabled = is_enabled_with(subs, &id, Some(&subscription));
+                    describe_plugin(
+                        id,
+                        Some(subscription),
+                        None,
+                        None,
+                        enabled,
+                        false,
+                        Some("Not synced".to_string()),
+                    )
+                }
+            };
+            info.commit = commit;
+            plugins.push(info);
+        }
+
+        for dir in manager.discover() {
+            if seen.contains(&dir) {
+                continue;
+            }
+            let name = match state.get_by_dir(&dir) {
+                Some(p) => p.manifest.name.clone(),
+                None => manager
+                    .load_single(&dir)
+                    .map(|p| p.manifest.name)
+                    .unwrap_or_else(|_| dir_name(&dir)),
+            };
+            plugins.push(describe(&dir, local_id(&name), None));
+        }
+
+        plugins.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        plugins
+    }
+}
+
+/// Clone or pull a subscription and record its commit in the lockfile.
+async fn sync_and_lock(
+    subs: &mut SubscriptionManager,
+    subscription: &PluginSubscription,
+) -> PluginServiceResult<SyncResult> {
+    let mut manager = subs.clone();
+    let sub = subscription.clone();
+    let (manager, result) = tokio::task::spawn_blocking(move || {
+        let result = manager.sync_subscription(&sub);
+        if result.is_success() {
+            match manager.lock_subscription(&sub) {
+                Ok(Some(_)) => {
+                    if let Err(e) = manager.save_lockfile() {
+                        warn!(subscription = %sub.id(), error = %e, "failed to save lockfile");
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!(subscription = %sub.id(), error = %e, "failed to lock plugin"),
+            }
+        }
+        (manager, result)
+    })
+    .await
+    .map_err(|e| PluginServiceError::Sync(e.to_string()))?;
+
+    *subs = manager;
+    if result.is_failure() {
+        let error = result.error.as_deref().unwrap_or("unknown error");
+        return Err(PluginServiceError::Sync(format!(
+            "{} {}: {error}",
+            result.action, result.subscription_id
+        )));
+    }
+    Ok(result)
+}
+
+/// Enable key for a plugin found in a plugin directory.
+fn local_id(name: &str) -> String {
+    format!("{name}@local")
+}
+
+/// The enable key and subscription for a loaded plugin.
+fn plugin_id(
+    subs: &SubscriptionManager,
+    dir: &Path,
+    name: &str,
+) -> (String, Option<PluginSubscription>) {
+    subs.subscriptions()
+        .into_iter()
+        .find(|(s, _)| subs.plugin_dir_for(s).as_deref() == Some(dir))
+        .map(|(s, _)| (s.id(), Some(s)))
+        .unwrap_or_else(|| (local_id(name), None))
+}
+
+fn is_enabled(subs: &SubscriptionManager, id: &str) -> bool {
+    let subscription = subs
+        .subscriptions()
+        .into_iter()
+        .find(|(s, _)| s.id() == id)
+        .map(|(s, _)| s);
+    is_enabled_with(subs, id, subscription.as_ref())
+}
+
+fn is_enabled_with(
+    subs: &SubscriptionManager,
+    id: &str,
+    subscription: Option<&PluginSubscription>,
+) -> bool {
+    subs.is_enabled(id)
+        .unwrap_or_else(|| subscription.is_none_or(|s| s.enabled))
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.display().to_string())
+}
+
+fn describe_plugin(
+    id: String,
+    subscription: Option<PluginSubscription>,
+    dir: Option<&Path>,
+    plugin: Option<&LoadedPlugin>,
+    enabled: bool,
+    loaded: bool,
+    error: Option<String>,
+) -> PluginInfo {
+    let name = plugin
+        .map(|p| p.manifest.name.clone())
+        .unwrap_or_else(|| match dir {
+            Some(dir) if subscription.is_none() => dir_name(dir),
+            _ => id.clone(),
+        });
+    PluginInfo {
+        name,
+        version: plugin.and_then(|p| p.manifest.version.clone()),
+        description: plugin.and_then(|p| p.manifest.description.clone()),
+        commit: None,
+        path: dir.map(Path::to_path_buf),
+        enabled,
+        loaded,
+        capabilities: plugin.map(|p| p.manifest.capability_summary(&p.plugin_dir)),
+        skills: plugin
+            .map(|p| {
+                p.skill_contents
+                    .iter()
+                    .map(|s| s.def.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        agents: plugin
+            .map(|p| {
+                p.agent_configs
+                    .iter()
+                    .map(|a| a.config.agent.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        hooks: plugin
+            .and_then(|p| p.hooks_config.as_ref())
+            .map(|h| h.hooks.values().map(Vec::len).sum())
+            .unwrap_or(0),
+        mcp_servers: plugin
+            .map(|p| p.mcp_servers.iter().map(|s| s.name.clone()).collect())
+            .unwrap_or_default(),
+        error,
+        id,
+        subscription,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_plugin::{PluginManager, RuntimePluginsConfig};
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, name: &str) -> PathBuf {
+        let root = dir.join(name);
+        std::fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            root.join(MANIFEST_PATH),
+            format!(r#"{{"name": "{name}", "version": "1.0.0", "skills": "./skills/"}}"#),
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("skills/hello")).unwrap();
+        std::fs::write(
+            root.join("skills/hello/SKILL.md"),
+            "---\nname: hello\ndescription: Say hello\n---\nSay hello.",
+        )
+        .unwrap();
+        root
+    }
+
+    async fn service(tmp: &Path) -> PluginService {
+        let project = tmp.join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let subs = SubscriptionManager::new(Vec::new(), Some(&project))
+            .unwrap()
+            .with_global_config_path(tmp.join("plugins.json"))
+            .unwrap()
+            .with_cache_dir(tmp.join("cache"));
+        let watcher = PluginWatcher::new(PluginManager::new(vec![tmp.join("plugins")]));
+        let service = PluginService::new(Arc::new(watcher), PluginRuntime::new(), subs);
+        service.load_initial().await;
+        service
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_enable_apply_live() {
+        let tmp = TempDir::new().unwrap();
+        write_plugin(&tmp.path().join("plugins"), "greeter");
+        let service = service(tmp.path()).await;
+
+        let plugins = service.list().await;
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].id, "greeter@local");
+        assert_eq!(plugins[0].skills, vec!["hello"]);
+        assert!(plugins[0].enabled && plugins[0].loaded);
+        assert_eq!(service.runtime().skill_count(), 1);
+
+        let plugin = service.set_enabled("greeter", false).await.unwrap();
+        assert!(!plugin.enabled && !plugin.loaded);
+        assert_eq!(plugin.version.as_deref(), Some("1.0.0"));
+        assert_eq!(service.runtime().skill_count(), 0);
+        let global = RuntimePluginsConfig::load(&tmp.path().join("plugins.json")).unwrap();
+        assert_eq!(global.is_enabled("greeter@local"), Some(false));
+
+        // A restart keeps it disabled
+        let restarted = self::service(tmp.path()).await;
+        assert_eq!(restarted.runtime().skill_count(), 0);
+        assert!(!restarted.get("greeter@local").await.unwrap().loaded);
+
+        let plugin = service.set_enabled("greeter@local", true).await.unwrap();
+        assert!(plugin.enabled && plugin.loaded);
+        assert_eq!(service.runtime().skill_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_install_local_and_remove() {
+        let tmp = TempDir::new().unwrap();
+        let source = write_plugin(&tmp.path().join("elsewhere"), "local-tool");
+        let service = service(tmp.path()).await;
+
+        let missing = service
+            .install(&tmp.path().join("nope").display().to_string(), None, false)
+            .await;
+        assert!(matches!(missing, Err(PluginServiceError::Rejected(_))));
+
+        let plugin = service
+            .install(&source.display().to_string(), None, true)
+            .await
+            .unwrap();
+        assert_eq!(plugin.name, "local-tool");
+        assert!(plugin.id.starts_with("local/"));
+        assert!(plugin.loaded);
+        assert_eq!(service.runtime().skill_count(), 1);
+        let project =
+            RuntimePluginsConfig::load(&tmp.path().join("project/.arawn/plugins.json")).unwrap();
+        assert_eq!(project.subscriptions.len(), 1);
+
+        let duplicate = service
+            .install(&source.display().to_string(), None, false)
+            .await;
+        assert!(matches!(duplicate, Err(PluginServiceError::Rejected(_))));
+
+        let (result, _) = service.sync("local-tool").await.unwrap();
+        assert_eq!(result.action, SyncAction::Skipped);
+
+        service.remove("local-tool", true).await.unwrap();
+        assert!(source.exists(), "local plugins are never deleted");
+        assert!(service.list().await.is_empty());
+        assert_eq!(service.runtime().skill_count(), 0);
+        assert!(matches!(
+            service.get("local-tool").await,
+            Err(PluginServiceError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_remove_rejects_directory_plugins() {
+        let tmp = TempDir::new().unwrap();
+        write_plugin(&tmp.path().join("plugins"), "greeter");
+        let service = service(tmp.path()).await;
+
+        let err = service.remove("greeter", false).await.unwrap_err();
+        assert!(matches!(err, PluginServiceError::Rejected(_)));
+    }
+}
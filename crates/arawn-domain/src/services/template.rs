@@ -0,0 +1,912 @@
+//! Workstream templates.
+//!
+//! A template is a directory that scaffolds a new workstream:
+//!
+//! ```text
+//! template.toml         description, parameters, tags, settings, notes, repos
+//! production/...        copied into the workstream's production directory
+//! work/...              copied into the workstream's work directory
+//! workflows/*.toml      registered as workflows
+//! ```
+//!
+//! Templates come from the configured templates directory or from plugins,
+//! which offer theirs as `<plugin>:<template>`. Strings in `template.toml`,
+//! text files and file names may use `{{variable}}` placeholders, filled
+//! from the template's parameters and the built-in `title`, `workstream_id`
+//! and `date` variables. Unknown placeholders are left as they are.
+//!
+//! ```toml
+//! description = "Research project"
+//! tags = ["research"]
+//!
+//! [[params]]
+//! name = "topic"
+//! description = "What the research is about"
+//!
+//! [settings]
+//! system_prompt = "You are helping research {{topic}}."
+//! bootstrap_files = ["BRIEF.md"]
+//!
+//! [[notes]]
+//! title = "Open questions"
+//! content = "What is already known about {{topic}}?"
+//!
+//! [[repos]]
+//! url = "https://github.com/example/{{topic}}-notes"
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use arawn_memory::MemoryStore;
+use arawn_memory::types::Note;
+use arawn_types::AgentSettings;
+use arawn_workstream::store::Workstream;
+use arawn_workstream::{WorkstreamError, WorkstreamManager};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+/// File describing a template, at the root of its directory.
+pub const TEMPLATE_MANIFEST: &str = "template.toml";
+
+/// Variables every template can use without declaring them.
+const BUILTIN_VARS: [&str; 3] = ["title", "workstream_id", "date"];
+
+/// Errors from loading or applying a workstream template.
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    /// No template with this name exists.
+    #[error("Template not found: {0}")]
+    NotFound(String),
+
+    /// The template's `template.toml` could not be used.
+    #[error("Invalid template '{name}': {reason}")]
+    Invalid { name: String, reason: String },
+
+    /// The parameters given don't match what the template declares.
+    #[error("Invalid template parameters: {0}")]
+    Params(String),
+
+    /// Workstream storage error.
+    #[error("Workstream error: {0}")]
+    Workstream(#[from] WorkstreamError),
+
+    /// Memory storage error.
+    #[error("Memory error: {0}")]
+    Memory(#[from] arawn_memory::MemoryError),
+
+    /// Filesystem error.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Result type for template operations.
+pub type TemplateResult<T> = std::result::Result<T, TemplateError>;
+
+/// Where a template was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum TemplateSource {
+    /// The configured templates directory.
+    Directory,
+    /// A plugin's templates directory.
+    Plugin { plugin: String },
+}
+
+/// A parameter a template asks for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateParam {
+    /// Name used in `{{name}}` placeholders.
+    pub name: String,
+    /// What the value is for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when none is given. Parameters without one are required.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+impl TemplateParam {
+    /// Whether a value must be given.
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+    }
+}
+
+/// A note created in every workstream made from the template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TemplateNote {
+    #[serde(default)]
+    title: Option<String>,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// A repository cloned into `production/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TemplateRepo {
+    url: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Contents of `template.toml`, minus the parameter declarations.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TemplateBody {
+    default_model: Option<String>,
+    tags: Vec<String>,
+    settings: AgentSettings,
+    notes: Vec<TemplateNote>,
+    repos: Vec<TemplateRepo>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct TemplateManifestFile {
+    description: Option<String>,
+    params: Vec<TemplateParam>,
+    #[serde(flatten)]
+    body: toml::Table,
+}
+
+/// A template found on disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkstreamTemplate {
+    /// Name to create workstreams with (`<plugin>:<template>` for plugins).
+    pub name: String,
+    /// What the template is for.
+    pub description: Option<String>,
+    /// Where the template was found.
+    pub source: TemplateSource,
+    /// Parameters the template asks for.
+    pub params: Vec<TemplateParam>,
+    /// Template directory.
+    #[serde(skip)]
+    pub dir: PathBuf,
+    #[serde(skip)]
+    body: TemplateBody,
+}
+
+/// A workstream to create from a template.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRequest {
+    /// Template name.
+    pub template: String,
+    /// Title of the new workstream.
+    pub title: String,
+    /// Default model; overrides the template's.
+    pub default_model: Option<String>,
+    /// Tags, added to the template's.
+    pub tags: Vec<String>,
+    /// Values for the template's parameters.
+    pub params: HashMap<String, String>,
+}
+
+/// What applying a template produced.
+#[derive(Debug, Clone)]
+pub struct TemplateOutcome {
+    /// The new workstream.
+    pub workstream: Workstream,
+    /// Files copied into `production/` and `work/`.
+    pub files: usize,
+    /// Notes created.
+    pub notes: usize,
+    /// Workflow files written to the workflow directory.
+    pub workflows: Vec<String>,
+    /// Paths of cloned repositories, relative to `production/`.
+    pub repos: Vec<String>,
+    /// Parts of the template that could not be applied.
+    pub warnings: Vec<String>,
+}
+
+/// Finds templates and creates workstreams from them.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    roots: Vec<(PathBuf, TemplateSource)>,
+    workflow_dir: Option<PathBuf>,
+}
+
+impl TemplateRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory whose subdirectories are templates.
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.roots.push((dir.into(), TemplateSource::Directory));
+        self
+    }
+
+    /// Adds a plugin's templates directory.
+    pub fn with_plugin_dir(mut self, plugin: &str, dir: impl Into<PathBuf>) -> Self {
+        self.roots.push((
+            dir.into(),
+            TemplateSource::Plugin {
+                plugin: plugin.to_string(),
+            },
+        ));
+        self
+    }
+
+    /// Sets where template workflows are written. The workflow loader picks
+    /// them up from there; without it, template workflows are skipped.
+    pub fn with_workflow_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.workflow_dir = Some(dir.into());
+        self
+    }
+
+    /// Lists available templates, sorted by name.
+    ///
+    /// Directories are scanned on every call, so templates added or edited
+    /// on disk show up without a restart. Invalid templates are skipped with
+    /// a warning, and the first of two templates with the same name wins.
+    pub fn list(&self) -> Vec<WorkstreamTemplate> {
+        let mut seen = HashSet::new();
+        let mut templates = Vec::new();
+
+        for (root, source) in &self.roots {
+            let Ok(entries) = fs::read_dir(root) else {
+                continue;
+            };
+            let mut dirs: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.join(TEMPLATE_MANIFEST).is_file())
+                .collect();
+            dirs.sort();
+
+            for dir in dirs {
+                match load_template(&dir, source) {
+                    Ok(template) => {
+                        if seen.insert(template.name.clone()) {
+                            templates.push(template);
+                        }
+                    }
+                    Err(e) => warn!(dir = %dir.display(), error = %e, "Skipping template"),
+                }
+            }
+        }
+
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    /// Looks up a template by name.
+    pub fn get(&self, name: &str) -> TemplateResult<WorkstreamTemplate> {
+        self.list()
+            .into_iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| TemplateError::NotFound(name.to_string()))
+    }
+
+    /// Creates a workstream from a template.
+    ///
+    /// Parameters are checked before anything is created. Notes need a
+    /// memory store and files need the manager's directory manager; parts
+    /// that can't be applied (including failed clones and workflow files
+    /// that already exist) are reported as warnings. If copying files
+    /// fails, the half-built workstream is archived.
+    pub fn create_workstream(
+        &self,
+        workstreams: &WorkstreamManager,
+        memory: Option<&MemoryStore>,
+        request: &TemplateRequest,
+    ) -> TemplateResult<TemplateOutcome> {
+        let template = self.get(&request.template)?;
+        let mut vars = template.resolve_params(&request.params)?;
+        vars.insert("title".to_string(), request.title.clone());
+        vars.insert(
+            "date".to_string(),
+            chrono::Utc::now().format("%Y-%m-%d").to_string(),
+        );
+
+        // Everything but the workstream ID is known up front, so tags and the
+        // default model can be set at creation
+        let preview = template.render_body(&vars)?;
+        let mut tags = request.tags.clone();
+        for tag in preview.tags {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let default_model = request.default_model.clone().or(preview.default_model);
+        let workstream =
+            workstreams.create_workstream(&request.title, default_model.as_deref(), &tags)?;
+
+        match self.apply(&template, &workstream, vars, workstreams, memory) {
+            Ok(outcome) => {
+                tracing::info!(
+                    template = %template.name,
+                    workstream_id = %outcome.workstream.id,
+                    files = outcome.files,
+                    notes = outcome.notes,
+                    workflows = outcome.workflows.len(),
+                    repos = outcome.repos.len(),
+                    "Created workstream from template"
+                );
+                Ok(outcome)
+            }
+            Err(e) => {
+                if let Err(archive_err) = workstreams.archive_workstream(&workstream.id) {
+                    warn!(
+                        workstream_id = %workstream.id,
+                        error = %archive_err,
+                        "Failed to archive workstream after template error"
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn apply(
+        &self,
+        template: &WorkstreamTemplate,
+        workstream: &Workstream,
+        mut vars: HashMap<String, String>,
+        workstreams: &WorkstreamManager,
+        memory: Option<&MemoryStore>,
+    ) -> TemplateResult<TemplateOutcome> {
+        vars.insert("workstream_id".to_string(), workstream.id.clone());
+        let body = template.render_body(&vars)?;
+
+        let mut outcome = TemplateOutcome {
+            workstream: workstream.clone(),
+            files: 0,
+            notes: 0,
+            workflows: Vec::new(),
+            repos: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        if !body.settings.is_default() {
+            outcome.workstream = workstreams.update_settings(&workstream.id, &body.settings)?;
+        }
+
+        // Files
+        let dm = workstreams.directory_manager();
+        for (subdir, dest) in [
+            (
+                "production",
+                dm.map(|dm| dm.production_path(&workstream.id)),
+            ),
+            ("work", dm.map(|dm| dm.work_path(&workstream.id))),
+        ] {
+            let src = template.dir.join(subdir);
+            if !src.is_dir() {
+                continue;
+            }
+            match dest {
+                Some(dest) => {
+                    outcome.files += copy_rendered(&src, &dest, &vars, &mut outcome.warnings)?
+                }
+                None => outcome.warnings.push(format!(
+                    "Directory management not configured; skipped {subdir}/ files"
+                )),
+            }
+        }
+
+        // Notes, linked to the workstream by tagging them with its ID
+        if !body.notes.is_empty() {
+            match memory {
+                Some(store) => {
+                    for spec in &body.notes {
+                        let mut note = Note::new(&spec.content).with_tag(&workstream.id);
+                        if let Some(ref title) = spec.title {
+                            note = note.with_title(title);
+                        }
+                        for tag in &spec.tags {
+                            note = note.with_tag(tag);
+                        }
+                        store.insert_note(&note)?;
+                        outcome.notes += 1;
+                    }
+                }
+                None => outcome.warnings.push(format!(
+                    "Memory not configured; skipped {} note(s)",
+                    body.notes.len()
+                )),
+            }
+        }
+
+        // Workflows
+        let workflows_src = template.dir.join("workflows");
+        if workflows_src.is_dir() {
+            match self.workflow_dir {
+                Some(ref workflow_dir) => {
+                    self.write_workflows(&workflows_src, workflow_dir, &vars, &mut outcome)?
+                }
+                None => outcome
+                    .warnings
+                    .push("Workflows not configured; skipped template workflows".to_string()),
+            }
+        }
+
+        // Repositories
+        for repo in &body.repos {
+            let Some(dm) = dm else {
+                outcome.warnings.push(format!(
+                    "Directory management not configured; skipped clone of {}",
+                    repo.url
+                ));
+                continue;
+            };
+            match dm.clone_repo(&workstream.id, &repo.url, repo.name.as_deref()) {
+                Ok(result) => {
+                    let prod = dm.production_path(&workstream.id);
+                    let rel = result.path.strip_prefix(&prod).unwrap_or(&result.path);
+                    outcome.repos.push(rel.to_string_lossy().to_string());
+                }
+                Err(e) => outcome
+                    .warnings
+                    .push(format!("Failed to clone {}: {}", repo.url, e)),
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Writes rendered `*.toml` workflow files, leaving existing files alone.
+    fn write_workflows(
+        &self,
+        src: &Path,
+        workflow_dir: &Path,
+        vars: &HashMap<String, String>,
+        outcome: &mut TemplateOutcome,
+    ) -> TemplateResult<()> {
+        fs::create_dir_all(workflow_dir)?;
+
+        let mut files: Vec<PathBuf> = fs::read_dir(src)?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let file_name = render(&path.file_name().unwrap().to_string_lossy(), vars);
+            if !is_safe_relative(Path::new(&file_name)) || file_name.contains('/') {
+                outcome
+                    .warnings
+                    .push(format!("Skipped workflow with unsafe name: {file_name}"));
+                continue;
+            }
+            let content = render(&fs::read_to_string(&path)?, vars);
+            if let Err(e) = content.parse::<toml::Table>() {
+                outcome
+                    .warnings
+                    .push(format!("Skipped invalid workflow {file_name}: {e}"));
+                continue;
+            }
+
+            let dest = workflow_dir.join(&file_name);
+            if dest.exists() {
+                outcome.warnings.push(format!(
+                    "Workflow {file_name} already exists; left unchanged"
+                ));
+                continue;
+            }
+            fs::write(&dest, content)?;
+            outcome.workflows.push(file_name);
+        }
+        Ok(())
+    }
+}
+
+impl WorkstreamTemplate {
+    /// Checks `values` against the declared parameters and fills in defaults.
+    fn resolve_params(
+        &self,
+        values: &HashMap<String, String>,
+    ) -> TemplateResult<HashMap<String, String>> {
+        let mut unknown: Vec<&str> = values
+            .keys()
+            .filter(|k| !self.params.iter().any(|p| &p.name == *k))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(TemplateError::Params(format!(
+                "unknown parameter(s): {}",
+                unknown.join(", ")
+            )));
+        }
+
+        let mut resolved = HashMap::new();
+        let mut missing = Vec::new();
+        for param in &self.params {
+            match values.get(&param.name).or(param.default.as_ref()) {
+                Some(value) => {
+                    resolved.insert(param.name.clone(), value.clone());
+                }
+                None => missing.push(param.name.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(TemplateError::Params(format!(
+                "missing required parameter(s): {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(resolved)
+    }
+
+    /// Renders every string in the template body.
+    fn render_body(&self, vars: &HashMap<String, String>) -> TemplateResult<TemplateBody> {
+        let invalid = |e: serde_json::Error| TemplateError::Invalid {
+            name: self.name.clone(),
+            reason: e.to_string(),
+        };
+        let value = serde_json::to_value(&self.body).map_err(invalid)?;
+        serde_json::from_value(render_json(value, vars)).map_err(invalid)
+    }
+}
+
+/// Reads a template directory.
+fn load_template(dir: &Path, source: &TemplateSource) -> TemplateResult<WorkstreamTemplate> {
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match source {
+        TemplateSource::Directory => dir_name,
+        TemplateSource::Plugin { plugin } => format!("{plugin}:{dir_name}"),
+    };
+    let invalid = |reason: String| TemplateError::Invalid {
+        name: name.clone(),
+        reason,
+    };
+
+    let content = fs::read_to_string(dir.join(TEMPLATE_MANIFEST))?;
+    let file: TemplateManifestFile =
+        toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let body: TemplateBody = file
+        .body
+        .try_into()
+        .map_err(|e: toml::de::Error| invalid(e.to_string()))?;
+
+    let mut names = HashSet::new();
+    for param in &file.params {
+        let valid = !param.name.is_empty()
+            && param
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(invalid(format!("invalid parameter name '{}'", param.name)));
+        }
+        if BUILTIN_VARS.contains(&param.name.as_str()) {
+            return Err(invalid(format!(
+                "parameter '{}' shadows a built-in variable",
+                param.name
+            )));
+        }
+        if !names.insert(param.name.as_str()) {
+            return Err(invalid(format!("duplicate parameter '{}'", param.name)));
+        }
+    }
+
+    Ok(WorkstreamTemplate {
+        name,
+        description: file.description,
+        source: source.clone(),
+        params: file.params,
+        dir: dir.to_path_buf(),
+        body,
+    })
+}
+
+/// Replaces `{{name}}` placeholders whose name is in `vars`.
+fn render(text: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        out.push_str(&rest[..start]);
+        match vars.get(key) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + len + 4]),
+        }
+        rest = &rest[start + len + 4..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn render_json(value: serde_json::Value, vars: &HashMap<String, String>) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::String(s) => Value::String(render(&s, vars)),
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|v| render_json(v, vars)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, render_json(v, vars)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Copies `src` into `dest`, rendering file names and UTF-8 file contents.
+/// Existing files are not overwritten. Returns the number of files copied.
+fn copy_rendered(
+    src: &Path,
+    dest: &Path,
+    vars: &HashMap<String, String>,
+    warnings: &mut Vec<String>,
+) -> TemplateResult<usize> {
+    let mut copied = 0;
+    for entry in walkdir(src)? {
+        let rel = entry.strip_prefix(src).unwrap_or(&entry);
+        let rendered = PathBuf::from(render(&rel.to_string_lossy(), vars));
+        if !is_safe_relative(&rendered) {
+            warnings.push(format!(
+                "Skipped file with unsafe path: {}",
+                rendered.display()
+            ));
+            continue;
+        }
+
+        let target = dest.join(&rendered);
+        if target.exists() {
+            warnings.push(format!(
+                "{} already exists; left unchanged",
+                rendered.display()
+            ));
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let bytes = fs::read(&entry)?;
+        match String::from_utf8(bytes) {
+            Ok(text) => fs::write(&target, render(&text, vars))?,
+            Err(e) => fs::write(&target, e.into_bytes())?,
+        }
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Regular files under `dir`, sorted.
+fn walkdir(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            files.extend(walkdir(&entry.path())?);
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_workstream::{DirectoryManager, WorkstreamConfig};
+    use tempfile::TempDir;
+
+    struct Env {
+        dir: TempDir,
+        manager: WorkstreamManager,
+        memory: MemoryStore,
+        registry: TemplateRegistry,
+    }
+
+    const RESEARCH: &str = r#"
+description = "Research project"
+tags = ["research", "{{topic}}"]
+default_model = "sonnet"
+
+[[params]]
+name = "topic"
+description = "What the research is about"
+
+[[params]]
+name = "depth"
+default = "shallow"
+
+[settings]
+system_prompt = "Research {{topic}} ({{depth}})."
+bootstrap_files = ["BRIEF.md"]
+
+[[notes]]
+title = "Questions about {{topic}}"
+content = "Started {{date}} in {{workstream_id}}"
+tags = ["questions"]
+"#;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn create_env() -> Env {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path().join("workstreams");
+        let config = WorkstreamConfig {
+            db_path: dir.path().join("workstreams.db"),
+            data_dir: data_dir.clone(),
+            session_timeout_minutes: 60,
+        };
+        let manager = WorkstreamManager::new(&config)
+            .unwrap()
+            .with_directory_manager(DirectoryManager::new(&data_dir));
+
+        let templates = dir.path().join("templates");
+        let research = templates.join("research");
+        write(&research.join(TEMPLATE_MANIFEST), RESEARCH);
+        write(
+            &research.join("production/BRIEF.md"),
+            "# {{title}}\n\nTopic: {{topic}}, kept as {{unknown}}\n",
+        );
+        write(&research.join("work/{{topic}}/scratch.txt"), "notes");
+        write(
+            &research.join("workflows/{{workstream_id}}-digest.toml"),
+            "[workflow]\nname = \"{{workstream_id}}-digest\"\n",
+        );
+
+        let registry = TemplateRegistry::new()
+            .with_dir(&templates)
+            .with_workflow_dir(dir.path().join("workflows"));
+
+        Env {
+            dir,
+            manager,
+            memory: MemoryStore::open_in_memory().unwrap(),
+            registry,
+        }
+    }
+
+    fn request(params: &[(&str, &str)]) -> TemplateRequest {
+        TemplateRequest {
+            template: "research".to_string(),
+            title: "Fusion".to_string(),
+            tags: vec!["physics".to_string()],
+            params: params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let vars = HashMap::from([("a".to_string(), "1".to_string())]);
+        assert_eq!(render("x{{a}}y{{ a }}z", &vars), "x1y1z");
+        assert_eq!(render("{{b}} {{a", &vars), "{{b}} {{a");
+    }
+
+    #[test]
+    fn test_list_templates() {
+        let env = create_env();
+        let plugin_dir = env.dir.path().join("plugin-templates");
+        write(
+            &plugin_dir.join("paper").join(TEMPLATE_MANIFEST),
+            "description = \"Paper\"",
+        );
+        write(
+            &plugin_dir.join("broken").join(TEMPLATE_MANIFEST),
+            "colour = \"blue\"",
+        );
+        let registry = env.registry.with_plugin_dir("writing", &plugin_dir);
+
+        let templates = registry.list();
+        let names: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["research", "writing:paper"]);
+        assert_eq!(templates[0].params.len(), 2);
+        assert!(templates[0].params[0].is_required());
+        assert_eq!(
+            templates[1].source,
+            TemplateSource::Plugin {
+                plugin: "writing".to_string()
+            }
+        );
+        assert!(matches!(
+            registry.get("missing"),
+            Err(TemplateError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_workstream_from_template() {
+        let env = create_env();
+        let outcome = env
+            .registry
+            .create_workstream(
+                &env.manager,
+                Some(&env.memory),
+                &request(&[("topic", "tokamaks")]),
+            )
+            .unwrap();
+
+        let ws = &outcome.workstream;
+        assert_eq!(ws.title, "Fusion");
+        assert_eq!(ws.default_model.as_deref(), Some("sonnet"));
+        assert_eq!(
+            ws.settings.system_prompt.as_deref(),
+            Some("Research tokamaks (shallow).")
+        );
+        assert_eq!(ws.settings.bootstrap_files, vec!["BRIEF.md"]);
+        assert_eq!(
+            env.manager.get_tags(&ws.id).unwrap(),
+            vec!["physics", "research", "tokamaks"]
+        );
+
+        let dm = env.manager.directory_manager().unwrap();
+        assert_eq!(outcome.files, 2);
+        assert_eq!(
+            fs::read_to_string(dm.production_path(&ws.id).join("BRIEF.md")).unwrap(),
+            "# Fusion\n\nTopic: tokamaks, kept as {{unknown}}\n"
+        );
+        assert!(dm.work_path(&ws.id).join("tokamaks/scratch.txt").exists());
+
+        let notes = env.memory.list_notes_by_tag(&ws.id, 10).unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].title.as_deref(), Some("Questions about tokamaks"));
+        assert!(notes[0].content.ends_with(&ws.id));
+
+        let workflow = format!("{}-digest.toml", ws.id);
+        assert_eq!(outcome.workflows, vec![workflow.clone()]);
+        let content = fs::read_to_string(env.dir.path().join("workflows").join(workflow)).unwrap();
+        assert!(content.contains(&format!("name = \"{}-digest\"", ws.id)));
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+    }
+
+    #[test]
+    fn test_create_workstream_checks_params_first() {
+        let env = create_env();
+        for params in [&[][..], &[("topic", "x"), ("colour", "blue")][..]] {
+            let err = env
+                .registry
+                .create_workstream(&env.manager, None, &request(params))
+                .unwrap_err();
+            assert!(matches!(err, TemplateError::Params(_)), "{err}");
+        }
+        assert!(env.manager.list_workstreams().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_create_workstream_without_memory_warns() {
+        let env = create_env();
+        let outcome = env
+            .registry
+            .create_workstream(&env.manager, None, &request(&[("topic", "t")]))
+            .unwrap();
+        assert_eq!(outcome.notes, 0);
+        assert_eq!(outcome.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_params_rejected_on_load() {
+        let dir = TempDir::new().unwrap();
+        for (name, manifest) in [
+            ("shadow", "[[params]]\nname = \"title\""),
+            ("spaces", "[[params]]\nname = \"two words\""),
+        ] {
+            write(&dir.path().join(name).join(TEMPLATE_MANIFEST), manifest);
+        }
+        let registry = TemplateRegistry::new().with_dir(dir.path());
+        assert!(registry.list().is_empty());
+    }
+}
@@ -0,0 +1,680 @@
+//! Workflow and runtime catalog management.
+//!
+//! The same operations the agent has through its `workflow` and `catalog`
+//! tools, for transport layers. Workflows live as `<name>.toml` files in the
+//! workflow directory and are registered with the [`PipelineEngine`] as they
+//! are saved; runtimes are `.wasm` modules in the [`RuntimeCatalog`].
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arawn_pipeline::sandbox::ScriptExecutor;
+use arawn_pipeline::{
+    CatalogEntry, ExecutionResult, PipelineEngine, PipelineError, RuntimeCatalog, RuntimeCategory,
+    ScheduleInfo, ValidationIssue, WorkflowDefinition, WorkflowFile, build_executor_factory,
+};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Errors from managing workflows and runtimes.
+#[derive(Debug, Error)]
+pub enum WorkflowError {
+    /// No workflow, runtime or schedule with this name or ID.
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// The workflow definition was rejected; `issues` lists every problem.
+    #[error("Invalid workflow '{name}': {}", issues.first().map(|i| i.message.as_str()).unwrap_or("invalid"))]
+    Invalid {
+        name: String,
+        issues: Vec<ValidationIssue>,
+    },
+
+    /// The request can't be carried out (bad name, builtin runtime, ...).
+    #[error("{0}")]
+    Rejected(String),
+
+    /// Pipeline engine, compiler or catalog error.
+    #[error(transparent)]
+    Pipeline(#[from] PipelineError),
+
+    /// Reading or writing workflow or runtime files failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type for workflow operations.
+pub type WorkflowResult<T> = std::result::Result<T, WorkflowError>;
+
+/// A workflow known to the engine, the workflow directory, or both.
+#[derive(Debug, Clone)]
+pub struct WorkflowSummary {
+    /// Workflow name.
+    pub name: String,
+    /// Description from the definition file.
+    pub description: String,
+    /// Whether the engine can run it.
+    pub registered: bool,
+    /// Definition file, if the workflow has one.
+    pub path: Option<PathBuf>,
+    /// Number of tasks in the definition.
+    pub task_count: usize,
+}
+
+/// A workflow with its definition and cron schedules.
+#[derive(Debug, Clone)]
+pub struct WorkflowDetail {
+    /// Name, registration and file.
+    pub summary: WorkflowSummary,
+    /// Parsed definition (None for workflows registered without a file).
+    pub definition: Option<WorkflowDefinition>,
+    /// TOML source of the definition file.
+    pub source: Option<String>,
+    /// Cron schedules for this workflow.
+    pub schedules: Vec<ScheduleInfo>,
+}
+
+/// How a runtime's `.wasm` module is provided.
+#[derive(Debug, Clone)]
+pub enum RuntimeSource {
+    /// Rust source to compile to `wasm32-wasip1`.
+    Rust(String),
+    /// A pre-built module on the server's filesystem.
+    WasmPath(PathBuf),
+}
+
+/// A runtime catalog entry.
+#[derive(Debug, Clone)]
+pub struct RuntimeInfo {
+    /// Runtime name, as used in a task's `runtime` field.
+    pub name: String,
+    /// Catalog entry.
+    pub entry: CatalogEntry,
+    /// Absolute path of the module.
+    pub resolved_path: Option<PathBuf>,
+    /// Whether the module file exists.
+    pub wasm_exists: bool,
+}
+
+/// Workflow and runtime catalog service.
+#[derive(Clone)]
+pub struct WorkflowService {
+    engine: Arc<PipelineEngine>,
+    workflow_dir: PathBuf,
+    executor: Arc<ScriptExecutor>,
+    catalog: Arc<RwLock<RuntimeCatalog>>,
+}
+
+impl WorkflowService {
+    /// Create a workflow service over the given engine, workflow directory,
+    /// script executor and runtime catalog.
+    pub fn new(
+        engine: Arc<PipelineEngine>,
+        workflow_dir: impl Into<PathBuf>,
+        executor: Arc<ScriptExecutor>,
+        catalog: Arc<RwLock<RuntimeCatalog>>,
+    ) -> Self {
+        Self {
+            engine,
+            workflow_dir: workflow_dir.into(),
+            executor,
+            catalog,
+        }
+    }
+
+    /// The pipeline engine.
+    pub fn engine(&self) -> &Arc<PipelineEngine> {
+        &self.engine
+    }
+
+    /// The directory workflow definitions are saved to.
+    pub fn workflow_dir(&self) -> &Path {
+        &self.workflow_dir
+    }
+
+    // ── Workflows ───────────────────────────────────────────────────────
+
+    /// List workflows, sorted by name.
+    ///
+    /// Includes registered workflows without a file and definition files
+    /// that failed to register.
+    pub async fn list(&self) -> Vec<WorkflowSummary> {
+        let mut workflows: BTreeMap<String, WorkflowSummary> = BTreeMap::new();
+        for (path, definition) in self.definition_files() {
+            let registered = self.engine.has_workflow(&definition.name).await;
+            workflows.insert(
+                definition.name.clone(),
+                summary(&definition, registered, Some(path)),
+            );
+        }
+        for name in self.engine.list_workflows().await {
+            workflows
+                .entry(name.clone())
+                .or_insert_with(|| WorkflowSummary {
+                    name,
+                    description: String::new(),
+                    registered: true,
+                    path: None,
+                    task_count: 0,
+                });
+        }
+        workflows.into_values().collect()
+    }
+
+    /// Get a workflow with its definition and schedules.
+    pub async fn get(&self, name: &str) -> WorkflowResult<WorkflowDetail> {
+        let registered = self.engine.has_workflow(name).await;
+        let file = self.find_file(name);
+        if file.is_none() && !registered {
+            return Err(WorkflowError::NotFound(format!("Workflow '{name}'")));
+        }
+
+        let (summary, definition, source) = match file {
+            Some((path, definition)) => {
+                let source = std::fs::read_to_string(&path).ok();
+                (
+                    summary(&definition, registered, Some(path)),
+                    Some(definition),
+                    source,
+                )
+            }
+            None => (
+                WorkflowSummary {
+                    name: name.to_string(),
+                    description: String::new(),
+                    registered,
+                    path: None,
+                    task_count: 0,
+                },
+                None,
+                None,
+            ),
+        };
+
+        Ok(WorkflowDetail {
+            summary,
+            definition,
+            source,
+            schedules: self.schedules_for(name).await,
+        })
+    }
+
+    /// Validate a TOML definition without saving it.
+    ///
+    /// `name`, when given, must match the definition's `[workflow] name`.
+    pub fn check(&self, name: Option<&str>, toml: &str) -> WorkflowResult<WorkflowFile> {
+        let file = WorkflowFile::from_toml(toml).map_err(|e| WorkflowError::Invalid {
+            name: name.unwrap_or_default().to_string(),
+            issues: vec![ValidationIssue {
+                task: None,
+                message: e.to_string(),
+            }],
+        })?;
+
+        let mut issues = Vec::new();
+        // An empty name is already one of the definition's own issues
+        if !file.workflow.name.is_empty()
+            && let Err(e) = validate_name(&file.workflow.name)
+        {
+            issues.push(ValidationIssue {
+                task: None,
+                message: e,
+            });
+        }
+        if let Some(name) = name
+            && name != file.workflow.name
+        {
+            issues.push(ValidationIssue {
+                task: None,
+                message: format!(
+                    "Workflow name '{}' does not match '{}'",
+                    file.workflow.name, name
+                ),
+            });
+        }
+        issues.extend(file.workflow.issues());
+
+        if issues.is_empty() {
+            Ok(file)
+        } else {
+            Err(WorkflowError::Invalid {
+                name: file.workflow.name,
+                issues,
+            })
+        }
+    }
+
+    /// Validate, write and register a workflow definition.
+    ///
+    /// Returns the saved workflow and whether it is new. An existing file
+    /// for the same workflow is replaced.
+    pub async fn save(
+        &self,
+        name: Option<&str>,
+        toml: &str,
+    ) -> WorkflowResult<(WorkflowSummary, bool)> {
+        let file = self.check(name, toml)?;
+        let definition = file.workflow;
+        let name = definition.name.clone();
+
+        let factory = build_executor_factory(self.executor.clone(), self.catalog.clone());
+        let tasks = definition.to_dynamic_tasks(&factory)?;
+
+        std::fs::create_dir_all(&self.workflow_dir)?;
+        let existing = self.find_file(&name).map(|(path, _)| path);
+        let created = existing.is_none() && !self.engine.has_workflow(&name).await;
+        let path = existing.unwrap_or_else(|| self.workflow_dir.join(format!("{name}.toml")));
+        std::fs::write(&path, toml)?;
+
+        self.engine
+            .register_dynamic_workflow(&name, &definition.description, tasks)
+            .await?;
+        debug!(name = %name, path = %path.display(), "Workflow saved and registered");
+
+        Ok((summary(&definition, true, Some(path)), created))
+    }
+
+    /// Unregister a workflow and delete its definition file.
+    pub async fn delete(&self, name: &str) -> WorkflowResult<()> {
+        let file = self.find_file(name);
+        let registered = self.engine.unregister_workflow(name).await;
+        match file {
+            Some((path, _)) => std::fs::remove_file(path)?,
+            None if !registered => {
+                return Err(WorkflowError::NotFound(format!("Workflow '{name}'")));
+            }
+            None => {}
+        }
+        debug!(name = %name, "Workflow deleted");
+        Ok(())
+    }
+
+    /// Run a workflow to completion with `input` as the context's `input`.
+    pub async fn run(
+        &self,
+        name: &str,
+        input: serde_json::Value,
+    ) -> WorkflowResult<ExecutionResult> {
+        let mut ctx = cloacina_workflow::context::Context::new();
+        ctx.insert("input".to_string(), input)
+            .map_err(|e| WorkflowError::Rejected(format!("Failed to build context: {e}")))?;
+        self.engine.execute(name, ctx).await.map_err(not_found)
+    }
+
+    /// Fire a workflow trigger with each entry of `values` as a context key.
+    pub async fn trigger(
+        &self,
+        name: &str,
+        values: serde_json::Map<String, serde_json::Value>,
+    ) -> WorkflowResult<ExecutionResult> {
+        let mut ctx = cloacina_workflow::context::Context::new();
+        for (key, value) in values {
+            ctx.insert(key, value)
+                .map_err(|e| WorkflowError::Rejected(format!("Failed to build context: {e}")))?;
+        }
+        self.engine.trigger(name, ctx).await.map_err(not_found)
+    }
+
+    /// Register a cron schedule for a workflow. Returns the schedule ID.
+    pub async fn schedule(&self, name: &str, cron: &str, timezone: &str) -> WorkflowResult<String> {
+        self.engine
+            .schedule_cron(name, cron, timezone)
+            .await
+            .map_err(not_found)
+    }
+
+    /// All cron schedules.
+    pub async fn schedules(&self) -> WorkflowResult<Vec<ScheduleInfo>> {
+        Ok(self.engine.list_schedules().await?)
+    }
+
+    /// Cancel a cron schedule.
+    pub async fn cancel_schedule(&self, schedule_id: &str) -> WorkflowResult<()> {
+        let exists = self.schedules().await?.iter().any(|s| s.id == schedule_id);
+        if !exists {
+            return Err(WorkflowError::NotFound(format!("Schedule '{schedule_id}'")));
+        }
+        Ok(self.engine.cancel_schedule(schedule_id).await?)
+    }
+
+    async fn schedules_for(&self, name: &str) -> Vec<ScheduleInfo> {
+        match self.engine.list_schedules().await {
+            Ok(schedules) => schedules
+                .into_iter()
+                .filter(|s| s.workflow_name == name)
+                .collect(),
+            Err(e) => {
+                debug!("Failed to list schedules (cron may be disabled): {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Parsed definition files in the workflow directory.
+    fn definition_files(&self) -> Vec<(PathBuf, WorkflowDefinition)> {
+        let Ok(entries) = std::fs::read_dir(&self.workflow_dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| match WorkflowFile::from_file(&path) {
+                Ok(file) => Some((path, file.workflow)),
+                Err(e) => {
+                    warn!("Skipping unparseable workflow {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The definition file for a workflow, by `[workflow] name`.
+    fn find_file(&self, name: &str) -> Option<(PathBuf, WorkflowDefinition)> {
+        self.definition_files()
+            .into_iter()
+            .find(|(_, definition)| definition.name == name)
+    }
+
+    // ── Runtimes ────────────────────────────────────────────────────────
+
+    /// List runtime catalog entries, sorted by name.
+    pub async fn runtimes(&self) -> Vec<RuntimeInfo> {
+        let catalog = self.catalog.read().await;
+        catalog
+            .list()
+            .iter()
+            .map(|(name, entry)| runtime_info(&catalog, name, entry))
+            .collect()
+    }
+
+    /// Get a runtime catalog entry.
+    pub async fn runtime(&self, name: &str) -> WorkflowResult<RuntimeInfo> {
+        let catalog = self.catalog.read().await;
+        catalog
+            .get(name)
+            .map(|entry| runtime_info(&catalog, name, entry))
+            .ok_or_else(|| WorkflowError::NotFound(format!("Runtime '{name}'")))
+    }
+
+    /// Add or replace a custom runtime, compiling it first if given as
+    /// Rust source. Builtin runtimes can't be replaced.
+    pub async fn save_runtime(
+        &self,
+        name: &str,
+        description: String,
+        source: RuntimeSource,
+    ) -> WorkflowResult<RuntimeInfo> {
+        validate_name(name).map_err(WorkflowError::Rejected)?;
+        if self.is_builtin(name).await {
+            return Err(WorkflowError::Rejected(format!(
+                "Cannot replace builtin runtime '{name}'"
+            )));
+        }
+
+        let wasm_path = match source {
+            RuntimeSource::Rust(source) => self.executor.compile(&source).await?.wasm_path,
+            RuntimeSource::WasmPath(path) => {
+                if !path.is_file() {
+                    return Err(WorkflowError::Rejected(format!(
+                        "WASM file not found: {}",
+                        path.display()
+                    )));
+                }
+                path
+            }
+        };
+
+        let mut catalog = self.catalog.write().await;
+        let custom_dir = catalog.root().join("custom");
+        std::fs::create_dir_all(&custom_dir)?;
+        let filename = format!("{name}.wasm");
+        std::fs::copy(&wasm_path, custom_dir.join(&filename))?;
+
+        let entry = CatalogEntry {
+            description,
+            path: format!("custom/{filename}"),
+            category: RuntimeCategory::Custom,
+        };
+        catalog.add(name, entry.clone())?;
+        debug!(name = %name, "Runtime saved");
+
+        Ok(runtime_info(&catalog, name, &entry))
+    }
+
+    /// Remove a custom runtime and its module. Builtin runtimes can't be
+    /// removed.
+    pub async fn remove_runtime(&self, name: &str) -> WorkflowResult<()> {
+        let mut catalog = self.catalog.write().await;
+        match catalog.get(name) {
+            None => return Err(WorkflowError::NotFound(format!("Runtime '{name}'"))),
+            Some(entry) if entry.category == RuntimeCategory::Builtin => {
+                return Err(WorkflowError::Rejected(format!(
+                    "Cannot remove builtin runtime '{name}'"
+                )));
+            }
+            Some(_) => {}
+        }
+
+        if let Some(path) = catalog.resolve_path(name)
+            && path.exists()
+        {
+            std::fs::remove_file(path)?;
+        }
+        catalog.remove(name)?;
+        debug!(name = %name, "Runtime removed");
+        Ok(())
+    }
+
+    async fn is_builtin(&self, name: &str) -> bool {
+        self.catalog
+            .read()
+            .await
+            .get(name)
+            .is_some_and(|e| e.category == RuntimeCategory::Builtin)
+    }
+}
+
+fn summary(
+    definition: &WorkflowDefinition,
+    registered: bool,
+    path: Option<PathBuf>,
+) -> WorkflowSummary {
+    WorkflowSummary {
+        name: definition.name.clone(),
+        description: definition.description.clone(),
+        registered,
+        path,
+        task_count: definition.tasks.len(),
+    }
+}
+
+fn runtime_info(catalog: &RuntimeCatalog, name: &str, entry: &CatalogEntry) -> RuntimeInfo {
+    let resolved_path = catalog.resolve_path(name);
+    RuntimeInfo {
+        name: name.to_string(),
+        entry: entry.clone(),
+        wasm_exists: resolved_path.as_ref().is_some_and(|p| p.exists()),
+        resolved_path,
+    }
+}
+
+/// Map the engine's "no such workflow" to [`WorkflowError::NotFound`].
+fn not_found(e: PipelineError) -> WorkflowError {
+    match e {
+        PipelineError::WorkflowNotFound(name) => {
+            WorkflowError::NotFound(format!("Workflow '{name}'"))
+        }
+        e => e.into(),
+    }
+}
+
+/// Validate a workflow or runtime name for use as a file name.
+fn validate_name(name: &str) -> std::result::Result<(), String> {
+    if name.is_empty() {
+        return Err("Name cannot be empty".into());
+    }
+    if name.contains('/') || name.contains('\\') {
+        return Err(format!("Name '{name}' must not contain path separators"));
+    }
+    if name.contains("..") {
+        return Err(format!("Name '{name}' must not contain '..'"));
+    }
+    if name.starts_with('.') {
+        return Err(format!("Name '{name}' must not start with '.'"));
+    }
+    if name.chars().any(|c| c.is_control()) {
+        return Err(format!("Name '{name}' must not contain control characters"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_pipeline::PipelineConfig;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    const ECHO: &str = r#"
+[workflow]
+name = "echo"
+description = "Echo the input"
+
+[[workflow.tasks]]
+id = "only"
+runtime = "passthrough"
+"#;
+
+    async fn service(dir: &Path) -> WorkflowService {
+        let engine = PipelineEngine::new(
+            &dir.join("pipeline.db"),
+            PipelineConfig {
+                cron_enabled: false,
+                triggers_enabled: false,
+                ..PipelineConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+        let executor =
+            ScriptExecutor::new(dir.join("wasm-cache"), Duration::from_secs(30)).unwrap();
+        let catalog = RuntimeCatalog::load(&dir.join("runtimes")).unwrap();
+        WorkflowService::new(
+            Arc::new(engine),
+            dir.join("workflows"),
+            Arc::new(executor),
+            Arc::new(RwLock::new(catalog)),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_get_list_delete() {
+        let dir = TempDir::new().unwrap();
+        let service = service(dir.path()).await;
+
+        let (saved, created) = service.save(None, ECHO).await.unwrap();
+        assert!(created);
+        assert!(saved.registered);
+        assert_eq!(saved.task_count, 1);
+        assert!(dir.path().join("workflows/echo.toml").exists());
+
+        let (_, created) = service.save(Some("echo"), ECHO).await.unwrap();
+        assert!(!created);
+
+        let detail = service.get("echo").await.unwrap();
+        assert_eq!(detail.summary.description, "Echo the input");
+        assert_eq!(detail.source.as_deref(), Some(ECHO));
+        assert!(detail.definition.is_some());
+
+        let names: Vec<String> = service.list().await.into_iter().map(|w| w.name).collect();
+        assert_eq!(names, vec!["echo"]);
+
+        service.delete("echo").await.unwrap();
+        assert!(!service.engine().has_workflow("echo").await);
+        assert!(matches!(
+            service.get("echo").await,
+            Err(WorkflowError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.delete("echo").await,
+            Err(WorkflowError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_collects_issues() {
+        let dir = TempDir::new().unwrap();
+        let service = service(dir.path()).await;
+        let toml = r#"
+[workflow]
+name = "broken"
+[[workflow.tasks]]
+id = "a"
+dependencies = ["nope"]
+"#;
+        match service.check(Some("other"), toml) {
+            Err(WorkflowError::Invalid { name, issues }) => {
+                assert_eq!(name, "broken");
+                assert_eq!(issues.len(), 3);
+                assert!(issues[0].message.contains("does not match"));
+                assert_eq!(issues[1].task.as_deref(), Some("a"));
+            }
+            other => panic!("expected Invalid, got {other:?}"),
+        }
+
+        match service.check(None, "not toml [") {
+            Err(WorkflowError::Invalid { issues, .. }) => assert_eq!(issues.len(), 1),
+            other => panic!("expected Invalid, got {other:?}"),
+        }
+
+        let err = service.save(None, toml).await.unwrap_err();
+        assert!(matches!(err, WorkflowError::Invalid { .. }));
+        assert!(!dir.path().join("workflows/broken.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_missing_workflow() {
+        let dir = TempDir::new().unwrap();
+        let service = service(dir.path()).await;
+        let err = service
+            .run("missing", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WorkflowError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_runtimes() {
+        let dir = TempDir::new().unwrap();
+        let service = service(dir.path()).await;
+        let wasm = dir.path().join("mod.wasm");
+        std::fs::write(&wasm, b"\0asm").unwrap();
+
+        let info = service
+            .save_runtime("mine", "Mine".into(), RuntimeSource::WasmPath(wasm))
+            .await
+            .unwrap();
+        assert_eq!(info.entry.category, RuntimeCategory::Custom);
+        assert!(info.wasm_exists);
+        assert_eq!(service.runtimes().await.len(), 1);
+
+        let err = service
+            .save_runtime(
+                "../x",
+                String::new(),
+                RuntimeSource::WasmPath(PathBuf::from("/nope")),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WorkflowError::Rejected(_)));
+
+        service.remove_runtime("mine").await.unwrap();
+        assert!(matches!(
+            service.runtime("mine").await,
+            Err(WorkflowError::NotFound(_))
+        ));
+    }
+}
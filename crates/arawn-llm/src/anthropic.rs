@@ -14,7 +14,10 @@ use std::time::Duration;
 use crate::api_key::ApiKeyProvider;
 use crate::backend::{ContentDelta, LlmBackend, ResponseStream, StreamEvent, with_retry};
 use crate::error::{LlmError, Result};
-use crate::types::{CompletionRequest, CompletionResponse, ContentBlock, Role, StopReason, Usage};
+use crate::types::{
+    CompletionRequest, CompletionResponse, ContentBlock, Role, StopReason, ToolChoice,
+    ToolDefinition, Usage,
+};
 
 /// Default API base URL.
 const DEFAULT_API_BASE: &str = "https://api.anthropic.com";
@@ -213,8 +216,9 @@ impl LlmBackend for AnthropicBackend {
         // Ensure streaming is off for this method
         let mut request = request;
         request.stream = false;
+        let structured_tool = apply_response_schema(&mut request);
 
-        with_retry(
+        let response = with_retry(
             self.config.max_retries,
             self.config.retry_backoff,
             "anthropic",
@@ -228,10 +232,21 @@ impl LlmBackend for AnthropicBackend {
                 Self::handle_response(response).await
             },
         )
-        .await
+        .await?;
+
+        Ok(match structured_tool {
+            Some(name) => structured_tool_to_text(response, &name),
+            None => response,
+        })
     }
 
     async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        if request.response_schema.is_some() {
+            return Err(LlmError::InvalidRequest(
+                "structured output is not supported for streaming requests".to_string(),
+            ));
+        }
+
         // Ensure streaming is on
         let mut request = request;
         request.stream = true;
@@ -256,6 +271,49 @@ impl LlmBackend for AnthropicBackend {
     fn supports_native_tools(&self) -> bool {
         true
     }
+
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+}
+
+/// Map `response_schema` onto a forced tool call, returning the tool name.
+///
+/// Anthropic has no response-format option, so the schema becomes the input
+/// schema of a tool the model must call. The schema root must be an object.
+fn apply_response_schema(request: &mut CompletionRequest) -> Option<String> {
+    let schema = request.response_schema.take()?;
+    let description = schema
+        .description
+        .unwrap_or_else(|| format!("Return the {} result.", schema.name));
+    request.tools.push(ToolDefinition::new(
+        schema.name.clone(),
+        description,
+        schema.schema,
+    ));
+    request.tool_choice = Some(ToolChoice::Tool {
+        name: schema.name.clone(),
+    });
+    Some(schema.name)
+}
+
+/// Replace the forced structured-output tool call with its input as JSON text.
+fn structured_tool_to_text(
+    mut response: CompletionResponse,
+    tool_name: &str,
+) -> CompletionResponse {
+    let input = response.content.iter().find_map(|block| match block {
+        ContentBlock::ToolUse { name, input, .. } if name == tool_name => Some(input.to_string()),
+        _ => None,
+    });
+    if let Some(text) = input {
+        response.content = vec![ContentBlock::Text {
+            text,
+            cache_control: None,
+        }];
+        response.stop_reason = Some(StopReason::EndTurn);
+    }
+    response
 }
 
 /// Create a shared Anthropic backend.
@@ -779,6 +837,44 @@ mod tests {
         assert_eq!(backend.name(), "anthropic");
     }
 
+    #[test]
+    fn test_response_schema_maps_to_forced_tool() {
+        let mut request = CompletionRequest::new("claude", vec![], 256).with_response_schema(
+            crate::types::ResponseSchema::new("verdict", serde_json::json!({"type": "object"})),
+        );
+        let name = apply_response_schema(&mut request).unwrap();
+
+        assert!(request.response_schema.is_none());
+        assert_eq!(request.tools[0].name, "verdict");
+        assert!(
+            matches!(request.tool_choice, Some(ToolChoice::Tool { ref name }) if name == "verdict")
+        );
+        let body = serde_json::to_value(&request).unwrap();
+        assert!(body.get("response_schema").is_none());
+
+        let response = CompletionResponse::new(
+            "msg",
+            "claude",
+            vec![
+                ContentBlock::Text {
+                    text: "Sure.".to_string(),
+                    cache_control: None,
+                },
+                ContentBlock::ToolUse {
+                    id: "t1".to_string(),
+                    name,
+                    input: serde_json::json!({"allow": true}),
+                    cache_control: None,
+                },
+            ],
+            StopReason::ToolUse,
+            Usage::new(1, 1),
+        );
+        let converted = structured_tool_to_text(response, "verdict");
+        assert_eq!(converted.text(), r#"{"allow":true}"#);
+        assert_eq!(converted.stop_reason, Some(StopReason::EndTurn));
+    }
+
     #[test]
     fn test_supports_native_tools() {
         let config = AnthropicConfig::new("key");
@@ -5,6 +5,7 @@
 
 use async_trait::async_trait;
 use futures::Stream;
+use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
@@ -87,7 +88,8 @@ where
 pub type ResponseStream = Pin<Box<dyn Stream<Item = Result<StreamEvent>> + Send + 'static>>;
 
 /// Events emitted during streaming.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamEvent {
     /// Message started.
     MessageStart { id: String, model: String },
@@ -111,7 +113,8 @@ pub enum StreamEvent {
 }
 
 /// Delta content in a streaming response.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum ContentDelta {
     /// Text being streamed.
     TextDelta(String),
@@ -261,6 +264,14 @@ pub trait LlmBackend: Send + Sync {
         false
     }
 
+    /// Returns true if backend enforces `request.response_schema` natively.
+    ///
+    /// When false (default), [`crate::StructuredCompletion`] describes the
+    /// schema in the system prompt instead and clears `response_schema`.
+    fn supports_structured_output(&self) -> bool {
+        false
+    }
+
     /// Instructions for HOW to call tools (model-specific format).
     ///
     /// This is appended to the system prompt to tell the model the expected
@@ -0,0 +1,506 @@
+//! Full-fidelity request capture.
+//!
+//! Where [`crate::interaction_log`] records metadata only, capture stores the
+//! complete [`CompletionRequest`] and [`CompletionResponse`] of every call
+//! (or the full event sequence for streamed calls) as [`CaptureRecord`]s in
+//! daily-rotating JSONL files. Secrets are redacted before anything is
+//! written. Records carry a [`request_fingerprint`] so a capture can be served
+//! back deterministically by [`crate::ReplayBackend`].
+//!
+//! Capture is opt-in: wrap a backend in a [`CapturingBackend`].
+//!
+//! ```rust,ignore
+//! use arawn_llm::{CaptureLog, CaptureLogConfig, CapturingBackend, Redactor};
+//!
+//! let log = Arc::new(CaptureLog::new(CaptureLogConfig::default(), Redactor::new())?);
+//! let backend: SharedBackend = Arc::new(CapturingBackend::new(backend, log));
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::StreamExt;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::backend::{LlmBackend, ParsedToolCall, ResponseStream, SharedBackend, StreamEvent};
+use crate::error::Result;
+use crate::interaction_log::DailyJsonlWriter;
+use crate::types::{CompletionRequest, CompletionResponse, ToolDefinition};
+
+/// Replacement text for redacted secrets.
+pub const REDACTED: &str = "[REDACTED]";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Records
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A complete captured LLM call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    /// Unique identifier for this capture.
+    pub id: String,
+    /// ISO-8601 timestamp when the request was sent.
+    pub timestamp: String,
+    /// Wall-clock duration in milliseconds (until the stream ended, if streamed).
+    pub duration_ms: u64,
+    /// Fingerprint of the request, computed before redaction.
+    pub fingerprint: String,
+    /// Name of the backend that served the call.
+    pub backend: String,
+    /// The request as sent.
+    pub request: CompletionRequest,
+    /// The response (non-streaming calls).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<CompletionResponse>,
+    /// Every event received (streaming calls).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stream_events: Vec<StreamEvent>,
+    /// Error returned by the backend, if the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CaptureRecord {
+    fn new(backend: &str, request: &CompletionRequest) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            duration_ms: 0,
+            fingerprint: request_fingerprint(request),
+            backend: backend.to_string(),
+            request: request.clone(),
+            response: None,
+            stream_events: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+/// Stable fingerprint of the parts of a request that determine the response.
+///
+/// Covers model, system prompt, messages, tools, tool choice and response
+/// schema. Sampling parameters, the streaming flag, metadata and prompt-cache
+/// markers are ignored, so the same conversation matches whether it was sent
+/// streamed or not.
+pub fn request_fingerprint(request: &CompletionRequest) -> String {
+    let mut canonical = serde_json::json!({
+        "model": request.model,
+        "system": request.system.as_ref().map(|s| s.to_text()),
+        "messages": request.messages,
+        "tools": request.tools,
+        "tool_choice": request.tool_choice,
+        "response_schema": request.response_schema,
+    });
+    strip_key(&mut canonical, "cache_control");
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn strip_key(value: &mut serde_json::Value, key: &str) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.remove(key);
+            map.values_mut().for_each(|v| strip_key(v, key));
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| strip_key(v, key)),
+        _ => {}
+    }
+}
+
+/// Read capture records from a JSONL file, or from every `.jsonl` file in a
+/// directory (in file name order).
+pub fn read_captures(path: &Path) -> std::io::Result<Vec<CaptureRecord>> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut records = Vec::new();
+    for file in files {
+        for (n, line) in fs::read_to_string(&file)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(line).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", file.display(), n + 1, e),
+                )
+            })?;
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Redaction
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Built-in patterns for common credential formats.
+const BUILTIN_PATTERNS: &[&str] = &[
+    r"sk-ant-[A-Za-z0-9_\-]{16,}",
+    r"sk-(?:proj-)?[A-Za-z0-9_\-]{20,}",
+    r"gsk_[A-Za-z0-9]{20,}",
+    r"gh[pousr]_[A-Za-z0-9]{20,}",
+    r"github_pat_[A-Za-z0-9_]{20,}",
+    r"xox[abprs]-[A-Za-z0-9\-]{10,}",
+    r"AKIA[0-9A-Z]{16}",
+    r"(?i)bearer\s+[A-Za-z0-9._~+/\-]{16,}=*",
+    r#"(?i)(?:api[_-]?key|secret|password|passwd|token)["']?\s*[:=]\s*["']?[^\s"',;]{8,}"#,
+];
+
+/// Replaces credentials in captured text with [`REDACTED`].
+///
+/// Matches common key formats out of the box; exact secret values (such as
+/// configured API keys) and extra patterns can be added.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    secrets: Vec<String>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor {
+    /// Create a redactor with the built-in credential patterns.
+    pub fn new() -> Self {
+        Self {
+            patterns: BUILTIN_PATTERNS
+                .iter()
+                .map(|p| Regex::new(p).expect("built-in redaction pattern"))
+                .collect(),
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Add a regex pattern to redact.
+    pub fn with_pattern(mut self, pattern: &str) -> std::result::Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Add an exact secret value to redact (values shorter than 4 characters are ignored).
+    pub fn with_secret(mut self, secret: impl Into<String>) -> Self {
+        let secret = secret.into();
+        if secret.len() >= 4 {
+            self.secrets.push(secret);
+        }
+        self
+    }
+
+    /// Redact a string.
+    pub fn redact_str(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in &self.secrets {
+            if out.contains(secret.as_str()) {
+                out = out.replace(secret.as_str(), REDACTED);
+            }
+        }
+        for pattern in &self.patterns {
+            if pattern.is_match(&out) {
+                out = pattern.replace_all(&out, REDACTED).into_owned();
+            }
+        }
+        out
+    }
+
+    /// Redact every string in a JSON value, in place.
+    pub fn redact_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.redact_str(s),
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| self.redact_value(v)),
+            serde_json::Value::Object(map) => map.values_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Log
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Configuration for the capture log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureLogConfig {
+    /// Directory for JSONL files. Defaults to `~/.config/arawn/captures/`.
+    pub path: Option<PathBuf>,
+    /// Days to retain capture files.
+    pub retention_days: u32,
+}
+
+impl Default for CaptureLogConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            retention_days: 14,
+        }
+    }
+}
+
+impl CaptureLogConfig {
+    /// Resolve the capture directory, falling back to the XDG default.
+    pub fn resolved_path(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(|| {
+            dirs::config_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("arawn")
+                .join("captures")
+        })
+    }
+}
+
+/// Writes redacted [`CaptureRecord`]s to `captures-YYYY-MM-DD.jsonl` files.
+pub struct CaptureLog {
+    writer: DailyJsonlWriter,
+    redactor: Redactor,
+}
+
+impl CaptureLog {
+    /// Create the capture log. Runs retention cleanup on init.
+    pub fn new(config: CaptureLogConfig, redactor: Redactor) -> std::io::Result<Self> {
+        let writer = DailyJsonlWriter::new(config.resolved_path(), "captures");
+        writer.init(config.retention_days)?;
+        Ok(Self { writer, redactor })
+    }
+
+    /// Redact and append a record.
+    pub fn log(&self, record: &CaptureRecord) -> std::io::Result<()> {
+        let mut value = serde_json::to_value(record)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.redactor.redact_value(&mut value);
+        self.writer.write_line(&value.to_string())?;
+
+        tracing::debug!(
+            capture_id = %record.id,
+            fingerprint = %record.fingerprint,
+            "LLM call captured"
+        );
+        Ok(())
+    }
+
+    fn log_or_warn(&self, record: &CaptureRecord) {
+        if let Err(e) = self.log(record) {
+            tracing::warn!(error = %e, "Failed to write LLM capture");
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Capturing backend
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Backend wrapper that captures every call to a [`CaptureLog`].
+pub struct CapturingBackend {
+    inner: SharedBackend,
+    log: Arc<CaptureLog>,
+}
+
+impl CapturingBackend {
+    /// Wrap a backend.
+    pub fn new(inner: SharedBackend, log: Arc<CaptureLog>) -> Self {
+        Self { inner, log }
+    }
+}
+
+/// Collects stream events and writes the record when the stream is dropped,
+/// so cancelled streams are captured too.
+struct StreamCapture {
+    record: CaptureRecord,
+    started: Instant,
+    log: Arc<CaptureLog>,
+}
+
+impl Drop for StreamCapture {
+    fn drop(&mut self) {
+        self.record.duration_ms = self.started.elapsed().as_millis() as u64;
+        self.log.log_or_warn(&self.record);
+    }
+}
+
+#[async_trait]
+impl LlmBackend for CapturingBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let mut record = CaptureRecord::new(self.inner.name(), &request);
+        let started = Instant::now();
+        let result = self.inner.complete(request).await;
+        record.duration_ms = started.elapsed().as_millis() as u64;
+
+        match &result {
+            Ok(response) => record.response = Some(response.clone()),
+            Err(e) => record.error = Some(e.to_string()),
+        }
+        self.log.log_or_warn(&record);
+        result
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let mut record = CaptureRecord::new(self.inner.name(), &request);
+        let started = Instant::now();
+
+        let stream = match self.inner.complete_stream(request).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                record.duration_ms = started.elapsed().as_millis() as u64;
+                record.error = Some(e.to_string());
+                self.log.log_or_warn(&record);
+                return Err(e);
+            }
+        };
+
+        let mut capture = StreamCapture {
+            record,
+            started,
+            log: self.log.clone(),
+        };
+        Ok(Box::pin(stream.map(move |item| {
+            match &item {
+                Ok(event) => capture.record.stream_events.push(event.clone()),
+                Err(e) => capture.record.error = Some(e.to_string()),
+            }
+            item
+        })))
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    fn tool_calling_instructions(&self) -> Option<&str> {
+        self.inner.tool_calling_instructions()
+    }
+
+    fn format_tool_definitions(&self, tools: &[ToolDefinition]) -> String {
+        self.inner.format_tool_definitions(tools)
+    }
+
+    fn format_tool_result(&self, tool_use_id: &str, content: &str, is_error: bool) -> String {
+        self.inner
+            .format_tool_result(tool_use_id, content, is_error)
+    }
+
+    fn parse_tool_calls(&self, text: &str) -> (String, Vec<ParsedToolCall>) {
+        self.inner.parse_tool_calls(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::types::Message;
+    use tempfile::TempDir;
+
+    fn capture_log(dir: &TempDir) -> Arc<CaptureLog> {
+        let config = CaptureLogConfig {
+            path: Some(dir.path().to_path_buf()),
+            retention_days: 1,
+        };
+        Arc::new(CaptureLog::new(config, Redactor::new().with_secret("hunter2-secret")).unwrap())
+    }
+
+    #[test]
+    fn test_redactor_builtin_and_literal_secrets() {
+        let redactor = Redactor::new().with_secret("correct-horse");
+        let text = "key sk-ant-REDACTED, header Bearer abcdefghijklmnopqrstu, \
+                    api_key=supersecretvalue and correct-horse";
+        let out = redactor.redact_str(text);
+        assert!(!out.contains("sk-ant-api03"));
+        assert!(!out.contains("abcdefghijklmnopqrstu"));
+        assert!(!out.contains("supersecretvalue"));
+        assert!(!out.contains("correct-horse"));
+        assert!(out.contains(REDACTED));
+
+        assert_eq!(redactor.redact_str("nothing to see"), "nothing to see");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_sampling_and_streaming() {
+        let request = CompletionRequest::new("m", vec![Message::user("hi")], 100);
+        let streamed = request.clone().with_streaming().with_temperature(0.9);
+        assert_eq!(
+            request_fingerprint(&request),
+            request_fingerprint(&streamed)
+        );
+
+        let other = CompletionRequest::new("m", vec![Message::user("hello")], 100);
+        assert_ne!(request_fingerprint(&request), request_fingerprint(&other));
+    }
+
+    #[tokio::test]
+    async fn test_capturing_backend_writes_redacted_records() {
+        let dir = TempDir::new().unwrap();
+        let backend = CapturingBackend::new(
+            Arc::new(MockBackend::with_text("ok hunter2-secret")),
+            capture_log(&dir),
+        );
+
+        let request =
+            CompletionRequest::new("m", vec![Message::user("my key is hunter2-secret")], 100);
+        let fingerprint = request_fingerprint(&request);
+        backend.complete(request).await.unwrap();
+
+        let records = read_captures(dir.path()).unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.fingerprint, fingerprint);
+        assert_eq!(record.backend, "mock");
+        let raw = serde_json::to_string(record).unwrap();
+        assert!(!raw.contains("hunter2-secret"));
+        assert_eq!(record.response.as_ref().unwrap().text(), "ok [REDACTED]");
+    }
+
+    #[tokio::test]
+    async fn test_capturing_backend_records_stream_events() {
+        let dir = TempDir::new().unwrap();
+        let backend = CapturingBackend::new(
+            Arc::new(MockBackend::with_text("streamed")),
+            capture_log(&dir),
+        );
+
+        let request = CompletionRequest::new("m", vec![Message::user("hi")], 100);
+        let mut stream = backend.complete_stream(request).await.unwrap();
+        let mut count = 0;
+        while stream.next().await.is_some() {
+            count += 1;
+        }
+        drop(stream);
+
+        let records = read_captures(dir.path()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].response.is_none());
+        assert_eq!(records[0].stream_events.len(), count);
+        assert!(matches!(
+            records[0].stream_events[0],
+            StreamEvent::MessageStart { .. }
+        ));
+    }
+}
@@ -29,6 +29,7 @@ use crate::CompletionResponse;
 use crate::anthropic::{AnthropicBackend, AnthropicConfig};
 use crate::backend::{LlmBackend, ResponseStream, SharedBackend};
 use crate::error::{LlmError, Result};
+use crate::gemini::{GeminiBackend, GeminiConfig};
 use crate::openai::{OpenAiBackend, OpenAiConfig};
 use crate::types::CompletionRequest;
 
@@ -43,6 +44,8 @@ pub enum Provider {
     Anthropic,
     /// OpenAI API
     OpenAi,
+    /// Google Gemini API
+    Gemini,
     /// Groq cloud inference
     Groq,
     /// Local Ollama instance
@@ -55,6 +58,7 @@ impl Provider {
         match self {
             Provider::Anthropic => "anthropic",
             Provider::OpenAi => "openai",
+            Provider::Gemini => "gemini",
             Provider::Groq => "groq",
             Provider::Ollama => "ollama",
         }
@@ -65,6 +69,7 @@ impl Provider {
         match name.to_lowercase().as_str() {
             "anthropic" | "claude" => Some(Provider::Anthropic),
             "openai" | "gpt" => Some(Provider::OpenAi),
+            "gemini" | "google" => Some(Provider::Gemini),
             "groq" => Some(Provider::Groq),
             "ollama" | "local" => Some(Provider::Ollama),
             _ => None,
@@ -74,7 +79,7 @@ impl Provider {
     /// Check if this provider requires an API key.
     pub fn requires_api_key(&self) -> bool {
         match self {
-            Provider::Anthropic | Provider::OpenAi | Provider::Groq => true,
+            Provider::Anthropic | Provider::OpenAi | Provider::Gemini | Provider::Groq => true,
             Provider::Ollama => false,
         }
     }
@@ -99,6 +104,9 @@ pub struct LlmClientConfig {
     /// OpenAI configuration (optional).
     pub openai: Option<OpenAiConfig>,
 
+    /// Gemini configuration (optional).
+    pub gemini: Option<GeminiConfig>,
+
     /// Groq configuration (optional).
     pub groq: Option<OpenAiConfig>,
 
@@ -133,6 +141,12 @@ impl LlmClientConfig {
         self
     }
 
+    /// Configure Gemini backend.
+    pub fn with_gemini(mut self, config: GeminiConfig) -> Self {
+        self.gemini = Some(config);
+        self
+    }
+
     /// Configure Groq backend.
     pub fn with_groq(mut self, config: OpenAiConfig) -> Self {
         self.groq = Some(config);
@@ -168,6 +182,7 @@ impl LlmClientConfig {
     /// Looks for:
     /// - `ANTHROPIC_API_KEY` - Anthropic configuration
     /// - `OPENAI_API_KEY` - OpenAI configuration
+    /// - `GEMINI_API_KEY` - Gemini configuration
     /// - `GROQ_API_KEY` - Groq configuration
     /// - `OLLAMA_HOST` - Ollama configuration (defaults to localhost)
     /// - `LLM_PRIMARY` - Primary provider name
@@ -184,6 +199,10 @@ impl LlmClientConfig {
             config.openai = Some(config_result);
         }
 
+        if let Ok(config_result) = GeminiConfig::from_env() {
+            config.gemini = Some(config_result);
+        }
+
         if let Ok(config_result) = OpenAiConfig::groq_from_env() {
             config.groq = Some(config_result);
         }
@@ -227,6 +246,7 @@ impl LlmClientConfig {
         let preference = [
             Provider::Anthropic,
             Provider::OpenAi,
+            Provider::Gemini,
             Provider::Groq,
             Provider::Ollama,
         ];
@@ -241,6 +261,7 @@ impl LlmClientConfig {
         match provider {
             Provider::Anthropic => self.anthropic.is_some(),
             Provider::OpenAi => self.openai.is_some(),
+            Provider::Gemini => self.gemini.is_some(),
             Provider::Groq => self.groq.is_some(),
             Provider::Ollama => self.ollama.is_some(),
         }
@@ -280,6 +301,11 @@ impl LlmClient {
             backends.insert(Provider::OpenAi, Arc::new(backend));
         }
 
+        if let Some(ref gemini_config) = config.gemini {
+            let backend = GeminiBackend::new(gemini_config.clone())?;
+            backends.insert(Provider::Gemini, Arc::new(backend));
+        }
+
         if let Some(ref groq_config) = config.groq {
             let backend = OpenAiBackend::new(groq_config.clone())?;
             backends.insert(Provider::Groq, Arc::new(backend));
@@ -292,7 +318,7 @@ impl LlmClient {
 
         if backends.is_empty() {
             return Err(LlmError::Config(
-                "No LLM providers configured. Set ANTHROPIC_API_KEY, OPENAI_API_KEY, GROQ_API_KEY, or ensure Ollama is running.".to_string()
+                "No LLM providers configured. Set ANTHROPIC_API_KEY, OPENAI_API_KEY, GEMINI_API_KEY, GROQ_API_KEY, or ensure Ollama is running.".to_string()
             ));
         }
 
@@ -428,9 +454,19 @@ impl LlmClient {
                         match fallback_backend.complete(request.clone()).await {
                             Ok(response) => {
                                 tracing::info!(provider = %fallback, "Fallback succeeded");
+                                crate::metrics::record_fallback(
+                                    &provider.to_string(),
+                                    &fallback.to_string(),
+                                    true,
+                                );
                                 return Ok(response);
                             }
                             Err(fallback_error) => {
+                                crate::metrics::record_fallback(
+                                    &provider.to_string(),
+                                    &fallback.to_string(),
+                                    false,
+                                );
                                 tracing::warn!(
                                     provider = %fallback,
                                     error = %fallback_error,
@@ -498,6 +534,13 @@ impl LlmBackend for LlmClient {
             .map(|b| b.supports_native_tools())
             .unwrap_or(false)
     }
+
+    fn supports_structured_output(&self) -> bool {
+        self.backends
+            .get(&self.primary)
+            .map(|b| b.supports_structured_output())
+            .unwrap_or(false)
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -513,6 +556,7 @@ mod tests {
     fn test_provider_name() {
         assert_eq!(Provider::Anthropic.name(), "anthropic");
         assert_eq!(Provider::OpenAi.name(), "openai");
+        assert_eq!(Provider::Gemini.name(), "gemini");
         assert_eq!(Provider::Groq.name(), "groq");
         assert_eq!(Provider::Ollama.name(), "ollama");
     }
@@ -523,6 +567,8 @@ mod tests {
         assert_eq!(Provider::from_name("claude"), Some(Provider::Anthropic));
         assert_eq!(Provider::from_name("openai"), Some(Provider::OpenAi));
         assert_eq!(Provider::from_name("gpt"), Some(Provider::OpenAi));
+        assert_eq!(Provider::from_name("gemini"), Some(Provider::Gemini));
+        assert_eq!(Provider::from_name("google"), Some(Provider::Gemini));
         assert_eq!(Provider::from_name("groq"), Some(Provider::Groq));
         assert_eq!(Provider::from_name("ollama"), Some(Provider::Ollama));
         assert_eq!(Provider::from_name("local"), Some(Provider::Ollama));
@@ -533,6 +579,7 @@ mod tests {
     fn test_provider_requires_api_key() {
         assert!(Provider::Anthropic.requires_api_key());
         assert!(Provider::OpenAi.requires_api_key());
+        assert!(Provider::Gemini.requires_api_key());
         assert!(Provider::Groq.requires_api_key());
         assert!(!Provider::Ollama.requires_api_key());
     }
@@ -577,7 +577,7 @@ pub mod local {
 /// `EmbeddingConfig`. It avoids a dependency from arawn-llm → arawn-config.
 #[derive(Debug, Clone)]
 pub struct EmbedderSpec {
-    /// Provider name: "local", "openai", or "mock".
+    /// Provider name: "local", "openai", "gemini", or "mock".
     pub provider: String,
     /// OpenAI API key (required for "openai" provider).
     pub openai_api_key: Option<String>,
@@ -585,6 +585,12 @@ pub struct EmbedderSpec {
     pub openai_model: Option<String>,
     /// OpenAI base URL override.
     pub openai_base_url: Option<String>,
+    /// Gemini API key (required for "gemini" provider).
+    pub gemini_api_key: Option<String>,
+    /// Gemini model name.
+    pub gemini_model: Option<String>,
+    /// Gemini base URL override.
+    pub gemini_base_url: Option<String>,
     /// Local ONNX model path.
     pub local_model_path: Option<std::path::PathBuf>,
     /// Local tokenizer.json path.
@@ -626,6 +632,26 @@ pub async fn build_embedder(spec: &EmbedderSpec) -> Result<SharedEmbedder> {
             }
             Ok(Arc::new(OpenAiEmbedder::new(config)?))
         }
+        "gemini" => {
+            let api_key = spec.gemini_api_key.as_deref().ok_or_else(|| {
+                crate::error::LlmError::Config(
+                    "Gemini embedding provider requires an API key. \
+                     Set GEMINI_API_KEY or configure [embedding.gemini] api_key."
+                        .to_string(),
+                )
+            })?;
+            let mut config = crate::gemini::GeminiEmbedderConfig::new(api_key);
+            if let Some(ref model) = spec.gemini_model {
+                config = config.with_model(model);
+            }
+            if let Some(ref base_url) = spec.gemini_base_url {
+                config = config.with_base_url(base_url);
+            }
+            if let Some(dimensions) = spec.dimensions {
+                config = config.with_dimensions(dimensions);
+            }
+            Ok(Arc::new(crate::gemini::GeminiEmbedder::new(config)?))
+        }
         #[cfg(feature = "local-embeddings")]
         "local" => {
             let dims = spec.dimensions.unwrap_or(384);
@@ -680,7 +706,7 @@ pub async fn build_embedder(spec: &EmbedderSpec) -> Result<SharedEmbedder> {
             Ok(Arc::new(MockEmbedder::new(dims)))
         }
         other => Err(crate::error::LlmError::Config(format!(
-            "Unknown embedding provider '{}'. Valid: local, openai, mock",
+            "Unknown embedding provider '{}'. Valid: local, openai, gemini, mock",
             other
         ))),
     }
@@ -1082,6 +1108,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: Some(128),
@@ -1100,6 +1129,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: None,
@@ -1117,6 +1149,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: None,
@@ -1140,6 +1175,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: None,
@@ -1163,6 +1201,9 @@ mod tests {
             openai_api_key: Some("test-key".to_string()),
             openai_model: Some("text-embedding-ada-002".to_string()),
             openai_base_url: Some("http://localhost:8080/v1".to_string()),
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: Some(256),
@@ -1174,6 +1215,27 @@ mod tests {
         assert_eq!(embedder.dimensions(), 256);
     }
 
+    #[tokio::test]
+    async fn test_build_embedder_gemini_with_config() {
+        let spec = EmbedderSpec {
+            provider: "gemini".to_string(),
+            openai_api_key: None,
+            openai_model: None,
+            openai_base_url: None,
+            gemini_api_key: Some("test-key".to_string()),
+            gemini_model: Some("gemini-embedding-001".to_string()),
+            gemini_base_url: None,
+            local_model_path: None,
+            local_tokenizer_path: None,
+            dimensions: Some(1536),
+            local_model_url: None,
+            local_tokenizer_url: None,
+        };
+        let embedder = build_embedder(&spec).await.unwrap();
+        assert_eq!(embedder.name(), "gemini");
+        assert_eq!(embedder.dimensions(), 1536);
+    }
+
     #[tokio::test]
     async fn test_build_embedder_local_without_feature() {
         // When local-embeddings feature is disabled, "local" falls back to mock
@@ -1182,6 +1244,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: Some(64),
@@ -1286,6 +1351,9 @@ mod tests {
             openai_api_key: None,
             openai_model: None,
             openai_base_url: None,
+            gemini_api_key: None,
+            gemini_model: None,
+            gemini_base_url: None,
             local_model_path: None,
             local_tokenizer_path: None,
             dimensions: None,
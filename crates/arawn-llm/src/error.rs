@@ -298,6 +298,14 @@ pub enum LlmError {
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    /// The model's structured output did not match the requested schema.
+    #[error("Structured output error: {0}")]
+    StructuredOutput(String),
+
+    /// The provider refused the prompt or response on safety grounds.
+    #[error("Content blocked: {0}")]
+    ContentBlocked(String),
+
     /// Internal error.
     #[error("Internal error: {0}")]
     Internal(String),
@@ -325,6 +333,22 @@ impl LlmError {
         }
     }
 
+    /// Short, stable name of the error variant (used as a metrics label).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Backend(_) => "backend",
+            Self::Network(_) => "network",
+            Self::Config(_) => "config",
+            Self::Serialization(_) => "serialization",
+            Self::InvalidRequest(_) => "invalid_request",
+            Self::RateLimit(_) => "rate_limit",
+            Self::Auth(_) => "auth",
+            Self::StructuredOutput(_) => "structured_output",
+            Self::ContentBlocked(_) => "content_blocked",
+            Self::Internal(_) => "internal",
+        }
+    }
+
     /// Returns true if this error is retryable.
     pub fn is_retryable(&self) -> bool {
         matches!(self, Self::Network(_) | Self::RateLimit(_))
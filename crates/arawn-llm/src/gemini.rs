@@ -0,0 +1,1257 @@
+//! Google Gemini API backend implementation.
+//!
+//! This module provides `GeminiBackend`, which talks to the Gemini
+//! `generateContent` API directly (rather than through the OpenAI-compatible
+//! shim) to keep native function calling, streaming and usage details, and
+//! `GeminiEmbedder` for the `embedContent` API.
+//!
+//! Gemini function calls carry no IDs; the backend assigns one per call and
+//! maps tool results back to function names from the conversation history.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use reqwest::{Client, Response, header};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::api_key::ApiKeyProvider;
+use crate::backend::{ContentDelta, LlmBackend, ResponseStream, StreamEvent, with_retry};
+use crate::embeddings::Embedder;
+use crate::error::{LlmError, RateLimitInfo, Result};
+use crate::types::{
+    CompletionRequest, CompletionResponse, ContentBlock, Role, StopReason, ToolChoice,
+    ToolResultContent, Usage,
+};
+
+/// Default Gemini API base URL.
+const DEFAULT_GEMINI_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Default timeout for requests.
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// Default maximum retries for transient errors.
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default initial backoff between retries.
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+/// Default embedding model.
+const DEFAULT_EMBEDDING_MODEL: &str = "text-embedding-004";
+
+/// Finish reasons that mean the response was withheld.
+const BLOCKED_FINISH_REASONS: &[&str] = &[
+    "SAFETY",
+    "RECITATION",
+    "BLOCKLIST",
+    "PROHIBITED_CONTENT",
+    "SPII",
+    "IMAGE_SAFETY",
+];
+
+/// JSON Schema keywords the Gemini schema subset rejects.
+const UNSUPPORTED_SCHEMA_KEYS: &[&str] = &[
+    "$schema",
+    "$id",
+    "additionalProperties",
+    "default",
+    "examples",
+];
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Configuration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Configuration for the Gemini backend.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use arawn_llm::GeminiConfig;
+///
+/// let config = GeminiConfig::new("AIza...").with_model("gemini-2.5-flash");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GeminiConfig {
+    /// API key for authentication. Supports hot-loading via `ApiKeyProvider::Dynamic`.
+    pub api_key: ApiKeyProvider,
+
+    /// Base URL for the API.
+    pub base_url: String,
+
+    /// Model to use (can be overridden per request).
+    pub model: Option<String>,
+
+    /// Request timeout.
+    pub timeout: Duration,
+
+    /// Maximum retries for transient errors.
+    pub max_retries: u32,
+
+    /// Initial backoff duration for retries.
+    pub retry_backoff: Duration,
+}
+
+impl GeminiConfig {
+    /// Create a new config with the given API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: ApiKeyProvider::from_static(api_key),
+            base_url: DEFAULT_GEMINI_BASE.to_string(),
+            model: None,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
+        }
+    }
+
+    /// Create config from the `GEMINI_API_KEY` environment variable.
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("GEMINI_API_KEY").map_err(|_| {
+            LlmError::Config("GEMINI_API_KEY environment variable not set".to_string())
+        })?;
+        Ok(Self::new(api_key))
+    }
+
+    /// Set a custom base URL.
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Set the default model.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set max retries.
+    pub fn with_max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Set retry backoff.
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gemini Backend
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Google Gemini API backend.
+pub struct GeminiBackend {
+    client: Client,
+    config: GeminiConfig,
+}
+
+impl GeminiBackend {
+    /// Create a new Gemini backend with the given configuration.
+    pub fn new(config: GeminiConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| LlmError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a Gemini backend from environment.
+    pub fn from_env() -> Result<Self> {
+        Self::new(GeminiConfig::from_env()?)
+    }
+
+    fn model_for(&self, request: &CompletionRequest) -> String {
+        self.config
+            .model
+            .clone()
+            .unwrap_or_else(|| request.model.clone())
+    }
+
+    fn generate_url(&self, model: &str) -> String {
+        format!("{}/models/{}:generateContent", self.config.base_url, model)
+    }
+
+    fn stream_url(&self, model: &str) -> String {
+        format!(
+            "{}/models/{}:streamGenerateContent?alt=sse",
+            self.config.base_url, model
+        )
+    }
+
+    /// Add authentication headers to a request.
+    fn add_headers(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let builder = builder.header(header::CONTENT_TYPE, "application/json");
+
+        if let Some(api_key) = self.config.api_key.resolve() {
+            builder.header("x-goog-api-key", api_key)
+        } else {
+            builder
+        }
+    }
+
+    /// Convert our CompletionRequest to the Gemini format.
+    fn to_gemini_request(&self, request: &CompletionRequest) -> GeminiRequest {
+        // Gemini function responses are keyed by name, not call ID.
+        let mut call_names: HashMap<String, String> = HashMap::new();
+        let mut contents: Vec<GeminiContent> = Vec::new();
+
+        for message in &request.messages {
+            let mut parts = Vec::new();
+            for block in message.content.blocks() {
+                match block {
+                    ContentBlock::Text { text, .. } => {
+                        if !text.is_empty() {
+                            parts.push(serde_json::json!({ "text": text }));
+                        }
+                    }
+                    ContentBlock::ToolUse {
+                        id, name, input, ..
+                    } => {
+                        parts.push(serde_json::json!({
+                            "functionCall": { "name": name, "args": input }
+                        }));
+                        call_names.insert(id, name);
+                    }
+                    ContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        is_error,
+                        ..
+                    } => {
+                        let name = call_names.get(&tool_use_id).cloned().unwrap_or(tool_use_id);
+                        let key = if is_error { "error" } else { "content" };
+                        parts.push(serde_json::json!({
+                            "functionResponse": {
+                                "name": name,
+                                "response": { key: tool_result_text(content.as_ref()) }
+                            }
+                        }));
+                    }
+                }
+            }
+            if parts.is_empty() {
+                continue;
+            }
+
+            let role = match message.role {
+                Role::User => "user",
+                Role::Assistant => "model",
+            };
+            // Gemini requires alternating turns; merge consecutive same-role messages.
+            match contents.last_mut() {
+                Some(last) if last.role == role => last.parts.extend(parts),
+                _ => contents.push(GeminiContent {
+                    role: role.to_string(),
+                    parts,
+                }),
+            }
+        }
+
+        let system_instruction = request.system.as_ref().map(|system| GeminiContent {
+            role: "user".to_string(),
+            parts: vec![serde_json::json!({ "text": system.to_text() })],
+        });
+
+        let tools = if request.tools.is_empty() {
+            None
+        } else {
+            let declarations: Vec<Value> = request
+                .tools
+                .iter()
+                .map(|t| {
+                    serde_json::json!({
+                        "name": t.name,
+                        "description": t.description,
+                        "parameters": gemini_schema(&t.input_schema),
+                    })
+                })
+                .collect();
+            Some(vec![
+                serde_json::json!({ "functionDeclarations": declarations }),
+            ])
+        };
+
+        let tool_config = request.tool_choice.as_ref().map(|choice| {
+            let config = match choice {
+                ToolChoice::Auto => serde_json::json!({ "mode": "AUTO" }),
+                ToolChoice::Any => serde_json::json!({ "mode": "ANY" }),
+                ToolChoice::None => serde_json::json!({ "mode": "NONE" }),
+                ToolChoice::Tool { name } => {
+                    serde_json::json!({ "mode": "ANY", "allowedFunctionNames": [name] })
+                }
+            };
+            serde_json::json!({ "functionCallingConfig": config })
+        });
+
+        let mut generation_config = serde_json::json!({ "maxOutputTokens": request.max_tokens });
+        if let Some(temperature) = request.temperature {
+            generation_config["temperature"] = serde_json::json!(temperature);
+        }
+        if let Some(top_p) = request.top_p {
+            generation_config["topP"] = serde_json::json!(top_p);
+        }
+        if let Some(top_k) = request.top_k {
+            generation_config["topK"] = serde_json::json!(top_k);
+        }
+        if !request.stop_sequences.is_empty() {
+            generation_config["stopSequences"] = serde_json::json!(request.stop_sequences);
+        }
+        if let Some(ref schema) = request.response_schema {
+            generation_config["responseMimeType"] = serde_json::json!("application/json");
+            generation_config["responseSchema"] = gemini_schema(&schema.schema);
+        }
+
+        GeminiRequest {
+            contents,
+            system_instruction,
+            tools,
+            tool_config,
+            generation_config,
+        }
+    }
+
+    /// Handle a successful response.
+    async fn handle_response(response: Response, model: &str) -> Result<CompletionResponse> {
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let body = response.text().await?;
+        let parsed: GeminiResponse =
+            serde_json::from_str(&body).map_err(|e| LlmError::Serialization(e.to_string()))?;
+
+        parsed.into_completion(model)
+    }
+
+    /// Handle an error response.
+    async fn handle_error_response(response: Response) -> LlmError {
+        let status = response.status();
+
+        let retry_after_header = response
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        let body = response.text().await.unwrap_or_default();
+
+        let Ok(error) = serde_json::from_str::<GeminiErrorResponse>(&body) else {
+            return LlmError::Backend(format!("HTTP {}: {}", status, body));
+        };
+        let message = error.error.message;
+        match status.as_u16() {
+            400 if message.contains("API key") => {
+                LlmError::Auth(format!("Authentication failed: {}", message))
+            }
+            400 | 404 => LlmError::InvalidRequest(message),
+            401 | 403 => LlmError::Auth(format!("Authentication failed: {}", message)),
+            429 => LlmError::RateLimit(RateLimitInfo::parse_openai(
+                &message,
+                retry_after_header.as_deref(),
+            )),
+            500..=599 => LlmError::Backend(format!("Server error: {}", message)),
+            _ => LlmError::Backend(message),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmBackend for GeminiBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let model = self.model_for(&request);
+        let gemini_request = self.to_gemini_request(&request);
+
+        tracing::debug!(
+            backend = "gemini",
+            model = %model,
+            contents = %gemini_request.contents.len(),
+            tools = %request.tools.len(),
+            "Sending Gemini request"
+        );
+
+        with_retry(
+            self.config.max_retries,
+            self.config.retry_backoff,
+            "gemini",
+            || async {
+                let response = self
+                    .add_headers(self.client.post(self.generate_url(&model)))
+                    .json(&gemini_request)
+                    .send()
+                    .await?;
+
+                Self::handle_response(response, &model).await
+            },
+        )
+        .await
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let model = self.model_for(&request);
+        let gemini_request = self.to_gemini_request(&request);
+
+        let response = self
+            .add_headers(self.client.post(self.stream_url(&model)))
+            .json(&gemini_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(parse_gemini_sse_stream(response.bytes_stream(), model))
+    }
+
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+}
+
+fn tool_result_text(content: Option<&ToolResultContent>) -> String {
+    match content {
+        Some(ToolResultContent::Text(t)) => t.clone(),
+        Some(ToolResultContent::Blocks(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => String::new(),
+    }
+}
+
+/// Strip JSON Schema keywords outside the subset Gemini accepts.
+fn gemini_schema(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !UNSUPPORTED_SCHEMA_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| {
+                    // Property names are user data, not keywords.
+                    let value = if key == "properties" {
+                        match value {
+                            Value::Object(props) => Value::Object(
+                                props
+                                    .iter()
+                                    .map(|(name, prop)| (name.clone(), gemini_schema(prop)))
+                                    .collect(),
+                            ),
+                            other => other.clone(),
+                        }
+                    } else {
+                        gemini_schema(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(gemini_schema).collect()),
+        other => other.clone(),
+    }
+}
+
+fn new_call_id() -> String {
+    format!("call_{}", &uuid::Uuid::new_v4().simple().to_string()[..16])
+}
+
+fn map_finish_reason(reason: Option<&str>, has_calls: bool) -> StopReason {
+    if has_calls {
+        return StopReason::ToolUse;
+    }
+    match reason {
+        Some("MAX_TOKENS") => StopReason::MaxTokens,
+        _ => StopReason::EndTurn,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gemini API Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_config: Option<Value>,
+    generation_config: Value,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct GeminiContent {
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    parts: Vec<Value>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    prompt_feedback: Option<GeminiPromptFeedback>,
+    usage_metadata: Option<GeminiUsage>,
+    model_version: Option<String>,
+    response_id: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    content: Option<GeminiContent>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPromptFeedback {
+    block_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsage {
+    #[serde(default)]
+    prompt_token_count: u32,
+    #[serde(default)]
+    candidates_token_count: u32,
+    #[serde(default)]
+    thoughts_token_count: u32,
+    #[serde(default)]
+    cached_content_token_count: u32,
+}
+
+impl From<GeminiUsage> for Usage {
+    fn from(usage: GeminiUsage) -> Self {
+        Usage {
+            // Cached tokens are included in the prompt count.
+            input_tokens: usage
+                .prompt_token_count
+                .saturating_sub(usage.cached_content_token_count),
+            // Thinking tokens are billed as output.
+            output_tokens: usage.candidates_token_count + usage.thoughts_token_count,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: usage.cached_content_token_count,
+        }
+    }
+}
+
+impl GeminiResponse {
+    /// Return an error if the prompt or the (only) candidate was blocked.
+    fn check_blocked(&self) -> Result<()> {
+        if let Some(reason) = self
+            .prompt_feedback
+            .as_ref()
+            .and_then(|f| f.block_reason.as_deref())
+        {
+            return Err(LlmError::ContentBlocked(format!(
+                "prompt blocked by Gemini ({})",
+                reason
+            )));
+        }
+        if let Some(candidate) = self.candidates.first()
+            && let Some(reason) = candidate.finish_reason.as_deref()
+            && BLOCKED_FINISH_REASONS.contains(&reason)
+            && candidate
+                .content
+                .as_ref()
+                .is_none_or(|c| c.parts.is_empty())
+        {
+            return Err(LlmError::ContentBlocked(format!(
+                "response blocked by Gemini ({})",
+                reason
+            )));
+        }
+        Ok(())
+    }
+
+    fn into_completion(self, model: &str) -> Result<CompletionResponse> {
+        self.check_blocked()?;
+
+        let candidate = self.candidates.into_iter().next();
+        let finish_reason = candidate.as_ref().and_then(|c| c.finish_reason.clone());
+        let parts = candidate
+            .and_then(|c| c.content)
+            .map(|c| c.parts)
+            .unwrap_or_default();
+
+        let mut content = Vec::new();
+        for part in parts {
+            if part.get("thought").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            if let Some(text) = part.get("text").and_then(Value::as_str) {
+                match content.last_mut() {
+                    Some(ContentBlock::Text { text: existing, .. }) => existing.push_str(text),
+                    _ => content.push(ContentBlock::text(text)),
+                }
+            } else if let Some(call) = part.get("functionCall") {
+                content.push(ContentBlock::ToolUse {
+                    id: call
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .map(String::from)
+                        .unwrap_or_else(new_call_id),
+                    name: call
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    input: call
+                        .get("args")
+                        .cloned()
+                        .unwrap_or_else(|| serde_json::json!({})),
+                    cache_control: None,
+                });
+            }
+        }
+
+        let has_calls = content
+            .iter()
+            .any(|b| matches!(b, ContentBlock::ToolUse { .. }));
+        Ok(CompletionResponse::new(
+            self.response_id.unwrap_or_else(new_call_id),
+            self.model_version.unwrap_or_else(|| model.to_string()),
+            content,
+            map_finish_reason(finish_reason.as_deref(), has_calls),
+            self.usage_metadata.unwrap_or_default().into(),
+        ))
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GeminiErrorResponse {
+    error: GeminiError,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GeminiError {
+    message: String,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// SSE Streaming
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn parse_gemini_sse_stream(
+    byte_stream: impl Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+    model: String,
+) -> ResponseStream {
+    Box::pin(futures::stream::unfold(
+        GeminiSseState {
+            byte_stream: Box::pin(byte_stream),
+            buffer: String::new(),
+            pending: VecDeque::new(),
+            done: false,
+            model,
+            started: false,
+            next_index: 0,
+            text_block: None,
+            has_calls: false,
+            finish_reason: None,
+            usage: GeminiUsage::default(),
+        },
+        |mut state| async move {
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((event, state));
+                }
+                if state.done {
+                    return None;
+                }
+
+                // Process complete lines in the buffer
+                if let Some(line_end) = state.buffer.find('\n') {
+                    let line = state.buffer[..line_end].trim().to_string();
+                    state.buffer = state.buffer[line_end + 1..].to_string();
+                    if let Some(data) = line.strip_prefix("data:") {
+                        state.handle_chunk(data.trim());
+                    }
+                    continue;
+                }
+
+                // Need more data
+                match state.byte_stream.next().await {
+                    Some(Ok(bytes)) => {
+                        state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+                    }
+                    Some(Err(e)) => {
+                        state.done = true;
+                        return Some((Err(LlmError::Network(e.to_string())), state));
+                    }
+                    None => {
+                        let rest = std::mem::take(&mut state.buffer);
+                        if let Some(data) = rest.trim().strip_prefix("data:") {
+                            state.handle_chunk(data.trim());
+                        }
+                        state.finish();
+                    }
+                }
+            }
+        },
+    ))
+}
+
+struct GeminiSseState {
+    byte_stream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    buffer: String,
+    pending: VecDeque<Result<StreamEvent>>,
+    done: bool,
+    model: String,
+    started: bool,
+    next_index: usize,
+    text_block: Option<usize>,
+    has_calls: bool,
+    finish_reason: Option<String>,
+    usage: GeminiUsage,
+}
+
+impl GeminiSseState {
+    fn push(&mut self, event: StreamEvent) {
+        self.pending.push_back(Ok(event));
+    }
+
+    fn handle_chunk(&mut self, data: &str) {
+        if data.is_empty() || self.done {
+            return;
+        }
+        let chunk: GeminiResponse = match serde_json::from_str(data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                tracing::warn!(error = %e, "Skipping unparseable Gemini stream chunk");
+                return;
+            }
+        };
+
+        if !self.started {
+            self.started = true;
+            let id = chunk.response_id.clone().unwrap_or_else(new_call_id);
+            let model = chunk
+                .model_version
+                .clone()
+                .unwrap_or_else(|| self.model.clone());
+            self.push(StreamEvent::MessageStart { id, model });
+        }
+
+        if let Err(e) = chunk.check_blocked() {
+            self.pending.push_back(Err(e));
+            self.done = true;
+            return;
+        }
+        if let Some(usage) = chunk.usage_metadata {
+            self.usage = usage;
+        }
+
+        let Some(candidate) = chunk.candidates.into_iter().next() else {
+            return;
+        };
+        if candidate.finish_reason.is_some() {
+            self.finish_reason = candidate.finish_reason;
+        }
+        let parts = candidate.content.map(|c| c.parts).unwrap_or_default();
+        for part in parts {
+            if part.get("thought").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            if let Some(text) = part.get("text").and_then(Value::as_str) {
+                let index = match self.text_block {
+                    Some(index) => index,
+                    None => {
+                        let index = self.next_index;
+                        self.next_index += 1;
+                        self.text_block = Some(index);
+                        self.push(StreamEvent::ContentBlockStart {
+                            index,
+                            content_type: "text".to_string(),
+                        });
+                        index
+                    }
+                };
+                self.push(StreamEvent::ContentBlockDelta {
+                    index,
+                    delta: ContentDelta::TextDelta(text.to_string()),
+                });
+            } else if let Some(call) = part.get("functionCall") {
+                // Function calls arrive whole, never split across chunks.
+                self.close_text();
+                let index = self.next_index;
+                self.next_index += 1;
+                self.has_calls = true;
+                let args = call
+                    .get("args")
+                    .cloned()
+                    .unwrap_or_else(|| serde_json::json!({}));
+                self.push(StreamEvent::ContentBlockStart {
+                    index,
+                    content_type: "tool_use".to_string(),
+                });
+                self.push(StreamEvent::ContentBlockDelta {
+                    index,
+                    delta: ContentDelta::InputJsonDelta(args.to_string()),
+                });
+                self.push(StreamEvent::ContentBlockStop { index });
+            }
+        }
+    }
+
+    fn close_text(&mut self) {
+        if let Some(index) = self.text_block.take() {
+            self.push(StreamEvent::ContentBlockStop { index });
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.done {
+            return;
+        }
+        self.done = true;
+        self.close_text();
+        self.push(StreamEvent::MessageDelta {
+            stop_reason: map_finish_reason(self.finish_reason.as_deref(), self.has_calls),
+            usage: self.usage.clone().into(),
+        });
+        self.push(StreamEvent::MessageStop);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gemini Embedder
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Configuration for Gemini embeddings.
+#[derive(Debug, Clone)]
+pub struct GeminiEmbedderConfig {
+    /// API key for authentication. Supports hot-loading via `ApiKeyProvider::Dynamic`.
+    pub api_key: ApiKeyProvider,
+    /// Base URL for the API.
+    pub base_url: String,
+    /// Model to use for embeddings.
+    pub model: String,
+    /// Request timeout.
+    pub timeout: Duration,
+    /// Requested output dimensionality (defaults to the model's native size).
+    pub dimensions: Option<usize>,
+}
+
+impl GeminiEmbedderConfig {
+    /// Create a new config with the given API key.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: ApiKeyProvider::from_static(api_key),
+            base_url: DEFAULT_GEMINI_BASE.to_string(),
+            model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            timeout: Duration::from_secs(60),
+            dimensions: None,
+        }
+    }
+
+    /// Set a custom base URL.
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Set the model to use.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Request reduced output dimensions.
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+}
+
+/// Gemini `batchEmbedContents` API client.
+pub struct GeminiEmbedder {
+    client: Client,
+    config: GeminiEmbedderConfig,
+    dimensions: usize,
+}
+
+impl GeminiEmbedder {
+    /// Create a new Gemini embedder.
+    pub fn new(config: GeminiEmbedderConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| LlmError::Internal(format!("Failed to create HTTP client: {}", e)))?;
+
+        let dimensions = config.dimensions.unwrap_or(match config.model.as_str() {
+            "gemini-embedding-001" => 3072,
+            _ => 768,
+        });
+
+        Ok(Self {
+            client,
+            config,
+            dimensions,
+        })
+    }
+
+    fn batch_url(&self) -> String {
+        format!(
+            "{}/models/{}:batchEmbedContents",
+            self.config.base_url, self.config.model
+        )
+    }
+}
+
+#[async_trait]
+impl Embedder for GeminiEmbedder {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let results = self.embed_batch(&[text]).await?;
+        results
+            .into_iter()
+            .next()
+            .ok_or_else(|| LlmError::Internal("No embedding returned".to_string()))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let model = format!("models/{}", self.config.model);
+        let requests: Vec<Value> = texts
+            .iter()
+            .map(|text| {
+                let mut request = serde_json::json!({
+                    "model": model,
+                    "content": { "parts": [{ "text": text }] },
+                });
+                if let Some(dimensions) = self.config.dimensions {
+                    request["outputDimensionality"] = serde_json::json!(dimensions);
+                }
+                request
+            })
+            .collect();
+
+        let api_key =
+            self.config.api_key.resolve().ok_or_else(|| {
+                LlmError::Auth("Gemini embedding API key not available.".to_string())
+            })?;
+        let response = self
+            .client
+            .post(self.batch_url())
+            .header("x-goog-api-key", api_key)
+            .header(header::CONTENT_TYPE, "application/json")
+            .json(&serde_json::json!({ "requests": requests }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(LlmError::Backend(format!(
+                "Embedding request failed: HTTP {} - {}",
+                status, body
+            )));
+        }
+
+        let result: GeminiEmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::Serialization(format!("Failed to parse response: {}", e)))?;
+
+        Ok(result.embeddings.into_iter().map(|e| e.values).collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    fn name(&self) -> &str {
+        "gemini"
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GeminiEmbeddingResponse {
+    embeddings: Vec<GeminiEmbedding>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct GeminiEmbedding {
+    values: Vec<f32>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Message, ResponseSchema, ToolDefinition, ToolResultBlock};
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn backend(server: &MockServer) -> GeminiBackend {
+        GeminiBackend::new(
+            GeminiConfig::new("test-key")
+                .with_base_url(server.uri())
+                .with_max_retries(0),
+        )
+        .unwrap()
+    }
+
+    fn request(text: &str) -> CompletionRequest {
+        CompletionRequest::new("gemini-2.5-flash", vec![Message::user(text)], 256)
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let backend = GeminiBackend::new(GeminiConfig::new("key")).unwrap();
+        let request = CompletionRequest::new(
+            "gemini-2.5-flash",
+            vec![
+                Message::user("Read a.rs"),
+                Message::assistant_blocks(vec![ContentBlock::tool_use(
+                    "call_1",
+                    "read_file",
+                    json!({"path": "a.rs"}),
+                )]),
+                Message::tool_results(vec![ToolResultBlock::success("call_1", "fn main() {}")]),
+            ],
+            256,
+        )
+        .with_system("Be brief.")
+        .with_tools(vec![ToolDefinition::new(
+            "read_file",
+            "Read a file",
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "properties": {"default": {"type": "string", "default": "x"}},
+                "additionalProperties": false
+            }),
+        )])
+        .with_tool_choice(ToolChoice::Tool {
+            name: "read_file".to_string(),
+        });
+
+        let body = serde_json::to_value(backend.to_gemini_request(&request)).unwrap();
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(
+            body["contents"][1]["parts"][0]["functionCall"]["name"],
+            "read_file"
+        );
+        let response = &body["contents"][2]["parts"][0]["functionResponse"];
+        assert_eq!(response["name"], "read_file");
+        assert_eq!(response["response"]["content"], "fn main() {}");
+
+        let params = &body["tools"][0]["functionDeclarations"][0]["parameters"];
+        assert!(params.get("$schema").is_none());
+        assert!(params.get("additionalProperties").is_none());
+        // A property named "default" survives; the keyword inside it does not
+        assert_eq!(params["properties"]["default"], json!({"type": "string"}));
+
+        assert_eq!(
+            body["toolConfig"]["functionCallingConfig"],
+            json!({"mode": "ANY", "allowedFunctionNames": ["read_file"]})
+        );
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 256);
+    }
+
+    #[tokio::test]
+    async fn test_complete_text_and_usage() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .and(header("x-goog-api-key", "test-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"text": "Hello"}, {"text": " there"}]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {
+                    "promptTokenCount": 12,
+                    "candidatesTokenCount": 3,
+                    "thoughtsTokenCount": 2,
+                    "cachedContentTokenCount": 4
+                },
+                "modelVersion": "gemini-2.5-flash-001",
+                "responseId": "resp-1"
+            })))
+            .mount(&server)
+            .await;
+
+        let response = backend(&server).complete(request("Hi")).await.unwrap();
+        assert_eq!(response.id, "resp-1");
+        assert_eq!(response.model, "gemini-2.5-flash-001");
+        assert_eq!(response.text(), "Hello there");
+        assert_eq!(response.stop_reason, Some(StopReason::EndTurn));
+        assert_eq!(response.usage.input_tokens, 8);
+        assert_eq!(response.usage.cache_read_input_tokens, 4);
+        assert_eq!(response.usage.output_tokens, 5);
+    }
+
+    #[tokio::test]
+    async fn test_complete_function_call() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:generateContent"))
+            .and(body_partial_json(json!({
+                "generationConfig": {"responseMimeType": "application/json"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [
+                        {"functionCall": {"name": "read_file", "args": {"path": "a.rs"}}}
+                    ]},
+                    "finishReason": "STOP"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let request = request("Read a.rs")
+            .with_response_schema(ResponseSchema::new("out", json!({"type": "object"})));
+        let response = backend(&server).complete(request).await.unwrap();
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        let uses = response.tool_uses();
+        assert_eq!(uses[0].name, "read_file");
+        assert_eq!(uses[0].input["path"], "a.rs");
+        assert!(uses[0].id.starts_with("call_"));
+    }
+
+    #[tokio::test]
+    async fn test_safety_block_maps_to_content_blocked() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "promptFeedback": {"blockReason": "SAFETY"}
+            })))
+            .mount(&server)
+            .await;
+
+        let err = backend(&server).complete(request("bad")).await.unwrap_err();
+        assert!(matches!(err, LlmError::ContentBlocked(ref m) if m.contains("SAFETY")));
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_http_errors_are_mapped() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_json(json!({
+                "error": {"code": 429, "message": "Resource exhausted", "status": "RESOURCE_EXHAUSTED"}
+            })))
+            .mount(&server)
+            .await;
+
+        let err = backend(&server).complete(request("Hi")).await.unwrap_err();
+        assert!(matches!(err, LlmError::RateLimit(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_text_and_function_call() {
+        let server = MockServer::start().await;
+        let sse = [
+            json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "Let me "}]}}], "responseId": "r1"}),
+            json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "check."}]}}]}),
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{"functionCall": {"name": "ls", "args": {"dir": "."}}}]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {"promptTokenCount": 7, "candidatesTokenCount": 9}
+            }),
+        ]
+        .iter()
+        .map(|chunk| format!("data: {}\r\n\r\n", chunk))
+        .collect::<String>();
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.5-flash:streamGenerateContent"))
+            .and(query_param("alt", "sse"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(sse, "text/event-stream"))
+            .mount(&server)
+            .await;
+
+        let events: Vec<StreamEvent> = backend(&server)
+            .complete_stream(request("Hi").with_streaming())
+            .await
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+
+        assert!(matches!(&events[0], StreamEvent::MessageStart { id, .. } if id == "r1"));
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::ContentBlockDelta {
+                    delta: ContentDelta::TextDelta(t),
+                    ..
+                } => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Let me check.");
+        assert!(events.iter().any(|e| matches!(
+            e,
+            StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: ContentDelta::InputJsonDelta(json)
+            } if json.contains("\"dir\"")
+        )));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            StreamEvent::MessageDelta { stop_reason: StopReason::ToolUse, usage }
+                if usage.input_tokens == 7 && usage.output_tokens == 9
+        )));
+        assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+    }
+
+    #[tokio::test]
+    async fn test_embedder_batch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/models/text-embedding-004:batchEmbedContents"))
+            .and(body_partial_json(json!({
+                "requests": [{"model": "models/text-embedding-004", "outputDimensionality": 3}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "embeddings": [{"values": [0.1, 0.2, 0.3]}, {"values": [0.4, 0.5, 0.6]}]
+            })))
+            .mount(&server)
+            .await;
+
+        let embedder = GeminiEmbedder::new(
+            GeminiEmbedderConfig::new("test-key")
+                .with_base_url(server.uri())
+                .with_dimensions(3),
+        )
+        .unwrap();
+        assert_eq!(embedder.dimensions(), 3);
+        let vectors = embedder.embed_batch(&["a", "b"]).await.unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(vectors[1], vec![0.4, 0.5, 0.6]);
+    }
+}
@@ -176,30 +176,18 @@ impl InteractionLogConfig {
 /// Thread-safe JSONL writer with daily file rotation.
 pub struct InteractionLogger {
     config: InteractionLogConfig,
-    state: Mutex<WriterState>,
-}
-
-struct WriterState {
-    current_date: Option<NaiveDate>,
-    writer: Option<BufWriter<File>>,
+    writer: DailyJsonlWriter,
 }
 
 impl InteractionLogger {
     /// Create a new logger. Runs retention cleanup on init.
     pub fn new(config: InteractionLogConfig) -> std::io::Result<Self> {
+        let writer = DailyJsonlWriter::new(config.resolved_path(), "interactions");
         if config.enabled {
-            let dir = config.resolved_path();
-            fs::create_dir_all(&dir)?;
-            cleanup_old_files(&dir, config.retention_days)?;
+            writer.init(config.retention_days)?;
         }
 
-        Ok(Self {
-            config,
-            state: Mutex::new(WriterState {
-                current_date: None,
-                writer: None,
-            }),
-        })
+        Ok(Self { config, writer })
     }
 
     /// Log an interaction record. No-op if disabled.
@@ -210,14 +198,56 @@ impl InteractionLogger {
 
         let line = serde_json::to_string(record)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.writer.write_line(&line)?;
+
+        tracing::debug!(
+            interaction_id = %record.id,
+            model = %record.model,
+            duration_ms = record.duration_ms,
+            "interaction logged"
+        );
+
+        Ok(())
+    }
+}
+
+/// Appends lines to `<prefix>-YYYY-MM-DD.jsonl` files, rotating daily.
+pub(crate) struct DailyJsonlWriter {
+    dir: PathBuf,
+    prefix: &'static str,
+    state: Mutex<WriterState>,
+}
+
+struct WriterState {
+    current_date: Option<NaiveDate>,
+    writer: Option<BufWriter<File>>,
+}
+
+impl DailyJsonlWriter {
+    pub(crate) fn new(dir: PathBuf, prefix: &'static str) -> Self {
+        Self {
+            dir,
+            prefix,
+            state: Mutex::new(WriterState {
+                current_date: None,
+                writer: None,
+            }),
+        }
+    }
+
+    /// Create the directory and delete files older than `retention_days`.
+    pub(crate) fn init(&self, retention_days: u32) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        cleanup_old_files(&self.dir, self.prefix, retention_days)
+    }
 
+    pub(crate) fn write_line(&self, line: &str) -> std::io::Result<()> {
         let today = Utc::now().date_naive();
         let mut state = self.state.lock().unwrap();
 
         // Rotate if date changed or no writer yet.
         if state.current_date != Some(today) {
-            let dir = self.config.resolved_path();
-            let path = dir.join(format!("interactions-{}.jsonl", today));
+            let path = self.dir.join(format!("{}-{}.jsonl", self.prefix, today));
             let file = OpenOptions::new().create(true).append(true).open(path)?;
             state.writer = Some(BufWriter::new(file));
             state.current_date = Some(today);
@@ -227,20 +257,12 @@ impl InteractionLogger {
             writeln!(w, "{}", line)?;
             w.flush()?;
         }
-
-        tracing::debug!(
-            interaction_id = %record.id,
-            model = %record.model,
-            duration_ms = record.duration_ms,
-            "interaction logged"
-        );
-
         Ok(())
     }
 }
 
-/// Delete JSONL files older than `retention_days`.
-fn cleanup_old_files(dir: &Path, retention_days: u32) -> std::io::Result<()> {
+/// Delete `<prefix>-YYYY-MM-DD.jsonl` files older than `retention_days`.
+fn cleanup_old_files(dir: &Path, prefix: &str, retention_days: u32) -> std::io::Result<()> {
     let cutoff = Utc::now().date_naive() - chrono::Duration::days(retention_days as i64);
 
     for entry in fs::read_dir(dir)? {
@@ -248,15 +270,16 @@ fn cleanup_old_files(dir: &Path, retention_days: u32) -> std::io::Result<()> {
         let name = entry.file_name();
         let name = name.to_string_lossy();
 
-        // Parse date from filename: interactions-YYYY-MM-DD.jsonl
+        // Parse date from filename: <prefix>-YYYY-MM-DD.jsonl
         if let Some(date_str) = name
-            .strip_prefix("interactions-")
+            .strip_prefix(prefix)
+            .and_then(|s| s.strip_prefix('-'))
             .and_then(|s| s.strip_suffix(".jsonl"))
             && let Ok(file_date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
             && file_date < cutoff
         {
             fs::remove_file(entry.path())?;
-            tracing::info!(file = %name, "removed expired interaction log");
+            tracing::info!(file = %name, "removed expired {} log", prefix);
         }
     }
 
@@ -1,7 +1,7 @@
 //! LLM client abstraction for Arawn.
 //!
 //! This crate provides a unified interface for interacting with various LLM providers
-//! (Anthropic, OpenAI, Ollama, etc.) with support for streaming responses and tool calling.
+//! (Anthropic, OpenAI, Gemini, Ollama, etc.) with support for streaming responses and tool calling.
 //!
 //! # Architecture
 //!
@@ -24,14 +24,23 @@
 
 pub mod api_key;
 pub mod backend;
+pub mod capture;
 pub mod client;
 pub mod embeddings;
 pub mod error;
 pub mod interaction_log;
+pub mod metrics;
+pub mod pricing;
+pub mod prompt_tools;
+pub mod reload;
+pub mod replay;
+pub mod router;
+pub mod structured;
 pub mod types;
 
 // Provider implementations
 pub mod anthropic;
+pub mod gemini;
 pub mod openai;
 
 pub use api_key::ApiKeyProvider;
@@ -40,9 +49,9 @@ pub use backend::{ContentDelta, LlmBackend, ResponseStream, SharedBackend, Strea
 pub use backend::{MockBackend, MockResponse};
 pub use error::{LlmError, ResponseValidationError, Result};
 pub use types::{
-    CacheControl, CompletionRequest, CompletionResponse, Content, ContentBlock, Message, Role,
-    StopReason, SystemPrompt, ToolChoice, ToolDefinition, ToolResultBlock, ToolResultContent,
-    ToolUseBlock, Usage,
+    CacheControl, CompletionRequest, CompletionResponse, Content, ContentBlock, Message,
+    ResponseSchema, Role, StopReason, SystemPrompt, ToolChoice, ToolDefinition, ToolResultBlock,
+    ToolResultContent, ToolUseBlock, Usage,
 };
 
 // Re-export embeddings
@@ -53,11 +62,43 @@ pub use embeddings::{
 
 // Re-export provider configs
 pub use anthropic::{AnthropicBackend, AnthropicConfig};
+pub use gemini::{GeminiBackend, GeminiConfig, GeminiEmbedder, GeminiEmbedderConfig};
 pub use openai::{OpenAiBackend, OpenAiConfig};
 
 // Re-export client
 pub use client::{LlmClient, LlmClientConfig, Provider};
 
+// Re-export pricing
+pub use pricing::{ModelPricing, PricingTable};
+
+// Re-export capture and replay
+pub use capture::{
+    CaptureLog, CaptureLogConfig, CaptureRecord, CapturingBackend, Redactor, read_captures,
+    request_fingerprint,
+};
+pub use replay::ReplayBackend;
+
+// Re-export metrics
+pub use metrics::MeteredBackend;
+
+// Re-export reloadable backend
+pub use reload::ReloadableBackend;
+
+// Re-export prompt-based tool calling
+pub use prompt_tools::{
+    DEFAULT_TOOL_REPAIRS, ParsedToolOutput, PromptToolBackend, ToolCallSegment,
+    ToolCallStreamParser, ToolPromptFormat, repair_json,
+};
+
+// Re-export structured output
+pub use structured::{DEFAULT_MAX_REPAIRS, StructuredCompletion, StructuredResponse};
+
+// Re-export router
+pub use router::{
+    LlmClassifier, LlmRouter, RouteClassifier, RouteCondition, RouteDecision, RouteProfile,
+    RoutingContext, RoutingRule, SharedRouter,
+};
+
 // Re-export local embeddings when feature is enabled
 #[cfg(feature = "local-embeddings")]
 pub use embeddings::local::LocalEmbedder;
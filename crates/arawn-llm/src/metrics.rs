@@ -0,0 +1,306 @@
+//! Metrics and trace spans for LLM calls.
+//!
+//! Metrics go through the [`metrics`] facade, so they cost nothing until a
+//! recorder is installed (the server installs a Prometheus recorder). Wrap a
+//! backend in a [`MeteredBackend`] to record per-provider call counts,
+//! latency, token usage and errors. Each call runs inside an `llm_call`
+//! tracing span, which exported traces show under the agent turn.
+//!
+//! ```rust,ignore
+//! use arawn_llm::MeteredBackend;
+//!
+//! let backend: SharedBackend = Arc::new(MeteredBackend::new(backend, "default"));
+//! ```
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tracing::{Instrument, Span, field};
+
+use crate::backend::{LlmBackend, ParsedToolCall, ResponseStream, SharedBackend, StreamEvent};
+use crate::error::{LlmError, Result};
+use crate::types::{CompletionRequest, CompletionResponse, ToolDefinition, Usage};
+
+/// Completed LLM calls, by `provider`, `profile`, `model` and `outcome`.
+pub const LLM_REQUESTS_TOTAL: &str = "arawn_llm_requests_total";
+/// LLM call latency (until the stream ends, if streamed), by `provider` and `profile`.
+pub const LLM_REQUEST_DURATION_SECONDS: &str = "arawn_llm_request_duration_seconds";
+/// Tokens used, by `provider`, `profile`, `model` and `kind` (`input`/`output`).
+pub const LLM_TOKENS_TOTAL: &str = "arawn_llm_tokens_total";
+/// Failed LLM calls, by `provider`, `profile` and error `kind`.
+pub const LLM_ERRORS_TOTAL: &str = "arawn_llm_errors_total";
+/// Provider fallbacks attempted by [`crate::LlmClient`], by `from`, `to` and `outcome`.
+pub const LLM_FALLBACKS_TOTAL: &str = "arawn_llm_fallbacks_total";
+
+/// Record a fallback from one provider to another.
+pub(crate) fn record_fallback(from: &str, to: &str, succeeded: bool) {
+    metrics::counter!(
+        LLM_FALLBACKS_TOTAL,
+        "from" => from.to_string(),
+        "to" => to.to_string(),
+        "outcome" => if succeeded { "ok" } else { "error" },
+    )
+    .increment(1);
+}
+
+/// Labels shared by every metric of one call.
+#[derive(Clone)]
+struct CallLabels {
+    provider: String,
+    profile: String,
+    model: String,
+}
+
+impl CallLabels {
+    fn record(&self, started: Instant, usage: Option<&Usage>, error: Option<&str>) {
+        let outcome = if error.is_some() { "error" } else { "ok" };
+        metrics::counter!(
+            LLM_REQUESTS_TOTAL,
+            "provider" => self.provider.clone(),
+            "profile" => self.profile.clone(),
+            "model" => self.model.clone(),
+            "outcome" => outcome,
+        )
+        .increment(1);
+        metrics::histogram!(
+            LLM_REQUEST_DURATION_SECONDS,
+            "provider" => self.provider.clone(),
+            "profile" => self.profile.clone(),
+        )
+        .record(started.elapsed().as_secs_f64());
+        if let Some(kind) = error {
+            metrics::counter!(
+                LLM_ERRORS_TOTAL,
+                "provider" => self.provider.clone(),
+                "profile" => self.profile.clone(),
+                "kind" => kind.to_string(),
+            )
+            .increment(1);
+        }
+        if let Some(usage) = usage {
+            for (kind, tokens) in [
+                ("input", usage.input_tokens),
+                ("output", usage.output_tokens),
+            ] {
+                metrics::counter!(
+                    LLM_TOKENS_TOTAL,
+                    "provider" => self.provider.clone(),
+                    "profile" => self.profile.clone(),
+                    "model" => self.model.clone(),
+                    "kind" => kind,
+                )
+                .increment(u64::from(tokens));
+            }
+        }
+    }
+}
+
+/// Wraps a backend, recording metrics and an `llm_call` span for every call.
+pub struct MeteredBackend {
+    inner: SharedBackend,
+    profile: String,
+}
+
+impl MeteredBackend {
+    /// Wrap `inner`, labelling its metrics with the LLM profile name.
+    pub fn new(inner: SharedBackend, profile: impl Into<String>) -> Self {
+        Self {
+            inner,
+            profile: profile.into(),
+        }
+    }
+
+    fn labels(&self, request: &CompletionRequest) -> CallLabels {
+        CallLabels {
+            provider: self.inner.name().to_string(),
+            profile: self.profile.clone(),
+            model: request.model.clone(),
+        }
+    }
+
+    fn span(labels: &CallLabels) -> Span {
+        tracing::info_span!(
+            "llm_call",
+            otel.kind = "client",
+            provider = %labels.provider,
+            profile = %labels.profile,
+            model = %labels.model,
+            input_tokens = field::Empty,
+            output_tokens = field::Empty,
+            error = field::Empty,
+        )
+    }
+}
+
+fn record_on_span(span: &Span, usage: Option<&Usage>, error: Option<&str>) {
+    if let Some(usage) = usage {
+        span.record("input_tokens", usage.input_tokens);
+        span.record("output_tokens", usage.output_tokens);
+    }
+    if let Some(error) = error {
+        span.record("error", error);
+    }
+}
+
+/// Records a streamed call's metrics once the stream is dropped.
+struct StreamMeter {
+    labels: CallLabels,
+    started: Instant,
+    span: Span,
+    usage: Option<Usage>,
+    error: Option<&'static str>,
+}
+
+impl Drop for StreamMeter {
+    fn drop(&mut self) {
+        record_on_span(&self.span, self.usage.as_ref(), self.error);
+        self.labels
+            .record(self.started, self.usage.as_ref(), self.error);
+    }
+}
+
+#[async_trait]
+impl LlmBackend for MeteredBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let labels = self.labels(&request);
+        let span = Self::span(&labels);
+        let started = Instant::now();
+        let result = self.inner.complete(request).instrument(span.clone()).await;
+
+        let usage = result.as_ref().ok().map(|r| &r.usage);
+        let error = result.as_ref().err().map(LlmError::kind);
+        record_on_span(&span, usage, error);
+        labels.record(started, usage, error);
+        result
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let labels = self.labels(&request);
+        let span = Self::span(&labels);
+        let started = Instant::now();
+
+        let stream = match self
+            .inner
+            .complete_stream(request)
+            .instrument(span.clone())
+            .await
+        {
+            Ok(stream) => stream,
+            Err(e) => {
+                record_on_span(&span, None, Some(e.kind()));
+                labels.record(started, None, Some(e.kind()));
+                return Err(e);
+            }
+        };
+
+        let mut meter = StreamMeter {
+            labels,
+            started,
+            span,
+            usage: None,
+            error: None,
+        };
+        Ok(Box::pin(stream.map(move |item| {
+            // Borrow the whole meter so the closure owns it (and drops it with the stream)
+            let meter = &mut meter;
+            match &item {
+                Ok(StreamEvent::MessageDelta { usage, .. }) => meter.usage = Some(usage.clone()),
+                Ok(StreamEvent::Error { .. }) => meter.error = Some("stream"),
+                Ok(_) => {}
+                Err(e) => meter.error = Some(e.kind()),
+            }
+            item
+        })))
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    fn tool_calling_instructions(&self) -> Option<&str> {
+        self.inner.tool_calling_instructions()
+    }
+
+    fn format_tool_definitions(&self, tools: &[ToolDefinition]) -> String {
+        self.inner.format_tool_definitions(tools)
+    }
+
+    fn format_tool_result(&self, tool_use_id: &str, content: &str, is_error: bool) -> String {
+        self.inner
+            .format_tool_result(tool_use_id, content, is_error)
+    }
+
+    fn parse_tool_calls(&self, text: &str) -> (String, Vec<ParsedToolCall>) {
+        self.inner.parse_tool_calls(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use metrics_exporter_prometheus::PrometheusBuilder;
+
+    use super::*;
+    use crate::backend::{MockBackend, MockResponse};
+    use crate::types::Message;
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new("test-model", vec![Message::user("Hi")], 100)
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_metered_backend_records_calls_and_tokens() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let backend = MeteredBackend::new(Arc::new(MockBackend::with_text("Hello")), "fast");
+        backend.complete(request()).await.unwrap();
+        let backend = MeteredBackend::new(Arc::new(MockBackend::with_text("Hello")), "fast");
+        let mut stream = backend.complete_stream(request()).await.unwrap();
+        while stream.next().await.is_some() {}
+        drop(stream);
+
+        let output = handle.render();
+        assert!(output.contains(
+            r#"arawn_llm_requests_total{provider="mock",profile="fast",model="test-model",outcome="ok"} 2"#
+        ));
+        assert!(output.contains("arawn_llm_request_duration_seconds"));
+        // MockBackend reports 20 output tokens per call, streamed or not
+        assert!(output.contains(
+            r#"arawn_llm_tokens_total{provider="mock",profile="fast",model="test-model",kind="output"} 40"#
+        ));
+        assert!(!output.contains(LLM_ERRORS_TOTAL));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_metered_backend_records_errors() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let backend = MeteredBackend::new(
+            Arc::new(MockBackend::with_results(vec![MockResponse::Error(
+                "overloaded".to_string(),
+            )])),
+            "default",
+        );
+        assert!(backend.complete(request()).await.is_err());
+
+        let output = handle.render();
+        assert!(output.contains(r#"outcome="error"} 1"#));
+        assert!(output.contains(
+            r#"arawn_llm_errors_total{provider="mock",profile="default",kind="backend"} 1"#
+        ));
+    }
+}
@@ -72,6 +72,9 @@ pub struct OpenAiConfig {
 
     /// Name for this backend instance.
     pub name: String,
+
+    /// Whether the endpoint accepts `response_format: json_schema`.
+    pub structured_output: bool,
 }
 
 impl OpenAiConfig {
@@ -85,6 +88,7 @@ impl OpenAiConfig {
             max_retries: DEFAULT_MAX_RETRIES,
             retry_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
             name: "openai".to_string(),
+            structured_output: true,
         }
     }
 
@@ -98,6 +102,8 @@ impl OpenAiConfig {
             max_retries: DEFAULT_MAX_RETRIES,
             retry_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
             name: "groq".to_string(),
+            // json_schema is only available on some Groq models
+            structured_output: false,
         }
     }
 
@@ -111,6 +117,7 @@ impl OpenAiConfig {
             max_retries: DEFAULT_MAX_RETRIES,
             retry_backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
             name: "ollama".to_string(),
+            structured_output: true,
         }
     }
 
@@ -165,6 +172,12 @@ impl OpenAiConfig {
         self.retry_backoff = backoff;
         self
     }
+
+    /// Set whether the endpoint supports native structured output.
+    pub fn with_structured_output(mut self, enabled: bool) -> Self {
+        self.structured_output = enabled;
+        self
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -364,6 +377,21 @@ impl OpenAiBackend {
             .clone()
             .unwrap_or_else(|| request.model.clone());
 
+        let response_format = request
+            .response_schema
+            .as_ref()
+            .filter(|_| self.config.structured_output)
+            .map(|schema| {
+                let mut json_schema = serde_json::json!({
+                    "name": schema.name,
+                    "schema": schema.schema,
+                });
+                if let Some(ref description) = schema.description {
+                    json_schema["description"] = serde_json::json!(description);
+                }
+                serde_json::json!({ "type": "json_schema", "json_schema": json_schema })
+            });
+
         OpenAiChatRequest {
             model,
             messages,
@@ -373,6 +401,7 @@ impl OpenAiBackend {
             stream: Some(request.stream),
             tools,
             stop,
+            response_format,
         }
     }
 
@@ -490,6 +519,10 @@ impl LlmBackend for OpenAiBackend {
     fn supports_native_tools(&self) -> bool {
         true
     }
+
+    fn supports_structured_output(&self) -> bool {
+        self.config.structured_output
+    }
 }
 
 /// Create a shared OpenAI-compatible backend.
@@ -517,6 +550,8 @@ struct OpenAiChatRequest {
     tools: Option<Vec<OpenAiTool>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -1106,6 +1141,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_to_openai_request_with_response_schema() {
+        let schema = crate::types::ResponseSchema::new(
+            "verdict",
+            serde_json::json!({"type": "object", "properties": {"allow": {"type": "boolean"}}}),
+        )
+        .with_description("Allow or deny");
+        let request = CompletionRequest::new("gpt-4o", vec![Message::user("Hi")], 100)
+            .with_response_schema(schema);
+
+        let backend = OpenAiBackend::new(OpenAiConfig::openai("key")).unwrap();
+        assert!(backend.supports_structured_output());
+        let body = serde_json::to_value(backend.to_openai_request(&request)).unwrap();
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["name"], "verdict");
+        assert_eq!(
+            body["response_format"]["json_schema"]["description"],
+            "Allow or deny"
+        );
+
+        let groq = OpenAiBackend::new(OpenAiConfig::groq("key")).unwrap();
+        assert!(!groq.supports_structured_output());
+        let body = serde_json::to_value(groq.to_openai_request(&request)).unwrap();
+        assert!(body.get("response_format").is_none());
+    }
+
     #[test]
     fn test_to_openai_request_uses_request_model_when_no_config_model() {
         let config = OpenAiConfig::openai("key");
@@ -0,0 +1,159 @@
+//! Model pricing for converting token usage into cost.
+//!
+//! Prices are expressed in USD per million tokens. Models are looked up by
+//! exact name first, then by the longest configured prefix, so a single entry
+//! for `claude-sonnet-4` covers dated variants like `claude-sonnet-4-20250514`.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use arawn_llm::{ModelPricing, PricingTable, Usage};
+//!
+//! let table = PricingTable::new()
+//!     .with_model("claude-sonnet-4", ModelPricing::new(3.0, 15.0).with_cache(3.75, 0.30));
+//!
+//! let cost = table.cost("claude-sonnet-4-20250514", &Usage::new(1_000_000, 100_000));
+//! assert_eq!(cost, Some(4.5));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::types::Usage;
+
+const PER_MILLION: f64 = 1_000_000.0;
+
+/// Price of a single model, in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    /// Uncached input tokens.
+    pub input_per_mtok: f64,
+    /// Output tokens.
+    pub output_per_mtok: f64,
+    /// Tokens written to the prompt cache. Defaults to the input price.
+    pub cache_write_per_mtok: Option<f64>,
+    /// Tokens read from the prompt cache. Defaults to the input price.
+    pub cache_read_per_mtok: Option<f64>,
+}
+
+impl ModelPricing {
+    /// Create pricing with input and output rates.
+    pub fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+            cache_write_per_mtok: None,
+            cache_read_per_mtok: None,
+        }
+    }
+
+    /// Set prompt-cache write and read rates.
+    pub fn with_cache(mut self, write_per_mtok: f64, read_per_mtok: f64) -> Self {
+        self.cache_write_per_mtok = Some(write_per_mtok);
+        self.cache_read_per_mtok = Some(read_per_mtok);
+        self
+    }
+
+    /// Cost of the given usage in USD.
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let cache_write = self.cache_write_per_mtok.unwrap_or(self.input_per_mtok);
+        let cache_read = self.cache_read_per_mtok.unwrap_or(self.input_per_mtok);
+
+        (usage.input_tokens as f64 * self.input_per_mtok
+            + usage.output_tokens as f64 * self.output_per_mtok
+            + usage.cache_creation_input_tokens as f64 * cache_write
+            + usage.cache_read_input_tokens as f64 * cache_read)
+            / PER_MILLION
+    }
+}
+
+/// Lookup table of model prices.
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    /// Create an empty pricing table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the price of a model (or model prefix).
+    pub fn with_model(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.models.insert(model.into(), pricing);
+        self
+    }
+
+    /// Whether no prices are configured.
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// Number of configured entries.
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Find the price for a model: exact match, else the longest matching prefix.
+    pub fn get(&self, model: &str) -> Option<&ModelPricing> {
+        if let Some(pricing) = self.models.get(model) {
+            return Some(pricing);
+        }
+        self.models
+            .iter()
+            .filter(|(name, _)| model.starts_with(name.as_str()))
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, pricing)| pricing)
+    }
+
+    /// Cost of the given usage in USD, or `None` if the model has no price.
+    pub fn cost(&self, model: &str, usage: &Usage) -> Option<f64> {
+        self.get(model).map(|p| p.cost(usage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cost_input_output() {
+        let pricing = ModelPricing::new(3.0, 15.0);
+        let cost = pricing.cost(&Usage::new(1_000_000, 100_000));
+        assert!((cost - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_cache_tokens() {
+        let usage = Usage {
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_creation_input_tokens: 1_000_000,
+            cache_read_input_tokens: 1_000_000,
+        };
+
+        let with_cache = ModelPricing::new(3.0, 15.0).with_cache(3.75, 0.30);
+        assert!((with_cache.cost(&usage) - 4.05).abs() < 1e-9);
+
+        // Without explicit cache rates, cached tokens are billed as input
+        let without = ModelPricing::new(3.0, 15.0);
+        assert!((without.cost(&usage) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lookup_exact_then_longest_prefix() {
+        let table = PricingTable::new()
+            .with_model("gpt-4o", ModelPricing::new(2.5, 10.0))
+            .with_model("gpt-4o-mini", ModelPricing::new(0.15, 0.6))
+            .with_model("gpt-4o-2024-08-06", ModelPricing::new(5.0, 15.0));
+
+        assert_eq!(table.get("gpt-4o-2024-08-06").unwrap().input_per_mtok, 5.0);
+        assert_eq!(
+            table.get("gpt-4o-mini-2024-07-18").unwrap().input_per_mtok,
+            0.15
+        );
+        assert_eq!(table.get("gpt-4o-2024-11-20").unwrap().input_per_mtok, 2.5);
+        assert!(table.get("llama3").is_none());
+        assert!(table.cost("llama3", &Usage::new(10, 10)).is_none());
+    }
+}
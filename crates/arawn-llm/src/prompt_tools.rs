@@ -0,0 +1,1157 @@
+//! Prompt-based tool calling for models without native function calling.
+//!
+//! [`PromptToolBackend`] wraps any backend and translates between the
+//! native tool representation used by the agent (`request.tools`,
+//! `ToolUse`/`ToolResult` blocks) and plain text in one of the built-in
+//! [`ToolPromptFormat`]s:
+//!
+//! - **Requests**: tool definitions and calling instructions are appended to
+//!   the system prompt; earlier tool calls and results in the history are
+//!   rendered as text.
+//! - **Responses**: tool calls are parsed out of the model's text and
+//!   returned as `ToolUse` blocks with `StopReason::ToolUse`.
+//! - **Streams**: call markup is held back from text deltas and emitted as
+//!   `tool_use` blocks once complete, so it never reaches the user.
+//!
+//! Call bodies go through [`repair_json`] (trailing commas, unclosed
+//! braces, Python literals). Calls that still cannot be decoded trigger a
+//! bounded repair round-trip asking the model to re-emit them.
+//!
+//! ```rust,ignore
+//! use arawn_llm::{PromptToolBackend, ToolPromptFormat};
+//!
+//! let backend: SharedBackend = Arc::new(PromptToolBackend::new(ollama, ToolPromptFormat::Hermes));
+//! ```
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::backend::{
+    ContentDelta, LlmBackend, ParsedToolCall, ResponseStream, SharedBackend, StreamEvent,
+};
+use crate::error::Result;
+use crate::types::{
+    CompletionRequest, CompletionResponse, Content, ContentBlock, Message, StopReason,
+    SystemPrompt, ToolChoice, ToolDefinition, ToolResultContent, Usage,
+};
+
+/// Default number of repair round-trips for malformed tool calls.
+pub const DEFAULT_TOOL_REPAIRS: u32 = 1;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Formats
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Built-in text formats for tool calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolPromptFormat {
+    /// Fenced JSON block: ```` ```tool_call {"name": …, "arguments": …} ``` ````.
+    Json,
+    /// XML elements: `<tool_call><name>…</name><arguments>{…}</arguments></tool_call>`.
+    Xml,
+    /// Hermes / Qwen style: `<tools>` signatures, `<tool_call>{…}</tool_call>`,
+    /// `<tool_response>` results.
+    Hermes,
+}
+
+impl FromStr for ToolPromptFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "xml" => Ok(Self::Xml),
+            "hermes" => Ok(Self::Hermes),
+            other => Err(format!(
+                "unknown tool format '{}' (expected json, xml or hermes)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ToolPromptFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Xml => "xml",
+            Self::Hermes => "hermes",
+        })
+    }
+}
+
+impl ToolPromptFormat {
+    /// Opening and closing markers of a tool call.
+    fn markers(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Json => ("```tool_call", "```"),
+            Self::Xml | Self::Hermes => ("<tool_call>", "</tool_call>"),
+        }
+    }
+
+    /// Instructions telling the model how to call tools.
+    pub fn instructions(&self) -> &'static str {
+        match self {
+            Self::Json => {
+                "To call a tool, reply with a fenced block tagged `tool_call` containing a JSON \
+                 object with the tool `name` and its `arguments`:\n\n\
+                 ```tool_call\n{\"name\": \"tool_name\", \"arguments\": {\"param\": \"value\"}}\n```\n\n\
+                 Emit one block per call. After calling tools, stop and wait for the results. \
+                 If no tool is needed, answer normally without a tool_call block."
+            }
+            Self::Xml => {
+                "To call a tool, reply with a <tool_call> element containing the tool <name> and \
+                 its <arguments> as a JSON object:\n\n\
+                 <tool_call>\n<name>tool_name</name>\n<arguments>{\"param\": \"value\"}</arguments>\n</tool_call>\n\n\
+                 Emit one element per call. After calling tools, stop and wait for the results. \
+                 If no tool is needed, answer normally without a <tool_call> element."
+            }
+            Self::Hermes => {
+                "For each function call, return a JSON object with the function name and \
+                 arguments within <tool_call></tool_call> XML tags:\n\
+                 <tool_call>\n{\"name\": <function-name>, \"arguments\": <args-json-object>}\n</tool_call>"
+            }
+        }
+    }
+
+    /// Render tool definitions for the system prompt.
+    pub fn format_definitions(&self, tools: &[ToolDefinition]) -> String {
+        match self {
+            Self::Json => {
+                let mut out = String::from("## Available Tools\n");
+                for tool in tools {
+                    out.push_str(&format!(
+                        "\n### {}\n{}\n\nArguments (JSON Schema):\n```json\n{}\n```\n",
+                        tool.name, tool.description, tool.input_schema
+                    ));
+                }
+                out
+            }
+            Self::Xml => {
+                let mut out = String::from("<tools>\n");
+                for tool in tools {
+                    out.push_str(&format!(
+                        "<tool name=\"{}\">\n<description>{}</description>\n<parameters>{}</parameters>\n</tool>\n",
+                        tool.name, tool.description, tool.input_schema
+                    ));
+                }
+                out.push_str("</tools>");
+                out
+            }
+            Self::Hermes => {
+                let mut out = String::from(
+                    "You are a function calling AI model. You are provided with function \
+                     signatures within <tools></tools> XML tags. You may call one or more \
+                     functions to assist with the user query. Don't make assumptions about what \
+                     values to plug into functions.\n<tools>\n",
+                );
+                for tool in tools {
+                    let signature = serde_json::json!({
+                        "type": "function",
+                        "function": {
+                            "name": tool.name,
+                            "description": tool.description,
+                            "parameters": tool.input_schema,
+                        }
+                    });
+                    out.push_str(&signature.to_string());
+                    out.push('\n');
+                }
+                out.push_str("</tools>");
+                out
+            }
+        }
+    }
+
+    /// Render a tool call as the model would have written it.
+    pub fn format_call(&self, name: &str, arguments: &Value) -> String {
+        match self {
+            Self::Json => format!(
+                "```tool_call\n{}\n```",
+                serde_json::json!({ "name": name, "arguments": arguments })
+            ),
+            Self::Xml => format!(
+                "<tool_call>\n<name>{}</name>\n<arguments>{}</arguments>\n</tool_call>",
+                name, arguments
+            ),
+            Self::Hermes => format!(
+                "<tool_call>\n{}\n</tool_call>",
+                serde_json::json!({ "name": name, "arguments": arguments })
+            ),
+        }
+    }
+
+    /// Render a tool result for the conversation.
+    pub fn format_result(&self, tool: &str, content: &str, is_error: bool) -> String {
+        match self {
+            Self::Json => {
+                let status = if is_error { "failed" } else { "returned" };
+                format!(
+                    "Tool `{}` {}:\n```tool_result\n{}\n```",
+                    tool, status, content
+                )
+            }
+            Self::Xml => {
+                let status = if is_error { "error" } else { "ok" };
+                format!(
+                    "<tool_result name=\"{}\" status=\"{}\">\n{}\n</tool_result>",
+                    tool, status, content
+                )
+            }
+            Self::Hermes => {
+                let mut body = serde_json::json!({ "name": tool, "content": content });
+                if is_error {
+                    body["error"] = Value::Bool(true);
+                }
+                format!("<tool_response>\n{}\n</tool_response>", body)
+            }
+        }
+    }
+
+    /// Parse complete model output into remaining text and tool calls.
+    pub fn parse(&self, text: &str) -> ParsedToolOutput {
+        let mut parser = ToolCallStreamParser::new(*self);
+        let mut segments = parser.push(text);
+        segments.extend(parser.finish());
+
+        let mut output = ParsedToolOutput::default();
+        for segment in segments {
+            match segment {
+                ToolCallSegment::Text(t) => output.text.push_str(&t),
+                ToolCallSegment::Call(call) => output.calls.push(call),
+                ToolCallSegment::Malformed(error) => output.malformed.push(error),
+            }
+        }
+        output.text = output.text.trim().to_string();
+        output
+    }
+
+    /// Decode the body between a call's markers.
+    fn decode(&self, body: &str) -> std::result::Result<ParsedToolCall, String> {
+        let (name, arguments) = match self {
+            Self::Xml if body.contains("<name>") => {
+                let name = tag_content(body, "name")
+                    .ok_or_else(|| "missing <name> element".to_string())?
+                    .trim()
+                    .to_string();
+                let arguments = match tag_content(body, "arguments") {
+                    Some(args) if !args.trim().is_empty() => repair_json(args)
+                        .map_err(|e| format!("{}: invalid arguments: {}", name, e))?,
+                    _ => Value::Object(Default::default()),
+                };
+                (name, arguments)
+            }
+            _ => {
+                let value = repair_json(body).map_err(|e| format!("invalid JSON: {}", e))?;
+                let name = value
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| "missing \"name\" field".to_string())?
+                    .to_string();
+                let arguments = match ["arguments", "parameters", "input"]
+                    .iter()
+                    .find_map(|key| value.get(*key))
+                {
+                    // Some models double-encode the arguments.
+                    Some(Value::String(s)) => {
+                        repair_json(s).map_err(|e| format!("{}: invalid arguments: {}", name, e))?
+                    }
+                    Some(args) => args.clone(),
+                    None => Value::Object(Default::default()),
+                };
+                (name, arguments)
+            }
+        };
+
+        if name.is_empty() {
+            return Err("empty tool name".to_string());
+        }
+        if !arguments.is_object() {
+            return Err(format!("{}: arguments must be a JSON object", name));
+        }
+        Ok(ParsedToolCall {
+            id: new_call_id(),
+            name,
+            arguments,
+        })
+    }
+}
+
+/// Result of parsing model output for tool calls.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedToolOutput {
+    /// Text outside of tool calls, trimmed.
+    pub text: String,
+    /// Successfully decoded calls, in order.
+    pub calls: Vec<ParsedToolCall>,
+    /// Descriptions of calls that could not be decoded.
+    pub malformed: Vec<String>,
+}
+
+fn new_call_id() -> String {
+    format!("call_{}", &Uuid::new_v4().simple().to_string()[..16])
+}
+
+fn tag_content<'a>(body: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = body.find(&open)? + open.len();
+    let end = body[start..].find(&close).map_or(body.len(), |i| start + i);
+    Some(&body[start..end])
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Streaming parser
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A piece of parsed model output.
+#[derive(Debug, Clone)]
+pub enum ToolCallSegment {
+    /// Plain text to show the user.
+    Text(String),
+    /// A complete tool call.
+    Call(ParsedToolCall),
+    /// A call that could not be decoded, with the reason.
+    Malformed(String),
+}
+
+/// Incremental tool call parser for streamed text.
+///
+/// Text is released as soon as it cannot be the start of a call marker;
+/// call bodies are buffered until their closing marker (or the end of the
+/// stream, for calls the model never closed).
+#[derive(Debug)]
+pub struct ToolCallStreamParser {
+    format: ToolPromptFormat,
+    pending: String,
+    in_call: bool,
+}
+
+impl ToolCallStreamParser {
+    /// Create a parser for the given format.
+    pub fn new(format: ToolPromptFormat) -> Self {
+        Self {
+            format,
+            pending: String::new(),
+            in_call: false,
+        }
+    }
+
+    /// Feed a chunk of text and return the segments it completes.
+    pub fn push(&mut self, chunk: &str) -> Vec<ToolCallSegment> {
+        self.pending.push_str(chunk);
+        let (open, close) = self.format.markers();
+        let mut segments = Vec::new();
+
+        loop {
+            if self.in_call {
+                let Some(end) = self.pending.find(close) else {
+                    break;
+                };
+                let body: String = self.pending.drain(..end + close.len()).collect();
+                segments.push(self.decode_segment(&body[..end]));
+                self.in_call = false;
+            } else if let Some(start) = self.pending.find(open) {
+                let text: String = self.pending.drain(..start + open.len()).collect();
+                push_text(&mut segments, &text[..start]);
+                self.in_call = true;
+            } else {
+                // Hold back a suffix that could be the start of the marker.
+                let keep = partial_marker_len(&self.pending, open);
+                let release = self.pending.len() - keep;
+                let text: String = self.pending.drain(..release).collect();
+                push_text(&mut segments, &text);
+                break;
+            }
+        }
+        segments
+    }
+
+    /// Flush buffered output at the end of the stream.
+    pub fn finish(&mut self) -> Vec<ToolCallSegment> {
+        let rest = std::mem::take(&mut self.pending);
+        let mut segments = Vec::new();
+        if self.in_call {
+            self.in_call = false;
+            if !rest.trim().is_empty() {
+                segments.push(self.decode_segment(&rest));
+            }
+        } else {
+            push_text(&mut segments, &rest);
+        }
+        segments
+    }
+
+    fn decode_segment(&self, body: &str) -> ToolCallSegment {
+        match self.format.decode(body) {
+            Ok(call) => ToolCallSegment::Call(call),
+            Err(error) => ToolCallSegment::Malformed(error),
+        }
+    }
+}
+
+fn push_text(segments: &mut Vec<ToolCallSegment>, text: &str) {
+    if !text.is_empty() {
+        segments.push(ToolCallSegment::Text(text.to_string()));
+    }
+}
+
+/// Length of the longest suffix of `text` that is a proper prefix of `marker`.
+fn partial_marker_len(text: &str, marker: &str) -> usize {
+    (1..marker.len())
+        .rev()
+        .find(|&n| text.ends_with(&marker[..n]))
+        .unwrap_or(0)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON repair
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parse JSON emitted by a model, repairing common mistakes.
+///
+/// Handles code fences, prose before the object, trailing commas, raw
+/// newlines in strings, Python `True`/`False`/`None`, unterminated strings
+/// and missing closing braces. Text after the top-level value is ignored.
+pub fn repair_json(text: &str) -> std::result::Result<Value, serde_json::Error> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Ok(value);
+    }
+
+    let unfenced = trimmed
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```");
+    let start = unfenced.find(['{', '[']).unwrap_or(0);
+    serde_json::from_str(&repair_json_text(&unfenced[start..]))
+}
+
+fn repair_json_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut closers: Vec<char> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                _ if escaped => {
+                    escaped = false;
+                    out.push(c);
+                }
+                '\\' => {
+                    escaped = true;
+                    out.push(c);
+                }
+                '"' => {
+                    in_string = false;
+                    out.push(c);
+                }
+                '\n' => out.push_str("\\n"),
+                '\t' => out.push_str("\\t"),
+                _ => out.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' => {
+                closers.push('}');
+                out.push(c);
+            }
+            '[' => {
+                closers.push(']');
+                out.push(c);
+            }
+            '}' | ']' => {
+                strip_trailing_comma(&mut out);
+                if closers.last() == Some(&c) {
+                    closers.pop();
+                }
+                out.push(c);
+                if closers.is_empty() {
+                    return out;
+                }
+            }
+            c if c.is_ascii_alphabetic() => {
+                let mut word = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if !next.is_ascii_alphanumeric() && next != '_' {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                out.push_str(match word.as_str() {
+                    "True" => "true",
+                    "False" => "false",
+                    "None" => "null",
+                    other => other,
+                });
+            }
+            _ => out.push(c),
+        }
+    }
+
+    if in_string {
+        if escaped {
+            out.pop();
+        }
+        out.push('"');
+    }
+    strip_trailing_comma(&mut out);
+    while let Some(closer) = closers.pop() {
+        out.push(closer);
+    }
+    out
+}
+
+fn strip_trailing_comma(out: &mut String) {
+    let trimmed_len = out.trim_end().len();
+    if out[..trimmed_len].ends_with(',') {
+        out.truncate(trimmed_len - 1);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Backend adapter
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Backend adapter that implements tool calling through the prompt.
+pub struct PromptToolBackend {
+    inner: SharedBackend,
+    format: ToolPromptFormat,
+    max_repairs: u32,
+}
+
+impl PromptToolBackend {
+    /// Wrap a backend using the given tool format.
+    pub fn new(inner: SharedBackend, format: ToolPromptFormat) -> Self {
+        Self {
+            inner,
+            format,
+            max_repairs: DEFAULT_TOOL_REPAIRS,
+        }
+    }
+
+    /// Set how many times a malformed call is sent back for correction.
+    pub fn with_max_repairs(mut self, max_repairs: u32) -> Self {
+        self.max_repairs = max_repairs;
+        self
+    }
+
+    /// The tool format in use.
+    pub fn format(&self) -> ToolPromptFormat {
+        self.format
+    }
+
+    /// Rewrite a native tool request into a plain-text one.
+    pub fn prepare_request(&self, mut request: CompletionRequest) -> CompletionRequest {
+        let tools = std::mem::take(&mut request.tools);
+        let choice = request.tool_choice.take();
+
+        if !tools.is_empty() && !matches!(choice, Some(ToolChoice::None)) {
+            let mut section = format!(
+                "{}\n\n{}",
+                self.format_tool_definitions(&tools),
+                self.tool_calling_instructions().unwrap_or_default()
+            );
+            match choice {
+                Some(ToolChoice::Any) => {
+                    section.push_str("\n\nYou must call at least one tool in this response.")
+                }
+                Some(ToolChoice::Tool { ref name }) => section.push_str(&format!(
+                    "\n\nYou must call the `{}` tool in this response.",
+                    name
+                )),
+                _ => {}
+            }
+            let system = match request.system.take() {
+                Some(existing) => format!("{}\n\n{}", existing.to_text(), section),
+                None => section,
+            };
+            request.system = Some(SystemPrompt::Text(system));
+        }
+
+        // Tool names by call ID, for rendering results.
+        let mut names: HashMap<String, String> = HashMap::new();
+        request.messages = request
+            .messages
+            .into_iter()
+            .map(|message| self.render_message(message, &mut names))
+            .collect();
+        request
+    }
+
+    fn render_message(&self, message: Message, names: &mut HashMap<String, String>) -> Message {
+        let Content::Blocks(blocks) = message.content else {
+            return message;
+        };
+        if !blocks
+            .iter()
+            .any(|b| !matches!(b, ContentBlock::Text { .. }))
+        {
+            return Message {
+                role: message.role,
+                content: Content::Blocks(blocks),
+            };
+        }
+
+        let parts: Vec<String> = blocks
+            .into_iter()
+            .map(|block| match block {
+                ContentBlock::Text { text, .. } => text,
+                ContentBlock::ToolUse {
+                    id, name, input, ..
+                } => {
+                    let rendered = self.format.format_call(&name, &input);
+                    names.insert(id, name);
+                    rendered
+                }
+                ContentBlock::ToolResult {
+                    tool_use_id,
+                    content,
+                    is_error,
+                    ..
+                } => {
+                    let tool = names.get(&tool_use_id).unwrap_or(&tool_use_id);
+                    self.format
+                        .format_result(tool, &result_text(content.as_ref()), is_error)
+                }
+            })
+            .collect();
+        Message {
+            role: message.role,
+            content: Content::Text(parts.join("\n\n")),
+        }
+    }
+
+    /// Convert parsed calls into a native-looking response.
+    fn rewrite_response(
+        &self,
+        mut response: CompletionResponse,
+    ) -> (CompletionResponse, Vec<String>) {
+        let mut content = Vec::new();
+        let mut malformed = Vec::new();
+        let mut has_calls = false;
+
+        for block in std::mem::take(&mut response.content) {
+            match block {
+                ContentBlock::Text { text, .. } => {
+                    let parsed = self.format.parse(&text);
+                    if !parsed.text.is_empty() {
+                        content.push(ContentBlock::text(parsed.text));
+                    }
+                    has_calls |= !parsed.calls.is_empty();
+                    content.extend(parsed.calls.into_iter().map(ContentBlock::from));
+                    malformed.extend(parsed.malformed);
+                }
+                other => {
+                    has_calls |= matches!(other, ContentBlock::ToolUse { .. });
+                    content.push(other);
+                }
+            }
+        }
+
+        response.content = content;
+        if has_calls {
+            response.stop_reason = Some(StopReason::ToolUse);
+        }
+        (response, malformed)
+    }
+}
+
+fn result_text(content: Option<&ToolResultContent>) -> String {
+    match content {
+        None => String::new(),
+        Some(ToolResultContent::Text(text)) => text.clone(),
+        Some(ToolResultContent::Blocks(blocks)) => blocks
+            .iter()
+            .map(|b| match b.get("text").and_then(Value::as_str) {
+                Some(text) => text.to_string(),
+                None => b.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+#[async_trait]
+impl LlmBackend for PromptToolBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let mut request = self.prepare_request(request);
+        let mut spent = Usage::default();
+        let mut attempt = 0;
+
+        loop {
+            let raw = self.inner.complete(request.clone()).await?;
+            let raw_text = raw.text();
+            let (mut response, malformed) = self.rewrite_response(raw);
+            // Report the usage of failed attempts on the final response.
+            response.usage.input_tokens += spent.input_tokens;
+            response.usage.output_tokens += spent.output_tokens;
+
+            if malformed.is_empty() || response.has_tool_use() || attempt >= self.max_repairs {
+                if !malformed.is_empty() {
+                    tracing::warn!(errors = ?malformed, "Dropping malformed prompt-based tool calls");
+                }
+                return Ok(response);
+            }
+
+            attempt += 1;
+            tracing::debug!(attempt, errors = ?malformed, "Repairing malformed tool call");
+            spent = response.usage;
+            request.messages.push(Message::assistant(raw_text));
+            request.messages.push(Message::user(format!(
+                "Your tool call could not be parsed:\n- {}\n\nRe-emit the call using exactly the format from the instructions.",
+                malformed.join("\n- ")
+            )));
+        }
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let request = self.prepare_request(request);
+        let inner = self.inner.complete_stream(request).await?;
+        let state = StreamState {
+            parser: ToolCallStreamParser::new(self.format),
+            next_index: 0,
+            text_block: None,
+            saw_call: false,
+            flushed: false,
+        };
+
+        let stream = futures::stream::unfold(
+            (inner, state, false),
+            |(mut inner, mut state, done)| async move {
+                if done {
+                    return None;
+                }
+                match inner.next().await {
+                    Some(Ok(event)) => {
+                        let events = state.translate(event);
+                        Some((events, (inner, state, false)))
+                    }
+                    Some(Err(e)) => Some((vec![Err(e)], (inner, state, true))),
+                    None => {
+                        let events = state.flush();
+                        Some((events, (inner, state, true)))
+                    }
+                }
+            },
+        )
+        .flat_map(futures::stream::iter);
+
+        Ok(Box::pin(stream))
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        false
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.inner.supports_structured_output()
+    }
+
+    fn tool_calling_instructions(&self) -> Option<&str> {
+        Some(self.format.instructions())
+    }
+
+    fn format_tool_definitions(&self, tools: &[ToolDefinition]) -> String {
+        self.format.format_definitions(tools)
+    }
+
+    fn format_tool_result(&self, tool_use_id: &str, content: &str, is_error: bool) -> String {
+        self.format.format_result(tool_use_id, content, is_error)
+    }
+
+    fn parse_tool_calls(&self, text: &str) -> (String, Vec<ParsedToolCall>) {
+        let parsed = self.format.parse(text);
+        (parsed.text, parsed.calls)
+    }
+}
+
+/// Re-indexes content blocks while tool calls are split out of the text.
+struct StreamState {
+    parser: ToolCallStreamParser,
+    next_index: usize,
+    text_block: Option<usize>,
+    saw_call: bool,
+    flushed: bool,
+}
+
+impl StreamState {
+    fn translate(&mut self, event: StreamEvent) -> Vec<Result<StreamEvent>> {
+        match event {
+            StreamEvent::ContentBlockDelta {
+                delta: ContentDelta::TextDelta(text),
+                ..
+            } => {
+                let segments = self.parser.push(&text);
+                self.emit(segments)
+            }
+            // Block boundaries are re-derived from the parsed segments.
+            StreamEvent::ContentBlockStart { .. } | StreamEvent::ContentBlockStop { .. } => {
+                Vec::new()
+            }
+            StreamEvent::ContentBlockDelta { delta, .. } => {
+                // Native tool input from an inner backend that ignored the prompt.
+                let index = self.next_index;
+                self.next_index += 1;
+                self.saw_call = true;
+                self.close_text()
+                    .into_iter()
+                    .chain([
+                        StreamEvent::ContentBlockStart {
+                            index,
+                            content_type: "tool_use".to_string(),
+                        },
+                        StreamEvent::ContentBlockDelta { index, delta },
+                        StreamEvent::ContentBlockStop { index },
+                    ])
+                    .map(Ok)
+                    .collect()
+            }
+            StreamEvent::MessageDelta { stop_reason, usage } => {
+                let mut events = self.flush();
+                events.push(Ok(StreamEvent::MessageDelta {
+                    stop_reason: if self.saw_call {
+                        StopReason::ToolUse
+                    } else {
+                        stop_reason
+                    },
+                    usage,
+                }));
+                events
+            }
+            StreamEvent::MessageStop => {
+                let mut events = self.flush();
+                events.push(Ok(StreamEvent::MessageStop));
+                events
+            }
+            other => vec![Ok(other)],
+        }
+    }
+
+    fn emit(&mut self, segments: Vec<ToolCallSegment>) -> Vec<Result<StreamEvent>> {
+        let mut events = Vec::new();
+        for segment in segments {
+            match segment {
+                ToolCallSegment::Text(text) => {
+                    let index = match self.text_block {
+                        Some(index) => index,
+                        None => {
+                            let index = self.next_index;
+                            self.next_index += 1;
+                            self.text_block = Some(index);
+                            events.push(StreamEvent::ContentBlockStart {
+                                index,
+                                content_type: "text".to_string(),
+                            });
+                            index
+                        }
+                    };
+                    events.push(StreamEvent::ContentBlockDelta {
+                        index,
+                        delta: ContentDelta::TextDelta(text),
+                    });
+                }
+                ToolCallSegment::Call(call) => {
+                    events.extend(self.close_text());
+                    let index = self.next_index;
+                    self.next_index += 1;
+                    self.saw_call = true;
+                    events.push(StreamEvent::ContentBlockStart {
+                        index,
+                        content_type: "tool_use".to_string(),
+                    });
+                    events.push(StreamEvent::ContentBlockDelta {
+                        index,
+                        delta: ContentDelta::InputJsonDelta(call.arguments.to_string()),
+                    });
+                    events.push(StreamEvent::ContentBlockStop { index });
+                }
+                ToolCallSegment::Malformed(error) => {
+                    tracing::warn!(error = %error, "Malformed prompt-based tool call in stream");
+                }
+            }
+        }
+        events.into_iter().map(Ok).collect()
+    }
+
+    fn close_text(&mut self) -> Option<StreamEvent> {
+        self.text_block
+            .take()
+            .map(|index| StreamEvent::ContentBlockStop { index })
+    }
+
+    /// Flush the parser once, at the first end-of-message signal.
+    fn flush(&mut self) -> Vec<Result<StreamEvent>> {
+        if self.flushed {
+            return Vec::new();
+        }
+        self.flushed = true;
+        let segments = self.parser.finish();
+        let mut events = self.emit(segments);
+        events.extend(self.close_text().map(Ok));
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::types::ToolResultBlock;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn tool() -> ToolDefinition {
+        ToolDefinition::new(
+            "read_file",
+            "Read a file",
+            json!({"type": "object", "properties": {"path": {"type": "string"}}}),
+        )
+    }
+
+    #[test]
+    fn test_parse_each_format() {
+        let cases = [
+            (
+                ToolPromptFormat::Json,
+                "Let me look.\n```tool_call\n{\"name\": \"read_file\", \"arguments\": {\"path\": \"a.rs\"}}\n```",
+            ),
+            (
+                ToolPromptFormat::Xml,
+                "Let me look.\n<tool_call><name>read_file</name><arguments>{\"path\": \"a.rs\"}</arguments></tool_call>",
+            ),
+            (
+                ToolPromptFormat::Hermes,
+                "Let me look.\n<tool_call>\n{\"name\": \"read_file\", \"arguments\": {\"path\": \"a.rs\"}}\n</tool_call>",
+            ),
+        ];
+        for (format, text) in cases {
+            let parsed = format.parse(text);
+            assert_eq!(parsed.text, "Let me look.", "{}", format);
+            assert_eq!(parsed.calls.len(), 1, "{}", format);
+            assert_eq!(parsed.calls[0].name, "read_file");
+            assert_eq!(parsed.calls[0].arguments["path"], "a.rs");
+            assert!(parsed.malformed.is_empty());
+
+            // Rendered calls parse back to the same call
+            let rendered = format.format_call("read_file", &json!({"path": "b.rs"}));
+            assert_eq!(format.parse(&rendered).calls[0].arguments["path"], "b.rs");
+        }
+    }
+
+    #[test]
+    fn test_parse_repairs_and_reports_malformed() {
+        let format = ToolPromptFormat::Hermes;
+        // Trailing comma, Python literal, unclosed call at end of output
+        let parsed = format.parse(
+            "<tool_call>{'x': 1}</tool_call><tool_call>{\"name\": \"grep\", \"arguments\": {\"all\": True,}",
+        );
+        assert_eq!(parsed.calls.len(), 1);
+        assert_eq!(parsed.calls[0].name, "grep");
+        assert_eq!(parsed.calls[0].arguments["all"], true);
+        assert_eq!(parsed.malformed.len(), 1);
+
+        // Double-encoded arguments
+        let parsed = format.parse(
+            r#"<tool_call>{"name": "read_file", "arguments": "{\"path\": \"a\"}"}</tool_call>"#,
+        );
+        assert_eq!(parsed.calls[0].arguments["path"], "a");
+    }
+
+    #[test]
+    fn test_repair_json() {
+        assert_eq!(
+            repair_json("```json\n{\"a\": [1, 2,],}\n```").unwrap(),
+            json!({"a": [1, 2]})
+        );
+        assert_eq!(
+            repair_json("Sure: {\"a\": \"line\nbreak\", \"b\": None} trailing").unwrap(),
+            json!({"a": "line\nbreak", "b": null})
+        );
+        assert_eq!(
+            repair_json("{\"a\": {\"b\": \"unterminated").unwrap(),
+            json!({"a": {"b": "unterminated"}})
+        );
+        assert!(repair_json("not json at all").is_err());
+    }
+
+    #[test]
+    fn test_stream_parser_handles_split_markers() {
+        let mut parser = ToolCallStreamParser::new(ToolPromptFormat::Xml);
+        let text = "Hi <b>there</b> <tool_call><name>ls</name></tool_call> done";
+        let mut segments = Vec::new();
+        for c in text.chars() {
+            segments.extend(parser.push(&c.to_string()));
+        }
+        segments.extend(parser.finish());
+
+        let visible: String = segments
+            .iter()
+            .filter_map(|s| match s {
+                ToolCallSegment::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(visible, "Hi <b>there</b>  done");
+        let calls: Vec<_> = segments
+            .iter()
+            .filter_map(|s| match s {
+                ToolCallSegment::Call(c) => Some(c.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(calls, vec!["ls"]);
+    }
+
+    #[test]
+    fn test_prepare_request_renders_tools_and_history() {
+        let backend = PromptToolBackend::new(
+            Arc::new(MockBackend::with_text("")),
+            ToolPromptFormat::Hermes,
+        );
+        let request = CompletionRequest::new(
+            "m",
+            vec![
+                Message::user("Read a.rs"),
+                Message::assistant_blocks(vec![ContentBlock::tool_use(
+                    "call_1",
+                    "read_file",
+                    json!({"path": "a.rs"}),
+                )]),
+                Message::tool_results(vec![ToolResultBlock::success("call_1", "fn main() {}")]),
+            ],
+            100,
+        )
+        .with_system("Be helpful.")
+        .with_tools(vec![tool()]);
+
+        let prepared = backend.prepare_request(request);
+        assert!(prepared.tools.is_empty());
+        let system = prepared.system.unwrap().to_text();
+        assert!(system.starts_with("Be helpful."));
+        assert!(system.contains("<tools>"));
+        assert!(system.contains("\"read_file\""));
+
+        let call = prepared.messages[1].content.as_text().unwrap();
+        assert!(call.starts_with("<tool_call>"));
+        let result = prepared.messages[2].content.as_text().unwrap();
+        assert!(result.contains("<tool_response>"));
+        assert!(result.contains("\"name\":\"read_file\""));
+        assert!(result.contains("fn main() {}"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_returns_tool_use() {
+        let backend = PromptToolBackend::new(
+            Arc::new(MockBackend::with_text(
+                "```tool_call\n{\"name\": \"read_file\", \"arguments\": {\"path\": \"a\"}}\n```",
+            )),
+            ToolPromptFormat::Json,
+        );
+        let request =
+            CompletionRequest::new("m", vec![Message::user("go")], 100).with_tools(vec![tool()]);
+
+        let response = backend.complete(request).await.unwrap();
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        let uses = response.tool_uses();
+        assert_eq!(uses.len(), 1);
+        assert_eq!(uses[0].name, "read_file");
+        assert!(uses[0].id.starts_with("call_"));
+        assert!(!backend.supports_native_tools());
+    }
+
+    #[tokio::test]
+    async fn test_complete_repairs_malformed_call() {
+        let text = |t: &str| {
+            CompletionResponse::new(
+                "msg",
+                "m",
+                vec![ContentBlock::text(t)],
+                StopReason::EndTurn,
+                Usage::new(10, 5),
+            )
+        };
+        let mock = Arc::new(MockBackend::new(vec![
+            text("<tool_call>{\"arguments\": {}}</tool_call>"),
+            text("<tool_call>{\"name\": \"read_file\", \"arguments\": {}}</tool_call>"),
+        ]));
+        let backend = PromptToolBackend::new(mock.clone(), ToolPromptFormat::Hermes);
+        let request =
+            CompletionRequest::new("m", vec![Message::user("go")], 100).with_tools(vec![tool()]);
+
+        let response = backend.complete(request).await.unwrap();
+        assert_eq!(response.tool_uses()[0].name, "read_file");
+        assert_eq!(response.usage.input_tokens, 20);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        let repair = requests[1].messages.last().unwrap().content.to_text();
+        assert!(repair.contains("missing \"name\" field"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_emits_tool_use_blocks() {
+        let backend = PromptToolBackend::new(
+            Arc::new(MockBackend::with_text(
+                "Checking. <tool_call>{\"name\": \"read_file\", \"arguments\": {\"path\": \"a\"}}</tool_call>",
+            )),
+            ToolPromptFormat::Hermes,
+        );
+        let request = CompletionRequest::new("m", vec![Message::user("go")], 100)
+            .with_tools(vec![tool()])
+            .with_streaming();
+
+        let events: Vec<StreamEvent> = backend
+            .complete_stream(request)
+            .await
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::ContentBlockDelta {
+                    delta: ContentDelta::TextDelta(t),
+                    ..
+                } => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Checking. ");
+        assert!(events.iter().any(|e| matches!(
+            e,
+            StreamEvent::ContentBlockStart { index: 1, content_type } if content_type == "tool_use"
+        )));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            StreamEvent::MessageDelta {
+                stop_reason: StopReason::ToolUse,
+                ..
+            }
+        )));
+        assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+    }
+}
@@ -0,0 +1,220 @@
+//! Backends that can be replaced while the server runs.
+//!
+//! A [`ReloadableBackend`] sits innermost in a profile's wrapper stack
+//! (below capture and metrics), so everything holding the profile's
+//! [`SharedBackend`] — the agent, the router, subagents, the indexer —
+//! picks up a new provider config on its next call.
+//!
+//! Requests still name the model the profile started with (the agent and
+//! router are built with it), so the wrapper rewrites that model to the
+//! current one. Swaps that would change how tools are called (a different
+//! provider or tool format) are refused; those need a restart.
+//!
+//! ```rust,ignore
+//! use arawn_llm::ReloadableBackend;
+//!
+//! let reloadable = Arc::new(ReloadableBackend::new(backend, "claude-sonnet-4-20250514"));
+//! let shared: SharedBackend = reloadable.clone();
+//! // later, after the profile's config changed
+//! reloadable.swap(new_backend, "claude-opus-4-20250514")?;
+//! ```
+
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::backend::{LlmBackend, ParsedToolCall, ResponseStream, SharedBackend};
+use crate::error::Result;
+use crate::types::{CompletionRequest, CompletionResponse, ToolDefinition};
+
+/// The backend and model currently in use.
+struct Current {
+    backend: SharedBackend,
+    model: String,
+}
+
+/// Wraps a backend so it can be swapped for another of the same kind.
+pub struct ReloadableBackend {
+    /// Provider name, fixed because `name()` hands out a borrow.
+    name: String,
+    /// Tool calling instructions, fixed for the same reason.
+    tool_instructions: Option<String>,
+    native_tools: bool,
+    /// Model requests name when they were built from the startup config.
+    initial_model: String,
+    current: RwLock<Current>,
+}
+
+impl ReloadableBackend {
+    /// Wrap `backend`, which serves `model`.
+    pub fn new(backend: SharedBackend, model: impl Into<String>) -> Self {
+        let model = model.into();
+        Self {
+            name: backend.name().to_string(),
+            tool_instructions: backend.tool_calling_instructions().map(str::to_string),
+            native_tools: backend.supports_native_tools(),
+            initial_model: model.clone(),
+            current: RwLock::new(Current { backend, model }),
+        }
+    }
+
+    /// The model requests are currently sent with.
+    pub fn model(&self) -> String {
+        self.read().model.clone()
+    }
+
+    /// Replace the backend and model.
+    ///
+    /// Fails without changing anything if `backend` is a different provider
+    /// or calls tools differently, since callers have already built prompts
+    /// around the current one.
+    pub fn swap(
+        &self,
+        backend: SharedBackend,
+        model: impl Into<String>,
+    ) -> std::result::Result<(), String> {
+        if backend.name() != self.name {
+            return Err(format!(
+                "provider changed from '{}' to '{}'",
+                self.name,
+                backend.name()
+            ));
+        }
+        if backend.supports_native_tools() != self.native_tools
+            || backend.tool_calling_instructions() != self.tool_instructions.as_deref()
+        {
+            return Err("tool calling format changed".to_string());
+        }
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        *current = Current {
+            backend,
+            model: model.into(),
+        };
+        Ok(())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Current> {
+        self.current.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn backend(&self) -> SharedBackend {
+        self.read().backend.clone()
+    }
+
+    /// Point requests for the startup model at the current one.
+    fn prepare(&self, mut request: CompletionRequest) -> (SharedBackend, CompletionRequest) {
+        let current = self.read();
+        if request.model == self.initial_model {
+            request.model = current.model.clone();
+        }
+        (current.backend.clone(), request)
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ReloadableBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let (backend, request) = self.prepare(request);
+        backend.complete(request).await
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let (backend, request) = self.prepare(request);
+        backend.complete_stream(request).await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.native_tools
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.backend().supports_structured_output()
+    }
+
+    fn tool_calling_instructions(&self) -> Option<&str> {
+        self.tool_instructions.as_deref()
+    }
+
+    fn format_tool_definitions(&self, tools: &[ToolDefinition]) -> String {
+        self.backend().format_tool_definitions(tools)
+    }
+
+    fn format_tool_result(&self, tool_use_id: &str, content: &str, is_error: bool) -> String {
+        self.backend()
+            .format_tool_result(tool_use_id, content, is_error)
+    }
+
+    fn parse_tool_calls(&self, text: &str) -> (String, Vec<ParsedToolCall>) {
+        self.backend().parse_tool_calls(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::types::Message;
+
+    fn request(model: &str) -> CompletionRequest {
+        CompletionRequest::new(model, vec![Message::user("Hi")], 100)
+    }
+
+    #[tokio::test]
+    async fn test_swap_replaces_backend_and_model() {
+        let first = Arc::new(MockBackend::with_text("first"));
+        let second = Arc::new(MockBackend::with_text("second"));
+        let backend = ReloadableBackend::new(first.clone(), "model-a");
+
+        let response = backend.complete(request("model-a")).await.unwrap();
+        assert_eq!(response.text(), "first");
+
+        backend.swap(second.clone(), "model-b").unwrap();
+        assert_eq!(backend.model(), "model-b");
+
+        let response = backend.complete(request("model-a")).await.unwrap();
+        assert_eq!(response.text(), "second");
+        assert_eq!(second.requests()[0].model, "model-b");
+        assert_eq!(first.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_other_models_pass_through() {
+        let inner = Arc::new(MockBackend::with_text("ok"));
+        let backend = ReloadableBackend::new(Arc::new(MockBackend::with_text("x")), "model-a");
+        backend.swap(inner.clone(), "model-b").unwrap();
+
+        backend.complete(request("indexer-model")).await.unwrap();
+        assert_eq!(inner.requests()[0].model, "indexer-model");
+    }
+
+    #[test]
+    fn test_swap_refuses_other_provider() {
+        struct Named(MockBackend);
+
+        #[async_trait]
+        impl LlmBackend for Named {
+            async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+                self.0.complete(request).await
+            }
+            async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+                self.0.complete_stream(request).await
+            }
+            fn name(&self) -> &str {
+                "other"
+            }
+        }
+
+        let backend = ReloadableBackend::new(Arc::new(MockBackend::with_text("a")), "model-a");
+        let err = backend
+            .swap(Arc::new(Named(MockBackend::with_text("b"))), "model-b")
+            .unwrap_err();
+        assert!(err.contains("provider changed"));
+        assert_eq!(backend.model(), "model-a");
+    }
+}
@@ -0,0 +1,339 @@
+//! Deterministic replay of captured LLM calls.
+//!
+//! [`ReplayBackend`] serves the responses stored in [`CaptureRecord`]s,
+//! matching each incoming request by [`request_fingerprint`]. Identical
+//! requests are served in recording order. A captured session can thereby be
+//! re-run offline as a regression test.
+//!
+//! ```rust,ignore
+//! use arawn_llm::ReplayBackend;
+//!
+//! let backend = ReplayBackend::from_path("tests/fixtures/session.jsonl")?;
+//! let agent = Agent::builder().with_backend(backend).build()?;
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::backend::{ContentDelta, LlmBackend, ResponseStream, StreamEvent};
+use crate::capture::{CaptureRecord, read_captures, request_fingerprint};
+use crate::error::{LlmError, Result};
+use crate::types::{CompletionRequest, CompletionResponse, ContentBlock, StopReason, Usage};
+
+/// Backend that serves recorded responses keyed by request fingerprint.
+pub struct ReplayBackend {
+    state: Mutex<ReplayState>,
+    sequential_fallback: bool,
+    native_tools: bool,
+    structured_output: bool,
+}
+
+struct ReplayState {
+    records: Vec<CaptureRecord>,
+    used: Vec<bool>,
+    by_fingerprint: HashMap<String, VecDeque<usize>>,
+}
+
+impl ReplayBackend {
+    /// Create a replay backend from records, in recording order.
+    pub fn new(records: Vec<CaptureRecord>) -> Self {
+        let mut by_fingerprint: HashMap<String, VecDeque<usize>> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            by_fingerprint
+                .entry(record.fingerprint.clone())
+                .or_default()
+                .push_back(i);
+        }
+        Self {
+            state: Mutex::new(ReplayState {
+                used: vec![false; records.len()],
+                records,
+                by_fingerprint,
+            }),
+            sequential_fallback: false,
+            native_tools: true,
+            structured_output: true,
+        }
+    }
+
+    /// Load records from a capture file or directory.
+    pub fn from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::new(read_captures(path.as_ref())?))
+    }
+
+    /// Serve the next unused record when no fingerprint matches.
+    ///
+    /// Useful when requests contain volatile content (timestamps in the
+    /// system prompt, generated IDs) but the call order is stable.
+    pub fn with_sequential_fallback(mut self) -> Self {
+        self.sequential_fallback = true;
+        self
+    }
+
+    /// Set the capabilities reported to callers.
+    ///
+    /// Defaults to native tools and structured output, matching the hosted
+    /// backends captures usually come from; these must match the original
+    /// backend for fingerprints to line up.
+    pub fn with_capabilities(mut self, native_tools: bool, structured_output: bool) -> Self {
+        self.native_tools = native_tools;
+        self.structured_output = structured_output;
+        self
+    }
+
+    /// Number of records not yet served.
+    pub fn remaining(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .used
+            .iter()
+            .filter(|u| !**u)
+            .count()
+    }
+
+    fn take(&self, request: &CompletionRequest) -> Result<CaptureRecord> {
+        let fingerprint = request_fingerprint(request);
+        let mut state = self.state.lock().unwrap();
+        let ReplayState {
+            records,
+            used,
+            by_fingerprint,
+        } = &mut *state;
+
+        // Queues may hold records already consumed by the sequential fallback.
+        let matched = by_fingerprint
+            .get_mut(&fingerprint)
+            .and_then(|queue| std::iter::from_fn(|| queue.pop_front()).find(|i| !used[*i]));
+        let index = matched
+            .or_else(|| {
+                self.sequential_fallback
+                    .then(|| used.iter().position(|u| !*u))
+                    .flatten()
+            })
+            .ok_or_else(|| {
+                LlmError::Backend(format!(
+                    "ReplayBackend: no recorded response for request {} (model {}, {} messages)",
+                    fingerprint,
+                    request.model,
+                    request.messages.len()
+                ))
+            })?;
+
+        used[index] = true;
+        Ok(records[index].clone())
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ReplayBackend {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let record = self.take(&request)?;
+        if let Some(error) = record.error {
+            return Err(LlmError::Backend(error));
+        }
+        match record.response {
+            Some(response) => Ok(response),
+            None => Ok(events_to_response(&record.stream_events)),
+        }
+    }
+
+    async fn complete_stream(&self, request: CompletionRequest) -> Result<ResponseStream> {
+        let record = self.take(&request)?;
+        let mut events: Vec<Result<StreamEvent>> = if !record.stream_events.is_empty() {
+            record.stream_events.into_iter().map(Ok).collect()
+        } else if let Some(ref response) = record.response {
+            response_to_events(response).into_iter().map(Ok).collect()
+        } else {
+            Vec::new()
+        };
+        if let Some(error) = record.error {
+            if events.is_empty() {
+                return Err(LlmError::Backend(error));
+            }
+            events.push(Err(LlmError::Backend(error)));
+        }
+        Ok(Box::pin(futures::stream::iter(events)))
+    }
+
+    fn name(&self) -> &str {
+        "replay"
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.native_tools
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        self.structured_output
+    }
+}
+
+/// Rebuild a response from recorded stream events (text content only).
+fn events_to_response(events: &[StreamEvent]) -> CompletionResponse {
+    let mut id = String::new();
+    let mut model = String::new();
+    let mut texts: Vec<(usize, String)> = Vec::new();
+    let mut stop_reason = StopReason::EndTurn;
+    let mut usage = Usage::default();
+
+    for event in events {
+        match event {
+            StreamEvent::MessageStart { id: i, model: m } => {
+                id = i.clone();
+                model = m.clone();
+            }
+            StreamEvent::ContentBlockDelta {
+                index,
+                delta: ContentDelta::TextDelta(text),
+            } => match texts.iter_mut().find(|(i, _)| i == index) {
+                Some((_, existing)) => existing.push_str(text),
+                None => texts.push((*index, text.clone())),
+            },
+            StreamEvent::MessageDelta {
+                stop_reason: reason,
+                usage: u,
+            } => {
+                stop_reason = *reason;
+                usage = u.clone();
+            }
+            _ => {}
+        }
+    }
+
+    let content = texts
+        .into_iter()
+        .map(|(_, text)| ContentBlock::Text {
+            text,
+            cache_control: None,
+        })
+        .collect();
+    CompletionResponse::new(id, model, content, stop_reason, usage)
+}
+
+/// Synthesize the stream events for a recorded non-streaming response.
+fn response_to_events(response: &CompletionResponse) -> Vec<StreamEvent> {
+    let mut events = vec![StreamEvent::MessageStart {
+        id: response.id.clone(),
+        model: response.model.clone(),
+    }];
+    for (index, block) in response.content.iter().enumerate() {
+        let (content_type, delta) = match block {
+            ContentBlock::Text { text, .. } => ("text", ContentDelta::TextDelta(text.clone())),
+            ContentBlock::ToolUse { input, .. } => {
+                ("tool_use", ContentDelta::InputJsonDelta(input.to_string()))
+            }
+            _ => continue,
+        };
+        events.push(StreamEvent::ContentBlockStart {
+            index,
+            content_type: content_type.to_string(),
+        });
+        events.push(StreamEvent::ContentBlockDelta { index, delta });
+        events.push(StreamEvent::ContentBlockStop { index });
+    }
+    events.push(StreamEvent::MessageDelta {
+        stop_reason: response.stop_reason.unwrap_or(StopReason::EndTurn),
+        usage: response.usage.clone(),
+    });
+    events.push(StreamEvent::MessageStop);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Message;
+    use futures::StreamExt;
+
+    fn record(prompt: &str, reply: &str) -> CaptureRecord {
+        let request = CompletionRequest::new("m", vec![Message::user(prompt)], 100);
+        CaptureRecord {
+            id: prompt.to_string(),
+            timestamp: String::new(),
+            duration_ms: 0,
+            fingerprint: request_fingerprint(&request),
+            backend: "test".to_string(),
+            request,
+            response: Some(CompletionResponse::new(
+                "msg",
+                "m",
+                vec![ContentBlock::Text {
+                    text: reply.to_string(),
+                    cache_control: None,
+                }],
+                StopReason::EndTurn,
+                Usage::new(1, 2),
+            )),
+            stream_events: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn request(prompt: &str) -> CompletionRequest {
+        CompletionRequest::new("m", vec![Message::user(prompt)], 100)
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_by_fingerprint_in_order() {
+        let backend = ReplayBackend::new(vec![
+            record("a", "first a"),
+            record("b", "only b"),
+            record("a", "second a"),
+        ]);
+
+        assert_eq!(
+            backend.complete(request("b")).await.unwrap().text(),
+            "only b"
+        );
+        assert_eq!(
+            backend.complete(request("a")).await.unwrap().text(),
+            "first a"
+        );
+        assert_eq!(
+            backend.complete(request("a")).await.unwrap().text(),
+            "second a"
+        );
+        assert_eq!(backend.remaining(), 0);
+
+        let err = backend.complete(request("a")).await.unwrap_err();
+        assert!(err.to_string().contains("no recorded response"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_sequential_fallback() {
+        let backend =
+            ReplayBackend::new(vec![record("a", "A"), record("b", "B")]).with_sequential_fallback();
+
+        assert_eq!(
+            backend.complete(request("changed")).await.unwrap().text(),
+            "A"
+        );
+        // "a" was consumed by the fallback, so its fingerprint no longer matches.
+        assert_eq!(backend.complete(request("a")).await.unwrap().text(), "B");
+    }
+
+    #[tokio::test]
+    async fn test_replay_converts_between_stream_and_response() {
+        let backend = ReplayBackend::new(vec![record("a", "hello")]);
+        let events: Vec<StreamEvent> = backend
+            .complete_stream(request("a").with_streaming())
+            .await
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+        assert!(matches!(events.last(), Some(StreamEvent::MessageStop)));
+
+        let mut streamed = record("b", "");
+        streamed.response = None;
+        streamed.stream_events = events;
+        let backend = ReplayBackend::new(vec![streamed]);
+        let response = backend.complete(request("b")).await.unwrap();
+        assert_eq!(response.text(), "hello");
+        assert_eq!(response.usage.output_tokens, 2);
+    }
+}
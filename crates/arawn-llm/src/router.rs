@@ -0,0 +1,859 @@
+//! Cost-aware routing of completion requests across LLM profiles.
+//!
+//! The [`LlmRouter`] picks one of several named profiles (a backend plus a
+//! model) for each request, so cheap models can serve short or tool-free
+//! calls while expensive models handle long or complex ones.
+//!
+//! Selection happens in this order:
+//!
+//! 1. **Explicit hint** — a hint naming a profile (e.g. from a skill or a
+//!    plugin agent) selects that profile directly.
+//! 2. **Rules** — the first [`RoutingRule`] whose [`RouteCondition`] matches
+//!    the [`RoutingContext`] wins. Conditions cover estimated prompt length,
+//!    tool presence, agent loop iteration and hints.
+//! 3. **Classifier** — an optional [`RouteClassifier`] (e.g. a small model)
+//!    labels the request; its pick is used if confident enough.
+//! 4. **Default profile**.
+//!
+//! Every decision carries a human-readable reason that is recorded as
+//! [`RoutingMetadata`] in the interaction log.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use arawn_llm::router::{LlmRouter, RouteCondition, RouteProfile, RoutingContext, RoutingRule};
+//!
+//! let router = LlmRouter::new(RouteProfile::new("quality", opus, "claude-opus-4"))
+//!     .with_profile(RouteProfile::new("fast", haiku, "claude-haiku-4"))
+//!     .with_rule(RoutingRule::new(
+//!         "short-no-tools",
+//!         RouteCondition::default().max_prompt_tokens(2000).has_tools(false),
+//!         "fast",
+//!     ));
+//! router.validate()?;
+//!
+//! let ctx = RoutingContext::from_request(&request).with_iteration(1);
+//! let decision = router.route(&request, &ctx).await;
+//! let response = decision.backend.complete(decision.apply(request)).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::backend::SharedBackend;
+use crate::error::{LlmError, Result};
+use crate::interaction_log::RoutingMetadata;
+use crate::types::{CompletionRequest, Message, Role};
+
+/// Approximate characters per token used for prompt-length estimates.
+const CHARS_PER_TOKEN: usize = 4;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Profiles and context
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A routable LLM profile: a backend and the model to request from it.
+#[derive(Clone)]
+pub struct RouteProfile {
+    /// Profile name (key in `llm_profiles`).
+    pub name: String,
+    /// Backend serving this profile.
+    pub backend: SharedBackend,
+    /// Model identifier sent with requests routed here.
+    pub model: String,
+}
+
+impl RouteProfile {
+    /// Create a new profile.
+    pub fn new(name: impl Into<String>, backend: SharedBackend, model: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            backend,
+            model: model.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for RouteProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteProfile")
+            .field("name", &self.name)
+            .field("backend", &self.backend.name())
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+/// Request features the router decides on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoutingContext {
+    /// Estimated prompt size in tokens (system prompt + messages + tools).
+    pub estimated_prompt_tokens: usize,
+    /// Whether the request offers tools to the model.
+    pub has_tools: bool,
+    /// Agent loop iteration (1 for the first call of a turn, 0 if unknown).
+    pub iteration: u32,
+    /// Explicit routing hint from a skill, agent or caller.
+    pub hint: Option<String>,
+}
+
+impl RoutingContext {
+    /// Derive prompt size and tool presence from a request.
+    pub fn from_request(request: &CompletionRequest) -> Self {
+        let system_chars = request.system.as_ref().map_or(0, |s| s.to_text().len());
+        let message_chars: usize = request.messages.iter().map(message_chars).sum();
+        let tool_chars: usize = request
+            .tools
+            .iter()
+            .map(|t| t.name.len() + t.description.len() + t.input_schema.to_string().len())
+            .sum();
+
+        Self {
+            estimated_prompt_tokens: (system_chars + message_chars + tool_chars) / CHARS_PER_TOKEN,
+            has_tools: !request.tools.is_empty(),
+            iteration: 0,
+            hint: None,
+        }
+    }
+
+    /// Set the agent loop iteration.
+    pub fn with_iteration(mut self, iteration: u32) -> Self {
+        self.iteration = iteration;
+        self
+    }
+
+    /// Set an explicit routing hint.
+    pub fn with_hint(mut self, hint: Option<String>) -> Self {
+        self.hint = hint;
+        self
+    }
+}
+
+fn message_chars(message: &Message) -> usize {
+    match message.content.as_text() {
+        Some(text) => text.len(),
+        None => serde_json::to_string(&message.content)
+            .map(|s| s.len())
+            .unwrap_or(0),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rules
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Conditions a request must satisfy for a rule to apply.
+///
+/// Unset fields match anything; all set fields must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteCondition {
+    /// Minimum estimated prompt tokens (inclusive).
+    pub min_prompt_tokens: Option<usize>,
+    /// Maximum estimated prompt tokens (inclusive).
+    pub max_prompt_tokens: Option<usize>,
+    /// Require tools to be present (`true`) or absent (`false`).
+    pub has_tools: Option<bool>,
+    /// Minimum agent loop iteration (inclusive).
+    pub min_iteration: Option<u32>,
+    /// Maximum agent loop iteration (inclusive).
+    pub max_iteration: Option<u32>,
+    /// Require this routing hint.
+    pub hint: Option<String>,
+}
+
+impl RouteCondition {
+    /// Require at least this many estimated prompt tokens.
+    pub fn min_prompt_tokens(mut self, tokens: usize) -> Self {
+        self.min_prompt_tokens = Some(tokens);
+        self
+    }
+
+    /// Require at most this many estimated prompt tokens.
+    pub fn max_prompt_tokens(mut self, tokens: usize) -> Self {
+        self.max_prompt_tokens = Some(tokens);
+        self
+    }
+
+    /// Require tools to be present or absent.
+    pub fn has_tools(mut self, has_tools: bool) -> Self {
+        self.has_tools = Some(has_tools);
+        self
+    }
+
+    /// Require the iteration to be at least `iteration`.
+    pub fn min_iteration(mut self, iteration: u32) -> Self {
+        self.min_iteration = Some(iteration);
+        self
+    }
+
+    /// Require the iteration to be at most `iteration`.
+    pub fn max_iteration(mut self, iteration: u32) -> Self {
+        self.max_iteration = Some(iteration);
+        self
+    }
+
+    /// Require a routing hint.
+    pub fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Check whether a context satisfies every set condition.
+    pub fn matches(&self, ctx: &RoutingContext) -> bool {
+        self.min_prompt_tokens
+            .is_none_or(|min| ctx.estimated_prompt_tokens >= min)
+            && self
+                .max_prompt_tokens
+                .is_none_or(|max| ctx.estimated_prompt_tokens <= max)
+            && self.has_tools.is_none_or(|want| ctx.has_tools == want)
+            && self.min_iteration.is_none_or(|min| ctx.iteration >= min)
+            && self.max_iteration.is_none_or(|max| ctx.iteration <= max)
+            && self
+                .hint
+                .as_ref()
+                .is_none_or(|want| ctx.hint.as_ref() == Some(want))
+    }
+
+    /// Describe the set conditions, for routing reasons.
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min_prompt_tokens {
+            parts.push(format!("prompt >= {} tokens", min));
+        }
+        if let Some(max) = self.max_prompt_tokens {
+            parts.push(format!("prompt <= {} tokens", max));
+        }
+        match self.has_tools {
+            Some(true) => parts.push("tools present".to_string()),
+            Some(false) => parts.push("no tools".to_string()),
+            None => {}
+        }
+        if let Some(min) = self.min_iteration {
+            parts.push(format!("iteration >= {}", min));
+        }
+        if let Some(max) = self.max_iteration {
+            parts.push(format!("iteration <= {}", max));
+        }
+        if let Some(ref hint) = self.hint {
+            parts.push(format!("hint '{}'", hint));
+        }
+        if parts.is_empty() {
+            "always".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// A routing rule: when the condition matches, use the named profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutingRule {
+    /// Rule name, used in routing reasons.
+    pub name: String,
+    /// When the rule applies.
+    pub condition: RouteCondition,
+    /// Profile to route to.
+    pub profile: String,
+}
+
+impl RoutingRule {
+    /// Create a new rule.
+    pub fn new(
+        name: impl Into<String>,
+        condition: RouteCondition,
+        profile: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            condition,
+            profile: profile.into(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Classifier
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A classifier's profile pick for a request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// Profile to route to.
+    pub profile: String,
+    /// Confidence 0.0–1.0.
+    pub confidence: f64,
+    /// Short explanation (e.g. the predicted label).
+    pub reason: String,
+}
+
+/// Optional second-stage classifier consulted when no rule matches.
+#[async_trait]
+pub trait RouteClassifier: Send + Sync {
+    /// Classify a request. Returns `Ok(None)` to abstain.
+    async fn classify(
+        &self,
+        request: &CompletionRequest,
+        ctx: &RoutingContext,
+    ) -> Result<Option<Classification>>;
+}
+
+/// System prompt for [`LlmClassifier`].
+const CLASSIFIER_SYSTEM_PROMPT: &str = "You route requests to language models. \
+Read the user's latest message and choose the single label that best describes the work needed. \
+Reply with JSON only: {\"label\": \"<label>\", \"confidence\": <0.0-1.0>}";
+
+/// Maximum characters of the latest user message sent to the classifier.
+const CLASSIFIER_MAX_INPUT_CHARS: usize = 2000;
+
+/// Classifier that asks a (cheap) model to label the request.
+///
+/// Each label maps to a profile. The classifier sees only the latest user
+/// message, truncated, so its own cost stays small.
+pub struct LlmClassifier {
+    backend: SharedBackend,
+    model: String,
+    /// `(label, description, profile)` triples.
+    labels: Vec<(String, String, String)>,
+}
+
+impl LlmClassifier {
+    /// Create a classifier using the given backend and model.
+    pub fn new(backend: SharedBackend, model: impl Into<String>) -> Self {
+        Self {
+            backend,
+            model: model.into(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Add a label, with a description shown to the model, mapping to a profile.
+    pub fn with_label(
+        mut self,
+        label: impl Into<String>,
+        description: impl Into<String>,
+        profile: impl Into<String>,
+    ) -> Self {
+        self.labels
+            .push((label.into(), description.into(), profile.into()));
+        self
+    }
+
+    /// Profiles this classifier can pick.
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.labels.iter().map(|(_, _, profile)| profile.as_str())
+    }
+
+    fn build_request(&self, request: &CompletionRequest) -> Option<CompletionRequest> {
+        let latest = request
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == Role::User && !m.content.to_text().trim().is_empty())?
+            .content
+            .to_text();
+        let latest: String = latest.chars().take(CLASSIFIER_MAX_INPUT_CHARS).collect();
+
+        let labels = self
+            .labels
+            .iter()
+            .map(|(label, description, _)| format!("- {}: {}", label, description))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Some(
+            CompletionRequest::new(
+                &self.model,
+                vec![Message::user(format!(
+                    "Labels:\n{}\n\nMessage:\n{}",
+                    labels, latest
+                ))],
+                64,
+            )
+            .with_system(CLASSIFIER_SYSTEM_PROMPT)
+            .with_temperature(0.0),
+        )
+    }
+}
+
+/// Classifier reply format.
+#[derive(Debug, Deserialize)]
+struct ClassifierReply {
+    label: String,
+    #[serde(default = "default_confidence")]
+    confidence: f64,
+}
+
+fn default_confidence() -> f64 {
+    1.0
+}
+
+/// Parse a classifier reply, tolerating prose around the JSON object.
+fn parse_classifier_reply(text: &str) -> Option<ClassifierReply> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&text[start..=end]).ok()
+}
+
+#[async_trait]
+impl RouteClassifier for LlmClassifier {
+    async fn classify(
+        &self,
+        request: &CompletionRequest,
+        _ctx: &RoutingContext,
+    ) -> Result<Option<Classification>> {
+        let Some(classifier_request) = self.build_request(request) else {
+            return Ok(None);
+        };
+        let response = self.backend.complete(classifier_request).await?;
+        let text = response.text();
+
+        let Some(reply) = parse_classifier_reply(&text) else {
+            tracing::debug!(reply = %text, "Unparseable routing classifier reply");
+            return Ok(None);
+        };
+
+        Ok(self
+            .labels
+            .iter()
+            .find(|(label, _, _)| label.eq_ignore_ascii_case(reply.label.trim()))
+            .map(|(label, _, profile)| Classification {
+                profile: profile.clone(),
+                confidence: reply.confidence.clamp(0.0, 1.0),
+                reason: format!("classified as '{}'", label),
+            }))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Router
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The outcome of routing a request.
+#[derive(Clone)]
+pub struct RouteDecision {
+    /// Selected profile name.
+    pub profile: String,
+    /// Backend to send the request to.
+    pub backend: SharedBackend,
+    /// Model to request.
+    pub model: String,
+    /// Why this profile was selected.
+    pub reason: String,
+    /// Classifier confidence, if a classifier decided.
+    pub confidence: Option<f64>,
+}
+
+impl RouteDecision {
+    fn new(profile: &RouteProfile, reason: String, confidence: Option<f64>) -> Self {
+        Self {
+            profile: profile.name.clone(),
+            backend: profile.backend.clone(),
+            model: profile.model.clone(),
+            reason,
+            confidence,
+        }
+    }
+
+    /// Rewrite a request to target the selected model.
+    pub fn apply(&self, mut request: CompletionRequest) -> CompletionRequest {
+        request.model = self.model.clone();
+        request
+    }
+
+    /// Routing metadata for the interaction log.
+    pub fn metadata(&self) -> RoutingMetadata {
+        RoutingMetadata {
+            profile: self.profile.clone(),
+            reason: self.reason.clone(),
+            confidence: self.confidence,
+        }
+    }
+}
+
+impl std::fmt::Debug for RouteDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteDecision")
+            .field("profile", &self.profile)
+            .field("model", &self.model)
+            .field("reason", &self.reason)
+            .field("confidence", &self.confidence)
+            .finish()
+    }
+}
+
+/// Selects a profile per request from hints, rules, a classifier and a default.
+pub struct LlmRouter {
+    profiles: HashMap<String, RouteProfile>,
+    default_profile: String,
+    rules: Vec<RoutingRule>,
+    classifier: Option<Arc<dyn RouteClassifier>>,
+    min_confidence: f64,
+}
+
+/// Shared router reference.
+pub type SharedRouter = Arc<LlmRouter>;
+
+impl LlmRouter {
+    /// Default minimum classifier confidence.
+    pub const DEFAULT_MIN_CONFIDENCE: f64 = 0.6;
+
+    /// Create a router whose fallback is `default_profile`.
+    pub fn new(default_profile: RouteProfile) -> Self {
+        let name = default_profile.name.clone();
+        let mut profiles = HashMap::new();
+        profiles.insert(name.clone(), default_profile);
+        Self {
+            profiles,
+            default_profile: name,
+            rules: Vec::new(),
+            classifier: None,
+            min_confidence: Self::DEFAULT_MIN_CONFIDENCE,
+        }
+    }
+
+    /// Add a routable profile.
+    pub fn with_profile(mut self, profile: RouteProfile) -> Self {
+        self.profiles.insert(profile.name.clone(), profile);
+        self
+    }
+
+    /// Append a rule. Rules are evaluated in insertion order.
+    pub fn with_rule(mut self, rule: RoutingRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Set a classifier consulted when no rule matches.
+    pub fn with_classifier(mut self, classifier: Arc<dyn RouteClassifier>) -> Self {
+        self.classifier = Some(classifier);
+        self
+    }
+
+    /// Set the minimum classifier confidence required to follow its pick.
+    pub fn with_min_confidence(mut self, min_confidence: f64) -> Self {
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    /// Name of the default profile.
+    pub fn default_profile(&self) -> &str {
+        &self.default_profile
+    }
+
+    /// Look up a profile by name.
+    pub fn profile(&self, name: &str) -> Option<&RouteProfile> {
+        self.profiles.get(name)
+    }
+
+    /// Names of all routable profiles, sorted.
+    pub fn profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.profiles.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// The configured rules, in evaluation order.
+    pub fn rules(&self) -> &[RoutingRule] {
+        &self.rules
+    }
+
+    /// Check that every rule references a known profile.
+    pub fn validate(&self) -> Result<()> {
+        for rule in &self.rules {
+            if !self.profiles.contains_key(&rule.profile) {
+                return Err(LlmError::Config(format!(
+                    "routing rule '{}' references unknown profile '{}'",
+                    rule.name, rule.profile
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Choose a profile for a request.
+    ///
+    /// Never fails: classifier errors and unknown profiles fall through to
+    /// the next stage and ultimately to the default profile.
+    pub async fn route(&self, request: &CompletionRequest, ctx: &RoutingContext) -> RouteDecision {
+        if let Some(ref hint) = ctx.hint
+            && let Some(profile) = self.profiles.get(hint)
+        {
+            return RouteDecision::new(profile, format!("hint '{}'", hint), None);
+        }
+
+        for rule in &self.rules {
+            if rule.condition.matches(ctx) {
+                if let Some(profile) = self.profiles.get(&rule.profile) {
+                    return RouteDecision::new(
+                        profile,
+                        format!("rule '{}': {}", rule.name, rule.condition.describe()),
+                        None,
+                    );
+                }
+                tracing::warn!(
+                    rule = %rule.name,
+                    profile = %rule.profile,
+                    "Routing rule references unknown profile"
+                );
+            }
+        }
+
+        if let Some(ref classifier) = self.classifier {
+            match classifier.classify(request, ctx).await {
+                Ok(Some(c)) if c.confidence >= self.min_confidence => {
+                    if let Some(profile) = self.profiles.get(&c.profile) {
+                        return RouteDecision::new(profile, c.reason, Some(c.confidence));
+                    }
+                }
+                Ok(Some(c)) => {
+                    tracing::debug!(
+                        profile = %c.profile,
+                        confidence = c.confidence,
+                        "Routing classifier below confidence threshold"
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, "Routing classifier failed");
+                }
+            }
+        }
+
+        let profile = &self.profiles[&self.default_profile];
+        RouteDecision::new(profile, "default profile".to_string(), None)
+    }
+}
+
+impl std::fmt::Debug for LlmRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LlmRouter")
+            .field("profiles", &self.profile_names())
+            .field("default_profile", &self.default_profile)
+            .field("rules", &self.rules)
+            .field("classifier", &self.classifier.is_some())
+            .field("min_confidence", &self.min_confidence)
+            .finish()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::types::ToolDefinition;
+
+    fn profile(name: &str) -> RouteProfile {
+        RouteProfile::new(
+            name,
+            Arc::new(MockBackend::with_text("ok")),
+            format!("{}-model", name),
+        )
+    }
+
+    fn router() -> LlmRouter {
+        LlmRouter::new(profile("quality"))
+            .with_profile(profile("fast"))
+            .with_profile(profile("long"))
+            .with_rule(RoutingRule::new(
+                "long-context",
+                RouteCondition::default().min_prompt_tokens(1000),
+                "long",
+            ))
+            .with_rule(RoutingRule::new(
+                "short-chat",
+                RouteCondition::default()
+                    .max_prompt_tokens(200)
+                    .has_tools(false),
+                "fast",
+            ))
+    }
+
+    fn request(text: &str) -> CompletionRequest {
+        CompletionRequest::new("unused", vec![Message::user(text)], 1024)
+    }
+
+    fn tool() -> ToolDefinition {
+        ToolDefinition::new("read_file", "Read a file", serde_json::json!({}))
+    }
+
+    struct FixedClassifier(Option<Classification>);
+
+    #[async_trait]
+    impl RouteClassifier for FixedClassifier {
+        async fn classify(
+            &self,
+            _request: &CompletionRequest,
+            _ctx: &RoutingContext,
+        ) -> Result<Option<Classification>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_context_from_request() {
+        let req = request(&"x".repeat(400)).with_tools(vec![tool()]);
+        let ctx = RoutingContext::from_request(&req);
+        assert!(ctx.has_tools);
+        assert!(ctx.estimated_prompt_tokens >= 100);
+        assert_eq!(ctx.iteration, 0);
+    }
+
+    #[test]
+    fn test_condition_matching() {
+        let cond = RouteCondition::default()
+            .max_prompt_tokens(100)
+            .has_tools(false)
+            .min_iteration(2);
+        let ctx = RoutingContext {
+            estimated_prompt_tokens: 50,
+            has_tools: false,
+            iteration: 2,
+            hint: None,
+        };
+        assert!(cond.matches(&ctx));
+        assert!(!cond.matches(&RoutingContext {
+            iteration: 1,
+            ..ctx.clone()
+        }));
+        assert!(!cond.matches(&RoutingContext {
+            has_tools: true,
+            ..ctx.clone()
+        }));
+        assert!(RouteCondition::default().matches(&ctx));
+    }
+
+    #[tokio::test]
+    async fn test_short_request_routes_to_fast() {
+        let router = router();
+        let req = request("hi");
+        let decision = router
+            .route(&req, &RoutingContext::from_request(&req))
+            .await;
+        assert_eq!(decision.profile, "fast");
+        assert_eq!(decision.model, "fast-model");
+        assert!(decision.reason.contains("short-chat"));
+        assert_eq!(decision.apply(req).model, "fast-model");
+    }
+
+    #[tokio::test]
+    async fn test_first_matching_rule_wins() {
+        let router = router();
+        let req = request(&"word ".repeat(2000));
+        let decision = router
+            .route(&req, &RoutingContext::from_request(&req))
+            .await;
+        assert_eq!(decision.profile, "long");
+    }
+
+    #[tokio::test]
+    async fn test_no_match_uses_default() {
+        let router = router();
+        let req = request("hi").with_tools(vec![tool()]);
+        let decision = router
+            .route(&req, &RoutingContext::from_request(&req))
+            .await;
+        assert_eq!(decision.profile, "quality");
+        assert_eq!(decision.reason, "default profile");
+        assert!(decision.metadata().confidence.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hint_naming_profile_takes_precedence() {
+        let router = router();
+        let req = request("hi");
+        let ctx = RoutingContext::from_request(&req).with_hint(Some("quality".into()));
+        let decision = router.route(&req, &ctx).await;
+        assert_eq!(decision.profile, "quality");
+        assert_eq!(decision.reason, "hint 'quality'");
+    }
+
+    #[tokio::test]
+    async fn test_hint_condition_and_iteration_rule() {
+        let router = LlmRouter::new(profile("quality"))
+            .with_profile(profile("fast"))
+            .with_rule(RoutingRule::new(
+                "cheap-hint",
+                RouteCondition::default().hint("cheap"),
+                "fast",
+            ))
+            .with_rule(RoutingRule::new(
+                "follow-up",
+                RouteCondition::default().min_iteration(2),
+                "fast",
+            ));
+        let req = request("hi");
+
+        let ctx = RoutingContext::from_request(&req).with_hint(Some("cheap".into()));
+        assert_eq!(router.route(&req, &ctx).await.profile, "fast");
+
+        let ctx = RoutingContext::from_request(&req).with_iteration(1);
+        assert_eq!(router.route(&req, &ctx).await.profile, "quality");
+        let ctx = RoutingContext::from_request(&req).with_iteration(3);
+        assert_eq!(router.route(&req, &ctx).await.profile, "fast");
+    }
+
+    #[tokio::test]
+    async fn test_classifier_confidence_threshold() {
+        let pick = |confidence| {
+            Some(Classification {
+                profile: "fast".into(),
+                confidence,
+                reason: "classified as 'simple'".into(),
+            })
+        };
+        let req = request("hi").with_tools(vec![tool()]);
+        let ctx = RoutingContext::from_request(&req);
+
+        let confident = router().with_classifier(Arc::new(FixedClassifier(pick(0.9))));
+        let decision = confident.route(&req, &ctx).await;
+        assert_eq!(decision.profile, "fast");
+        assert_eq!(decision.confidence, Some(0.9));
+
+        let unsure = router().with_classifier(Arc::new(FixedClassifier(pick(0.3))));
+        assert_eq!(unsure.route(&req, &ctx).await.profile, "quality");
+    }
+
+    #[tokio::test]
+    async fn test_llm_classifier_maps_label_to_profile() {
+        let backend = Arc::new(MockBackend::with_text(
+            "Sure: {\"label\": \"Simple\", \"confidence\": 0.8}",
+        ));
+        let classifier = LlmClassifier::new(backend.clone(), "tiny")
+            .with_label("simple", "small talk or quick lookups", "fast")
+            .with_label("complex", "multi-step reasoning or coding", "quality");
+
+        let req = request("what time is it?");
+        let result = classifier
+            .classify(&req, &RoutingContext::from_request(&req))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.profile, "fast");
+        assert_eq!(result.confidence, 0.8);
+
+        let sent = backend.requests();
+        assert_eq!(sent[0].model, "tiny");
+        assert!(sent[0].messages[0].content.to_text().contains("what time"));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_profile() {
+        let router = router().with_rule(RoutingRule::new(
+            "bad",
+            RouteCondition::default(),
+            "missing",
+        ));
+        assert!(matches!(router.validate(), Err(LlmError::Config(_))));
+        assert!(self::router().validate().is_ok());
+    }
+}
@@ -0,0 +1,525 @@
+//! Structured output with JSON Schema validation and repair.
+//!
+//! A [`ResponseSchema`] on a [`CompletionRequest`] asks for a JSON response.
+//! Backends that report [`LlmBackend::supports_structured_output`] enforce it
+//! natively; for the rest the schema is described in the system prompt. Either
+//! way the response is validated against the schema, and invalid output is fed
+//! back to the model with the validation errors for a bounded number of repair
+//! attempts.
+//!
+//! The validator covers the subset of JSON Schema used for LLM output:
+//! `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+//! `items`, `minItems`/`maxItems`, `minLength`/`maxLength`,
+//! `minimum`/`maximum` and `anyOf`/`oneOf`. Unknown keywords are ignored.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use arawn_llm::{CompletionRequest, Message, ResponseSchema, StructuredCompletion};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Verdict { allow: bool, reason: String }
+//!
+//! let request = CompletionRequest::new(model, vec![Message::user(prompt)], 512)
+//!     .with_response_schema(ResponseSchema::new("verdict", schema));
+//! let verdict: Verdict = backend.complete_json(request).await?;
+//! ```
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::backend::LlmBackend;
+use crate::error::{LlmError, Result};
+use crate::types::{
+    CompletionRequest, CompletionResponse, Message, ResponseSchema, SystemBlock, SystemPrompt,
+};
+
+/// Repair attempts made by [`StructuredCompletion::complete_json`].
+pub const DEFAULT_MAX_REPAIRS: u32 = 2;
+
+/// A validated structured response.
+#[derive(Debug, Clone)]
+pub struct StructuredResponse {
+    /// The JSON value, valid against the requested schema.
+    pub value: Value,
+    /// The final raw response from the backend.
+    pub response: CompletionResponse,
+    /// Number of repair attempts needed (0 if the first response was valid).
+    pub repairs: u32,
+}
+
+/// Structured completion helpers, available on every [`LlmBackend`].
+#[async_trait]
+pub trait StructuredCompletion {
+    /// Complete a request carrying a `response_schema`, validating the output
+    /// and retrying with repair prompts up to `max_repairs` times.
+    async fn complete_structured(
+        &self,
+        request: CompletionRequest,
+        max_repairs: u32,
+    ) -> Result<StructuredResponse>;
+
+    /// Complete a request carrying a `response_schema` and deserialize the
+    /// validated output into `T`.
+    async fn complete_json<T: DeserializeOwned>(&self, request: CompletionRequest) -> Result<T> {
+        let structured = self
+            .complete_structured(request, DEFAULT_MAX_REPAIRS)
+            .await?;
+        serde_json::from_value(structured.value).map_err(|e| LlmError::Serialization(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl<B: LlmBackend + ?Sized> StructuredCompletion for B {
+    async fn complete_structured(
+        &self,
+        request: CompletionRequest,
+        max_repairs: u32,
+    ) -> Result<StructuredResponse> {
+        let schema = request.response_schema.clone().ok_or_else(|| {
+            LlmError::InvalidRequest("structured completion requires a response_schema".into())
+        })?;
+
+        let mut request = request;
+        if !self.supports_structured_output() {
+            request.response_schema = None;
+            append_system(&mut request, &schema_instructions(&schema));
+        }
+
+        let mut repairs = 0;
+        loop {
+            let response = self.complete(request.clone()).await?;
+            let text = response.text();
+
+            let errors = match extract_json(&text) {
+                Some(value) => match validate(&schema.schema, &value) {
+                    Ok(()) => {
+                        return Ok(StructuredResponse {
+                            value,
+                            response,
+                            repairs,
+                        });
+                    }
+                    Err(errors) => errors,
+                },
+                None => vec!["response is not valid JSON".to_string()],
+            };
+
+            if repairs >= max_repairs {
+                return Err(LlmError::StructuredOutput(format!(
+                    "'{}' output invalid after {} repair attempts: {}",
+                    schema.name,
+                    repairs,
+                    errors.join("; ")
+                )));
+            }
+            repairs += 1;
+            tracing::debug!(
+                schema = %schema.name,
+                attempt = repairs,
+                errors = ?errors,
+                "Structured output invalid, requesting repair"
+            );
+
+            request.messages.push(Message::assistant(text));
+            request.messages.push(Message::user(repair_prompt(&errors)));
+        }
+    }
+}
+
+/// Instructions describing the schema, for backends without native support.
+pub fn schema_instructions(schema: &ResponseSchema) -> String {
+    let mut out = String::from(
+        "Respond with ONLY a JSON document that conforms to the following JSON Schema. \
+         No markdown, no explanation.\n\n",
+    );
+    if let Some(ref description) = schema.description {
+        out.push_str(&format!("The document is: {}\n\n", description));
+    }
+    out.push_str(&serde_json::to_string_pretty(&schema.schema).unwrap_or_default());
+    out
+}
+
+fn repair_prompt(errors: &[String]) -> String {
+    let mut out =
+        String::from("Your previous response did not conform to the required JSON Schema:\n");
+    for error in errors {
+        out.push_str(&format!("- {}\n", error));
+    }
+    out.push_str("\nRespond again with ONLY the corrected JSON document.");
+    out
+}
+
+fn append_system(request: &mut CompletionRequest, text: &str) {
+    request.system = Some(match request.system.take() {
+        None => SystemPrompt::Text(text.to_string()),
+        Some(SystemPrompt::Text(existing)) => {
+            SystemPrompt::Text(format!("{}\n\n{}", existing, text))
+        }
+        Some(SystemPrompt::Blocks(mut blocks)) => {
+            blocks.push(SystemBlock {
+                text: text.to_string(),
+                block_type: "text".to_string(),
+                cache_control: None,
+            });
+            SystemPrompt::Blocks(blocks)
+        }
+    });
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON extraction
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Extract a JSON document from model output.
+///
+/// Accepts bare JSON, JSON wrapped in markdown code fences, or the outermost
+/// object/array embedded in surrounding prose.
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(str::trim)
+        .unwrap_or(trimmed);
+
+    if let Ok(value) = serde_json::from_str(unfenced) {
+        return Some(value);
+    }
+
+    [('{', '}'), ('[', ']')].iter().find_map(|(open, close)| {
+        let start = unfenced.find(*open)?;
+        let end = unfenced.rfind(*close)?;
+        (end > start)
+            .then(|| serde_json::from_str(&unfenced[start..=end]).ok())
+            .flatten()
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Validation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Validate a value against a JSON Schema, returning every violation found.
+pub fn validate(schema: &Value, value: &Value) -> std::result::Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "$", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        // `true` / `{}`-like schemas accept anything; `false` accepts nothing.
+        if schema == &Value::Bool(false) {
+            errors.push(format!("{}: no value is allowed here", path));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !types.is_empty() && !types.iter().any(|t| matches_type(t, value)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        errors.push(format!(
+            "{}: must be one of {}",
+            path,
+            Value::Array(allowed.clone())
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(format!("{}: must equal {}", path, expected));
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(options)) = schema.get(keyword) {
+            let matching = options
+                .iter()
+                .filter(|option| {
+                    let mut scratch = Vec::new();
+                    validate_at(option, value, path, &mut scratch);
+                    scratch.is_empty()
+                })
+                .count();
+            let ok = if keyword == "oneOf" {
+                matching == 1
+            } else {
+                matching > 0
+            };
+            if !ok {
+                errors.push(format!("{}: does not match {}", path, keyword));
+            }
+        }
+    }
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(name) {
+                        errors.push(format!("{}: missing required property '{}'", path, name));
+                    }
+                }
+            }
+
+            for (key, child) in map {
+                let child_path = format!("{}.{}", path, key);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => validate_at(child_schema, child, &child_path, errors),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: unexpected property '{}'", path, key));
+                        }
+                        Some(extra @ Value::Object(_)) => {
+                            validate_at(extra, child, &child_path, errors)
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+                && (items.len() as u64) < min
+            {
+                errors.push(format!("{}: expected at least {} items", path, min));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                && (items.len() as u64) > max
+            {
+                errors.push(format!("{}: expected at most {} items", path, max));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_at(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+                && len < min
+            {
+                errors.push(format!("{}: expected at least {} characters", path, min));
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+                && len > max
+            {
+                errors.push(format!("{}: expected at most {} characters", path, max));
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                && n < min
+            {
+                errors.push(format!("{}: must be >= {}", path, min));
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                && n > max
+            {
+                errors.push(format!("{}: must be <= {}", path, max));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MockBackend;
+    use crate::types::{ContentBlock, StopReason, Usage};
+    use serde_json::json;
+
+    fn text_response(text: &str) -> CompletionResponse {
+        CompletionResponse::new(
+            "msg",
+            "mock-model",
+            vec![ContentBlock::Text {
+                text: text.to_string(),
+                cache_control: None,
+            }],
+            StopReason::EndTurn,
+            Usage::new(10, 20),
+        )
+    }
+
+    fn verdict_schema() -> ResponseSchema {
+        ResponseSchema::new(
+            "verdict",
+            json!({
+                "type": "object",
+                "properties": {
+                    "allow": { "type": "boolean" },
+                    "reason": { "type": "string", "minLength": 1 },
+                    "severity": { "enum": ["low", "high"] }
+                },
+                "required": ["allow", "reason"],
+                "additionalProperties": false
+            }),
+        )
+    }
+
+    #[test]
+    fn test_validate_reports_all_violations() {
+        let schema = verdict_schema().schema;
+        assert!(validate(&schema, &json!({"allow": true, "reason": "ok"})).is_ok());
+
+        let errors = validate(
+            &schema,
+            &json!({"allow": "yes", "severity": "medium", "extra": 1}),
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("missing required property 'reason'"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("$.allow: expected boolean"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("$.severity: must be one of"))
+        );
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.contains("unexpected property 'extra'"))
+        );
+    }
+
+    #[test]
+    fn test_validate_arrays_and_numbers() {
+        let schema = json!({
+            "type": "array",
+            "minItems": 1,
+            "items": { "type": "integer", "minimum": 0 }
+        });
+        assert!(validate(&schema, &json!([0, 5])).is_ok());
+        let errors = validate(&schema, &json!([1, -2, 1.5])).unwrap_err();
+        assert_eq!(
+            errors,
+            vec!["$[1]: must be >= 0", "$[2]: expected integer, got number"]
+        );
+        assert!(validate(&schema, &json!([])).is_err());
+    }
+
+    #[test]
+    fn test_extract_json() {
+        assert_eq!(extract_json("{\"a\": 1}"), Some(json!({"a": 1})));
+        assert_eq!(
+            extract_json("```json\n{\"a\": 1}\n```"),
+            Some(json!({"a": 1}))
+        );
+        assert_eq!(
+            extract_json("Here you go: {\"a\": [1]} hope that helps"),
+            Some(json!({"a": [1]}))
+        );
+        assert_eq!(extract_json("[1, 2]"), Some(json!([1, 2])));
+        assert_eq!(extract_json("no json here"), None);
+    }
+
+    #[tokio::test]
+    async fn test_complete_json_repairs_invalid_output() {
+        #[derive(serde::Deserialize)]
+        struct Verdict {
+            allow: bool,
+            reason: String,
+        }
+
+        let backend = MockBackend::new(vec![
+            text_response("not json"),
+            text_response("{\"allow\": true}"),
+            text_response("```json\n{\"allow\": false, \"reason\": \"unsafe\"}\n```"),
+        ]);
+        let request = CompletionRequest::new("m", vec![Message::user("check")], 256)
+            .with_response_schema(verdict_schema());
+
+        let verdict: Verdict = backend.complete_json(request).await.unwrap();
+        assert!(!verdict.allow);
+        assert_eq!(verdict.reason, "unsafe");
+
+        // Mock has no native support: schema moves into the system prompt,
+        // and each repair sees the previous output plus the errors.
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].response_schema.is_none());
+        let system = requests[0].system.as_ref().unwrap().to_text();
+        assert!(system.contains("\"additionalProperties\": false"));
+        let repair = requests[2].messages.last().unwrap().content.to_text();
+        assert!(repair.contains("missing required property 'reason'"));
+        assert_eq!(requests[2].messages.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_complete_structured_gives_up_after_max_repairs() {
+        let backend = MockBackend::new(vec![text_response("{}"), text_response("{}")]);
+        let request = CompletionRequest::new("m", vec![Message::user("check")], 256)
+            .with_response_schema(verdict_schema());
+
+        let err = backend.complete_structured(request, 1).await.unwrap_err();
+        assert!(matches!(err, LlmError::StructuredOutput(_)));
+        assert!(err.to_string().contains("after 1 repair attempts"));
+
+        let missing = backend
+            .complete_structured(CompletionRequest::new("m", vec![], 16), 0)
+            .await
+            .unwrap_err();
+        assert!(matches!(missing, LlmError::InvalidRequest(_)));
+    }
+}
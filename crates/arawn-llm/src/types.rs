@@ -117,6 +117,10 @@ pub struct CompletionRequest {
     /// Additional metadata.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+
+    /// JSON Schema the response must conform to (structured output).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
 }
 
 impl CompletionRequest {
@@ -135,6 +139,7 @@ impl CompletionRequest {
             top_k: None,
             stop_sequences: Vec::new(),
             metadata: HashMap::new(),
+            response_schema: None,
         }
     }
 
@@ -167,6 +172,65 @@ impl CompletionRequest {
         self.temperature = Some(temperature);
         self
     }
+
+    /// Require the response to be JSON conforming to the given schema.
+    pub fn with_response_schema(mut self, schema: ResponseSchema) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+}
+
+/// JSON Schema for structured output.
+///
+/// Backends with native support map this to their own mechanism (OpenAI
+/// `response_format: json_schema`, Anthropic forced tool use); the response
+/// text is then the JSON document. See [`crate::structured`] for the
+/// validating fallback used with other backends.
+///
+/// # Examples
+///
+/// ```rust
+/// use arawn_llm::ResponseSchema;
+/// use serde_json::json;
+///
+/// let schema = ResponseSchema::new(
+///     "verdict",
+///     json!({
+///         "type": "object",
+///         "properties": { "allow": { "type": "boolean" } },
+///         "required": ["allow"]
+///     }),
+/// )
+/// .with_description("Whether the action is allowed");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Schema name (letters, digits, `_` and `-`).
+    pub name: String,
+
+    /// What the output represents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The JSON Schema.
+    pub schema: serde_json::Value,
+}
+
+impl ResponseSchema {
+    /// Create a response schema.
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            schema,
+        }
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
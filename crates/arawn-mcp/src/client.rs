@@ -24,7 +24,7 @@ pub enum TransportType {
 }
 
 /// Configuration for an MCP server connection.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct McpServerConfig {
     /// Unique name for this server.
     pub name: String,
@@ -117,6 +117,11 @@ impl McpManager {
         self.clients.contains_key(name)
     }
 
+    /// Get a server's configuration by name.
+    pub fn server_config(&self, name: &str) -> Option<&McpServerConfig> {
+        self.configs.get(name)
+    }
+
     /// Get a connected client by name.
     pub fn get_client(&self, name: &str) -> Option<Arc<McpClient>> {
         self.clients.get(name).cloned()
@@ -163,7 +168,7 @@ impl McpManager {
 
     /// Connect to a single server.
     fn connect_server(&self, config: McpServerConfig) -> Result<McpClient> {
-        let mut client = McpClient::connect_stdio(config)?;
+        let mut client = McpClient::connect(config)?;
         client.initialize()?;
         Ok(client)
     }
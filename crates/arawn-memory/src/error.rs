@@ -29,6 +29,10 @@ pub enum MemoryError {
     #[error("Invalid UUID: {0}")]
     InvalidUuid(#[from] uuid::Error),
 
+    /// A query ran longer than allowed and was interrupted.
+    #[error("Query timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
     /// Invalid data or state.
     #[error("Invalid data: {0}")]
     InvalidData(String),
@@ -4,10 +4,17 @@
 //! SQLite extension, enabling entity storage, relationship management,
 //! and Cypher query support.
 
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write as _;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 
-use graphqlite::Graph;
+use graphqlite::{Graph, Value, escape_string};
 use serde::{Deserialize, Serialize};
+use serde_json::Map;
 use tracing::{debug, info};
 
 use crate::error::{MemoryError, Result};
@@ -16,6 +23,14 @@ use crate::error::{MemoryError, Result};
 // Types
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Label of the nodes that link memories to the entities they mention.
+pub const MEMORY_LABEL: &str = "Memory";
+
+/// Clauses rejected by [`GraphStore::query_read_only`].
+const WRITE_CLAUSES: &[&str] = &[
+    "CREATE", "MERGE", "SET", "DELETE", "DETACH", "REMOVE", "DROP", "LOAD", "CALL", "FOREACH",
+];
+
 /// A node/entity in the knowledge graph.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphNode {
@@ -45,7 +60,7 @@ impl GraphNode {
 }
 
 /// Relationship types supported in the knowledge graph.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RelationshipType {
     /// One entity supports another (evidence, citation).
@@ -80,6 +95,21 @@ impl RelationshipType {
             Self::IsA => "IS_A",
         }
     }
+
+    /// Parse the Cypher representation produced by [`as_str`](Self::as_str).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "SUPPORTS" => Some(Self::Supports),
+            "CONTRADICTS" => Some(Self::Contradicts),
+            "RELATED_TO" => Some(Self::RelatedTo),
+            "CITED_IN" => Some(Self::CitedIn),
+            "MENTIONS" => Some(Self::Mentions),
+            "PART_OF" => Some(Self::PartOf),
+            "CREATED_BY" => Some(Self::CreatedBy),
+            "IS_A" => Some(Self::IsA),
+            _ => None,
+        }
+    }
 }
 
 /// A relationship/edge in the knowledge graph.
@@ -124,6 +154,133 @@ pub struct QueryResult {
     pub row_count: usize,
 }
 
+/// Rows returned by a read-only Cypher query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CypherRows {
+    /// Column names, in the order graphqlite reports them.
+    pub columns: Vec<String>,
+    /// One object per row, keyed by column name.
+    pub rows: Vec<Map<String, serde_json::Value>>,
+}
+
+/// A set of nodes and the relationships between them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Subgraph {
+    /// Nodes in the subgraph.
+    pub nodes: Vec<GraphNode>,
+    /// Relationships whose endpoints are both in `nodes`.
+    pub relationships: Vec<GraphRelationship>,
+    /// Whether nodes were left out to stay within the requested size.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl Subgraph {
+    /// Render as GraphML, with labels, relationship types and properties as
+    /// string-valued data keys.
+    pub fn to_graphml(&self) -> String {
+        let node_keys: BTreeSet<&str> = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.properties.iter().map(|(k, _)| k.as_str()))
+            .collect();
+        let edge_keys: BTreeSet<&str> = self
+            .relationships
+            .iter()
+            .flat_map(|r| r.properties.iter().map(|(k, _)| k.as_str()))
+            .collect();
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        out.push_str(
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        );
+        out.push_str("  <key id=\"type\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>\n");
+        for key in &node_keys {
+            let _ = writeln!(
+                out,
+                "  <key id=\"n_{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"string\"/>",
+                xml_escape(key)
+            );
+        }
+        for key in &edge_keys {
+            let _ = writeln!(
+                out,
+                "  <key id=\"e_{0}\" for=\"edge\" attr.name=\"{0}\" attr.type=\"string\"/>",
+                xml_escape(key)
+            );
+        }
+        out.push_str("  <graph id=\"arawn\" edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id));
+            let _ = writeln!(
+                out,
+                "      <data key=\"label\">{}</data>",
+                xml_escape(&node.label)
+            );
+            for (key, value) in &node.properties {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"n_{}\">{}</data>",
+                    xml_escape(key),
+                    xml_escape(value)
+                );
+            }
+            out.push_str("    </node>\n");
+        }
+        for rel in &self.relationships {
+            let _ = writeln!(
+                out,
+                "    <edge source=\"{}\" target=\"{}\">",
+                xml_escape(&rel.from_id),
+                xml_escape(&rel.to_id)
+            );
+            let _ = writeln!(
+                out,
+                "      <data key=\"type\">{}</data>",
+                rel.rel_type.as_str()
+            );
+            for (key, value) in &rel.properties {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"e_{}\">{}</data>",
+                    xml_escape(key),
+                    xml_escape(value)
+                );
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Render as a Graphviz `digraph`, labelling nodes with their ID and
+    /// label and edges with their relationship type.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph arawn {\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\\n({})\"];",
+                dot_escape(&node.id),
+                dot_escape(&node.id),
+                dot_escape(&node.label)
+            );
+        }
+        for rel in &self.relationships {
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                dot_escape(&rel.from_id),
+                dot_escape(&rel.to_id),
+                rel.rel_type.as_str()
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Graph Store
 // ─────────────────────────────────────────────────────────────────────────────
@@ -262,6 +419,241 @@ impl GraphStore {
             relationship_count: stats.edges as usize,
         })
     }
+
+    /// Get an entity by ID.
+    pub fn get_entity(&self, id: &str) -> Result<Option<GraphNode>> {
+        let node = self
+            .graph
+            .get_node(id)
+            .map_err(|e| MemoryError::Query(e.to_string()))?;
+        Ok(node.as_ref().and_then(parse_node))
+    }
+
+    /// List entities ordered by ID, with the total number of matches.
+    ///
+    /// `search` is a case-insensitive substring of the ID, `name` or
+    /// `context` property. Memory nodes are only listed when asked for by
+    /// label.
+    pub fn list_entities(
+        &self,
+        label: Option<&str>,
+        search: Option<&str>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<(Vec<GraphNode>, usize)> {
+        let pattern = match label {
+            Some(label) if is_identifier(label) => format!("(n:{label})"),
+            Some(label) => {
+                return Err(MemoryError::Query(format!("Invalid label: {label}")));
+            }
+            None => "(n)".to_string(),
+        };
+        let mut conditions = Vec::new();
+        if label.is_none() {
+            conditions.push(format!("NOT n:{MEMORY_LABEL}"));
+        }
+        if let Some(search) = search.filter(|s| !s.is_empty()) {
+            let search = escape_string(&search.to_lowercase());
+            conditions.push(format!(
+                "(toLower(n.id) CONTAINS '{search}' OR toLower(n.name) CONTAINS '{search}' \
+                 OR toLower(n.context) CONTAINS '{search}')"
+            ));
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+
+        let total = self
+            .graph
+            .query(&format!("MATCH {pattern}{filter} RETURN count(n) AS total"))
+            .map_err(|e| MemoryError::Query(e.to_string()))?
+            .iter()
+            .next()
+            .and_then(|row| row.get_value("total").and_then(Value::as_i64))
+            .unwrap_or(0) as usize;
+
+        let page = self
+            .graph
+            .query(&format!(
+                "MATCH {pattern}{filter} RETURN n ORDER BY n.id SKIP {offset} LIMIT {limit}"
+            ))
+            .map_err(|e| MemoryError::Query(e.to_string()))?;
+        let nodes = page
+            .iter()
+            .filter_map(|row| row.get_value("n").and_then(parse_node))
+            .collect();
+        Ok((nodes, total))
+    }
+
+    /// Get the relationships of an entity, in both directions.
+    pub fn get_relationships(&self, id: &str) -> Result<Vec<GraphRelationship>> {
+        let id = escape_string(id);
+        let result = self
+            .graph
+            .query(&format!(
+                "MATCH (a)-[r]->(b) WHERE a.id = '{id}' OR b.id = '{id}' \
+                 RETURN a.id AS source, b.id AS target, r"
+            ))
+            .map_err(|e| MemoryError::Query(e.to_string()))?;
+        Ok(result.iter().filter_map(parse_relationship).collect())
+    }
+
+    /// IDs of the memories linked to an entity.
+    pub fn linked_memory_ids(&self, id: &str) -> Result<Vec<String>> {
+        let result = self
+            .graph
+            .query(&format!(
+                "MATCH (m:{MEMORY_LABEL})-[r]-(n {{id: '{}'}}) RETURN DISTINCT m.id AS id",
+                escape_string(id)
+            ))
+            .map_err(|e| MemoryError::Query(e.to_string()))?;
+        Ok(result
+            .iter()
+            .filter_map(|row| row.get_value("id").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// The entities within `hops` relationships of an entity, in either
+    /// direction, and the relationships followed to reach them.
+    ///
+    /// Stops adding nodes at `max_nodes` and marks the result truncated.
+    /// Returns `None` if the entity does not exist.
+    pub fn neighborhood(
+        &self,
+        id: &str,
+        hops: usize,
+        max_nodes: usize,
+    ) -> Result<Option<Subgraph>> {
+        let Some(root) = self.get_entity(id)? else {
+            return Ok(None);
+        };
+
+        let mut subgraph = Subgraph {
+            nodes: vec![root],
+            ..Default::default()
+        };
+        let mut seen = HashSet::from([id.to_string()]);
+        let mut followed = HashSet::new();
+        let mut frontier = vec![id.to_string()];
+
+        for _ in 0..hops {
+            let mut next = Vec::new();
+            for node_id in &frontier {
+                for rel in self.get_relationships(node_id)? {
+                    let other = if rel.from_id == *node_id {
+                        &rel.to_id
+                    } else {
+                        &rel.from_id
+                    };
+                    if !seen.contains(other) {
+                        if seen.len() >= max_nodes {
+                            subgraph.truncated = true;
+                            continue;
+                        }
+                        let Some(node) = self.get_entity(other)? else {
+                            continue;
+                        };
+                        seen.insert(other.clone());
+                        next.push(other.clone());
+                        subgraph.nodes.push(node);
+                    }
+                    let key = (rel.from_id.clone(), rel.to_id.clone(), rel.rel_type);
+                    if followed.insert(key) {
+                        subgraph.relationships.push(rel);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(Some(subgraph))
+    }
+
+    /// Every node and relationship in the graph.
+    pub fn export(&self) -> Result<Subgraph> {
+        let nodes = self
+            .graph
+            .get_all_nodes(None)
+            .map_err(|e| MemoryError::Query(e.to_string()))?
+            .iter()
+            .filter_map(parse_node)
+            .collect();
+        let relationships = self
+            .graph
+            .get_all_edges()
+            .map_err(|e| MemoryError::Query(e.to_string()))?
+            .iter()
+            .filter_map(parse_relationship)
+            .collect();
+        Ok(Subgraph {
+            nodes,
+            relationships,
+            truncated: false,
+        })
+    }
+
+    /// Run a Cypher query that may not modify the graph.
+    ///
+    /// Queries containing write clauses (`CREATE`, `MERGE`, `SET`, `DELETE`,
+    /// ...) are rejected before they run. Queries still running after
+    /// `timeout` are interrupted and fail with [`MemoryError::Timeout`].
+    pub fn query_read_only(&self, cypher: &str, timeout: Duration) -> Result<CypherRows> {
+        if let Some(clause) = write_clause(cypher) {
+            return Err(MemoryError::Query(format!(
+                "{clause} is not allowed in read-only queries"
+            )));
+        }
+
+        let interrupt = self
+            .graph
+            .connection()
+            .sqlite_connection()
+            .get_interrupt_handle();
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let (done, finished) = mpsc::channel::<()>();
+        let watchdog = {
+            let timed_out = timed_out.clone();
+            std::thread::spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(timeout) {
+                    timed_out.store(true, Ordering::SeqCst);
+                    interrupt.interrupt();
+                }
+            })
+        };
+        let result = self.graph.query(cypher);
+        let _ = done.send(());
+        let _ = watchdog.join();
+
+        // An interrupted query comes back as an empty result, not an error
+        if timed_out.load(Ordering::SeqCst) {
+            return Err(MemoryError::Timeout(timeout));
+        }
+        let result = result.map_err(|e| MemoryError::Query(e.to_string()))?;
+
+        let columns = result.columns().to_vec();
+        let rows = result
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        let value = row
+                            .get_value(column)
+                            .and_then(|v| serde_json::to_value(v).ok())
+                            .unwrap_or(serde_json::Value::Null);
+                        (column.clone(), value)
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(CypherRows { columns, rows })
+    }
 }
 
 /// Statistics about the graph store.
@@ -273,6 +665,128 @@ pub struct GraphStats {
     pub relationship_count: usize,
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parse a graphqlite node: `{ "labels": [..], "properties": { "id": .., .. } }`.
+fn parse_node(value: &Value) -> Option<GraphNode> {
+    let Value::Object(obj) = value else {
+        return None;
+    };
+    let Some(Value::Object(props)) = obj.get("properties") else {
+        return None;
+    };
+    let id = props.get("id")?.as_str()?.to_string();
+    let label = match obj.get("labels") {
+        Some(Value::Array(labels)) => labels.first().and_then(Value::as_str).unwrap_or_default(),
+        _ => "",
+    };
+    Some(GraphNode {
+        id,
+        label: label.to_string(),
+        properties: parse_properties(props),
+    })
+}
+
+/// Parse a row with `source`, `target` and relationship `r` columns.
+fn parse_relationship(row: &graphqlite::Row) -> Option<GraphRelationship> {
+    let from_id = row.get_value("source")?.as_str()?.to_string();
+    let to_id = row.get_value("target")?.as_str()?.to_string();
+    let Value::Object(rel) = row.get_value("r")? else {
+        return None;
+    };
+    let rel_type = rel.get("type").and_then(Value::as_str)?;
+    let Some(rel_type) = RelationshipType::parse(rel_type) else {
+        debug!("Skipping relationship with unknown type {}", rel_type);
+        return None;
+    };
+    let properties = match rel.get("properties") {
+        Some(Value::Object(props)) => parse_properties(props),
+        _ => Vec::new(),
+    };
+    Some(GraphRelationship {
+        from_id,
+        to_id,
+        rel_type,
+        properties,
+    })
+}
+
+/// Properties other than `id`, sorted by key. graphqlite infers types for
+/// stored strings, so non-string values are converted back to their JSON text.
+fn parse_properties(props: &std::collections::HashMap<String, Value>) -> Vec<(String, String)> {
+    let mut properties: Vec<(String, String)> = props
+        .iter()
+        .filter(|(key, _)| key.as_str() != "id")
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                other => serde_json::to_string(other).unwrap_or_default(),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    properties.sort();
+    properties
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The first write clause in a query, ignoring string literals, quoted
+/// identifiers and property names.
+fn write_clause(cypher: &str) -> Option<String> {
+    let mut chars = cypher.chars().peekable();
+    let mut previous = ' ';
+    while let Some(c) = chars.next() {
+        if matches!(c, '\'' | '"' | '`') {
+            while let Some(inner) = chars.next() {
+                if inner == '\\' {
+                    chars.next();
+                } else if inner == c {
+                    break;
+                }
+            }
+        } else if c.is_ascii_alphabetic() && !previous.is_ascii_alphanumeric() && previous != '_' {
+            let mut word = String::from(c);
+            while let Some(&next) = chars.peek() {
+                if !next.is_ascii_alphanumeric() && next != '_' {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+            let word = word.to_ascii_uppercase();
+            if previous != '.' && WRITE_CLAUSES.contains(&word.as_str()) {
+                return Some(word);
+            }
+            previous = 'a';
+            continue;
+        }
+        previous = c;
+    }
+    None
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -488,6 +1002,235 @@ mod tests {
         assert_eq!(stats.relationship_count, 4);
     }
 
+    /// rust -CREATED_BY-> mozilla, ownership -SUPPORTS-> rust,
+    /// borrowing -PART_OF-> ownership, and a memory mentioning rust.
+    fn create_sample_graph() -> GraphStore {
+        let graph = create_test_graph();
+        for (id, label, name) in [
+            ("rust", "Language", "Rust"),
+            ("mozilla", "Organization", "Mozilla"),
+            ("ownership", "Concept", "Ownership"),
+            ("borrowing", "Concept", "Borrowing"),
+        ] {
+            graph
+                .add_entity(&GraphNode::new(id, label).with_property("name", name))
+                .unwrap();
+        }
+        graph
+            .add_entity(&GraphNode::new("m1", MEMORY_LABEL))
+            .unwrap();
+        for (from, to, rel_type) in [
+            ("rust", "mozilla", RelationshipType::CreatedBy),
+            ("ownership", "rust", RelationshipType::Supports),
+            ("borrowing", "ownership", RelationshipType::PartOf),
+            ("m1", "rust", RelationshipType::Mentions),
+        ] {
+            graph
+                .add_relationship(
+                    &GraphRelationship::new(from, to, rel_type).with_property("label", "x"),
+                )
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_entity_and_relationships() {
+        let graph = create_sample_graph();
+
+        let rust = graph.get_entity("rust").unwrap().unwrap();
+        assert_eq!(rust.label, "Language");
+        assert_eq!(rust.properties, vec![("name".into(), "Rust".into())]);
+        assert!(graph.get_entity("python").unwrap().is_none());
+
+        let mut rels: Vec<_> = graph
+            .get_relationships("rust")
+            .unwrap()
+            .into_iter()
+            .map(|r| (r.from_id, r.to_id, r.rel_type.as_str()))
+            .collect();
+        rels.sort();
+        assert_eq!(
+            rels,
+            vec![
+                ("m1".into(), "rust".into(), "MENTIONS"),
+                ("ownership".into(), "rust".into(), "SUPPORTS"),
+                ("rust".into(), "mozilla".into(), "CREATED_BY"),
+            ]
+        );
+        assert_eq!(graph.linked_memory_ids("rust").unwrap(), vec!["m1"]);
+        assert!(graph.linked_memory_ids("mozilla").unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_list_entities() {
+        let graph = create_sample_graph();
+
+        let (page, total) = graph.list_entities(None, None, 2, 1).unwrap();
+        assert_eq!(total, 4);
+        let ids: Vec<_> = page.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["mozilla", "ownership"]);
+
+        let (concepts, total) = graph.list_entities(Some("Concept"), None, 10, 0).unwrap();
+        assert_eq!(total, 2);
+        assert!(concepts.iter().all(|n| n.label == "Concept"));
+
+        let (found, _) = graph.list_entities(None, Some("MOZ"), 10, 0).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "mozilla");
+
+        let (memories, _) = graph
+            .list_entities(Some(MEMORY_LABEL), None, 10, 0)
+            .unwrap();
+        assert_eq!(memories.len(), 1);
+
+        assert!(graph.list_entities(Some("X) DETACH"), None, 10, 0).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_neighborhood() {
+        let graph = create_sample_graph();
+
+        let one = graph.neighborhood("ownership", 1, 100).unwrap().unwrap();
+        let mut ids: Vec<_> = one.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["borrowing", "ownership", "rust"]);
+        assert_eq!(one.relationships.len(), 2);
+        assert!(!one.truncated);
+
+        let two = graph.neighborhood("ownership", 2, 100).unwrap().unwrap();
+        assert_eq!(two.nodes.len(), 5);
+        assert_eq!(two.relationships.len(), 4);
+
+        let capped = graph.neighborhood("ownership", 2, 2).unwrap().unwrap();
+        assert_eq!(capped.nodes.len(), 2);
+        assert!(capped.truncated);
+
+        assert!(graph.neighborhood("python", 1, 100).unwrap().is_none());
+    }
+
+    #[test]
+    #[serial]
+    fn test_query_read_only() {
+        let graph = create_sample_graph();
+
+        let rows = graph
+            .query_read_only(
+                "MATCH (n:Concept) RETURN n.id AS id ORDER BY id",
+                Duration::from_secs(5),
+            )
+            .unwrap();
+        assert_eq!(rows.columns, vec!["id"]);
+        assert_eq!(rows.rows.len(), 2);
+        assert_eq!(rows.rows[0]["id"], "borrowing");
+
+        // String literals may contain write keywords
+        let rows = graph
+            .query_read_only(
+                "MATCH (n) WHERE n.name = 'CREATE' RETURN n.name AS name",
+                Duration::from_secs(5),
+            )
+            .unwrap();
+        assert!(rows.rows.is_empty());
+
+        for cypher in [
+            "CREATE (n:Concept {id: 'x'})",
+            "MATCH (n) DETACH DELETE n",
+            "match (n) set n.name = 'x'",
+        ] {
+            let err = graph
+                .query_read_only(cypher, Duration::from_secs(5))
+                .unwrap_err();
+            assert!(matches!(err, MemoryError::Query(_)), "{cypher}: {err}");
+        }
+        assert_eq!(graph.stats().unwrap().node_count, 5);
+    }
+
+    #[test]
+    #[serial]
+    fn test_query_read_only_timeout() {
+        let graph = create_test_graph();
+        for i in 0..300 {
+            graph
+                .add_entity(&GraphNode::new(format!("n{i}"), "Node"))
+                .unwrap();
+        }
+
+        let err = graph
+            .query_read_only(
+                "MATCH (a), (b), (c) RETURN count(*) AS total",
+                Duration::from_millis(100),
+            )
+            .unwrap_err();
+        assert!(matches!(err, MemoryError::Timeout(_)));
+
+        // The connection is usable afterwards
+        let rows = graph
+            .query_read_only("RETURN 1 AS one", Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(rows.rows[0]["one"], 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_formats() {
+        let graph = create_sample_graph();
+        let export = graph.export().unwrap();
+        assert_eq!(export.nodes.len(), 5);
+        assert_eq!(export.relationships.len(), 4);
+
+        let graphml = export.to_graphml();
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains(r#"<key id="n_name" for="node""#));
+        assert!(graphml.contains(r#"<node id="rust">"#));
+        assert!(graphml.contains(r#"<edge source="rust" target="mozilla">"#));
+        assert!(graphml.contains(r#"<data key="type">CREATED_BY</data>"#));
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph arawn {"));
+        assert!(dot.contains(r#""rust" -> "mozilla" [label="CREATED_BY"];"#));
+
+        let quoted = Subgraph {
+            nodes: vec![GraphNode::new("a\"b", "<T>").with_property("k", "x & y")],
+            ..Default::default()
+        };
+        assert!(quoted.to_dot().contains(r#""a\"b""#));
+        let graphml = quoted.to_graphml();
+        assert!(graphml.contains("a&quot;b"));
+        assert!(graphml.contains("&lt;T&gt;"));
+        assert!(graphml.contains("x &amp; y"));
+    }
+
+    #[test]
+    fn test_write_clause_detection() {
+        assert_eq!(write_clause("MATCH (n) RETURN n"), None);
+        assert_eq!(
+            write_clause("MATCH (n) WHERE n.name = 'merge it' RETURN n"),
+            None
+        );
+        assert_eq!(write_clause("MATCH (n) RETURN n.`delete`"), None);
+        assert_eq!(write_clause("MATCH (n) RETURN n.created_at, n.set"), None);
+        assert_eq!(
+            write_clause("MATCH (n) REMOVE n.x").as_deref(),
+            Some("REMOVE")
+        );
+        assert_eq!(
+            write_clause("merge (n {id: 'a'})").as_deref(),
+            Some("MERGE")
+        );
+    }
+
+    #[test]
+    fn test_relationship_type_parse() {
+        for rel_type in [RelationshipType::Supports, RelationshipType::IsA] {
+            assert_eq!(RelationshipType::parse(rel_type.as_str()), Some(rel_type));
+        }
+        assert_eq!(RelationshipType::parse("KNOWS"), None);
+    }
+
     #[test]
     fn test_graph_node_builder() {
         let node = GraphNode::new("test", "Test")
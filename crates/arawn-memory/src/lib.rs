@@ -123,7 +123,8 @@ pub use vector::{
 
 // Re-export graph/knowledge store
 pub use graph::{
-    GraphNode, GraphRelationship, GraphStats, GraphStore, QueryResult, RelationshipType,
+    CypherRows, GraphNode, GraphRelationship, GraphStats, GraphStore, MEMORY_LABEL, QueryResult,
+    RelationshipType, Subgraph,
 };
 
 // Re-export validation
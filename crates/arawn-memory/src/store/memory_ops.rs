@@ -195,6 +195,30 @@ impl MemoryStore {
         Ok(memories)
     }
 
+    /// List memories extracted from a session, oldest first.
+    pub fn list_memories_for_session(&self, session_id: &str) -> Result<Vec<Memory>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT id, session_id, content_type, content, metadata, created_at, accessed_at, access_count,
+                   confidence_source, reinforcement_count, superseded, superseded_by,
+                   last_accessed, confidence_score, citation
+            FROM memories
+            WHERE session_id = ?1
+            ORDER BY created_at ASC
+            "#,
+        )?;
+        let mut rows = stmt.query(params![session_id])?;
+
+        let mut memories = Vec::new();
+        while let Some(row) = rows.next()? {
+            memories.push(Self::row_to_memory(row)?);
+        }
+
+        Ok(memories)
+    }
+
     /// Count memories with optional filtering.
     pub fn count_memories(&self, content_type: Option<ContentType>) -> Result<usize> {
         let conn = self.conn.lock().unwrap();
@@ -460,6 +484,30 @@ mod tests {
         assert_eq!(notes.len(), 5);
     }
 
+    #[test]
+    fn test_list_memories_for_session() {
+        let store = create_test_store();
+
+        let a1 = Memory::new(ContentType::Fact, "From A").with_session("sess-a");
+        let a2 = Memory::new(ContentType::Summary, "Also from A").with_session("sess-a");
+        let b = Memory::new(ContentType::Fact, "From B").with_session("sess-b");
+        let none = Memory::new(ContentType::Note, "No session");
+        for m in [&a1, &a2, &b, &none] {
+            store.insert_memory(m).unwrap();
+        }
+
+        let from_a = store.list_memories_for_session("sess-a").unwrap();
+        let ids: Vec<_> = from_a.iter().map(|m| m.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&a1.id) && ids.contains(&a2.id));
+        assert!(
+            store
+                .list_memories_for_session("sess-c")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_touch_memory() {
         let store = create_test_store();
@@ -228,6 +228,54 @@ impl MemoryStore {
         Ok(count as usize)
     }
 
+    /// Replace a tag on every note that carries it.
+    ///
+    /// Notes that already have `to` just lose `from`. Returns the number of
+    /// notes changed.
+    pub fn retag_notes(&self, from: &str, to: &str) -> Result<usize> {
+        if from == to {
+            return Ok(0);
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let pattern = format!("%\"{}\"%", from);
+        let notes = {
+            let mut stmt = tx.prepare(
+                "SELECT id, title, content, tags, created_at, updated_at FROM notes WHERE tags LIKE ?1",
+            )?;
+            let mut rows = stmt.query(params![pattern])?;
+            let mut notes = Vec::new();
+            while let Some(row) = rows.next()? {
+                notes.push(Self::row_to_note(row)?);
+            }
+            notes
+        };
+
+        let mut changed = 0;
+        for mut note in notes {
+            // LIKE can match a longer tag containing `from`
+            if !note.tags.iter().any(|t| t == from) {
+                continue;
+            }
+            let has_target = note.tags.iter().any(|t| t == to);
+            note.tags.retain(|t| t != from);
+            if !has_target {
+                note.tags.push(to.to_string());
+            }
+            tx.execute(
+                "UPDATE notes SET tags = ?2 WHERE id = ?1",
+                params![note.id.to_string(), serde_json::to_string(&note.tags)?],
+            )?;
+            changed += 1;
+        }
+        tx.commit()?;
+
+        debug!("Retagged {} notes from {} to {}", changed, from, to);
+        Ok(changed)
+    }
+
     /// Convert a database row to a Note struct.
     pub(crate) fn row_to_note(row: &rusqlite::Row) -> Result<Note> {
         let id_str: String = row.get(0)?;
@@ -370,6 +418,26 @@ mod tests {
         assert_eq!(all_three.len(), 1);
     }
 
+    #[test]
+    fn test_retag_notes() {
+        let store = create_test_store();
+
+        let plain = Note::new("one").with_tag("ws-old");
+        let both = Note::new("two").with_tag("ws-old").with_tag("ws-new");
+        let similar = Note::new("three").with_tag("ws-old-2");
+        for note in [&plain, &both, &similar] {
+            store.insert_note(note).unwrap();
+        }
+
+        assert_eq!(store.retag_notes("ws-old", "ws-new").unwrap(), 2);
+
+        let tags = |id| store.get_note(id).unwrap().unwrap().tags;
+        assert_eq!(tags(plain.id), vec!["ws-new"]);
+        assert_eq!(tags(both.id), vec!["ws-new"]);
+        assert_eq!(tags(similar.id), vec!["ws-old-2"]);
+        assert_eq!(store.retag_notes("ws-old", "ws-new").unwrap(), 0);
+    }
+
     #[test]
     fn test_count_notes_by_tag() {
         let store = create_test_store();
@@ -3,7 +3,7 @@
 use tracing::{debug, info, warn};
 
 use crate::error::Result;
-use crate::graph::{GraphNode, GraphRelationship, RelationshipType};
+use crate::graph::{GraphNode, GraphRelationship, MEMORY_LABEL, RelationshipType};
 use crate::types::{Memory, MemoryId};
 
 use super::{MemoryStore, MemoryWithContext, RelatedEntity, StoreFactResult, StoreOptions};
@@ -46,7 +46,7 @@ impl MemoryStore {
         // 3. Create graph entities and relationships if graph is initialized
         if !options.entities.is_empty() {
             if let Some(graph) = &self.graph {
-                let memory_node = GraphNode::new(memory.id.to_string(), "Memory")
+                let memory_node = GraphNode::new(memory.id.to_string(), MEMORY_LABEL)
                     .with_property("content_type", memory.content_type.as_str());
                 graph.add_entity(&memory_node)?;
 
@@ -170,7 +170,7 @@ impl MemoryStore {
         {
             let _ = graph.delete_entity(&memory.id.to_string());
 
-            let memory_node = GraphNode::new(memory.id.to_string(), "Memory")
+            let memory_node = GraphNode::new(memory.id.to_string(), MEMORY_LABEL)
                 .with_property("content_type", memory.content_type.as_str());
             graph.add_entity(&memory_node)?;
 
@@ -200,6 +200,18 @@ impl MemoryStore {
         crate::vector::store_embedding(&conn, memory_id, embedding)
     }
 
+    /// Get the stored embedding for a memory.
+    ///
+    /// Returns `None` if the memory has no embedding or vectors are not
+    /// initialized.
+    pub fn get_embedding(&self, memory_id: MemoryId) -> Result<Option<Vec<f32>>> {
+        if !self.has_vectors() {
+            return Ok(None);
+        }
+        let conn = self.conn.lock().unwrap();
+        crate::vector::get_embedding(&conn, memory_id)
+    }
+
     /// Delete an embedding for a memory.
     pub fn delete_embedding(&self, memory_id: MemoryId) -> Result<bool> {
         let conn = self.conn.lock().unwrap();
@@ -283,6 +295,7 @@ mod tests {
 
         assert!(store.has_embedding(memory.id).unwrap());
         assert_eq!(store.count_embeddings().unwrap(), 1);
+        assert_eq!(store.get_embedding(memory.id).unwrap(), Some(embedding));
     }
 
     #[test]
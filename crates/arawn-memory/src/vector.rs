@@ -89,6 +89,24 @@ pub fn store_embedding(conn: &Connection, memory_id: MemoryId, embedding: &[f32]
     Ok(())
 }
 
+/// Get the stored embedding for a memory, if any.
+pub fn get_embedding(conn: &Connection, memory_id: MemoryId) -> Result<Option<Vec<f32>>> {
+    let mut stmt = conn.prepare("SELECT embedding FROM memory_embeddings WHERE memory_id = ?1")?;
+    let mut rows = stmt.query(params![memory_id.to_string()])?;
+
+    match rows.next()? {
+        Some(row) => {
+            let bytes: Vec<u8> = row.get(0)?;
+            let embedding = bytes
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            Ok(Some(embedding))
+        }
+        None => Ok(None),
+    }
+}
+
 /// Delete an embedding for a memory.
 pub fn delete_embedding(conn: &Connection, memory_id: MemoryId) -> Result<bool> {
     let rows = conn.execute(
@@ -292,6 +292,38 @@ impl WorkflowFile {
 // Validation
 // ---------------------------------------------------------------------------
 
+/// A single problem found while validating a [`WorkflowDefinition`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Task the problem was found in, if it concerns a single task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn workflow(message: impl Into<String>) -> Self {
+        Self {
+            task: None,
+            message: message.into(),
+        }
+    }
+
+    fn task(task: &str, message: impl Into<String>) -> Self {
+        Self {
+            task: Some(task.to_string()),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 impl WorkflowDefinition {
     /// Validate the workflow definition.
     ///
@@ -301,57 +333,74 @@ impl WorkflowDefinition {
     /// - All dependency references point to existing tasks
     /// - No cycles in the dependency graph
     /// - Script actions have valid language
+    ///
+    /// Returns the first problem found; use [`issues`](Self::issues) for all
+    /// of them.
     pub fn validate(&self) -> Result<(), PipelineError> {
+        match self.issues().into_iter().next() {
+            Some(issue) => Err(PipelineError::InvalidWorkflow(issue.message)),
+            None => Ok(()),
+        }
+    }
+
+    /// Every problem [`validate`](Self::validate) checks for, in the order
+    /// it checks them. Empty when the definition is valid.
+    pub fn issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
         if self.name.is_empty() {
-            return Err(PipelineError::InvalidWorkflow(
-                "Workflow name cannot be empty".into(),
-            ));
+            issues.push(ValidationIssue::workflow("Workflow name cannot be empty"));
         }
 
         if self.tasks.is_empty() {
-            return Err(PipelineError::InvalidWorkflow(
-                "Workflow must have at least one task".into(),
+            issues.push(ValidationIssue::workflow(
+                "Workflow must have at least one task",
             ));
+            return issues;
         }
 
         // Check for duplicate task IDs
         let mut seen_ids = HashSet::new();
         for task in &self.tasks {
             if task.id.is_empty() {
-                return Err(PipelineError::InvalidWorkflow(
-                    "Task ID cannot be empty".into(),
+                issues.push(ValidationIssue::workflow("Task ID cannot be empty"));
+            } else if !seen_ids.insert(&task.id) {
+                issues.push(ValidationIssue::task(
+                    &task.id,
+                    format!("Duplicate task ID: {}", task.id),
                 ));
             }
-            if !seen_ids.insert(&task.id) {
-                return Err(PipelineError::InvalidWorkflow(format!(
-                    "Duplicate task ID: {}",
-                    task.id
-                )));
-            }
         }
 
         // Check all dependencies reference existing tasks
+        let mut unknown_deps = false;
         for task in &self.tasks {
             for dep in &task.dependencies {
                 if !seen_ids.contains(dep) {
-                    return Err(PipelineError::InvalidWorkflow(format!(
-                        "Task '{}' depends on unknown task '{}'",
-                        task.id, dep
-                    )));
+                    unknown_deps = true;
+                    issues.push(ValidationIssue::task(
+                        &task.id,
+                        format!("Task '{}' depends on unknown task '{}'", task.id, dep),
+                    ));
                 }
             }
         }
 
-        // Cycle detection via topological sort (Kahn's algorithm)
-        self.detect_cycles()?;
+        // Cycle detection via topological sort (Kahn's algorithm). The graph
+        // is only well-formed once every dependency exists.
+        if !unknown_deps && self.has_cycle() {
+            issues.push(ValidationIssue::workflow(
+                "Cycle detected in task dependencies",
+            ));
+        }
 
         // Each task must have either `runtime` or `action`
         for task in &self.tasks {
             if task.runtime.is_none() && task.action.is_none() {
-                return Err(PipelineError::InvalidWorkflow(format!(
-                    "Task '{}' must have either 'runtime' or 'action'",
-                    task.id
-                )));
+                issues.push(ValidationIssue::task(
+                    &task.id,
+                    format!("Task '{}' must have either 'runtime' or 'action'", task.id),
+                ));
             }
         }
 
@@ -361,18 +410,21 @@ impl WorkflowDefinition {
                 && let ActionDefinition::Script { language, .. } = action
                 && language != "rust"
             {
-                return Err(PipelineError::InvalidWorkflow(format!(
-                    "Unsupported script language '{}' in task '{}'. Only 'rust' is supported.",
-                    language, task.id
-                )));
+                issues.push(ValidationIssue::task(
+                    &task.id,
+                    format!(
+                        "Unsupported script language '{}' in task '{}'. Only 'rust' is supported.",
+                        language, task.id
+                    ),
+                ));
             }
         }
 
-        Ok(())
+        issues
     }
 
     /// Detect cycles in the task dependency graph using Kahn's algorithm.
-    fn detect_cycles(&self) -> Result<(), PipelineError> {
+    fn has_cycle(&self) -> bool {
         let task_ids: Vec<&str> = self.tasks.iter().map(|t| t.id.as_str()).collect();
         let id_to_idx: HashMap<&str, usize> = task_ids
             .iter()
@@ -384,8 +436,7 @@ impl WorkflowDefinition {
         let mut in_degree = vec![0usize; n];
         let mut adj: Vec<Vec<usize>> = vec![vec![]; n];
 
-        for task in &self.tasks {
-            let idx = id_to_idx[task.id.as_str()];
+        for (idx, task) in self.tasks.iter().enumerate() {
             for dep in &task.dependencies {
                 let dep_idx = id_to_idx[dep.as_str()];
                 adj[dep_idx].push(idx);
@@ -406,13 +457,7 @@ impl WorkflowDefinition {
             }
         }
 
-        if visited != n {
-            return Err(PipelineError::InvalidWorkflow(
-                "Cycle detected in task dependencies".into(),
-            ));
-        }
-
-        Ok(())
+        visited != n
     }
 }
 
@@ -1211,6 +1256,34 @@ dependencies = ["b"]
         assert!(err.to_string().contains("Cycle detected"));
     }
 
+    #[test]
+    fn test_issues_reports_every_problem() {
+        let toml = r#"
+[workflow]
+name = "test"
+[[workflow.tasks]]
+id = "a"
+action = { type = "tool", name = "x" }
+dependencies = ["missing"]
+[[workflow.tasks]]
+id = "a"
+[[workflow.tasks]]
+id = "b"
+action = { type = "script", source_file = "x.rs", language = "python" }
+"#;
+        let wf = WorkflowFile::from_toml(toml).unwrap();
+        let issues = wf.workflow.issues();
+        let tasks: Vec<Option<&str>> = issues.iter().map(|i| i.task.as_deref()).collect();
+        assert_eq!(tasks, vec![Some("a"), Some("a"), Some("a"), Some("b")]);
+        assert!(issues[0].message.contains("Duplicate task ID"));
+        assert!(issues[1].message.contains("unknown task 'missing'"));
+        assert!(issues[2].message.contains("either 'runtime' or 'action'"));
+        assert!(issues[3].message.contains("Unsupported script language"));
+
+        let err = wf.workflow.validate().unwrap_err();
+        assert!(err.to_string().contains(&issues[0].message));
+    }
+
     #[test]
     fn test_validate_linear_chain_no_cycle() {
         let toml = r#"
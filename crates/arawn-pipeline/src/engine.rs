@@ -6,15 +6,24 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 
 use cloacina::UniversalUuid;
 use cloacina::prelude::*;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, broadcast};
 use tracing::{debug, info};
 
 use crate::error::PipelineError;
 use crate::task::DynamicTask;
 
+/// Workflow executions, by `workflow` and `status`.
+pub const PIPELINE_RUNS_TOTAL: &str = "arawn_pipeline_runs_total";
+/// Workflow execution latency, by `workflow`.
+pub const PIPELINE_RUN_DURATION_SECONDS: &str = "arawn_pipeline_run_duration_seconds";
+
+/// Buffered [`WorkflowRunEvent`]s per subscriber before the oldest are dropped.
+const RUN_EVENT_CAPACITY: usize = 64;
+
 /// Configuration for the pipeline engine.
 ///
 /// # Examples
@@ -83,6 +92,22 @@ pub enum ExecutionStatus {
     TimedOut,
 }
 
+/// A finished [`PipelineEngine::execute`] call, broadcast to
+/// [`PipelineEngine::subscribe_runs`] subscribers.
+#[derive(Debug, Clone)]
+pub struct WorkflowRunEvent {
+    /// Workflow name.
+    pub workflow: String,
+    /// Execution ID (None when the run failed to start).
+    pub execution_id: Option<String>,
+    /// `completed`, `running`, `failed`, `timed_out` or `error`.
+    pub status: &'static str,
+    /// Failure message for `failed` and `error` runs.
+    pub error: Option<String>,
+    /// Wall-clock duration of the call.
+    pub duration_ms: u64,
+}
+
 /// Information about a scheduled workflow.
 #[derive(Debug, Clone)]
 pub struct ScheduleInfo {
@@ -325,6 +350,62 @@ mod tests {
         engine.shutdown().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_subscribe_runs() {
+        let dir = TempDir::new().unwrap();
+        let engine = test_engine(dir.path()).await;
+        let mut runs = engine.subscribe_runs();
+
+        let task = crate::task::DynamicTask::new(
+            "noop",
+            std::sync::Arc::new(|ctx| Box::pin(async move { Ok(ctx) })),
+        );
+        engine
+            .register_dynamic_workflow("run-events", "desc", vec![task])
+            .await
+            .unwrap();
+
+        let ctx = cloacina_workflow::context::Context::new();
+        let result = engine.execute("run-events", ctx).await.unwrap();
+        let event = runs.recv().await.unwrap();
+        assert_eq!(event.workflow, "run-events");
+        assert_eq!(event.status, "completed");
+        assert_eq!(event.execution_id, Some(result.execution_id));
+        assert!(event.error.is_none());
+
+        let ctx = cloacina_workflow::context::Context::new();
+        assert!(engine.execute("missing", ctx).await.is_err());
+        let event = runs.recv().await.unwrap();
+        assert_eq!(event.status, "error");
+        assert!(event.execution_id.is_none());
+        assert!(event.error.is_some());
+        engine.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unregister_workflow() {
+        let dir = TempDir::new().unwrap();
+        let engine = test_engine(dir.path()).await;
+        let task = crate::task::DynamicTask::new(
+            "noop",
+            std::sync::Arc::new(|ctx| Box::pin(async move { Ok(ctx) })),
+        );
+        engine
+            .register_dynamic_workflow("doomed", "desc", vec![task])
+            .await
+            .unwrap();
+
+        assert!(engine.unregister_workflow("doomed").await);
+        assert!(!engine.has_workflow("doomed").await);
+        assert!(!engine.unregister_workflow("doomed").await);
+        let ctx = cloacina_workflow::context::Context::new();
+        assert!(matches!(
+            engine.execute("doomed", ctx).await,
+            Err(PipelineError::WorkflowNotFound(_))
+        ));
+        engine.shutdown().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_execution_preserves_initial_context() {
         let dir = TempDir::new().unwrap();
@@ -464,6 +545,8 @@ pub struct PipelineEngine {
     runner: DefaultRunner,
     /// Registered workflows by name, for push trigger execution.
     workflows: Arc<RwLock<HashMap<String, Workflow>>>,
+    /// Finished runs, for observers such as webhooks.
+    runs: broadcast::Sender<WorkflowRunEvent>,
 }
 
 impl PipelineEngine {
@@ -494,6 +577,7 @@ impl PipelineEngine {
         Ok(Self {
             runner,
             workflows: Arc::new(RwLock::new(HashMap::new())),
+            runs: broadcast::channel(RUN_EVENT_CAPACITY).0,
         })
     }
 
@@ -559,6 +643,61 @@ impl PipelineEngine {
         &self,
         workflow_name: &str,
         context: Context<serde_json::Value>,
+    ) -> Result<ExecutionResult, PipelineError> {
+        let started = Instant::now();
+        let result = self.run_workflow(workflow_name, context).await;
+
+        let status = match &result {
+            Ok(r) => match r.status {
+                ExecutionStatus::Completed => "completed",
+                ExecutionStatus::Running => "running",
+                ExecutionStatus::Failed(_) => "failed",
+                ExecutionStatus::TimedOut => "timed_out",
+            },
+            Err(_) => "error",
+        };
+        metrics::counter!(
+            PIPELINE_RUNS_TOTAL,
+            "workflow" => workflow_name.to_string(),
+            "status" => status,
+        )
+        .increment(1);
+        metrics::histogram!(
+            PIPELINE_RUN_DURATION_SECONDS,
+            "workflow" => workflow_name.to_string(),
+        )
+        .record(started.elapsed().as_secs_f64());
+
+        // No subscribers is not an error
+        let _ = self.runs.send(WorkflowRunEvent {
+            workflow: workflow_name.to_string(),
+            execution_id: result.as_ref().ok().map(|r| r.execution_id.clone()),
+            status,
+            error: match &result {
+                Ok(ExecutionResult {
+                    status: ExecutionStatus::Failed(msg),
+                    ..
+                }) => Some(msg.clone()),
+                Err(e) => Some(e.to_string()),
+                Ok(_) => None,
+            },
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
+        result
+    }
+
+    /// Subscribe to finished [`execute`](Self::execute) calls.
+    ///
+    /// Runs started by cron schedules or push triggers inside the runner are
+    /// not reported.
+    pub fn subscribe_runs(&self) -> broadcast::Receiver<WorkflowRunEvent> {
+        self.runs.subscribe()
+    }
+
+    async fn run_workflow(
+        &self,
+        workflow_name: &str,
+        context: Context<serde_json::Value>,
     ) -> Result<ExecutionResult, PipelineError> {
         let workflows = self.workflows.read().await;
         if !workflows.contains_key(workflow_name) {
@@ -704,6 +843,18 @@ impl PipelineEngine {
         self.workflows.read().await.contains_key(name)
     }
 
+    /// Remove a workflow so it can no longer be executed, scheduled or
+    /// triggered. Returns whether it was registered.
+    ///
+    /// Existing cron schedules are left alone; cancel them separately.
+    pub async fn unregister_workflow(&self, name: &str) -> bool {
+        let removed = self.workflows.write().await.remove(name).is_some();
+        if removed {
+            info!("Workflow unregistered: {}", name);
+        }
+        removed
+    }
+
     /// Gracefully shut down the engine.
     ///
     /// Drains running workflows and stops background services.
@@ -31,9 +31,12 @@ pub use catalog::{CatalogEntry, RuntimeCatalog, RuntimeCategory};
 pub use context::{ContextResolver, resolve_params, resolve_template_string};
 pub use definition::{
     ActionDefinition, ActionExecutorFactory, Capabilities, RuntimeConfig, ScheduleConfig,
-    TaskDefinition, TriggerConfig, WorkflowDefinition, WorkflowFile,
+    TaskDefinition, TriggerConfig, ValidationIssue, WorkflowDefinition, WorkflowFile,
+};
+pub use engine::{
+    ExecutionResult, ExecutionStatus, PipelineConfig, PipelineEngine, ScheduleInfo,
+    WorkflowRunEvent,
 };
-pub use engine::{ExecutionResult, ExecutionStatus, PipelineConfig, PipelineEngine, ScheduleInfo};
 pub use error::{PipelineError, Result};
 pub use factory::build_executor_factory;
 pub use loader::{WatcherHandle, WorkflowEvent, WorkflowLoader};
@@ -7,18 +7,18 @@
 //! - [`AgentSpawner`]: Low-level spawner that creates agents from configs
 //! - [`PluginSubagentSpawner`]: Implements [`SubagentSpawner`] trait for use with `DelegateTool`
 
-use arawn_agent::Agent;
 use arawn_agent::tool::ToolRegistry;
 use arawn_agent::types::AgentConfig;
+use arawn_agent::{Agent, UsageMeter};
 use arawn_config::CompactionConfig;
-use arawn_llm::SharedBackend;
 use arawn_llm::types::{CompletionRequest, Message};
+use arawn_llm::{SharedBackend, SharedRouter};
 use arawn_types::{
     DelegationOutcome, SharedHookDispatcher, SubagentInfo, SubagentResult, SubagentSpawner,
 };
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use std::time::Instant;
 
 use crate::Result;
@@ -188,6 +188,10 @@ pub struct AgentSpawner {
     backend: SharedBackend,
     /// Default max_iterations from `[agent.default]` config (fallback for all agents).
     default_max_iterations: Option<u32>,
+    /// Optional LLM router shared with subagents.
+    router: Option<SharedRouter>,
+    /// Optional usage meter shared with subagents.
+    usage_meter: Option<UsageMeter>,
 }
 
 impl AgentSpawner {
@@ -197,9 +201,25 @@ impl AgentSpawner {
             parent_tools,
             backend,
             default_max_iterations: None,
+            router: None,
+            usage_meter: None,
         }
     }
 
+    /// Route subagent LLM calls through a router.
+    ///
+    /// Agents declaring `routing_hint` steer the router towards a profile.
+    pub fn with_router(mut self, router: SharedRouter) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// Record subagent LLM usage through a usage meter.
+    pub fn with_usage_meter(mut self, meter: UsageMeter) -> Self {
+        self.usage_meter = Some(meter);
+        self
+    }
+
     /// Create a new agent spawner with a default max_iterations.
     ///
     /// The `default_max_iterations` is applied to all spawned agents unless
@@ -243,13 +263,25 @@ impl AgentSpawner {
             agent_config.max_iterations = max_iter as u32;
         }
 
+        if let Some(ref hint) = config.agent.routing_hint {
+            agent_config.routing_hint = Some(hint.clone());
+        }
+
         // Note: model override is stored in config.agent.model but actual
         // backend switching requires the config resolver (deferred to wiring task).
 
-        let agent = Agent::builder()
+        let mut builder = Agent::builder()
             .with_shared_backend(self.backend.clone())
             .with_tools(constrained_tools)
-            .with_config(agent_config)
+            .with_config(agent_config);
+        if let Some(ref router) = self.router {
+            builder = builder.with_router(router.clone());
+        }
+        if let Some(ref meter) = self.usage_meter {
+            builder = builder.with_usage_meter(meter.clone());
+        }
+
+        let agent = builder
             .build()
             .map_err(|e| crate::PluginError::AgentConfigParse {
                 reason: format!("failed to build agent '{}': {}", config.agent.name, e),
@@ -287,6 +319,19 @@ impl AgentSpawner {
 // Plugin Subagent Spawner (implements SubagentSpawner trait)
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Agent configurations keyed by agent name, with the plugin each came from.
+#[derive(Debug, Clone, Default)]
+pub struct PluginAgents {
+    /// Agent configurations keyed by agent name.
+    pub configs: HashMap<String, PluginAgentConfig>,
+    /// Source plugin name for each agent (for SubagentInfo).
+    pub sources: HashMap<String, String>,
+}
+
+/// Plugin agents shared with a spawner, so they can be replaced while it
+/// is in use (e.g. when plugins are enabled or reloaded).
+pub type SharedPluginAgents = Arc<RwLock<PluginAgents>>;
+
 /// A subagent spawner backed by plugin-defined agent configurations.
 ///
 /// This struct implements the [`SubagentSpawner`] trait from `arawn-types`,
@@ -309,10 +354,8 @@ impl AgentSpawner {
 pub struct PluginSubagentSpawner {
     /// The underlying agent spawner.
     spawner: AgentSpawner,
-    /// Agent configurations keyed by agent name.
-    agent_configs: HashMap<String, PluginAgentConfig>,
-    /// Source plugin name for each agent (for SubagentInfo).
-    agent_sources: HashMap<String, String>,
+    /// Available agents and their source plugins.
+    agents: SharedPluginAgents,
     /// Optional hook dispatcher for subagent lifecycle events.
     hook_dispatcher: Option<SharedHookDispatcher>,
     /// Optional backend for result compaction.
@@ -333,14 +376,7 @@ impl PluginSubagentSpawner {
         backend: SharedBackend,
         agent_configs: HashMap<String, PluginAgentConfig>,
     ) -> Self {
-        Self {
-            spawner: AgentSpawner::new(parent_tools, backend),
-            agent_configs,
-            agent_sources: HashMap::new(),
-            hook_dispatcher: None,
-            compaction_backend: None,
-            compaction_config: CompactionConfig::default(),
-        }
+        Self::with_sources(parent_tools, backend, agent_configs, HashMap::new())
     }
 
     /// Create a spawner with source plugin tracking.
@@ -352,11 +388,26 @@ impl PluginSubagentSpawner {
         backend: SharedBackend,
         agent_configs: HashMap<String, PluginAgentConfig>,
         agent_sources: HashMap<String, String>,
+    ) -> Self {
+        let agents = PluginAgents {
+            configs: agent_configs,
+            sources: agent_sources,
+        };
+        Self::with_shared_agents(parent_tools, backend, Arc::new(RwLock::new(agents)))
+    }
+
+    /// Create a spawner over agents that may change while it is in use.
+    ///
+    /// Every lookup reads the current contents of `agents`, so replacing
+    /// them takes effect on the next delegation.
+    pub fn with_shared_agents(
+        parent_tools: Arc<ToolRegistry>,
+        backend: SharedBackend,
+        agents: SharedPluginAgents,
     ) -> Self {
         Self {
             spawner: AgentSpawner::new(parent_tools, backend),
-            agent_configs,
-            agent_sources,
+            agents,
             hook_dispatcher: None,
             compaction_backend: None,
             compaction_config: CompactionConfig::default(),
@@ -391,26 +442,50 @@ impl PluginSubagentSpawner {
         self
     }
 
+    /// Route subagent LLM calls through a router.
+    pub fn with_router(mut self, router: SharedRouter) -> Self {
+        self.spawner = self.spawner.with_router(router);
+        self
+    }
+
+    /// Record subagent LLM usage through a usage meter.
+    pub fn with_usage_meter(mut self, meter: UsageMeter) -> Self {
+        self.spawner = self.spawner.with_usage_meter(meter);
+        self
+    }
+
     /// Get the number of available agents.
     pub fn agent_count(&self) -> usize {
-        self.agent_configs.len()
+        self.agents().configs.len()
     }
 
     /// Check if any agents are available.
     pub fn is_empty(&self) -> bool {
-        self.agent_configs.is_empty()
+        self.agents().configs.is_empty()
     }
 
     /// Get the names of all available agents.
-    pub fn agent_names(&self) -> Vec<&str> {
-        self.agent_configs.keys().map(|s| s.as_str()).collect()
+    pub fn agent_names(&self) -> Vec<String> {
+        self.agents().configs.keys().cloned().collect()
+    }
+
+    /// The current agents.
+    fn agents(&self) -> RwLockReadGuard<'_, PluginAgents> {
+        self.agents.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Look up an agent's configuration.
+    fn agent_config(&self, name: &str) -> Option<PluginAgentConfig> {
+        self.agents().configs.get(name).cloned()
     }
 }
 
 #[async_trait]
 impl SubagentSpawner for PluginSubagentSpawner {
     async fn list_agents(&self) -> Vec<SubagentInfo> {
-        self.agent_configs
+        let agents = self.agents();
+        agents
+            .configs
             .iter()
             .map(|(name, config)| {
                 let tools = config
@@ -424,7 +499,7 @@ impl SubagentSpawner for PluginSubagentSpawner {
                     name: name.clone(),
                     description: config.agent.description.clone(),
                     tools,
-                    source: self.agent_sources.get(name).cloned(),
+                    source: agents.sources.get(name).cloned(),
                 }
             })
             .collect()
@@ -440,18 +515,14 @@ impl SubagentSpawner for PluginSubagentSpawner {
         use arawn_agent::types::Session;
 
         // Look up the agent config
-        let config = match self.agent_configs.get(agent_name) {
-            Some(c) => c,
-            None => {
-                return DelegationOutcome::UnknownAgent {
-                    name: agent_name.to_string(),
-                    available: self.agent_configs.keys().cloned().collect(),
-                };
-            }
+        let Some(mut agent_config) = self.agent_config(agent_name) else {
+            return DelegationOutcome::UnknownAgent {
+                name: agent_name.to_string(),
+                available: self.agent_names(),
+            };
         };
 
         // Spawn the agent with max_turns override if specified
-        let mut agent_config = config.clone();
         if let Some(max) = max_turns
             && let Some(ref mut constraints) = agent_config.agent.constraints
         {
@@ -565,14 +636,13 @@ impl SubagentSpawner for PluginSubagentSpawner {
 
         // Verify the agent exists
         let config = self
-            .agent_configs
-            .get(agent_name)
+            .agent_config(agent_name)
             .ok_or_else(|| format!("Unknown agent: {}", agent_name))?;
 
         // Spawn the agent
         let agent = self
             .spawner
-            .spawn(config)
+            .spawn(&config)
             .map_err(|e| format!("Failed to spawn agent '{}': {}", agent_name, e))?;
 
         // Build context preamble if context is provided
@@ -675,7 +745,7 @@ impl SubagentSpawner for PluginSubagentSpawner {
     }
 
     async fn has_agent(&self, name: &str) -> bool {
-        self.agent_configs.contains_key(name)
+        self.agents().configs.contains_key(name)
     }
 }
 
@@ -741,6 +811,7 @@ mod tests {
                 name: name.to_string(),
                 description: format!("Test agent: {}", name),
                 model: None,
+                routing_hint: None,
                 system_prompt: Some(AgentSystemPrompt {
                     text: format!("You are the {} agent.", name),
                 }),
@@ -817,6 +888,7 @@ mod tests {
                 name: "open".to_string(),
                 description: "No constraints".to_string(),
                 model: None,
+                routing_hint: None,
                 system_prompt: None,
                 constraints: None,
             },
@@ -912,8 +984,30 @@ mod tests {
         assert!(!spawner.is_empty());
 
         let names = spawner.agent_names();
-        assert!(names.contains(&"a"));
-        assert!(names.contains(&"b"));
+        assert!(names.contains(&"a".to_string()));
+        assert!(names.contains(&"b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_subagent_spawner_shared_agents() {
+        let parent_tools = make_parent_tools();
+        let backend: SharedBackend = Arc::new(MockBackend::with_text("test"));
+        let agents = SharedPluginAgents::default();
+        let spawner =
+            PluginSubagentSpawner::with_shared_agents(parent_tools, backend, agents.clone());
+        assert!(!spawner.has_agent("a").await);
+
+        {
+            let mut agents = agents.write().unwrap();
+            agents
+                .configs
+                .insert("a".to_string(), make_agent_config("a", vec![], None));
+            agents.sources.insert("a".to_string(), "tools".to_string());
+        }
+        assert!(spawner.has_agent("a").await);
+        let listed = spawner.list_agents().await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].source.as_deref(), Some("tools"));
     }
 
     #[test]
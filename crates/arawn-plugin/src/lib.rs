@@ -19,25 +19,35 @@
 //!     hooks.json             # hook configuration
 //!   agents/
 //!     my-agent.md            # agent: markdown with YAML frontmatter
+//!   .mcp.json                # MCP servers (or inline `mcpServers` in manifest)
 //! ```
 
 pub mod agent_spawner;
 pub mod hooks;
+pub mod lockfile;
 pub mod manager;
 pub mod manifest;
+pub mod mcp;
+pub mod runtime;
 pub mod skill;
 pub mod subscription;
 pub mod types;
 pub mod validation;
 pub mod watcher;
 
-pub use agent_spawner::{AgentSpawner, PluginSubagentSpawner};
+pub use agent_spawner::{AgentSpawner, PluginAgents, PluginSubagentSpawner, SharedPluginAgents};
 pub use arawn_types::HookOutcome;
 pub use hooks::HookDispatcher;
+pub use lockfile::{LOCKFILE_NAME, LockCheck, LockedPlugin, PluginLockfile};
 pub use manager::{LoadedAgent, LoadedPlugin, LoadedSkill, PluginManager};
 pub use manifest::{CapabilitySummary, PluginManifest};
+pub use mcp::{PluginMcpServerDef, namespaced_server_name};
+pub use runtime::PluginRuntime;
 pub use skill::{Skill, SkillInvocation, SkillRegistry};
-pub use subscription::{GitOps, RuntimePluginsConfig, SubscriptionManager, SyncAction, SyncResult};
+pub use subscription::{
+    GitOps, OutdatedPlugin, RuntimePluginsConfig, SubscriptionManager, SyncAction, SyncMode,
+    SyncResult, parse_source,
+};
 pub use types::{
     AgentConstraints, AgentSection, AgentSystemPrompt, HookAction, HookDef, HookEvent,
     HookMatcherGroup, HookType, HooksConfig, PluginAgentConfig, PluginAgentDef, PromptFragment,
@@ -0,0 +1,331 @@
+//! Plugin lockfile for reproducible subscription sets.
+//!
+//! `arawn-plugins.lock` records, for every subscribed plugin, the commit that
+//! was resolved from its ref and a content hash of the checked-out files.
+//! Committing the lockfile alongside a project pins the exact plugin set
+//! across machines.
+//!
+//! ## Format
+//!
+//! ```toml
+//! version = 1
+//!
+//! [plugins."github/author-repo"]
+//! source = "https://github.com/author/repo.git"
+//! ref = "v1.0.0"
+//! commit = "3f2c9a0d..."
+//! content_hash = "sha256:9b1e..."
+//! ```
+
+use crate::PluginError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// File name of the plugin lockfile.
+pub const LOCKFILE_NAME: &str = "arawn-plugins.lock";
+
+/// Current lockfile format version.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Prefix for content hashes, naming the algorithm.
+const HASH_PREFIX: &str = "sha256:";
+
+/// The parsed contents of `arawn-plugins.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginLockfile {
+    /// Format version.
+    pub version: u32,
+    /// Locked plugins keyed by subscription ID.
+    #[serde(default)]
+    pub plugins: BTreeMap<String, LockedPlugin>,
+}
+
+impl Default for PluginLockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+/// A single pinned plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPlugin {
+    /// Clone URL the plugin was fetched from.
+    pub source: String,
+    /// The ref (branch, tag, or commit) the subscription requested.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Resolved commit SHA.
+    pub commit: String,
+    /// Content hash of the checked-out files (excluding `.git`).
+    pub content_hash: String,
+}
+
+impl PluginLockfile {
+    /// Load a lockfile from disk. Returns an empty lockfile if it doesn't exist.
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        Self::from_toml(&content)
+    }
+
+    /// Parse a lockfile from TOML.
+    pub fn from_toml(content: &str) -> crate::Result<Self> {
+        let lockfile: Self = toml::from_str(content).map_err(|e| PluginError::ManifestParse {
+            reason: format!("invalid {}: {}", LOCKFILE_NAME, e),
+        })?;
+        if lockfile.version > LOCKFILE_VERSION {
+            return Err(PluginError::Validation {
+                field: "version".to_string(),
+                message: format!(
+                    "{} version {} is newer than supported version {}",
+                    LOCKFILE_NAME, lockfile.version, LOCKFILE_VERSION
+                ),
+            });
+        }
+        Ok(lockfile)
+    }
+
+    /// Serialize to TOML.
+    pub fn to_toml(&self) -> crate::Result<String> {
+        let body = toml::to_string_pretty(self).map_err(|e| PluginError::ManifestParse {
+            reason: e.to_string(),
+        })?;
+        Ok(format!(
+            "# This file is generated by `arawn plugin`. Do not edit by hand.\n{}",
+            body
+        ))
+    }
+
+    /// Save the lockfile to disk, creating parent directories if needed.
+    pub fn save(&self, path: &Path) -> crate::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    /// Get the locked entry for a subscription.
+    pub fn get(&self, subscription_id: &str) -> Option<&LockedPlugin> {
+        self.plugins.get(subscription_id)
+    }
+
+    /// Insert or replace the locked entry for a subscription.
+    pub fn insert(&mut self, subscription_id: impl Into<String>, entry: LockedPlugin) {
+        self.plugins.insert(subscription_id.into(), entry);
+    }
+
+    /// Remove a subscription's entry. Returns true if it was present.
+    pub fn remove(&mut self, subscription_id: &str) -> bool {
+        self.plugins.remove(subscription_id).is_some()
+    }
+}
+
+/// Outcome of checking a checked-out plugin against its lock entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockCheck {
+    /// The subscription has no lock entry.
+    Unlocked,
+    /// Commit and content match the lockfile.
+    Verified,
+    /// The checkout is at a different commit than the lockfile records.
+    CommitMismatch { locked: String, actual: String },
+    /// Same commit, but the files on disk differ from the locked hash.
+    ContentMismatch { locked: String, actual: String },
+    /// The plugin could not be inspected (missing checkout, git failure).
+    Unavailable { reason: String },
+}
+
+impl LockCheck {
+    /// Whether the plugin may be loaded without complaint.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, LockCheck::Unlocked | LockCheck::Verified)
+    }
+}
+
+impl std::fmt::Display for LockCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockCheck::Unlocked => write!(f, "not in lockfile"),
+            LockCheck::Verified => write!(f, "verified"),
+            LockCheck::CommitMismatch { locked, actual } => write!(
+                f,
+                "commit {} does not match locked commit {}",
+                short_commit(actual),
+                short_commit(locked)
+            ),
+            LockCheck::ContentMismatch { locked, actual } => write!(
+                f,
+                "content hash {} does not match locked hash {}",
+                actual, locked
+            ),
+            LockCheck::Unavailable { reason } => write!(f, "cannot verify: {}", reason),
+        }
+    }
+}
+
+/// Shorten a commit SHA for display.
+pub fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
+/// Compute a content hash over all files in a plugin directory.
+///
+/// Files are visited in sorted relative-path order and `.git` is skipped, so
+/// the hash is stable across clones and platforms. Each file contributes its
+/// relative path (with `/` separators) and its bytes.
+pub fn content_hash(dir: &Path) -> std::io::Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    let mut buf = Vec::new();
+    for rel in files {
+        let rel_str = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        buf.clear();
+        std::fs::File::open(dir.join(&rel))?.read_to_end(&mut buf)?;
+
+        hasher.update(rel_str.as_bytes());
+        hasher.update([0]);
+        hasher.update((buf.len() as u64).to_le_bytes());
+        hasher.update(&buf);
+    }
+
+    Ok(format!("{}{}", HASH_PREFIX, hex::encode(hasher.finalize())))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if entry.file_name() == ".git" {
+                continue;
+            }
+            collect_files(root, &path, out)?;
+        } else if file_type.is_file()
+            && let Ok(rel) = path.strip_prefix(root)
+        {
+            out.push(rel.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entry() -> LockedPlugin {
+        LockedPlugin {
+            source: "https://github.com/author/repo.git".to_string(),
+            git_ref: "v1.0.0".to_string(),
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            content_hash: "sha256:abc".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_toml() {
+        let mut lock = PluginLockfile::default();
+        lock.insert("github/author/repo@v1.0.0", sample_entry());
+
+        let toml = lock.to_toml().unwrap();
+        assert!(toml.contains("ref = \"v1.0.0\""));
+        let parsed = PluginLockfile::from_toml(&toml).unwrap();
+        assert_eq!(parsed, lock);
+    }
+
+    #[test]
+    fn test_load_missing_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = PluginLockfile::load(&dir.path().join(LOCKFILE_NAME)).unwrap();
+        assert!(lock.plugins.is_empty());
+        assert_eq!(lock.version, LOCKFILE_VERSION);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(LOCKFILE_NAME);
+        let mut lock = PluginLockfile::default();
+        lock.insert("id", sample_entry());
+        lock.save(&path).unwrap();
+
+        let loaded = PluginLockfile::load(&path).unwrap();
+        assert_eq!(loaded.get("id"), Some(&sample_entry()));
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let err = PluginLockfile::from_toml("version = 99\n").unwrap_err();
+        assert!(matches!(err, PluginError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut lock = PluginLockfile::default();
+        lock.insert("id", sample_entry());
+        assert!(lock.remove("id"));
+        assert!(!lock.remove("id"));
+    }
+
+    #[test]
+    fn test_content_hash_stable_and_ignores_git() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("skills/a")).unwrap();
+        std::fs::write(dir.path().join("skills/a/SKILL.md"), "hello").unwrap();
+        std::fs::write(dir.path().join("README.md"), "readme").unwrap();
+
+        let before = content_hash(dir.path()).unwrap();
+        assert!(before.starts_with("sha256:"));
+
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+        assert_eq!(content_hash(dir.path()).unwrap(), before);
+
+        std::fs::write(dir.path().join("skills/a/SKILL.md"), "changed").unwrap();
+        assert_ne!(content_hash(dir.path()).unwrap(), before);
+    }
+
+    #[test]
+    fn test_content_hash_depends_on_paths() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+        std::fs::write(a.path().join("one.md"), "x").unwrap();
+        std::fs::write(b.path().join("two.md"), "x").unwrap();
+        assert_ne!(
+            content_hash(a.path()).unwrap(),
+            content_hash(b.path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_lock_check_display_and_ok() {
+        assert!(LockCheck::Verified.is_ok());
+        assert!(LockCheck::Unlocked.is_ok());
+        let mismatch = LockCheck::CommitMismatch {
+            locked: "aaaaaaaaaaaaaaaaaaaa".to_string(),
+            actual: "bbbbbbbbbbbbbbbbbbbb".to_string(),
+        };
+        assert!(!mismatch.is_ok());
+        assert_eq!(
+            mismatch.to_string(),
+            "commit bbbbbbbbbbbb does not match locked commit aaaaaaaaaaaa"
+        );
+    }
+}
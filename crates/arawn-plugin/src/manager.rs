@@ -7,6 +7,7 @@
 use crate::manifest::{PluginManifest, PluginMeta};
 use crate::types::{HooksConfig, HooksConfigExt, PluginAgentConfig, PluginAgentDef, SkillDef};
 use crate::{PluginError, Result};
+use arawn_mcp::{McpManager, McpServerConfig};
 use std::path::{Path, PathBuf};
 
 /// The path to the plugin manifest relative to the plugin root.
@@ -25,6 +26,8 @@ pub struct LoadedPlugin {
     pub agent_configs: Vec<LoadedAgent>,
     /// Loaded hooks configuration (from hooks/hooks.json or path in manifest).
     pub hooks_config: Option<HooksConfig>,
+    /// MCP servers declared via `mcpServers`, namespaced as `plugin:server`.
+    pub mcp_servers: Vec<McpServerConfig>,
 }
 
 impl LoadedPlugin {
@@ -87,61 +90,55 @@ impl PluginManager {
         &self.plugin_dirs
     }
 
-    /// Discover and load all plugins from configured directories.
+    /// Find the plugin root directories under the configured directories.
     ///
-    /// Scans each directory for subdirectories containing `plugin.toml`.
-    /// Invalid plugins are logged as warnings and skipped.
-    pub fn load_all(&self) -> Vec<LoadedPlugin> {
-        let mut loaded = Vec::new();
+    /// A configured directory that itself contains `.claude-plugin/plugin.json`
+    /// (such as a subscription checkout) is a plugin; otherwise each of its
+    /// subdirectories that contains one is. Results are sorted per directory.
+    pub fn discover(&self) -> Vec<PathBuf> {
+        let mut found = Vec::new();
 
         for dir in &self.plugin_dirs {
             if !dir.exists() {
                 tracing::debug!(dir = %dir.display(), "plugin directory does not exist, skipping");
                 continue;
             }
+            if dir.join(MANIFEST_PATH).exists() {
+                found.push(dir.clone());
+                continue;
+            }
 
-            match self.scan_directory(dir) {
-                Ok(plugins) => loaded.extend(plugins),
+            let entries = match std::fs::read_dir(dir) {
+                Ok(entries) => entries,
                 Err(e) => {
                     tracing::warn!(
                         dir = %dir.display(),
                         error = %e,
                         "failed to scan plugin directory"
                     );
+                    continue;
                 }
-            }
+            };
+            let mut children: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_dir() && path.join(MANIFEST_PATH).exists())
+                .collect();
+            children.sort();
+            found.extend(children);
         }
 
-        tracing::info!(
-            count = loaded.len(),
-            plugins = ?loaded.iter().map(|p| &p.manifest.name).collect::<Vec<_>>(),
-            "loaded plugins"
-        );
-
-        loaded
+        found
     }
 
-    /// Scan a single directory for plugin subdirectories.
+    /// Discover and load all plugins from configured directories.
     ///
-    /// A plugin is identified by having a `.claude-plugin/plugin.json` file.
-    fn scan_directory(&self, dir: &Path) -> Result<Vec<LoadedPlugin>> {
-        let mut plugins = Vec::new();
-
-        let entries = std::fs::read_dir(dir)?;
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-
-            if !path.is_dir() {
-                continue;
-            }
-
-            let manifest_path = path.join(MANIFEST_PATH);
-            if !manifest_path.exists() {
-                continue;
-            }
+    /// See [`discover`](Self::discover) for which directories are plugins.
+    /// Invalid plugins are logged as warnings and skipped.
+    pub fn load_all(&self) -> Vec<LoadedPlugin> {
+        let mut loaded = Vec::new();
 
-            match self.load_plugin(&path, &manifest_path) {
+        for path in self.discover() {
+            match self.load_single(&path) {
                 Ok(plugin) => {
                     let meta = plugin.meta();
                     tracing::info!(
@@ -149,9 +146,10 @@ impl PluginManager {
                         version = %meta.version,
                         skills = plugin.skill_contents.len(),
                         agents = plugin.agent_configs.len(),
+                        mcp_servers = plugin.mcp_servers.len(),
                         "loaded plugin"
                     );
-                    plugins.push(plugin);
+                    loaded.push(plugin);
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -163,7 +161,13 @@ impl PluginManager {
             }
         }
 
-        Ok(plugins)
+        tracing::info!(
+            count = loaded.len(),
+            plugins = ?loaded.iter().map(|p| &p.manifest.name).collect::<Vec<_>>(),
+            "loaded plugins"
+        );
+
+        loaded
     }
 
     /// Load a single plugin from its directory.
@@ -182,12 +186,20 @@ impl PluginManager {
         // Load hooks configuration
         let hooks_config = self.load_hooks(plugin_dir, &manifest);
 
+        // Resolve declared MCP servers
+        let mcp_servers = manifest
+            .mcp_servers
+            .as_ref()
+            .map(|value| crate::mcp::load_plugin_mcp_servers(&manifest.name, value, plugin_dir))
+            .unwrap_or_default();
+
         Ok(LoadedPlugin {
             manifest,
             plugin_dir: plugin_dir.to_path_buf(),
             skill_contents,
             agent_configs,
             hooks_config,
+            mcp_servers,
         })
     }
 
@@ -373,6 +385,27 @@ impl PluginManager {
         }
         self.load_plugin(plugin_dir, &manifest_path)
     }
+
+    /// Register and start the MCP servers declared by a plugin.
+    ///
+    /// Servers are registered under the `plugin:server` namespace. Calling
+    /// this again after a reload restarts only servers whose config changed
+    /// and stops servers the plugin no longer declares. Returns the names of
+    /// servers that were (re)started.
+    ///
+    /// Connecting spawns processes and performs blocking I/O; call from a
+    /// blocking context (e.g. `spawn_blocking`) when inside an async runtime.
+    pub fn start_mcp_servers(&self, plugin: &LoadedPlugin, mcp: &mut McpManager) -> Vec<String> {
+        crate::mcp::sync_plugin_mcp_servers(plugin, mcp)
+    }
+
+    /// Stop and unregister all MCP servers belonging to a plugin.
+    ///
+    /// Used when a plugin is disabled or removed. Returns the number of
+    /// servers stopped.
+    pub fn stop_mcp_servers(&self, plugin_name: &str, mcp: &mut McpManager) -> usize {
+        crate::mcp::stop_plugin_mcp_servers(plugin_name, mcp)
+    }
 }
 
 /// Extract a field value from YAML frontmatter in a markdown file.
@@ -412,6 +445,7 @@ fn extract_frontmatter_field(content: &str, field: &str) -> Option<String> {
 /// description: Agent description
 /// capabilities: ["task1", "task2"]
 /// tools: ["shell", "file_read"]
+/// routing_hint: fast
 /// ---
 ///
 /// # Agent Name
@@ -422,6 +456,7 @@ fn parse_agent_markdown(name: &str, content: &str) -> Result<(PluginAgentDef, Pl
     use crate::types::{AgentConstraints, AgentSection, AgentSystemPrompt};
 
     let description = extract_frontmatter_field(content, "description").unwrap_or_default();
+    let routing_hint = extract_frontmatter_field(content, "routing_hint").filter(|s| !s.is_empty());
 
     // Extract tools from frontmatter (simplified parsing)
     let tools: Vec<String> = extract_frontmatter_field(content, "tools")
@@ -455,6 +490,7 @@ fn parse_agent_markdown(name: &str, content: &str) -> Result<(PluginAgentDef, Pl
             name: name.to_string(),
             description,
             model: None,
+            routing_hint,
             system_prompt: system_prompt.map(|text| AgentSystemPrompt { text }),
             constraints: if tools.is_empty() {
                 None
@@ -602,6 +638,21 @@ You are the {name} agent. Help the user with {name}-related tasks.
         assert!(names.contains(&"beta"));
     }
 
+    #[test]
+    fn test_discover_accepts_plugin_root_dirs() {
+        let tmp = TempDir::new().unwrap();
+        create_test_plugin(tmp.path(), "beta");
+        create_test_plugin(tmp.path(), "alpha");
+        let checkout = create_test_plugin(&tmp.path().join("cache"), "gamma");
+
+        let manager = PluginManager::new(vec![tmp.path().to_path_buf(), checkout.clone()]);
+        assert_eq!(
+            manager.discover(),
+            vec![tmp.path().join("alpha"), tmp.path().join("beta"), checkout]
+        );
+        assert_eq!(manager.load_all().len(), 3);
+    }
+
     #[test]
     fn test_load_all_skips_nonexistent_dirs() {
         let manager = PluginManager::new(vec![PathBuf::from("/nonexistent/path/plugins")]);
@@ -780,6 +831,16 @@ You are a code review expert.
         assert_eq!(constraints.tools, vec!["shell", "file_read"]);
     }
 
+    #[test]
+    fn test_parse_agent_markdown_routing_hint() {
+        let content = "---\ndescription: Quick lookups\nrouting_hint: fast\n---\n\nBe brief.\n";
+        let (_, config) = parse_agent_markdown("lookup", content).unwrap();
+        assert_eq!(config.agent.routing_hint.as_deref(), Some("fast"));
+
+        let (_, config) = parse_agent_markdown("plain", "---\ndescription: x\n---\nBody").unwrap();
+        assert!(config.agent.routing_hint.is_none());
+    }
+
     #[test]
     fn test_manifest_path_constant() {
         assert_eq!(MANIFEST_PATH, ".claude-plugin/plugin.json");
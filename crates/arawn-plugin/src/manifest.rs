@@ -3,13 +3,14 @@
 //! A plugin manifest (`.claude-plugin/plugin.json`) describes plugin metadata
 //! and paths to component directories. This follows Claude Code's plugin format.
 
+use crate::types::PromptFragment;
 use crate::validation::{self, ManifestValidationError};
 use crate::{PluginError, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Summary of declared vs discovered capabilities for a plugin.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CapabilitySummary {
     /// Whether skills are declared in manifest.
     pub skills_declared: bool,
@@ -176,6 +177,18 @@ pub struct PluginManifest {
     /// Path to output styles directory.
     #[serde(default, rename = "outputStyles")]
     pub output_styles: Option<PathOrPaths>,
+
+    /// Path to workstream templates directory (Arawn extension).
+    ///
+    /// Each subdirectory with a `template.toml` is a template, offered as
+    /// `<plugin>:<template>`.
+    #[serde(default)]
+    pub templates: Option<PathOrPaths>,
+
+    /// Text added to the agent's system prompt while the plugin is enabled
+    /// (Arawn extension).
+    #[serde(default)]
+    pub prompt: Option<PromptFragment>,
 }
 
 /// A path or array of paths (Claude supports both).
@@ -335,6 +348,14 @@ impl PluginManifest {
             errors.push(e);
         }
 
+        // Check templates paths
+        if let Some(ref templates) = self.templates
+            && let Err(e) =
+                validation::validate_paths_exist("templates", &templates.to_vec(), plugin_dir)
+        {
+            errors.push(e);
+        }
+
         errors
     }
 
@@ -396,6 +417,14 @@ impl PluginManifest {
             .unwrap_or_default()
     }
 
+    /// Get the workstream templates paths resolved against a base directory.
+    pub fn templates_paths(&self, plugin_dir: &Path) -> Vec<PathBuf> {
+        self.templates
+            .as_ref()
+            .map(|p| p.resolve(plugin_dir))
+            .unwrap_or_default()
+    }
+
     /// Get plugin metadata in the legacy format.
     pub fn plugin_meta(&self) -> PluginMeta {
         PluginMeta::from(self)
@@ -522,6 +551,21 @@ mod tests {
         assert_eq!(paths, vec![PathBuf::from("/plugin/hooks/hooks.json")]);
     }
 
+    #[test]
+    fn test_templates_paths() {
+        let json = r#"{ "name": "test", "templates": "./templates" }"#;
+        let manifest = PluginManifest::from_json(json).unwrap();
+
+        let paths = manifest.templates_paths(Path::new("/plugin"));
+        assert_eq!(paths, vec![PathBuf::from("/plugin/templates")]);
+        assert!(
+            PluginManifest::from_json(r#"{ "name": "test" }"#)
+                .unwrap()
+                .templates_paths(Path::new("/plugin"))
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_plugin_meta_conversion() {
         let manifest = PluginManifest::from_json(sample_manifest_json()).unwrap();
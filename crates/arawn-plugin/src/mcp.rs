@@ -0,0 +1,411 @@
+//! MCP servers declared by plugins.
+//!
+//! A plugin manifest may declare MCP servers via its `mcpServers` field,
+//! either inline or as a path to a `.mcp.json` file. This follows Claude
+//! Code's format:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "db": {
+//!       "command": "${CLAUDE_PLUGIN_ROOT}/bin/db-server",
+//!       "args": ["--config", "${CLAUDE_PLUGIN_ROOT}/db.toml"],
+//!       "env": { "LOG_LEVEL": "info" }
+//!     },
+//!     "search": { "type": "http", "url": "http://localhost:8080/mcp" }
+//!   }
+//! }
+//! ```
+//!
+//! Servers are registered with the [`McpManager`] under a `plugin:server`
+//! namespace so they cannot collide with servers from `arawn.toml` or from
+//! other plugins.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use arawn_mcp::{McpManager, McpServerConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::manager::LoadedPlugin;
+use crate::{PluginError, Result, expand_plugin_root};
+
+/// Separator between plugin name and server name in namespaced server names.
+pub const MCP_NAMESPACE_SEPARATOR: char = ':';
+
+/// A single MCP server entry in a plugin's `mcpServers` config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginMcpServerDef {
+    /// Transport type: `stdio` (default) or `http`.
+    #[serde(default, rename = "type")]
+    pub transport: Option<String>,
+
+    /// Command to spawn (stdio transport).
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Command arguments (stdio transport).
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Environment variables (stdio transport).
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// Server URL (http transport).
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// HTTP headers (http transport).
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+impl PluginMcpServerDef {
+    /// Whether this entry uses the HTTP transport.
+    pub fn is_http(&self) -> bool {
+        matches!(self.transport.as_deref(), Some("http" | "streamable-http"))
+            || (self.transport.is_none() && self.command.is_none() && self.url.is_some())
+    }
+
+    /// Convert to an [`McpServerConfig`], expanding `${CLAUDE_PLUGIN_ROOT}`.
+    ///
+    /// `name` should already be namespaced (see [`namespaced_server_name`]).
+    pub fn to_server_config(&self, name: &str, plugin_dir: &Path) -> Result<McpServerConfig> {
+        let expand = |s: &str| expand_plugin_root(s, plugin_dir);
+
+        match self.transport.as_deref() {
+            None | Some("stdio") | Some("http") | Some("streamable-http") => {}
+            Some(other) => {
+                return Err(PluginError::Validation {
+                    field: format!("mcpServers.{}.type", name),
+                    message: format!("unsupported MCP transport '{}'", other),
+                });
+            }
+        }
+
+        if self.is_http() {
+            let url = self.url.as_deref().ok_or_else(|| PluginError::Validation {
+                field: format!("mcpServers.{}.url", name),
+                message: "http transport requires a url".to_string(),
+            })?;
+            let mut headers: Vec<_> = self.headers.iter().collect();
+            headers.sort();
+            let mut config = McpServerConfig::http(name, expand(url));
+            for (k, v) in headers {
+                config = config.with_header(k, expand(v));
+            }
+            Ok(config)
+        } else {
+            let command = self
+                .command
+                .as_deref()
+                .ok_or_else(|| PluginError::Validation {
+                    field: format!("mcpServers.{}.command", name),
+                    message: "stdio transport requires a command".to_string(),
+                })?;
+            let mut env: Vec<(String, String)> = self
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), expand(v)))
+                .collect();
+            env.sort();
+            env.push((
+                crate::CLAUDE_PLUGIN_ROOT_VAR.to_string(),
+                plugin_dir.display().to_string(),
+            ));
+            Ok(McpServerConfig::new(name, expand(command))
+                .with_args(self.args.iter().map(|a| expand(a)).collect())
+                .with_env(env))
+        }
+    }
+}
+
+/// Build the namespaced server name `plugin:server`.
+pub fn namespaced_server_name(plugin_name: &str, server_name: &str) -> String {
+    format!("{}{}{}", plugin_name, MCP_NAMESPACE_SEPARATOR, server_name)
+}
+
+/// Parse the manifest's `mcpServers` value into server definitions.
+///
+/// Accepts an inline object, or a string path (relative to the plugin root)
+/// to a JSON file. Both forms may either be the server map directly or wrap
+/// it in a top-level `mcpServers` key.
+pub fn parse_mcp_servers(
+    value: &serde_json::Value,
+    plugin_dir: &Path,
+) -> Result<BTreeMap<String, PluginMcpServerDef>> {
+    let resolved = match value {
+        serde_json::Value::String(path) => {
+            let path = expand_plugin_root(path, plugin_dir);
+            let path = Path::new(&path);
+            let path = if path.is_relative() {
+                plugin_dir.join(path)
+            } else {
+                path.to_path_buf()
+            };
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).map_err(|e| PluginError::ManifestParse {
+                reason: format!("{}: {}", path.display(), e),
+            })?
+        }
+        other => other.clone(),
+    };
+
+    let servers = match resolved.get("mcpServers") {
+        Some(inner) => inner.clone(),
+        None => resolved,
+    };
+
+    serde_json::from_value(servers).map_err(|e| PluginError::ManifestParse {
+        reason: format!("invalid mcpServers: {}", e),
+    })
+}
+
+/// Resolve a plugin's declared MCP servers into namespaced server configs.
+///
+/// Invalid entries are logged and skipped so one bad server does not
+/// prevent the rest of the plugin from loading.
+pub fn load_plugin_mcp_servers(
+    plugin_name: &str,
+    value: &serde_json::Value,
+    plugin_dir: &Path,
+) -> Vec<McpServerConfig> {
+    let defs = match parse_mcp_servers(value, plugin_dir) {
+        Ok(defs) => defs,
+        Err(e) => {
+            tracing::warn!(
+                plugin = %plugin_name,
+                error = %e,
+                "failed to parse plugin mcpServers, skipping"
+            );
+            return Vec::new();
+        }
+    };
+
+    defs.iter()
+        .filter_map(|(server_name, def)| {
+            let name = namespaced_server_name(plugin_name, server_name);
+            match def.to_server_config(&name, plugin_dir) {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    tracing::warn!(
+                        plugin = %plugin_name,
+                        server = %server_name,
+                        error = %e,
+                        "invalid plugin MCP server, skipping"
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Names of all servers in the manager that belong to the given plugin.
+pub fn plugin_server_names(plugin_name: &str, mcp: &McpManager) -> Vec<String> {
+    let prefix = namespaced_server_name(plugin_name, "");
+    mcp.server_names()
+        .into_iter()
+        .filter(|n| n.starts_with(&prefix))
+        .map(String::from)
+        .collect()
+}
+
+/// Register and connect a plugin's MCP servers.
+///
+/// Servers whose config is unchanged and already connected are left
+/// running; changed servers are restarted and servers no longer declared
+/// are stopped. Returns the names of servers that were (re)started.
+pub fn sync_plugin_mcp_servers(plugin: &LoadedPlugin, mcp: &mut McpManager) -> Vec<String> {
+    let plugin_name = &plugin.manifest.name;
+    let declared: HashMap<&str, &McpServerConfig> = plugin
+        .mcp_servers
+        .iter()
+        .map(|c| (c.name.as_str(), c))
+        .collect();
+
+    // Stop servers that are no longer declared
+    for name in plugin_server_names(plugin_name, mcp) {
+        if !declared.contains_key(name.as_str()) {
+            tracing::info!(plugin = %plugin_name, server = %name, "stopping removed plugin MCP server");
+            mcp.remove_server(&name);
+        }
+    }
+
+    let mut started = Vec::new();
+    for config in &plugin.mcp_servers {
+        let unchanged = mcp.server_config(&config.name) == Some(config);
+        if unchanged && mcp.is_connected(&config.name) {
+            continue;
+        }
+        if !unchanged {
+            mcp.remove_server(&config.name);
+            mcp.add_server(config.clone());
+        }
+        match mcp.connect_server_by_name(&config.name) {
+            Ok(()) => {
+                tracing::info!(plugin = %plugin_name, server = %config.name, "started plugin MCP server");
+                started.push(config.name.clone());
+            }
+            Err(e) => {
+                tracing::warn!(
+                    plugin = %plugin_name,
+                    server = %config.name,
+                    error = %e,
+                    "failed to start plugin MCP server"
+                );
+            }
+        }
+    }
+
+    started
+}
+
+/// Stop and unregister all MCP servers belonging to a plugin.
+///
+/// Returns the number of servers removed.
+pub fn stop_plugin_mcp_servers(plugin_name: &str, mcp: &mut McpManager) -> usize {
+    let names = plugin_server_names(plugin_name, mcp);
+    for name in &names {
+        tracing::info!(plugin = %plugin_name, server = %name, "stopping plugin MCP server");
+        mcp.remove_server(name);
+    }
+    names.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_mcp::TransportType;
+    use serde_json::json;
+
+    #[test]
+    fn test_namespaced_server_name() {
+        assert_eq!(namespaced_server_name("journal", "db"), "journal:db");
+    }
+
+    #[test]
+    fn test_parse_inline_servers() {
+        let value = json!({
+            "db": { "command": "server", "args": ["--x"] },
+            "web": { "type": "http", "url": "http://localhost:1234/mcp" }
+        });
+        let defs = parse_mcp_servers(&value, Path::new("/p")).unwrap();
+        assert_eq!(defs.len(), 2);
+        assert_eq!(defs["db"].command.as_deref(), Some("server"));
+        assert!(defs["web"].is_http());
+        assert!(!defs["db"].is_http());
+    }
+
+    #[test]
+    fn test_parse_wrapped_servers() {
+        let value = json!({ "mcpServers": { "db": { "command": "server" } } });
+        let defs = parse_mcp_servers(&value, Path::new("/p")).unwrap();
+        assert_eq!(defs.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_servers_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".mcp.json"),
+            r#"{ "mcpServers": { "db": { "command": "server" } } }"#,
+        )
+        .unwrap();
+
+        let defs = parse_mcp_servers(&json!("./.mcp.json"), dir.path()).unwrap();
+        assert!(defs.contains_key("db"));
+    }
+
+    #[test]
+    fn test_parse_servers_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = parse_mcp_servers(&json!("./missing.json"), dir.path()).unwrap_err();
+        assert!(matches!(err, PluginError::Io(_)));
+    }
+
+    #[test]
+    fn test_stdio_config_expands_plugin_root() {
+        let def: PluginMcpServerDef = serde_json::from_value(json!({
+            "command": "${CLAUDE_PLUGIN_ROOT}/bin/server",
+            "args": ["--config", "${CLAUDE_PLUGIN_ROOT}/cfg.toml"],
+            "env": { "DATA": "${CLAUDE_PLUGIN_ROOT}/data" }
+        }))
+        .unwrap();
+
+        let config = def
+            .to_server_config("my-plugin:db", Path::new("/plugins/my-plugin"))
+            .unwrap();
+        assert_eq!(config.name, "my-plugin:db");
+        assert_eq!(config.transport, TransportType::Stdio);
+        assert_eq!(config.command, "/plugins/my-plugin/bin/server");
+        assert_eq!(config.args, vec!["--config", "/plugins/my-plugin/cfg.toml"]);
+        assert!(
+            config
+                .env
+                .contains(&("DATA".to_string(), "/plugins/my-plugin/data".to_string()))
+        );
+        assert!(config.env.contains(&(
+            "CLAUDE_PLUGIN_ROOT".to_string(),
+            "/plugins/my-plugin".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_http_config() {
+        let def: PluginMcpServerDef = serde_json::from_value(json!({
+            "type": "http",
+            "url": "http://localhost:9000/mcp",
+            "headers": { "X-Key": "abc" }
+        }))
+        .unwrap();
+
+        let config = def.to_server_config("p:web", Path::new("/p")).unwrap();
+        assert!(config.is_http());
+        assert_eq!(config.url.as_deref(), Some("http://localhost:9000/mcp"));
+        assert_eq!(
+            config.headers,
+            vec![("X-Key".to_string(), "abc".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_missing_command_is_error() {
+        let def: PluginMcpServerDef = serde_json::from_value(json!({ "args": ["x"] })).unwrap();
+        let err = def.to_server_config("p:s", Path::new("/p")).unwrap_err();
+        assert!(matches!(err, PluginError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_unsupported_transport_is_error() {
+        let def: PluginMcpServerDef =
+            serde_json::from_value(json!({ "type": "sse", "url": "http://x" })).unwrap();
+        assert!(def.to_server_config("p:s", Path::new("/p")).is_err());
+    }
+
+    #[test]
+    fn test_load_skips_invalid_entries() {
+        let value = json!({
+            "good": { "command": "server" },
+            "bad": { "type": "http" }
+        });
+        let configs = load_plugin_mcp_servers("p", &value, Path::new("/p"));
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].name, "p:good");
+    }
+
+    #[test]
+    fn test_stop_plugin_servers_only_removes_own_namespace() {
+        let mut mcp = McpManager::new();
+        mcp.add_server(McpServerConfig::new("alpha:db", "x"));
+        mcp.add_server(McpServerConfig::new("alpha:web", "x"));
+        mcp.add_server(McpServerConfig::new("alphabet:db", "x"));
+        mcp.add_server(McpServerConfig::new("global", "x"));
+
+        assert_eq!(stop_plugin_mcp_servers("alpha", &mut mcp), 2);
+        let mut remaining = mcp.server_names();
+        remaining.sort();
+        assert_eq!(remaining, vec!["alphabet:db", "global"]);
+    }
+}
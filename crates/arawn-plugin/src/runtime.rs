@@ -0,0 +1,386 @@
+//! Live plugin capabilities.
+//!
+//! [`PluginRuntime`] holds the hooks, skills, subagents and prompt fragments
+//! contributed by the enabled plugins. The agent is built once against the
+//! runtime's shared handles; [`PluginRuntime::apply`] swaps their contents
+//! whenever the set of loaded plugins changes, so enabling, disabling or
+//! reloading a plugin takes effect on the next turn without a restart.
+
+use std::sync::{Arc, RwLock};
+
+use arawn_agent::SharedPluginPrompts;
+use arawn_types::{
+    ExpandedSkill, HookDispatch, HookOutcome, SharedHookDispatcher, SharedSkillExpander,
+    SkillExpander,
+};
+
+use crate::agent_spawner::{PluginAgents, SharedPluginAgents};
+use crate::hooks::HookDispatcher;
+use crate::manager::LoadedPlugin;
+use crate::skill::{SkillRegistry, detect_invocation, parse_skill, substitute_args};
+
+/// The capabilities of the currently enabled plugins.
+#[derive(Clone, Default)]
+pub struct PluginRuntime {
+    hooks: Arc<LiveHookDispatcher>,
+    skills: Arc<LiveSkills>,
+    agents: SharedPluginAgents,
+    prompts: SharedPluginPrompts,
+}
+
+impl PluginRuntime {
+    /// Create an empty runtime.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hook dispatcher that always runs the current plugins' hooks.
+    pub fn hook_dispatcher(&self) -> SharedHookDispatcher {
+        self.hooks.clone()
+    }
+
+    /// Skill expander over the current plugins' skills.
+    pub fn skill_expander(&self) -> SharedSkillExpander {
+        self.skills.clone()
+    }
+
+    /// The current plugins' subagents, for a `PluginSubagentSpawner`.
+    pub fn agents(&self) -> SharedPluginAgents {
+        self.agents.clone()
+    }
+
+    /// The current plugins' system prompt fragments.
+    pub fn prompts(&self) -> SharedPluginPrompts {
+        self.prompts.clone()
+    }
+
+    /// Replace all capabilities with those of `plugins`.
+    ///
+    /// Plugins are applied in name order, so when two plugins define an
+    /// agent with the same name the later one wins.
+    pub fn apply(&self, plugins: &[&LoadedPlugin]) {
+        let mut plugins = plugins.to_vec();
+        plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+
+        let mut hooks = HookDispatcher::new();
+        let mut skills = SkillRegistry::new();
+        let mut agents = PluginAgents::default();
+        let mut prompts = Vec::new();
+
+        for plugin in plugins {
+            let name = &plugin.manifest.name;
+            if let Some(ref config) = plugin.hooks_config {
+                hooks.register_from_config(config, &plugin.plugin_dir);
+            }
+            for loaded in &plugin.skill_contents {
+                match parse_skill(&loaded.content, name) {
+                    Ok(skill) => skills.register(skill),
+                    Err(e) => tracing::warn!(
+                        plugin = %name,
+                        skill = %loaded.def.name,
+                        error = %e,
+                        "skipping skill with invalid frontmatter"
+                    ),
+                }
+            }
+            for loaded in &plugin.agent_configs {
+                let agent = loaded.config.agent.name.clone();
+                agents.configs.insert(agent.clone(), loaded.config.clone());
+                agents.sources.insert(agent, name.clone());
+            }
+            if let Some(text) = plugin_prompt(plugin) {
+                prompts.push((name.clone(), text));
+            }
+        }
+
+        tracing::debug!(
+            hooks = hooks.len(),
+            skills = skills.len(),
+            agents = agents.configs.len(),
+            prompts = prompts.len(),
+            "applied plugin capabilities"
+        );
+        self.hooks.replace(hooks);
+        *self
+            .skills
+            .registry
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = skills;
+        *self.agents.write().unwrap_or_else(|e| e.into_inner()) = agents;
+        *self.prompts.write().unwrap_or_else(|e| e.into_inner()) = prompts;
+    }
+
+    /// Number of registered hooks.
+    pub fn hook_count(&self) -> usize {
+        self.hooks.current().len()
+    }
+
+    /// Number of registered skills.
+    pub fn skill_count(&self) -> usize {
+        self.skills
+            .registry
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Number of registered subagents.
+    pub fn agent_count(&self) -> usize {
+        self.agents
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .configs
+            .len()
+    }
+}
+
+/// The system prompt fragment for a plugin: its manifest `prompt`, followed
+/// by the skills and subagents it offers. `None` if it has none of these.
+pub fn plugin_prompt(plugin: &LoadedPlugin) -> Option<String> {
+    let name = &plugin.manifest.name;
+    let mut sections = Vec::new();
+
+    if let Some(text) = plugin
+        .manifest
+        .prompt
+        .as_ref()
+        .and_then(|p| p.system.as_deref())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        sections.push(text.to_string());
+    }
+
+    if !plugin.skill_contents.is_empty() {
+        let mut lines = vec![format!(
+            "Skills (the user runs them as `/{name}:<skill> <args>`):"
+        )];
+        for skill in &plugin.skill_contents {
+            lines.push(list_item(
+                &format!("/{name}:{}", skill.def.name),
+                &skill.def.description,
+            ));
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    if !plugin.agent_configs.is_empty() {
+        let mut lines = vec!["Subagents (use the `delegate` tool):".to_string()];
+        for agent in &plugin.agent_configs {
+            lines.push(list_item(
+                &agent.config.agent.name,
+                &agent.config.agent.description,
+            ));
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    (!sections.is_empty()).then(|| sections.join("\n\n"))
+}
+
+fn list_item(name: &str, description: &str) -> String {
+    if description.is_empty() {
+        format!("- `{name}`")
+    } else {
+        format!("- `{name}` — {description}")
+    }
+}
+
+/// Hook dispatcher whose hooks can be replaced while it is shared.
+#[derive(Debug, Default)]
+struct LiveHookDispatcher {
+    current: RwLock<Arc<HookDispatcher>>,
+}
+
+impl LiveHookDispatcher {
+    fn current(&self) -> Arc<HookDispatcher> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn replace(&self, hooks: HookDispatcher) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(hooks);
+    }
+}
+
+#[async_trait::async_trait]
+impl HookDispatch for LiveHookDispatcher {
+    async fn dispatch_pre_tool_use(
+        &self,
+        tool_name: &str,
+        params: &serde_json::Value,
+    ) -> HookOutcome {
+        self.current()
+            .dispatch_pre_tool_use(tool_name, params)
+            .await
+    }
+
+    async fn dispatch_post_tool_use(
+        &self,
+        tool_name: &str,
+        params: &serde_json::Value,
+        result: &serde_json::Value,
+    ) -> HookOutcome {
+        self.current()
+            .dispatch_post_tool_use(tool_name, params, result)
+            .await
+    }
+
+    async fn dispatch_session_start(&self, session_id: &str) -> HookOutcome {
+        self.current().dispatch_session_start(session_id).await
+    }
+
+    async fn dispatch_session_end(&self, session_id: &str, turn_count: usize) -> HookOutcome {
+        self.current()
+            .dispatch_session_end(session_id, turn_count)
+            .await
+    }
+
+    async fn dispatch_stop(&self, response: &str) -> HookOutcome {
+        self.current().dispatch_stop(response).await
+    }
+
+    async fn dispatch_subagent_started(
+        &self,
+        parent_session_id: &str,
+        subagent_name: &str,
+        task_preview: &str,
+    ) -> HookOutcome {
+        self.current()
+            .dispatch_subagent_started(parent_session_id, subagent_name, task_preview)
+            .await
+    }
+
+    async fn dispatch_subagent_completed(
+        &self,
+        parent_session_id: &str,
+        subagent_name: &str,
+        result_preview: &str,
+        duration_ms: u64,
+        success: bool,
+    ) -> HookOutcome {
+        self.current()
+            .dispatch_subagent_completed(
+                parent_session_id,
+                subagent_name,
+                result_preview,
+                duration_ms,
+                success,
+            )
+            .await
+    }
+
+    fn len(&self) -> usize {
+        self.current().len()
+    }
+}
+
+/// Skill registry that can be replaced while it is shared.
+#[derive(Debug, Default)]
+struct LiveSkills {
+    registry: RwLock<SkillRegistry>,
+}
+
+impl SkillExpander for LiveSkills {
+    fn expand(&self, message: &str) -> Option<ExpandedSkill> {
+        let invocation = detect_invocation(message)?;
+        let registry = self.registry.read().unwrap_or_else(|e| e.into_inner());
+        let skill = registry.get_by_invocation(&invocation)?;
+        match substitute_args(skill, &invocation.raw_args) {
+            Ok(prompt) => Some(ExpandedSkill {
+                name: format!("{}:{}", skill.plugin_name, skill.name),
+                prompt,
+                routing_hint: skill.routing_hint.clone(),
+            }),
+            Err(e) => {
+                tracing::warn!(skill = %skill.name, error = %e, "skill invocation rejected");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::PluginManager;
+    use std::path::Path;
+
+    fn write_plugin(dir: &Path, name: &str) {
+        let root = dir.join(name);
+        std::fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            root.join(".claude-plugin/plugin.json"),
+            format!(
+                r#"{{"name": "{name}", "skills": "./skills/", "agents": "./agents/",
+                    "prompt": {{"system": "Keep a journal."}}}}"#
+            ),
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("skills/entry")).unwrap();
+        std::fs::write(
+            root.join("skills/entry/SKILL.md"),
+            "---\nname: entry\ndescription: Write an entry\nargs:\n  - name: topic\n    required: true\nrouting_hint: fast\n---\nWrite about {topic}.",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("agents")).unwrap();
+        std::fs::write(
+            root.join("agents/scribe.md"),
+            "---\nname: scribe\ndescription: Writes things down\n---\nYou are a scribe.",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("hooks")).unwrap();
+        std::fs::write(
+            root.join("hooks/hooks.json"),
+            r#"{"hooks": {"Stop": [{"hooks": [{"type": "command", "command": "true"}]}]}}"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_apply_swaps_capabilities() {
+        let dir = tempfile::tempdir().unwrap();
+        write_plugin(dir.path(), "journal");
+        let plugin = PluginManager::new(vec![])
+            .load_single(&dir.path().join("journal"))
+            .unwrap();
+
+        let runtime = PluginRuntime::new();
+        let hooks = runtime.hook_dispatcher();
+        let expander = runtime.skill_expander();
+        assert!(hooks.is_empty());
+        assert!(expander.expand("/journal:entry rust").is_none());
+
+        runtime.apply(&[&plugin]);
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(runtime.skill_count(), 1);
+        assert_eq!(runtime.agent_count(), 1);
+        assert_eq!(
+            runtime.agents().read().unwrap().sources["scribe"],
+            "journal"
+        );
+
+        let skill = expander.expand("/journal:entry rust").unwrap();
+        assert_eq!(skill.name, "journal:entry");
+        assert_eq!(skill.prompt, "Write about rust.");
+        assert_eq!(skill.routing_hint.as_deref(), Some("fast"));
+        // Missing required argument and ordinary messages are not expanded
+        assert!(expander.expand("/journal:entry").is_none());
+        assert!(expander.expand("hello").is_none());
+
+        let prompts = runtime.prompts();
+        let prompts = prompts.read().unwrap().clone();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].1.starts_with("Keep a journal."));
+        assert!(prompts[0].1.contains("- `/journal:entry` — Write an entry"));
+        assert!(prompts[0].1.contains("- `scribe` — Writes things down"));
+
+        runtime.apply(&[]);
+        assert!(hooks.is_empty());
+        assert!(expander.expand("/journal:entry rust").is_none());
+        assert_eq!(runtime.agent_count(), 0);
+        assert!(runtime.prompts().read().unwrap().is_empty());
+    }
+}
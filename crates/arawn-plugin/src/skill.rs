@@ -18,6 +18,7 @@
 //!   - name: pr_number
 //!     description: PR number to review
 //!     required: true
+//! routing_hint: quality
 //! ---
 //!
 //! # PR Review
@@ -44,6 +45,8 @@ pub struct Skill {
     pub body: String,
     /// Which plugin this skill came from.
     pub plugin_name: String,
+    /// Optional LLM routing hint applied while the skill runs.
+    pub routing_hint: Option<String>,
 }
 
 /// Result of parsing a `/skill-name args` or `/plugin:skill args` invocation from a user message.
@@ -67,6 +70,8 @@ struct SkillFrontmatter {
     uses_tools: Vec<String>,
     #[serde(default)]
     args: Vec<SkillArg>,
+    #[serde(default)]
+    routing_hint: Option<String>,
 }
 
 /// Parse a skill from its markdown content.
@@ -95,6 +100,7 @@ pub fn parse_skill(content: &str, plugin_name: &str) -> Result<Skill> {
         args: frontmatter.args,
         body,
         plugin_name: plugin_name.to_string(),
+        routing_hint: frontmatter.routing_hint,
     })
 }
 
@@ -374,6 +380,14 @@ Mood: {mood}
         assert!(skill.args[1].required);
         assert!(skill.body.contains("# Journal Entry for {date}"));
         assert_eq!(skill.plugin_name, "journal");
+        assert!(skill.routing_hint.is_none());
+    }
+
+    #[test]
+    fn test_parse_skill_routing_hint() {
+        let content = "---\nname: summarize\nrouting_hint: fast\n---\nSummarize.";
+        let skill = parse_skill(content, "notes").unwrap();
+        assert_eq!(skill.routing_hint.as_deref(), Some("fast"));
     }
 
     #[test]
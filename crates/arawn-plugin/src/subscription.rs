@@ -24,8 +24,12 @@
 //!   ]
 //! }
 //! ```
+//!
+//! Resolved commits and content hashes are pinned in `arawn-plugins.lock`
+//! (see [`crate::lockfile`]).
 
-use arawn_config::{PluginSource, PluginSubscription};
+use crate::lockfile::{self, LOCKFILE_NAME, LockCheck, LockedPlugin, PluginLockfile};
+use arawn_config::{PluginLockMode, PluginSource, PluginSubscription};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -148,6 +152,22 @@ pub struct SubscriptionManager {
     project_config_path: Option<PathBuf>,
     /// Path to plugin cache directory.
     cache_dir: PathBuf,
+    /// Pinned commits and content hashes.
+    lockfile: PluginLockfile,
+    /// Path to the lockfile (project dir if given, else global config dir).
+    lockfile_path: PathBuf,
+    /// How the lockfile is enforced.
+    lock_mode: PluginLockMode,
+}
+
+/// How subscriptions are synced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    /// Clone or pull the latest commit of each subscription's ref.
+    Latest,
+    /// Check out exactly the commits recorded in the lockfile and verify
+    /// their content. Subscriptions without a lock entry fail.
+    Locked,
 }
 
 impl SubscriptionManager {
@@ -180,6 +200,11 @@ impl SubscriptionManager {
             (None, RuntimePluginsConfig::default())
         };
 
+        let lockfile_path = project_dir
+            .map(|dir| dir.join(LOCKFILE_NAME))
+            .unwrap_or_else(|| config_dir.join(LOCKFILE_NAME));
+        let lockfile = PluginLockfile::load(&lockfile_path)?;
+
         Ok(Self {
             config_subscriptions,
             global_config,
@@ -187,14 +212,54 @@ impl SubscriptionManager {
             global_config_path,
             project_config_path,
             cache_dir,
+            lockfile,
+            lockfile_path,
+            lock_mode: PluginLockMode::default(),
         })
     }
 
+    /// Set the lockfile enforcement policy.
+    pub fn with_lock_mode(mut self, mode: PluginLockMode) -> Self {
+        self.lock_mode = mode;
+        self
+    }
+
+    /// Use a lockfile at a specific path instead of the default location.
+    pub fn with_lockfile_path(mut self, path: PathBuf) -> crate::Result<Self> {
+        self.lockfile = PluginLockfile::load(&path)?;
+        self.lockfile_path = path;
+        Ok(self)
+    }
+
+    /// Use a global runtime config at a specific path instead of
+    /// `~/.config/arawn/plugins.json`.
+    pub fn with_global_config_path(mut self, path: PathBuf) -> crate::Result<Self> {
+        self.global_config = RuntimePluginsConfig::load(&path)?;
+        self.global_config_path = path;
+        Ok(self)
+    }
+
+    /// Use a specific cache directory for cloned plugins.
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
     /// Get all active subscriptions, merged from all sources.
     ///
     /// Subscriptions are deduplicated by ID, with later sources taking
     /// precedence (project > global > config).
     pub fn all_subscriptions(&self) -> Vec<PluginSubscription> {
+        self.subscriptions()
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(sub, _)| sub)
+            .collect()
+    }
+
+    /// Get every subscription, including disabled ones, with its effective
+    /// enabled state.
+    pub fn subscriptions(&self) -> Vec<(PluginSubscription, bool)> {
         let mut seen = std::collections::HashSet::new();
         let mut result = Vec::new();
 
@@ -216,21 +281,70 @@ impl SubscriptionManager {
             }
         }
 
-        // Filter out disabled subscriptions
-        result.retain(|sub| {
-            let id = sub.id();
-            // Check project config first, then global
-            if let Some(enabled) = self.project_config.is_enabled(&id) {
-                return enabled;
-            }
-            if let Some(enabled) = self.global_config.is_enabled(&id) {
-                return enabled;
-            }
-            // Default to the subscription's own enabled flag
-            sub.enabled
-        });
-
         result
+            .into_iter()
+            .map(|sub| {
+                // Default to the subscription's own enabled flag
+                let enabled = self.is_enabled(&sub.id()).unwrap_or(sub.enabled);
+                (sub, enabled)
+            })
+            .collect()
+    }
+
+    /// Look up a plugin's enabled state, checking the project config first,
+    /// then the global one.
+    ///
+    /// Keys are subscription IDs or `name@local` for plugins found in plugin
+    /// directories. Returns `None` if neither config mentions the plugin.
+    pub fn is_enabled(&self, plugin_id: &str) -> Option<bool> {
+        self.project_config
+            .is_enabled(plugin_id)
+            .or_else(|| self.global_config.is_enabled(plugin_id))
+    }
+
+    /// Enable or disable a plugin and save the change.
+    ///
+    /// The state is written to the project config if that config already
+    /// mentions the plugin (as a subscription or an enabled entry), so a
+    /// project override keeps applying; otherwise to the global config.
+    pub fn persist_enabled(&mut self, plugin_id: &str, enabled: bool) -> crate::Result<()> {
+        let in_project = self.project_config_path.is_some()
+            && (self.project_config.is_enabled(plugin_id).is_some()
+                || self
+                    .project_config
+                    .subscriptions
+                    .iter()
+                    .any(|s| s.id() == plugin_id));
+        if in_project {
+            self.project_config.set_enabled(plugin_id, enabled);
+            self.save_project_config()
+        } else {
+            self.global_config.set_enabled(plugin_id, enabled);
+            self.save_global_config()
+        }
+    }
+
+    /// Remove a runtime-added subscription from the global and project
+    /// configs, forget its enabled state and lock entry, and save.
+    ///
+    /// Returns `false` if no runtime config had the subscription (for
+    /// example because it is declared in `arawn.toml`).
+    pub fn remove_subscription(&mut self, subscription_id: &str) -> crate::Result<bool> {
+        let mut removed = false;
+        for config in [&mut self.global_config, &mut self.project_config] {
+            let before = config.subscriptions.len();
+            config.remove_subscription(subscription_id);
+            removed |= config.subscriptions.len() != before;
+            config.enabled_plugins.remove(subscription_id);
+        }
+        if !removed {
+            return Ok(false);
+        }
+        self.unlock_subscription(subscription_id);
+        self.save_global_config()?;
+        self.save_project_config()?;
+        self.save_lockfile()?;
+        Ok(true)
     }
 
     /// Get the cache directory for a subscription.
@@ -258,6 +372,11 @@ impl SubscriptionManager {
         &mut self.project_config
     }
 
+    /// Path of the project runtime config, if a project directory was given.
+    pub fn project_config_path(&self) -> Option<&Path> {
+        self.project_config_path.as_deref()
+    }
+
     /// Save the global runtime config.
     pub fn save_global_config(&self) -> crate::Result<()> {
         self.global_config.save(&self.global_config_path)
@@ -297,6 +416,110 @@ impl SubscriptionManager {
         &self.cache_dir
     }
 
+    /// Get the lockfile enforcement policy.
+    pub fn lock_mode(&self) -> PluginLockMode {
+        self.lock_mode
+    }
+
+    /// Get the plugin lockfile.
+    pub fn lockfile(&self) -> &PluginLockfile {
+        &self.lockfile
+    }
+
+    /// Get the path the lockfile is read from and saved to.
+    pub fn lockfile_path(&self) -> &Path {
+        &self.lockfile_path
+    }
+
+    /// Save the plugin lockfile.
+    pub fn save_lockfile(&self) -> crate::Result<()> {
+        self.lockfile.save(&self.lockfile_path)
+    }
+
+    /// Record the current checkout of a subscription in the lockfile.
+    ///
+    /// Returns `Ok(None)` for local subscriptions, which are never locked.
+    /// The lockfile must be saved with [`save_lockfile`](Self::save_lockfile).
+    pub fn lock_subscription(
+        &mut self,
+        subscription: &PluginSubscription,
+    ) -> Result<Option<LockedPlugin>, String> {
+        if subscription.source == PluginSource::Local {
+            return Ok(None);
+        }
+        let dir = self.cache_dir_for(subscription);
+        let commit = GitOps::current_commit(&dir)
+            .ok_or_else(|| format!("cannot resolve commit in {}", dir.display()))?;
+        let content_hash = lockfile::content_hash(&dir)
+            .map_err(|e| format!("cannot hash {}: {}", dir.display(), e))?;
+        let entry = LockedPlugin {
+            source: subscription.clone_url().unwrap_or_default(),
+            git_ref: subscription.effective_ref().to_string(),
+            commit,
+            content_hash,
+        };
+        self.lockfile.insert(subscription.id(), entry.clone());
+        Ok(Some(entry))
+    }
+
+    /// Remove a subscription from the lockfile. Returns true if it was locked.
+    pub fn unlock_subscription(&mut self, subscription_id: &str) -> bool {
+        self.lockfile.remove(subscription_id)
+    }
+
+    /// Check a subscription's checkout against its lock entry.
+    pub fn verify_subscription(&self, subscription: &PluginSubscription) -> LockCheck {
+        match self.lockfile.get(&subscription.id()) {
+            Some(locked) if subscription.source != PluginSource::Local => {
+                verify_checkout(&self.cache_dir_for(subscription), locked)
+            }
+            _ => LockCheck::Unlocked,
+        }
+    }
+
+    /// Check every active subscription against the lockfile.
+    pub fn verify_all(&self) -> Vec<(String, LockCheck)> {
+        self.all_subscriptions()
+            .iter()
+            .map(|sub| (sub.id(), self.verify_subscription(sub)))
+            .collect()
+    }
+
+    /// Sync mode implied by the lock policy (used for startup auto-update).
+    pub fn default_sync_mode(&self) -> SyncMode {
+        match self.lock_mode {
+            PluginLockMode::Strict => SyncMode::Locked,
+            PluginLockMode::Warn | PluginLockMode::Off => SyncMode::Latest,
+        }
+    }
+
+    /// Compare each locked subscription with the latest commit of its ref.
+    ///
+    /// Queries remotes with `git ls-remote`; nothing on disk is changed.
+    pub fn outdated(&self) -> Vec<OutdatedPlugin> {
+        self.all_subscriptions()
+            .into_iter()
+            .filter(|sub| sub.source != PluginSource::Local)
+            .map(|sub| {
+                let locked = self.lockfile.get(&sub.id()).map(|l| l.commit.clone());
+                let (latest, error) = match sub.clone_url() {
+                    Some(url) => match GitOps::remote_commit(&url, sub.effective_ref()) {
+                        Ok(commit) => (Some(commit), None),
+                        Err(e) => (None, Some(e)),
+                    },
+                    None => (None, Some("No clone URL available".to_string())),
+                };
+                OutdatedPlugin {
+                    subscription_id: sub.id(),
+                    git_ref: sub.effective_ref().to_string(),
+                    locked,
+                    latest,
+                    error,
+                }
+            })
+            .collect()
+    }
+
     /// Check if auto-update is disabled via environment variable.
     ///
     /// Returns true if `ARAWN_DISABLE_PLUGIN_UPDATES=1` is set.
@@ -321,7 +544,15 @@ impl SubscriptionManager {
     ///
     /// Returns a list of sync results for each subscription.
     /// Uses tokio::task::spawn_blocking for git operations.
+    ///
+    /// The sync mode follows the lock policy (see [`default_sync_mode`](Self::default_sync_mode)).
     pub async fn sync_all_async(&self) -> Vec<SyncResult> {
+        self.sync_all_with_mode_async(self.default_sync_mode())
+            .await
+    }
+
+    /// Sync all subscriptions in parallel using an explicit sync mode.
+    pub async fn sync_all_with_mode_async(&self, mode: SyncMode) -> Vec<SyncResult> {
         use tokio::task;
         use tokio::time::{Duration, timeout};
 
@@ -347,6 +578,7 @@ impl SubscriptionManager {
         for sub in subscriptions {
             let cache_dir = self.cache_dir.clone();
             let sub_id_for_handle = sub.id();
+            let locked = self.lockfile.get(&sub.id()).cloned();
             let handle = task::spawn(async move {
                 let sub_id = sub.id();
                 let dest = cache_dir.join(&sub_id);
@@ -366,6 +598,9 @@ impl SubscriptionManager {
                 let sub_id_for_panic = sub_id.clone();
                 let sync_result = task::spawn_blocking(move || {
                     let sub_id = sub.id();
+                    if mode == SyncMode::Locked {
+                        return sync_to_lock(&sub, &dest_clone, locked.as_ref());
+                    }
                     if dest_clone.join(".git").exists() {
                         // Update existing clone
                         match GitOps::pull(&dest_clone, sub.effective_ref()) {
@@ -471,6 +706,12 @@ impl SubscriptionManager {
         results
     }
 
+    /// Sync a single subscription to its locked commit and verify its content.
+    pub fn sync_locked(&self, subscription: &PluginSubscription) -> SyncResult {
+        let dest = self.cache_dir_for(subscription);
+        sync_to_lock(subscription, &dest, self.lockfile.get(&subscription.id()))
+    }
+
     /// Sync a single subscription (clone or update).
     pub fn sync_subscription(&self, subscription: &PluginSubscription) -> SyncResult {
         let dest = self.cache_dir_for(subscription);
@@ -553,14 +794,198 @@ impl SubscriptionManager {
     }
 
     /// Get all plugin directories (synced subscriptions + local paths).
+    ///
+    /// In strict lock mode, plugins whose checkout does not match the
+    /// lockfile are left out.
     pub fn plugin_dirs(&self) -> Vec<PathBuf> {
-        self.all_subscriptions()
+        self.loadable_dirs(&self.all_subscriptions())
+    }
+
+    /// Like [`plugin_dirs`](Self::plugin_dirs), but including disabled
+    /// subscriptions, so they can be watched and enabled later.
+    pub fn known_plugin_dirs(&self) -> Vec<PathBuf> {
+        let subs: Vec<PluginSubscription> = self
+            .subscriptions()
+            .into_iter()
+            .map(|(sub, _)| sub)
+            .collect();
+        self.loadable_dirs(&subs)
+    }
+
+    fn loadable_dirs(&self, subscriptions: &[PluginSubscription]) -> Vec<PathBuf> {
+        subscriptions
             .iter()
+            .filter(|sub| {
+                if self.lock_mode != PluginLockMode::Strict {
+                    return true;
+                }
+                let check = self.verify_subscription(sub);
+                if !check.is_ok() {
+                    tracing::warn!(
+                        subscription = %sub.id(),
+                        "refusing to load plugin: {}",
+                        check
+                    );
+                }
+                check.is_ok()
+            })
             .filter_map(|sub| self.plugin_dir_for(sub))
             .collect()
     }
 }
 
+/// Parse a plugin source as typed by a user into a subscription.
+///
+/// Paths (absolute, or starting with `.` or `~`) become local
+/// subscriptions, URLs and `git@` remotes become URL subscriptions, and
+/// anything else is GitHub `owner/repo` shorthand.
+pub fn parse_source(source: &str, git_ref: Option<String>) -> PluginSubscription {
+    let sub = if source.contains("://") || source.starts_with("git@") {
+        PluginSubscription::url(source)
+    } else if source.starts_with('/') || source.starts_with('.') || source.starts_with('~') {
+        let path = match source.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest))
+                .unwrap_or_else(|| PathBuf::from(source)),
+            None => PathBuf::from(source),
+        };
+        PluginSubscription::local(path)
+    } else {
+        PluginSubscription::github(source)
+    };
+    match git_ref {
+        Some(r) => sub.with_ref(r),
+        None => sub,
+    }
+}
+
+/// Check a checkout against its lock entry.
+fn verify_checkout(dir: &Path, locked: &LockedPlugin) -> LockCheck {
+    if !dir.exists() {
+        return LockCheck::Unavailable {
+            reason: format!("{} does not exist", dir.display()),
+        };
+    }
+    let Some(actual) = GitOps::current_commit(dir) else {
+        return LockCheck::Unavailable {
+            reason: format!("cannot resolve commit in {}", dir.display()),
+        };
+    };
+    if actual != locked.commit {
+        return LockCheck::CommitMismatch {
+            locked: locked.commit.clone(),
+            actual,
+        };
+    }
+    match lockfile::content_hash(dir) {
+        Ok(hash) if hash == locked.content_hash => LockCheck::Verified,
+        Ok(hash) => LockCheck::ContentMismatch {
+            locked: locked.content_hash.clone(),
+            actual: hash,
+        },
+        Err(e) => LockCheck::Unavailable {
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// Clone (if needed) and check out a subscription's locked commit, then
+/// verify the content hash.
+fn sync_to_lock(
+    subscription: &PluginSubscription,
+    dest: &Path,
+    locked: Option<&LockedPlugin>,
+) -> SyncResult {
+    let sub_id = subscription.id();
+
+    if subscription.source == PluginSource::Local {
+        return SyncResult {
+            subscription_id: sub_id,
+            action: SyncAction::Skipped,
+            path: subscription.path.clone(),
+            error: None,
+        };
+    }
+
+    let Some(locked) = locked else {
+        return SyncResult {
+            subscription_id: sub_id,
+            action: SyncAction::IntegrityFailed,
+            path: None,
+            error: Some(format!("not in {}", LOCKFILE_NAME)),
+        };
+    };
+
+    let action = if dest.join(".git").exists() {
+        SyncAction::Updated
+    } else {
+        let url = subscription
+            .clone_url()
+            .unwrap_or_else(|| locked.source.clone());
+        if let Err(e) = GitOps::clone(&url, dest, &locked.git_ref) {
+            return SyncResult {
+                subscription_id: sub_id,
+                action: SyncAction::CloneFailed,
+                path: None,
+                error: Some(e),
+            };
+        }
+        SyncAction::Cloned
+    };
+
+    if GitOps::current_commit(dest).as_deref() != Some(locked.commit.as_str())
+        && let Err(e) = GitOps::checkout_commit(dest, &locked.commit)
+    {
+        return SyncResult {
+            subscription_id: sub_id,
+            action: SyncAction::UpdateFailed,
+            path: Some(dest.to_path_buf()),
+            error: Some(e),
+        };
+    }
+
+    match verify_checkout(dest, locked) {
+        LockCheck::Verified => SyncResult {
+            subscription_id: sub_id,
+            action,
+            path: Some(dest.to_path_buf()),
+            error: None,
+        },
+        check => SyncResult {
+            subscription_id: sub_id,
+            action: SyncAction::IntegrityFailed,
+            path: Some(dest.to_path_buf()),
+            error: Some(check.to_string()),
+        },
+    }
+}
+
+/// A subscription's locked commit compared with the latest remote commit.
+#[derive(Debug, Clone)]
+pub struct OutdatedPlugin {
+    /// Subscription ID.
+    pub subscription_id: String,
+    /// The ref being tracked.
+    pub git_ref: String,
+    /// Commit recorded in the lockfile, if any.
+    pub locked: Option<String>,
+    /// Latest commit of the ref on the remote, if it could be resolved.
+    pub latest: Option<String>,
+    /// Error querying the remote.
+    pub error: Option<String>,
+}
+
+impl OutdatedPlugin {
+    /// Whether a newer commit is available than the locked one.
+    pub fn is_outdated(&self) -> bool {
+        match (&self.locked, &self.latest) {
+            (Some(locked), Some(latest)) => locked != latest,
+            (None, Some(_)) => true,
+            _ => false,
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Git Operations
 // ─────────────────────────────────────────────────────────────────────────────
@@ -669,6 +1094,66 @@ impl GitOps {
         Ok(())
     }
 
+    /// Check out a specific commit (detached HEAD).
+    ///
+    /// Shallow clones may not contain the commit, so it is fetched first if
+    /// missing.
+    pub fn checkout_commit(repo_dir: &Path, commit: &str) -> Result<(), String> {
+        let has_commit = Command::new("git")
+            .args(["cat-file", "-e", &format!("{}^{{commit}}", commit)])
+            .current_dir(repo_dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+
+        if !has_commit {
+            let fetch_output = Command::new("git")
+                .args(["fetch", "--depth", "1", "origin", commit])
+                .current_dir(repo_dir)
+                .output()
+                .map_err(|e| format!("Failed to execute git fetch: {}", e))?;
+            if !fetch_output.status.success() {
+                let stderr = String::from_utf8_lossy(&fetch_output.stderr);
+                return Err(format!("git fetch {} failed: {}", commit, stderr.trim()));
+            }
+        }
+
+        let output = Command::new("git")
+            .args(["checkout", "--detach", commit])
+            .current_dir(repo_dir)
+            .output()
+            .map_err(|e| format!("Failed to execute git checkout: {}", e))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(format!("git checkout failed: {}", stderr.trim()))
+        }
+    }
+
+    /// Resolve the commit a ref points to on a remote, without cloning.
+    ///
+    /// Annotated tags are peeled to the commit they reference.
+    pub fn remote_commit(url: &str, git_ref: &str) -> Result<String, String> {
+        if git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(git_ref.to_string());
+        }
+
+        let output = Command::new("git")
+            .args(["ls-remote", url, git_ref, &format!("{}^{{}}", git_ref)])
+            .output()
+            .map_err(|e| format!("Failed to execute git ls-remote: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git ls-remote failed: {}", stderr.trim()));
+        }
+
+        parse_ls_remote(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| format!("ref '{}' not found on remote", git_ref))
+    }
+
     /// Check if git is available on the system.
     pub fn is_available() -> bool {
         Command::new("git")
@@ -702,6 +1187,22 @@ impl GitOps {
     }
 }
 
+/// Pick the commit from `git ls-remote` output, preferring peeled tags.
+fn parse_ls_remote(output: &str) -> Option<String> {
+    let mut first = None;
+    for line in output.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(sha), Some(name)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if name.ends_with("^{}") {
+            return Some(sha.to_string());
+        }
+        first.get_or_insert_with(|| sha.to_string());
+    }
+    first
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Sync Results
 // ─────────────────────────────────────────────────────────────────────────────
@@ -732,7 +1233,7 @@ impl SyncResult {
     pub fn is_failure(&self) -> bool {
         matches!(
             self.action,
-            SyncAction::CloneFailed | SyncAction::UpdateFailed
+            SyncAction::CloneFailed | SyncAction::UpdateFailed | SyncAction::IntegrityFailed
         )
     }
 }
@@ -750,6 +1251,8 @@ pub enum SyncAction {
     CloneFailed,
     /// Update operation failed.
     UpdateFailed,
+    /// Checkout does not match the lockfile.
+    IntegrityFailed,
 }
 
 impl std::fmt::Display for SyncAction {
@@ -760,6 +1263,7 @@ impl std::fmt::Display for SyncAction {
             SyncAction::Skipped => write!(f, "skipped"),
             SyncAction::CloneFailed => write!(f, "clone failed"),
             SyncAction::UpdateFailed => write!(f, "update failed"),
+            SyncAction::IntegrityFailed => write!(f, "integrity check failed"),
         }
     }
 }
@@ -980,6 +1484,108 @@ mod tests {
         assert_eq!(all_subs[0].repo.as_deref(), Some("another/plugin"));
     }
 
+    #[test]
+    fn test_subscriptions_include_disabled() {
+        let tmp = TempDir::new().unwrap();
+        let mut global = RuntimePluginsConfig::default();
+        global.set_enabled("github/off-plugin", false);
+        global.save(&tmp.path().join("plugins.json")).unwrap();
+
+        let config_subs = vec![
+            PluginSubscription::github("off/plugin"),
+            PluginSubscription::github("on/plugin"),
+        ];
+        let manager = SubscriptionManager::new(config_subs, None)
+            .unwrap()
+            .with_global_config_path(tmp.path().join("plugins.json"))
+            .unwrap();
+
+        let subs = manager.subscriptions();
+        assert_eq!(subs.len(), 2);
+        assert!(!subs[0].1);
+        assert!(subs[1].1);
+        assert_eq!(manager.is_enabled("github/off-plugin"), Some(false));
+        assert_eq!(manager.is_enabled("github/on-plugin"), None);
+    }
+
+    #[test]
+    fn test_persist_enabled_prefers_project_entry() {
+        let tmp = TempDir::new().unwrap();
+        let project_dir = tmp.path().join("project");
+        std::fs::create_dir_all(project_dir.join(".arawn")).unwrap();
+        let mut project = RuntimePluginsConfig::default();
+        project.add_subscription(PluginSubscription::github("project/plugin"));
+        project
+            .save(&project_dir.join(".arawn/plugins.json"))
+            .unwrap();
+
+        let global_path = tmp.path().join("plugins.json");
+        let mut manager = SubscriptionManager::new(Vec::new(), Some(&project_dir))
+            .unwrap()
+            .with_global_config_path(global_path.clone())
+            .unwrap();
+
+        manager
+            .persist_enabled("github/project-plugin", false)
+            .unwrap();
+        manager.persist_enabled("journal@local", false).unwrap();
+
+        let project = RuntimePluginsConfig::load(&project_dir.join(".arawn/plugins.json")).unwrap();
+        assert_eq!(project.is_enabled("github/project-plugin"), Some(false));
+        assert_eq!(project.is_enabled("journal@local"), None);
+        let global = RuntimePluginsConfig::load(&global_path).unwrap();
+        assert_eq!(global.is_enabled("journal@local"), Some(false));
+        assert!(manager.all_subscriptions().is_empty());
+    }
+
+    #[test]
+    fn test_remove_subscription_only_runtime_added() {
+        let tmp = TempDir::new().unwrap();
+        let global_path = tmp.path().join("plugins.json");
+        let mut global = RuntimePluginsConfig::default();
+        global.add_subscription(PluginSubscription::github("runtime/plugin"));
+        global.set_enabled("github/runtime-plugin", true);
+        global.save(&global_path).unwrap();
+
+        let mut manager =
+            SubscriptionManager::new(vec![PluginSubscription::github("config/plugin")], None)
+                .unwrap()
+                .with_global_config_path(global_path.clone())
+                .unwrap()
+                .with_lockfile_path(tmp.path().join(LOCKFILE_NAME))
+                .unwrap();
+
+        assert!(!manager.remove_subscription("github/config-plugin").unwrap());
+        assert!(
+            manager
+                .remove_subscription("github/runtime-plugin")
+                .unwrap()
+        );
+
+        let global = RuntimePluginsConfig::load(&global_path).unwrap();
+        assert!(global.subscriptions.is_empty());
+        assert!(global.enabled_plugins.is_empty());
+        assert_eq!(manager.all_subscriptions().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_source() {
+        let sub = parse_source("owner/repo", Some("v1".to_string()));
+        assert_eq!(sub.source, PluginSource::GitHub);
+        assert_eq!(sub.repo.as_deref(), Some("owner/repo"));
+        assert_eq!(sub.effective_ref(), "v1");
+
+        let sub = parse_source("https://example.com/plugin.git", None);
+        assert_eq!(sub.source, PluginSource::Url);
+        let sub = parse_source("git@example.com:me/plugin.git", None);
+        assert_eq!(sub.source, PluginSource::Url);
+
+        let sub = parse_source("/opt/plugins/journal", None);
+        assert_eq!(sub.source, PluginSource::Local);
+        assert_eq!(sub.path, Some(PathBuf::from("/opt/plugins/journal")));
+        assert_eq!(parse_source("./journal", None).source, PluginSource::Local);
+    }
+
     // ── Git Operations Tests ─────────────────────────────────────────────
 
     #[test]
@@ -1787,4 +2393,180 @@ mod tests {
         assert!(results[0].is_success());
         assert!(results[0].path.is_some());
     }
+
+    /// Run git in `dir`, panicking on failure.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Create a local upstream repo with one commit and return (dir, HEAD).
+    fn local_upstream(tmp: &TempDir) -> (PathBuf, String) {
+        let upstream = tmp.path().join("upstream");
+        std::fs::create_dir_all(upstream.join("skills")).unwrap();
+        std::fs::write(upstream.join("skills/hello.md"), "v1").unwrap();
+        git(&upstream, &["init", "-q", "-b", "main"]);
+        git(&upstream, &["add", "."]);
+        git(&upstream, &["commit", "-q", "-m", "v1"]);
+        let head = git(&upstream, &["rev-parse", "HEAD"]);
+        (upstream, head)
+    }
+
+    fn commit_change(upstream: &Path, content: &str) -> String {
+        std::fs::write(upstream.join("skills/hello.md"), content).unwrap();
+        git(upstream, &["commit", "-q", "-am", content]);
+        git(upstream, &["rev-parse", "HEAD"])
+    }
+
+    fn locked_manager(tmp: &TempDir, sub: &PluginSubscription) -> SubscriptionManager {
+        SubscriptionManager::new(vec![sub.clone()], None)
+            .unwrap()
+            .with_cache_dir(tmp.path().join("cache"))
+            .with_lockfile_path(tmp.path().join(LOCKFILE_NAME))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_ls_remote_prefers_peeled_tag() {
+        let output = "aaaa\trefs/tags/v1\nbbbb\trefs/tags/v1^{}\n";
+        assert_eq!(parse_ls_remote(output), Some("bbbb".to_string()));
+        assert_eq!(
+            parse_ls_remote("cccc\trefs/heads/main\n"),
+            Some("cccc".to_string())
+        );
+        assert_eq!(parse_ls_remote(""), None);
+    }
+
+    #[test]
+    fn test_lock_and_verify_subscription() {
+        if !GitOps::is_available() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let (upstream, head) = local_upstream(&tmp);
+        let sub =
+            PluginSubscription::url(format!("file://{}", upstream.display())).with_ref("main");
+
+        let mut manager = locked_manager(&tmp, &sub);
+        assert_eq!(manager.verify_subscription(&sub), LockCheck::Unlocked);
+
+        assert!(manager.sync_subscription(&sub).is_success());
+        let locked = manager.lock_subscription(&sub).unwrap().unwrap();
+        assert_eq!(locked.commit, head);
+        assert_eq!(locked.git_ref, "main");
+        manager.save_lockfile().unwrap();
+
+        let reloaded = locked_manager(&tmp, &sub);
+        assert_eq!(reloaded.verify_subscription(&sub), LockCheck::Verified);
+
+        // Tampering with the checkout is detected
+        let dir = reloaded.cache_dir_for(&sub);
+        std::fs::write(dir.join("skills/hello.md"), "tampered").unwrap();
+        assert!(matches!(
+            reloaded.verify_subscription(&sub),
+            LockCheck::ContentMismatch { .. }
+        ));
+
+        // Strict mode refuses to load the mismatched plugin
+        let strict = locked_manager(&tmp, &sub).with_lock_mode(PluginLockMode::Strict);
+        assert!(strict.plugin_dirs().is_empty());
+        let warn = locked_manager(&tmp, &sub).with_lock_mode(PluginLockMode::Warn);
+        assert_eq!(warn.plugin_dirs().len(), 1);
+    }
+
+    #[test]
+    fn test_update_detected_as_commit_mismatch_and_outdated() {
+        if !GitOps::is_available() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let (upstream, head) = local_upstream(&tmp);
+        let sub =
+            PluginSubscription::url(format!("file://{}", upstream.display())).with_ref("main");
+
+        let mut manager = locked_manager(&tmp, &sub);
+        manager.sync_subscription(&sub);
+        manager.lock_subscription(&sub).unwrap();
+
+        let new_head = commit_change(&upstream, "v2");
+        let report = manager.outdated();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].locked.as_deref(), Some(head.as_str()));
+        assert_eq!(report[0].latest.as_deref(), Some(new_head.as_str()));
+        assert!(report[0].is_outdated());
+
+        assert_eq!(manager.sync_subscription(&sub).action, SyncAction::Updated);
+        assert_eq!(
+            manager.verify_subscription(&sub),
+            LockCheck::CommitMismatch {
+                locked: head,
+                actual: new_head,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sync_locked_checks_out_pinned_commit() {
+        if !GitOps::is_available() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let (upstream, head) = local_upstream(&tmp);
+        let sub =
+            PluginSubscription::url(format!("file://{}", upstream.display())).with_ref("main");
+
+        let mut manager = locked_manager(&tmp, &sub);
+        manager.sync_subscription(&sub);
+        manager.lock_subscription(&sub).unwrap();
+
+        // Upstream moves on and the cache is wiped; a locked sync restores
+        // the pinned commit rather than the latest.
+        commit_change(&upstream, "v2");
+        std::fs::remove_dir_all(manager.cache_dir_for(&sub)).unwrap();
+
+        let result = manager.sync_locked(&sub);
+        assert_eq!(result.action, SyncAction::Cloned, "{:?}", result.error);
+        let dir = manager.cache_dir_for(&sub);
+        assert_eq!(GitOps::current_commit(&dir).as_deref(), Some(head.as_str()));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("skills/hello.md")).unwrap(),
+            "v1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_locked_without_entry_fails() {
+        // SAFETY: Test context
+        unsafe { std::env::remove_var("ARAWN_DISABLE_PLUGIN_UPDATES") };
+
+        let tmp = TempDir::new().unwrap();
+        let sub = PluginSubscription::url("file:///nonexistent/plugin.git");
+        let manager = locked_manager(&tmp, &sub);
+
+        let results = manager.sync_all_with_mode_async(SyncMode::Locked).await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].action, SyncAction::IntegrityFailed);
+        assert!(results[0].is_failure());
+    }
+
+    #[test]
+    fn test_default_sync_mode_follows_lock_mode() {
+        let tmp = TempDir::new().unwrap();
+        let sub = PluginSubscription::github("author/repo");
+        let manager = locked_manager(&tmp, &sub);
+        assert_eq!(manager.default_sync_mode(), SyncMode::Latest);
+        let manager = manager.with_lock_mode(PluginLockMode::Strict);
+        assert_eq!(manager.default_sync_mode(), SyncMode::Locked);
+    }
 }
@@ -102,6 +102,10 @@ pub struct AgentSection {
     /// Optional model override (e.g., "claude-sonnet").
     #[serde(default)]
     pub model: Option<String>,
+    /// Routing hint for the LLM router (a profile name such as "fast", or a
+    /// hint matched by `[routing]` rules).
+    #[serde(default)]
+    pub routing_hint: Option<String>,
     /// System prompt configuration.
     #[serde(default)]
     pub system_prompt: Option<AgentSystemPrompt>,
@@ -8,11 +8,15 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use arawn_mcp::McpManager;
 use notify_debouncer_mini::{DebouncedEventKind, new_debouncer};
 use tokio::sync::{RwLock, mpsc};
 
 use crate::manager::{LoadedPlugin, MANIFEST_PATH, PluginManager};
 
+/// Shared MCP manager that plugin-declared servers are registered with.
+pub type SharedMcpManager = Arc<RwLock<McpManager>>;
+
 /// Event emitted when a plugin is reloaded, added, or removed.
 #[derive(Debug, Clone)]
 pub enum PluginEvent {
@@ -42,6 +46,11 @@ impl PluginState {
         self.plugins.values().find(|p| p.manifest.name == name)
     }
 
+    /// Get the plugin loaded from a directory.
+    pub fn get_by_dir(&self, plugin_dir: &Path) -> Option<&LoadedPlugin> {
+        self.plugins.get(plugin_dir)
+    }
+
     /// Get the number of loaded plugins.
     pub fn len(&self) -> usize {
         self.plugins.len()
@@ -61,6 +70,10 @@ pub struct PluginWatcher {
     state: Arc<RwLock<PluginState>>,
     /// Debounce duration.
     debounce: Duration,
+    /// MCP manager for plugin-declared servers (None = don't start them).
+    ///
+    /// Shared with the watch thread so a manager attached later is used.
+    mcp_manager: Arc<std::sync::RwLock<Option<SharedMcpManager>>>,
 }
 
 impl PluginWatcher {
@@ -70,7 +83,39 @@ impl PluginWatcher {
             manager,
             state: Arc::new(RwLock::new(PluginState::default())),
             debounce: Duration::from_millis(500),
+            mcp_manager: Arc::default(),
+        }
+    }
+
+    /// Start, restart and stop plugin-declared MCP servers on this manager
+    /// as plugins are loaded, reloaded and removed.
+    pub fn with_mcp_manager(self, mcp_manager: SharedMcpManager) -> Self {
+        self.set_mcp_manager(mcp_manager);
+        self
+    }
+
+    /// Attach the MCP manager to an already shared watcher.
+    ///
+    /// See [`with_mcp_manager`](Self::with_mcp_manager).
+    pub fn set_mcp_manager(&self, mcp_manager: SharedMcpManager) {
+        *self.mcp_manager.write().unwrap_or_else(|e| e.into_inner()) = Some(mcp_manager);
+    }
+
+    fn mcp_manager(&self) -> Option<SharedMcpManager> {
+        current_mcp_manager(&self.mcp_manager)
+    }
+
+    /// Start the MCP servers of all currently loaded plugins.
+    ///
+    /// Use this when the MCP manager is attached after [`load_initial`](Self::load_initial).
+    pub async fn start_mcp_servers(&self) -> Vec<String> {
+        let mut started = Vec::new();
+        let plugins: Vec<LoadedPlugin> =
+            self.state.read().await.plugins.values().cloned().collect();
+        for plugin in &plugins {
+            started.extend(sync_mcp_servers(self.mcp_manager().as_ref(), plugin).await);
         }
+        started
     }
 
     /// Set the debounce duration.
@@ -79,27 +124,45 @@ impl PluginWatcher {
         self
     }
 
+    /// The plugin manager used for discovery and loading.
+    pub fn manager(&self) -> &PluginManager {
+        &self.manager
+    }
+
     /// Get a reference to the shared plugin state.
     pub fn state(&self) -> Arc<RwLock<PluginState>> {
         self.state.clone()
     }
 
     /// Perform initial load of all plugins.
+    ///
+    /// Plugins that fail to load are reported as [`PluginEvent::Error`].
     pub async fn load_initial(&self) -> Vec<PluginEvent> {
-        let loaded = self.manager.load_all();
         let mut events = Vec::new();
         let mut state = self.state.write().await;
 
-        for plugin in loaded {
-            let name = plugin.manifest.name.clone();
-            let dir = plugin.plugin_dir.clone();
-            state.plugins.insert(dir.clone(), plugin);
-            events.push(PluginEvent::Reloaded {
-                name,
-                plugin_dir: dir,
-            });
+        for dir in self.manager.discover() {
+            match self.manager.load_single(&dir) {
+                Ok(plugin) => {
+                    let name = plugin.manifest.name.clone();
+                    state.plugins.insert(dir.clone(), plugin);
+                    events.push(PluginEvent::Reloaded {
+                        name,
+                        plugin_dir: dir,
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!(dir = %dir.display(), error = %e, "failed to load plugin, skipping");
+                    events.push(PluginEvent::Error {
+                        plugin_dir: dir,
+                        error: e.to_string(),
+                    });
+                }
+            }
         }
+        drop(state);
 
+        self.start_mcp_servers().await;
         events
     }
 
@@ -109,6 +172,7 @@ impl PluginWatcher {
             Ok(plugin) => {
                 let name = plugin.manifest.name.clone();
                 let dir = plugin.plugin_dir.clone();
+                sync_mcp_servers(self.mcp_manager().as_ref(), &plugin).await;
                 let mut state = self.state.write().await;
                 state.plugins.insert(dir.clone(), plugin);
                 tracing::info!(name = %name, dir = %dir.display(), "plugin reloaded");
@@ -132,6 +196,7 @@ impl PluginWatcher {
         let mut state = self.state.write().await;
         if let Some(plugin) = state.plugins.remove(plugin_dir) {
             let name = plugin.manifest.name.clone();
+            stop_mcp_servers(self.mcp_manager().as_ref(), &name).await;
             tracing::info!(name = %name, dir = %plugin_dir.display(), "plugin removed");
             Some(PluginEvent::Removed {
                 name,
@@ -168,6 +233,7 @@ impl PluginWatcher {
         }
 
         let state = self.state.clone();
+        let mcp_manager = self.mcp_manager.clone();
         let plugin_dirs: Vec<PathBuf> = self.manager.plugin_dirs().to_vec();
 
         let handle = std::thread::spawn(move || {
@@ -192,27 +258,33 @@ impl PluginWatcher {
                 // Process each affected plugin
                 for (plugin_dir, manifest_exists) in affected {
                     let state = state.clone();
+                    let mcp_manager = current_mcp_manager(&mcp_manager);
                     let event_tx = event_tx.clone();
 
                     if manifest_exists {
                         // Reload
-                        let evt = tokio::runtime::Handle::current()
-                            .block_on(async { reload_from_dir(&state, &plugin_dir).await });
+                        let evt = tokio::runtime::Handle::current().block_on(async {
+                            reload_from_dir(&state, mcp_manager.as_ref(), &plugin_dir).await
+                        });
                         if let Err(e) = event_tx.blocking_send(evt) {
                             tracing::warn!("failed to send plugin event: {e}");
                         }
                     } else {
                         // Plugin directory or manifest removed
                         let evt = tokio::runtime::Handle::current().block_on(async {
-                            let mut st = state.write().await;
-                            st.plugins.remove(&plugin_dir).map(|p| {
-                                let name = p.manifest.name.clone();
-                                tracing::info!(name = %name, "plugin removed (directory/manifest gone)");
-                                PluginEvent::Removed {
-                                    name,
-                                    plugin_dir: plugin_dir.clone(),
+                            let removed = state.write().await.plugins.remove(&plugin_dir);
+                            match removed {
+                                Some(p) => {
+                                    let name = p.manifest.name.clone();
+                                    stop_mcp_servers(mcp_manager.as_ref(), &name).await;
+                                    tracing::info!(name = %name, "plugin removed (directory/manifest gone)");
+                                    Some(PluginEvent::Removed {
+                                        name,
+                                        plugin_dir: plugin_dir.clone(),
+                                    })
                                 }
-                            })
+                                None => None,
+                            }
                         });
                         if let Some(evt) = evt
                             && let Err(e) = event_tx.blocking_send(evt)
@@ -230,9 +302,16 @@ impl PluginWatcher {
 
 /// Find the plugin directory containing a given path.
 ///
-/// A plugin directory is a direct subdirectory of a plugin search directory
-/// that contains `.claude-plugin/plugin.json`.
+/// A plugin directory is either a search directory that itself contains
+/// `.claude-plugin/plugin.json`, or a direct subdirectory of a search
+/// directory.
 fn find_plugin_dir(path: &Path, plugin_dirs: &[PathBuf]) -> Option<PathBuf> {
+    if let Some(root) = plugin_dirs
+        .iter()
+        .find(|dir| path.starts_with(dir) && dir.join(MANIFEST_PATH).exists())
+    {
+        return Some(root.clone());
+    }
     for search_dir in plugin_dirs {
         // Walk up from the changed path to find the plugin root
         let mut candidate = path;
@@ -247,14 +326,64 @@ fn find_plugin_dir(path: &Path, plugin_dirs: &[PathBuf]) -> Option<PathBuf> {
     None
 }
 
+fn current_mcp_manager(
+    slot: &std::sync::RwLock<Option<SharedMcpManager>>,
+) -> Option<SharedMcpManager> {
+    slot.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Bring a plugin's MCP servers in line with its (re)loaded manifest.
+///
+/// MCP connections do blocking I/O (process spawn, blocking HTTP client), so
+/// the work runs on the blocking thread pool.
+async fn sync_mcp_servers(
+    mcp_manager: Option<&SharedMcpManager>,
+    plugin: &LoadedPlugin,
+) -> Vec<String> {
+    let Some(mcp) = mcp_manager.cloned() else {
+        return Vec::new();
+    };
+    let plugin = plugin.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut mcp = mcp.blocking_write();
+        crate::mcp::sync_plugin_mcp_servers(&plugin, &mut mcp)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "plugin MCP server sync panicked");
+        Vec::new()
+    })
+}
+
+/// Stop all MCP servers belonging to a removed plugin.
+async fn stop_mcp_servers(mcp_manager: Option<&SharedMcpManager>, plugin_name: &str) {
+    let Some(mcp) = mcp_manager.cloned() else {
+        return;
+    };
+    let plugin_name = plugin_name.to_string();
+    if let Err(e) = tokio::task::spawn_blocking(move || {
+        let mut mcp = mcp.blocking_write();
+        crate::mcp::stop_plugin_mcp_servers(&plugin_name, &mut mcp);
+    })
+    .await
+    {
+        tracing::warn!(error = %e, "plugin MCP server shutdown panicked");
+    }
+}
+
 /// Reload a plugin from its directory into the shared state.
-async fn reload_from_dir(state: &Arc<RwLock<PluginState>>, plugin_dir: &Path) -> PluginEvent {
+async fn reload_from_dir(
+    state: &Arc<RwLock<PluginState>>,
+    mcp_manager: Option<&SharedMcpManager>,
+    plugin_dir: &Path,
+) -> PluginEvent {
     // Use a temporary manager to load the single plugin
     let manager = PluginManager::new(vec![]);
     match manager.load_single(plugin_dir) {
         Ok(plugin) => {
             let name = plugin.manifest.name.clone();
             let dir = plugin.plugin_dir.clone();
+            sync_mcp_servers(mcp_manager, &plugin).await;
             let mut st = state.write().await;
             st.plugins.insert(dir.clone(), plugin);
             tracing::info!(name = %name, "plugin hot-reloaded");
@@ -774,7 +903,7 @@ mod tests {
         let plugin_dir = create_test_plugin(tmp.path(), "direct-reload");
 
         let state = Arc::new(RwLock::new(PluginState::default()));
-        let event = reload_from_dir(&state, &plugin_dir).await;
+        let event = reload_from_dir(&state, None, &plugin_dir).await;
 
         match event {
             PluginEvent::Reloaded {
@@ -800,7 +929,7 @@ mod tests {
         fs::write(bad_dir.join(MANIFEST_PATH), "invalid json{{{").unwrap();
 
         let state = Arc::new(RwLock::new(PluginState::default()));
-        let event = reload_from_dir(&state, &bad_dir).await;
+        let event = reload_from_dir(&state, None, &bad_dir).await;
 
         match event {
             PluginEvent::Error { plugin_dir, error } => {
@@ -822,7 +951,7 @@ mod tests {
         let state = Arc::new(RwLock::new(PluginState::default()));
 
         // Load initially
-        reload_from_dir(&state, &plugin_dir).await;
+        reload_from_dir(&state, None, &plugin_dir).await;
         assert_eq!(
             state
                 .read()
@@ -843,7 +972,7 @@ mod tests {
         .unwrap();
 
         // Reload should replace
-        reload_from_dir(&state, &plugin_dir).await;
+        reload_from_dir(&state, None, &plugin_dir).await;
         assert_eq!(
             state
                 .read()
@@ -954,4 +1083,74 @@ mod tests {
         assert!(names.contains(&"a"));
         assert!(names.contains(&"b"));
     }
+
+    #[tokio::test]
+    async fn test_load_initial_reports_invalid_plugins() {
+        let tmp = TempDir::new().unwrap();
+        create_test_plugin(tmp.path(), "good");
+        let bad = tmp.path().join("bad");
+        fs::create_dir_all(bad.join(".claude-plugin")).unwrap();
+        fs::write(bad.join(MANIFEST_PATH), "not json").unwrap();
+
+        let manager = PluginManager::new(vec![tmp.path().to_path_buf()]);
+        let watcher = PluginWatcher::new(manager);
+
+        let events = watcher.load_initial().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], PluginEvent::Error { plugin_dir, .. } if *plugin_dir == bad));
+        assert_eq!(watcher.state().read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_plugin_mcp_servers_follow_plugin_lifecycle() {
+        let tmp = TempDir::new().unwrap();
+        let plugin_dir = tmp.path().join("with-mcp");
+        fs::create_dir_all(plugin_dir.join(".claude-plugin")).unwrap();
+        fs::write(
+            plugin_dir.join(MANIFEST_PATH),
+            r#"{
+  "name": "with-mcp",
+  "mcpServers": {
+    "db": { "command": "${CLAUDE_PLUGIN_ROOT}/missing-server" },
+    "web": { "type": "http", "url": "http://127.0.0.1:1/mcp" }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let mcp = Arc::new(RwLock::new(McpManager::new()));
+        let manager = PluginManager::new(vec![tmp.path().to_path_buf()]);
+        let watcher = PluginWatcher::new(manager).with_mcp_manager(mcp.clone());
+        watcher.load_initial().await;
+
+        {
+            let mcp = mcp.read().await;
+            assert!(mcp.has_server("with-mcp:db"));
+            assert!(mcp.has_server("with-mcp:web"));
+            let config = mcp.server_config("with-mcp:db").unwrap();
+            assert_eq!(
+                config.command,
+                format!("{}/missing-server", plugin_dir.display())
+            );
+        }
+
+        // Manifest change drops the "web" server
+        fs::write(
+            plugin_dir.join(MANIFEST_PATH),
+            r#"{
+  "name": "with-mcp",
+  "mcpServers": { "db": { "command": "${CLAUDE_PLUGIN_ROOT}/missing-server" } }
+}"#,
+        )
+        .unwrap();
+        watcher.reload_plugin(&plugin_dir).await;
+        {
+            let mcp = mcp.read().await;
+            assert!(mcp.has_server("with-mcp:db"));
+            assert!(!mcp.has_server("with-mcp:web"));
+        }
+
+        watcher.remove_plugin(&plugin_dir).await;
+        assert_eq!(mcp.read().await.config_count(), 0);
+    }
 }
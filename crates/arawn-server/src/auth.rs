@@ -1,21 +1,30 @@
 //! Authentication middleware.
 //!
 //! Provides token-based authentication with optional Tailscale identity validation.
+//! Besides the shared server token, requests may use named API tokens from the
+//! [`AccessStore`]; those act as a user with a limited set of scopes, and only
+//! see the workstreams, sessions, notes and memories that user owns.
 //!
 //! # Security
 //!
 //! Token comparison uses constant-time comparison to prevent timing attacks.
+//! Named tokens are looked up by SHA-256 hash.
 
+use std::collections::HashSet;
+use std::fmt;
+
+use arawn_domain::{AccessStore, ResourceKind, SCRATCH_ID, TokenScope, scopes_allow};
 use axum::{
     body::Body,
     extract::{Request, State},
-    http::{StatusCode, header::AUTHORIZATION},
+    http::{Method, StatusCode, header::AUTHORIZATION},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 use subtle::ConstantTimeEq;
 
+use crate::error::ServerError;
 use crate::state::AppState;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -26,10 +35,22 @@ use crate::state::AppState;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Identity {
-    /// Authenticated via bearer token.
+    /// Authenticated via the shared server token (or auth is disabled).
+    ///
+    /// Has every scope and sees all data.
     Token,
     /// Authenticated via Tailscale identity.
-    Tailscale { user: String },
+    Tailscale {
+        user: String,
+        #[serde(default)]
+        scopes: Vec<TokenScope>,
+    },
+    /// Authenticated via a named API token.
+    ApiToken {
+        token_id: String,
+        user: String,
+        scopes: Vec<TokenScope>,
+    },
 }
 
 impl Identity {
@@ -46,10 +67,43 @@ impl Identity {
     /// Get the Tailscale user if this is a Tailscale identity.
     pub fn tailscale_user(&self) -> Option<&str> {
         match self {
-            Identity::Tailscale { user } => Some(user),
+            Identity::Tailscale { user, .. } => Some(user),
             _ => None,
         }
     }
+
+    /// The user this identity acts as (`None` for the shared token).
+    pub fn user(&self) -> Option<&str> {
+        match self {
+            Identity::Token => None,
+            Identity::Tailscale { user, .. } | Identity::ApiToken { user, .. } => Some(user),
+        }
+    }
+
+    /// Whether this identity grants `scope`.
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        match self {
+            Identity::Token => true,
+            Identity::Tailscale { scopes, .. } | Identity::ApiToken { scopes, .. } => {
+                scopes_allow(scopes, scope)
+            }
+        }
+    }
+
+    /// Whether this identity sees every user's data.
+    pub fn is_admin(&self) -> bool {
+        self.has_scope(TokenScope::Admin)
+    }
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identity::Token => write!(f, "token"),
+            Identity::Tailscale { user, .. } => write!(f, "tailscale:{}", user),
+            Identity::ApiToken { user, .. } => write!(f, "user:{}", user),
+        }
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -67,6 +121,8 @@ pub enum AuthError {
     InvalidToken,
     /// Tailscale user not in allowed list.
     TailscaleNotAllowed,
+    /// The identity lacks the scope the route requires.
+    InsufficientScope(TokenScope),
 }
 
 impl std::fmt::Display for AuthError {
@@ -76,6 +132,9 @@ impl std::fmt::Display for AuthError {
             AuthError::InvalidFormat => write!(f, "Invalid authorization format"),
             AuthError::InvalidToken => write!(f, "Invalid token"),
             AuthError::TailscaleNotAllowed => write!(f, "Tailscale user not allowed"),
+            AuthError::InsufficientScope(scope) => {
+                write!(f, "Token lacks the '{}' scope", scope)
+            }
         }
     }
 }
@@ -84,12 +143,14 @@ impl std::error::Error for AuthError {}
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
-        let (status, message) = match self {
-            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing authorization token"),
-            AuthError::InvalidFormat => (StatusCode::BAD_REQUEST, "Invalid authorization format"),
-            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid token"),
-            AuthError::TailscaleNotAllowed => (StatusCode::FORBIDDEN, "Tailscale user not allowed"),
+        let status = match self {
+            AuthError::MissingToken | AuthError::InvalidToken => StatusCode::UNAUTHORIZED,
+            AuthError::InvalidFormat => StatusCode::BAD_REQUEST,
+            AuthError::TailscaleNotAllowed | AuthError::InsufficientScope(_) => {
+                StatusCode::FORBIDDEN
+            }
         };
+        let message = self.to_string();
 
         let body = serde_json::json!({
             "error": message,
@@ -158,10 +219,28 @@ pub async fn auth_middleware(
 
 /// Validate a request and return the identity.
 fn validate_request(request: &Request<Body>, state: &AppState) -> Result<Identity, AuthError> {
-    // If no auth token configured (localhost mode), skip auth entirely
-    let Some(ref expected_token) = state.config().auth_token else {
-        return Ok(Identity::Token);
-    };
+    let identity = identify(request, state)?;
+
+    let scope = required_scope(request.method(), request.uri().path());
+    if !identity.has_scope(scope) {
+        return Err(AuthError::InsufficientScope(scope));
+    }
+    Ok(identity)
+}
+
+/// Work out who sent a request.
+fn identify(request: &Request<Body>, state: &AppState) -> Result<Identity, AuthError> {
+    // If no auth token configured (localhost mode), only named tokens narrow access
+    if state.config().auth_token.is_none() {
+        let bearer = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.strip_prefix("Bearer "));
+        return Ok(bearer
+            .and_then(|token| authenticate_bearer(state, token).ok())
+            .unwrap_or(Identity::Token));
+    }
 
     // Try Authorization header first
     if let Some(auth_header) = request.headers().get(AUTHORIZATION) {
@@ -169,13 +248,7 @@ fn validate_request(request: &Request<Body>, state: &AppState) -> Result<Identit
 
         // Check for Bearer token
         if let Some(token) = auth_str.strip_prefix("Bearer ") {
-            // Use constant-time comparison to prevent timing attacks.
-            // This ensures the comparison takes the same amount of time
-            // regardless of how many characters match.
-            if constant_time_eq(token, expected_token) {
-                return Ok(Identity::Token);
-            }
-            return Err(AuthError::InvalidToken);
+            return authenticate_bearer(state, token);
         }
 
         return Err(AuthError::InvalidFormat);
@@ -190,6 +263,7 @@ fn validate_request(request: &Request<Body>, state: &AppState) -> Result<Identit
         if allowed_users.iter().any(|u| u == ts_user) {
             return Ok(Identity::Tailscale {
                 user: ts_user.to_string(),
+                scopes: state.config().tailscale_scopes.clone(),
             });
         }
 
@@ -199,6 +273,149 @@ fn validate_request(request: &Request<Body>, state: &AppState) -> Result<Identit
     Err(AuthError::MissingToken)
 }
 
+/// Authenticate a bearer token: the shared server token or a named API token.
+///
+/// Also used by the WebSocket `auth` message.
+pub(crate) fn authenticate_bearer(state: &AppState, token: &str) -> Result<Identity, AuthError> {
+    // Use constant-time comparison to prevent timing attacks.
+    // This ensures the comparison takes the same amount of time
+    // regardless of how many characters match.
+    match &state.config().auth_token {
+        Some(expected) if constant_time_eq(token, expected) => return Ok(Identity::Token),
+        None if state.access_store().is_none() => return Ok(Identity::Token),
+        _ => {}
+    }
+
+    let Some(store) = state.access_store() else {
+        return Err(AuthError::InvalidToken);
+    };
+    match store.authenticate(token) {
+        Ok(Some(api_token)) => Ok(Identity::ApiToken {
+            token_id: api_token.id,
+            user: api_token.user,
+            scopes: api_token.scopes,
+        }),
+        Ok(None) => Err(AuthError::InvalidToken),
+        Err(e) => {
+            tracing::warn!("Failed to look up API token: {}", e);
+            Err(AuthError::InvalidToken)
+        }
+    }
+}
+
+/// The scope a route requires.
+///
+/// Reads need `read`, everything else needs `chat`; managing MCP servers needs
+/// `mcp-manage`, and server-wide logs, usage, metrics and webhooks, and
+/// changes to the runtime catalog, plugins and config, need `admin`.
+fn required_scope(method: &Method, path: &str) -> TokenScope {
+    let path = path.strip_prefix("/api/v1").unwrap_or(path);
+    let is_read = method == Method::GET || method == Method::HEAD;
+
+    if path.starts_with("/logs")
+        || path.starts_with("/usage")
+        || path.starts_with("/webhooks")
+        || path == "/metrics"
+        || path == "/memory/graph/query"
+        || path == "/memory/graph/export"
+        || (path.starts_with("/runtimes") && !is_read)
+        || (path.starts_with("/plugins") && !is_read)
+        || (path == "/config" && !is_read)
+    {
+        TokenScope::Admin
+    } else if path.starts_with("/mcp") && !is_read {
+        TokenScope::McpManage
+    } else if is_read {
+        TokenScope::Read
+    } else {
+        TokenScope::Chat
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ownership
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether `identity` may access a resource.
+///
+/// Without an access store nothing is isolated. Admins see everything, and
+/// everyone shares the scratch workstream (its sessions are still owned).
+/// Resources without a recorded owner predate multi-user support and are
+/// only visible to admins.
+pub(crate) fn can_access(
+    state: &AppState,
+    identity: &Identity,
+    kind: ResourceKind,
+    id: &str,
+) -> bool {
+    let Some(store) = state.access_store() else {
+        return true;
+    };
+    if identity.is_admin() || (kind == ResourceKind::Workstream && id == SCRATCH_ID) {
+        return true;
+    }
+    match store.owner(kind, id) {
+        Ok(owner) => owner.is_some() && owner.as_deref() == identity.user(),
+        Err(e) => {
+            tracing::warn!("Failed to look up owner of {:?} {}: {}", kind, id, e);
+            false
+        }
+    }
+}
+
+/// Like [`can_access`], but as a `404 Not Found` so other users' resources
+/// are indistinguishable from missing ones.
+pub(crate) fn authorize(
+    state: &AppState,
+    identity: &Identity,
+    kind: ResourceKind,
+    id: &str,
+) -> Result<(), ServerError> {
+    if can_access(state, identity, kind, id) {
+        Ok(())
+    } else {
+        Err(ServerError::NotFound(format!("{:?} {}", kind, id)))
+    }
+}
+
+/// Record the identity's user as the owner of a new resource.
+pub(crate) fn claim(state: &AppState, identity: &Identity, kind: ResourceKind, id: &str) {
+    if let (Some(store), Some(user)) = (state.access_store(), identity.user())
+        && let Err(e) = store.set_owner(kind, id, user)
+    {
+        tracing::warn!("Failed to record owner of {:?} {}: {}", kind, id, e);
+    }
+}
+
+/// Forget the owner of a deleted resource.
+pub(crate) fn release(state: &AppState, kind: ResourceKind, id: &str) {
+    if let Some(store) = state.access_store()
+        && let Err(e) = store.clear_owner(kind, id)
+    {
+        tracing::warn!("Failed to clear owner of {:?} {}: {}", kind, id, e);
+    }
+}
+
+/// IDs of the resources of `kind` the identity may list (`None` = all).
+pub(crate) fn visible_ids(
+    state: &AppState,
+    identity: &Identity,
+    kind: ResourceKind,
+) -> Option<HashSet<String>> {
+    let store: &AccessStore = state.access_store()?;
+    if identity.is_admin() {
+        return None;
+    }
+    let mut ids = identity
+        .user()
+        .and_then(|user| store.owned(kind, user).ok())
+        .unwrap_or_default();
+    if kind == ResourceKind::Workstream {
+        ids.insert(SCRATCH_ID.to_string());
+    }
+    Some(ids)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Extension extractor
 // ─────────────────────────────────────────────────────────────────────────────
@@ -214,7 +431,8 @@ fn validate_request(request: &Request<Body>, state: &AppState) -> Result<Identit
 /// async fn my_handler(Extension(identity): Extension<Identity>) -> impl IntoResponse {
 ///     match identity {
 ///         Identity::Token => "Authenticated via token",
-///         Identity::Tailscale { user } => format!("Hello, {}", user),
+///         Identity::Tailscale { user, .. } => format!("Hello, {}", user),
+///         Identity::ApiToken { user, .. } => format!("Hello, {}", user),
 ///     }
 /// }
 /// ```
@@ -244,8 +462,9 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
         middleware,
-        routing::get,
+        routing::{get, post},
     };
+    use std::sync::Arc;
     use tower::ServiceExt;
 
     fn create_test_state(tailscale_users: Option<Vec<String>>) -> AppState {
@@ -263,10 +482,7 @@ mod tests {
     }
 
     async fn protected_handler(axum::Extension(identity): axum::Extension<Identity>) -> String {
-        match identity {
-            Identity::Token => "token".to_string(),
-            Identity::Tailscale { user } => format!("tailscale:{}", user),
-        }
+        identity.to_string()
     }
 
     fn create_test_router(state: AppState) -> Router {
@@ -457,10 +673,227 @@ mod tests {
 
         let tailscale = Identity::Tailscale {
             user: "alice".to_string(),
+            scopes: vec![TokenScope::Read],
         };
         assert!(!tailscale.is_token());
         assert!(tailscale.is_tailscale());
         assert_eq!(tailscale.tailscale_user(), Some("alice"));
+        assert_eq!(tailscale.user(), Some("alice"));
+        assert!(tailscale.has_scope(TokenScope::Read));
+        assert!(!tailscale.has_scope(TokenScope::Chat));
+        assert!(token.is_admin());
+        assert!(!tailscale.is_admin());
+    }
+
+    // ── Named tokens and scopes ────────────────────────────────────────────
+
+    fn create_state_with_tokens() -> (AppState, Arc<AccessStore>) {
+        let store = Arc::new(AccessStore::open_in_memory().unwrap());
+        let state = create_test_state(None).with_access_store(store.clone());
+        (state, store)
+    }
+
+    async fn request_as(
+        state: AppState,
+        method: Method,
+        uri: &str,
+        token: &str,
+    ) -> (StatusCode, String) {
+        let app = Router::new()
+            .route("/protected", get(protected_handler).post(protected_handler))
+            .route("/api/v1/mcp/servers", post(protected_handler))
+            .route("/api/v1/logs", get(protected_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("Authorization", format!("Bearer {}", token))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_auth_with_named_token() {
+        let (state, store) = create_state_with_tokens();
+        let (_, secret) = store
+            .create_token("laptop", "alice", &[TokenScope::Read], None)
+            .unwrap();
+
+        let (status, body) = request_as(state.clone(), Method::GET, "/protected", &secret).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "user:alice");
+
+        // The shared token still works
+        let (status, body) =
+            request_as(state.clone(), Method::GET, "/protected", "test-token-12345").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "token");
+
+        store.revoke_token("laptop").unwrap();
+        let (status, _) = request_as(state, Method::GET, "/protected", &secret).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_auth_enforces_scopes() {
+        let (state, store) = create_state_with_tokens();
+        let (_, reader) = store
+            .create_token("reader", "alice", &[TokenScope::Read], None)
+            .unwrap();
+        let (_, chatter) = store
+            .create_token(
+                "chatter",
+                "bob",
+                &[TokenScope::Read, TokenScope::Chat],
+                None,
+            )
+            .unwrap();
+        let (_, admin) = store
+            .create_token("admin", "carol", &[TokenScope::Admin], None)
+            .unwrap();
+
+        let (status, body) = request_as(state.clone(), Method::POST, "/protected", &reader).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.contains("chat"));
+
+        let (status, _) = request_as(state.clone(), Method::POST, "/protected", &chatter).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) =
+            request_as(state.clone(), Method::POST, "/api/v1/mcp/servers", &chatter).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = request_as(state.clone(), Method::GET, "/api/v1/logs", &chatter).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) =
+            request_as(state.clone(), Method::POST, "/api/v1/mcp/servers", &admin).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = request_as(state, Method::GET, "/api/v1/logs", &admin).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/workstreams"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/chat"),
+            TokenScope::Chat
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/mcp/servers"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/mcp/servers/x"),
+            TokenScope::McpManage
+        );
+        assert_eq!(required_scope(&Method::GET, "/usage"), TokenScope::Admin);
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/webhooks"),
+            TokenScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/runtimes"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::PUT, "/api/v1/runtimes/x"),
+            TokenScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/workflows/x/run"),
+            TokenScope::Chat
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/memory/graph/entities/rust"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/memory/graph/query"),
+            TokenScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/memory/graph/export"),
+            TokenScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/plugins"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/plugins/journal/disable"),
+            TokenScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/config"),
+            TokenScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::PATCH, "/api/v1/config"),
+            TokenScope::Admin
+        );
+    }
+
+    #[test]
+    fn test_ownership() {
+        let (state, store) = create_state_with_tokens();
+        let alice = Identity::ApiToken {
+            token_id: "t1".to_string(),
+            user: "alice".to_string(),
+            scopes: vec![TokenScope::Read, TokenScope::Chat],
+        };
+        let bob = Identity::Tailscale {
+            user: "bob".to_string(),
+            scopes: vec![TokenScope::Read, TokenScope::Chat],
+        };
+
+        claim(&state, &alice, ResourceKind::Note, "n1");
+        assert!(can_access(&state, &alice, ResourceKind::Note, "n1"));
+        assert!(!can_access(&state, &bob, ResourceKind::Note, "n1"));
+        assert!(can_access(
+            &state,
+            &Identity::Token,
+            ResourceKind::Note,
+            "n1"
+        ));
+        assert!(authorize(&state, &bob, ResourceKind::Note, "n1").is_err());
+
+        // Unowned resources are admin-only; scratch is shared
+        assert!(!can_access(&state, &alice, ResourceKind::Workstream, "ws"));
+        assert!(can_access(
+            &state,
+            &bob,
+            ResourceKind::Workstream,
+            SCRATCH_ID
+        ));
+
+        assert_eq!(
+            visible_ids(&state, &alice, ResourceKind::Note),
+            Some(HashSet::from(["n1".to_string()]))
+        );
+        assert_eq!(
+            visible_ids(&state, &Identity::Token, ResourceKind::Note),
+            None
+        );
+
+        release(&state, ResourceKind::Note, "n1");
+        assert_eq!(store.owner(ResourceKind::Note, "n1").unwrap(), None);
     }
 
     // ── Security tests ─────────────────────────────────────────────────────
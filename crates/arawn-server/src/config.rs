@@ -3,6 +3,8 @@
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use arawn_domain::TokenScope;
+
 /// Default grace period for session reconnect tokens (30 seconds).
 pub const DEFAULT_RECONNECT_GRACE_PERIOD: Duration = Duration::from_secs(30);
 
@@ -27,6 +29,9 @@ pub struct ServerConfig {
     /// Optional list of allowed Tailscale users.
     pub tailscale_users: Option<Vec<String>>,
 
+    /// Scopes granted to Tailscale users. Default: `read` and `chat`.
+    pub tailscale_scopes: Vec<TokenScope>,
+
     /// Enable rate limiting.
     pub rate_limiting: bool,
 
@@ -70,6 +75,7 @@ impl Default for ServerConfig {
             bind_address: "127.0.0.1:8080".parse().unwrap(),
             auth_token: None,
             tailscale_users: None,
+            tailscale_scopes: vec![TokenScope::Read, TokenScope::Chat],
             rate_limiting: true,
             api_rpm: 120,
             request_logging: true,
@@ -105,6 +111,12 @@ impl ServerConfig {
         self
     }
 
+    /// Set the scopes granted to Tailscale users.
+    pub fn with_tailscale_scopes(mut self, scopes: Vec<TokenScope>) -> Self {
+        self.tailscale_scopes = scopes;
+        self
+    }
+
     /// Enable or disable rate limiting.
     pub fn with_rate_limiting(mut self, enabled: bool) -> Self {
         self.rate_limiting = enabled;
@@ -16,6 +16,10 @@ pub enum ServerError {
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    /// Authenticated, but not allowed to do this.
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     /// Resource not found.
     #[error("Not found: {0}")]
     NotFound(String),
@@ -28,6 +32,15 @@ pub enum ServerError {
     #[error("Rate limit exceeded: {0}")]
     RateLimitExceeded(RateLimitError),
 
+    /// Request content was rejected; `details` describes every problem.
+    #[error("Validation failed: {message}")]
+    Validation {
+        /// Summary of the problem.
+        message: String,
+        /// Structured description of what is wrong.
+        details: serde_json::Value,
+    },
+
     /// Resource conflict (e.g., already exists).
     #[error("Conflict: {0}")]
     Conflict(String),
@@ -126,6 +139,7 @@ impl From<arawn_domain::WorkstreamError> for ServerError {
         use arawn_domain::WorkstreamError;
         match e {
             WorkstreamError::NotFound(msg) => ServerError::NotFound(msg),
+            WorkstreamError::InvalidOperation(msg) => ServerError::BadRequest(msg),
             WorkstreamError::Database(e) => ServerError::Storage(e.to_string()),
             WorkstreamError::Io(e) => ServerError::Storage(format!("IO error: {}", e)),
             WorkstreamError::Serde(e) => ServerError::Serialization(e),
@@ -136,6 +150,69 @@ impl From<arawn_domain::WorkstreamError> for ServerError {
     }
 }
 
+impl From<arawn_domain::TemplateError> for ServerError {
+    fn from(e: arawn_domain::TemplateError) -> Self {
+        use arawn_domain::TemplateError;
+        match e {
+            TemplateError::NotFound(name) => {
+                ServerError::NotFound(format!("Template not found: {}", name))
+            }
+            TemplateError::Params(_) | TemplateError::Invalid { .. } => {
+                ServerError::BadRequest(e.to_string())
+            }
+            TemplateError::Workstream(e) => e.into(),
+            TemplateError::Memory(e) => ServerError::Storage(e.to_string()),
+            TemplateError::Io(e) => ServerError::Storage(format!("IO error: {}", e)),
+        }
+    }
+}
+
+impl From<arawn_domain::WorkflowError> for ServerError {
+    fn from(e: arawn_domain::WorkflowError) -> Self {
+        use arawn_domain::WorkflowError;
+        match e {
+            WorkflowError::NotFound(msg) => ServerError::NotFound(msg),
+            WorkflowError::Invalid {
+                ref name,
+                ref issues,
+            } => ServerError::Validation {
+                message: e.to_string(),
+                details: serde_json::json!({ "workflow": name, "issues": issues }),
+            },
+            WorkflowError::Rejected(msg) => ServerError::BadRequest(msg),
+            WorkflowError::Pipeline(e) => ServerError::BadRequest(e.to_string()),
+            WorkflowError::Io(e) => ServerError::Storage(format!("IO error: {}", e)),
+        }
+    }
+}
+
+impl From<arawn_domain::PluginServiceError> for ServerError {
+    fn from(e: arawn_domain::PluginServiceError) -> Self {
+        use arawn_domain::PluginServiceError;
+        match e {
+            PluginServiceError::NotFound(msg) => ServerError::NotFound(msg),
+            PluginServiceError::Rejected(msg) => ServerError::BadRequest(msg),
+            PluginServiceError::Sync(_) => ServerError::BadRequest(e.to_string()),
+            PluginServiceError::Plugin(e) => ServerError::Internal(e.to_string()),
+            PluginServiceError::Io(e) => ServerError::Storage(format!("IO error: {}", e)),
+        }
+    }
+}
+
+impl From<arawn_domain::ConfigServiceError> for ServerError {
+    fn from(e: arawn_domain::ConfigServiceError) -> Self {
+        use arawn_domain::ConfigServiceError;
+        match e {
+            ConfigServiceError::Invalid(ref problems) => ServerError::Validation {
+                message: e.to_string(),
+                details: serde_json::json!({ "issues": problems }),
+            },
+            ConfigServiceError::Rejected(msg) => ServerError::BadRequest(msg),
+            ConfigServiceError::Config(e) => e.into(),
+        }
+    }
+}
+
 impl From<arawn_domain::ConfigError> for ServerError {
     fn from(e: arawn_domain::ConfigError) -> Self {
         use arawn_domain::ConfigError;
@@ -170,6 +247,9 @@ pub struct ErrorResponse {
     pub code: String,
     /// Human-readable error message.
     pub message: String,
+    /// Structured details, for validation errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
 impl ServerError {
@@ -180,8 +260,10 @@ impl ServerError {
         }
         match self {
             ServerError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ServerError::Forbidden(_) => StatusCode::FORBIDDEN,
             ServerError::NotFound(_) => StatusCode::NOT_FOUND,
             ServerError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ServerError::Validation { .. } => StatusCode::UNPROCESSABLE_ENTITY,
             ServerError::Conflict(_) => StatusCode::CONFLICT,
             ServerError::RateLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
             ServerError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
@@ -196,8 +278,10 @@ impl ServerError {
         }
         match self {
             ServerError::Unauthorized(_) => "unauthorized",
+            ServerError::Forbidden(_) => "forbidden",
             ServerError::NotFound(_) => "not_found",
             ServerError::BadRequest(_) => "bad_request",
+            ServerError::Validation { .. } => "validation_failed",
             ServerError::Conflict(_) => "conflict",
             ServerError::RateLimitExceeded(_) => "rate_limit_exceeded",
             ServerError::ServiceUnavailable(_) => "service_unavailable",
@@ -218,6 +302,10 @@ impl IntoResponse for ServerError {
         let code = self.error_code();
 
         let message = self.to_string();
+        let details = match &self {
+            ServerError::Validation { details, .. } => Some(details.clone()),
+            _ => None,
+        };
 
         match &self {
             ServerError::Internal(_) | ServerError::Agent(_) | ServerError::Serialization(_)
@@ -233,6 +321,7 @@ impl IntoResponse for ServerError {
         let body = ErrorResponse {
             code: code.to_string(),
             message,
+            details,
         };
 
         // Build response with optional Retry-After header
@@ -288,6 +377,16 @@ mod tests {
         assert_eq!(err.error_code(), "service_unavailable");
     }
 
+    #[test]
+    fn test_status_code_validation() {
+        let err = ServerError::Validation {
+            message: "bad workflow".into(),
+            details: serde_json::json!({ "issues": [] }),
+        };
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.error_code(), "validation_failed");
+    }
+
     #[test]
     fn test_status_code_internal() {
         let err = ServerError::Internal("boom".into());
@@ -382,6 +481,23 @@ mod tests {
         assert_eq!(err.error_code(), "storage_error");
     }
 
+    #[test]
+    fn test_from_workstream_invalid_operation() {
+        let ws_err = arawn_domain::WorkstreamError::InvalidOperation("same workstream".into());
+        let err: ServerError = ws_err.into();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_from_template_errors() {
+        let err: ServerError = arawn_domain::TemplateError::NotFound("research".into()).into();
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+        let err: ServerError =
+            arawn_domain::TemplateError::Params("missing required parameter(s): topic".into())
+                .into();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_from_workstream_migration() {
         let ws_err = arawn_domain::WorkstreamError::Migration("v2 failed".into());
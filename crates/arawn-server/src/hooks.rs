@@ -0,0 +1,290 @@
+//! Inbound webhook triggers.
+//!
+//! Each [`InboundHook`] is served at `/hooks/{name}`, outside the bearer-auth
+//! middleware: callers authenticate with the hook's own secret instead,
+//! either as an HMAC-SHA256 signature of the raw body or as a plain token.
+//! The payload is mapped into named values with JSON pointers and handed to
+//! the hook's target — a pipeline workflow or a chat turn in a workstream.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::http::{HeaderMap, header::AUTHORIZATION};
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+use crate::webhooks::SIGNATURE_HEADER;
+
+/// Default header carrying the secret for token-authenticated hooks.
+pub const TOKEN_HEADER: &str = "X-Arawn-Token";
+
+/// Context key holding the full payload.
+pub const PAYLOAD_KEY: &str = "payload";
+
+/// Hooks by name.
+pub type InboundHooks = HashMap<String, InboundHook>;
+
+/// How requests to a hook are authenticated.
+#[derive(Clone)]
+pub enum HookAuth {
+    /// `sha256=<hex>` (or bare hex) HMAC-SHA256 of the raw body in `header`.
+    Hmac { secret: String, header: String },
+    /// The token itself in `header`, or as `Authorization: Bearer <token>`.
+    Token { token: String, header: String },
+}
+
+impl HookAuth {
+    /// HMAC authentication using the default signature header.
+    pub fn hmac(secret: impl Into<String>) -> Self {
+        Self::Hmac {
+            secret: secret.into(),
+            header: SIGNATURE_HEADER.to_string(),
+        }
+    }
+
+    /// Token authentication using the default token header.
+    pub fn token(token: impl Into<String>) -> Self {
+        Self::Token {
+            token: token.into(),
+            header: TOKEN_HEADER.to_string(),
+        }
+    }
+
+    /// Use a different header for the signature or token.
+    pub fn with_header(mut self, name: impl Into<String>) -> Self {
+        match &mut self {
+            Self::Hmac { header, .. } | Self::Token { header, .. } => *header = name.into(),
+        }
+        self
+    }
+
+    /// Check a request against this scheme.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> bool {
+        match self {
+            Self::Hmac { secret, header } => {
+                let Some(signature) = header_str(headers, header) else {
+                    return false;
+                };
+                let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+                let Ok(signature) = hex::decode(signature.trim()) else {
+                    return false;
+                };
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                    .expect("HMAC accepts any key length");
+                mac.update(body);
+                mac.verify_slice(&signature).is_ok()
+            }
+            Self::Token { token, header } => {
+                let provided = header_str(headers, header).or_else(|| {
+                    header_str(headers, AUTHORIZATION.as_str())
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                });
+                provided
+                    .is_some_and(|provided| bool::from(provided.as_bytes().ct_eq(token.as_bytes())))
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for HookAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hmac { header, .. } => f
+                .debug_struct("Hmac")
+                .field("secret", &"[REDACTED]")
+                .field("header", header)
+                .finish(),
+            Self::Token { header, .. } => f
+                .debug_struct("Token")
+                .field("token", &"[REDACTED]")
+                .field("header", header)
+                .finish(),
+        }
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// What a hook starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookTarget {
+    /// Trigger a pipeline workflow.
+    Workflow(String),
+    /// Run a chat turn in a new session of a workstream (ID or title).
+    Workstream(String),
+}
+
+/// A configured inbound webhook.
+#[derive(Debug, Clone)]
+pub struct InboundHook {
+    pub name: String,
+    pub auth: HookAuth,
+    pub target: HookTarget,
+    /// Value name → JSON pointer into the payload.
+    pub context: BTreeMap<String, String>,
+    /// `{{name}}` template for chat turns.
+    pub message: Option<String>,
+}
+
+impl InboundHook {
+    /// Create a hook with no context mapping or message template.
+    pub fn new(name: impl Into<String>, auth: HookAuth, target: HookTarget) -> Self {
+        Self {
+            name: name.into(),
+            auth,
+            target,
+            context: BTreeMap::new(),
+            message: None,
+        }
+    }
+
+    /// Map a JSON pointer into the payload to a named value.
+    pub fn with_context(mut self, name: impl Into<String>, pointer: impl Into<String>) -> Self {
+        self.context.insert(name.into(), pointer.into());
+        self
+    }
+
+    /// Set the message template for chat turns.
+    pub fn with_message(mut self, template: impl Into<String>) -> Self {
+        self.message = Some(template.into());
+        self
+    }
+
+    /// The full payload under `payload`, plus every mapped value the
+    /// payload contains.
+    pub fn values(&self, payload: &Value) -> Map<String, Value> {
+        let mut values = Map::new();
+        values.insert(PAYLOAD_KEY.to_string(), payload.clone());
+        for (name, pointer) in &self.context {
+            if let Some(value) = payload.pointer(pointer) {
+                values.insert(name.clone(), value.clone());
+            }
+        }
+        values
+    }
+
+    /// The chat message for a payload: the rendered template, or the
+    /// pretty-printed payload when there is none.
+    pub fn render_message(&self, values: &Map<String, Value>) -> String {
+        match &self.message {
+            Some(template) => render(template, values),
+            None => {
+                let payload = values.get(PAYLOAD_KEY).unwrap_or(&Value::Null);
+                format!(
+                    "Webhook `{}` received:\n\n```json\n{}\n```",
+                    self.name,
+                    serde_json::to_string_pretty(payload).unwrap_or_default()
+                )
+            }
+        }
+    }
+}
+
+/// Parse a request body as JSON, falling back to a string for other content.
+pub fn parse_payload(body: &[u8]) -> Value {
+    serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+/// Replaces `{{name}}` placeholders whose name is in `values`; strings are
+/// inserted as-is and other values as JSON.
+fn render(text: &str, values: &Map<String, Value>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        out.push_str(&rest[..start]);
+        match values.get(key) {
+            Some(Value::String(s)) => out.push_str(s),
+            Some(value) => out.push_str(&value.to_string()),
+            None => out.push_str(&rest[start..start + len + 4]),
+        }
+        rest = &rest[start + len + 4..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    fn signature(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_verify_hmac() {
+        let body = br#"{"ok":true}"#;
+        let auth = HookAuth::hmac("s3cret");
+        let sig = signature("s3cret", body);
+
+        assert!(auth.verify(&headers(SIGNATURE_HEADER, &sig), body));
+        assert!(auth.verify(&headers(SIGNATURE_HEADER, &sig[7..]), body));
+        assert!(!auth.verify(&headers(SIGNATURE_HEADER, &sig), b"{}"));
+        assert!(!auth.verify(&headers(SIGNATURE_HEADER, &signature("other", body)), body));
+        assert!(!auth.verify(&HeaderMap::new(), body));
+
+        let github = HookAuth::hmac("s3cret").with_header("X-Hub-Signature-256");
+        assert!(github.verify(&headers("x-hub-signature-256", &sig), body));
+    }
+
+    #[test]
+    fn test_verify_token() {
+        let auth = HookAuth::token("tok");
+        assert!(auth.verify(&headers(TOKEN_HEADER, "tok"), b""));
+        assert!(auth.verify(&headers("authorization", "Bearer tok"), b""));
+        assert!(!auth.verify(&headers(TOKEN_HEADER, "nope"), b""));
+        assert!(!auth.verify(&HeaderMap::new(), b""));
+    }
+
+    #[test]
+    fn test_values_and_message() {
+        let hook = InboundHook::new(
+            "ci",
+            HookAuth::token("tok"),
+            HookTarget::Workstream("inbox".to_string()),
+        )
+        .with_context("repo", "/repository/name")
+        .with_context("run", "/run/id")
+        .with_context("missing", "/nope")
+        .with_message("Build {{run}} failed in {{repo}} ({{missing}})");
+
+        let payload = json!({"repository": {"name": "arawn"}, "run": {"id": 42}});
+        let values = hook.values(&payload);
+        assert_eq!(values[PAYLOAD_KEY], payload);
+        assert_eq!(values["repo"], "arawn");
+        assert!(!values.contains_key("missing"));
+        assert_eq!(
+            hook.render_message(&values),
+            "Build 42 failed in arawn ({{missing}})"
+        );
+
+        let plain = InboundHook::new("ci", HookAuth::token("tok"), hook.target.clone());
+        let message = plain.render_message(&plain.values(&payload));
+        assert!(message.starts_with("Webhook `ci` received:"));
+        assert!(message.contains("\"arawn\""));
+    }
+
+    #[test]
+    fn test_parse_payload() {
+        assert_eq!(parse_payload(br#"{"a":1}"#), json!({"a": 1}));
+        assert_eq!(parse_payload(b"plain text"), json!("plain text"));
+    }
+}
@@ -29,10 +29,13 @@
 pub mod auth;
 pub mod config;
 pub mod error;
+pub mod hooks;
+pub mod metrics;
 pub mod ratelimit;
 pub mod routes;
 pub mod session_cache;
 pub mod state;
+pub mod webhooks;
 
 pub use auth::{AuthError, AuthIdentity, Identity, auth_middleware};
 pub use config::ServerConfig;
@@ -81,8 +84,19 @@ impl Server {
             .merge(routes::openapi::swagger_ui())
             // WebSocket (auth happens via message, not HTTP header)
             .route("/ws", get(routes::ws_handler))
+            // Inbound webhook triggers (auth via each hook's own secret)
+            .route(
+                "/hooks/{name}",
+                axum::routing::post(routes::trigger_hook_handler),
+            )
             // API routes will be added here
             .nest("/api/v1", self.api_routes())
+            // OpenAI-compatible facade
+            .nest("/v1", self.openai_routes())
+            // Prometheus scrape endpoint (auth required)
+            .merge(self.metrics_routes())
+            // Request metrics per matched route (innermost, so the route is known)
+            .layer(middleware::from_fn(metrics::http_metrics_middleware))
             // Request logging (inner layer, runs first)
             .layer(middleware::from_fn_with_state(
                 self.state.clone(),
@@ -100,6 +114,18 @@ impl Server {
             .with_state(self.state.clone())
     }
 
+    /// Prometheus metrics route, behind the auth middleware.
+    fn metrics_routes(&self) -> Router<AppState> {
+        use axum::routing::get;
+
+        Router::new()
+            .route("/metrics", get(routes::metrics_handler))
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::auth_middleware,
+            ))
+    }
+
     /// API routes (v1).
     ///
     /// All API routes require authentication via the auth middleware.
@@ -125,10 +151,24 @@ impl Server {
                 "/sessions/{id}/messages",
                 get(routes::get_session_messages_handler),
             )
+            .route("/sessions/{id}/fork", post(routes::fork_session_handler))
             // Memory endpoints
             .route("/memory", post(routes::store_memory_handler))
             .route("/memory/search", get(routes::memory_search_handler))
             .route("/memory/{id}", delete(routes::delete_memory_handler))
+            // Knowledge graph endpoints
+            .route("/memory/graph", get(routes::graph_stats_handler))
+            .route("/memory/graph/entities", get(routes::list_entities_handler))
+            .route(
+                "/memory/graph/entities/{id}",
+                get(routes::get_entity_handler),
+            )
+            .route(
+                "/memory/graph/entities/{id}/neighbors",
+                get(routes::entity_neighbors_handler),
+            )
+            .route("/memory/graph/query", post(routes::graph_query_handler))
+            .route("/memory/graph/export", get(routes::graph_export_handler))
             // Notes endpoints
             .route(
                 "/notes",
@@ -160,6 +200,14 @@ impl Server {
                 get(routes::list_workstream_sessions_handler),
             )
             .route("/workstreams/{id}/promote", post(routes::promote_handler))
+            .route(
+                "/workstreams/{id}/merge",
+                post(routes::merge_workstream_handler),
+            )
+            .route(
+                "/workstreams/{id}/split",
+                post(routes::split_workstream_handler),
+            )
             .route(
                 "/workstreams/{id}/files/promote",
                 post(routes::promote_file_handler),
@@ -171,12 +219,30 @@ impl Server {
             .route("/workstreams/{id}/clone", post(routes::clone_repo_handler))
             .route("/workstreams/{id}/usage", get(routes::get_usage_handler))
             .route("/workstreams/{id}/cleanup", post(routes::cleanup_handler))
+            .route(
+                "/workstreams/{id}/snapshots",
+                get(routes::list_snapshots_handler).post(routes::create_snapshot_handler),
+            )
+            .route(
+                "/workstreams/{id}/snapshots/{snapshot_id}/diff",
+                get(routes::diff_snapshot_handler),
+            )
+            .route(
+                "/workstreams/{id}/snapshots/{snapshot_id}/restore",
+                post(routes::restore_snapshot_handler),
+            )
             .route(
                 "/workstreams/{id}/compress",
                 post(routes::compress_workstream_handler),
             )
+            .route("/templates", get(routes::list_templates_handler))
             // Config endpoint
-            .route("/config", get(routes::get_config_handler))
+            .route(
+                "/config",
+                get(routes::get_config_handler)
+                    .put(routes::put_config_handler)
+                    .patch(routes::patch_config_handler),
+            )
             // Agent endpoints
             .route("/agents", get(routes::list_agents_handler))
             .route("/agents/{id}", get(routes::get_agent_handler))
@@ -207,6 +273,77 @@ impl Server {
             // Logs endpoints
             .route("/logs", get(routes::get_logs_handler))
             .route("/logs/files", get(routes::list_log_files_handler))
+            // Usage endpoint
+            .route("/usage", get(routes::get_usage_report_handler))
+            // Webhook endpoints
+            .route(
+                "/webhooks",
+                post(routes::create_webhook_handler).get(routes::list_webhooks_handler),
+            )
+            .route(
+                "/webhooks/dead-letters",
+                get(routes::list_dead_letters_handler),
+            )
+            .route(
+                "/webhooks/dead-letters/{id}",
+                delete(routes::delete_dead_letter_handler),
+            )
+            .route(
+                "/webhooks/dead-letters/{id}/redeliver",
+                post(routes::redeliver_dead_letter_handler),
+            )
+            .route(
+                "/webhooks/{id}",
+                get(routes::get_webhook_handler)
+                    .patch(routes::update_webhook_handler)
+                    .delete(routes::delete_webhook_handler),
+            )
+            // Workflow endpoints
+            .route(
+                "/workflows",
+                get(routes::list_workflows_handler).post(routes::create_workflow_handler),
+            )
+            .route(
+                "/workflows/{name}",
+                get(routes::get_workflow_handler)
+                    .put(routes::put_workflow_handler)
+                    .delete(routes::delete_workflow_handler),
+            )
+            .route("/workflows/{name}/run", post(routes::run_workflow_handler))
+            .route(
+                "/workflows/{name}/schedules",
+                post(routes::schedule_workflow_handler),
+            )
+            .route(
+                "/workflows/{name}/schedules/{id}",
+                delete(routes::cancel_schedule_handler),
+            )
+            // Runtime catalog endpoints
+            .route("/runtimes", get(routes::list_runtimes_handler))
+            .route(
+                "/runtimes/{name}",
+                get(routes::get_runtime_handler)
+                    .put(routes::put_runtime_handler)
+                    .delete(routes::delete_runtime_handler),
+            )
+            // Plugin endpoints
+            .route(
+                "/plugins",
+                get(routes::list_plugins_handler).post(routes::install_plugin_handler),
+            )
+            .route("/plugins/sync", post(routes::sync_plugins_handler))
+            .route(
+                "/plugins/{id}",
+                get(routes::get_plugin_handler).delete(routes::remove_plugin_handler),
+            )
+            .route("/plugins/{id}/enable", post(routes::enable_plugin_handler))
+            .route(
+                "/plugins/{id}/disable",
+                post(routes::disable_plugin_handler),
+            )
+            .route("/plugins/{id}/sync", post(routes::sync_plugin_handler))
+            // Search endpoints
+            .route("/search/messages", get(routes::search_messages_handler))
             // Command endpoints
             .route("/commands", get(routes::list_commands_handler))
             .route("/commands/compact", post(routes::compact_command_handler))
@@ -221,6 +358,19 @@ impl Server {
             ))
     }
 
+    /// OpenAI-compatible routes, authenticated like the v1 API.
+    fn openai_routes(&self) -> Router<AppState> {
+        use axum::routing::{get, post};
+
+        Router::new()
+            .route("/chat/completions", post(routes::chat_completions_handler))
+            .route("/models", get(routes::list_models_handler))
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::auth_middleware,
+            ))
+    }
+
     /// Run the server.
     pub async fn run(self) -> Result<()> {
         let addr = self.state.config().bind_address;
@@ -0,0 +1,124 @@
+//! Prometheus metrics for the server.
+//!
+//! [`install_recorder`] installs the process-wide Prometheus recorder that
+//! every crate's metrics (agent turns, LLM calls, tools, pipelines) are
+//! reported to. The server adds HTTP and WebSocket traffic and session cache
+//! lookups, and refreshes gauges for MCP servers, the memory store and the
+//! session cache whenever `/metrics` is scraped.
+
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+
+use crate::state::AppState;
+
+/// HTTP requests, by `method`, `route` (the matched route pattern) and `status`.
+pub const HTTP_REQUESTS_TOTAL: &str = "arawn_http_requests_total";
+/// HTTP request latency, by `method` and `route`.
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "arawn_http_request_duration_seconds";
+/// Open WebSocket connections.
+pub const WS_CONNECTIONS: &str = "arawn_ws_connections";
+/// WebSocket messages, by `direction` (`received`/`sent`).
+pub const WS_MESSAGES_TOTAL: &str = "arawn_ws_messages_total";
+/// Session cache lookups for an existing session, by `result` (`hit`/`miss`).
+pub const SESSION_CACHE_LOOKUPS_TOTAL: &str = "arawn_session_cache_lookups_total";
+/// Sessions currently held in the session cache.
+pub const SESSION_CACHE_SIZE: &str = "arawn_session_cache_size";
+/// Configured MCP servers, by `server`: 1 when connected, 0 otherwise.
+pub const MCP_SERVER_UP: &str = "arawn_mcp_server_up";
+/// Memory store entries, by `kind` (`memories`, `notes`, `sessions`, `embeddings`).
+pub const MEMORY_STORE_ENTRIES: &str = "arawn_memory_store_entries";
+
+/// Histogram buckets (seconds), spanning fast HTTP calls to long agent turns.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Install the global Prometheus recorder and return a handle for rendering.
+///
+/// Fails if a recorder is already installed.
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets(LATENCY_BUCKETS)?
+        .install_recorder()
+}
+
+/// Middleware recording request counts and latency per matched route.
+///
+/// Added with `Router::layer` so the matched route pattern is available;
+/// requests that match no route are labelled `unmatched`.
+pub async fn http_metrics_middleware(request: Request<Body>, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics::counter!(
+        HTTP_REQUESTS_TOTAL,
+        "method" => method.clone(),
+        "route" => route.clone(),
+        "status" => response.status().as_u16().to_string(),
+    )
+    .increment(1);
+    metrics::histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        "method" => method,
+        "route" => route,
+    )
+    .record(started.elapsed().as_secs_f64());
+    response
+}
+
+/// Record a session cache lookup.
+pub(crate) fn record_session_lookup(hit: bool) {
+    metrics::counter!(
+        SESSION_CACHE_LOOKUPS_TOTAL,
+        "result" => if hit { "hit" } else { "miss" },
+    )
+    .increment(1);
+}
+
+/// Record a WebSocket message.
+pub(crate) fn record_ws_message(direction: &'static str) {
+    metrics::counter!(WS_MESSAGES_TOTAL, "direction" => direction).increment(1);
+}
+
+/// Update gauges that are sampled rather than tracked as they change.
+pub(crate) async fn refresh_gauges(state: &AppState) {
+    metrics::gauge!(SESSION_CACHE_SIZE).set(state.session_cache().len().await as f64);
+
+    if let Some(manager) = state.mcp_manager() {
+        let manager = manager.read().await;
+        for name in manager.server_names() {
+            let up = if manager.is_connected(name) { 1.0 } else { 0.0 };
+            metrics::gauge!(MCP_SERVER_UP, "server" => name.to_string()).set(up);
+        }
+    }
+
+    if let Some(store) = state.memory_store() {
+        match store.stats() {
+            Ok(stats) => {
+                for (kind, count) in [
+                    ("memories", stats.memory_count),
+                    ("notes", stats.note_count),
+                    ("sessions", stats.session_count),
+                    ("embeddings", stats.embedding_count),
+                ] {
+                    metrics::gauge!(MEMORY_STORE_ENTRIES, "kind" => kind).set(count as f64);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to read memory store stats for metrics"),
+        }
+    }
+}
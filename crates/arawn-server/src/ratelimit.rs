@@ -4,7 +4,8 @@
 
 use std::net::IpAddr;
 use std::num::NonZeroU32;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
 
 use axum::{
     body::Body,
@@ -69,6 +70,54 @@ pub fn create_rate_limiter(requests_per_minute: u32) -> SharedRateLimiter {
     Arc::new(RateLimiter::keyed(quota))
 }
 
+/// The API rate limit in effect, which a config reload can change.
+///
+/// Starts from `ServerConfig::rate_limiting` / `api_rpm`. Changing the rate
+/// swaps in a fresh limiter, so per-IP budgets start over.
+#[derive(Debug)]
+pub struct ApiRateLimit {
+    enabled: AtomicBool,
+    rpm: AtomicU32,
+    limiter: RwLock<SharedRateLimiter>,
+}
+
+impl ApiRateLimit {
+    /// Create a rate limit of `rpm` requests per minute per IP.
+    pub fn new(enabled: bool, rpm: u32) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            rpm: AtomicU32::new(rpm),
+            limiter: RwLock::new(create_rate_limiter(rpm)),
+        }
+    }
+
+    /// Whether rate limiting is on.
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Requests per minute per IP.
+    pub fn rpm(&self) -> u32 {
+        self.rpm.load(Ordering::Relaxed)
+    }
+
+    /// The current per-IP limiter.
+    pub fn limiter(&self) -> SharedRateLimiter {
+        self.limiter
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Turn rate limiting on or off and set the rate.
+    pub fn update(&self, enabled: bool, rpm: u32) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if self.rpm.swap(rpm, Ordering::Relaxed) != rpm {
+            *self.limiter.write().unwrap_or_else(|e| e.into_inner()) = create_rate_limiter(rpm);
+        }
+    }
+}
+
 /// Extract client IP address from request headers.
 ///
 /// Checks in order:
@@ -119,14 +168,16 @@ fn extract_client_ip(request: &Request<Body>) -> IpAddr {
 /// other users. Extracts client IP from X-Forwarded-For, X-Real-IP headers,
 /// or falls back to the connection address.
 ///
-/// The rate limiter is created from `config.api_rpm` and stored in AppState.
+/// The limit starts from `config.api_rpm` and follows config reloads (see
+/// [`ApiRateLimit`]).
 pub async fn rate_limit_middleware(
     State(state): State<AppState>,
     request: Request<Body>,
     next: Next,
 ) -> Response {
     // Skip if rate limiting is disabled
-    if !state.config().rate_limiting {
+    let rate_limit = state.rate_limit();
+    if !rate_limit.enabled() {
         return next.run(request).await;
     }
 
@@ -134,7 +185,7 @@ pub async fn rate_limit_middleware(
     let client_ip = extract_client_ip(&request);
 
     // Check rate limit for this IP using the configured limiter
-    match state.rate_limiter().check_key(&client_ip) {
+    match rate_limit.limiter().check_key(&client_ip) {
         Ok(_) => next.run(request).await,
         Err(_not_until) => {
             // Use a fixed retry-after of 1 second for simplicity
@@ -144,7 +195,7 @@ pub async fn rate_limit_middleware(
             tracing::warn!(
                 path = %request.uri().path(),
                 client_ip = %client_ip,
-                api_rpm = %state.config().api_rpm,
+                api_rpm = %rate_limit.rpm(),
                 retry_after_seconds = retry_after,
                 "Rate limit exceeded"
             );
@@ -123,12 +123,8 @@ pub async fn chat_handler(
     }
 
     // Log chat request for audit trail
-    let identity_str = match &identity {
-        Identity::Token => "token".to_string(),
-        Identity::Tailscale { user } => format!("tailscale:{}", user),
-    };
     tracing::debug!(
-        identity = %identity_str,
+        identity = %identity,
         session_id = ?request.session_id,
         message_len = request.message.len(),
         "Chat request received"
@@ -141,8 +137,10 @@ pub async fn chat_handler(
         .and_then(|s| Uuid::parse_str(s).ok())
         .map(SessionId::from_uuid);
 
-    // Get or create session
-    let session_id = state.get_or_create_session(session_id).await;
+    // Get or create session (owned by the caller)
+    let session_id = state
+        .open_session(&identity, session_id, arawn_domain::SCRATCH_ID)
+        .await?;
 
     // Get session from cache
     let mut session = state
@@ -153,6 +151,15 @@ pub async fn chat_handler(
             ServerError::Internal("Session disappeared during processing".to_string())
         })?;
 
+    // Inherit the workstream's agent settings for this turn
+    if let Some(domain) = state.domain()
+        && let Some(workstream_id) = state.session_cache().get_workstream_id(&session_id).await
+    {
+        domain
+            .chat()
+            .apply_workstream_settings(&mut session, &workstream_id);
+    }
+
     // Execute turn
     let response = state
         .agent()
@@ -167,14 +174,17 @@ pub async fn chat_handler(
     state.update_session(session_id, session).await;
 
     // Persist the turn to workstream storage
-    if let Some(turn) = completed_turn
-        && let Some(workstream_id) = state.session_cache().get_workstream_id(&session_id).await
-        && let Err(e) = state
-            .session_cache()
-            .save_turn(session_id, &turn, &workstream_id)
-            .await
-    {
-        tracing::warn!("Failed to persist turn to workstream: {}", e);
+    if let Some(turn) = completed_turn {
+        let workstream_id = state.session_cache().get_workstream_id(&session_id).await;
+        state.emit_turn_webhooks(session_id, workstream_id.as_deref(), &turn);
+        if let Some(workstream_id) = workstream_id
+            && let Err(e) = state
+                .session_cache()
+                .save_turn(session_id, &turn, &workstream_id)
+                .await
+        {
+            tracing::warn!("Failed to persist turn to workstream: {}", e);
+        }
     }
 
     // Build response - warn if tool calls/results don't match
@@ -239,12 +249,8 @@ pub async fn chat_stream_handler(
     }
 
     // Log chat stream request for audit trail
-    let identity_str = match &identity {
-        Identity::Token => "token".to_string(),
-        Identity::Tailscale { user } => format!("tailscale:{}", user),
-    };
     tracing::debug!(
-        identity = %identity_str,
+        identity = %identity,
         session_id = ?request.session_id,
         message_len = request.message.len(),
         "Chat stream request received"
@@ -257,8 +263,10 @@ pub async fn chat_stream_handler(
         .and_then(|s| Uuid::parse_str(s).ok())
         .map(SessionId::from_uuid);
 
-    // Get or create session
-    let session_id = state.get_or_create_session(session_id).await;
+    // Get or create session (owned by the caller)
+    let session_id = state
+        .open_session(&identity, session_id, arawn_domain::SCRATCH_ID)
+        .await?;
 
     // Get session from cache
     let mut session = state
@@ -269,6 +277,15 @@ pub async fn chat_stream_handler(
             ServerError::Internal("Session disappeared during processing".to_string())
         })?;
 
+    // Inherit the workstream's agent settings for this turn
+    if let Some(domain) = state.domain()
+        && let Some(workstream_id) = state.session_cache().get_workstream_id(&session_id).await
+    {
+        domain
+            .chat()
+            .apply_workstream_settings(&mut session, &workstream_id);
+    }
+
     // Get the agent stream
     let cancellation = CancellationToken::new();
     let stream = state
@@ -17,10 +17,11 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use utoipa::ToSchema;
 
-use arawn_domain::{CompactionResult, CompactorConfig, SessionCompactor, SessionId};
+use arawn_domain::{CompactionResult, CompactorConfig, ResourceKind, SessionCompactor, SessionId};
 use uuid::Uuid;
 
-use crate::auth::Identity;
+use super::sessions::{ForkSessionRequest, fork_session};
+use crate::auth::{self, Identity};
 use crate::error::ServerError;
 use crate::state::AppState;
 
@@ -141,6 +142,7 @@ impl CommandRegistry {
             model: model.to_string(),
             ..Default::default()
         }));
+        registry.register(ForkCommand);
         registry
     }
 
@@ -345,6 +347,57 @@ impl CommandHandler for CompactCommand {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Fork Command
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parameters for the fork command.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForkParams {
+    /// Session to fork.
+    pub session_id: String,
+    /// Where to cut the history.
+    #[serde(flatten)]
+    pub request: ForkSessionRequest,
+}
+
+/// The fork command handler.
+///
+/// Creates a new session whose history is a prefix of an existing one.
+pub struct ForkCommand;
+
+#[async_trait]
+impl CommandHandler for ForkCommand {
+    fn name(&self) -> &str {
+        "fork"
+    }
+
+    fn description(&self) -> &str {
+        "Fork a session from an earlier message into a new session"
+    }
+
+    async fn execute(
+        &self,
+        state: &AppState,
+        params: serde_json::Value,
+    ) -> CommandResult<CommandOutput> {
+        let params: ForkParams = serde_json::from_value(params)
+            .map_err(|e| CommandError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        let response = fork_session(state, &params.session_id, &params.request)
+            .await
+            .map_err(|e| match e {
+                ServerError::NotFound(msg) => CommandError::not_found(msg),
+                ServerError::BadRequest(msg) => CommandError::invalid_params(msg),
+                other => CommandError::execution_failed(other.to_string()),
+            })?;
+
+        Ok(CommandOutput::Completed {
+            result: serde_json::to_value(response).unwrap(),
+        })
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -387,9 +440,15 @@ pub async fn list_commands_handler(
 )]
 pub async fn compact_command_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Json(request): Json<CompactRequest>,
 ) -> Result<Json<CompactResponse>, ServerError> {
+    auth::authorize(
+        &state,
+        &identity,
+        ResourceKind::Session,
+        &request.session_id,
+    )?;
     let model = &state.agent().config().model;
     let command = CompactCommand::new(CompactorConfig {
         model: model.clone(),
@@ -427,9 +486,15 @@ pub async fn compact_command_handler(
 )]
 pub async fn compact_command_stream_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Json(request): Json<CompactRequest>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>>, ServerError> {
+    auth::authorize(
+        &state,
+        &identity,
+        ResourceKind::Session,
+        &request.session_id,
+    )?;
     let uuid: Uuid = request
         .session_id
         .parse()
@@ -547,6 +612,21 @@ mod tests {
         assert!(commands.iter().any(|c| c.name == "compact"));
     }
 
+    #[test]
+    fn test_command_registry_with_compact_includes_fork() {
+        let registry = CommandRegistry::with_compact("test-model");
+        assert!(registry.get("fork").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fork_command_invalid_session_id() {
+        let state = create_test_state();
+        let result = ForkCommand
+            .execute(&state, serde_json::json!({ "session_id": "not-a-uuid" }))
+            .await;
+        assert_eq!(result.unwrap_err().code, "invalid_params");
+    }
+
     #[test]
     fn test_command_registry_register_and_lookup() {
         let mut registry = CommandRegistry::new();
@@ -1,11 +1,20 @@
-//! Configuration endpoint.
+//! Configuration endpoints.
 //!
-//! Exposes non-sensitive server configuration for clients.
+//! Exposes non-sensitive server configuration for clients, and lets admins
+//! replace or patch the `arawn.toml` configuration. Updates are validated,
+//! saved to the config file layer that defines each changed section, and
+//! applied live where possible (LLM profiles, tool output limits, recall,
+//! rate limits, MCP servers); the response lists the keys that still need
+//! a restart.
+
+use std::sync::Arc;
 
 use axum::{Extension, Json, extract::State};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use arawn_domain::{ArawnConfig, ConfigService, ConfigUpdate};
+
 use crate::auth::Identity;
 use crate::error::ServerError;
 use crate::state::AppState;
@@ -58,6 +67,47 @@ pub struct ConfigResponse {
     pub auth_required: bool,
 }
 
+/// Result of updating the configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigUpdateResponse {
+    /// Every changed key (dotted, e.g. `llm_profiles.fast.model`), sorted.
+    pub changed: Vec<String>,
+    /// Changed keys that took effect immediately.
+    pub applied: Vec<String>,
+    /// Changed keys that take effect after `arawn start` is restarted.
+    pub restart_required: Vec<String>,
+    /// Config files that were written.
+    pub persisted: Vec<String>,
+    /// Problems applying accepted changes (e.g. an MCP server that failed to start).
+    pub warnings: Vec<String>,
+}
+
+impl From<ConfigUpdate> for ConfigUpdateResponse {
+    fn from(u: ConfigUpdate) -> Self {
+        Self {
+            changed: u.changed,
+            applied: u.applied,
+            restart_required: u.restart_required,
+            persisted: u
+                .persisted
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect(),
+            warnings: u.warnings,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn require_config_service(state: &AppState) -> Result<&Arc<ConfigService>, ServerError> {
+    state
+        .config_service()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Config updates not enabled".to_string()))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -84,18 +134,16 @@ pub async fn get_config_handler(
         memory_enabled: state.memory_store().is_some(),
         embeddings_enabled: state.indexer().is_some(),
         mcp_enabled: state.mcp_manager().is_some(),
-        rate_limiting: config.rate_limiting,
+        rate_limiting: state.rate_limit().enabled(),
         request_logging: config.request_logging,
     };
 
-    // Use actual rate limit config values instead of hardcoded defaults
+    // Report the API requests per minute currently in effect
     let limits = ConfigLimits {
-        max_concurrent_requests: if config.rate_limiting {
-            // Return the API requests per minute from rate limit config
-            Some(crate::ratelimit::RateLimitConfig::default().api_rpm)
-        } else {
-            None
-        },
+        max_concurrent_requests: state
+            .rate_limit()
+            .enabled()
+            .then(|| state.rate_limit().rpm()),
     };
 
     Ok(Json(ConfigResponse {
@@ -108,6 +156,63 @@ pub async fn get_config_handler(
     }))
 }
 
+/// PUT /api/v1/config - Replace the configuration.
+///
+/// The body is the whole config in JSON (the same structure as `arawn.toml`).
+#[utoipa::path(
+    put,
+    path = "/api/v1/config",
+    request_body(content = Object, description = "Complete configuration, shaped like arawn.toml"),
+    responses(
+        (status = 200, description = "Configuration saved; lists what was applied and what needs a restart", body = ConfigUpdateResponse),
+        (status = 400, description = "Body is not a valid configuration"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 422, description = "Configuration failed validation"),
+        (status = 503, description = "Config updates not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "config"
+)]
+pub async fn put_config_handler(
+    State(state): State<AppState>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<ConfigUpdateResponse>, ServerError> {
+    let service = require_config_service(&state)?;
+    let config: ArawnConfig = serde_json::from_value(body)
+        .map_err(|e| ServerError::BadRequest(format!("invalid config: {}", e)))?;
+    let update = service.replace(config).await?;
+    Ok(Json(update.into()))
+}
+
+/// PATCH /api/v1/config - Update part of the configuration.
+///
+/// The body is a JSON merge patch (RFC 7386): objects are merged, `null`
+/// removes a key, and anything else replaces it.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/config",
+    request_body(content = Object, description = "JSON merge patch against the current configuration"),
+    responses(
+        (status = 200, description = "Configuration saved; lists what was applied and what needs a restart", body = ConfigUpdateResponse),
+        (status = 400, description = "Patch does not produce a valid configuration"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 422, description = "Configuration failed validation"),
+        (status = 503, description = "Config updates not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "config"
+)]
+pub async fn patch_config_handler(
+    State(state): State<AppState>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<ConfigUpdateResponse>, ServerError> {
+    let service = require_config_service(&state)?;
+    let update = service.patch(patch).await?;
+    Ok(Json(update.into()))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -118,6 +223,7 @@ mod tests {
     use crate::auth::auth_middleware;
     use crate::config::ServerConfig;
     use arawn_domain::{Agent, ToolRegistry};
+    use arawn_domain::{ConfigService, ConfigSource};
     use arawn_llm::MockBackend;
     use axum::{
         Router,
@@ -126,6 +232,7 @@ mod tests {
         middleware,
         routing::get,
     };
+    use tempfile::TempDir;
     use tower::ServiceExt;
 
     fn create_test_state() -> AppState {
@@ -141,7 +248,12 @@ mod tests {
 
     fn create_test_router(state: AppState) -> Router {
         Router::new()
-            .route("/config", get(get_config_handler))
+            .route(
+                "/config",
+                get(get_config_handler)
+                    .put(put_config_handler)
+                    .patch(patch_config_handler),
+            )
             .layer(middleware::from_fn_with_state(
                 state.clone(),
                 auth_middleware,
@@ -196,4 +308,71 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    fn with_config_file(state: AppState, dir: &TempDir) -> AppState {
+        let path = dir.path().join("arawn.toml");
+        std::fs::write(&path, "[server]\napi_rpm = 120\n").unwrap();
+        let config = arawn_domain::ArawnConfig::from_toml("[server]\napi_rpm = 120\n").unwrap();
+        let source = ConfigSource { path, loaded: true };
+        state.with_config_service(ConfigService::new(config, vec![source]))
+    }
+
+    fn patch_request(body: &str) -> Request<Body> {
+        Request::builder()
+            .method("PATCH")
+            .uri("/config")
+            .header("Authorization", "Bearer test-token")
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_applies_rate_limit() {
+        let dir = TempDir::new().unwrap();
+        let state = with_config_file(create_test_state(), &dir);
+        let app = create_test_router(state.clone());
+
+        let response = app
+            .oneshot(patch_request(
+                r#"{"server": {"api_rpm": 30, "port": 9999}}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: ConfigUpdateResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(result.applied, vec!["server.api_rpm"]);
+        assert_eq!(result.restart_required, vec!["server.port"]);
+        assert_eq!(result.persisted.len(), 1);
+        assert_eq!(state.rate_limit().rpm(), 30);
+
+        let saved = std::fs::read_to_string(dir.path().join("arawn.toml")).unwrap();
+        assert!(saved.contains("api_rpm = 30"));
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_rejects_invalid() {
+        let dir = TempDir::new().unwrap();
+        let state = with_config_file(create_test_state(), &dir);
+        let app = create_test_router(state.clone());
+
+        let response = app
+            .oneshot(patch_request(r#"{"server": {"api_rpm": 0}}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(state.rate_limit().rpm(), 120);
+    }
+
+    #[tokio::test]
+    async fn test_patch_config_without_service() {
+        let app = create_test_router(create_test_state());
+
+        let response = app.oneshot(patch_request("{}")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
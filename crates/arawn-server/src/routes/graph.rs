@@ -0,0 +1,799 @@
+//! Knowledge graph endpoints.
+//!
+//! Read access to the entity graph built by session indexing: listing and
+//! searching entities, entity detail with relationships and linked
+//! memories, N-hop traversal, read-only Cypher queries and full exports.
+//! All of them return 503 when the memory store has no graph.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use arawn_domain::{
+    GraphNode, GraphRelationship, GraphStore, MemoryError, MemoryId, MemoryStore, ResourceKind,
+    Subgraph,
+};
+
+use super::memory::{memory_visible, require_memory_store};
+use super::pagination::PaginationParams;
+use crate::auth::{self, Identity};
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// Maximum traversal depth for `/neighbors`.
+pub const MAX_HOPS: usize = 3;
+
+/// Maximum nodes returned by a traversal.
+pub const MAX_TRAVERSAL_NODES: usize = 500;
+
+/// Default Cypher query timeout.
+pub const DEFAULT_QUERY_TIMEOUT_MS: u64 = 5_000;
+
+/// Upper bound on a requested Cypher query timeout.
+pub const MAX_QUERY_TIMEOUT_MS: u64 = 30_000;
+
+/// Maximum rows returned by a Cypher query.
+pub const MAX_QUERY_ROWS: usize = 1_000;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// An entity in the knowledge graph.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GraphEntity {
+    /// Entity ID (lowercased, underscored name).
+    pub id: String,
+    /// Entity label, e.g. `Person`, `Concept` or `Memory`.
+    pub label: String,
+    /// Entity properties.
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+impl From<GraphNode> for GraphEntity {
+    fn from(node: GraphNode) -> Self {
+        Self {
+            id: node.id,
+            label: node.label,
+            properties: node.properties.into_iter().collect(),
+        }
+    }
+}
+
+/// A directed relationship between two entities.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GraphEdge {
+    /// Source entity ID.
+    pub source: String,
+    /// Target entity ID.
+    pub target: String,
+    /// Relationship type, e.g. `RELATED_TO`.
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    /// Relationship properties.
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+}
+
+impl From<GraphRelationship> for GraphEdge {
+    fn from(rel: GraphRelationship) -> Self {
+        Self {
+            source: rel.from_id,
+            target: rel.to_id,
+            rel_type: rel.rel_type.as_str().to_string(),
+            properties: rel.properties.into_iter().collect(),
+        }
+    }
+}
+
+/// Knowledge graph size.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GraphStatsResponse {
+    /// Number of nodes, including memory nodes.
+    pub node_count: usize,
+    /// Number of relationships.
+    pub relationship_count: usize,
+}
+
+/// Query params for listing entities.
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+pub struct ListEntitiesQuery {
+    /// Only entities with this label.
+    pub label: Option<String>,
+    /// Case-insensitive substring of the ID, name or context.
+    pub q: Option<String>,
+}
+
+/// A page of entities.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ListEntitiesResponse {
+    /// Entities ordered by ID.
+    pub entities: Vec<GraphEntity>,
+    /// Total number of matching entities.
+    pub total: usize,
+    /// Maximum items per page (as requested).
+    pub limit: usize,
+    /// Offset from the start of the collection.
+    pub offset: usize,
+}
+
+/// A memory that mentions an entity.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LinkedMemory {
+    /// Memory ID.
+    pub id: String,
+    /// Content type.
+    pub content_type: String,
+    /// Content text.
+    pub content: String,
+    /// Session the memory belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// An entity with its relationships and linked memories.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EntityDetailResponse {
+    /// The entity.
+    pub entity: GraphEntity,
+    /// Relationships in both directions.
+    pub relationships: Vec<GraphEdge>,
+    /// Memories linked to the entity that the caller may see.
+    pub memories: Vec<LinkedMemory>,
+}
+
+/// Query params for traversals.
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct NeighborsQuery {
+    /// Relationships to follow from the entity (default: 1, max: 3).
+    #[serde(default = "default_hops")]
+    pub hops: usize,
+    /// Maximum nodes to return (default: 100, max: 500).
+    #[serde(default = "default_node_limit")]
+    pub limit: usize,
+}
+
+fn default_hops() -> usize {
+    1
+}
+
+fn default_node_limit() -> usize {
+    100
+}
+
+/// Nodes and the relationships between them.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SubgraphResponse {
+    /// Nodes, starting with the entity traversed from.
+    pub nodes: Vec<GraphEntity>,
+    /// Relationships between the nodes.
+    pub relationships: Vec<GraphEdge>,
+    /// Whether nodes were left out to stay within `limit`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl From<Subgraph> for SubgraphResponse {
+    fn from(subgraph: Subgraph) -> Self {
+        Self {
+            nodes: subgraph.nodes.into_iter().map(Into::into).collect(),
+            relationships: subgraph.relationships.into_iter().map(Into::into).collect(),
+            truncated: subgraph.truncated,
+        }
+    }
+}
+
+/// A read-only Cypher query.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CypherQueryRequest {
+    /// The query; write clauses such as `CREATE` or `SET` are rejected.
+    pub query: String,
+    /// Timeout in milliseconds (default: 5000, max: 30000).
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Rows returned by a Cypher query.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CypherQueryResponse {
+    /// Column names.
+    pub columns: Vec<String>,
+    /// One object per row, keyed by column name.
+    #[schema(value_type = Vec<Object>)]
+    pub rows: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// Number of rows returned.
+    pub row_count: usize,
+    /// Whether rows beyond the first 1000 were dropped.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Graph export format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// `{ "nodes": [...], "relationships": [...] }`.
+    #[default]
+    Json,
+    /// GraphML XML, for Gephi, yEd and Cytoscape.
+    Graphml,
+    /// Graphviz DOT.
+    Dot,
+}
+
+/// Query params for exports.
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+pub struct ExportQuery {
+    /// Output format (default: json).
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Get the memory store, returning 503 unless its knowledge graph is enabled.
+fn require_graph(state: &AppState) -> Result<Arc<MemoryStore>, ServerError> {
+    let store = require_memory_store(state)?;
+    if !store.has_graph() {
+        return Err(ServerError::ServiceUnavailable(
+            "Knowledge graph not enabled".to_string(),
+        ));
+    }
+    Ok(store.clone())
+}
+
+/// Run a blocking graph operation off the async runtime.
+async fn with_graph<T, F>(store: Arc<MemoryStore>, f: F) -> Result<T, ServerError>
+where
+    T: Send + 'static,
+    F: FnOnce(&GraphStore) -> Result<T, MemoryError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let graph = store.graph().ok_or_else(|| {
+            ServerError::ServiceUnavailable("Knowledge graph not enabled".to_string())
+        })?;
+        f(graph).map_err(graph_error)
+    })
+    .await
+    .map_err(|e| ServerError::Internal(format!("Graph task failed: {}", e)))?
+}
+
+/// Invalid queries and timeouts are the caller's problem; the rest are ours.
+fn graph_error(e: MemoryError) -> ServerError {
+    match e {
+        MemoryError::Query(_) | MemoryError::Timeout(_) => ServerError::BadRequest(e.to_string()),
+        e => ServerError::Internal(format!("Graph error: {}", e)),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/memory/graph - Knowledge graph statistics.
+#[utoipa::path(
+    get,
+    path = "/api/v1/memory/graph",
+    responses(
+        (status = 200, description = "Graph statistics", body = GraphStatsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn graph_stats_handler(
+    State(state): State<AppState>,
+) -> Result<Json<GraphStatsResponse>, ServerError> {
+    let store = require_graph(&state)?;
+    let stats = with_graph(store, |graph| graph.stats()).await?;
+    Ok(Json(GraphStatsResponse {
+        node_count: stats.node_count,
+        relationship_count: stats.relationship_count,
+    }))
+}
+
+/// GET /api/v1/memory/graph/entities - List and search entities.
+///
+/// Memory nodes are left out unless `label=Memory` is given.
+#[utoipa::path(
+    get,
+    path = "/api/v1/memory/graph/entities",
+    params(ListEntitiesQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Entities ordered by ID", body = ListEntitiesResponse),
+        (status = 400, description = "Invalid label"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn list_entities_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ListEntitiesQuery>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<ListEntitiesResponse>, ServerError> {
+    let store = require_graph(&state)?;
+    let limit = pagination.effective_limit();
+    let offset = pagination.offset;
+
+    let (nodes, total) = with_graph(store, move |graph| {
+        graph.list_entities(query.label.as_deref(), query.q.as_deref(), limit, offset)
+    })
+    .await?;
+
+    Ok(Json(ListEntitiesResponse {
+        entities: nodes.into_iter().map(Into::into).collect(),
+        total,
+        limit,
+        offset,
+    }))
+}
+
+/// GET /api/v1/memory/graph/entities/:id - Get an entity.
+///
+/// Includes relationships in both directions and the memories linked to
+/// the entity that the caller may see.
+#[utoipa::path(
+    get,
+    path = "/api/v1/memory/graph/entities/{id}",
+    params(("id" = String, Path, description = "Entity ID")),
+    responses(
+        (status = 200, description = "Entity found", body = EntityDetailResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Entity not found"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn get_entity_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(id): Path<String>,
+) -> Result<Json<EntityDetailResponse>, ServerError> {
+    let store = require_graph(&state)?;
+
+    let lookup = id.clone();
+    let (entity, relationships, memory_ids) = with_graph(store.clone(), move |graph| {
+        let Some(entity) = graph.get_entity(&lookup)? else {
+            return Ok(None);
+        };
+        let relationships = graph.get_relationships(&lookup)?;
+        let memory_ids = graph.linked_memory_ids(&lookup)?;
+        Ok(Some((entity, relationships, memory_ids)))
+    })
+    .await?
+    .ok_or_else(|| ServerError::NotFound(format!("Entity {} not found", id)))?;
+
+    let visible_memories = auth::visible_ids(&state, &identity, ResourceKind::Memory);
+    let visible_sessions = auth::visible_ids(&state, &identity, ResourceKind::Session);
+    let mut memories = Vec::new();
+    for memory_id in memory_ids {
+        let Ok(uuid) = uuid::Uuid::parse_str(&memory_id) else {
+            continue;
+        };
+        let memory = match store.get_memory(MemoryId(uuid)) {
+            Ok(Some(memory)) => memory,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!(memory_id = %memory_id, error = %e, "Failed to load linked memory");
+                continue;
+            }
+        };
+        if !memory_visible(
+            visible_memories.as_ref(),
+            visible_sessions.as_ref(),
+            &memory,
+        ) {
+            continue;
+        }
+        memories.push(LinkedMemory {
+            id: memory_id,
+            content_type: memory.content_type.as_str().to_string(),
+            content: memory.content,
+            session_id: memory.session_id,
+        });
+    }
+
+    Ok(Json(EntityDetailResponse {
+        entity: entity.into(),
+        relationships: relationships.into_iter().map(Into::into).collect(),
+        memories,
+    }))
+}
+
+/// GET /api/v1/memory/graph/entities/:id/neighbors - Traverse from an entity.
+///
+/// Follows relationships in either direction for up to `hops` steps.
+#[utoipa::path(
+    get,
+    path = "/api/v1/memory/graph/entities/{id}/neighbors",
+    params(("id" = String, Path, description = "Entity ID"), NeighborsQuery),
+    responses(
+        (status = 200, description = "Entities within `hops` relationships", body = SubgraphResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Entity not found"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn entity_neighbors_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<NeighborsQuery>,
+) -> Result<Json<SubgraphResponse>, ServerError> {
+    let store = require_graph(&state)?;
+    let hops = query.hops.clamp(1, MAX_HOPS);
+    let limit = query.limit.clamp(1, MAX_TRAVERSAL_NODES);
+
+    let lookup = id.clone();
+    let subgraph = with_graph(store, move |graph| graph.neighborhood(&lookup, hops, limit))
+        .await?
+        .ok_or_else(|| ServerError::NotFound(format!("Entity {} not found", id)))?;
+
+    Ok(Json(subgraph.into()))
+}
+
+/// POST /api/v1/memory/graph/query - Run a read-only Cypher query.
+///
+/// Requires the `admin` scope. Queries containing write clauses are
+/// rejected, and queries running past the timeout are interrupted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/memory/graph/query",
+    request_body = CypherQueryRequest,
+    responses(
+        (status = 200, description = "Query rows", body = CypherQueryResponse),
+        (status = 400, description = "Invalid query, write clause, or timeout"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn graph_query_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CypherQueryRequest>,
+) -> Result<Json<CypherQueryResponse>, ServerError> {
+    let store = require_graph(&state)?;
+    if request.query.trim().is_empty() {
+        return Err(ServerError::BadRequest("query is required".to_string()));
+    }
+    let timeout = Duration::from_millis(
+        request
+            .timeout_ms
+            .unwrap_or(DEFAULT_QUERY_TIMEOUT_MS)
+            .clamp(1, MAX_QUERY_TIMEOUT_MS),
+    );
+
+    let mut result = with_graph(store, move |graph| {
+        graph.query_read_only(&request.query, timeout)
+    })
+    .await?;
+
+    let truncated = result.rows.len() > MAX_QUERY_ROWS;
+    result.rows.truncate(MAX_QUERY_ROWS);
+    Ok(Json(CypherQueryResponse {
+        columns: result.columns,
+        row_count: result.rows.len(),
+        rows: result.rows,
+        truncated,
+    }))
+}
+
+/// GET /api/v1/memory/graph/export - Export the whole graph.
+///
+/// Requires the `admin` scope. Returns JSON, GraphML or DOT depending on
+/// `format`, including memory nodes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/memory/graph/export",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "The graph in the requested format", content(
+            (SubgraphResponse = "application/json"),
+            (String = "application/graphml+xml"),
+            (String = "text/vnd.graphviz"),
+        )),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Knowledge graph not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "graph"
+)]
+pub async fn graph_export_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ServerError> {
+    let store = require_graph(&state)?;
+    let subgraph = with_graph(store, |graph| graph.export()).await?;
+
+    let (content_type, filename, body) = match query.format {
+        ExportFormat::Json => {
+            let body = serde_json::to_string_pretty(&SubgraphResponse::from(subgraph))?;
+            ("application/json", "arawn-graph.json", body)
+        }
+        ExportFormat::Graphml => (
+            "application/graphml+xml",
+            "arawn-graph.graphml",
+            subgraph.to_graphml(),
+        ),
+        ExportFormat::Dot => ("text/vnd.graphviz", "arawn-graph.dot", subgraph.to_dot()),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::auth_middleware;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, ContentType, MEMORY_LABEL, Memory, RelationshipType, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::{get, post},
+    };
+    use tower::ServiceExt;
+
+    fn create_test_state(with_graph: bool) -> (AppState, String) {
+        let backend = MockBackend::with_text("Test");
+        let agent = Agent::builder()
+            .with_backend(backend)
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+
+        let mut store = MemoryStore::open_in_memory().unwrap();
+        let mut memory_id = String::new();
+        if with_graph {
+            store.init_graph().unwrap();
+            let memory = Memory::new(ContentType::Fact, "Rust was created at Mozilla");
+            store.insert_memory(&memory).unwrap();
+            memory_id = memory.id.to_string();
+            for (id, label) in [
+                ("rust", "Language"),
+                ("mozilla", "Organization"),
+                ("ownership", "Concept"),
+            ] {
+                store
+                    .add_graph_entity(&GraphNode::new(id, label).with_property("name", id))
+                    .unwrap();
+            }
+            store
+                .add_graph_entity(&GraphNode::new(&memory_id, MEMORY_LABEL))
+                .unwrap();
+            for (from, to, rel_type) in [
+                ("rust", "mozilla", RelationshipType::CreatedBy),
+                ("ownership", "rust", RelationshipType::PartOf),
+                (memory_id.as_str(), "rust", RelationshipType::Mentions),
+            ] {
+                store
+                    .add_graph_relationship(&GraphRelationship::new(from, to, rel_type))
+                    .unwrap();
+            }
+        }
+
+        let mut state = AppState::new(agent, ServerConfig::new(Some("test-token".to_string())));
+        state.services.memory_store = Some(Arc::new(store));
+        (state, memory_id)
+    }
+
+    fn create_test_router(state: AppState) -> Router {
+        Router::new()
+            .route("/memory/graph", get(graph_stats_handler))
+            .route("/memory/graph/entities", get(list_entities_handler))
+            .route("/memory/graph/entities/{id}", get(get_entity_handler))
+            .route(
+                "/memory/graph/entities/{id}/neighbors",
+                get(entity_neighbors_handler),
+            )
+            .route("/memory/graph/query", post(graph_query_handler))
+            .route("/memory/graph/export", get(graph_export_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        body: Option<&str>,
+    ) -> (StatusCode, String, Option<String>) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", "Bearer test-token")
+            .header("Content-Type", "application/json")
+            .body(body.map(|b| Body::from(b.to_string())).unwrap_or_default())
+            .unwrap();
+        let response = create_test_router(state.clone())
+            .oneshot(request)
+            .await
+            .unwrap();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap().to_string());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            String::from_utf8(body.to_vec()).unwrap(),
+            content_type,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_graph_not_enabled() {
+        let (state, _) = create_test_state(false);
+        let (status, _, _) = send(&state, "GET", "/memory/graph/entities", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_stats_and_list_entities() {
+        let (state, _) = create_test_state(true);
+
+        let (status, body, _) = send(&state, "GET", "/memory/graph", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let stats: GraphStatsResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(stats.node_count, 4);
+        assert_eq!(stats.relationship_count, 3);
+
+        let (_, body, _) = send(&state, "GET", "/memory/graph/entities?limit=2", None).await;
+        let page: ListEntitiesResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(page.total, 3);
+        let ids: Vec<_> = page.entities.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["mozilla", "ownership"]);
+
+        let (_, body, _) = send(&state, "GET", "/memory/graph/entities?q=RUS", None).await;
+        let page: ListEntitiesResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(page.entities.len(), 1);
+        assert_eq!(page.entities[0].properties["name"], "rust");
+
+        let (status, _, _) = send(
+            &state,
+            "GET",
+            "/memory/graph/entities?label=Bad%20Label",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_entity_with_memories() {
+        let (state, memory_id) = create_test_state(true);
+
+        let (status, body, _) = send(&state, "GET", "/memory/graph/entities/rust", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let detail: EntityDetailResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(detail.entity.label, "Language");
+        assert_eq!(detail.relationships.len(), 3);
+        assert_eq!(detail.memories.len(), 1);
+        assert_eq!(detail.memories[0].id, memory_id);
+        assert_eq!(detail.memories[0].content, "Rust was created at Mozilla");
+
+        let (status, _, _) = send(&state, "GET", "/memory/graph/entities/python", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_neighbors() {
+        let (state, _) = create_test_state(true);
+
+        let (status, body, _) = send(
+            &state,
+            "GET",
+            "/memory/graph/entities/ownership/neighbors",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let one: SubgraphResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(one.nodes.len(), 2);
+        assert_eq!(one.nodes[0].id, "ownership");
+
+        let (_, body, _) = send(
+            &state,
+            "GET",
+            "/memory/graph/entities/ownership/neighbors?hops=2",
+            None,
+        )
+        .await;
+        let two: SubgraphResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(two.nodes.len(), 4);
+        assert_eq!(two.relationships.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_query() {
+        let (state, _) = create_test_state(true);
+
+        let (status, body, _) = send(
+            &state,
+            "POST",
+            "/memory/graph/query",
+            Some(r#"{"query": "MATCH (n:Concept) RETURN n.id AS id"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let result: CypherQueryResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.columns, vec!["id"]);
+        assert_eq!(result.row_count, 1);
+        assert_eq!(result.rows[0]["id"], "ownership");
+
+        let (status, body, _) = send(
+            &state,
+            "POST",
+            "/memory/graph/query",
+            Some(r#"{"query": "MATCH (n) DETACH DELETE n"}"#),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.contains("DETACH"));
+    }
+
+    #[tokio::test]
+    async fn test_export_formats() {
+        let (state, _) = create_test_state(true);
+
+        let (status, body, content_type) = send(&state, "GET", "/memory/graph/export", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        let export: SubgraphResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(export.nodes.len(), 4);
+        assert_eq!(export.relationships.len(), 3);
+
+        let (_, body, content_type) =
+            send(&state, "GET", "/memory/graph/export?format=graphml", None).await;
+        assert_eq!(content_type.as_deref(), Some("application/graphml+xml"));
+        assert!(body.contains("<graphml"));
+
+        let (_, body, content_type) =
+            send(&state, "GET", "/memory/graph/export?format=dot", None).await;
+        assert_eq!(content_type.as_deref(), Some("text/vnd.graphviz"));
+        assert!(body.contains(r#""rust" -> "mozilla""#));
+    }
+}
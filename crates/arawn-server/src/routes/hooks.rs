@@ -0,0 +1,341 @@
+//! Inbound webhook trigger endpoint.
+//!
+//! `POST /hooks/{name}` verifies the request against the hook's secret,
+//! maps the payload and starts the hook's target in the background. The
+//! run is tracked as a `hook` task (see `/api/v1/tasks/{id}`).
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use utoipa::ToSchema;
+
+use crate::auth::Identity;
+use crate::error::ServerError;
+use crate::hooks::{HookTarget, InboundHook, parse_payload};
+use crate::routes::workflows::RunWorkflowResponse;
+use crate::state::{AppState, TaskStatus, TrackedTask};
+
+/// Task type of hook runs.
+pub const HOOK_TASK_TYPE: &str = "hook";
+
+/// A hook run that was started.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HookAcceptedResponse {
+    /// Task tracking the run (see `/api/v1/tasks/{id}`).
+    pub task_id: String,
+    /// Hook that was triggered.
+    pub hook: String,
+}
+
+/// POST /hooks/{name} - Trigger an inbound webhook.
+///
+/// Authenticated with the hook's own secret rather than the server token:
+/// an HMAC-SHA256 signature of the raw body, or the token itself.
+#[utoipa::path(
+    post,
+    path = "/hooks/{name}",
+    params(("name" = String, Path, description = "Hook name")),
+    request_body(content = Object, description = "Arbitrary payload; non-JSON bodies are passed as a string"),
+    responses(
+        (status = 202, description = "Run started as a task", body = HookAcceptedResponse),
+        (status = 401, description = "Missing or invalid signature or token"),
+        (status = 404, description = "Unknown hook"),
+        (status = 503, description = "Hook target not available"),
+    ),
+    tag = "hooks"
+)]
+pub async fn trigger_hook_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<HookAcceptedResponse>), ServerError> {
+    let hook = state
+        .inbound_hooks()
+        .and_then(|hooks| hooks.get(&name))
+        .cloned()
+        .ok_or_else(|| ServerError::NotFound(format!("Hook '{name}'")))?;
+    if !hook.auth.verify(&headers, &body) {
+        tracing::warn!(hook = %name, "Rejected inbound webhook with invalid credentials");
+        return Err(ServerError::Unauthorized(
+            "Invalid webhook signature or token".to_string(),
+        ));
+    }
+
+    let description = match &hook.target {
+        HookTarget::Workflow(workflow) => {
+            if state.workflows().is_none() {
+                return Err(ServerError::ServiceUnavailable(
+                    "Workflows not enabled".to_string(),
+                ));
+            }
+            format!("Hook {name}: running workflow {workflow}")
+        }
+        HookTarget::Workstream(workstream) => {
+            if state.workstreams().is_none() {
+                return Err(ServerError::ServiceUnavailable(
+                    "Workstreams not configured".to_string(),
+                ));
+            }
+            format!("Hook {name}: chat turn in workstream {workstream}")
+        }
+    };
+
+    let values = hook.values(&parse_payload(&body));
+    let task_id = uuid::Uuid::new_v4().to_string();
+    let mut task = TrackedTask::new(&task_id, HOOK_TASK_TYPE);
+    task.message = Some(description);
+    task.start();
+    state.tasks().write().await.insert(task_id.clone(), task);
+    tracing::info!(hook = %name, task_id = %task_id, "Inbound webhook triggered");
+
+    let id = task_id.clone();
+    tokio::spawn(async move {
+        let result = run_hook(&state, &hook, values).await;
+        let mut tasks = state.tasks().write().await;
+        let Some(task) = tasks.get_mut(&id) else {
+            return;
+        };
+        if task.status == TaskStatus::Cancelled {
+            return;
+        }
+        match result {
+            Ok(outcome) => {
+                match outcome.error {
+                    Some(error) => task.fail(error),
+                    None => task.complete(Some(format!("Hook {} finished", hook.name))),
+                }
+                if let Some(session_id) = outcome.session_id {
+                    task.session_id = Some(session_id);
+                }
+                task.result = Some(outcome.result);
+            }
+            Err(e) => task.fail(e.to_string()),
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(HookAcceptedResponse {
+            task_id,
+            hook: name,
+        }),
+    ))
+}
+
+/// What a finished hook run reports on its task.
+struct HookOutcome {
+    result: Value,
+    error: Option<String>,
+    session_id: Option<String>,
+}
+
+async fn run_hook(
+    state: &AppState,
+    hook: &InboundHook,
+    values: Map<String, Value>,
+) -> Result<HookOutcome, ServerError> {
+    match &hook.target {
+        HookTarget::Workflow(workflow) => {
+            let workflows = state.workflows().ok_or_else(|| {
+                ServerError::ServiceUnavailable("Workflows not enabled".to_string())
+            })?;
+            let response = RunWorkflowResponse::from(workflows.trigger(workflow, values).await?);
+            Ok(HookOutcome {
+                error: response.error.clone(),
+                result: serde_json::to_value(&response)?,
+                session_id: None,
+            })
+        }
+        HookTarget::Workstream(workstream) => {
+            let prompt = hook.render_message(&values);
+            run_turn(state, workstream, &prompt).await
+        }
+    }
+}
+
+/// Run a chat turn in a new session of the workstream with the given ID or title.
+async fn run_turn(
+    state: &AppState,
+    workstream: &str,
+    prompt: &str,
+) -> Result<HookOutcome, ServerError> {
+    let mgr = state
+        .workstreams()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Workstreams not configured".to_string()))?;
+    let workstream_id = match mgr.get_workstream(workstream) {
+        Ok(ws) => ws.id,
+        Err(_) => mgr
+            .list_workstreams()?
+            .into_iter()
+            .find(|ws| ws.title.eq_ignore_ascii_case(workstream))
+            .map(|ws| ws.id)
+            .ok_or_else(|| ServerError::NotFound(format!("Workstream '{workstream}'")))?,
+    };
+
+    let session_id = state
+        .open_session(&Identity::Token, None, &workstream_id)
+        .await?;
+    let mut session = state
+        .session_cache()
+        .get(&session_id)
+        .await
+        .ok_or_else(|| {
+            ServerError::Internal("Session disappeared during processing".to_string())
+        })?;
+    if let Some(domain) = state.domain() {
+        domain
+            .chat()
+            .apply_workstream_settings(&mut session, &workstream_id);
+    }
+
+    let response = state
+        .agent()
+        .turn(&mut session, prompt, Some(&workstream_id))
+        .await
+        .map_err(ServerError::Agent)?;
+
+    let completed_turn = session.current_turn().cloned();
+    state.update_session(session_id, session).await;
+    if let Some(turn) = completed_turn {
+        state.emit_turn_webhooks(session_id, Some(&workstream_id), &turn);
+        if let Err(e) = state
+            .session_cache()
+            .save_turn(session_id, &turn, &workstream_id)
+            .await
+        {
+            tracing::warn!("Failed to persist turn to workstream: {}", e);
+        }
+    }
+
+    Ok(HookOutcome {
+        result: json!({
+            "session_id": session_id.to_string(),
+            "workstream_id": workstream_id,
+            "response": response.text,
+            "truncated": response.truncated,
+        }),
+        error: None,
+        session_id: Some(session_id.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::{HookAuth, InboundHooks, TOKEN_HEADER};
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use axum::{Router, body::Body, http::Request, routing::post};
+    use tower::ServiceExt;
+
+    fn create_test_state(hooks: InboundHooks) -> AppState {
+        let backend = MockBackend::with_text("Triaged");
+        let agent = Agent::builder()
+            .with_backend(backend)
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, crate::config::ServerConfig::new(None)).with_inbound_hooks(hooks)
+    }
+
+    fn router(state: AppState) -> Router {
+        Router::new()
+            .route("/hooks/{name}", post(trigger_hook_handler))
+            .with_state(state)
+    }
+
+    fn hooks(target: HookTarget) -> InboundHooks {
+        let hook = InboundHook::new("ci", HookAuth::token("tok"), target)
+            .with_context("repo", "/repo")
+            .with_message("CI failed in {{repo}}");
+        InboundHooks::from([("ci".to_string(), hook)])
+    }
+
+    async fn send(state: AppState, uri: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method("POST").uri(uri);
+        if let Some(token) = token {
+            request = request.header(TOKEN_HEADER, token);
+        }
+        let request = request.body(Body::from(r#"{"repo": "arawn"}"#)).unwrap();
+        router(state).oneshot(request).await.unwrap().status()
+    }
+
+    async fn wait_for_task(state: &AppState) -> TrackedTask {
+        for _ in 0..100 {
+            let tasks = state.tasks().read().await;
+            if let Some(task) = tasks.values().next()
+                && task.status != TaskStatus::Running
+            {
+                return task.clone();
+            }
+            drop(tasks);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("hook task did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hook() {
+        let state = create_test_state(InboundHooks::new());
+        assert_eq!(
+            send(state, "/hooks/ci", Some("tok")).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_token() {
+        let state = create_test_state(hooks(HookTarget::Workflow("triage".to_string())));
+        assert_eq!(
+            send(state.clone(), "/hooks/ci", Some("wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send(state.clone(), "/hooks/ci", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert!(state.tasks().read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_workflow_target_requires_pipeline() {
+        let state = create_test_state(hooks(HookTarget::Workflow("triage".to_string())));
+        assert_eq!(
+            send(state, "/hooks/ci", Some("tok")).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workstream_target_runs_turn() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mgr = arawn_workstream::WorkstreamManager::new(&arawn_workstream::WorkstreamConfig {
+            db_path: tmp.path().join("workstreams.db"),
+            data_dir: tmp.path().join("workstreams"),
+            session_timeout_minutes: 30,
+        })
+        .unwrap();
+        mgr.create_workstream("Inbox", None, &[]).unwrap();
+        let state = create_test_state(hooks(HookTarget::Workstream("inbox".to_string())))
+            .with_workstreams(mgr)
+            .build_domain_services();
+
+        assert_eq!(
+            send(state.clone(), "/hooks/ci", Some("tok")).await,
+            StatusCode::ACCEPTED
+        );
+
+        let task = wait_for_task(&state).await;
+        assert_eq!(task.task_type, HOOK_TASK_TYPE);
+        assert_eq!(task.status, TaskStatus::Completed, "{:?}", task.error);
+        let result = task.result.unwrap();
+        assert_eq!(result["response"], "Triaged");
+        assert_eq!(task.session_id.as_deref(), result["session_id"].as_str());
+    }
+}
@@ -9,16 +9,19 @@ use axum::{
     http::StatusCode,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use utoipa::ToSchema;
 
-use arawn_domain::{ContentType, Memory, MemoryId, MemoryNote, MemoryStore, NoteId};
+use arawn_domain::{
+    ContentType, Memory, MemoryId, MemoryNote, MemoryStore, NoteId, ResourceKind, SessionId,
+};
 
 use super::pagination::PaginationParams;
-use crate::auth::Identity;
+use crate::auth::{self, Identity};
 use crate::error::ServerError;
 use crate::state::AppState;
+use crate::webhooks::WebhookEvent;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Types
@@ -198,12 +201,26 @@ pub struct StoreMemoryResponse {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Get the memory store from app state, returning 503 if not configured.
-fn require_memory_store(state: &AppState) -> Result<&Arc<MemoryStore>, ServerError> {
+pub(super) fn require_memory_store(state: &AppState) -> Result<&Arc<MemoryStore>, ServerError> {
     state
         .memory_store()
         .ok_or_else(|| ServerError::ServiceUnavailable("Memory storage not configured".to_string()))
 }
 
+/// Whether a memory was stored by the identity or extracted from one of its
+/// sessions, given its [`auth::visible_ids`] for memories and sessions.
+pub(super) fn memory_visible(
+    visible_memories: Option<&HashSet<String>>,
+    visible_sessions: Option<&HashSet<String>>,
+    memory: &Memory,
+) -> bool {
+    visible_memories.is_none_or(|ids| ids.contains(&memory.id.to_string()))
+        || memory
+            .session_id
+            .as_ref()
+            .is_some_and(|sid| visible_sessions.is_none_or(|ids| ids.contains(sid)))
+}
+
 /// Convert an `arawn_memory::Note` to the API `Note` type.
 fn to_api_note(note: MemoryNote) -> Note {
     Note {
@@ -235,7 +252,7 @@ fn to_api_note(note: MemoryNote) -> Note {
 )]
 pub async fn create_note_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Json(request): Json<CreateNoteRequest>,
 ) -> Result<(StatusCode, Json<Note>), ServerError> {
     let store = require_memory_store(&state)?;
@@ -251,6 +268,7 @@ pub async fn create_note_handler(
     store
         .insert_note(&note)
         .map_err(|e| ServerError::Internal(format!("Failed to create note: {}", e)))?;
+    auth::claim(&state, &identity, ResourceKind::Note, &note.id.to_string());
 
     Ok((StatusCode::CREATED, Json(to_api_note(note))))
 }
@@ -273,18 +291,30 @@ pub async fn create_note_handler(
 )]
 pub async fn list_notes_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Query(query): Query<ListNotesQuery>,
     Query(pagination): Query<PaginationParams>,
 ) -> Result<Json<ListNotesResponse>, ServerError> {
     let store = require_memory_store(&state)?;
     let limit = pagination.effective_limit();
 
-    let (notes, total) = if let Some(ref tag) = query.tag {
-        // Tag-filtered: fetch all matching, paginate in memory
-        let all = store
-            .list_notes_by_tag(tag, 10_000)
-            .map_err(|e| ServerError::Internal(format!("Failed to list notes: {}", e)))?;
+    let visible = auth::visible_ids(&state, &identity, ResourceKind::Note);
+
+    let (notes, total) = if query.tag.is_some() || visible.is_some() {
+        // Tag- or owner-filtered: fetch all matching, paginate in memory
+        let all = match query.tag {
+            Some(ref tag) => store.list_notes_by_tag(tag, 10_000),
+            None => store.list_notes(10_000, 0),
+        }
+        .map_err(|e| ServerError::Internal(format!("Failed to list notes: {}", e)))?;
+        let all: Vec<_> = all
+            .into_iter()
+            .filter(|n| {
+                visible
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&n.id.to_string()))
+            })
+            .collect();
         let total = all.len();
         let offset = pagination.offset.min(total);
         let end = (offset + limit).min(total);
@@ -326,13 +356,14 @@ pub async fn list_notes_handler(
 )]
 pub async fn get_note_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(note_id): Path<String>,
 ) -> Result<Json<Note>, ServerError> {
     let store = require_memory_store(&state)?;
 
     let id = NoteId::parse(&note_id)
         .map_err(|_| ServerError::BadRequest(format!("Invalid note ID: {}", note_id)))?;
+    auth::authorize(&state, &identity, ResourceKind::Note, &note_id)?;
 
     let note = store
         .get_note(id)
@@ -361,7 +392,7 @@ pub async fn get_note_handler(
 )]
 pub async fn update_note_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(note_id): Path<String>,
     Json(request): Json<UpdateNoteRequest>,
 ) -> Result<Json<Note>, ServerError> {
@@ -369,6 +400,7 @@ pub async fn update_note_handler(
 
     let id = NoteId::parse(&note_id)
         .map_err(|_| ServerError::BadRequest(format!("Invalid note ID: {}", note_id)))?;
+    auth::authorize(&state, &identity, ResourceKind::Note, &note_id)?;
 
     let mut note = store
         .get_note(id)
@@ -411,19 +443,21 @@ pub async fn update_note_handler(
 )]
 pub async fn delete_note_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(note_id): Path<String>,
 ) -> Result<StatusCode, ServerError> {
     let store = require_memory_store(&state)?;
 
     let id = NoteId::parse(&note_id)
         .map_err(|_| ServerError::BadRequest(format!("Invalid note ID: {}", note_id)))?;
+    auth::authorize(&state, &identity, ResourceKind::Note, &note_id)?;
 
     let deleted = store
         .delete_note(id)
         .map_err(|e| ServerError::Internal(format!("Failed to delete note: {}", e)))?;
 
     if deleted {
+        auth::release(&state, ResourceKind::Note, &note_id);
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err(ServerError::NotFound(format!("Note {} not found", note_id)))
@@ -456,13 +490,16 @@ pub async fn delete_note_handler(
 )]
 pub async fn memory_search_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Query(query): Query<MemorySearchQuery>,
 ) -> Result<Json<MemorySearchResponse>, ServerError> {
     let store = require_memory_store(&state)?;
 
     let mut results: Vec<MemorySearchResult> = Vec::new();
     let mut degraded = false;
+    let visible_memories = auth::visible_ids(&state, &identity, ResourceKind::Memory);
+    let visible_sessions = auth::visible_ids(&state, &identity, ResourceKind::Session);
+    let visible_notes = auth::visible_ids(&state, &identity, ResourceKind::Note);
 
     // Search memories (facts, summaries, etc.)
     match store.search_memories(&query.q, query.limit) {
@@ -474,6 +511,13 @@ pub async fn memory_search_handler(
                 {
                     continue;
                 }
+                if !memory_visible(
+                    visible_memories.as_ref(),
+                    visible_sessions.as_ref(),
+                    &memory,
+                ) {
+                    continue;
+                }
                 let citation = memory
                     .citation
                     .as_ref()
@@ -501,6 +545,12 @@ pub async fn memory_search_handler(
         && let Ok(notes) = store.search_notes(&query.q, remaining)
     {
         for note in notes {
+            if visible_notes
+                .as_ref()
+                .is_some_and(|ids| !ids.contains(&note.id.to_string()))
+            {
+                continue;
+            }
             results.push(MemorySearchResult {
                 id: note.id.to_string(),
                 content_type: "note".to_string(),
@@ -545,7 +595,7 @@ pub async fn memory_search_handler(
 )]
 pub async fn store_memory_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Json(request): Json<StoreMemoryRequest>,
 ) -> Result<(StatusCode, Json<StoreMemoryResponse>), ServerError> {
     let store = require_memory_store(&state)?;
@@ -566,6 +616,32 @@ pub async fn store_memory_handler(
     store
         .insert_memory(&memory)
         .map_err(|e| ServerError::Internal(format!("Failed to store memory: {}", e)))?;
+    auth::claim(
+        &state,
+        &identity,
+        ResourceKind::Memory,
+        &memory.id.to_string(),
+    );
+
+    // Tie the webhook event to the session's workstream when there is one
+    let workstream_id = match request
+        .session_id
+        .as_deref()
+        .and_then(|id| uuid::Uuid::parse_str(id).ok())
+    {
+        Some(uuid) => {
+            state
+                .session_cache()
+                .get_workstream_id(&SessionId::from_uuid(uuid))
+                .await
+        }
+        None => None,
+    };
+    state.emit_webhook(WebhookEvent::memory_stored(
+        workstream_id.as_deref(),
+        &memory.id.to_string(),
+        content_type.as_str(),
+    ));
 
     Ok((
         StatusCode::CREATED,
@@ -595,7 +671,7 @@ pub async fn store_memory_handler(
 )]
 pub async fn delete_memory_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(memory_id): Path<String>,
 ) -> Result<StatusCode, ServerError> {
     let store = require_memory_store(&state)?;
@@ -604,11 +680,13 @@ pub async fn delete_memory_handler(
     let uuid = uuid::Uuid::parse_str(&memory_id)
         .map_err(|_| ServerError::BadRequest(format!("Invalid memory ID: {}", memory_id)))?;
     let id = MemoryId(uuid);
+    auth::authorize(&state, &identity, ResourceKind::Memory, &memory_id)?;
 
     // Delete the memory
     store
         .delete_memory(id)
         .map_err(|e| ServerError::Internal(format!("Failed to delete memory: {}", e)))?;
+    auth::release(&state, ResourceKind::Memory, &memory_id);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -0,0 +1,120 @@
+//! Prometheus metrics endpoint.
+//!
+//! Serves everything recorded through the process-wide Prometheus recorder
+//! in the text exposition format, for scraping by Prometheus or compatible
+//! agents.
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET /metrics - Prometheus metrics.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text format", body = String, content_type = "text/plain"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Metrics not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "metrics"
+)]
+pub async fn metrics_handler(State(state): State<AppState>) -> Result<Response, ServerError> {
+    let handle = state
+        .metrics()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Metrics not enabled".to_string()))?;
+
+    crate::metrics::refresh_gauges(&state).await;
+    Ok((
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        handle.render(),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::auth_middleware;
+    use crate::config::ServerConfig;
+    use crate::metrics::http_metrics_middleware;
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+    };
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use tower::ServiceExt;
+
+    fn create_test_state() -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    async fn get_metrics(state: AppState) -> (StatusCode, String) {
+        let app = Router::new()
+            .route("/metrics", get(metrics_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .layer(middleware::from_fn(http_metrics_middleware))
+            .with_state(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/metrics")
+                    .header("Authorization", "Bearer test-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_metrics_renders_http_requests() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let state = create_test_state().with_metrics(recorder.handle());
+        let _guard = metrics::set_default_local_recorder(&recorder);
+
+        let (status, _) = get_metrics(state.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = get_metrics(state).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains(
+            r#"arawn_http_requests_total{method="GET",route="/metrics",status="200"} 1"#
+        ));
+        assert!(body.contains("arawn_http_request_duration_seconds"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_disabled() {
+        let (status, _) = get_metrics(create_test_state()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -4,14 +4,24 @@ pub mod agents;
 pub mod chat;
 pub mod commands;
 pub mod config;
+pub mod graph;
 pub mod health;
+pub mod hooks;
 pub mod logs;
 pub mod mcp;
 pub mod memory;
+pub mod metrics;
+pub mod openai;
 pub mod openapi;
 pub mod pagination;
+pub mod plugins;
+pub mod search;
 pub mod sessions;
 pub mod tasks;
+pub mod templates;
+pub mod usage;
+pub mod webhooks;
+pub mod workflows;
 pub mod workstreams;
 pub mod ws;
 
@@ -25,8 +35,18 @@ pub use commands::{
     CompactRequest, CompactResponse, ListCommandsResponse, SharedCommandRegistry,
     compact_command_handler, compact_command_stream_handler, list_commands_handler,
 };
-pub use config::{ConfigFeatures, ConfigLimits, ConfigResponse, get_config_handler};
+pub use config::{
+    ConfigFeatures, ConfigLimits, ConfigResponse, ConfigUpdateResponse, get_config_handler,
+    patch_config_handler, put_config_handler,
+};
+pub use graph::{
+    CypherQueryRequest, CypherQueryResponse, EntityDetailResponse, ExportFormat, GraphEdge,
+    GraphEntity, GraphStatsResponse, LinkedMemory, ListEntitiesResponse, SubgraphResponse,
+    entity_neighbors_handler, get_entity_handler, graph_export_handler, graph_query_handler,
+    graph_stats_handler, list_entities_handler,
+};
 pub use health::health_routes;
+pub use hooks::{HOOK_TASK_TYPE, HookAcceptedResponse, trigger_hook_handler};
 pub use logs::{
     LogEntry, LogFileInfo, LogFilesResponse, LogsQuery, LogsResponse, get_logs_handler,
     list_log_files_handler,
@@ -43,25 +63,68 @@ pub use memory::{
     delete_memory_handler, delete_note_handler, get_note_handler, list_notes_handler,
     memory_search_handler, store_memory_handler, update_note_handler,
 };
+pub use metrics::metrics_handler;
+pub use openai::{
+    ChatCompletionRequest, ChatCompletionResponse, ModelListResponse, chat_completions_handler,
+    list_models_handler,
+};
+pub use plugins::{
+    CapabilitySummaryResponse, InstallPluginRequest, ListPluginsResponse, PluginResponse,
+    RemovePluginQuery, SyncPluginResponse, SyncPluginsResponse, SyncResultResponse,
+    disable_plugin_handler, enable_plugin_handler, get_plugin_handler, install_plugin_handler,
+    list_plugins_handler, remove_plugin_handler, sync_plugin_handler, sync_plugins_handler,
+};
+pub use search::{
+    MessageSearchFacetsResponse, MessageSearchHitResponse, MessageSearchParams,
+    MessageSearchResponse, search_messages_handler,
+};
 pub use sessions::{
-    CreateSessionRequest, ListSessionsResponse, MessageInfo, SessionDetail,
-    SessionMessagesResponse, SessionSummary, UpdateSessionRequest, create_session_handler,
-    delete_session_handler, get_session_handler, get_session_messages_handler,
-    list_sessions_handler, update_session_handler,
+    CreateSessionRequest, ForkSessionRequest, ForkSessionResponse, ListSessionsResponse,
+    MessageInfo, SessionDetail, SessionMessagesResponse, SessionSummary, UpdateSessionRequest,
+    create_session_handler, delete_session_handler, fork_session_handler, get_session_handler,
+    get_session_messages_handler, list_sessions_handler, update_session_handler,
 };
 pub use tasks::{
     ListTasksResponse, TaskDetail, TaskSummary, cancel_task_handler, get_task_handler,
     list_tasks_handler,
 };
+pub use templates::{
+    ListTemplatesResponse, TemplateInfo, TemplateParamInfo, list_templates_handler,
+};
+pub use usage::{
+    UsageReportQuery, UsageReportResponse, UsageRowResponse, WorkstreamBudgetResponse,
+    get_usage_report_handler,
+};
+pub use webhooks::{
+    CreateWebhookRequest, CreateWebhookResponse, DeadLetterResponse, DeadLettersQuery,
+    ListDeadLettersResponse, ListWebhooksResponse, RedeliverResponse, UpdateWebhookRequest,
+    WebhookResponse, create_webhook_handler, delete_dead_letter_handler, delete_webhook_handler,
+    get_webhook_handler, list_dead_letters_handler, list_webhooks_handler,
+    redeliver_dead_letter_handler, update_webhook_handler,
+};
+pub use workflows::{
+    ListRuntimesResponse, ListWorkflowsResponse, RunWorkflowAcceptedResponse, RunWorkflowRequest,
+    RunWorkflowResponse, RuntimeResponse, SaveRuntimeRequest, SaveWorkflowRequest,
+    ScheduleResponse, ScheduleWorkflowRequest, WorkflowDetailResponse, WorkflowSummaryResponse,
+    cancel_schedule_handler, create_workflow_handler, delete_runtime_handler,
+    delete_workflow_handler, get_runtime_handler, get_workflow_handler, list_runtimes_handler,
+    list_workflows_handler, put_runtime_handler, put_workflow_handler, run_workflow_handler,
+    schedule_workflow_handler,
+};
 pub use workstreams::{
     CleanupRequest, CleanupResponse, CloneRepoRequest, CloneRepoResponse, CompressResponse,
-    CreateWorkstreamRequest, ExportFileRequest, ExportFileResponse, MessageListResponse,
-    MessageResponse, PromoteFileRequest, PromoteFileResponse, PromoteRequest, SendMessageRequest,
-    SessionListResponse, SessionResponse, SessionUsageResponse, UpdateWorkstreamRequest,
-    UsageResponse, WorkstreamListResponse, WorkstreamResponse, cleanup_handler, clone_repo_handler,
-    compress_workstream_handler, create_workstream_handler, delete_workstream_handler,
+    CreateSnapshotRequest, CreateWorkstreamRequest, ExportFileRequest, ExportFileResponse,
+    ListSnapshotsResponse, MergeWorkstreamRequest, MergeWorkstreamResponse, MessageListResponse,
+    MessageResponse, PromoteFileRequest, PromoteFileResponse, PromoteRequest,
+    RestoreSnapshotResponse, SendMessageRequest, SessionListResponse, SessionResponse,
+    SessionUsageResponse, SnapshotDiffQuery, SnapshotDiffResponse, SnapshotResponse,
+    SplitWorkstreamRequest, SplitWorkstreamResponse, TemplateAppliedResponse,
+    UpdateWorkstreamRequest, UsageResponse, WorkstreamListResponse, WorkstreamResponse,
+    cleanup_handler, clone_repo_handler, compress_workstream_handler, create_snapshot_handler,
+    create_workstream_handler, delete_workstream_handler, diff_snapshot_handler,
     export_file_handler, get_usage_handler, get_workstream_handler, list_messages_handler,
-    list_workstream_sessions_handler, list_workstreams_handler, promote_file_handler,
-    promote_handler, send_message_handler, update_workstream_handler,
+    list_snapshots_handler, list_workstream_sessions_handler, list_workstreams_handler,
+    merge_workstream_handler, promote_file_handler, promote_handler, restore_snapshot_handler,
+    send_message_handler, split_workstream_handler, update_workstream_handler,
 };
 pub use ws::{ClientMessage, ServerMessage, ws_handler};
@@ -0,0 +1,1159 @@
+//! OpenAI-compatible chat completions facade.
+//!
+//! Lets OpenAI SDK based tools talk to the Arawn agent. Requests run a full
+//! agent turn (tools, memory, workstream settings); `model` picks where:
+//!
+//! - `arawn` — the default agent in the scratch workstream
+//! - an LLM profile name — the default agent routed to that profile
+//! - `workstream:<id or title>` — a session in that workstream
+//!
+//! Each request gets a new session seeded with the earlier messages, unless
+//! it names an existing one in the `X-Arawn-Session` header. The session ID
+//! is returned in the same header.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, HeaderValue},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use arawn_domain::{
+    ResourceKind, Session, SessionId, StreamChunk, ToolCall, ToolResultRecord, Turn, TurnId,
+    estimate_tokens,
+};
+
+use crate::auth::{self, Identity};
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// Model ID for the default agent.
+pub const DEFAULT_MODEL: &str = "arawn";
+
+/// Prefix for model IDs that target a workstream.
+pub const WORKSTREAM_MODEL_PREFIX: &str = "workstream:";
+
+/// Header naming the Arawn session a request continues (and answers with).
+pub const SESSION_HEADER: &str = "x-arawn-session";
+
+/// Maximum size of the final user message in bytes (100KB).
+const MAX_MESSAGE_BYTES: usize = 100 * 1024;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Request/Response Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request body for `POST /v1/chat/completions`.
+///
+/// Sampling parameters (`temperature`, `max_tokens`, ...) are accepted and
+/// ignored; the agent's configuration decides them.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChatCompletionRequest {
+    /// `arawn`, an LLM profile, or `workstream:<id or title>`.
+    pub model: String,
+    /// Conversation so far; the last message must be from the user.
+    pub messages: Vec<ChatCompletionMessage>,
+    /// Stream the response as server-sent events.
+    #[serde(default)]
+    pub stream: bool,
+    /// Streaming options.
+    #[serde(default)]
+    pub stream_options: Option<StreamOptions>,
+    /// Annotate the response with the tools the agent ran (Arawn extension).
+    #[serde(default)]
+    pub tool_activity: bool,
+}
+
+/// A message in an OpenAI conversation.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ChatCompletionMessage {
+    /// `system`, `developer`, `user`, `assistant` or `tool`.
+    pub role: String,
+    /// A string, or an array of content parts (only `text` parts are used).
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub content: serde_json::Value,
+}
+
+/// Streaming options.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct StreamOptions {
+    /// Send a final chunk with (estimated) token usage.
+    #[serde(default)]
+    pub include_usage: bool,
+}
+
+/// Response body for a non-streaming completion.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChatCompletionResponse {
+    /// Completion ID.
+    pub id: String,
+    /// Always `chat.completion`.
+    pub object: String,
+    /// Unix timestamp of creation.
+    pub created: i64,
+    /// Model from the request.
+    pub model: String,
+    /// The single completion choice.
+    pub choices: Vec<ChatCompletionChoice>,
+    /// Token usage for the whole agent turn, tool iterations included.
+    pub usage: CompletionUsage,
+    /// Tools the agent ran, when `tool_activity` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_activity: Option<Vec<ToolActivity>>,
+}
+
+/// A completion choice.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChatCompletionChoice {
+    /// Choice index (always 0).
+    pub index: u32,
+    /// The assistant message.
+    pub message: AssistantMessage,
+    /// `stop`, or `length` when the agent hit its iteration limit.
+    pub finish_reason: String,
+}
+
+/// The assistant's reply.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssistantMessage {
+    /// Always `assistant`.
+    pub role: String,
+    /// Reply text.
+    pub content: String,
+}
+
+/// Token usage in OpenAI terms.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompletionUsage {
+    /// Input tokens.
+    pub prompt_tokens: u32,
+    /// Output tokens.
+    pub completion_tokens: u32,
+    /// Sum of both.
+    pub total_tokens: u32,
+}
+
+impl CompletionUsage {
+    fn new(prompt_tokens: u32, completion_tokens: u32) -> Self {
+        Self {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+}
+
+/// A tool the agent ran while answering.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ToolActivity {
+    /// Tool call ID.
+    pub id: String,
+    /// Tool name.
+    pub name: String,
+    /// Whether it succeeded; absent while the tool is still running.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// A chunk of a streaming completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// Completion ID, shared by all chunks.
+    pub id: String,
+    /// Always `chat.completion.chunk`.
+    pub object: String,
+    /// Unix timestamp of creation.
+    pub created: i64,
+    /// Model from the request.
+    pub model: String,
+    /// Zero or one choice deltas.
+    pub choices: Vec<ChunkChoice>,
+    /// Estimated usage, on the final chunk when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<CompletionUsage>,
+    /// Tool start/end, when `tool_activity` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_activity: Option<ToolActivity>,
+}
+
+/// A choice delta in a streaming chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkChoice {
+    /// Choice index (always 0).
+    pub index: u32,
+    /// New content.
+    pub delta: ChunkDelta,
+    /// Set on the last content chunk.
+    pub finish_reason: Option<String>,
+}
+
+/// New content in a streaming chunk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChunkDelta {
+    /// `assistant` on the first chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Text delta.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// Response body for `GET /v1/models`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModelListResponse {
+    /// Always `list`.
+    pub object: String,
+    /// Available models.
+    pub data: Vec<ModelInfo>,
+}
+
+/// A model that can be named in a completion request.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModelInfo {
+    /// Model ID.
+    pub id: String,
+    /// Always `model`.
+    pub object: String,
+    /// Unix timestamp (workstream creation time, 0 for profiles).
+    pub created: i64,
+    /// Always `arawn`.
+    pub owned_by: String,
+}
+
+impl ModelInfo {
+    fn new(id: impl Into<String>, created: i64) -> Self {
+        Self {
+            id: id.into(),
+            object: "model".to_string(),
+            created,
+            owned_by: "arawn".to_string(),
+        }
+    }
+}
+
+/// Errors in the OpenAI response shape (`{"error": {...}}`).
+#[derive(Debug)]
+pub struct OpenAiError(ServerError);
+
+impl From<ServerError> for OpenAiError {
+    fn from(e: ServerError) -> Self {
+        Self(e)
+    }
+}
+
+impl IntoResponse for OpenAiError {
+    fn into_response(self) -> Response {
+        let status = self.0.status_code();
+        let kind = match status.as_u16() {
+            400 | 404 => "invalid_request_error",
+            401 => "authentication_error",
+            403 => "permission_error",
+            429 => "rate_limit_error",
+            _ => "server_error",
+        };
+        let code = if status.as_u16() == 404 {
+            "model_not_found"
+        } else {
+            self.0.error_code()
+        };
+        let retry_after = self.0.retry_after();
+        let body = serde_json::json!({
+            "error": {
+                "message": self.0.to_string(),
+                "type": kind,
+                "param": null,
+                "code": code,
+            }
+        });
+
+        let mut response = (status, Json(body)).into_response();
+        if let Some(retry_after) = retry_after
+            && let Ok(value) = HeaderValue::from_str(&retry_after.as_secs().max(1).to_string())
+        {
+            response.headers_mut().insert("Retry-After", value);
+        }
+        response
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Request Mapping
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Where a model ID sends the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModelTarget {
+    Default,
+    Profile(String),
+    Workstream(String),
+}
+
+fn resolve_model(
+    state: &AppState,
+    identity: &Identity,
+    model: &str,
+) -> Result<ModelTarget, ServerError> {
+    if model == DEFAULT_MODEL {
+        return Ok(ModelTarget::Default);
+    }
+
+    if let Some(name) = model.strip_prefix(WORKSTREAM_MODEL_PREFIX) {
+        let mgr = state.workstreams().ok_or_else(|| {
+            ServerError::ServiceUnavailable("Workstreams not configured".to_string())
+        })?;
+        let id = match mgr.get_workstream(name) {
+            Ok(ws) => ws.id,
+            Err(_) => {
+                let visible = auth::visible_ids(state, identity, ResourceKind::Workstream);
+                mgr.list_workstreams()?
+                    .into_iter()
+                    .filter(|ws| visible.as_ref().is_none_or(|ids| ids.contains(&ws.id)))
+                    .find(|ws| ws.title.eq_ignore_ascii_case(name))
+                    .map(|ws| ws.id)
+                    .ok_or_else(|| ServerError::NotFound(format!("Workstream '{}'", name)))?
+            }
+        };
+        auth::authorize(state, identity, ResourceKind::Workstream, &id)?;
+        return Ok(ModelTarget::Workstream(id));
+    }
+
+    if let Some(router) = state.agent().router()
+        && router.profile(model).is_some()
+    {
+        return Ok(ModelTarget::Profile(model.to_string()));
+    }
+
+    Err(ServerError::NotFound(format!(
+        "Model '{}' (see GET /v1/models)",
+        model
+    )))
+}
+
+/// An OpenAI message list mapped onto an agent turn.
+#[derive(Debug, Default, PartialEq)]
+struct Conversation {
+    /// System and developer messages, added to the session's system prompt.
+    system: Vec<String>,
+    /// Earlier (user, assistant) exchanges.
+    history: Vec<(String, String)>,
+    /// The message to answer.
+    prompt: String,
+}
+
+/// Text of a message's content: a string, or the `text` parts of an array.
+fn content_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .filter(|p| p["type"] == "text")
+            .filter_map(|p| p["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn parse_conversation(messages: &[ChatCompletionMessage]) -> Result<Conversation, ServerError> {
+    let Some((last, earlier)) = messages.split_last() else {
+        return Err(ServerError::BadRequest("messages is empty".to_string()));
+    };
+    if last.role != "user" {
+        return Err(ServerError::BadRequest(
+            "The last message must have role 'user'".to_string(),
+        ));
+    }
+
+    let mut conversation = Conversation::default();
+    let mut pending_user: Vec<String> = Vec::new();
+    for message in earlier {
+        let text = content_text(&message.content);
+        match message.role.as_str() {
+            "system" | "developer" => conversation.system.push(text),
+            "user" => pending_user.push(text),
+            "assistant" => conversation.history.push((pending_user.join("\n\n"), text)),
+            // Tool results belong to the client's own tool loop
+            _ => continue,
+        }
+        if message.role == "assistant" {
+            pending_user.clear();
+        }
+    }
+
+    pending_user.push(content_text(&last.content));
+    conversation.prompt = pending_user.join("\n\n");
+    if conversation.prompt.trim().is_empty() {
+        return Err(ServerError::BadRequest(
+            "The last user message is empty".to_string(),
+        ));
+    }
+    if conversation.prompt.len() > MAX_MESSAGE_BYTES {
+        return Err(ServerError::BadRequest(format!(
+            "Message too large: {} bytes (max {} bytes)",
+            conversation.prompt.len(),
+            MAX_MESSAGE_BYTES
+        )));
+    }
+    Ok(conversation)
+}
+
+/// A session ready for the agent turn.
+struct PreparedTurn {
+    session_id: SessionId,
+    session: Session,
+    workstream_id: Option<String>,
+    prompt: String,
+}
+
+async fn prepare_turn(
+    state: &AppState,
+    identity: &Identity,
+    headers: &HeaderMap,
+    request: &ChatCompletionRequest,
+) -> Result<PreparedTurn, ServerError> {
+    let target = resolve_model(state, identity, &request.model)?;
+    let conversation = parse_conversation(&request.messages)?;
+
+    let existing = match headers.get(SESSION_HEADER) {
+        Some(value) => {
+            let id = value
+                .to_str()
+                .ok()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .ok_or_else(|| {
+                    ServerError::BadRequest(format!("Invalid {} header", SESSION_HEADER))
+                })?;
+            Some(SessionId::from_uuid(id))
+        }
+        None => None,
+    };
+
+    let workstream_id = match target {
+        ModelTarget::Workstream(ref id) => id.as_str(),
+        _ => arawn_domain::SCRATCH_ID,
+    };
+    let session_id = state
+        .open_session(identity, existing, workstream_id)
+        .await?;
+    let mut session = state
+        .session_cache()
+        .get(&session_id)
+        .await
+        .ok_or_else(|| {
+            ServerError::Internal("Session disappeared during processing".to_string())
+        })?;
+
+    // A new session has not seen the conversation yet
+    if session.is_empty() {
+        for (user, assistant) in &conversation.history {
+            session
+                .start_turn(user.as_str())
+                .complete(assistant.as_str());
+        }
+    }
+
+    let workstream_id = state.session_cache().get_workstream_id(&session_id).await;
+    if let Some(domain) = state.domain()
+        && let Some(ref ws_id) = workstream_id
+    {
+        domain.chat().apply_workstream_settings(&mut session, ws_id);
+    }
+
+    let profile = match target {
+        ModelTarget::Profile(name) => Some(name),
+        _ => None,
+    };
+    if !conversation.system.is_empty() || profile.is_some() {
+        let mut settings = session.agent_settings().unwrap_or_default();
+        if !conversation.system.is_empty() {
+            let extra = conversation.system.join("\n\n");
+            settings.system_prompt = Some(match settings.system_prompt.take() {
+                Some(prompt) => format!("{}\n\n{}", prompt, extra),
+                None => extra,
+            });
+        }
+        if profile.is_some() {
+            settings.profile = profile;
+        }
+        session.set_agent_settings(&settings);
+    }
+
+    Ok(PreparedTurn {
+        session_id,
+        session,
+        workstream_id,
+        prompt: conversation.prompt,
+    })
+}
+
+fn completion_id() -> String {
+    format!("chatcmpl-{}", Uuid::new_v4().simple())
+}
+
+fn with_session_header(mut response: Response, session_id: SessionId) -> Response {
+    if let Ok(value) = HeaderValue::from_str(&session_id.to_string()) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// POST /v1/chat/completions - OpenAI-compatible chat completion.
+///
+/// Runs a full agent turn and answers in the OpenAI shape, or streams
+/// `chat.completion.chunk` events ending in `data: [DONE]` when `stream` is set.
+#[utoipa::path(
+    post,
+    path = "/v1/chat/completions",
+    request_body = ChatCompletionRequest,
+    responses(
+        (status = 200, description = "Completion, or an SSE stream of chunks", body = ChatCompletionResponse),
+        (status = 400, description = "Invalid messages"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Unknown model"),
+        (status = 500, description = "Agent error"),
+    ),
+    params(
+        ("x-arawn-session" = Option<String>, Header, description = "Continue this Arawn session"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "openai"
+)]
+pub async fn chat_completions_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    headers: HeaderMap,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Result<Response, OpenAiError> {
+    tracing::debug!(
+        identity = %identity,
+        model = %request.model,
+        messages = request.messages.len(),
+        stream = request.stream,
+        "Chat completion request received"
+    );
+
+    let prepared = prepare_turn(&state, &identity, &headers, &request).await?;
+    let session_id = prepared.session_id;
+    let response = if request.stream {
+        stream_completion(state, request, prepared).into_response()
+    } else {
+        Json(complete(&state, &request, prepared).await?).into_response()
+    };
+    Ok(with_session_header(response, session_id))
+}
+
+async fn complete(
+    state: &AppState,
+    request: &ChatCompletionRequest,
+    prepared: PreparedTurn,
+) -> Result<ChatCompletionResponse, ServerError> {
+    let PreparedTurn {
+        session_id,
+        mut session,
+        workstream_id,
+        prompt,
+    } = prepared;
+
+    let response = state
+        .agent()
+        .turn(&mut session, &prompt, workstream_id.as_deref())
+        .await
+        .map_err(ServerError::Agent)?;
+
+    let completed_turn = session.current_turn().cloned();
+    state.update_session(session_id, session).await;
+
+    if let Some(turn) = completed_turn {
+        state.emit_turn_webhooks(session_id, workstream_id.as_deref(), &turn);
+        if let Some(ref ws_id) = workstream_id
+            && let Err(e) = state
+                .session_cache()
+                .save_turn(session_id, &turn, ws_id)
+                .await
+        {
+            tracing::warn!("Failed to persist turn to workstream: {}", e);
+        }
+    }
+
+    let tool_activity = request.tool_activity.then(|| {
+        response
+            .tool_calls
+            .iter()
+            .map(|call| ToolActivity {
+                id: call.id.clone(),
+                name: call.name.clone(),
+                success: response
+                    .tool_results
+                    .iter()
+                    .find(|r| r.tool_call_id == call.id)
+                    .map(|r| r.success),
+            })
+            .collect()
+    });
+
+    Ok(ChatCompletionResponse {
+        id: completion_id(),
+        object: "chat.completion".to_string(),
+        created: chrono::Utc::now().timestamp(),
+        model: request.model.clone(),
+        choices: vec![ChatCompletionChoice {
+            index: 0,
+            message: AssistantMessage {
+                role: "assistant".to_string(),
+                content: response.text,
+            },
+            finish_reason: if response.truncated { "length" } else { "stop" }.to_string(),
+        }],
+        usage: CompletionUsage::new(response.usage.input_tokens, response.usage.output_tokens),
+        tool_activity,
+    })
+}
+
+fn stream_completion(
+    state: AppState,
+    request: ChatCompletionRequest,
+    prepared: PreparedTurn,
+) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
+    let PreparedTurn {
+        session_id,
+        mut session,
+        workstream_id,
+        prompt,
+    } = prepared;
+
+    let stream = state.agent().turn_stream(
+        &mut session,
+        &prompt,
+        CancellationToken::new(),
+        workstream_id.as_deref(),
+    );
+
+    let include_usage = request
+        .stream_options
+        .as_ref()
+        .is_some_and(|o| o.include_usage);
+    let prompt_tokens: usize = request
+        .messages
+        .iter()
+        .map(|m| estimate_tokens(&content_text(&m.content)))
+        .sum();
+    let id = completion_id();
+    let created = chrono::Utc::now().timestamp();
+    let model = request.model;
+    let tool_activity = request.tool_activity;
+
+    let sse_stream = async_stream::stream! {
+        use futures::StreamExt;
+
+        state.update_session(session_id, session).await;
+
+        let chunk = |delta: ChunkDelta, finish_reason: Option<&str>| ChatCompletionChunk {
+            id: id.clone(),
+            object: "chat.completion.chunk".to_string(),
+            created,
+            model: model.clone(),
+            choices: vec![ChunkChoice {
+                index: 0,
+                delta,
+                finish_reason: finish_reason.map(str::to_string),
+            }],
+            usage: None,
+            tool_activity: None,
+        };
+        let event = |chunk: &ChatCompletionChunk| {
+            Event::default().json_data(chunk).unwrap_or_else(|_| Event::default())
+        };
+
+        yield Ok(event(&chunk(
+            ChunkDelta { role: Some("assistant".to_string()), content: None },
+            None,
+        )));
+
+        let mut full_response = String::new();
+        let mut tool_calls: Vec<ToolCall> = Vec::new();
+        let mut tool_results: Vec<ToolResultRecord> = Vec::new();
+        let mut tool_output: HashMap<String, String> = HashMap::new();
+
+        let mut stream = std::pin::pin!(stream);
+        while let Some(item) = stream.next().await {
+            match item {
+                StreamChunk::Text { content } => {
+                    full_response.push_str(&content);
+                    yield Ok(event(&chunk(
+                        ChunkDelta { role: None, content: Some(content) },
+                        None,
+                    )));
+                }
+                StreamChunk::ToolStart { id: call_id, name, arguments } => {
+                    if tool_activity {
+                        let mut c = chunk(ChunkDelta::default(), None);
+                        c.tool_activity = Some(ToolActivity {
+                            id: call_id.clone(),
+                            name: name.clone(),
+                            success: None,
+                        });
+                        yield Ok(event(&c));
+                    }
+                    tool_calls.push(ToolCall { id: call_id, name, arguments });
+                }
+                StreamChunk::ToolOutput { id: call_id, content } => {
+                    tool_output.entry(call_id).or_default().push_str(&content);
+                }
+                StreamChunk::ToolEnd { id: call_id, success, content } => {
+                    if tool_activity {
+                        let name = tool_calls
+                            .iter()
+                            .find(|c| c.id == call_id)
+                            .map(|c| c.name.clone())
+                            .unwrap_or_default();
+                        let mut c = chunk(ChunkDelta::default(), None);
+                        c.tool_activity = Some(ToolActivity {
+                            id: call_id.clone(),
+                            name,
+                            success: Some(success),
+                        });
+                        yield Ok(event(&c));
+                    }
+                    let content = tool_output.remove(&call_id).unwrap_or(content);
+                    tool_results.push(ToolResultRecord {
+                        tool_call_id: call_id,
+                        success,
+                        content,
+                    });
+                }
+                StreamChunk::Done { .. } => {
+                    let turn = Turn {
+                        id: TurnId::new(),
+                        user_message: prompt.clone(),
+                        assistant_response: (!full_response.is_empty())
+                            .then(|| full_response.clone()),
+                        tool_calls: tool_calls.clone(),
+                        tool_results: tool_results.clone(),
+                        started_at: chrono::Utc::now(),
+                        completed_at: Some(chrono::Utc::now()),
+                    };
+                    if let Some(ref ws_id) = workstream_id
+                        && let Err(e) = state.session_cache().save_turn(session_id, &turn, ws_id).await
+                    {
+                        tracing::warn!("Failed to persist turn to workstream: {}", e);
+                    }
+                    state.emit_turn_webhooks(session_id, workstream_id.as_deref(), &turn);
+                    // Keep the cached session in step for follow-up requests
+                    let response_text = full_response.clone();
+                    state
+                        .session_cache()
+                        .with_session_mut(&session_id, move |s| {
+                            if let Some(t) = s.current_turn_mut() {
+                                t.complete(response_text);
+                            }
+                        })
+                        .await;
+
+                    yield Ok(event(&chunk(ChunkDelta::default(), Some("stop"))));
+                }
+                StreamChunk::Error { message } => {
+                    let body = serde_json::json!({
+                        "error": { "message": message, "type": "server_error", "param": null, "code": "agent_error" }
+                    });
+                    yield Ok(Event::default().json_data(body).unwrap_or_else(|_| Event::default()));
+                }
+            }
+        }
+
+        if include_usage {
+            let usage = CompletionUsage::new(
+                prompt_tokens as u32,
+                estimate_tokens(&full_response) as u32,
+            );
+            let mut c = chunk(ChunkDelta::default(), None);
+            c.choices.clear();
+            c.usage = Some(usage);
+            yield Ok(event(&c));
+        }
+        yield Ok(Event::default().data("[DONE]"));
+    };
+
+    Sse::new(sse_stream).keep_alive(KeepAlive::default())
+}
+
+/// GET /v1/models - List models usable in completion requests.
+///
+/// Lists `arawn`, each LLM profile, and `workstream:<id>` for active workstreams.
+#[utoipa::path(
+    get,
+    path = "/v1/models",
+    responses(
+        (status = 200, description = "Available models", body = ModelListResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "openai"
+)]
+pub async fn list_models_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<ModelListResponse>, OpenAiError> {
+    let mut data = vec![ModelInfo::new(DEFAULT_MODEL, 0)];
+
+    if let Some(router) = state.agent().router() {
+        data.extend(
+            router
+                .profile_names()
+                .into_iter()
+                .map(|name| ModelInfo::new(name, 0)),
+        );
+    }
+
+    if let Some(mgr) = state.workstreams() {
+        let visible = auth::visible_ids(&state, &identity, ResourceKind::Workstream);
+        data.extend(
+            mgr.list_workstreams()
+                .map_err(ServerError::from)?
+                .into_iter()
+                .filter(|ws| !ws.is_scratch)
+                .filter(|ws| visible.as_ref().is_none_or(|ids| ids.contains(&ws.id)))
+                .map(|ws| {
+                    ModelInfo::new(
+                        format!("{}{}", WORKSTREAM_MODEL_PREFIX, ws.id),
+                        ws.created_at.timestamp(),
+                    )
+                }),
+        );
+    }
+
+    Ok(Json(ModelListResponse {
+        object: "list".to_string(),
+        data,
+    }))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::auth_middleware;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::{LlmRouter, MockBackend, RouteProfile};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::{get, post},
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn message(role: &str, content: serde_json::Value) -> ChatCompletionMessage {
+        ChatCompletionMessage {
+            role: role.to_string(),
+            content,
+        }
+    }
+
+    fn create_state(backend: Arc<MockBackend>) -> AppState {
+        let router = LlmRouter::new(RouteProfile::new("default", backend.clone(), "m"))
+            .with_profile(RouteProfile::new("fast", backend.clone(), "m-small"));
+        let agent = Agent::builder()
+            .with_shared_backend(backend)
+            .with_router(Arc::new(router))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    fn create_router(state: AppState) -> Router {
+        Router::new()
+            .route("/v1/chat/completions", post(chat_completions_handler))
+            .route("/v1/models", get(list_models_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state)
+    }
+
+    async fn post_completion(
+        state: AppState,
+        body: serde_json::Value,
+    ) -> (StatusCode, HeaderMap, String) {
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/chat/completions")
+                    .header("Authorization", "Bearer test-token")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, headers, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[test]
+    fn test_parse_conversation() {
+        let messages = vec![
+            message("system", serde_json::json!("Be terse.")),
+            message("user", serde_json::json!("Hi")),
+            message("assistant", serde_json::json!("Hello")),
+            message("tool", serde_json::json!("ignored")),
+            message("user", serde_json::json!("First part")),
+            message(
+                "user",
+                serde_json::json!([
+                    {"type": "text", "text": "Second part"},
+                    {"type": "image_url", "image_url": {"url": "x"}}
+                ]),
+            ),
+        ];
+        let conversation = parse_conversation(&messages).unwrap();
+        assert_eq!(conversation.system, vec!["Be terse."]);
+        assert_eq!(
+            conversation.history,
+            vec![("Hi".to_string(), "Hello".to_string())]
+        );
+        assert_eq!(conversation.prompt, "First part\n\nSecond part");
+
+        assert!(parse_conversation(&[]).is_err());
+        assert!(parse_conversation(&[message("assistant", serde_json::json!("x"))]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion() {
+        let backend = Arc::new(MockBackend::with_text("Hello from Arawn"));
+        let state = create_state(backend.clone());
+
+        let (status, headers, body) = post_completion(
+            state,
+            serde_json::json!({
+                "model": "arawn",
+                "messages": [
+                    {"role": "system", "content": "Answer in English."},
+                    {"role": "user", "content": "Earlier question"},
+                    {"role": "assistant", "content": "Earlier answer"},
+                    {"role": "user", "content": "Hi"}
+                ]
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(headers.contains_key(SESSION_HEADER));
+        let result: ChatCompletionResponse = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.object, "chat.completion");
+        assert_eq!(result.model, "arawn");
+        assert_eq!(result.choices[0].message.content, "Hello from Arawn");
+        assert_eq!(result.choices[0].finish_reason, "stop");
+        assert_eq!(
+            result.usage.total_tokens,
+            result.usage.prompt_tokens + result.usage.completion_tokens
+        );
+        assert!(result.tool_activity.is_none());
+
+        // The earlier exchange and system message reach the model
+        let request = &backend.requests()[0];
+        let texts: Vec<String> = request
+            .messages
+            .iter()
+            .map(|m| m.content.to_text())
+            .collect();
+        assert!(texts.iter().any(|t| t.contains("Earlier answer")));
+        assert!(
+            request
+                .system
+                .as_ref()
+                .is_some_and(|s| s.to_text().contains("Answer in English."))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_errors_use_openai_shape() {
+        let state = create_state(Arc::new(MockBackend::with_text("x")));
+
+        let (status, _, body) = post_completion(
+            state.clone(),
+            serde_json::json!({"model": "gpt-4", "messages": [{"role": "user", "content": "Hi"}]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let error: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(error["error"]["code"], "model_not_found");
+        assert_eq!(error["error"]["type"], "invalid_request_error");
+
+        let (status, _, _) = post_completion(
+            state,
+            serde_json::json!({"model": "arawn", "messages": [{"role": "assistant", "content": "Hi"}]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_profile_model() {
+        let backend = Arc::new(MockBackend::with_text("quick"));
+        let state = create_state(backend.clone());
+
+        let (status, _, _) = post_completion(
+            state,
+            serde_json::json!({"model": "fast", "messages": [{"role": "user", "content": "Hi"}]}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(backend.requests()[0].model, "m-small");
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_stream() {
+        // The agent stream consumes two mock responses for a single turn
+        let response = || {
+            arawn_llm::CompletionResponse::new(
+                "msg_1",
+                "model",
+                vec![arawn_llm::ContentBlock::Text {
+                    text: "Streamed".to_string(),
+                    cache_control: None,
+                }],
+                arawn_llm::StopReason::EndTurn,
+                arawn_llm::Usage::new(10, 20),
+            )
+        };
+        let state = create_state(Arc::new(MockBackend::new(vec![response(), response()])));
+
+        let (status, _, body) = post_completion(
+            state,
+            serde_json::json!({
+                "model": "arawn",
+                "stream": true,
+                "stream_options": {"include_usage": true},
+                "messages": [{"role": "user", "content": "Hi"}]
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let data: Vec<&str> = body
+            .lines()
+            .filter_map(|l| l.strip_prefix("data: "))
+            .collect();
+        assert_eq!(data.last(), Some(&"[DONE]"));
+
+        let chunks: Vec<ChatCompletionChunk> = data
+            .iter()
+            .filter_map(|d| serde_json::from_str(d).ok())
+            .collect();
+        assert_eq!(
+            chunks[0].choices[0].delta.role.as_deref(),
+            Some("assistant")
+        );
+        let text: String = chunks
+            .iter()
+            .filter_map(|c| c.choices.first())
+            .filter_map(|c| c.delta.content.clone())
+            .collect();
+        assert_eq!(text, "Streamed");
+        assert!(
+            chunks.iter().any(
+                |c| c.choices.first().and_then(|c| c.finish_reason.as_deref()) == Some("stop")
+            )
+        );
+        assert!(chunks.last().unwrap().usage.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_workstream_model() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mgr = arawn_workstream::WorkstreamManager::new(&arawn_workstream::WorkstreamConfig {
+            db_path: tmp.path().join("workstreams.db"),
+            data_dir: tmp.path().join("workstreams"),
+            session_timeout_minutes: 30,
+        })
+        .unwrap();
+        let ws = mgr.create_workstream("Editor", None, &[]).unwrap();
+        let state = create_state(Arc::new(MockBackend::with_text("Saved")))
+            .with_workstreams(mgr)
+            .build_domain_services();
+
+        let (status, headers, _) = post_completion(
+            state.clone(),
+            serde_json::json!({"model": "workstream:editor", "messages": [{"role": "user", "content": "Hi"}]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let session_id = headers[SESSION_HEADER].to_str().unwrap();
+        let mgr = state.workstreams().unwrap();
+        let messages = mgr.get_messages(&ws.id).unwrap();
+        assert!(messages.iter().any(|m| m.content == "Saved"));
+        assert!(
+            messages
+                .iter()
+                .all(|m| m.session_id.as_deref() == Some(session_id))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_models() {
+        let state = create_state(Arc::new(MockBackend::with_text("x")));
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/models")
+                    .header("Authorization", "Bearer test-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: ModelListResponse = serde_json::from_slice(&body).unwrap();
+        let ids: Vec<&str> = result.data.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["arawn", "default", "fast"]);
+    }
+
+    #[tokio::test]
+    async fn test_requires_auth() {
+        let state = create_state(Arc::new(MockBackend::with_text("x")));
+        let response = create_router(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/models")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
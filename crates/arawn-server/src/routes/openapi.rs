@@ -3,7 +3,10 @@
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks, workstreams};
+use super::{
+    agents, chat, commands, config, graph, health, hooks, mcp, memory, metrics, openai, plugins,
+    search, sessions, tasks, templates, usage, webhooks, workflows, workstreams,
+};
 
 /// OpenAPI documentation for the Arawn API.
 #[derive(OpenApi)]
@@ -22,6 +25,8 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         health::health,
         // Config
         config::get_config_handler,
+        config::put_config_handler,
+        config::patch_config_handler,
         // Sessions
         sessions::create_session_handler,
         sessions::list_sessions_handler,
@@ -29,6 +34,7 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         sessions::delete_session_handler,
         sessions::update_session_handler,
         sessions::get_session_messages_handler,
+        sessions::fork_session_handler,
         // Workstreams
         workstreams::create_workstream_handler,
         workstreams::list_workstreams_handler,
@@ -39,11 +45,18 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         workstreams::send_message_handler,
         workstreams::list_messages_handler,
         workstreams::promote_handler,
+        workstreams::merge_workstream_handler,
+        workstreams::split_workstream_handler,
         workstreams::promote_file_handler,
         workstreams::export_file_handler,
         workstreams::clone_repo_handler,
         workstreams::get_usage_handler,
         workstreams::cleanup_handler,
+        workstreams::list_snapshots_handler,
+        workstreams::create_snapshot_handler,
+        workstreams::diff_snapshot_handler,
+        workstreams::restore_snapshot_handler,
+        templates::list_templates_handler,
         // Memory
         memory::create_note_handler,
         memory::list_notes_handler,
@@ -53,6 +66,13 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         memory::memory_search_handler,
         memory::store_memory_handler,
         memory::delete_memory_handler,
+        // Knowledge graph
+        graph::graph_stats_handler,
+        graph::list_entities_handler,
+        graph::get_entity_handler,
+        graph::entity_neighbors_handler,
+        graph::graph_query_handler,
+        graph::graph_export_handler,
         // Agents
         agents::list_agents_handler,
         agents::get_agent_handler,
@@ -74,6 +94,48 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         commands::list_commands_handler,
         commands::compact_command_handler,
         commands::compact_command_stream_handler,
+        // Usage
+        usage::get_usage_report_handler,
+        // Webhooks
+        webhooks::create_webhook_handler,
+        webhooks::list_webhooks_handler,
+        webhooks::get_webhook_handler,
+        webhooks::update_webhook_handler,
+        webhooks::delete_webhook_handler,
+        webhooks::list_dead_letters_handler,
+        webhooks::redeliver_dead_letter_handler,
+        webhooks::delete_dead_letter_handler,
+        // Workflows
+        workflows::list_workflows_handler,
+        workflows::create_workflow_handler,
+        workflows::get_workflow_handler,
+        workflows::put_workflow_handler,
+        workflows::delete_workflow_handler,
+        workflows::run_workflow_handler,
+        workflows::schedule_workflow_handler,
+        workflows::cancel_schedule_handler,
+        workflows::list_runtimes_handler,
+        workflows::get_runtime_handler,
+        workflows::put_runtime_handler,
+        workflows::delete_runtime_handler,
+        // Plugins
+        plugins::list_plugins_handler,
+        plugins::install_plugin_handler,
+        plugins::get_plugin_handler,
+        plugins::remove_plugin_handler,
+        plugins::enable_plugin_handler,
+        plugins::disable_plugin_handler,
+        plugins::sync_plugin_handler,
+        plugins::sync_plugins_handler,
+        // Inbound hooks
+        hooks::trigger_hook_handler,
+        // Metrics
+        metrics::metrics_handler,
+        // Search
+        search::search_messages_handler,
+        // OpenAI compatibility
+        openai::chat_completions_handler,
+        openai::list_models_handler,
     ),
     components(
         schemas(
@@ -83,6 +145,7 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             config::ConfigResponse,
             config::ConfigFeatures,
             config::ConfigLimits,
+            config::ConfigUpdateResponse,
             // Sessions
             sessions::CreateSessionRequest,
             sessions::UpdateSessionRequest,
@@ -92,6 +155,8 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             sessions::ListSessionsResponse,
             sessions::MessageInfo,
             sessions::SessionMessagesResponse,
+            sessions::ForkSessionRequest,
+            sessions::ForkSessionResponse,
             // Workstreams
             workstreams::CreateWorkstreamRequest,
             workstreams::UpdateWorkstreamRequest,
@@ -103,6 +168,10 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             workstreams::SessionResponse,
             workstreams::SessionListResponse,
             workstreams::PromoteRequest,
+            workstreams::MergeWorkstreamRequest,
+            workstreams::MergeWorkstreamResponse,
+            workstreams::SplitWorkstreamRequest,
+            workstreams::SplitWorkstreamResponse,
             workstreams::PromoteFileRequest,
             workstreams::PromoteFileResponse,
             workstreams::ExportFileRequest,
@@ -113,6 +182,15 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             workstreams::SessionUsageResponse,
             workstreams::CleanupRequest,
             workstreams::CleanupResponse,
+            workstreams::CreateSnapshotRequest,
+            workstreams::SnapshotResponse,
+            workstreams::ListSnapshotsResponse,
+            workstreams::SnapshotDiffResponse,
+            workstreams::RestoreSnapshotResponse,
+            workstreams::TemplateAppliedResponse,
+            templates::TemplateInfo,
+            templates::TemplateParamInfo,
+            templates::ListTemplatesResponse,
             // Memory
             memory::Note,
             memory::CreateNoteRequest,
@@ -122,6 +200,17 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             memory::MemorySearchResponse,
             memory::StoreMemoryRequest,
             memory::StoreMemoryResponse,
+            // Knowledge graph
+            graph::GraphEntity,
+            graph::GraphEdge,
+            graph::GraphStatsResponse,
+            graph::ListEntitiesResponse,
+            graph::LinkedMemory,
+            graph::EntityDetailResponse,
+            graph::SubgraphResponse,
+            graph::CypherQueryRequest,
+            graph::CypherQueryResponse,
+            graph::ExportFormat,
             // Agents
             agents::AgentToolInfo,
             agents::AgentSummary,
@@ -150,6 +239,57 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
             commands::ListCommandsResponse,
             commands::CompactRequest,
             commands::CompactResponse,
+            // Usage
+            usage::UsageRowResponse,
+            usage::WorkstreamBudgetResponse,
+            usage::UsageReportResponse,
+            // Webhooks
+            webhooks::CreateWebhookRequest,
+            webhooks::UpdateWebhookRequest,
+            webhooks::WebhookResponse,
+            webhooks::CreateWebhookResponse,
+            webhooks::ListWebhooksResponse,
+            webhooks::DeadLetterResponse,
+            webhooks::ListDeadLettersResponse,
+            webhooks::RedeliverResponse,
+            // Workflows
+            workflows::SaveWorkflowRequest,
+            workflows::WorkflowSummaryResponse,
+            workflows::WorkflowDetailResponse,
+            workflows::ListWorkflowsResponse,
+            workflows::ScheduleResponse,
+            workflows::ScheduleWorkflowRequest,
+            workflows::RunWorkflowRequest,
+            workflows::RunWorkflowResponse,
+            workflows::RunWorkflowAcceptedResponse,
+            workflows::SaveRuntimeRequest,
+            workflows::RuntimeResponse,
+            workflows::ListRuntimesResponse,
+            // Plugins
+            plugins::InstallPluginRequest,
+            plugins::CapabilitySummaryResponse,
+            plugins::PluginResponse,
+            plugins::ListPluginsResponse,
+            plugins::SyncResultResponse,
+            plugins::SyncPluginResponse,
+            plugins::SyncPluginsResponse,
+            // Inbound hooks
+            hooks::HookAcceptedResponse,
+            // Search
+            search::MessageSearchHitResponse,
+            search::MessageSearchFacetsResponse,
+            search::MessageSearchResponse,
+            // OpenAI compatibility
+            openai::ChatCompletionRequest,
+            openai::ChatCompletionMessage,
+            openai::StreamOptions,
+            openai::ChatCompletionResponse,
+            openai::ChatCompletionChoice,
+            openai::AssistantMessage,
+            openai::CompletionUsage,
+            openai::ToolActivity,
+            openai::ModelListResponse,
+            openai::ModelInfo,
         )
     ),
     modifiers(&SecurityAddon),
@@ -159,10 +299,19 @@ use super::{agents, chat, commands, config, health, mcp, memory, sessions, tasks
         (name = "sessions", description = "Session management"),
         (name = "workstreams", description = "Workstream management"),
         (name = "memory", description = "Memory and notes"),
+        (name = "graph", description = "Knowledge graph"),
         (name = "chat", description = "Chat endpoints"),
         (name = "agents", description = "Agent information"),
         (name = "tasks", description = "Background tasks"),
         (name = "mcp", description = "MCP server management"),
+        (name = "usage", description = "Token usage and cost accounting"),
+        (name = "webhooks", description = "Outbound webhook subscriptions"),
+        (name = "workflows", description = "Pipeline workflows and WASM runtimes"),
+        (name = "plugins", description = "Plugin management"),
+        (name = "hooks", description = "Inbound webhook triggers"),
+        (name = "metrics", description = "Prometheus metrics"),
+        (name = "search", description = "Search over message history"),
+        (name = "openai", description = "OpenAI-compatible chat completions"),
     )
 )]
 pub struct ApiDoc;
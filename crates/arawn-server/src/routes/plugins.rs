@@ -0,0 +1,623 @@
+//! Plugin management endpoints.
+//!
+//! Lists plugins from the plugin directories and subscriptions, subscribes to
+//! new ones, and enables, disables, syncs and removes them. Changes are saved
+//! to the runtime `plugins.json` files and applied to the running agent's
+//! skills, hooks, subagents and prompt fragments immediately.
+//!
+//! `{id}` is a plugin name, a subscription ID (`github/owner-repo`,
+//! percent-encoded) or a GitHub `owner/repo` (percent-encoded).
+
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use arawn_domain::{
+    CapabilitySummary, PluginInfo, PluginService, PluginSource, SyncAction, SyncResult,
+};
+
+use crate::error::ServerError;
+use crate::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Request/Response Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request to subscribe to a plugin.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InstallPluginRequest {
+    /// GitHub `owner/repo` shorthand, a git URL, or an absolute path on the server.
+    pub source: String,
+    /// Git branch, tag or commit (default: `main`).
+    #[serde(default, rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Save to the project's `.arawn/plugins.json` instead of the global one.
+    #[serde(default)]
+    pub project: bool,
+}
+
+/// Declared vs discovered capabilities of a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CapabilitySummaryResponse {
+    /// Whether the manifest declares skills.
+    pub skills_declared: bool,
+    /// Skills found on disk.
+    pub skills_found: usize,
+    /// Whether the manifest declares agents.
+    pub agents_declared: bool,
+    /// Agents found on disk.
+    pub agents_found: usize,
+    /// Whether the manifest declares hooks.
+    pub hooks_declared: bool,
+    /// Hook config files found.
+    pub hooks_found: usize,
+    /// Whether the manifest declares commands.
+    pub commands_declared: bool,
+    /// Commands found on disk.
+    pub commands_found: usize,
+    /// Capabilities found but not declared, or declared but missing.
+    pub warnings: Vec<String>,
+}
+
+impl From<CapabilitySummary> for CapabilitySummaryResponse {
+    fn from(c: CapabilitySummary) -> Self {
+        Self {
+            warnings: c
+                .warnings()
+                .into_iter()
+                .chain(c.errors().into_iter().map(|e| e.to_string()))
+                .collect(),
+            skills_declared: c.skills_declared,
+            skills_found: c.skills_found,
+            agents_declared: c.agents_declared,
+            agents_found: c.agents_found,
+            hooks_declared: c.hooks_declared,
+            hooks_found: c.hooks_found,
+            commands_declared: c.commands_declared,
+            commands_found: c.commands_found,
+        }
+    }
+}
+
+/// A plugin.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PluginResponse {
+    /// Subscription ID, or `name@local` for plugins in plugin directories.
+    pub id: String,
+    /// Plugin name.
+    pub name: String,
+    /// Plugin version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Plugin description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Where the plugin comes from: `directory`, `github`, `url` or `local`.
+    pub source: String,
+    /// GitHub `owner/repo` of a GitHub subscription.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Clone URL of a URL subscription.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Requested git ref of a subscription.
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Locked commit of the subscription checkout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Plugin directory on the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Whether the plugin is enabled.
+    pub enabled: bool,
+    /// Whether the plugin is loaded into the running agent.
+    pub loaded: bool,
+    /// Declared vs discovered capabilities (absent if the manifest can't be read).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<CapabilitySummaryResponse>,
+    /// Skill names (invoked as `/plugin:skill`).
+    pub skills: Vec<String>,
+    /// Subagent names (available to the `delegate` tool).
+    pub agents: Vec<String>,
+    /// Number of hooks.
+    pub hooks: usize,
+    /// MCP servers, namespaced as `plugin:server`.
+    pub mcp_servers: Vec<String>,
+    /// Why the plugin could not be loaded or synced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<PluginInfo> for PluginResponse {
+    fn from(p: PluginInfo) -> Self {
+        let source = match p.subscription.as_ref().map(|s| s.source) {
+            None => "directory",
+            Some(PluginSource::GitHub) => "github",
+            Some(PluginSource::Url) => "url",
+            Some(PluginSource::Local) => "local",
+        };
+        let (repo, url, git_ref) = match p.subscription {
+            Some(s) => (s.repo, s.url, s.git_ref),
+            None => (None, None, None),
+        };
+        Self {
+            id: p.id,
+            name: p.name,
+            version: p.version,
+            description: p.description,
+            source: source.to_string(),
+            repo,
+            url,
+            git_ref,
+            commit: p.commit,
+            path: p.path.map(|p| p.display().to_string()),
+            enabled: p.enabled,
+            loaded: p.loaded,
+            capabilities: p.capabilities.map(Into::into),
+            skills: p.skills,
+            agents: p.agents,
+            hooks: p.hooks,
+            mcp_servers: p.mcp_servers,
+            error: p.error,
+        }
+    }
+}
+
+/// Response for listing plugins.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListPluginsResponse {
+    /// Plugins, sorted by name.
+    pub plugins: Vec<PluginResponse>,
+}
+
+/// Outcome of syncing one subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SyncResultResponse {
+    /// Subscription ID (or plugin ID for plugins in plugin directories).
+    pub id: String,
+    /// `cloned`, `updated`, `skipped`, `clone_failed`, `update_failed` or
+    /// `integrity_failed`.
+    pub action: String,
+    /// Error message for failed syncs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<SyncResult> for SyncResultResponse {
+    fn from(r: SyncResult) -> Self {
+        let action = match r.action {
+            SyncAction::Cloned => "cloned",
+            SyncAction::Updated => "updated",
+            SyncAction::Skipped => "skipped",
+            SyncAction::CloneFailed => "clone_failed",
+            SyncAction::UpdateFailed => "update_failed",
+            SyncAction::IntegrityFailed => "integrity_failed",
+        };
+        Self {
+            id: r.subscription_id,
+            action: action.to_string(),
+            error: r.error,
+        }
+    }
+}
+
+/// Response for syncing one plugin.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncPluginResponse {
+    /// What the sync did.
+    pub result: SyncResultResponse,
+    /// The plugin after reloading.
+    pub plugin: PluginResponse,
+}
+
+/// Response for syncing all subscriptions.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncPluginsResponse {
+    /// One result per enabled subscription.
+    pub results: Vec<SyncResultResponse>,
+}
+
+/// Query parameters for removing a plugin.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+pub struct RemovePluginQuery {
+    /// Also delete the git checkout.
+    #[serde(default)]
+    pub delete_cache: bool,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn require_plugins(state: &AppState) -> Result<&Arc<PluginService>, ServerError> {
+    state
+        .plugins()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Plugins not enabled".to_string()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/plugins - List plugins.
+#[utoipa::path(
+    get,
+    path = "/api/v1/plugins",
+    responses(
+        (status = 200, description = "Plugins with their capabilities and load errors", body = ListPluginsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn list_plugins_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListPluginsResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    Ok(Json(ListPluginsResponse {
+        plugins: plugins.list().await.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /api/v1/plugins - Subscribe to a plugin.
+#[utoipa::path(
+    post,
+    path = "/api/v1/plugins",
+    request_body = InstallPluginRequest,
+    responses(
+        (status = 201, description = "Plugin subscribed and loaded", body = PluginResponse),
+        (status = 400, description = "Bad source, already subscribed, or clone failed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn install_plugin_handler(
+    State(state): State<AppState>,
+    Json(request): Json<InstallPluginRequest>,
+) -> Result<(StatusCode, Json<PluginResponse>), ServerError> {
+    let plugins = require_plugins(&state)?;
+    if request.source.trim().is_empty() {
+        return Err(ServerError::BadRequest("source is required".to_string()));
+    }
+    let plugin = plugins
+        .install(&request.source, request.git_ref, request.project)
+        .await?;
+    Ok((StatusCode::CREATED, Json(plugin.into())))
+}
+
+/// GET /api/v1/plugins/{id} - Get a plugin.
+#[utoipa::path(
+    get,
+    path = "/api/v1/plugins/{id}",
+    params(("id" = String, Path, description = "Plugin name or ID")),
+    responses(
+        (status = 200, description = "Plugin", body = PluginResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plugin not found"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn get_plugin_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PluginResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    Ok(Json(plugins.get(&id).await?.into()))
+}
+
+/// DELETE /api/v1/plugins/{id} - Unsubscribe from a plugin.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/plugins/{id}",
+    params(("id" = String, Path, description = "Plugin name or subscription ID"), RemovePluginQuery),
+    responses(
+        (status = 204, description = "Plugin removed"),
+        (status = 400, description = "Plugin is not a runtime subscription; disable it instead"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plugin not found"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn remove_plugin_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<RemovePluginQuery>,
+) -> Result<StatusCode, ServerError> {
+    let plugins = require_plugins(&state)?;
+    plugins.remove(&id, query.delete_cache).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/plugins/{id}/enable - Enable a plugin.
+#[utoipa::path(
+    post,
+    path = "/api/v1/plugins/{id}/enable",
+    params(("id" = String, Path, description = "Plugin name or ID")),
+    responses(
+        (status = 200, description = "Plugin enabled and loaded", body = PluginResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plugin not found"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn enable_plugin_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PluginResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    Ok(Json(plugins.set_enabled(&id, true).await?.into()))
+}
+
+/// POST /api/v1/plugins/{id}/disable - Disable a plugin.
+#[utoipa::path(
+    post,
+    path = "/api/v1/plugins/{id}/disable",
+    params(("id" = String, Path, description = "Plugin name or ID")),
+    responses(
+        (status = 200, description = "Plugin disabled and unloaded", body = PluginResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plugin not found"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn disable_plugin_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<PluginResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    Ok(Json(plugins.set_enabled(&id, false).await?.into()))
+}
+
+/// POST /api/v1/plugins/{id}/sync - Update a plugin.
+#[utoipa::path(
+    post,
+    path = "/api/v1/plugins/{id}/sync",
+    params(("id" = String, Path, description = "Plugin name or ID")),
+    responses(
+        (status = 200, description = "Plugin updated, relocked and reloaded", body = SyncPluginResponse),
+        (status = 400, description = "Update failed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plugin not found"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn sync_plugin_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<SyncPluginResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    let (result, plugin) = plugins.sync(&id).await?;
+    Ok(Json(SyncPluginResponse {
+        result: result.into(),
+        plugin: plugin.into(),
+    }))
+}
+
+/// POST /api/v1/plugins/sync - Update all plugins.
+#[utoipa::path(
+    post,
+    path = "/api/v1/plugins/sync",
+    responses(
+        (status = 200, description = "Per-subscription results; plugins are reloaded", body = SyncPluginsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Plugins not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "plugins"
+)]
+pub async fn sync_plugins_handler(
+    State(state): State<AppState>,
+) -> Result<Json<SyncPluginsResponse>, ServerError> {
+    let plugins = require_plugins(&state)?;
+    Ok(Json(SyncPluginsResponse {
+        results: plugins
+            .sync_all()
+            .await
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, PluginRuntime, PluginWatcher, SubscriptionManager, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use arawn_plugin::PluginManager;
+    use axum::{
+        Router,
+        body::Body,
+        http::Request,
+        routing::{get, post},
+    };
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    fn create_state() -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    fn write_plugin(dir: &std::path::Path, name: &str) {
+        let root = dir.join(name);
+        std::fs::create_dir_all(root.join(".claude-plugin")).unwrap();
+        std::fs::write(
+            root.join(".claude-plugin/plugin.json"),
+            format!(r#"{{"name": "{name}", "description": "Greets", "skills": "./skills/"}}"#),
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("skills/hello")).unwrap();
+        std::fs::write(
+            root.join("skills/hello/SKILL.md"),
+            "---\nname: hello\ndescription: Say hello\n---\nSay hello.",
+        )
+        .unwrap();
+    }
+
+    async fn create_state_with_plugins(dir: &TempDir) -> (AppState, PluginRuntime) {
+        write_plugin(&dir.path().join("plugins"), "greeter");
+        let subs = SubscriptionManager::new(Vec::new(), None)
+            .unwrap()
+            .with_global_config_path(dir.path().join("plugins.json"))
+            .unwrap()
+            .with_cache_dir(dir.path().join("cache"));
+        let watcher = PluginWatcher::new(PluginManager::new(vec![dir.path().join("plugins")]));
+        let runtime = PluginRuntime::new();
+        let service = PluginService::new(Arc::new(watcher), runtime.clone(), subs);
+        service.load_initial().await;
+        (create_state().with_plugins(service), runtime)
+    }
+
+    fn router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/plugins",
+                get(list_plugins_handler).post(install_plugin_handler),
+            )
+            .route("/plugins/sync", post(sync_plugins_handler))
+            .route(
+                "/plugins/{id}",
+                get(get_plugin_handler).delete(remove_plugin_handler),
+            )
+            .route("/plugins/{id}/enable", post(enable_plugin_handler))
+            .route("/plugins/{id}/disable", post(disable_plugin_handler))
+            .route("/plugins/{id}/sync", post(sync_plugin_handler))
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        let body = match body {
+            Some(json) => {
+                request = request.header("Content-Type", "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+        let response = router(state.clone())
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, json)
+    }
+
+    #[tokio::test]
+    async fn test_plugins_unavailable_when_disabled() {
+        let state = create_state();
+        let (status, _) = send(&state, "GET", "/plugins", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_list_disable_enable() {
+        let dir = TempDir::new().unwrap();
+        let (state, runtime) = create_state_with_plugins(&dir).await;
+
+        let (status, list) = send(&state, "GET", "/plugins", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let plugin = &list["plugins"][0];
+        assert_eq!(plugin["id"], "greeter@local");
+        assert_eq!(plugin["source"], "directory");
+        assert_eq!(plugin["loaded"], true);
+        assert_eq!(plugin["skills"][0], "hello");
+        assert_eq!(plugin["capabilities"]["skills_found"], 1);
+        assert_eq!(runtime.skill_count(), 1);
+
+        let (status, plugin) = send(&state, "POST", "/plugins/greeter/disable", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(plugin["enabled"], false);
+        assert_eq!(plugin["loaded"], false);
+        assert_eq!(runtime.skill_count(), 0);
+
+        let (status, plugin) = send(&state, "POST", "/plugins/greeter%40local/enable", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(plugin["loaded"], true);
+        assert_eq!(runtime.skill_count(), 1);
+
+        let (status, _) = send(&state, "POST", "/plugins/nope/enable", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_install_sync_remove_local() {
+        let dir = TempDir::new().unwrap();
+        let (state, runtime) = create_state_with_plugins(&dir).await;
+        write_plugin(&dir.path().join("elsewhere"), "extra");
+        let source = dir.path().join("elsewhere/extra").display().to_string();
+
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/plugins",
+            Some(serde_json::json!({ "source": "" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, plugin) = send(
+            &state,
+            "POST",
+            "/plugins",
+            Some(serde_json::json!({ "source": source })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(plugin["name"], "extra");
+        assert_eq!(plugin["source"], "local");
+        assert_eq!(runtime.skill_count(), 2);
+
+        let (status, synced) = send(&state, "POST", "/plugins/extra/sync", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(synced["result"]["action"], "skipped");
+
+        let (status, all) = send(&state, "POST", "/plugins/sync", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(all["results"].as_array().unwrap().len(), 1);
+
+        // Directory plugins can only be disabled
+        let (status, _) = send(&state, "DELETE", "/plugins/greeter", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(&state, "DELETE", "/plugins/extra?delete_cache=true", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(runtime.skill_count(), 1);
+        let (status, _) = send(&state, "GET", "/plugins/extra", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,405 @@
+//! Search endpoints.
+//!
+//! Full-text search over message history across all workstreams. The index
+//! is brought up to date with the JSONL history on every query.
+
+use std::collections::BTreeMap;
+
+use arawn_domain::{ResourceKind, SCRATCH_ID};
+use arawn_types::{MessageSearchHit, MessageSearchQuery, MessageSearchResults};
+use axum::{Extension, Json, extract::Query, extract::State};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::pagination::MAX_PAGE_SIZE;
+use crate::auth::{self, Identity};
+use crate::error::ServerError;
+use crate::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Query parameters for message search.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct MessageSearchParams {
+    /// Words to search for. All must match; a trailing `*` matches a prefix.
+    pub q: String,
+    /// Only search this workstream.
+    pub workstream: Option<String>,
+    /// Only search this session.
+    pub session: Option<String>,
+    /// Comma-separated roles (`user`, `assistant`, `system`, `tool_use`, `tool_result`).
+    pub role: Option<String>,
+    /// Comma-separated tags; only workstreams with one of them are searched.
+    pub tag: Option<String>,
+    /// Earliest message time (RFC 3339 or YYYY-MM-DD, UTC).
+    pub since: Option<String>,
+    /// Latest message time (RFC 3339 or YYYY-MM-DD, UTC; a date includes the whole day).
+    pub until: Option<String>,
+    /// Maximum number of hits (default: 20, max: 100).
+    pub limit: Option<usize>,
+    /// Number of hits to skip.
+    #[serde(default)]
+    pub offset: usize,
+}
+
+/// A message matching the search.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MessageSearchHitResponse {
+    /// Message ID.
+    pub message_id: String,
+    /// Workstream the message belongs to.
+    pub workstream_id: String,
+    /// Title of that workstream.
+    pub workstream_title: String,
+    /// Session the message was sent in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Message role.
+    pub role: String,
+    /// When the message was sent.
+    pub timestamp: String,
+    /// Excerpt around the match, with matched terms wrapped in `**`.
+    pub snippet: String,
+    /// Relevance score; higher is better.
+    pub score: f64,
+}
+
+impl From<MessageSearchHit> for MessageSearchHitResponse {
+    fn from(hit: MessageSearchHit) -> Self {
+        Self {
+            message_id: hit.message_id,
+            workstream_id: hit.workstream_id,
+            workstream_title: hit.workstream_title,
+            session_id: hit.session_id,
+            role: hit.role,
+            timestamp: hit.timestamp.to_rfc3339(),
+            snippet: hit.snippet,
+            score: hit.score,
+        }
+    }
+}
+
+/// Match counts per facet value, across all pages.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct MessageSearchFacetsResponse {
+    /// Matches per role.
+    pub roles: BTreeMap<String, usize>,
+    /// Matches per workstream ID.
+    pub workstreams: BTreeMap<String, usize>,
+}
+
+/// Upper bound on matches scanned when results must be filtered by owner.
+const MAX_RESTRICTED_MATCHES: usize = 10_000;
+
+/// Keep hits in visible workstreams (and, in shared scratch, visible
+/// sessions), recomputing facets and total before paging.
+fn restrict_results(
+    all: MessageSearchResults,
+    workstreams: &std::collections::HashSet<String>,
+    sessions: &std::collections::HashSet<String>,
+    limit: usize,
+    offset: usize,
+) -> MessageSearchResults {
+    let visible: Vec<MessageSearchHit> = all
+        .hits
+        .into_iter()
+        .filter(|hit| {
+            workstreams.contains(&hit.workstream_id)
+                && (hit.workstream_id != SCRATCH_ID
+                    || hit
+                        .session_id
+                        .as_ref()
+                        .is_some_and(|s| sessions.contains(s)))
+        })
+        .collect();
+
+    let mut results = MessageSearchResults {
+        total: visible.len(),
+        ..Default::default()
+    };
+    for hit in &visible {
+        *results.facets.roles.entry(hit.role.clone()).or_default() += 1;
+        *results
+            .facets
+            .workstreams
+            .entry(hit.workstream_id.clone())
+            .or_default() += 1;
+    }
+    results.hits = visible.into_iter().skip(offset).take(limit).collect();
+    results
+}
+
+/// Response for message search.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MessageSearchResponse {
+    /// The query that was executed.
+    pub query: String,
+    /// Hits ordered by relevance.
+    pub hits: Vec<MessageSearchHitResponse>,
+    /// Total matches across all pages.
+    pub total: usize,
+    /// Page size used.
+    pub limit: usize,
+    /// Hits skipped.
+    pub offset: usize,
+    /// Match counts per role and workstream.
+    pub facets: MessageSearchFacetsResponse,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parse an RFC 3339 timestamp or a date. Dates resolve to the start of the
+/// day, or to its last instant when `end_of_day` is set.
+fn parse_time(
+    field: &str,
+    value: Option<&str>,
+    end_of_day: bool,
+) -> Result<Option<DateTime<Utc>>, ServerError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(ts.with_timezone(&Utc)));
+    }
+    let day = value.parse::<NaiveDate>().map_err(|_| {
+        ServerError::BadRequest(format!(
+            "Invalid {} '{}' (expected RFC 3339 or YYYY-MM-DD)",
+            field, value
+        ))
+    })?;
+    let time = if end_of_day {
+        day.and_hms_micro_opt(23, 59, 59, 999_999)
+    } else {
+        day.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.map(|t| t.and_utc()))
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/search/messages - Full-text search over message history.
+#[utoipa::path(
+    get,
+    path = "/api/v1/search/messages",
+    params(MessageSearchParams),
+    responses(
+        (status = 200, description = "Search results", body = MessageSearchResponse),
+        (status = 400, description = "Missing query or invalid parameters"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Workstreams not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "search"
+)]
+pub async fn search_messages_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(params): Query<MessageSearchParams>,
+) -> Result<Json<MessageSearchResponse>, ServerError> {
+    let manager = state
+        .workstreams()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Workstreams not configured".to_string()))?;
+
+    if !params.q.chars().any(char::is_alphanumeric) {
+        return Err(ServerError::BadRequest(
+            "Query parameter 'q' must contain a search term".to_string(),
+        ));
+    }
+
+    let limit = params
+        .limit
+        .unwrap_or(arawn_types::DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_PAGE_SIZE);
+    let mut query = MessageSearchQuery::new(params.q.clone())
+        .with_time_range(
+            parse_time("since", params.since.as_deref(), false)?,
+            parse_time("until", params.until.as_deref(), true)?,
+        )
+        .with_page(limit, params.offset);
+    query.workstream_id = params.workstream;
+    query.session_id = params.session;
+    query.roles = split_list(params.role.as_deref());
+    query.tags = split_list(params.tag.as_deref());
+
+    let visible_workstreams = auth::visible_ids(&state, &identity, ResourceKind::Workstream);
+    let results = match visible_workstreams {
+        None => manager
+            .search_messages(&query)
+            .map_err(|e| ServerError::Storage(e.to_string()))?,
+        Some(workstreams) => {
+            // Restricted identities: fetch every match, drop what they can't
+            // see, then page and count in memory so totals don't leak.
+            query.limit = MAX_RESTRICTED_MATCHES;
+            query.offset = 0;
+            let all = manager
+                .search_messages(&query)
+                .map_err(|e| ServerError::Storage(e.to_string()))?;
+            let sessions =
+                auth::visible_ids(&state, &identity, ResourceKind::Session).unwrap_or_default();
+            restrict_results(all, &workstreams, &sessions, limit, params.offset)
+        }
+    };
+
+    Ok(Json(MessageSearchResponse {
+        query: params.q,
+        hits: results.hits.into_iter().map(Into::into).collect(),
+        total: results.total,
+        limit,
+        offset: params.offset,
+        facets: MessageSearchFacetsResponse {
+            roles: results.facets.roles,
+            workstreams: results.facets.workstreams,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::auth_middleware;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use arawn_workstream::{MessageRole, WorkstreamConfig, WorkstreamManager};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    fn create_test_state(with_workstreams: bool) -> (AppState, tempfile::TempDir) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        let state = AppState::new(agent, ServerConfig::new(Some("test-token".to_string())));
+        if !with_workstreams {
+            return (state, temp_dir);
+        }
+
+        let mgr = WorkstreamManager::new(&WorkstreamConfig {
+            db_path: temp_dir.path().join("workstreams.db"),
+            data_dir: temp_dir.path().join("workstreams"),
+            session_timeout_minutes: 30,
+        })
+        .unwrap();
+        let ops = mgr
+            .create_workstream("Ops", None, &["infra".to_string()])
+            .unwrap();
+        let blog = mgr.create_workstream("Blog", None, &[]).unwrap();
+        for (ws, role, content) in [
+            (
+                &ops.id,
+                MessageRole::User,
+                "How do we rotate the TLS certificates?",
+            ),
+            (
+                &ops.id,
+                MessageRole::Assistant,
+                "Certificates rotate via the renew job.",
+            ),
+            (
+                &blog.id,
+                MessageRole::User,
+                "Write about certificates for beginners",
+            ),
+        ] {
+            mgr.send_message(Some(ws), None, role, content, None)
+                .unwrap();
+        }
+        (state.with_workstreams(mgr), temp_dir)
+    }
+
+    async fn request(state: AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+        let app = Router::new()
+            .route("/search/messages", get(search_messages_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("Authorization", "Bearer test-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_with_facets() {
+        let (state, _dir) = create_test_state(true);
+
+        let (status, body) = request(state.clone(), "/search/messages?q=certificates").await;
+        assert_eq!(status, StatusCode::OK);
+        let resp: MessageSearchResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(resp.total, 3);
+        assert_eq!(resp.facets.roles["user"], 2);
+        assert_eq!(resp.limit, 20);
+
+        let (_, body) = request(
+            state.clone(),
+            "/search/messages?q=certificates&tag=infra&role=user,assistant&limit=1",
+        )
+        .await;
+        let resp: MessageSearchResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(resp.total, 2);
+        assert_eq!(resp.hits.len(), 1);
+        assert_eq!(resp.hits[0].workstream_title, "Ops");
+
+        let (_, body) = request(state, "/search/messages?q=certificates&until=2000-01-01").await;
+        let resp: MessageSearchResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(resp.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_bad_request() {
+        let (state, _dir) = create_test_state(true);
+
+        let (status, _) = request(state.clone(), "/search/messages?q=%20").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = request(state, "/search/messages?q=x&since=last-week").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_search_messages_without_workstreams() {
+        let (state, _dir) = create_test_state(false);
+        let (status, _) = request(state, "/search/messages?q=anything").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
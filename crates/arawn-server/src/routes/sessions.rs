@@ -9,10 +9,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
 
-use arawn_domain::{Session, SessionId};
+use arawn_domain::{ForkPoint, ResourceKind, Session, SessionId};
 
 use super::pagination::PaginationParams;
-use crate::auth::Identity;
+use crate::auth::{self, Identity};
 use crate::error::ServerError;
 use crate::state::AppState;
 
@@ -47,6 +47,50 @@ pub struct UpdateSessionRequest {
     pub workstream_id: Option<String>,
 }
 
+/// Request to fork a session.
+///
+/// At most one of `message_id` and `turns` may be set; with neither, the
+/// whole session is copied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ForkSessionRequest {
+    /// Keep history up to and including this workstream message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Keep only the first N turns (fork just before the next user message).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turns: Option<usize>,
+}
+
+impl ForkSessionRequest {
+    /// Resolve the requested fork point.
+    fn fork_point(&self) -> Result<ForkPoint, ServerError> {
+        match (&self.message_id, self.turns) {
+            (Some(_), Some(_)) => Err(ServerError::BadRequest(
+                "Specify either message_id or turns, not both".to_string(),
+            )),
+            (Some(id), None) => Ok(ForkPoint::Message(id.clone())),
+            (None, Some(n)) => Ok(ForkPoint::Turns(n)),
+            (None, None) => Ok(ForkPoint::End),
+        }
+    }
+}
+
+/// Response from forking a session.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ForkSessionResponse {
+    /// ID of the new session.
+    pub session_id: String,
+    /// Workstream both sessions belong to.
+    pub workstream_id: String,
+    /// Session the fork was copied from.
+    pub parent_session_id: String,
+    /// Last message copied from the parent (absent for an empty fork).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forked_from_message_id: Option<String>,
+    /// Number of messages copied into the new session.
+    pub messages_copied: usize,
+}
+
 /// Message info for conversation history.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MessageInfo {
@@ -163,11 +207,17 @@ pub struct ListSessionsResponse {
 )]
 pub async fn create_session_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Json(request): Json<CreateSessionRequest>,
 ) -> Result<(StatusCode, Json<SessionDetail>), ServerError> {
     // Create a new session with optional metadata
     let session_id = state.get_or_create_session(None).await;
+    auth::claim(
+        &state,
+        &identity,
+        ResourceKind::Session,
+        &session_id.to_string(),
+    );
 
     // Update metadata if provided
     if !request.metadata.is_empty() || request.title.is_some() {
@@ -231,15 +281,20 @@ pub async fn create_session_handler(
 )]
 pub async fn list_sessions_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Query(pagination): Query<PaginationParams>,
 ) -> Result<Json<ListSessionsResponse>, ServerError> {
     let mut summaries: Vec<SessionSummary> = Vec::new();
     let mut seen_ids = std::collections::HashSet::new();
+    let visible = auth::visible_ids(&state, &identity, ResourceKind::Session);
+    let is_visible = |id: &str| visible.as_ref().is_none_or(|ids| ids.contains(id));
 
     // Get sessions from the cache (active sessions)
     let cached_sessions = state.session_cache().all_sessions().await;
     for (_, session) in cached_sessions {
+        if !is_visible(&session.id.to_string()) {
+            continue;
+        }
         let title = session
             .metadata
             .get("title")
@@ -263,7 +318,7 @@ pub async fn list_sessions_handler(
             if let Ok(ws_sessions) = workstreams.list_sessions(&ws.id) {
                 for ws_session in ws_sessions {
                     // Skip if we already have this session from cache
-                    if seen_ids.contains(&ws_session.id) {
+                    if seen_ids.contains(&ws_session.id) || !is_visible(&ws_session.id) {
                         continue;
                     }
                     seen_ids.insert(ws_session.id.clone());
@@ -311,10 +366,11 @@ pub async fn list_sessions_handler(
 )]
 pub async fn get_session_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(session_id): Path<String>,
 ) -> Result<Json<SessionDetail>, ServerError> {
     let id = parse_session_id(&session_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Session, &session_id)?;
 
     // Helper to get allowed paths for a session
     let get_allowed_paths = |ws_id: &str, sess_id: &str| {
@@ -385,13 +441,15 @@ pub async fn get_session_handler(
 )]
 pub async fn delete_session_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(session_id): Path<String>,
 ) -> Result<StatusCode, ServerError> {
     let id = parse_session_id(&session_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Session, &session_id)?;
 
     // Try removing from the in-memory cache first (handles active sessions)
     let was_in_cache = state.close_session(id).await;
+    auth::release(&state, ResourceKind::Session, &session_id);
 
     // Also delete from persistent workstream storage (whether or not it was in cache)
     if let Some(workstreams) = state.workstreams() {
@@ -440,7 +498,7 @@ pub async fn delete_session_handler(
 )]
 pub async fn update_session_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(session_id): Path<String>,
     Json(request): Json<UpdateSessionRequest>,
 ) -> Result<Json<SessionDetail>, ServerError> {
@@ -452,6 +510,7 @@ pub async fn update_session_handler(
     );
 
     let id = parse_session_id(&session_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Session, &session_id)?;
 
     // Track the workstream ID for potential reload after reassignment
     let mut target_workstream_id: Option<String> = None;
@@ -467,6 +526,12 @@ pub async fn update_session_handler(
                 new_workstream_id
             )));
         }
+        auth::authorize(
+            &state,
+            &identity,
+            ResourceKind::Workstream,
+            new_workstream_id,
+        )?;
         tracing::info!(
             session_id = %session_id,
             new_workstream_id = %new_workstream_id,
@@ -646,6 +711,75 @@ pub async fn update_session_handler(
     }
 }
 
+/// POST /api/v1/sessions/:id/fork - Fork a session from an earlier message.
+///
+/// Creates a new session in the same workstream whose history is a prefix
+/// of this one. The original session is left untouched.
+#[utoipa::path(
+    post,
+    path = "/api/v1/sessions/{id}/fork",
+    params(("id" = String, Path, description = "Session ID")),
+    request_body = ForkSessionRequest,
+    responses(
+        (status = 201, description = "Session forked", body = ForkSessionResponse),
+        (status = 400, description = "Invalid session ID or fork point"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session or message not found"),
+        (status = 503, description = "Workstreams not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sessions"
+)]
+pub async fn fork_session_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(session_id): Path<String>,
+    Json(request): Json<ForkSessionRequest>,
+) -> Result<(StatusCode, Json<ForkSessionResponse>), ServerError> {
+    auth::authorize(&state, &identity, ResourceKind::Session, &session_id)?;
+    let response = fork_session(&state, &session_id, &request).await?;
+    auth::claim(
+        &state,
+        &identity,
+        ResourceKind::Session,
+        &response.session_id,
+    );
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Fork a persisted session (shared by the REST endpoint and the `fork` command).
+pub(crate) async fn fork_session(
+    state: &AppState,
+    session_id: &str,
+    request: &ForkSessionRequest,
+) -> Result<ForkSessionResponse, ServerError> {
+    let id = parse_session_id(session_id)?;
+    let point = request.fork_point()?;
+    let workstreams = state
+        .workstreams()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Workstreams not configured".to_string()))?;
+
+    // Cached sessions know their workstream; otherwise look for a stored record
+    let workstream_id = match state.session_cache().get_workstream_id(&id).await {
+        Some(ws_id) => ws_id,
+        None => workstreams
+            .store()
+            .get_session(session_id)
+            .map(|s| s.workstream_id)
+            .map_err(|_| ServerError::NotFound(format!("Session {} not found", session_id)))?,
+    };
+
+    let (fork, messages_copied) = workstreams.fork_session(&workstream_id, session_id, &point)?;
+
+    Ok(ForkSessionResponse {
+        session_id: fork.id,
+        workstream_id: fork.workstream_id,
+        parent_session_id: session_id.to_string(),
+        forked_from_message_id: fork.forked_from_message_id,
+        messages_copied,
+    })
+}
+
 /// GET /api/v1/sessions/:id/messages - Get session conversation history.
 #[utoipa::path(
     get,
@@ -661,10 +795,11 @@ pub async fn update_session_handler(
 )]
 pub async fn get_session_messages_handler(
     State(state): State<AppState>,
-    Extension(_identity): Extension<Identity>,
+    Extension(identity): Extension<Identity>,
     Path(session_id): Path<String>,
 ) -> Result<Json<SessionMessagesResponse>, ServerError> {
     let id = parse_session_id(&session_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Session, &session_id)?;
 
     // Try session cache first
     let session = if let Some(session) = state.session_cache().get(&id).await {
@@ -847,6 +982,64 @@ mod tests {
             .with_state(state)
     }
 
+    #[tokio::test]
+    async fn test_sessions_isolated_by_owner() {
+        use arawn_domain::{AccessStore, TokenScope};
+
+        let store = AccessStore::open_in_memory().unwrap();
+        let scopes = [TokenScope::Read, TokenScope::Chat];
+        let (_, alice) = store.create_token("a", "alice", &scopes, None).unwrap();
+        let (_, bob) = store.create_token("b", "bob", &scopes, None).unwrap();
+        let state = create_test_state().with_access_store(std::sync::Arc::new(store));
+        let app = create_test_router(state);
+
+        let send = |method: &str, uri: &str, token: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Authorization", format!("Bearer {}", token))
+                .header("Content-Type", "application/json")
+                .body(Body::from(if method == "POST" { "{}" } else { "" }))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/sessions", &alice))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let created: SessionDetail = serde_json::from_slice(&body).unwrap();
+        let uri = format!("/sessions/{}", created.id);
+
+        for (token, expected) in [(&alice, 1), (&bob, 0)] {
+            let response = app
+                .clone()
+                .oneshot(send("GET", "/sessions", token))
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let result: ListSessionsResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(result.total, expected);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(send("GET", &uri, &alice))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.clone().oneshot(send("GET", &uri, &bob)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app.oneshot(send("DELETE", &uri, &bob)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_list_sessions_empty() {
         let state = create_test_state();
@@ -83,6 +83,10 @@ pub struct TaskDetail {
     /// Error message if failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Output of a completed task, for tasks that produce one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<serde_json::Value>,
 }
 
 /// Response for listing tasks.
@@ -120,6 +124,7 @@ fn task_to_detail(task: &TrackedTask) -> TaskDetail {
         started_at: task.started_at.map(|dt| dt.to_rfc3339()),
         completed_at: task.completed_at.map(|dt| dt.to_rfc3339()),
         error: task.error.clone(),
+        result: task.result.clone(),
     }
 }
 
@@ -0,0 +1,165 @@
+//! Workstream template listing endpoint.
+//!
+//! Templates are applied through `POST /api/v1/workstreams` with a
+//! `template` field; this endpoint lists what is available.
+
+use axum::{Json, extract::State};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use arawn_domain::{TemplateSource, WorkstreamTemplate};
+
+use crate::error::ServerError;
+use crate::state::AppState;
+
+/// A parameter a template asks for.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemplateParamInfo {
+    /// Parameter name.
+    pub name: String,
+    /// What the value is for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Value used when none is given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Whether a value must be given.
+    pub required: bool,
+}
+
+/// An available workstream template.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemplateInfo {
+    /// Template name (`<plugin>:<template>` for plugin templates).
+    pub name: String,
+    /// What the template is for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Plugin that provides the template, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    /// Parameters the template asks for.
+    pub params: Vec<TemplateParamInfo>,
+}
+
+/// Response for listing templates.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListTemplatesResponse {
+    /// Available templates, sorted by name.
+    pub templates: Vec<TemplateInfo>,
+}
+
+impl From<WorkstreamTemplate> for TemplateInfo {
+    fn from(t: WorkstreamTemplate) -> Self {
+        Self {
+            plugin: match t.source {
+                TemplateSource::Plugin { plugin } => Some(plugin),
+                TemplateSource::Directory => None,
+            },
+            params: t
+                .params
+                .into_iter()
+                .map(|p| TemplateParamInfo {
+                    required: p.is_required(),
+                    name: p.name,
+                    description: p.description,
+                    default: p.default,
+                })
+                .collect(),
+            name: t.name,
+            description: t.description,
+        }
+    }
+}
+
+/// GET /api/v1/templates - List workstream templates.
+#[utoipa::path(
+    get,
+    path = "/api/v1/templates",
+    responses(
+        (status = 200, description = "Available templates", body = ListTemplatesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Workstream templates not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn list_templates_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListTemplatesResponse>, ServerError> {
+    let templates = state.templates().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Workstream templates not configured".to_string())
+    })?;
+
+    Ok(Json(ListTemplatesResponse {
+        templates: templates.list().into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, TemplateRegistry, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use axum::{Router, body::Body, http::Request, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    fn create_state() -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    async fn get_templates(state: AppState) -> (StatusCode, serde_json::Value) {
+        let app = Router::new()
+            .route("/templates", get(list_templates_handler))
+            .with_state(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/templates")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        let research = dir.path().join("research");
+        std::fs::create_dir_all(&research).unwrap();
+        std::fs::write(
+            research.join("template.toml"),
+            "description = \"Research\"\n[[params]]\nname = \"topic\"\n",
+        )
+        .unwrap();
+
+        let state = create_state().with_templates(TemplateRegistry::new().with_dir(dir.path()));
+        let (status, body) = get_templates(state).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["templates"][0]["name"], "research");
+        assert_eq!(body["templates"][0]["params"][0]["required"], true);
+        assert!(body["templates"][0].get("plugin").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_templates_not_configured() {
+        let (status, _) = get_templates(create_state()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -0,0 +1,321 @@
+//! Token usage and cost reporting endpoint.
+//!
+//! Reports aggregated LLM usage from the usage ledger, grouped by day,
+//! session, workstream, profile or model. When filtered to a single
+//! workstream, the response also includes its spend against configured limits.
+
+use arawn_domain::{UsageGroupBy, UsageQuery, UsageRow};
+use arawn_types::BudgetStatus;
+use axum::{Extension, Json, extract::Query, extract::State};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth::Identity;
+use crate::error::ServerError;
+use crate::state::AppState;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Query parameters for the usage endpoint.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct UsageReportQuery {
+    /// Only include usage from this workstream.
+    pub workstream: Option<String>,
+    /// Only include usage from this session.
+    pub session: Option<String>,
+    /// Only include usage from this LLM profile.
+    pub profile: Option<String>,
+    /// First day included (YYYY-MM-DD, UTC).
+    pub since: Option<String>,
+    /// Last day included (YYYY-MM-DD, UTC).
+    pub until: Option<String>,
+    /// Group rows by `day` (default), `session`, `workstream`, `profile` or `model`.
+    pub group_by: Option<String>,
+}
+
+/// Aggregated usage for one group.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UsageRowResponse {
+    /// Group value (day, session ID, ...); empty workstream means no workstream.
+    pub key: String,
+    /// Number of LLM calls.
+    pub calls: u64,
+    /// Input tokens.
+    pub input_tokens: u64,
+    /// Output tokens.
+    pub output_tokens: u64,
+    /// Prompt-cache write tokens.
+    pub cache_creation_input_tokens: u64,
+    /// Prompt-cache read tokens.
+    pub cache_read_input_tokens: u64,
+    /// Cost in USD.
+    pub cost_usd: f64,
+}
+
+impl From<UsageRow> for UsageRowResponse {
+    fn from(row: UsageRow) -> Self {
+        Self {
+            key: row.key,
+            calls: row.calls,
+            input_tokens: row.input_tokens,
+            output_tokens: row.output_tokens,
+            cache_creation_input_tokens: row.cache_creation_input_tokens,
+            cache_read_input_tokens: row.cache_read_input_tokens,
+            cost_usd: row.cost_usd,
+        }
+    }
+}
+
+/// Spend of a workstream against its limits for the current period.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkstreamBudgetResponse {
+    /// Workstream ID.
+    pub workstream_id: String,
+    /// Limit period (`day`, `month` or `total`).
+    pub period: String,
+    /// Spend in the current period (USD).
+    pub spent_usd: f64,
+    /// Soft limit (USD), if configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_limit_usd: Option<f64>,
+    /// Hard limit (USD), if configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hard_limit_usd: Option<f64>,
+    /// `within_limit`, `soft_limit_exceeded` or `hard_limit_exceeded`.
+    pub status: String,
+}
+
+/// Response for the usage endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UsageReportResponse {
+    /// Dimension the rows are grouped by.
+    pub group_by: String,
+    /// One row per group, ordered by key.
+    pub rows: Vec<UsageRowResponse>,
+    /// Sum over all rows.
+    pub total: UsageRowResponse,
+    /// Spend against limits (only when filtered by workstream).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<WorkstreamBudgetResponse>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn parse_day(field: &str, value: Option<&str>) -> Result<Option<NaiveDate>, ServerError> {
+    value
+        .map(|v| {
+            v.parse::<NaiveDate>().map_err(|_| {
+                ServerError::BadRequest(format!(
+                    "Invalid {} date '{}' (expected YYYY-MM-DD)",
+                    field, v
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn enum_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/usage - Aggregated token usage and cost.
+#[utoipa::path(
+    get,
+    path = "/api/v1/usage",
+    params(UsageReportQuery),
+    responses(
+        (status = 200, description = "Usage report", body = UsageReportResponse),
+        (status = 400, description = "Invalid query parameters"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Usage accounting not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "usage"
+)]
+pub async fn get_usage_report_handler(
+    State(state): State<AppState>,
+    Extension(_identity): Extension<Identity>,
+    Query(query): Query<UsageReportQuery>,
+) -> Result<Json<UsageReportResponse>, ServerError> {
+    let ledger = state.usage_ledger().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Usage accounting not enabled".to_string())
+    })?;
+
+    let group_by = match query.group_by.as_deref() {
+        Some(g) => g.parse::<UsageGroupBy>().map_err(ServerError::BadRequest)?,
+        None => UsageGroupBy::default(),
+    };
+    let usage_query = UsageQuery {
+        workstream_id: query.workstream.clone(),
+        session_id: query.session,
+        profile: query.profile,
+        since: parse_day("since", query.since.as_deref())?,
+        until: parse_day("until", query.until.as_deref())?,
+        group_by,
+    };
+
+    let report = ledger
+        .report(&usage_query)
+        .map_err(|e| ServerError::Storage(e.to_string()))?;
+
+    let budget = match query.workstream {
+        Some(ws_id) => {
+            let limits = ledger.limits();
+            let limit = limits.limit_for(&ws_id);
+            let since = limits.period().start(Utc::now().date_naive());
+            let spent_usd = ledger
+                .spend_since(&ws_id, since)
+                .map_err(|e| ServerError::Storage(e.to_string()))?;
+            let status = match limit.status(spent_usd) {
+                BudgetStatus::WithinLimit => "within_limit",
+                BudgetStatus::SoftLimitExceeded { .. } => "soft_limit_exceeded",
+                BudgetStatus::HardLimitExceeded { .. } => "hard_limit_exceeded",
+            };
+            Some(WorkstreamBudgetResponse {
+                workstream_id: ws_id,
+                period: enum_name(&limits.period()),
+                spent_usd,
+                soft_limit_usd: limit.soft_usd,
+                hard_limit_usd: limit.hard_usd,
+                status: status.to_string(),
+            })
+        }
+        None => None,
+    };
+
+    Ok(Json(UsageReportResponse {
+        group_by: enum_name(&report.group_by),
+        rows: report.rows.into_iter().map(Into::into).collect(),
+        total: report.total.into(),
+        budget,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::auth_middleware;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, LimitPeriod, SpendLimit, SpendLimits, ToolRegistry, UsageLedger};
+    use arawn_llm::MockBackend;
+    use arawn_types::{UsageEntry, UsageRecorder};
+    use axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn create_test_state(ledger: Option<Arc<UsageLedger>>) -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        let state = AppState::new(agent, ServerConfig::new(Some("test-token".to_string())));
+        match ledger {
+            Some(ledger) => state.with_usage_ledger(ledger),
+            None => state,
+        }
+    }
+
+    async fn request(state: AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+        let app = Router::new()
+            .route("/usage", get(get_usage_report_handler))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("Authorization", "Bearer test-token")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn entry(ws: &str, profile: &str, cost_usd: f64) -> UsageEntry {
+        UsageEntry {
+            session_id: "s1".to_string(),
+            workstream_id: Some(ws.to_string()),
+            profile: profile.to_string(),
+            model: "m".to_string(),
+            input_tokens: 10,
+            output_tokens: 5,
+            cost_usd,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_usage_report_grouped_with_budget() {
+        let limits = SpendLimits::new(LimitPeriod::Total)
+            .with_default(SpendLimit::new(Some(1.0), Some(5.0)));
+        let ledger = Arc::new(UsageLedger::open_in_memory().unwrap().with_limits(limits));
+        ledger.record(&entry("ws-a", "fast", 0.5)).unwrap();
+        ledger.record(&entry("ws-a", "quality", 1.0)).unwrap();
+        ledger.record(&entry("ws-b", "fast", 2.0)).unwrap();
+
+        let (status, body) = request(
+            create_test_state(Some(ledger)),
+            "/usage?workstream=ws-a&group_by=profile",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let report: UsageReportResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(report.group_by, "profile");
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].key, "fast");
+        assert_eq!(report.total.calls, 2);
+        assert!((report.total.cost_usd - 1.5).abs() < 1e-9);
+
+        let budget = report.budget.unwrap();
+        assert_eq!(budget.period, "total");
+        assert_eq!(budget.status, "soft_limit_exceeded");
+        assert_eq!(budget.hard_limit_usd, Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn test_usage_report_invalid_params() {
+        let ledger = Arc::new(UsageLedger::open_in_memory().unwrap());
+        let state = create_test_state(Some(ledger));
+
+        let (status, _) = request(state.clone(), "/usage?group_by=week").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = request(state, "/usage?since=yesterday").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_usage_report_disabled() {
+        let (status, _) = request(create_test_state(None), "/usage").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
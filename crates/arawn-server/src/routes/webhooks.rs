@@ -0,0 +1,662 @@
+//! Outbound webhook subscription endpoints.
+//!
+//! Subscriptions are persisted in the webhook store; deliveries that ran out
+//! of retries are listed as dead letters and can be redelivered.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use arawn_domain::{DeadLetter, NewWebhook, WebhookEventKind, WebhookSubscription, WebhookUpdate};
+
+use crate::error::ServerError;
+use crate::state::AppState;
+use crate::webhooks::WebhookDispatcher;
+
+/// Default number of dead letters listed.
+const DEFAULT_DEAD_LETTER_LIMIT: usize = 50;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Request/Response Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request to create a webhook subscription.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// Endpoint events are POSTed to.
+    pub url: String,
+    /// Events to deliver (empty or omitted for all).
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub events: Vec<WebhookEventKind>,
+    /// Only deliver events from these workstreams (empty or omitted for all).
+    #[serde(default)]
+    pub workstreams: Vec<String>,
+    /// Free-form description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Signing secret (generated when omitted).
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Request to update a webhook subscription; omitted fields are unchanged.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    /// New endpoint URL.
+    pub url: Option<String>,
+    /// New event filter (empty for all).
+    #[schema(value_type = Option<Vec<String>>)]
+    pub events: Option<Vec<WebhookEventKind>>,
+    /// New workstream filter (empty for all).
+    pub workstreams: Option<Vec<String>>,
+    /// New description.
+    pub description: Option<String>,
+    /// Pause or resume deliveries.
+    pub enabled: Option<bool>,
+}
+
+/// A webhook subscription (without its secret).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    /// Subscription ID.
+    pub id: String,
+    /// Endpoint URL.
+    pub url: String,
+    /// Delivered events (empty means all).
+    #[schema(value_type = Vec<String>)]
+    pub events: Vec<WebhookEventKind>,
+    /// Workstream filter (empty means all).
+    pub workstreams: Vec<String>,
+    /// Free-form description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether deliveries are active.
+    pub enabled: bool,
+    /// When the subscription was created.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookSubscription> for WebhookResponse {
+    fn from(w: WebhookSubscription) -> Self {
+        Self {
+            id: w.id,
+            url: w.url,
+            events: w.events,
+            workstreams: w.workstreams,
+            description: w.description,
+            enabled: w.enabled,
+            created_at: w.created_at,
+        }
+    }
+}
+
+/// A newly created webhook subscription, including its signing secret.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateWebhookResponse {
+    /// The subscription.
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    /// HMAC-SHA256 signing secret. Only returned here.
+    pub secret: String,
+}
+
+/// Response for listing webhook subscriptions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListWebhooksResponse {
+    /// Subscriptions, oldest first.
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// Query for listing dead letters.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DeadLettersQuery {
+    /// Only dead letters for this subscription.
+    pub webhook_id: Option<String>,
+    /// Maximum entries (default 50).
+    pub limit: Option<usize>,
+}
+
+/// A delivery that failed after every retry.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DeadLetterResponse {
+    /// Dead letter ID.
+    pub id: String,
+    /// Subscription the delivery was for.
+    pub webhook_id: String,
+    /// ID of the undelivered event.
+    pub event_id: String,
+    /// Event type.
+    #[schema(value_type = String)]
+    pub event: WebhookEventKind,
+    /// The event as it was sent.
+    pub payload: serde_json::Value,
+    /// Delivery attempts made.
+    pub attempts: u32,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_status: Option<u16>,
+    /// Error of the last attempt.
+    pub last_error: String,
+    /// When the delivery was given up on.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DeadLetter> for DeadLetterResponse {
+    fn from(d: DeadLetter) -> Self {
+        Self {
+            payload: serde_json::from_str(&d.payload)
+                .unwrap_or(serde_json::Value::String(d.payload)),
+            id: d.id,
+            webhook_id: d.subscription_id,
+            event_id: d.event_id,
+            event: d.event,
+            attempts: d.attempts,
+            last_status: d.last_status,
+            last_error: d.last_error,
+            created_at: d.created_at,
+        }
+    }
+}
+
+/// Response for listing dead letters.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListDeadLettersResponse {
+    /// Dead letters, newest first.
+    pub dead_letters: Vec<DeadLetterResponse>,
+}
+
+/// Result of a redelivery attempt.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RedeliverResponse {
+    /// Whether the endpoint accepted the event (the dead letter is then removed).
+    pub delivered: bool,
+    /// HTTP status returned by the endpoint, if it answered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Why the redelivery failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn require_webhooks(state: &AppState) -> Result<&Arc<WebhookDispatcher>, ServerError> {
+    state
+        .webhooks()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Webhooks not configured".to_string()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// POST /api/v1/webhooks - Create a webhook subscription.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid URL or secret"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn create_webhook_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreateWebhookResponse>), ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    let webhook = webhooks.store().create(NewWebhook {
+        url: request.url,
+        secret: request.secret,
+        events: request.events,
+        workstreams: request.workstreams,
+        description: request.description,
+    })?;
+
+    let secret = webhook.secret.clone();
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateWebhookResponse {
+            webhook: webhook.into(),
+            secret,
+        }),
+    ))
+}
+
+/// GET /api/v1/webhooks - List webhook subscriptions.
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    responses(
+        (status = 200, description = "Webhook subscriptions", body = ListWebhooksResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn list_webhooks_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListWebhooksResponse>, ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    Ok(Json(ListWebhooksResponse {
+        webhooks: webhooks
+            .store()
+            .list()?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+/// GET /api/v1/webhooks/{id} - Get a webhook subscription.
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{id}",
+    params(("id" = String, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook subscription", body = WebhookResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Webhook not found"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn get_webhook_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<WebhookResponse>, ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    let webhook = webhooks
+        .store()
+        .get(&id)?
+        .ok_or_else(|| ServerError::NotFound(format!("Webhook {} not found", id)))?;
+    Ok(Json(webhook.into()))
+}
+
+/// PATCH /api/v1/webhooks/{id} - Update a webhook subscription.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/webhooks/{id}",
+    params(("id" = String, Path, description = "Webhook ID")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Invalid URL"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Webhook not found"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn update_webhook_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    let webhook = webhooks.store().update(
+        &id,
+        WebhookUpdate {
+            url: request.url,
+            events: request.events,
+            workstreams: request.workstreams,
+            description: request.description,
+            enabled: request.enabled,
+        },
+    )?;
+    Ok(Json(webhook.into()))
+}
+
+/// DELETE /api/v1/webhooks/{id} - Delete a webhook subscription.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/{id}",
+    params(("id" = String, Path, description = "Webhook ID")),
+    responses(
+        (status = 204, description = "Webhook and its dead letters deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Webhook not found"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn delete_webhook_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ServerError> {
+    require_webhooks(&state)?.store().delete(&id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/webhooks/dead-letters - List failed deliveries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/dead-letters",
+    params(DeadLettersQuery),
+    responses(
+        (status = 200, description = "Dead letters", body = ListDeadLettersResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn list_dead_letters_handler(
+    State(state): State<AppState>,
+    Query(query): Query<DeadLettersQuery>,
+) -> Result<Json<ListDeadLettersResponse>, ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    let dead_letters = webhooks.store().dead_letters(
+        query.webhook_id.as_deref(),
+        query.limit.unwrap_or(DEFAULT_DEAD_LETTER_LIMIT),
+    )?;
+    Ok(Json(ListDeadLettersResponse {
+        dead_letters: dead_letters.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /api/v1/webhooks/dead-letters/{id}/redeliver - Retry a failed delivery.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/dead-letters/{id}/redeliver",
+    params(("id" = String, Path, description = "Dead letter ID")),
+    responses(
+        (status = 200, description = "Redelivery attempted", body = RedeliverResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Dead letter not found"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn redeliver_dead_letter_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<RedeliverResponse>, ServerError> {
+    let webhooks = require_webhooks(&state)?;
+    let dead = webhooks
+        .store()
+        .dead_letter(&id)?
+        .ok_or_else(|| ServerError::NotFound(format!("Dead letter {} not found", id)))?;
+
+    Ok(Json(match webhooks.redeliver(&dead).await {
+        Ok(()) => RedeliverResponse {
+            delivered: true,
+            status: None,
+            error: None,
+        },
+        Err(failure) => RedeliverResponse {
+            delivered: false,
+            status: failure.status,
+            error: Some(failure.error),
+        },
+    }))
+}
+
+/// DELETE /api/v1/webhooks/dead-letters/{id} - Discard a failed delivery.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/dead-letters/{id}",
+    params(("id" = String, Path, description = "Dead letter ID")),
+    responses(
+        (status = 204, description = "Dead letter deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Dead letter not found"),
+        (status = 503, description = "Webhooks not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn delete_dead_letter_handler(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ServerError> {
+    require_webhooks(&state)?.store().delete_dead_letter(&id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, ToolRegistry, WebhookStore};
+    use arawn_llm::MockBackend;
+    use axum::{
+        Router,
+        body::Body,
+        http::Request,
+        routing::{get, post},
+    };
+    use tower::ServiceExt;
+
+    fn create_state() -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    fn create_state_with_webhooks() -> AppState {
+        let store = Arc::new(WebhookStore::open_in_memory().unwrap());
+        create_state().with_webhooks(Arc::new(WebhookDispatcher::new(store)))
+    }
+
+    fn router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/webhooks",
+                post(create_webhook_handler).get(list_webhooks_handler),
+            )
+            .route("/webhooks/dead-letters", get(list_dead_letters_handler))
+            .route(
+                "/webhooks/dead-letters/{id}",
+                axum::routing::delete(delete_dead_letter_handler),
+            )
+            .route(
+                "/webhooks/dead-letters/{id}/redeliver",
+                post(redeliver_dead_letter_handler),
+            )
+            .route(
+                "/webhooks/{id}",
+                get(get_webhook_handler)
+                    .patch(update_webhook_handler)
+                    .delete(delete_webhook_handler),
+            )
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        let body = match body {
+            Some(json) => {
+                request = request.header("Content-Type", "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+        let response = router(state.clone())
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_webhook_crud() {
+        let state = create_state_with_webhooks();
+
+        let (status, created) = send(
+            &state,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({
+                "url": "https://example.com/hook",
+                "events": ["tool_failed", "session_ended"],
+                "workstreams": ["ws1"],
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert!(created["secret"].as_str().unwrap().starts_with("whsec_"));
+        assert_eq!(
+            created["events"],
+            serde_json::json!(["session_ended", "tool_failed"])
+        );
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let (status, listed) = send(&state, "GET", "/webhooks", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["webhooks"].as_array().unwrap().len(), 1);
+        assert!(listed["webhooks"][0].get("secret").is_none());
+
+        let (status, updated) = send(
+            &state,
+            "PATCH",
+            &format!("/webhooks/{}", id),
+            Some(serde_json::json!({ "enabled": false })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["enabled"], false);
+        assert_eq!(updated["workstreams"], serde_json::json!(["ws1"]));
+
+        let (status, _) = send(&state, "DELETE", &format!("/webhooks/{}", id), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&state, "GET", &format!("/webhooks/{}", id), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_validation() {
+        let state = create_state_with_webhooks();
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({ "url": "not-a-url" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/webhooks",
+            Some(serde_json::json!({ "url": "https://x", "events": ["bogus"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letters() {
+        let state = create_state_with_webhooks();
+        let store = state.webhooks().unwrap().store().clone();
+        // Nothing listens on port 9, so redelivery fails
+        let webhook = store
+            .create(NewWebhook {
+                url: "http://127.0.0.1:9/hook".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        let dead = store
+            .record_dead_letter(
+                &webhook.id,
+                "evt1",
+                WebhookEventKind::DiskPressure,
+                r#"{"type":"disk_pressure"}"#,
+                5,
+                Some(503),
+                "HTTP 503",
+            )
+            .unwrap();
+
+        let (status, listed) = send(
+            &state,
+            "GET",
+            &format!("/webhooks/dead-letters?webhook_id={}", webhook.id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["dead_letters"][0]["event"], "disk_pressure");
+        assert_eq!(
+            listed["dead_letters"][0]["payload"]["type"],
+            "disk_pressure"
+        );
+        assert_eq!(listed["dead_letters"][0]["last_status"], 503);
+
+        let (status, result) = send(
+            &state,
+            "POST",
+            &format!("/webhooks/dead-letters/{}/redeliver", dead.id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["delivered"], false);
+        assert!(store.dead_letter(&dead.id).unwrap().is_some());
+
+        let (status, _) = send(
+            &state,
+            "DELETE",
+            &format!("/webhooks/dead-letters/{}", dead.id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(
+            &state,
+            "POST",
+            &format!("/webhooks/dead-letters/{}/redeliver", dead.id),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_not_configured() {
+        let (status, _) = send(&create_state(), "GET", "/webhooks", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
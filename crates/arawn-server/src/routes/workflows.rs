@@ -0,0 +1,908 @@
+//! Workflow and runtime catalog endpoints.
+//!
+//! Workflows are TOML definitions run by the pipeline engine; runtimes are
+//! the WASM modules their tasks execute. Runs are synchronous by default, or
+//! tracked as a task with `"async": true`.
+
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use arawn_domain::{
+    ExecutionResult, ExecutionStatus, RuntimeCategory, RuntimeInfo, RuntimeSource, ScheduleInfo,
+    WorkflowService, WorkflowSummary,
+};
+
+use crate::error::ServerError;
+use crate::state::{AppState, TaskStatus, TrackedTask};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Request/Response Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request to create or replace a workflow.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveWorkflowRequest {
+    /// Workflow definition as TOML (a `[workflow]` table with its tasks).
+    pub definition: String,
+}
+
+/// A workflow known to the engine or the workflow directory.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowSummaryResponse {
+    /// Workflow name.
+    pub name: String,
+    /// Description from the definition.
+    pub description: String,
+    /// Whether the engine can run it.
+    pub registered: bool,
+    /// Definition file on the server, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Number of tasks.
+    pub task_count: usize,
+}
+
+impl From<WorkflowSummary> for WorkflowSummaryResponse {
+    fn from(w: WorkflowSummary) -> Self {
+        Self {
+            name: w.name,
+            description: w.description,
+            registered: w.registered,
+            path: w.path.map(|p| p.display().to_string()),
+            task_count: w.task_count,
+        }
+    }
+}
+
+/// A cron schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleResponse {
+    /// Schedule ID.
+    pub id: String,
+    /// Scheduled workflow.
+    pub workflow_name: String,
+    /// Cron expression.
+    pub cron_expr: String,
+    /// Whether the schedule is active.
+    pub enabled: bool,
+}
+
+impl From<ScheduleInfo> for ScheduleResponse {
+    fn from(s: ScheduleInfo) -> Self {
+        Self {
+            id: s.id,
+            workflow_name: s.workflow_name,
+            cron_expr: s.cron_expr,
+            enabled: s.enabled,
+        }
+    }
+}
+
+/// A workflow with its definition and schedules.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowDetailResponse {
+    /// Name, registration and file.
+    #[serde(flatten)]
+    pub workflow: WorkflowSummaryResponse,
+    /// Parsed definition (absent for workflows registered without a file).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub definition: Option<serde_json::Value>,
+    /// TOML source of the definition.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Cron schedules for this workflow.
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+/// Response for listing workflows.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListWorkflowsResponse {
+    /// Workflows, sorted by name.
+    pub workflows: Vec<WorkflowSummaryResponse>,
+    /// All cron schedules (empty when cron is disabled).
+    pub schedules: Vec<ScheduleResponse>,
+}
+
+/// Request to run a workflow.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct RunWorkflowRequest {
+    /// Available to tasks as `{{input.*}}`.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub input: serde_json::Value,
+    /// Return immediately with a task ID instead of waiting for the run.
+    #[serde(default, rename = "async")]
+    pub run_async: bool,
+}
+
+/// Result of a workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RunWorkflowResponse {
+    /// Execution ID.
+    pub execution_id: String,
+    /// `completed`, `failed`, `running` or `timed_out`.
+    pub status: String,
+    /// Failure message for failed runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Final context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub output: Option<serde_json::Value>,
+}
+
+impl From<ExecutionResult> for RunWorkflowResponse {
+    fn from(r: ExecutionResult) -> Self {
+        let (status, error) = match r.status {
+            ExecutionStatus::Completed => ("completed", None),
+            ExecutionStatus::Failed(msg) => ("failed", Some(msg)),
+            ExecutionStatus::Running => ("running", None),
+            ExecutionStatus::TimedOut => ("timed_out", None),
+        };
+        Self {
+            execution_id: r.execution_id,
+            status: status.to_string(),
+            error,
+            output: r.output,
+        }
+    }
+}
+
+/// An asynchronous run that was started.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RunWorkflowAcceptedResponse {
+    /// Task tracking the run (see `/api/v1/tasks/{id}`).
+    pub task_id: String,
+    /// Workflow being run.
+    pub workflow: String,
+}
+
+/// Request to schedule a workflow.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScheduleWorkflowRequest {
+    /// Cron expression (e.g. `0 9 * * *`).
+    pub cron: String,
+    /// IANA timezone (default `UTC`).
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+/// Request to add or replace a custom runtime. Give exactly one of `source`
+/// and `wasm_path`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SaveRuntimeRequest {
+    /// Human-readable description.
+    #[serde(default)]
+    pub description: String,
+    /// Rust source to compile to WASM.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Path of a pre-built `.wasm` module on the server.
+    #[serde(default)]
+    pub wasm_path: Option<String>,
+}
+
+/// A runtime catalog entry.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RuntimeResponse {
+    /// Runtime name, as used in a task's `runtime` field.
+    pub name: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Module path relative to the runtimes directory.
+    pub path: String,
+    /// `builtin` or `custom`.
+    pub category: String,
+    /// Absolute module path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<String>,
+    /// Whether the module file exists.
+    pub wasm_exists: bool,
+}
+
+impl From<RuntimeInfo> for RuntimeResponse {
+    fn from(r: RuntimeInfo) -> Self {
+        Self {
+            name: r.name,
+            description: r.entry.description,
+            path: r.entry.path,
+            category: match r.entry.category {
+                RuntimeCategory::Builtin => "builtin",
+                RuntimeCategory::Custom => "custom",
+            }
+            .to_string(),
+            resolved_path: r.resolved_path.map(|p| p.display().to_string()),
+            wasm_exists: r.wasm_exists,
+        }
+    }
+}
+
+/// Response for listing runtimes.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListRuntimesResponse {
+    /// Runtimes, sorted by name.
+    pub runtimes: Vec<RuntimeResponse>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn require_workflows(state: &AppState) -> Result<&Arc<WorkflowService>, ServerError> {
+    state
+        .workflows()
+        .ok_or_else(|| ServerError::ServiceUnavailable("Workflows not enabled".to_string()))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Workflow Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/workflows - List workflows and schedules.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows",
+    responses(
+        (status = 200, description = "Workflows and schedules", body = ListWorkflowsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn list_workflows_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListWorkflowsResponse>, ServerError> {
+    let workflows = require_workflows(&state)?;
+    let schedules = match workflows.schedules().await {
+        Ok(schedules) => schedules.into_iter().map(Into::into).collect(),
+        Err(e) => {
+            tracing::debug!("Failed to list schedules (cron may be disabled): {e}");
+            Vec::new()
+        }
+    };
+    Ok(Json(ListWorkflowsResponse {
+        workflows: workflows.list().await.into_iter().map(Into::into).collect(),
+        schedules,
+    }))
+}
+
+/// POST /api/v1/workflows - Create a workflow.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows",
+    request_body = SaveWorkflowRequest,
+    responses(
+        (status = 201, description = "Workflow created", body = WorkflowSummaryResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Workflow already exists"),
+        (status = 422, description = "Invalid definition; `details.issues` lists the problems"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn create_workflow_handler(
+    State(state): State<AppState>,
+    Json(request): Json<SaveWorkflowRequest>,
+) -> Result<(StatusCode, Json<WorkflowSummaryResponse>), ServerError> {
+    let workflows = require_workflows(&state)?;
+    let name = workflows.check(None, &request.definition)?.workflow.name;
+    if workflows.get(&name).await.is_ok() {
+        return Err(ServerError::Conflict(format!(
+            "Workflow '{name}' already exists"
+        )));
+    }
+    let (workflow, _) = workflows.save(None, &request.definition).await?;
+    Ok((StatusCode::CREATED, Json(workflow.into())))
+}
+
+/// GET /api/v1/workflows/{name} - Get a workflow.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workflows/{name}",
+    params(("name" = String, Path, description = "Workflow name")),
+    responses(
+        (status = 200, description = "Workflow details", body = WorkflowDetailResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workflow not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn get_workflow_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<WorkflowDetailResponse>, ServerError> {
+    let detail = require_workflows(&state)?.get(&name).await?;
+    Ok(Json(WorkflowDetailResponse {
+        workflow: detail.summary.into(),
+        definition: detail.definition.map(serde_json::to_value).transpose()?,
+        source: detail.source,
+        schedules: detail.schedules.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// PUT /api/v1/workflows/{name} - Create or replace a workflow.
+#[utoipa::path(
+    put,
+    path = "/api/v1/workflows/{name}",
+    params(("name" = String, Path, description = "Workflow name; must match the definition")),
+    request_body = SaveWorkflowRequest,
+    responses(
+        (status = 200, description = "Workflow replaced", body = WorkflowSummaryResponse),
+        (status = 201, description = "Workflow created", body = WorkflowSummaryResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 422, description = "Invalid definition; `details.issues` lists the problems"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn put_workflow_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<SaveWorkflowRequest>,
+) -> Result<(StatusCode, Json<WorkflowSummaryResponse>), ServerError> {
+    let (workflow, created) = require_workflows(&state)?
+        .save(Some(&name), &request.definition)
+        .await?;
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(workflow.into())))
+}
+
+/// DELETE /api/v1/workflows/{name} - Delete a workflow.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/workflows/{name}",
+    params(("name" = String, Path, description = "Workflow name")),
+    responses(
+        (status = 204, description = "Workflow deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workflow not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn delete_workflow_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ServerError> {
+    require_workflows(&state)?.delete(&name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/workflows/{name}/run - Run a workflow.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/{name}/run",
+    params(("name" = String, Path, description = "Workflow name")),
+    request_body = RunWorkflowRequest,
+    responses(
+        (status = 200, description = "Run finished", body = RunWorkflowResponse),
+        (status = 202, description = "Run started as a task", body = RunWorkflowAcceptedResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workflow not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn run_workflow_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<RunWorkflowRequest>,
+) -> Result<Response, ServerError> {
+    let workflows = Arc::clone(require_workflows(&state)?);
+    let input = if request.input.is_null() {
+        serde_json::json!({})
+    } else {
+        request.input
+    };
+
+    if !request.run_async {
+        let result = workflows.run(&name, input).await?;
+        return Ok(Json(RunWorkflowResponse::from(result)).into_response());
+    }
+
+    if !workflows.engine().has_workflow(&name).await {
+        return Err(ServerError::NotFound(format!("Workflow '{name}'")));
+    }
+
+    let task_id = uuid::Uuid::new_v4().to_string();
+    let mut task = TrackedTask::new(&task_id, "workflow");
+    task.message = Some(format!("Running workflow {name}"));
+    task.start();
+    state.tasks().write().await.insert(task_id.clone(), task);
+
+    let tasks = state.tasks().clone();
+    let id = task_id.clone();
+    let workflow = name.clone();
+    tokio::spawn(async move {
+        let result = workflows.run(&workflow, input).await;
+        let mut tasks = tasks.write().await;
+        let Some(task) = tasks.get_mut(&id) else {
+            return;
+        };
+        if task.status == TaskStatus::Cancelled {
+            return;
+        }
+        match result {
+            Ok(result) => {
+                let response = RunWorkflowResponse::from(result);
+                match &response.error {
+                    Some(error) => task.fail(error.clone()),
+                    None => task.complete(Some(format!("Workflow {workflow} {}", response.status))),
+                }
+                task.result = serde_json::to_value(&response).ok();
+            }
+            Err(e) => task.fail(e.to_string()),
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RunWorkflowAcceptedResponse {
+            task_id,
+            workflow: name,
+        }),
+    )
+        .into_response())
+}
+
+/// POST /api/v1/workflows/{name}/schedules - Schedule a workflow.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workflows/{name}/schedules",
+    params(("name" = String, Path, description = "Workflow name")),
+    request_body = ScheduleWorkflowRequest,
+    responses(
+        (status = 201, description = "Schedule created", body = ScheduleResponse),
+        (status = 400, description = "Invalid cron expression or timezone"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workflow not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn schedule_workflow_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<ScheduleWorkflowRequest>,
+) -> Result<(StatusCode, Json<ScheduleResponse>), ServerError> {
+    let workflows = require_workflows(&state)?;
+    let id = workflows
+        .schedule(&name, &request.cron, &request.timezone)
+        .await?;
+    // Prefer the stored schedule, which carries the runner's own ID
+    let schedule = workflows
+        .get(&name)
+        .await?
+        .schedules
+        .into_iter()
+        .find(|s| s.id == id || s.cron_expr == request.cron)
+        .map(ScheduleResponse::from)
+        .unwrap_or(ScheduleResponse {
+            id,
+            workflow_name: name,
+            cron_expr: request.cron,
+            enabled: true,
+        });
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
+
+/// DELETE /api/v1/workflows/{name}/schedules/{id} - Cancel a schedule.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/workflows/{name}/schedules/{id}",
+    params(
+        ("name" = String, Path, description = "Workflow name"),
+        ("id" = String, Path, description = "Schedule ID"),
+    ),
+    responses(
+        (status = 204, description = "Schedule cancelled"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn cancel_schedule_handler(
+    State(state): State<AppState>,
+    Path((name, id)): Path<(String, String)>,
+) -> Result<StatusCode, ServerError> {
+    let workflows = require_workflows(&state)?;
+    let belongs = workflows
+        .schedules()
+        .await?
+        .iter()
+        .any(|s| s.id == id && s.workflow_name == name);
+    if !belongs {
+        return Err(ServerError::NotFound(format!(
+            "Schedule '{id}' for workflow '{name}'"
+        )));
+    }
+    workflows.cancel_schedule(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Runtime Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// GET /api/v1/runtimes - List runtimes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runtimes",
+    responses(
+        (status = 200, description = "Runtime catalog", body = ListRuntimesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn list_runtimes_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListRuntimesResponse>, ServerError> {
+    let runtimes = require_workflows(&state)?.runtimes().await;
+    Ok(Json(ListRuntimesResponse {
+        runtimes: runtimes.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// GET /api/v1/runtimes/{name} - Get a runtime.
+#[utoipa::path(
+    get,
+    path = "/api/v1/runtimes/{name}",
+    params(("name" = String, Path, description = "Runtime name")),
+    responses(
+        (status = 200, description = "Runtime details", body = RuntimeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Runtime not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn get_runtime_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<RuntimeResponse>, ServerError> {
+    let runtime = require_workflows(&state)?.runtime(&name).await?;
+    Ok(Json(runtime.into()))
+}
+
+/// PUT /api/v1/runtimes/{name} - Add or replace a custom runtime.
+#[utoipa::path(
+    put,
+    path = "/api/v1/runtimes/{name}",
+    params(("name" = String, Path, description = "Runtime name")),
+    request_body = SaveRuntimeRequest,
+    responses(
+        (status = 200, description = "Runtime saved", body = RuntimeResponse),
+        (status = 400, description = "Invalid name, builtin runtime, or compilation failed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn put_runtime_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(request): Json<SaveRuntimeRequest>,
+) -> Result<Json<RuntimeResponse>, ServerError> {
+    let source = match (request.source, request.wasm_path) {
+        (Some(source), None) => RuntimeSource::Rust(source),
+        (None, Some(path)) => RuntimeSource::WasmPath(path.into()),
+        _ => {
+            return Err(ServerError::BadRequest(
+                "Give exactly one of 'source' and 'wasm_path'".to_string(),
+            ));
+        }
+    };
+    let runtime = require_workflows(&state)?
+        .save_runtime(&name, request.description, source)
+        .await?;
+    Ok(Json(runtime.into()))
+}
+
+/// DELETE /api/v1/runtimes/{name} - Remove a custom runtime.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/runtimes/{name}",
+    params(("name" = String, Path, description = "Runtime name")),
+    responses(
+        (status = 204, description = "Runtime removed"),
+        (status = 400, description = "Builtin runtimes can't be removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Token lacks the admin scope"),
+        (status = 404, description = "Runtime not found"),
+        (status = 503, description = "Workflows not enabled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workflows"
+)]
+pub async fn delete_runtime_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ServerError> {
+    require_workflows(&state)?.remove_runtime(&name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::MockBackend;
+    use arawn_pipeline::sandbox::ScriptExecutor;
+    use arawn_pipeline::{PipelineConfig, PipelineEngine, RuntimeCatalog};
+    use axum::{
+        Router,
+        body::Body,
+        http::Request,
+        routing::{get, post},
+    };
+    use tempfile::TempDir;
+    use tokio::sync::RwLock;
+    use tower::ServiceExt;
+
+    const DEFINITION: &str = r#"
+[workflow]
+name = "noop"
+description = "Does nothing"
+
+[[workflow.tasks]]
+id = "only"
+runtime = "passthrough"
+"#;
+
+    fn create_state() -> AppState {
+        let agent = Agent::builder()
+            .with_backend(MockBackend::with_text("Test"))
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        AppState::new(agent, ServerConfig::new(Some("test-token".to_string())))
+    }
+
+    async fn create_state_with_workflows(dir: &TempDir) -> AppState {
+        let engine = PipelineEngine::new(
+            &dir.path().join("pipeline.db"),
+            PipelineConfig {
+                cron_enabled: false,
+                triggers_enabled: false,
+                ..PipelineConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+        let executor = ScriptExecutor::new(
+            dir.path().join("wasm-cache"),
+            std::time::Duration::from_secs(30),
+        )
+        .unwrap();
+        let catalog = RuntimeCatalog::load(&dir.path().join("runtimes")).unwrap();
+        create_state().with_workflows(WorkflowService::new(
+            Arc::new(engine),
+            dir.path().join("workflows"),
+            Arc::new(executor),
+            Arc::new(RwLock::new(catalog)),
+        ))
+    }
+
+    fn router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/workflows",
+                get(list_workflows_handler).post(create_workflow_handler),
+            )
+            .route(
+                "/workflows/{name}",
+                get(get_workflow_handler)
+                    .put(put_workflow_handler)
+                    .delete(delete_workflow_handler),
+            )
+            .route("/workflows/{name}/run", post(run_workflow_handler))
+            .route("/runtimes", get(list_runtimes_handler))
+            .route(
+                "/runtimes/{name}",
+                get(get_runtime_handler)
+                    .put(put_runtime_handler)
+                    .delete(delete_runtime_handler),
+            )
+            .with_state(state)
+    }
+
+    async fn send(
+        state: &AppState,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        let body = match body {
+            Some(json) => {
+                request = request.header("Content-Type", "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+        let response = router(state.clone())
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, json)
+    }
+
+    #[tokio::test]
+    async fn test_workflows_unavailable_without_pipeline() {
+        let state = create_state();
+        let (status, _) = send(&state, "GET", "/workflows", None).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_workflow_crud() {
+        let dir = TempDir::new().unwrap();
+        let state = create_state_with_workflows(&dir).await;
+        let body = serde_json::json!({ "definition": DEFINITION });
+
+        let (status, created) = send(&state, "POST", "/workflows", Some(body.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["name"], "noop");
+        assert_eq!(created["registered"], true);
+
+        let (status, _) = send(&state, "POST", "/workflows", Some(body.clone())).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let (status, _) = send(&state, "PUT", "/workflows/noop", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, detail) = send(&state, "GET", "/workflows/noop", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(detail["description"], "Does nothing");
+        assert_eq!(detail["definition"]["tasks"][0]["id"], "only");
+        assert_eq!(detail["source"], DEFINITION);
+
+        let (status, list) = send(&state, "GET", "/workflows", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(list["workflows"].as_array().unwrap().len(), 1);
+
+        let (status, _) = send(&state, "DELETE", "/workflows/noop", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&state, "GET", "/workflows/noop", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_workflow_returns_issues() {
+        let dir = TempDir::new().unwrap();
+        let state = create_state_with_workflows(&dir).await;
+        let definition = r#"
+[workflow]
+name = "broken"
+[[workflow.tasks]]
+id = "a"
+dependencies = ["missing"]
+"#;
+
+        let (status, body) = send(
+            &state,
+            "PUT",
+            "/workflows/other",
+            Some(serde_json::json!({ "definition": definition })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["details"]["workflow"], "broken");
+        let issues = body["details"]["issues"].as_array().unwrap();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[1]["task"], "a");
+    }
+
+    #[tokio::test]
+    async fn test_run_async_tracks_task() {
+        let dir = TempDir::new().unwrap();
+        let state = create_state_with_workflows(&dir).await;
+
+        let (status, _) = send(
+            &state,
+            "POST",
+            "/workflows/missing/run",
+            Some(serde_json::json!({ "async": true })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        send(
+            &state,
+            "POST",
+            "/workflows",
+            Some(serde_json::json!({ "definition": DEFINITION })),
+        )
+        .await;
+        let (status, accepted) = send(
+            &state,
+            "POST",
+            "/workflows/noop/run",
+            Some(serde_json::json!({ "async": true, "input": { "x": 1 } })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let task_id = accepted["task_id"].as_str().unwrap();
+        let task = state.tasks().read().await.get(task_id).cloned().unwrap();
+        assert_eq!(task.task_type, "workflow");
+    }
+
+    #[tokio::test]
+    async fn test_runtime_endpoints() {
+        let dir = TempDir::new().unwrap();
+        let state = create_state_with_workflows(&dir).await;
+        let wasm = dir.path().join("mine.wasm");
+        std::fs::write(&wasm, b"\0asm").unwrap();
+
+        let (status, _) = send(
+            &state,
+            "PUT",
+            "/runtimes/mine",
+            Some(serde_json::json!({ "description": "Mine" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, runtime) = send(
+            &state,
+            "PUT",
+            "/runtimes/mine",
+            Some(serde_json::json!({ "description": "Mine", "wasm_path": wasm })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(runtime["category"], "custom");
+        assert_eq!(runtime["wasm_exists"], true);
+
+        let (_, list) = send(&state, "GET", "/runtimes", None).await;
+        assert_eq!(list["runtimes"][0]["name"], "mine");
+
+        let (status, _) = send(&state, "DELETE", "/runtimes/mine", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&state, "GET", "/runtimes/mine", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
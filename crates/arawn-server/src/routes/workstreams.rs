@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::path::Path as StdPath;
 use std::sync::Arc;
 
 use axum::{
-    Json,
+    Extension, Json,
     extract::{Path, Query, State},
     http::StatusCode,
 };
@@ -10,11 +11,16 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use arawn_types::AgentSettings;
+
 use arawn_domain::{
-    DirectoryError, DirectoryManager, MessageRole, SCRATCH_ID, WorkstreamManager, WorkstreamMessage,
+    DirectoryError, DirectoryManager, FileConflictPolicy, MessageRole, ResourceKind, SCRATCH_ID,
+    SessionId, Snapshot, SnapshotTrigger, TemplateOutcome, TemplateRequest, WorkstreamManager,
+    WorkstreamMessage,
 };
 
 use super::pagination::PaginationParams;
+use crate::auth::{self, Identity};
 use crate::error::ServerError;
 use crate::state::AppState;
 
@@ -43,6 +49,12 @@ pub struct CreateWorkstreamRequest {
     /// Tags for categorization.
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Template to scaffold the workstream from (see `GET /api/v1/templates`).
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Values for the template's parameters.
+    #[serde(default)]
+    pub params: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -66,6 +78,30 @@ pub struct WorkstreamResponse {
     /// Tags for categorization.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// Agent settings applied to this workstream's sessions.
+    #[schema(value_type = Object)]
+    pub settings: AgentSettings,
+    /// What the template added, when the workstream was created from one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateAppliedResponse>,
+}
+
+/// What a template added to a new workstream.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TemplateAppliedResponse {
+    /// Template name.
+    pub name: String,
+    /// Files copied into `production/` and `work/`.
+    pub files: usize,
+    /// Notes created.
+    pub notes: usize,
+    /// Workflow files registered.
+    pub workflows: Vec<String>,
+    /// Cloned repositories, relative to `production/`.
+    pub repos: Vec<String>,
+    /// Parts of the template that could not be applied.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -153,6 +189,61 @@ pub struct PromoteRequest {
     pub default_model: Option<String>,
 }
 
+/// Request to merge a workstream into another.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeWorkstreamRequest {
+    /// ID of the workstream to merge into.
+    pub target: String,
+    /// What to do when a file already exists in the target:
+    /// `rename` (default), `overwrite` or `skip`.
+    #[serde(default)]
+    pub on_conflict: Option<String>,
+}
+
+/// Response from merging workstreams.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MergeWorkstreamResponse {
+    /// The target workstream after the merge.
+    pub workstream: WorkstreamResponse,
+    /// IDs of the sessions moved from the source.
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    pub messages_moved: usize,
+    /// Files moved without a conflict.
+    pub files_moved: usize,
+    /// Conflicting files moved under a new name.
+    pub files_renamed: usize,
+    /// Conflicting files that replaced the target's copy.
+    pub files_overwritten: usize,
+    /// Conflicting files left in the source.
+    pub files_skipped: usize,
+    /// Notes retagged from the source ID to the target ID.
+    pub notes_retagged: usize,
+}
+
+/// Request to split sessions out into a new workstream.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SplitWorkstreamRequest {
+    /// IDs of the sessions to move.
+    pub session_ids: Vec<String>,
+    /// Title for the new workstream.
+    pub title: String,
+    /// Tags for the new workstream.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Response from splitting a workstream.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SplitWorkstreamResponse {
+    /// The newly created workstream.
+    pub workstream: WorkstreamResponse,
+    /// IDs of the sessions moved into it.
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    pub messages_moved: usize,
+}
+
 /// Request to promote a file from work/ to production/.
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct PromoteFileRequest {
@@ -267,6 +358,69 @@ fn is_zero(v: &usize) -> bool {
     *v == 0
 }
 
+/// Request to take a snapshot of the production directory.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CreateSnapshotRequest {
+    /// Optional label to help identify the snapshot later.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Summary of a production snapshot.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotResponse {
+    /// Snapshot ID.
+    pub id: String,
+    /// When the snapshot was taken.
+    pub created_at: String,
+    /// What caused the snapshot (`manual`, `promote`, or `restore`).
+    pub trigger: String,
+    /// Optional user-supplied label.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Number of files in the snapshot.
+    pub file_count: usize,
+    /// Total size of the snapshotted files in bytes.
+    pub total_bytes: u64,
+}
+
+/// Response listing a workstream's snapshots.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListSnapshotsResponse {
+    /// Snapshots, newest first.
+    pub snapshots: Vec<SnapshotResponse>,
+}
+
+/// Query parameters for diffing a snapshot.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SnapshotDiffQuery {
+    /// Snapshot to compare against. Defaults to the current production directory.
+    pub against: Option<String>,
+}
+
+/// Differences between a snapshot and a newer state.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotDiffResponse {
+    /// Paths present only in the newer state.
+    pub added: Vec<String>,
+    /// Paths present only in the snapshot.
+    pub removed: Vec<String>,
+    /// Paths whose contents changed.
+    pub modified: Vec<String>,
+}
+
+/// Response from restoring a snapshot.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoreSnapshotResponse {
+    /// Number of files written back to production.
+    pub files_restored: usize,
+    /// Number of files removed because the snapshot did not contain them.
+    pub files_removed: usize,
+    /// Snapshot of the pre-restore state, if production had any files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_snapshot_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateWorkstreamRequest {
     /// New title.
@@ -281,6 +435,10 @@ pub struct UpdateWorkstreamRequest {
     /// New tags.
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// Replacement agent settings (the whole record; `{}` clears them).
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub settings: Option<AgentSettings>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -295,6 +453,12 @@ pub struct SessionResponse {
     pub ended_at: Option<String>,
     /// Whether the session is currently active.
     pub is_active: bool,
+    /// Session this one was forked from, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_session_id: Option<String>,
+    /// Last message copied from the parent when forking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forked_from_message_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -331,9 +495,29 @@ fn to_workstream_response(
         created_at: ws.created_at.to_rfc3339(),
         updated_at: ws.updated_at.to_rfc3339(),
         tags,
+        settings: ws.settings.clone(),
+        template: None,
     }
 }
 
+fn template_response(
+    mgr: &WorkstreamManager,
+    name: String,
+    outcome: TemplateOutcome,
+) -> WorkstreamResponse {
+    let tags = mgr.get_tags(&outcome.workstream.id).ok();
+    let mut response = to_workstream_response(&outcome.workstream, tags);
+    response.template = Some(TemplateAppliedResponse {
+        name,
+        files: outcome.files,
+        notes: outcome.notes,
+        workflows: outcome.workflows,
+        repos: outcome.repos,
+        warnings: outcome.warnings,
+    });
+    response
+}
+
 fn to_message_response(msg: &WorkstreamMessage) -> MessageResponse {
     MessageResponse {
         id: msg.id.clone(),
@@ -355,19 +539,53 @@ fn to_message_response(msg: &WorkstreamMessage) -> MessageResponse {
     request_body = CreateWorkstreamRequest,
     responses(
         (status = 201, description = "Workstream created", body = WorkstreamResponse),
+        (status = 400, description = "Invalid template parameters"),
         (status = 401, description = "Unauthorized"),
-        (status = 503, description = "Workstreams not configured"),
+        (status = 404, description = "Template not found"),
+        (status = 503, description = "Workstreams or templates not configured"),
     ),
     security(("bearer_auth" = [])),
     tag = "workstreams"
 )]
 pub async fn create_workstream_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Json(req): Json<CreateWorkstreamRequest>,
 ) -> Result<(StatusCode, Json<WorkstreamResponse>), ServerError> {
     let mgr = get_manager(&state)?;
 
+    if let Some(template) = req.template {
+        let templates = state.templates().ok_or_else(|| {
+            ServerError::ServiceUnavailable("Workstream templates not configured".to_string())
+        })?;
+        let request = TemplateRequest {
+            template: template.clone(),
+            title: req.title,
+            default_model: req.default_model,
+            tags: req.tags,
+            params: req.params,
+        };
+        let outcome =
+            templates.create_workstream(mgr, state.memory_store().map(|m| m.as_ref()), &request)?;
+        auth::claim(
+            &state,
+            &identity,
+            ResourceKind::Workstream,
+            &outcome.workstream.id,
+        );
+        return Ok((
+            StatusCode::CREATED,
+            Json(template_response(mgr, template, outcome)),
+        ));
+    }
+    if !req.params.is_empty() {
+        return Err(ServerError::BadRequest(
+            "params given without a template".to_string(),
+        ));
+    }
+
     let ws = mgr.create_workstream(&req.title, req.default_model.as_deref(), &req.tags)?;
+    auth::claim(&state, &identity, ResourceKind::Workstream, &ws.id);
 
     // Create directory structure for the new workstream
     if let Some(dm) = state.directory_manager() {
@@ -399,6 +617,7 @@ pub async fn create_workstream_handler(
 )]
 pub async fn list_workstreams_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Query(query): Query<ListWorkstreamsQuery>,
     Query(pagination): Query<PaginationParams>,
 ) -> Result<Json<WorkstreamListResponse>, ServerError> {
@@ -409,8 +628,10 @@ pub async fn list_workstreams_handler(
     } else {
         mgr.list_workstreams()?
     };
+    let visible = auth::visible_ids(&state, &identity, ResourceKind::Workstream);
     let all_workstreams: Vec<_> = list
         .iter()
+        .filter(|ws| visible.as_ref().is_none_or(|ids| ids.contains(&ws.id)))
         .map(|ws| to_workstream_response(ws, None))
         .collect();
 
@@ -442,9 +663,11 @@ pub async fn list_workstreams_handler(
 )]
 pub async fn get_workstream_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
 ) -> Result<Json<WorkstreamResponse>, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     let ws = mgr.get_workstream(&id)?;
@@ -471,9 +694,11 @@ pub async fn get_workstream_handler(
 )]
 pub async fn delete_workstream_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     mgr.archive_workstream(&id)?;
@@ -500,14 +725,16 @@ pub async fn delete_workstream_handler(
 )]
 pub async fn update_workstream_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
     Json(req): Json<UpdateWorkstreamRequest>,
 ) -> Result<Json<WorkstreamResponse>, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     // Update workstream fields
-    let ws = mgr.update_workstream(
+    let mut ws = mgr.update_workstream(
         &id,
         req.title.as_deref(),
         req.summary.as_deref(),
@@ -519,6 +746,11 @@ pub async fn update_workstream_handler(
         mgr.set_tags(&id, tags)?;
     }
 
+    // Replace agent settings if provided
+    if let Some(ref settings) = req.settings {
+        ws = mgr.update_settings(&id, settings)?;
+    }
+
     let tags = mgr.get_tags(&ws.id).ok();
     Ok(Json(to_workstream_response(&ws, tags)))
 }
@@ -542,21 +774,27 @@ pub async fn update_workstream_handler(
 )]
 pub async fn list_workstream_sessions_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
     Query(pagination): Query<PaginationParams>,
 ) -> Result<Json<SessionListResponse>, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     let ws_sessions = mgr.list_sessions(&id)?;
+    let visible = scratch_session_filter(&state, &identity, &id);
     let all_sessions: Vec<_> = ws_sessions
         .iter()
+        .filter(|s| visible.as_ref().is_none_or(|ids| ids.contains(&s.id)))
         .map(|s| SessionResponse {
             id: s.id.clone(),
             workstream_id: s.workstream_id.clone(),
             started_at: s.started_at.to_rfc3339(),
             ended_at: s.ended_at.map(|dt| dt.to_rfc3339()),
             is_active: s.ended_at.is_none(),
+            parent_session_id: s.parent_session_id.clone(),
+            forked_from_message_id: s.forked_from_message_id.clone(),
         })
         .collect();
 
@@ -590,10 +828,12 @@ pub async fn list_workstream_sessions_handler(
 )]
 pub async fn send_message_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
     Json(req): Json<SendMessageRequest>,
 ) -> Result<(StatusCode, Json<MessageResponse>), ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     let role = match req.role.as_deref().unwrap_or("user") {
@@ -632,11 +872,13 @@ pub async fn send_message_handler(
 )]
 pub async fn list_messages_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
     Query(query): Query<MessageQuery>,
     Query(pagination): Query<PaginationParams>,
 ) -> Result<Json<MessageListResponse>, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     let result = if let Some(since_str) = &query.since {
@@ -649,7 +891,18 @@ pub async fn list_messages_handler(
     };
 
     let msgs = result?;
-    let all_messages: Vec<_> = msgs.iter().map(to_message_response).collect();
+    let visible = scratch_session_filter(&state, &identity, &id);
+    let all_messages: Vec<_> = msgs
+        .iter()
+        .filter(|m| {
+            visible.as_ref().is_none_or(|ids| {
+                m.session_id
+                    .as_ref()
+                    .is_some_and(|session_id| ids.contains(session_id))
+            })
+        })
+        .map(to_message_response)
+        .collect();
 
     let (paginated, total) = pagination.paginate(&all_messages);
 
@@ -680,10 +933,12 @@ pub async fn list_messages_handler(
 )]
 pub async fn promote_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
     Json(req): Json<PromoteRequest>,
 ) -> Result<(StatusCode, Json<WorkstreamResponse>), ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     if id != SCRATCH_ID {
@@ -691,13 +946,160 @@ pub async fn promote_handler(
             "Only the scratch workstream can be promoted".to_string(),
         ));
     }
+    // Scratch is shared, so promoting it moves every user's scratch sessions
+    if state.access_store().is_some() && !identity.is_admin() {
+        return Err(ServerError::Forbidden(
+            "Promoting the shared scratch workstream requires the admin scope".to_string(),
+        ));
+    }
 
     let ws = mgr.promote_scratch(&req.title, &req.tags, req.default_model.as_deref())?;
+    auth::claim(&state, &identity, ResourceKind::Workstream, &ws.id);
 
     let tags = mgr.get_tags(&ws.id).ok();
     Ok((StatusCode::CREATED, Json(to_workstream_response(&ws, tags))))
 }
 
+/// POST /api/v1/workstreams/:id/merge - Merge a workstream into another.
+///
+/// Moves sessions, messages, tags, files and notes into the target and
+/// archives the source.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workstreams/{id}/merge",
+    params(
+        ("id" = String, Path, description = "Workstream ID to merge (the source)"),
+    ),
+    request_body = MergeWorkstreamRequest,
+    responses(
+        (status = 200, description = "Workstreams merged", body = MergeWorkstreamResponse),
+        (status = 400, description = "Invalid merge (same, scratch or archived workstream)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream not found"),
+        (status = 503, description = "Workstreams not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn merge_workstream_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(id): Path<String>,
+    Json(req): Json<MergeWorkstreamRequest>,
+) -> Result<Json<MergeWorkstreamResponse>, ServerError> {
+    validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
+    validate_id(&req.target)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &req.target)?;
+    let mgr = get_manager(&state)?;
+
+    let on_conflict = match req.on_conflict.as_deref() {
+        Some(policy) => policy
+            .parse::<FileConflictPolicy>()
+            .map_err(ServerError::BadRequest)?,
+        None => FileConflictPolicy::default(),
+    };
+
+    let result = mgr.merge_workstreams(&id, &req.target, on_conflict)?;
+
+    // Notes are linked to a workstream by tagging them with its ID
+    let notes_retagged = match state.memory_store() {
+        Some(store) => store.retag_notes(&id, &req.target).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to retag notes after merge");
+            0
+        }),
+        None => 0,
+    };
+
+    invalidate_sessions(&state, &result.session_ids).await;
+
+    let files = result.files.unwrap_or_default();
+    let tags = mgr.get_tags(&result.workstream.id).ok();
+    Ok(Json(MergeWorkstreamResponse {
+        workstream: to_workstream_response(&result.workstream, tags),
+        session_ids: result.session_ids,
+        messages_moved: result.messages_moved,
+        files_moved: files.moved,
+        files_renamed: files.renamed,
+        files_overwritten: files.overwritten,
+        files_skipped: files.skipped,
+        notes_retagged,
+    }))
+}
+
+/// POST /api/v1/workstreams/:id/split - Move sessions into a new workstream.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workstreams/{id}/split",
+    params(
+        ("id" = String, Path, description = "Workstream ID to split sessions out of"),
+    ),
+    request_body = SplitWorkstreamRequest,
+    responses(
+        (status = 201, description = "Workstream created from sessions", body = SplitWorkstreamResponse),
+        (status = 400, description = "No sessions selected, or scratch workstream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream or session not found"),
+        (status = 503, description = "Workstreams not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn split_workstream_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(id): Path<String>,
+    Json(req): Json<SplitWorkstreamRequest>,
+) -> Result<(StatusCode, Json<SplitWorkstreamResponse>), ServerError> {
+    validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
+    let mgr = get_manager(&state)?;
+
+    for session_id in &req.session_ids {
+        auth::authorize(&state, &identity, ResourceKind::Session, session_id)?;
+    }
+    let result = mgr.split_workstream(&id, &req.session_ids, &req.title, &req.tags)?;
+    auth::claim(
+        &state,
+        &identity,
+        ResourceKind::Workstream,
+        &result.workstream.id,
+    );
+    invalidate_sessions(&state, &result.session_ids).await;
+
+    let tags = mgr.get_tags(&result.workstream.id).ok();
+    Ok((
+        StatusCode::CREATED,
+        Json(SplitWorkstreamResponse {
+            workstream: to_workstream_response(&result.workstream, tags),
+            session_ids: result.session_ids,
+            messages_moved: result.messages_moved,
+        }),
+    ))
+}
+
+/// Sessions visible to `identity` when listing the shared scratch workstream
+/// (`None` = all). Other workstreams belong to one user, so they aren't filtered.
+fn scratch_session_filter(
+    state: &AppState,
+    identity: &Identity,
+    workstream_id: &str,
+) -> Option<std::collections::HashSet<String>> {
+    if workstream_id != SCRATCH_ID {
+        return None;
+    }
+    auth::visible_ids(state, identity, ResourceKind::Session)
+}
+
+/// Drop moved sessions from the cache so they reload with their new workstream.
+async fn invalidate_sessions(state: &AppState, session_ids: &[String]) {
+    for id in session_ids {
+        if let Ok(uuid) = uuid::Uuid::parse_str(id) {
+            state.invalidate_session(SessionId::from_uuid(uuid)).await;
+        }
+    }
+}
+
 /// POST /api/v1/workstreams/:id/files/promote - Promote a file to production.
 #[utoipa::path(
     post,
@@ -718,10 +1120,12 @@ pub async fn promote_handler(
 )]
 pub async fn promote_file_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(workstream_id): Path<String>,
     Json(req): Json<PromoteFileRequest>,
 ) -> Result<(StatusCode, Json<PromoteFileResponse>), ServerError> {
     validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
     let mgr = get_manager(&state)?;
 
     // Get the directory manager
@@ -793,10 +1197,12 @@ pub async fn promote_file_handler(
 )]
 pub async fn export_file_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(workstream_id): Path<String>,
     Json(req): Json<ExportFileRequest>,
 ) -> Result<(StatusCode, Json<ExportFileResponse>), ServerError> {
     validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
     let mgr = get_manager(&state)?;
 
     // Get the directory manager
@@ -857,10 +1263,12 @@ pub async fn export_file_handler(
 )]
 pub async fn clone_repo_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(workstream_id): Path<String>,
     Json(req): Json<CloneRepoRequest>,
 ) -> Result<(StatusCode, Json<CloneRepoResponse>), ServerError> {
     validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
     let mgr = get_manager(&state)?;
 
     // Get the directory manager
@@ -926,9 +1334,11 @@ pub async fn clone_repo_handler(
 )]
 pub async fn get_usage_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(workstream_id): Path<String>,
 ) -> Result<Json<UsageResponse>, ServerError> {
     validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
     let mgr = get_manager(&state)?;
 
     // Get the directory manager
@@ -988,10 +1398,12 @@ pub async fn get_usage_handler(
 )]
 pub async fn cleanup_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(workstream_id): Path<String>,
     Json(req): Json<CleanupRequest>,
 ) -> Result<Json<CleanupResponse>, ServerError> {
     validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
     let mgr = get_manager(&state)?;
 
     // Get the directory manager
@@ -1020,6 +1432,202 @@ pub async fn cleanup_handler(
     }))
 }
 
+fn to_snapshot_response(snapshot: &Snapshot) -> SnapshotResponse {
+    SnapshotResponse {
+        id: snapshot.id.clone(),
+        created_at: snapshot.created_at.to_rfc3339(),
+        trigger: snapshot.trigger.to_string(),
+        label: snapshot.label.clone(),
+        file_count: snapshot.files.len(),
+        total_bytes: snapshot.total_bytes(),
+    }
+}
+
+fn snapshot_error(e: DirectoryError) -> ServerError {
+    match e {
+        DirectoryError::WorkstreamNotFound(ws) => {
+            ServerError::NotFound(format!("Workstream not found: {ws}"))
+        }
+        DirectoryError::SnapshotNotFound(id) => {
+            ServerError::NotFound(format!("Snapshot not found: {id}"))
+        }
+        DirectoryError::InvalidName(name) => {
+            ServerError::BadRequest(format!("Invalid workstream name: {name}"))
+        }
+        other => ServerError::Internal(format!("Snapshot operation failed: {other}")),
+    }
+}
+
+/// GET /api/v1/workstreams/:id/snapshots - List production snapshots.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workstreams/{id}/snapshots",
+    params(
+        ("id" = String, Path, description = "Workstream ID"),
+    ),
+    responses(
+        (status = 200, description = "Snapshots, newest first", body = ListSnapshotsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream not found"),
+        (status = 503, description = "Workstreams or directory management not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn list_snapshots_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(workstream_id): Path<String>,
+) -> Result<Json<ListSnapshotsResponse>, ServerError> {
+    validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
+    let mgr = get_manager(&state)?;
+
+    let dir_mgr = mgr.directory_manager().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Directory management not configured".to_string())
+    })?;
+
+    let snapshots = dir_mgr
+        .list_snapshots(&workstream_id)
+        .map_err(snapshot_error)?;
+
+    Ok(Json(ListSnapshotsResponse {
+        snapshots: snapshots.iter().map(to_snapshot_response).collect(),
+    }))
+}
+
+/// POST /api/v1/workstreams/:id/snapshots - Snapshot the production directory.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workstreams/{id}/snapshots",
+    params(
+        ("id" = String, Path, description = "Workstream ID"),
+    ),
+    request_body = CreateSnapshotRequest,
+    responses(
+        (status = 201, description = "Snapshot created", body = SnapshotResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream not found"),
+        (status = 503, description = "Workstreams or directory management not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn create_snapshot_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path(workstream_id): Path<String>,
+    Json(req): Json<CreateSnapshotRequest>,
+) -> Result<(StatusCode, Json<SnapshotResponse>), ServerError> {
+    validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
+    let mgr = get_manager(&state)?;
+
+    let dir_mgr = mgr.directory_manager().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Directory management not configured".to_string())
+    })?;
+
+    let snapshot = dir_mgr
+        .create_snapshot(
+            &workstream_id,
+            SnapshotTrigger::Manual,
+            req.label.as_deref(),
+        )
+        .map_err(snapshot_error)?;
+
+    Ok((StatusCode::CREATED, Json(to_snapshot_response(&snapshot))))
+}
+
+/// GET /api/v1/workstreams/:id/snapshots/:snapshot_id/diff - Diff a snapshot.
+///
+/// Compares the snapshot with the current production directory, or with
+/// another snapshot when `against` is given.
+#[utoipa::path(
+    get,
+    path = "/api/v1/workstreams/{id}/snapshots/{snapshot_id}/diff",
+    params(
+        ("id" = String, Path, description = "Workstream ID"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID"),
+        ("against" = Option<String>, Query, description = "Snapshot to compare against (defaults to current production)"),
+    ),
+    responses(
+        (status = 200, description = "Snapshot diff", body = SnapshotDiffResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream or snapshot not found"),
+        (status = 503, description = "Workstreams or directory management not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn diff_snapshot_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path((workstream_id, snapshot_id)): Path<(String, String)>,
+    Query(query): Query<SnapshotDiffQuery>,
+) -> Result<Json<SnapshotDiffResponse>, ServerError> {
+    validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
+    let mgr = get_manager(&state)?;
+
+    let dir_mgr = mgr.directory_manager().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Directory management not configured".to_string())
+    })?;
+
+    let diff = dir_mgr
+        .diff_snapshot(&workstream_id, &snapshot_id, query.against.as_deref())
+        .map_err(snapshot_error)?;
+
+    Ok(Json(SnapshotDiffResponse {
+        added: diff.added,
+        removed: diff.removed,
+        modified: diff.modified,
+    }))
+}
+
+/// POST /api/v1/workstreams/:id/snapshots/:snapshot_id/restore - Restore a snapshot.
+///
+/// Replaces the production directory with the snapshot's contents. The
+/// current state is snapshotted first so the restore can itself be undone.
+#[utoipa::path(
+    post,
+    path = "/api/v1/workstreams/{id}/snapshots/{snapshot_id}/restore",
+    params(
+        ("id" = String, Path, description = "Workstream ID"),
+        ("snapshot_id" = String, Path, description = "Snapshot ID"),
+    ),
+    responses(
+        (status = 200, description = "Snapshot restored", body = RestoreSnapshotResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Workstream or snapshot not found"),
+        (status = 503, description = "Workstreams or directory management not configured"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "workstreams"
+)]
+pub async fn restore_snapshot_handler(
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Path((workstream_id, snapshot_id)): Path<(String, String)>,
+) -> Result<Json<RestoreSnapshotResponse>, ServerError> {
+    validate_id(&workstream_id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &workstream_id)?;
+    let mgr = get_manager(&state)?;
+
+    let dir_mgr = mgr.directory_manager().ok_or_else(|| {
+        ServerError::ServiceUnavailable("Directory management not configured".to_string())
+    })?;
+
+    let result = dir_mgr
+        .restore_snapshot(&workstream_id, &snapshot_id)
+        .map_err(snapshot_error)?;
+
+    Ok(Json(RestoreSnapshotResponse {
+        files_restored: result.files_restored,
+        files_removed: result.files_removed,
+        safety_snapshot_id: result.safety_snapshot,
+    }))
+}
+
 /// Response from compression operation.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct CompressResponse {
@@ -1049,9 +1657,11 @@ pub struct CompressResponse {
 )]
 pub async fn compress_workstream_handler(
     State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
     Path(id): Path<String>,
 ) -> Result<Json<CompressResponse>, ServerError> {
     validate_id(&id)?;
+    auth::authorize(&state, &identity, ResourceKind::Workstream, &id)?;
     let mgr = get_manager(&state)?;
 
     let compressor = state
@@ -1104,6 +1714,7 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
         middleware,
+        response::Response,
         routing::{get, post},
     };
     use tower::ServiceExt;
@@ -1161,6 +1772,8 @@ mod tests {
                 post(send_message_handler).get(list_messages_handler),
             )
             .route("/workstreams/{id}/promote", post(promote_handler))
+            .route("/workstreams/{id}/merge", post(merge_workstream_handler))
+            .route("/workstreams/{id}/split", post(split_workstream_handler))
             .layer(middleware::from_fn_with_state(
                 state.clone(),
                 auth_middleware,
@@ -1254,6 +1867,82 @@ mod tests {
         assert_eq!(result["state"], "active");
     }
 
+    #[tokio::test]
+    async fn test_create_workstream_from_template() {
+        let (state, tmp) = create_state_with_workstreams();
+        let template_dir = tmp.path().join("templates/research");
+        std::fs::create_dir_all(&template_dir).unwrap();
+        std::fs::write(
+            template_dir.join("template.toml"),
+            "tags = [\"research\"]\n[[params]]\nname = \"topic\"\n",
+        )
+        .unwrap();
+        let state = state.with_templates(
+            arawn_domain::TemplateRegistry::new().with_dir(tmp.path().join("templates")),
+        );
+        let app = create_test_router(state);
+
+        let (key, val) = auth_header();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/workstreams")
+                    .header(key, val)
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(
+                        r#"{"title": "Fusion", "template": "research", "params": {"topic": "tokamaks"}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(result["title"], "Fusion");
+        assert_eq!(result["tags"][0], "research");
+        assert_eq!(result["template"]["name"], "research");
+    }
+
+    #[tokio::test]
+    async fn test_create_workstream_template_errors() {
+        let (state, tmp) = create_state_with_workstreams();
+        std::fs::create_dir_all(tmp.path().join("templates")).unwrap();
+        let state = state.with_templates(
+            arawn_domain::TemplateRegistry::new().with_dir(tmp.path().join("templates")),
+        );
+
+        let (key, val) = auth_header();
+        for (body, expected) in [
+            (
+                r#"{"title": "X", "template": "missing"}"#,
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                r#"{"title": "X", "params": {"topic": "y"}}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+        ] {
+            let response = create_test_router(state.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/workstreams")
+                        .header(key, val)
+                        .header("Content-Type", "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected, "{body}");
+        }
+    }
+
     #[tokio::test]
     async fn test_get_workstream_scratch() {
         let (state, _tmp) = create_state_with_workstreams();
@@ -1558,6 +2247,272 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    fn post_json(uri: &str, body: serde_json::Value) -> Request<Body> {
+        let (key, val) = auth_header();
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(key, val)
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_merge_workstream() {
+        let (state, _tmp) = create_state_with_workstreams();
+        let mgr = state.workstreams().unwrap().clone();
+        let source = mgr.create_workstream("Source", None, &[]).unwrap();
+        let target = mgr.create_workstream("Target", None, &[]).unwrap();
+        mgr.send_message(
+            Some(&source.id),
+            Some("s1"),
+            MessageRole::User,
+            "hello",
+            None,
+        )
+        .unwrap();
+
+        let app = create_test_router(state);
+        let response = app
+            .clone()
+            .oneshot(post_json(
+                &format!("/workstreams/{}/merge", source.id),
+                serde_json::json!({ "target": target.id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(result["workstream"]["id"], target.id);
+        assert_eq!(result["session_ids"], serde_json::json!(["s1"]));
+        assert_eq!(result["messages_moved"], 1);
+        assert_eq!(mgr.get_workstream(&source.id).unwrap().state, "archived");
+
+        // Unknown conflict policies are rejected
+        let other = mgr.create_workstream("Other", None, &[]).unwrap();
+        let response = app
+            .oneshot(post_json(
+                &format!("/workstreams/{}/merge", other.id),
+                serde_json::json!({ "target": target.id, "on_conflict": "clobber" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_merge_into_itself() {
+        let (state, _tmp) = create_state_with_workstreams();
+        let ws = state
+            .workstreams()
+            .unwrap()
+            .create_workstream("Only", None, &[])
+            .unwrap();
+
+        let response = create_test_router(state)
+            .oneshot(post_json(
+                &format!("/workstreams/{}/merge", ws.id),
+                serde_json::json!({ "target": ws.id }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_split_workstream() {
+        let (state, _tmp) = create_state_with_workstreams();
+        let mgr = state.workstreams().unwrap().clone();
+        let ws = mgr.create_workstream("Mixed", None, &[]).unwrap();
+        for session in ["keep", "move"] {
+            mgr.send_message(Some(&ws.id), Some(session), MessageRole::User, "hi", None)
+                .unwrap();
+        }
+
+        let app = create_test_router(state);
+        let response = app
+            .clone()
+            .oneshot(post_json(
+                &format!("/workstreams/{}/split", ws.id),
+                serde_json::json!({ "session_ids": ["move"], "title": "Spun off" }),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(result["workstream"]["title"], "Spun off");
+        assert_eq!(result["messages_moved"], 1);
+        let new_id = result["workstream"]["id"].as_str().unwrap();
+        assert_eq!(mgr.get_messages(new_id).unwrap().len(), 1);
+
+        let response = app
+            .oneshot(post_json(
+                &format!("/workstreams/{}/split", ws.id),
+                serde_json::json!({ "session_ids": ["missing"], "title": "Nope" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ── Snapshot tests (with directory management) ─────────────────────────
+
+    fn create_state_with_directories() -> (AppState, tempfile::TempDir) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backend = MockBackend::with_text("Test");
+        let agent = Agent::builder()
+            .with_backend(backend)
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        let state = AppState::new(agent, ServerConfig::new(Some("test-token".to_string())));
+
+        let ws_config = arawn_workstream::WorkstreamConfig {
+            db_path: temp_dir.path().join("workstreams.db"),
+            data_dir: temp_dir.path().join("workstreams"),
+            session_timeout_minutes: 30,
+        };
+        let dm = DirectoryManager::new(temp_dir.path().join("arawn"));
+        dm.create_workstream("docs").unwrap();
+        let mgr = arawn_workstream::WorkstreamManager::new(&ws_config)
+            .unwrap()
+            .with_directory_manager(dm);
+
+        (state.with_workstreams(mgr), temp_dir)
+    }
+
+    fn create_snapshot_router(state: AppState) -> Router {
+        Router::new()
+            .route(
+                "/workstreams/{id}/snapshots",
+                get(list_snapshots_handler).post(create_snapshot_handler),
+            )
+            .route(
+                "/workstreams/{id}/snapshots/{snapshot_id}/diff",
+                get(diff_snapshot_handler),
+            )
+            .route(
+                "/workstreams/{id}/snapshots/{snapshot_id}/restore",
+                post(restore_snapshot_handler),
+            )
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                auth_middleware,
+            ))
+            .with_state(state)
+    }
+
+    async fn send_json(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        body: Option<serde_json::Value>,
+    ) -> (StatusCode, serde_json::Value) {
+        let (key, val) = auth_header();
+        let mut builder = Request::builder().method(method).uri(uri).header(key, val);
+        let body = match body {
+            Some(json) => {
+                builder = builder.header("Content-Type", "application/json");
+                Body::from(json.to_string())
+            }
+            None => Body::empty(),
+        };
+        let response = app
+            .clone()
+            .oneshot(builder.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, json)
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_create_diff_restore() {
+        let (state, tmp) = create_state_with_directories();
+        let app = create_snapshot_router(state);
+        let prod = tmp.path().join("arawn/workstreams/docs/production");
+        std::fs::write(prod.join("plan.md"), "v1").unwrap();
+
+        let (status, created) = send_json(
+            &app,
+            "POST",
+            "/workstreams/docs/snapshots",
+            Some(serde_json::json!({"label": "before rewrite"})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["trigger"], "manual");
+        assert_eq!(created["label"], "before rewrite");
+        assert_eq!(created["file_count"], 1);
+        let snap_id = created["id"].as_str().unwrap().to_string();
+
+        std::fs::write(prod.join("plan.md"), "v2").unwrap();
+        std::fs::write(prod.join("extra.md"), "new").unwrap();
+
+        let (status, diff) = send_json(
+            &app,
+            "GET",
+            &format!("/workstreams/docs/snapshots/{snap_id}/diff"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(diff["added"], serde_json::json!(["extra.md"]));
+        assert_eq!(diff["modified"], serde_json::json!(["plan.md"]));
+
+        let (status, restored) = send_json(
+            &app,
+            "POST",
+            &format!("/workstreams/docs/snapshots/{snap_id}/restore"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(restored["files_restored"], 1);
+        assert_eq!(restored["files_removed"], 1);
+        assert!(restored["safety_snapshot_id"].is_string());
+        assert_eq!(std::fs::read_to_string(prod.join("plan.md")).unwrap(), "v1");
+        assert!(!prod.join("extra.md").exists());
+
+        let (status, list) = send_json(&app, "GET", "/workstreams/docs/snapshots", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let snapshots = list["snapshots"].as_array().unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0]["trigger"], "restore");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_not_found() {
+        let (state, _tmp) = create_state_with_directories();
+        let app = create_snapshot_router(state);
+
+        let (status, _) = send_json(
+            &app,
+            "POST",
+            "/workstreams/docs/snapshots/20260101T000000000Z-deadbeef/restore",
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = send_json(&app, "GET", "/workstreams/missing/snapshots", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
     // ── Without workstreams configured ─────────────────────────────────────
 
     #[tokio::test]
@@ -1608,6 +2563,17 @@ mod tests {
         assert_eq!(req.summary, Some("Summary text".to_string()));
         assert!(req.default_model.is_none());
         assert!(req.tags.is_none());
+        assert!(req.settings.is_none());
+    }
+
+    #[test]
+    fn test_update_workstream_request_settings() {
+        let json = r#"{"settings": {"profile": "fast", "denied_tools": ["shell"], "memory_scope": "session"}}"#;
+        let req: UpdateWorkstreamRequest = serde_json::from_str(json).unwrap();
+        let settings = req.settings.unwrap();
+        assert_eq!(settings.profile.as_deref(), Some("fast"));
+        assert_eq!(settings.denied_tools, vec!["shell".to_string()]);
+        assert_eq!(settings.memory_scope, arawn_types::MemoryScope::Session);
     }
 
     #[test]
@@ -1622,6 +2588,8 @@ mod tests {
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
             tags: None,
+            settings: AgentSettings::default(),
+            template: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"id\":\"ws-1\""));
@@ -1630,6 +2598,78 @@ mod tests {
         assert!(!json.contains("\"tags\""));
     }
 
+    async fn request(app: &Router, method: &str, uri: &str, token: &str, body: &str) -> Response {
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("Authorization", format!("Bearer {}", token))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn listed_ids(app: &Router, token: &str) -> Vec<String> {
+        let response = request(app, "GET", "/workstreams", token, "").await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        result["workstreams"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ws| ws["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_workstreams_isolated_by_owner() {
+        use arawn_domain::{AccessStore, TokenScope};
+
+        let (state, _tmp) = create_state_with_workstreams();
+        let store = AccessStore::open_in_memory().unwrap();
+        let scopes = [TokenScope::Read, TokenScope::Chat];
+        let (_, alice) = store.create_token("a", "alice", &scopes, None).unwrap();
+        let (_, bob) = store.create_token("b", "bob", &scopes, None).unwrap();
+        let app = create_test_router(state.with_access_store(Arc::new(store)));
+
+        let response = request(&app, "POST", "/workstreams", &alice, r#"{"title": "Mine"}"#).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+        let uri = format!("/workstreams/{}", id);
+
+        assert!(listed_ids(&app, &alice).await.contains(&id));
+        assert!(!listed_ids(&app, &bob).await.contains(&id));
+        assert!(listed_ids(&app, "test-token").await.contains(&id));
+
+        let response = request(&app, "GET", &uri, &alice, "").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = request(&app, "GET", &uri, &bob, "").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = request(&app, "DELETE", &uri, &bob, "").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // Scratch is shared, so only admins may promote it
+        let response = request(
+            &app,
+            "POST",
+            "/workstreams/scratch/promote",
+            &bob,
+            r#"{"title": "Taken"}"#,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
     #[test]
     fn test_promote_file_response_serialization() {
         let resp = PromoteFileResponse {
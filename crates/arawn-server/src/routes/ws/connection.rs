@@ -6,13 +6,16 @@ use std::time::Duration;
 
 use axum::extract::ws::{Message, WebSocket};
 use futures::{SinkExt, StreamExt};
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use arawn_domain::SessionId;
 
-use super::handlers::{MessageResponse, handle_message};
+use super::handlers::{MessageResponse, broadcast_roster, handle_message};
 use super::protocol::{ClientMessage, ServerMessage};
+use super::viewers::Outbox;
+use crate::auth::Identity;
 use crate::state::AppState;
 
 /// Unique identifier for a WebSocket connection.
@@ -24,6 +27,11 @@ impl ConnectionId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Parse a connection ID from its string form.
+    pub fn parse(s: &str) -> Option<Self> {
+        Uuid::parse_str(s).ok().map(Self)
+    }
 }
 
 impl Default for ConnectionId {
@@ -39,7 +47,8 @@ impl std::fmt::Display for ConnectionId {
 }
 
 /// Idle timeout for WebSocket connections (5 minutes).
-/// Connections that receive no messages for this duration will be closed.
+/// Connections that neither send nor are pushed any messages for this
+/// duration will be closed.
 pub const IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
 /// State for a WebSocket connection.
@@ -48,26 +57,59 @@ pub struct ConnectionState {
     pub id: ConnectionId,
     /// Whether the connection is authenticated.
     pub authenticated: bool,
+    /// Who the connection authenticated as (set together with `authenticated`).
+    pub identity: Option<Identity>,
     /// Current subscribed sessions.
     pub subscriptions: std::collections::HashSet<SessionId>,
+    /// Sessions subscribed to as a read-only observer.
+    pub observing: std::collections::HashSet<SessionId>,
     /// Reconnect tokens for owned sessions (session_id -> token).
     /// Used to create pending reconnects on disconnect.
     pub reconnect_tokens: std::collections::HashMap<SessionId, String>,
+    /// Channel other connections use to push messages to this one
+    /// (relayed responses, roster updates, handoffs).
+    pub outbox: Outbox,
     /// Cancellation token for cleanup.
     pub cancellation: CancellationToken,
 }
 
 impl ConnectionState {
-    /// Create a new connection state.
+    /// Create a new connection state with nowhere to deliver pushed messages.
     pub fn new() -> Self {
+        Self::with_outbox(mpsc::unbounded_channel().0)
+    }
+
+    /// Create a new connection state whose pushed messages go to `outbox`.
+    pub fn with_outbox(outbox: Outbox) -> Self {
         Self {
             id: ConnectionId::new(),
             authenticated: false,
+            identity: None,
             subscriptions: std::collections::HashSet::new(),
+            observing: std::collections::HashSet::new(),
             reconnect_tokens: std::collections::HashMap::new(),
+            outbox,
             cancellation: CancellationToken::new(),
         }
     }
+
+    /// Update local state for a message pushed by another connection.
+    ///
+    /// A handoff makes this connection the owner, so the reconnect token has
+    /// to be remembered for disconnects and the session is no longer observed.
+    fn note_pushed(&mut self, msg: &ServerMessage) {
+        if let ServerMessage::OwnershipChanged {
+            session_id,
+            owner: true,
+            reconnect_token: Some(token),
+        } = msg
+            && let Ok(uuid) = Uuid::parse_str(session_id)
+        {
+            let sid = SessionId::from_uuid(uuid);
+            self.observing.remove(&sid);
+            self.reconnect_tokens.insert(sid, token.clone());
+        }
+    }
 }
 
 impl Default for ConnectionState {
@@ -85,7 +127,8 @@ impl Drop for ConnectionState {
 /// Handle a WebSocket connection.
 pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr) {
     let (mut sender, mut receiver) = socket.split();
-    let mut conn_state = ConnectionState::new();
+    let (outbox, mut pushed) = mpsc::unbounded_channel();
+    let mut conn_state = ConnectionState::with_outbox(outbox);
 
     tracing::debug!(
         connection_id = %conn_state.id,
@@ -95,21 +138,36 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
 
     // Register this connection as active (for stale ownership detection)
     state.register_connection(conn_state.id).await;
+    metrics::gauge!(crate::metrics::WS_CONNECTIONS).increment(1.0);
 
     // Auto-authenticate if no auth token is configured (localhost mode)
     if state.config().auth_token.is_none() {
         conn_state.authenticated = true;
+        conn_state.identity = Some(Identity::Token);
     }
 
+    let idle = tokio::time::sleep(IDLE_TIMEOUT);
+    tokio::pin!(idle);
+
     loop {
-        // Wait for next message with idle timeout
-        let msg = match tokio::time::timeout(IDLE_TIMEOUT, receiver.next()).await {
-            Ok(Some(msg)) => msg,
-            Ok(None) => {
-                // Stream ended normally
-                break;
+        // Wait for the next client message or pushed message, with idle timeout
+        let msg = tokio::select! {
+            msg = receiver.next() => match msg {
+                Some(msg) => msg,
+                None => {
+                    // Stream ended normally
+                    break;
+                }
+            },
+            Some(msg) = pushed.recv() => {
+                idle.as_mut().reset(tokio::time::Instant::now() + IDLE_TIMEOUT);
+                conn_state.note_pushed(&msg);
+                if send_message(&mut sender, msg).await.is_err() {
+                    break;
+                }
+                continue;
             }
-            Err(_) => {
+            () = &mut idle => {
                 // Idle timeout exceeded
                 tracing::info!("WebSocket connection closed due to idle timeout");
                 let _ = send_message(
@@ -120,6 +178,8 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
                 break;
             }
         };
+        idle.as_mut()
+            .reset(tokio::time::Instant::now() + IDLE_TIMEOUT);
 
         // Parse incoming message. We accept both Text and Binary frames,
         // but Binary frames must contain valid UTF-8 JSON. This provides
@@ -153,6 +213,8 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
             }
         };
 
+        crate::metrics::record_ws_message("received");
+
         // Parse message
         let client_msg: ClientMessage = match serde_json::from_str(&msg) {
             Ok(m) => m,
@@ -189,12 +251,18 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
 
     // Unregister this connection so ownership checks know it's dead
     state.unregister_connection(conn_state.id).await;
+    metrics::gauge!(crate::metrics::WS_CONNECTIONS).decrement(1.0);
 
     // Release all session ownerships held by this connection, creating pending reconnects
     state
         .release_all_session_ownerships(conn_state.id, &conn_state.reconnect_tokens)
         .await;
 
+    // Tell the remaining viewers this connection left
+    for session_id in state.session_viewers().leave_all(conn_state.id).await {
+        broadcast_roster(&state, session_id, None).await;
+    }
+
     // Index any sessions this connection was subscribed to
     for session_id in &conn_state.subscriptions {
         if let Some(indexer) = state.indexer() {
@@ -205,6 +273,8 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
                 let indexer = Arc::clone(indexer);
                 let messages = crate::state::session_to_messages(&session);
                 let sid = session_id.to_string();
+                let ws_id = state.session_cache().get_workstream_id(session_id).await;
+                let state = state.clone();
                 tokio::spawn(async move {
                     let report = indexer
                         .index_session(&sid, &crate::state::messages_as_refs(&messages))
@@ -214,6 +284,13 @@ pub async fn handle_socket(socket: WebSocket, state: AppState, addr: SocketAddr)
                         report = %report,
                         "WebSocket close: background session indexing complete"
                     );
+                    if report.facts_inserted > 0 {
+                        state.emit_webhook(crate::webhooks::WebhookEvent::memories_indexed(
+                            &sid,
+                            ws_id.as_deref(),
+                            report.facts_inserted,
+                        ));
+                    }
                 });
             }
         }
@@ -228,6 +305,7 @@ pub async fn send_message(
     msg: ServerMessage,
 ) -> Result<(), axum::Error> {
     let json = serde_json::to_string(&msg).map_err(axum::Error::new)?;
+    crate::metrics::record_ws_message("sent");
     sender
         .send(Message::Text(json.into()))
         .await
@@ -3,11 +3,11 @@
 use futures::StreamExt;
 use uuid::Uuid;
 
-use arawn_domain::{SessionId, ToolCall, ToolResultRecord, Turn, TurnId};
-use subtle::ConstantTimeEq;
+use arawn_domain::{ResourceKind, SessionId, TokenScope, ToolCall, ToolResultRecord, Turn, TurnId};
 
-use super::connection::ConnectionState;
-use super::protocol::{ClientMessage, ServerMessage};
+use super::connection::{ConnectionId, ConnectionState};
+use super::protocol::{ClientMessage, ServerMessage, SessionViewer};
+use crate::auth::{self, Identity};
 use crate::routes::commands::{CommandOutput, CommandRegistry};
 use crate::state::AppState;
 
@@ -35,7 +35,8 @@ pub async fn handle_message(
         ClientMessage::Subscribe {
             session_id,
             reconnect_token,
-        } => handle_subscribe(session_id, reconnect_token, conn_state, app_state).await,
+            observe,
+        } => handle_subscribe(session_id, reconnect_token, observe, conn_state, app_state).await,
 
         ClientMessage::Unsubscribe { session_id } => {
             handle_unsubscribe(session_id, conn_state, app_state).await
@@ -52,6 +53,10 @@ pub async fn handle_message(
         ClientMessage::Command { command, args } => {
             handle_command(command, args, conn_state, app_state).await
         }
+
+        ClientMessage::Handoff { session_id, to } => {
+            handle_handoff(session_id, to, conn_state, app_state).await
+        }
     }
 }
 
@@ -61,20 +66,52 @@ fn handle_auth(
     conn_state: &mut ConnectionState,
     app_state: &AppState,
 ) -> MessageResponse {
-    let authed = match &app_state.config().auth_token {
-        None => true,
-        Some(expected) => {
-            let a = token.as_bytes();
-            let b = expected.as_bytes();
-            a.len() == b.len() && a.ct_eq(b).into()
+    let identity = match auth::authenticate_bearer(app_state, &token) {
+        Ok(identity) => Some(identity),
+        // Localhost mode accepts any token as the shared identity
+        Err(_) if app_state.config().auth_token.is_none() => Some(Identity::Token),
+        Err(_) => None,
+    };
+    match identity {
+        Some(identity) => {
+            conn_state.authenticated = true;
+            conn_state.identity = Some(identity);
+            MessageResponse::Single(ServerMessage::auth_success())
         }
+        None => MessageResponse::Single(ServerMessage::auth_failure("Invalid token")),
+    }
+}
+
+/// Check that the connection's identity has `scope` and may see an existing
+/// session. Returns the error to send back if not.
+async fn check_session_access(
+    conn_state: &ConnectionState,
+    app_state: &AppState,
+    scope: TokenScope,
+    session_id: Option<SessionId>,
+) -> Result<(), MessageResponse> {
+    let Some(identity) = conn_state.identity.as_ref() else {
+        return Err(MessageResponse::Single(ServerMessage::error(
+            "unauthorized",
+            "Authentication required",
+        )));
     };
-    if authed {
-        conn_state.authenticated = true;
-        MessageResponse::Single(ServerMessage::auth_success())
-    } else {
-        MessageResponse::Single(ServerMessage::auth_failure("Invalid token"))
+    if !identity.has_scope(scope) {
+        return Err(MessageResponse::Single(ServerMessage::error(
+            "forbidden",
+            format!("Token lacks the '{}' scope", scope),
+        )));
+    }
+    if let Some(sid) = session_id
+        && app_state.session_exists(sid).await
+        && !auth::can_access(app_state, identity, ResourceKind::Session, &sid.to_string())
+    {
+        return Err(MessageResponse::Single(ServerMessage::error(
+            "invalid_session",
+            "Session not found",
+        )));
     }
+    Ok(())
 }
 
 /// Handle session subscription.
@@ -82,9 +119,12 @@ fn handle_auth(
 /// First subscriber to a session becomes the owner (can send Chat messages).
 /// Subsequent subscribers are readers (receive messages but cannot send Chat).
 /// If a reconnect_token is provided, attempts to reclaim ownership after disconnect.
+/// With `observe`, the connection only watches: it never claims ownership
+/// itself, though the owner can hand it over.
 async fn handle_subscribe(
     session_id: String,
     reconnect_token: Option<String>,
+    observe: bool,
     conn_state: &mut ConnectionState,
     app_state: &AppState,
 ) -> MessageResponse {
@@ -98,54 +138,38 @@ async fn handle_subscribe(
     match Uuid::parse_str(&session_id) {
         Ok(uuid) => {
             let sid = SessionId::from_uuid(uuid);
-            conn_state.subscriptions.insert(sid);
-
-            // Lazy cleanup of expired pending reconnects
-            app_state.cleanup_expired_pending_reconnects().await;
-
-            // Try to reclaim with token first
-            if let Some(token) = reconnect_token
-                && let Some(new_token) = app_state
-                    .try_reclaim_with_token(sid, &token, conn_state.id)
-                    .await
+            if let Err(denied) =
+                check_session_access(conn_state, app_state, TokenScope::Read, Some(sid)).await
             {
-                // Successfully reclaimed ownership
-                return MessageResponse::Single(ServerMessage::subscribe_ack(
-                    &session_id,
-                    true,
-                    Some(new_token),
-                ));
+                return denied;
             }
-            // Token invalid or expired - fall through to normal subscription
-
-            // Check if session has a pending reconnect (someone else is expected to reconnect)
-            if app_state.has_pending_reconnect(sid).await {
-                // Session is reserved for reconnection - subscribe as reader
-                return MessageResponse::Single(ServerMessage::subscribe_ack(
-                    &session_id,
-                    false,
-                    None,
-                ));
-            }
-
-            // Try to claim ownership - first subscriber becomes owner
-            let is_owner = app_state
-                .try_claim_session_ownership(sid, conn_state.id)
-                .await;
+            conn_state.subscriptions.insert(sid);
+            join_viewers(sid, observe, conn_state, app_state).await;
 
-            // Generate reconnect token if we became the owner
-            let token = if is_owner {
-                Some(uuid::Uuid::new_v4().to_string())
+            let (owner, token) = if observe {
+                // Observing gives up ownership rather than keeping it silently
+                if app_state
+                    .release_session_ownership(sid, conn_state.id)
+                    .await
+                {
+                    conn_state.reconnect_tokens.remove(&sid);
+                }
+                conn_state.observing.insert(sid);
+                (false, None)
             } else {
-                None
+                conn_state.observing.remove(&sid);
+                claim_on_subscribe(sid, reconnect_token, conn_state, app_state).await
             };
 
-            // Store the token in connection state for later use
-            if let Some(ref t) = token {
-                conn_state.reconnect_tokens.insert(sid, t.clone());
-            }
-
-            MessageResponse::Single(ServerMessage::subscribe_ack(&session_id, is_owner, token))
+            let viewers = broadcast_roster(app_state, sid, Some(conn_state.id)).await;
+            MessageResponse::Single(ServerMessage::subscribe_ack(
+                &session_id,
+                owner,
+                token,
+                conn_state.id,
+                observe,
+                viewers,
+            ))
         }
         Err(_) => MessageResponse::Single(ServerMessage::error(
             "invalid_session",
@@ -154,6 +178,105 @@ async fn handle_subscribe(
     }
 }
 
+/// Work out whether a (non-observer) subscriber owns the session. Returns
+/// whether it does and, if so, its reconnect token.
+async fn claim_on_subscribe(
+    sid: SessionId,
+    reconnect_token: Option<String>,
+    conn_state: &mut ConnectionState,
+    app_state: &AppState,
+) -> (bool, Option<String>) {
+    // Lazy cleanup of expired pending reconnects
+    app_state.cleanup_expired_pending_reconnects().await;
+
+    // Try to reclaim with token first
+    if let Some(token) = reconnect_token
+        && let Some(new_token) = app_state
+            .try_reclaim_with_token(sid, &token, conn_state.id)
+            .await
+    {
+        // Successfully reclaimed ownership
+        conn_state.reconnect_tokens.insert(sid, new_token.clone());
+        return (true, Some(new_token));
+    }
+    // Token invalid or expired - fall through to normal subscription
+
+    // Check if session has a pending reconnect (someone else is expected to reconnect)
+    if app_state.has_pending_reconnect(sid).await {
+        // Session is reserved for reconnection - subscribe as reader
+        return (false, None);
+    }
+
+    // Try to claim ownership - first subscriber becomes owner
+    let is_owner = app_state
+        .try_claim_session_ownership(sid, conn_state.id)
+        .await;
+
+    // Generate reconnect token if we became the owner
+    let token = if is_owner {
+        Some(uuid::Uuid::new_v4().to_string())
+    } else {
+        None
+    };
+
+    // Store the token in connection state for later use
+    if let Some(ref t) = token {
+        conn_state.reconnect_tokens.insert(sid, t.clone());
+    }
+
+    (is_owner, token)
+}
+
+/// Register the connection as a viewer of a session.
+async fn join_viewers(
+    sid: SessionId,
+    observe: bool,
+    conn_state: &ConnectionState,
+    app_state: &AppState,
+) {
+    let identity = conn_state
+        .identity
+        .as_ref()
+        .map_or_else(|| "anonymous".to_string(), ToString::to_string);
+    let can_own = conn_state
+        .identity
+        .as_ref()
+        .is_some_and(|i| i.has_scope(TokenScope::Chat));
+    app_state
+        .session_viewers()
+        .join(
+            sid,
+            conn_state.id,
+            conn_state.outbox.clone(),
+            observe,
+            identity,
+            can_own,
+        )
+        .await;
+}
+
+/// Push the current roster to every viewer of a session except `except`
+/// (the connection whose action changed it). Returns the roster.
+pub(super) async fn broadcast_roster(
+    app_state: &AppState,
+    sid: SessionId,
+    except: Option<ConnectionId>,
+) -> Vec<SessionViewer> {
+    let owner = app_state.session_owner(sid).await;
+    let viewers = app_state.session_viewers().roster(sid, owner).await;
+    if !viewers.is_empty() {
+        let roster = ServerMessage::Roster {
+            session_id: sid.to_string(),
+            viewers: viewers.clone(),
+        };
+        app_state
+            .session_viewers()
+            .publish(sid, except, &roster)
+            .await;
+    }
+    viewers
+}
+
 /// Handle session unsubscription.
 ///
 /// Releases session ownership if this connection was the owner.
@@ -166,16 +289,108 @@ async fn handle_unsubscribe(
     if let Ok(uuid) = Uuid::parse_str(&session_id) {
         let sid = SessionId::from_uuid(uuid);
         conn_state.subscriptions.remove(&sid);
+        conn_state.observing.remove(&sid);
         conn_state.reconnect_tokens.remove(&sid);
 
         // Release ownership if we were the owner (no pending reconnect for explicit unsubscribe)
         app_state
             .release_session_ownership(sid, conn_state.id)
             .await;
+        if app_state.session_viewers().leave(sid, conn_state.id).await {
+            broadcast_roster(app_state, sid, None).await;
+        }
     }
     MessageResponse::None
 }
 
+/// Handle an ownership handoff.
+///
+/// The owner passes the session to another subscribed connection that may
+/// chat. The new owner gets `ownership_changed` with a fresh reconnect token;
+/// the old owner becomes a reader.
+async fn handle_handoff(
+    session_id: String,
+    to: String,
+    conn_state: &mut ConnectionState,
+    app_state: &AppState,
+) -> MessageResponse {
+    if !conn_state.authenticated {
+        return MessageResponse::Single(ServerMessage::error(
+            "unauthorized",
+            "Authentication required",
+        ));
+    }
+    let Ok(uuid) = Uuid::parse_str(&session_id) else {
+        return MessageResponse::Single(ServerMessage::error(
+            "invalid_session",
+            "Invalid session ID",
+        ));
+    };
+    let sid = SessionId::from_uuid(uuid);
+
+    if !app_state.is_session_owner(sid, conn_state.id).await {
+        return MessageResponse::Single(ServerMessage::error(
+            "session_not_owned",
+            "Only the session owner can hand it off",
+        ));
+    }
+    let target = match ConnectionId::parse(&to) {
+        Some(target) if target != conn_state.id => target,
+        _ => {
+            return MessageResponse::Single(ServerMessage::error(
+                "invalid_viewer",
+                "Handoff target must be another connection from the roster",
+            ));
+        }
+    };
+    if !app_state.session_viewers().can_own(sid, target).await {
+        return MessageResponse::Single(ServerMessage::error(
+            "invalid_viewer",
+            "Handoff target is not subscribed to this session or cannot chat",
+        ));
+    }
+    if !app_state
+        .transfer_session_ownership(sid, conn_state.id, target)
+        .await
+    {
+        return MessageResponse::Single(ServerMessage::error(
+            "invalid_viewer",
+            "Handoff target has disconnected",
+        ));
+    }
+
+    conn_state.reconnect_tokens.remove(&sid);
+    app_state
+        .session_viewers()
+        .set_observer(sid, target, false)
+        .await;
+    app_state
+        .session_viewers()
+        .send_to(
+            sid,
+            target,
+            ServerMessage::OwnershipChanged {
+                session_id: session_id.clone(),
+                owner: true,
+                reconnect_token: Some(Uuid::new_v4().to_string()),
+            },
+        )
+        .await;
+    tracing::info!(
+        session_id = %sid,
+        from = %conn_state.id,
+        to = %target,
+        "Session ownership handed off"
+    );
+    broadcast_roster(app_state, sid, Some(conn_state.id)).await;
+
+    MessageResponse::Single(ServerMessage::OwnershipChanged {
+        session_id,
+        owner: false,
+        reconnect_token: None,
+    })
+}
+
 /// Handle cancellation request.
 fn handle_cancel(session_id: String, conn_state: &mut ConnectionState) -> MessageResponse {
     if !conn_state.authenticated {
@@ -233,6 +448,15 @@ async fn handle_command(
     // For commands that need session context, inject the current session
     // if the client didn't provide one and we have a current subscription
     let args = inject_session_context(args, conn_state);
+    let target = args
+        .get("session_id")
+        .and_then(|v| v.as_str())
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .map(SessionId::from_uuid);
+    if let Err(denied) = check_session_access(conn_state, app_state, TokenScope::Chat, target).await
+    {
+        return denied;
+    }
 
     // Send progress message
     let command_name = command.clone();
@@ -240,6 +464,14 @@ async fn handle_command(
 
     // Execute the command
     let result = handler.execute(app_state, args).await;
+    if command == "fork"
+        && let (Ok(CommandOutput::Completed { result }), Some(identity)) =
+            (&result, conn_state.identity.as_ref())
+        && let Some(forked) = result.get("session_id").and_then(|v| v.as_str())
+    {
+        // A forked session belongs to whoever forked it
+        auth::claim(app_state, identity, ResourceKind::Session, forked);
+    }
 
     // Create response stream with progress and result
     let response_stream = async_stream::stream! {
@@ -326,9 +558,20 @@ async fn handle_chat(
         .as_ref()
         .and_then(|s| Uuid::parse_str(s).ok())
         .map(SessionId::from_uuid);
+    if let Err(denied) =
+        check_session_access(conn_state, app_state, TokenScope::Chat, session_id).await
+    {
+        return denied;
+    }
 
     // If a specific session is requested, check ownership
     if let Some(sid) = session_id {
+        if conn_state.observing.contains(&sid) {
+            return MessageResponse::Single(ServerMessage::error(
+                "session_not_owned",
+                "Observers are read-only. Ask the owner to hand the session off, or subscribe without observe.",
+            ));
+        }
         let is_owner = app_state.is_session_owner(sid, conn_state.id).await;
         if !is_owner {
             // Check if session has any owner at all
@@ -345,9 +588,12 @@ async fn handle_chat(
             }
             // No live owner - claim ownership
             drop(owners);
-            app_state
+            if app_state
                 .try_claim_session_ownership(sid, conn_state.id)
-                .await;
+                .await
+            {
+                broadcast_roster(app_state, sid, Some(conn_state.id)).await;
+            }
         }
     }
 
@@ -365,10 +611,26 @@ async fn handle_chat(
         ));
     }
 
+    let Some(identity) = conn_state.identity.clone() else {
+        return MessageResponse::Single(ServerMessage::error(
+            "unauthorized",
+            "Authentication required",
+        ));
+    };
+    if !auth::can_access(app_state, &identity, ResourceKind::Workstream, ws_id) {
+        return MessageResponse::Single(ServerMessage::error(
+            "invalid_workstream_id",
+            format!("Workstream '{}' not found", ws_id),
+        ));
+    }
+
     // Get or create session using the session cache
-    let session_id = app_state
-        .get_or_create_session_in_workstream(session_id, ws_id)
-        .await;
+    let session_id = match app_state.open_session(&identity, session_id, ws_id).await {
+        Ok(id) => id,
+        Err(e) => {
+            return MessageResponse::Single(ServerMessage::error("invalid_session", e.to_string()));
+        }
+    };
     let session_id_str = session_id.to_string();
 
     // NOTE: User message is persisted by save_turn() after the agent completes,
@@ -387,6 +649,10 @@ async fn handle_chat(
     // Get the agent stream
     let stream_result = {
         if let Some(mut session) = app_state.session_cache().get(&session_id).await {
+            // Inherit the workstream's agent settings for this turn
+            if let Some(domain) = app_state.domain() {
+                domain.chat().apply_workstream_settings(&mut session, ws_id);
+            }
             let cancellation = conn_state.cancellation.clone();
             let stream = app_state.agent().turn_stream(
                 &mut session,
@@ -414,8 +680,23 @@ async fn handle_chat(
     // Clone references for use in async stream
     let workstream_id_for_stream = workstream_id.clone();
     let session_cache = app_state.session_cache().clone();
+    let webhook_state = app_state.clone();
     let user_message = message.clone();
 
+    // Everyone else watching the session sees the message and the response
+    let viewers = app_state.session_viewers().clone();
+    let connection_id = conn_state.id;
+    viewers
+        .publish(
+            session_id,
+            Some(connection_id),
+            &ServerMessage::UserMessage {
+                session_id: session_id_str.clone(),
+                message: message.clone(),
+            },
+        )
+        .await;
+
     // Create response stream
     let session_id_for_stream = session_id_str.clone();
     let response_stream = async_stream::stream! {
@@ -504,6 +785,7 @@ async fn handle_chat(
                     if let Err(e) = session_cache.save_turn(session_id, &turn, &workstream_id_str).await {
                         tracing::warn!("Failed to persist turn to workstream: {}", e);
                     }
+                    webhook_state.emit_turn_webhooks(session_id, Some(&workstream_id_str), &turn);
 
                     yield ServerMessage::ChatChunk {
                         session_id: session_id_for_stream.clone(),
@@ -521,7 +803,17 @@ async fn handle_chat(
         }
     };
 
-    MessageResponse::Stream(Box::pin(response_stream))
+    let relayed = response_stream.then(move |msg| {
+        let viewers = viewers.clone();
+        async move {
+            if !matches!(msg, ServerMessage::SessionCreated { .. }) {
+                viewers.publish(session_id, Some(connection_id), &msg).await;
+            }
+            msg
+        }
+    });
+
+    MessageResponse::Stream(Box::pin(relayed))
 }
 
 #[cfg(test)]
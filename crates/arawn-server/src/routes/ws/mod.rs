@@ -3,13 +3,15 @@
 //! This module provides WebSocket support for the Arawn server, enabling:
 //! - Real-time chat with streaming responses
 //! - Tool execution status updates
-//! - Session subscription for multi-client scenarios
+//! - Session subscription for multi-client scenarios, with read-only
+//!   observers, a roster of viewers and ownership handoff
 //!
 //! ## Module Structure
 //!
 //! - `protocol` - Message types (ClientMessage, ServerMessage)
 //! - `connection` - Connection lifecycle and state management
 //! - `handlers` - Message processing logic
+//! - `viewers` - Connections subscribed to each session
 //!
 //! ## Security
 //!
@@ -20,6 +22,7 @@
 mod connection;
 mod handlers;
 mod protocol;
+mod viewers;
 
 use axum::{
     extract::{ConnectInfo, State, ws::WebSocketUpgrade},
@@ -33,7 +36,8 @@ use crate::state::AppState;
 // Re-export public types
 pub use connection::ConnectionId;
 pub use handlers::MessageResponse;
-pub use protocol::{ClientMessage, ServerMessage};
+pub use protocol::{ClientMessage, ServerMessage, SessionViewer, ViewerRole};
+pub use viewers::{Outbox, SessionViewers};
 
 /// GET /ws - WebSocket upgrade handler.
 ///
@@ -63,7 +67,7 @@ pub async fn ws_handler(
     }
 
     // Check WebSocket connection rate limit
-    if config.rate_limiting
+    if state.rate_limit().enabled()
         && let Err(response) = state.check_ws_connection_rate(addr.ip()).await
     {
         return response;
@@ -22,6 +22,10 @@ pub enum ClientMessage {
         /// Reconnect token from previous session ownership (for reclaiming after disconnect).
         #[serde(skip_serializing_if = "Option::is_none")]
         reconnect_token: Option<String>,
+        /// Watch the session read-only. Observers never become the owner on
+        /// their own and cannot send Chat, but can be handed ownership.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        observe: bool,
     },
     /// Unsubscribe from session updates.
     Unsubscribe {
@@ -48,6 +52,36 @@ pub enum ClientMessage {
         #[serde(default)]
         args: serde_json::Value,
     },
+    /// Hand session ownership to another viewer (owner only).
+    Handoff {
+        /// Session ID to hand off.
+        session_id: String,
+        /// Connection ID of the viewer to become the owner (from `roster`).
+        to: String,
+    },
+}
+
+/// Role of a connection subscribed to a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewerRole {
+    /// Can send Chat messages.
+    Owner,
+    /// Subscribed normally; becomes the owner if the session is free.
+    Reader,
+    /// Subscribed read-only.
+    Observer,
+}
+
+/// A connection subscribed to a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionViewer {
+    /// Connection ID (the target for `handoff`).
+    pub connection_id: String,
+    /// The viewer's role.
+    pub role: ViewerRole,
+    /// Who the connection authenticated as (e.g. `token`, `user:alice`).
+    pub identity: String,
 }
 
 /// Messages from server to client.
@@ -122,6 +156,40 @@ pub enum ServerMessage {
         /// Only present if this connection is the owner.
         #[serde(skip_serializing_if = "Option::is_none")]
         reconnect_token: Option<String>,
+        /// This connection's ID, as it appears in `roster`.
+        connection_id: String,
+        /// Whether this connection subscribed as a read-only observer.
+        #[serde(default)]
+        observer: bool,
+        /// The session's viewers, including this connection.
+        #[serde(default)]
+        viewers: Vec<SessionViewer>,
+    },
+    /// The connections subscribed to a session, pushed to the other viewers
+    /// whenever someone joins, leaves or ownership changes.
+    Roster {
+        /// Session ID.
+        session_id: String,
+        /// Subscribed connections, owner first.
+        viewers: Vec<SessionViewer>,
+    },
+    /// This connection gained or lost ownership of a session through a handoff.
+    OwnershipChanged {
+        /// Session ID.
+        session_id: String,
+        /// Whether this connection is now the owner.
+        owner: bool,
+        /// Reconnect token, present when this connection became the owner.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reconnect_token: Option<String>,
+    },
+    /// A message the owner sent, relayed to the session's other viewers
+    /// before the response streams in.
+    UserMessage {
+        /// Session ID.
+        session_id: String,
+        /// The message content.
+        message: String,
     },
     /// Command execution progress.
     CommandProgress {
@@ -280,11 +348,17 @@ impl ServerMessage {
         session_id: impl Into<String>,
         owner: bool,
         reconnect_token: Option<String>,
+        connection_id: impl std::fmt::Display,
+        observer: bool,
+        viewers: Vec<SessionViewer>,
     ) -> Self {
         Self::SubscribeAck {
             session_id: session_id.into(),
             owner,
             reconnect_token,
+            connection_id: connection_id.to_string(),
+            observer,
+            viewers,
         }
     }
 
@@ -335,14 +409,14 @@ mod tests {
         let json = r#"{"type": "subscribe", "session_id": "123"}"#;
         let msg: ClientMessage = serde_json::from_str(json).unwrap();
         assert!(
-            matches!(msg, ClientMessage::Subscribe { session_id, reconnect_token: None } if session_id == "123")
+            matches!(msg, ClientMessage::Subscribe { session_id, reconnect_token: None, observe: false } if session_id == "123")
         );
 
         // Subscribe with reconnect token
         let json = r#"{"type": "subscribe", "session_id": "456", "reconnect_token": "tok-xyz"}"#;
         let msg: ClientMessage = serde_json::from_str(json).unwrap();
         assert!(
-            matches!(msg, ClientMessage::Subscribe { session_id, reconnect_token: Some(tok) } if session_id == "456" && tok == "tok-xyz")
+            matches!(msg, ClientMessage::Subscribe { session_id, reconnect_token: Some(tok), observe: false } if session_id == "456" && tok == "tok-xyz")
         );
     }
 
@@ -414,7 +488,14 @@ mod tests {
     #[test]
     fn test_subscribe_ack_serialization() {
         // Owner with token
-        let msg = ServerMessage::subscribe_ack("session-123", true, Some("token-abc".to_string()));
+        let msg = ServerMessage::subscribe_ack(
+            "session-123",
+            true,
+            Some("token-abc".to_string()),
+            "conn-1",
+            false,
+            Vec::new(),
+        );
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("subscribe_ack"));
         assert!(json.contains("session-123"));
@@ -422,12 +503,53 @@ mod tests {
         assert!(json.contains("token-abc"));
 
         // Reader (non-owner, no token)
-        let msg = ServerMessage::subscribe_ack("session-456", false, None);
+        let msg =
+            ServerMessage::subscribe_ack("session-456", false, None, "conn-2", true, Vec::new());
         let json = serde_json::to_string(&msg).unwrap();
         assert!(json.contains("subscribe_ack"));
         assert!(json.contains("session-456"));
         assert!(json.contains(r#""owner":false"#));
         assert!(!json.contains("reconnect_token")); // should be omitted
+        assert!(json.contains(r#""connection_id":"conn-2""#));
+        assert!(json.contains(r#""observer":true"#));
+    }
+
+    #[test]
+    fn test_observer_and_handoff_messages() {
+        let json = r#"{"type": "subscribe", "session_id": "123", "observe": true}"#;
+        let msg: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::Subscribe { observe: true, .. }
+        ));
+
+        let json = r#"{"type": "handoff", "session_id": "123", "to": "conn-2"}"#;
+        let msg: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(
+            matches!(msg, ClientMessage::Handoff { session_id, to } if session_id == "123" && to == "conn-2")
+        );
+
+        let msg = ServerMessage::Roster {
+            session_id: "123".to_string(),
+            viewers: vec![SessionViewer {
+                connection_id: "conn-1".to_string(),
+                role: ViewerRole::Observer,
+                identity: "user:alice".to_string(),
+            }],
+        };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains(r#""type":"roster""#));
+        assert!(json.contains(r#""role":"observer""#));
+        assert!(json.contains("user:alice"));
+
+        let msg = ServerMessage::OwnershipChanged {
+            session_id: "123".to_string(),
+            owner: false,
+            reconnect_token: None,
+        };
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("ownership_changed"));
+        assert!(!json.contains("reconnect_token"));
     }
 
     #[test]
@@ -0,0 +1,300 @@
+//! Connections watching each session.
+//!
+//! Every subscribed connection registers its outbox here, so the owner's
+//! response stream can be relayed to readers and observers, and roster
+//! updates can reach everyone on a session. Ownership itself stays in
+//! `AppState::session_owners`; this registry only knows who is listening.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::{RwLock, mpsc};
+
+use arawn_domain::SessionId;
+
+use super::connection::ConnectionId;
+use super::protocol::{ServerMessage, SessionViewer, ViewerRole};
+
+/// Channel for pushing messages to a connection outside of its own requests.
+pub type Outbox = mpsc::UnboundedSender<ServerMessage>;
+
+/// A connection subscribed to a session.
+#[derive(Debug, Clone)]
+struct Viewer {
+    outbox: Outbox,
+    observer: bool,
+    identity: String,
+    /// Whether the connection may be handed ownership (has the chat scope).
+    can_own: bool,
+    /// Join order, so rosters list viewers in the order they arrived.
+    seq: u64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    sessions: HashMap<SessionId, HashMap<ConnectionId, Viewer>>,
+    next_seq: u64,
+}
+
+/// Registry of the connections subscribed to each session.
+#[derive(Debug, Clone, Default)]
+pub struct SessionViewers {
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl SessionViewers {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or update a connection's subscription to a session.
+    pub async fn join(
+        &self,
+        session_id: SessionId,
+        connection_id: ConnectionId,
+        outbox: Outbox,
+        observer: bool,
+        identity: String,
+        can_own: bool,
+    ) {
+        let mut inner = self.inner.write().await;
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        let viewers = inner.sessions.entry(session_id).or_default();
+        let seq = viewers.get(&connection_id).map_or(seq, |v| v.seq);
+        viewers.insert(
+            connection_id,
+            Viewer {
+                outbox,
+                observer,
+                identity,
+                can_own,
+                seq,
+            },
+        );
+    }
+
+    /// Remove a connection from a session. Returns `true` if it was subscribed.
+    pub async fn leave(&self, session_id: SessionId, connection_id: ConnectionId) -> bool {
+        let mut inner = self.inner.write().await;
+        let Some(viewers) = inner.sessions.get_mut(&session_id) else {
+            return false;
+        };
+        let removed = viewers.remove(&connection_id).is_some();
+        if viewers.is_empty() {
+            inner.sessions.remove(&session_id);
+        }
+        removed
+    }
+
+    /// Remove a connection from every session. Returns the sessions it left.
+    pub async fn leave_all(&self, connection_id: ConnectionId) -> Vec<SessionId> {
+        let mut inner = self.inner.write().await;
+        let mut left = Vec::new();
+        inner.sessions.retain(|session_id, viewers| {
+            if viewers.remove(&connection_id).is_some() {
+                left.push(*session_id);
+            }
+            !viewers.is_empty()
+        });
+        left
+    }
+
+    /// Mark a viewer as an observer or not (used when ownership is handed to
+    /// an observer, which then participates normally).
+    pub async fn set_observer(
+        &self,
+        session_id: SessionId,
+        connection_id: ConnectionId,
+        observer: bool,
+    ) {
+        let mut inner = self.inner.write().await;
+        if let Some(viewer) = inner
+            .sessions
+            .get_mut(&session_id)
+            .and_then(|v| v.get_mut(&connection_id))
+        {
+            viewer.observer = observer;
+        }
+    }
+
+    /// Whether a connection is subscribed to a session and may own it.
+    pub async fn can_own(&self, session_id: SessionId, connection_id: ConnectionId) -> bool {
+        let inner = self.inner.read().await;
+        inner
+            .sessions
+            .get(&session_id)
+            .and_then(|v| v.get(&connection_id))
+            .is_some_and(|v| v.can_own)
+    }
+
+    /// Number of connections subscribed to a session.
+    pub async fn count(&self, session_id: SessionId) -> usize {
+        let inner = self.inner.read().await;
+        inner.sessions.get(&session_id).map_or(0, HashMap::len)
+    }
+
+    /// Send a message to every viewer of a session except `except`.
+    pub async fn publish(
+        &self,
+        session_id: SessionId,
+        except: Option<ConnectionId>,
+        msg: &ServerMessage,
+    ) {
+        let inner = self.inner.read().await;
+        let Some(viewers) = inner.sessions.get(&session_id) else {
+            return;
+        };
+        for (connection_id, viewer) in viewers {
+            if Some(*connection_id) != except {
+                // A closed outbox means the connection is shutting down
+                let _ = viewer.outbox.send(msg.clone());
+            }
+        }
+    }
+
+    /// Send a message to one viewer of a session. Returns `false` if the
+    /// connection isn't subscribed or has gone away.
+    pub async fn send_to(
+        &self,
+        session_id: SessionId,
+        connection_id: ConnectionId,
+        msg: ServerMessage,
+    ) -> bool {
+        let inner = self.inner.read().await;
+        inner
+            .sessions
+            .get(&session_id)
+            .and_then(|v| v.get(&connection_id))
+            .is_some_and(|v| v.outbox.send(msg).is_ok())
+    }
+
+    /// The viewers of a session, owner first and then in join order.
+    pub async fn roster(
+        &self,
+        session_id: SessionId,
+        owner: Option<ConnectionId>,
+    ) -> Vec<SessionViewer> {
+        let inner = self.inner.read().await;
+        let Some(viewers) = inner.sessions.get(&session_id) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<_> = viewers.iter().collect();
+        entries.sort_by_key(|(id, v)| (Some(**id) != owner, v.seq));
+        entries
+            .into_iter()
+            .map(|(id, v)| SessionViewer {
+                connection_id: id.to_string(),
+                role: if Some(*id) == owner {
+                    ViewerRole::Owner
+                } else if v.observer {
+                    ViewerRole::Observer
+                } else {
+                    ViewerRole::Reader
+                },
+                identity: v.identity.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outbox() -> (Outbox, mpsc::UnboundedReceiver<ServerMessage>) {
+        mpsc::unbounded_channel()
+    }
+
+    #[tokio::test]
+    async fn test_publish_skips_sender() {
+        let viewers = SessionViewers::new();
+        let sid = SessionId::new();
+        let (a, mut a_rx) = outbox();
+        let (b, mut b_rx) = outbox();
+        let (conn_a, conn_b) = (ConnectionId::new(), ConnectionId::new());
+        viewers
+            .join(sid, conn_a, a, false, "token".into(), true)
+            .await;
+        viewers
+            .join(sid, conn_b, b, true, "token".into(), true)
+            .await;
+
+        viewers
+            .publish(sid, Some(conn_a), &ServerMessage::Pong)
+            .await;
+        assert!(matches!(b_rx.try_recv(), Ok(ServerMessage::Pong)));
+        assert!(a_rx.try_recv().is_err());
+
+        // Other sessions don't receive it
+        viewers
+            .publish(SessionId::new(), None, &ServerMessage::Pong)
+            .await;
+        assert!(b_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_roster_roles_and_order() {
+        let viewers = SessionViewers::new();
+        let sid = SessionId::new();
+        let (reader, observer, owner) = (
+            ConnectionId::new(),
+            ConnectionId::new(),
+            ConnectionId::new(),
+        );
+        viewers
+            .join(sid, reader, outbox().0, false, "token".into(), true)
+            .await;
+        viewers
+            .join(sid, observer, outbox().0, true, "user:bob".into(), false)
+            .await;
+        viewers
+            .join(sid, owner, outbox().0, false, "user:alice".into(), true)
+            .await;
+
+        let roster = viewers.roster(sid, Some(owner)).await;
+        let roles: Vec<_> = roster.iter().map(|v| v.role).collect();
+        assert_eq!(
+            roles,
+            vec![ViewerRole::Owner, ViewerRole::Reader, ViewerRole::Observer]
+        );
+        assert_eq!(roster[0].identity, "user:alice");
+        assert!(!viewers.can_own(sid, observer).await);
+        assert!(viewers.can_own(sid, reader).await);
+
+        viewers.set_observer(sid, observer, false).await;
+        let roster = viewers.roster(sid, None).await;
+        assert!(roster.iter().all(|v| v.role == ViewerRole::Reader));
+    }
+
+    #[tokio::test]
+    async fn test_leave_and_leave_all() {
+        let viewers = SessionViewers::new();
+        let (s1, s2) = (SessionId::new(), SessionId::new());
+        let (conn_a, conn_b) = (ConnectionId::new(), ConnectionId::new());
+        viewers
+            .join(s1, conn_a, outbox().0, false, "token".into(), true)
+            .await;
+        viewers
+            .join(s2, conn_a, outbox().0, false, "token".into(), true)
+            .await;
+        viewers
+            .join(s2, conn_b, outbox().0, false, "token".into(), true)
+            .await;
+
+        assert!(viewers.leave(s1, conn_a).await);
+        assert!(!viewers.leave(s1, conn_a).await);
+        assert_eq!(viewers.count(s1).await, 0);
+
+        viewers
+            .join(s1, conn_a, outbox().0, false, "token".into(), true)
+            .await;
+        let mut left = viewers.leave_all(conn_a).await;
+        left.sort_by_key(|s| s.to_string());
+        let mut expected = vec![s1, s2];
+        expected.sort_by_key(|s| s.to_string());
+        assert_eq!(left, expected);
+        assert_eq!(viewers.count(s2).await, 1);
+    }
+}
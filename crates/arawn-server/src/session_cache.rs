@@ -233,12 +233,14 @@ impl SessionCache {
 
                 // Check cache first (peek to avoid loading)
                 if let Some(session) = self.inner.peek(&session_id_str).await {
+                    crate::metrics::record_session_lookup(true);
                     // Touch the TTL by doing a get_or_load
                     let _ = self.inner.get_or_load(&session_id_str, workstream_id).await;
                     return Ok((id, session, false));
                 }
 
                 // Try to load from workstream
+                crate::metrics::record_session_lookup(false);
                 let (session, _) = self.get_or_load(id, workstream_id).await?;
                 Ok((id, session, false))
             }
@@ -15,8 +15,8 @@
 //! 4. `mcp_manager` — MCP server registry (in `SharedServices`)
 //! 5. `tasks` — background task tracking
 //!
-//! The `ws_connection_tracker` lock is independent and may be acquired at any
-//! point since it never nests with the above locks.
+//! The `ws_connection_tracker` and `session_viewers` locks are independent and
+//! may be acquired at any point since they never nest with the above locks.
 //!
 //! **Guidelines:**
 //! - Release locks before spawning tasks that acquire locks.
@@ -30,21 +30,27 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use arawn_domain::{
-    Agent, Compressor, DirectoryManager, DomainServices, McpManager, MemoryStore, SandboxManager,
-    Session, SessionId, SessionIndexer, WatcherHandle, WorkstreamManager,
+    AccessStore, Agent, Compressor, ConfigService, DirectoryManager, DomainServices, McpManager,
+    MemoryStore, PluginService, ResourceKind, SandboxManager, Session, SessionId, SessionIndexer,
+    TemplateRegistry, Turn, UsageLedger, WatcherHandle, WorkflowService, WorkstreamManager,
 };
 use arawn_types::{HasSessionConfig, SharedHookDispatcher};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use chrono::{DateTime, Utc};
+use metrics_exporter_prometheus::PrometheusHandle;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::auth::{self, Identity};
 use crate::config::ServerConfig;
-use crate::ratelimit::{SharedRateLimiter, create_rate_limiter};
-use crate::routes::ws::ConnectionId;
+use crate::error::ServerError;
+use crate::hooks::InboundHooks;
+use crate::ratelimit::{ApiRateLimit, SharedRateLimiter};
+use crate::routes::ws::{ConnectionId, SessionViewers};
 use crate::session_cache::SessionCache;
+use crate::webhooks::{WebhookDispatcher, WebhookEvent};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Session Ownership Types
@@ -131,6 +137,9 @@ pub struct TrackedTask {
     pub completed_at: Option<DateTime<Utc>>,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Output of a completed task, for tasks that produce one.
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
 }
 
 impl TrackedTask {
@@ -147,6 +156,7 @@ impl TrackedTask {
             started_at: None,
             completed_at: None,
             error: None,
+            result: None,
         }
     }
 
@@ -291,8 +301,8 @@ pub struct SharedServices {
     /// Server configuration.
     pub config: Arc<ServerConfig>,
 
-    /// Per-IP rate limiter (created from config.api_rpm).
-    pub rate_limiter: SharedRateLimiter,
+    /// API rate limit (starts from config.api_rpm, follows config reloads).
+    pub rate_limit: Arc<ApiRateLimit>,
 
     /// Workstream manager (optional — None if workstreams not configured).
     pub workstreams: Option<Arc<WorkstreamManager>>,
@@ -323,17 +333,45 @@ pub struct SharedServices {
 
     /// Session/workstream compressor for LLM-based summarization.
     pub compressor: Option<Arc<Compressor>>,
+
+    /// Token usage ledger (optional — None when usage accounting is disabled).
+    pub usage_ledger: Option<Arc<UsageLedger>>,
+
+    /// Workstream templates (optional — None when no template sources are configured).
+    pub templates: Option<Arc<TemplateRegistry>>,
+
+    /// API tokens and resource owners (optional — None disables named tokens
+    /// and per-user isolation).
+    pub access_store: Option<Arc<AccessStore>>,
+
+    /// Prometheus handle for rendering `/metrics` (None when metrics are disabled).
+    pub metrics: Option<PrometheusHandle>,
+
+    /// Outbound webhook delivery (optional — None disables webhooks).
+    pub webhooks: Option<Arc<WebhookDispatcher>>,
+
+    /// Workflows and runtime catalog (optional — None when the pipeline is disabled).
+    pub workflows: Option<Arc<WorkflowService>>,
+
+    /// Inbound webhook triggers served at `/hooks/{name}` (optional — None when none are configured).
+    pub inbound_hooks: Option<Arc<InboundHooks>>,
+
+    /// Plugin management (optional — None when the plugin system is disabled).
+    pub plugins: Option<Arc<PluginService>>,
+
+    /// Config updates and reloads (optional — None makes the config read-only).
+    pub config_service: Option<Arc<ConfigService>>,
 }
 
 impl SharedServices {
     /// Create new shared services with the given agent and config.
     pub fn new(agent: Agent, config: ServerConfig) -> Self {
-        let rate_limiter = create_rate_limiter(config.api_rpm);
+        let rate_limit = Arc::new(ApiRateLimit::new(config.rate_limiting, config.api_rpm));
 
         Self {
             agent: Arc::new(agent),
             config: Arc::new(config),
-            rate_limiter,
+            rate_limit,
             workstreams: None,
             indexer: None,
             hook_dispatcher: None,
@@ -344,6 +382,15 @@ impl SharedServices {
             memory_store: None,
             domain: None,
             compressor: None,
+            usage_ledger: None,
+            templates: None,
+            access_store: None,
+            metrics: None,
+            webhooks: None,
+            workflows: None,
+            inbound_hooks: None,
+            plugins: None,
+            config_service: None,
         }
     }
 
@@ -371,6 +418,12 @@ impl SharedServices {
         self
     }
 
+    /// Configure an MCP manager that is already shared with other components.
+    pub fn with_shared_mcp_manager(mut self, manager: SharedMcpManager) -> Self {
+        self.mcp_manager = Some(manager);
+        self
+    }
+
     /// Configure directory manager for path management.
     pub fn with_directory_manager(mut self, manager: DirectoryManager) -> Self {
         self.directory_manager = Some(Arc::new(manager));
@@ -401,6 +454,66 @@ impl SharedServices {
         self
     }
 
+    /// Configure the token usage ledger (shared with the agent's usage meter).
+    pub fn with_usage_ledger(mut self, ledger: Arc<UsageLedger>) -> Self {
+        self.usage_ledger = Some(ledger);
+        self
+    }
+
+    /// Configure the API token and ownership store.
+    pub fn with_access_store(mut self, store: Arc<AccessStore>) -> Self {
+        self.access_store = Some(store);
+        self
+    }
+
+    /// Configure workstream templates.
+    pub fn with_templates(mut self, templates: TemplateRegistry) -> Self {
+        self.templates = Some(Arc::new(templates));
+        self
+    }
+
+    /// Configure the Prometheus handle served at `/metrics`.
+    pub fn with_metrics(mut self, handle: PrometheusHandle) -> Self {
+        self.metrics = Some(handle);
+        self
+    }
+
+    /// Configure outbound webhook delivery.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookDispatcher>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Configure the workflow and runtime catalog service.
+    pub fn with_workflows(mut self, workflows: WorkflowService) -> Self {
+        self.workflows = Some(Arc::new(workflows));
+        self
+    }
+
+    /// Configure inbound webhook triggers.
+    pub fn with_inbound_hooks(mut self, hooks: InboundHooks) -> Self {
+        self.inbound_hooks = Some(Arc::new(hooks));
+        self
+    }
+
+    /// Configure plugin management.
+    pub fn with_plugins(mut self, plugins: PluginService) -> Self {
+        self.plugins = Some(Arc::new(plugins));
+        self
+    }
+
+    /// Configure config updates, keeping the rate limit in line with
+    /// `[server]` changes.
+    pub fn with_config_service(mut self, service: ConfigService) -> Self {
+        let rate_limit = self.rate_limit.clone();
+        service.on_change(&["server.rate_limiting", "server.api_rpm"], move |config| {
+            let server = config.server.clone().unwrap_or_default();
+            rate_limit.update(server.rate_limiting, server.api_rpm);
+        });
+        self.config_service = Some(Arc::new(service));
+        self
+    }
+
     /// Build domain services from the configured components.
     ///
     /// This creates a DomainServices instance from the current configuration.
@@ -498,6 +611,11 @@ pub struct RuntimeState {
     /// WebSocket connection rate limiter per IP address.
     /// Independent lock — does not nest with any other locks.
     pub ws_connection_tracker: WsConnectionTracker,
+
+    /// Connections subscribed to each session (owner, readers and observers),
+    /// used to relay the owner's stream and roster updates.
+    /// Independent lock — does not nest with any other locks.
+    pub session_viewers: SessionViewers,
 }
 
 impl RuntimeState {
@@ -510,6 +628,7 @@ impl RuntimeState {
             pending_reconnects: Arc::new(RwLock::new(HashMap::new())),
             active_connections: Arc::new(RwLock::new(HashSet::new())),
             ws_connection_tracker: WsConnectionTracker::new(),
+            session_viewers: SessionViewers::new(),
         }
     }
 
@@ -522,6 +641,7 @@ impl RuntimeState {
             pending_reconnects: Arc::new(RwLock::new(HashMap::new())),
             active_connections: Arc::new(RwLock::new(HashSet::new())),
             ws_connection_tracker: WsConnectionTracker::new(),
+            session_viewers: SessionViewers::new(),
         }
     }
 
@@ -597,6 +717,13 @@ impl AppState {
         self
     }
 
+    /// Create application state with an MCP manager shared with other components
+    /// (e.g. the plugin watcher, which starts plugin-declared servers).
+    pub fn with_shared_mcp_manager(mut self, manager: SharedMcpManager) -> Self {
+        self.services = self.services.with_shared_mcp_manager(manager);
+        self
+    }
+
     /// Create application state with directory manager for path management.
     pub fn with_directory_manager(mut self, manager: DirectoryManager) -> Self {
         self.services = self.services.with_directory_manager(manager);
@@ -621,6 +748,60 @@ impl AppState {
         self
     }
 
+    /// Create application state with the token usage ledger.
+    pub fn with_usage_ledger(mut self, ledger: Arc<UsageLedger>) -> Self {
+        self.services = self.services.with_usage_ledger(ledger);
+        self
+    }
+
+    /// Create application state with the API token and ownership store.
+    pub fn with_access_store(mut self, store: Arc<AccessStore>) -> Self {
+        self.services = self.services.with_access_store(store);
+        self
+    }
+
+    /// Create application state with workstream templates.
+    pub fn with_templates(mut self, templates: TemplateRegistry) -> Self {
+        self.services = self.services.with_templates(templates);
+        self
+    }
+
+    /// Create application state serving Prometheus metrics from `handle`.
+    pub fn with_metrics(mut self, handle: PrometheusHandle) -> Self {
+        self.services = self.services.with_metrics(handle);
+        self
+    }
+
+    /// Create application state with outbound webhook delivery.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookDispatcher>) -> Self {
+        self.services = self.services.with_webhooks(webhooks);
+        self
+    }
+
+    /// Create application state with workflow and runtime management.
+    pub fn with_workflows(mut self, workflows: WorkflowService) -> Self {
+        self.services = self.services.with_workflows(workflows);
+        self
+    }
+
+    /// Create application state with inbound webhook triggers.
+    pub fn with_inbound_hooks(mut self, hooks: InboundHooks) -> Self {
+        self.services = self.services.with_inbound_hooks(hooks);
+        self
+    }
+
+    /// Create application state with plugin management.
+    pub fn with_plugins(mut self, plugins: PluginService) -> Self {
+        self.services = self.services.with_plugins(plugins);
+        self
+    }
+
+    /// Create application state with config updates.
+    pub fn with_config_service(mut self, service: ConfigService) -> Self {
+        self.services = self.services.with_config_service(service);
+        self
+    }
+
     /// Configure session cache using a config provider.
     pub fn with_session_config<C: HasSessionConfig>(mut self, config: &C) -> Self {
         self.runtime.session_cache =
@@ -651,10 +832,16 @@ impl AppState {
         &self.services.config
     }
 
-    /// Get the rate limiter.
+    /// Get the current rate limiter.
+    #[inline]
+    pub fn rate_limiter(&self) -> SharedRateLimiter {
+        self.services.rate_limit.limiter()
+    }
+
+    /// Get the API rate limit.
     #[inline]
-    pub fn rate_limiter(&self) -> &SharedRateLimiter {
-        &self.services.rate_limiter
+    pub fn rate_limit(&self) -> &Arc<ApiRateLimit> {
+        &self.services.rate_limit
     }
 
     /// Get the workstream manager.
@@ -705,6 +892,66 @@ impl AppState {
         self.services.memory_store.as_ref()
     }
 
+    /// Get the Prometheus handle (if metrics are enabled).
+    #[inline]
+    pub fn metrics(&self) -> Option<&PrometheusHandle> {
+        self.services.metrics.as_ref()
+    }
+
+    /// Get the webhook dispatcher.
+    #[inline]
+    pub fn webhooks(&self) -> Option<&Arc<WebhookDispatcher>> {
+        self.services.webhooks.as_ref()
+    }
+
+    /// Get the workflow and runtime catalog service.
+    #[inline]
+    pub fn workflows(&self) -> Option<&Arc<WorkflowService>> {
+        self.services.workflows.as_ref()
+    }
+
+    /// Get the inbound webhook triggers.
+    #[inline]
+    pub fn inbound_hooks(&self) -> Option<&Arc<InboundHooks>> {
+        self.services.inbound_hooks.as_ref()
+    }
+
+    /// Get the plugin management service.
+    #[inline]
+    pub fn plugins(&self) -> Option<&Arc<PluginService>> {
+        self.services.plugins.as_ref()
+    }
+
+    /// Get the config update service.
+    #[inline]
+    pub fn config_service(&self) -> Option<&Arc<ConfigService>> {
+        self.services.config_service.as_ref()
+    }
+
+    /// Emit a webhook event (no-op when webhooks are disabled).
+    pub fn emit_webhook(&self, event: WebhookEvent) {
+        if let Some(webhooks) = self.webhooks() {
+            webhooks.emit(event);
+        }
+    }
+
+    /// Emit `turn_completed` and `tool_failed` webhooks for a finished turn.
+    pub fn emit_turn_webhooks(
+        &self,
+        session_id: SessionId,
+        workstream_id: Option<&str>,
+        turn: &Turn,
+    ) {
+        let Some(webhooks) = self.webhooks() else {
+            return;
+        };
+        let sid = session_id.to_string();
+        webhooks.emit(WebhookEvent::turn_completed(&sid, workstream_id, turn));
+        for event in WebhookEvent::tool_failures(&sid, workstream_id, turn) {
+            webhooks.emit(event);
+        }
+    }
+
     /// Get the domain services facade.
     #[inline]
     pub fn domain(&self) -> Option<&Arc<DomainServices>> {
@@ -717,6 +964,24 @@ impl AppState {
         self.services.compressor.as_ref()
     }
 
+    /// Get the token usage ledger.
+    #[inline]
+    pub fn usage_ledger(&self) -> Option<&Arc<UsageLedger>> {
+        self.services.usage_ledger.as_ref()
+    }
+
+    /// Get the API token and ownership store.
+    #[inline]
+    pub fn access_store(&self) -> Option<&Arc<AccessStore>> {
+        self.services.access_store.as_ref()
+    }
+
+    /// Get the workstream templates.
+    #[inline]
+    pub fn templates(&self) -> Option<&Arc<TemplateRegistry>> {
+        self.services.templates.as_ref()
+    }
+
     /// Get the session cache.
     #[inline]
     pub fn session_cache(&self) -> &SessionCache {
@@ -780,6 +1045,12 @@ impl AppState {
         &self.runtime.ws_connection_tracker
     }
 
+    /// Get the connections subscribed to each session.
+    #[inline]
+    pub fn session_viewers(&self) -> &SessionViewers {
+        &self.runtime.session_viewers
+    }
+
     /// Check WebSocket connection rate for an IP address.
     ///
     /// Returns `Ok(())` if the connection is allowed, `Err(Response)` if rate limited.
@@ -826,6 +1097,41 @@ impl AppState {
             .await
     }
 
+    /// Get or create a session in a workstream on behalf of `identity`.
+    ///
+    /// An existing session must be visible to the identity; a newly created
+    /// one (including one created under a client-chosen ID) is recorded as
+    /// owned by it.
+    pub async fn open_session(
+        &self,
+        identity: &Identity,
+        session_id: Option<SessionId>,
+        workstream_id: &str,
+    ) -> Result<SessionId, ServerError> {
+        let existing = match session_id {
+            Some(id) => self.session_exists(id).await,
+            None => false,
+        };
+        if let Some(id) = session_id.filter(|_| existing) {
+            auth::authorize(self, identity, ResourceKind::Session, &id.to_string())?;
+        }
+        let id = self
+            .get_or_create_session_in_workstream(session_id, workstream_id)
+            .await;
+        if !existing || session_id != Some(id) {
+            auth::claim(self, identity, ResourceKind::Session, &id.to_string());
+        }
+        Ok(id)
+    }
+
+    /// Whether a session is cached or persisted in workstream storage.
+    pub(crate) async fn session_exists(&self, id: SessionId) -> bool {
+        self.runtime.session_cache.contains(&id).await
+            || self
+                .workstreams()
+                .is_some_and(|ws| ws.store().get_session(&id.to_string()).is_ok())
+    }
+
     /// Get or create a session in a specific workstream.
     ///
     /// Sessions are loaded from workstream storage on cache miss and persisted back.
@@ -899,6 +1205,11 @@ impl AppState {
                 .await;
             debug!(session_id = %session_id, turn_count, ?outcome, "SessionEnd hook dispatched");
         }
+        self.emit_webhook(WebhookEvent::session_ended(
+            &session_id.to_string(),
+            workstream_id.as_deref(),
+            turn_count,
+        ));
 
         // Spawn background indexing if indexer is configured and session has turns
         if let Some(indexer) = &self.services.indexer
@@ -907,6 +1218,8 @@ impl AppState {
             let indexer = Arc::clone(indexer);
             let messages = session_to_messages(&session);
             let sid = session_id.to_string();
+            let state = self.clone();
+            let ws_id = workstream_id.clone();
 
             tokio::spawn(async move {
                 let report = indexer
@@ -917,6 +1230,13 @@ impl AppState {
                     report = %report,
                     "Background session indexing complete"
                 );
+                if report.facts_inserted > 0 {
+                    state.emit_webhook(WebhookEvent::memories_indexed(
+                        &sid,
+                        ws_id.as_deref(),
+                        report.facts_inserted,
+                    ));
+                }
                 if report.has_errors() {
                     warn!(
                         session_id = %sid,
@@ -1102,6 +1422,38 @@ impl AppState {
         }
     }
 
+    /// Hand ownership of a session from one connection to another.
+    ///
+    /// Succeeds only if `from` currently owns the session and `to` is a live
+    /// connection. Returns `true` if ownership moved.
+    pub async fn transfer_session_ownership(
+        &self,
+        session_id: SessionId,
+        from: ConnectionId,
+        to: ConnectionId,
+    ) -> bool {
+        if !self.is_connection_active(to).await {
+            return false;
+        }
+        let mut owners = self.runtime.session_owners.write().await;
+        if owners.get(&session_id) != Some(&from) {
+            return false;
+        }
+        owners.insert(session_id, to);
+        debug!(session_id = %session_id, from = %from, to = %to, "Session ownership handed off");
+        true
+    }
+
+    /// The current owner of a session, if any.
+    pub async fn session_owner(&self, session_id: SessionId) -> Option<ConnectionId> {
+        self.runtime
+            .session_owners
+            .read()
+            .await
+            .get(&session_id)
+            .copied()
+    }
+
     /// Release all session ownerships held by a connection, creating pending reconnects.
     ///
     /// Called when a WebSocket connection disconnects. Instead of immediately releasing
@@ -1643,7 +1995,7 @@ mod tests {
         // These should compile and return the expected types
         let _agent: &Arc<Agent> = state.agent();
         let _config: &Arc<ServerConfig> = state.config();
-        let _rate_limiter: &SharedRateLimiter = state.rate_limiter();
+        let _rate_limiter: SharedRateLimiter = state.rate_limiter();
         let _session_cache: &SessionCache = state.session_cache();
         let _tasks: &TaskStore = state.tasks();
         let _session_owners: &SessionOwners = state.session_owners();
@@ -1736,6 +2088,39 @@ mod tests {
         assert!(!state.is_session_owner(session_id, conn_b).await);
     }
 
+    #[tokio::test]
+    async fn test_transfer_session_ownership() {
+        let state = create_test_state();
+        let session_id = SessionId::new();
+        let conn_a = ConnectionId::new();
+        let conn_b = ConnectionId::new();
+
+        state.register_connection(conn_a).await;
+        assert!(state.try_claim_session_ownership(session_id, conn_a).await);
+
+        // Target must be a live connection
+        assert!(
+            !state
+                .transfer_session_ownership(session_id, conn_a, conn_b)
+                .await
+        );
+        state.register_connection(conn_b).await;
+
+        // Only the owner can hand off
+        assert!(
+            !state
+                .transfer_session_ownership(session_id, conn_b, conn_b)
+                .await
+        );
+        assert!(
+            state
+                .transfer_session_ownership(session_id, conn_a, conn_b)
+                .await
+        );
+        assert_eq!(state.session_owner(session_id).await, Some(conn_b));
+        assert!(!state.try_claim_session_ownership(session_id, conn_a).await);
+    }
+
     // ── Full Ownership Lifecycle Tests ────────────────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,816 @@
+//! Outbound webhook delivery.
+//!
+//! [`WebhookDispatcher::emit`] looks up the subscriptions that want an event
+//! and POSTs it to each of them in the background. Every request carries an
+//! HMAC-SHA256 signature of `"{timestamp}.{body}"` keyed with the
+//! subscription's secret, so receivers can verify where it came from and
+//! reject replays. Failed deliveries are retried with exponential backoff and
+//! recorded as dead letters once the attempts run out.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+
+use arawn_domain::{
+    DeadLetter, DiskPressureEvent, Turn, WebhookEventKind, WebhookStore, WebhookSubscription,
+};
+use arawn_types::{HookDispatch, HookOutcome, SharedHookDispatcher};
+
+/// Header carrying `sha256=<hex HMAC>` of `"{timestamp}.{body}"`.
+pub const SIGNATURE_HEADER: &str = "X-Arawn-Signature";
+/// Header carrying the Unix timestamp the signature was computed with.
+pub const TIMESTAMP_HEADER: &str = "X-Arawn-Timestamp";
+/// Header carrying the event type.
+pub const EVENT_HEADER: &str = "X-Arawn-Event";
+/// Header carrying the event ID, stable across retries and redeliveries.
+pub const DELIVERY_HEADER: &str = "X-Arawn-Delivery";
+
+/// Default per-request timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Tool output included in `tool_failed` payloads.
+const ERROR_PREVIEW_CHARS: usize = 500;
+
+// ── Events ──────────────────────────────────────────────────────────────
+
+/// An event as delivered to webhook endpoints.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: WebhookEventKind,
+    /// Workstream the event belongs to, used for subscription filtering.
+    pub workstream_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    /// Create an event with a fresh ID and the current time.
+    pub fn new(kind: WebhookEventKind, workstream_id: Option<String>, data: Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            workstream_id,
+            timestamp: Utc::now(),
+            data,
+        }
+    }
+
+    /// A session was closed.
+    pub fn session_ended(session_id: &str, workstream_id: Option<&str>, turn_count: usize) -> Self {
+        Self::new(
+            WebhookEventKind::SessionEnded,
+            workstream_id.map(str::to_string),
+            json!({ "session_id": session_id, "turn_count": turn_count }),
+        )
+    }
+
+    /// An agent turn finished.
+    pub fn turn_completed(session_id: &str, workstream_id: Option<&str>, turn: &Turn) -> Self {
+        Self::new(
+            WebhookEventKind::TurnCompleted,
+            workstream_id.map(str::to_string),
+            json!({
+                "session_id": session_id,
+                "turn_id": turn.id.to_string(),
+                "tool_calls": turn.tool_calls.len(),
+                "failed_tools": turn.tool_results.iter().filter(|r| !r.success).count(),
+                "has_response": turn.assistant_response.is_some(),
+            }),
+        )
+    }
+
+    /// One `tool_failed` event per failed tool call in a turn.
+    pub fn tool_failures(session_id: &str, workstream_id: Option<&str>, turn: &Turn) -> Vec<Self> {
+        turn.tool_results
+            .iter()
+            .filter(|result| !result.success)
+            .map(|result| {
+                let tool = turn
+                    .tool_calls
+                    .iter()
+                    .find(|call| call.id == result.tool_call_id)
+                    .map(|call| call.name.as_str());
+                Self::new(
+                    WebhookEventKind::ToolFailed,
+                    workstream_id.map(str::to_string),
+                    json!({
+                        "session_id": session_id,
+                        "turn_id": turn.id.to_string(),
+                        "tool_call_id": result.tool_call_id,
+                        "tool": tool,
+                        "error": result.content.chars().take(ERROR_PREVIEW_CHARS).collect::<String>(),
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    /// A background subagent finished (mirrors the `SubagentCompleted` hook).
+    pub fn subagent_completed(
+        parent_session_id: &str,
+        subagent_name: &str,
+        result_preview: &str,
+        duration_ms: u64,
+        success: bool,
+    ) -> Self {
+        Self::new(
+            WebhookEventKind::SubagentCompleted,
+            None,
+            json!({
+                "parent_session_id": parent_session_id,
+                "subagent": subagent_name,
+                "result_preview": result_preview,
+                "duration_ms": duration_ms,
+                "success": success,
+            }),
+        )
+    }
+
+    /// A workflow run finished.
+    pub fn workflow_finished(
+        workflow: &str,
+        execution_id: &str,
+        status: &str,
+        duration_ms: u64,
+        error: Option<&str>,
+    ) -> Self {
+        Self::new(
+            WebhookEventKind::WorkflowFinished,
+            None,
+            json!({
+                "workflow": workflow,
+                "execution_id": execution_id,
+                "status": status,
+                "duration_ms": duration_ms,
+                "error": error,
+            }),
+        )
+    }
+
+    /// Disk usage crossed a threshold. Workstream-scoped alerts are tied to
+    /// their workstream; the `total` alert is not.
+    pub fn disk_pressure(event: &DiskPressureEvent) -> Self {
+        let workstream_id = (event.scope != "total").then(|| event.scope.clone());
+        Self::new(
+            WebhookEventKind::DiskPressure,
+            workstream_id,
+            serde_json::to_value(event).unwrap_or(Value::Null),
+        )
+    }
+
+    /// A memory was stored through the API (`source: "api"`).
+    pub fn memory_stored(workstream_id: Option<&str>, memory_id: &str, content_type: &str) -> Self {
+        Self::new(
+            WebhookEventKind::MemoryStored,
+            workstream_id.map(str::to_string),
+            json!({
+                "source": "api",
+                "memory_id": memory_id,
+                "content_type": content_type,
+                "count": 1,
+            }),
+        )
+    }
+
+    /// Session indexing stored new facts (`source: "indexing"`).
+    pub fn memories_indexed(
+        session_id: &str,
+        workstream_id: Option<&str>,
+        facts_inserted: usize,
+    ) -> Self {
+        Self::new(
+            WebhookEventKind::MemoryStored,
+            workstream_id.map(str::to_string),
+            json!({
+                "source": "indexing",
+                "session_id": session_id,
+                "count": facts_inserted,
+            }),
+        )
+    }
+}
+
+// ── Signing ─────────────────────────────────────────────────────────────
+
+/// Compute the signature header value for a delivery.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+// ── Delivery ────────────────────────────────────────────────────────────
+
+/// How failed deliveries are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each one after.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after the given (1-based) failed attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+/// Why a delivery failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeliveryFailure {
+    pub attempts: u32,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    pub status: Option<u16>,
+    pub error: String,
+}
+
+/// Delivers events to webhook subscriptions.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    store: Arc<WebhookStore>,
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher over a subscription store.
+    pub fn new(store: Arc<WebhookStore>) -> Self {
+        Self {
+            store,
+            client: build_client(DEFAULT_TIMEOUT),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Set the retry policy.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the per-request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = build_client(timeout);
+        self
+    }
+
+    /// The subscription store.
+    pub fn store(&self) -> &Arc<WebhookStore> {
+        &self.store
+    }
+
+    /// Deliver an event to every matching subscription in the background.
+    pub fn emit(&self, event: WebhookEvent) {
+        let subscribers = match self
+            .store
+            .subscribers(event.kind, event.workstream_id.as_deref())
+        {
+            Ok(subscribers) => subscribers,
+            Err(e) => {
+                tracing::warn!(error = %e, event = %event.kind, "Failed to look up webhook subscribers");
+                return;
+            }
+        };
+        if subscribers.is_empty() {
+            return;
+        }
+
+        let body = match serde_json::to_string(&event) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(error = %e, event = %event.kind, "Failed to serialize webhook event");
+                return;
+            }
+        };
+        for webhook in subscribers {
+            let dispatcher = self.clone();
+            let body = body.clone();
+            let (event_id, kind) = (event.id.clone(), event.kind);
+            tokio::spawn(async move {
+                dispatcher
+                    .deliver_with_retries(&webhook, &event_id, kind, &body)
+                    .await;
+            });
+        }
+    }
+
+    /// Deliver with retries, recording a dead letter if every attempt fails.
+    async fn deliver_with_retries(
+        &self,
+        webhook: &WebhookSubscription,
+        event_id: &str,
+        kind: WebhookEventKind,
+        body: &str,
+    ) {
+        let max_attempts = self.retry.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            let Err((status, error)) = self.attempt(webhook, event_id, kind, body).await else {
+                return;
+            };
+            if attempt >= max_attempts {
+                tracing::warn!(
+                    webhook_id = %webhook.id,
+                    event = %kind,
+                    attempts = attempt,
+                    error = %error,
+                    "Webhook delivery failed, recording dead letter"
+                );
+                if let Err(e) = self.store.record_dead_letter(
+                    &webhook.id,
+                    event_id,
+                    kind,
+                    body,
+                    attempt,
+                    status,
+                    &error,
+                ) {
+                    tracing::warn!(error = %e, "Failed to record webhook dead letter");
+                }
+                return;
+            }
+            tracing::debug!(
+                webhook_id = %webhook.id,
+                event = %kind,
+                attempt,
+                error = %error,
+                "Webhook delivery failed, retrying"
+            );
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Redeliver a dead letter once, deleting it on success.
+    pub async fn redeliver(&self, dead: &DeadLetter) -> Result<(), DeliveryFailure> {
+        let webhook = match self.store.get(&dead.subscription_id) {
+            Ok(Some(webhook)) => webhook,
+            Ok(None) => {
+                return Err(DeliveryFailure {
+                    attempts: 0,
+                    status: None,
+                    error: format!("webhook {} no longer exists", dead.subscription_id),
+                });
+            }
+            Err(e) => {
+                return Err(DeliveryFailure {
+                    attempts: 0,
+                    status: None,
+                    error: e.to_string(),
+                });
+            }
+        };
+
+        match self
+            .attempt(&webhook, &dead.event_id, dead.event, &dead.payload)
+            .await
+        {
+            Ok(()) => {
+                if let Err(e) = self.store.delete_dead_letter(&dead.id) {
+                    tracing::warn!(error = %e, "Failed to delete redelivered dead letter");
+                }
+                Ok(())
+            }
+            Err((status, error)) => Err(DeliveryFailure {
+                attempts: 1,
+                status,
+                error,
+            }),
+        }
+    }
+
+    /// Make a single signed delivery attempt.
+    async fn attempt(
+        &self,
+        webhook: &WebhookSubscription,
+        event_id: &str,
+        kind: WebhookEventKind,
+        body: &str,
+    ) -> Result<(), (Option<u16>, String)> {
+        let timestamp = Utc::now().timestamp();
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign(&webhook.secret, timestamp, body))
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(EVENT_HEADER, kind.as_str())
+            .header(DELIVERY_HEADER, event_id)
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|e| (None, e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err((Some(status.as_u16()), format!("HTTP {}", status)))
+        }
+    }
+}
+
+fn build_client(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+// ── Hooks ───────────────────────────────────────────────────────────────
+
+/// Hook dispatcher that emits `subagent_completed` webhooks.
+///
+/// Wraps the plugin hook dispatcher (if any) so background subagents report
+/// through the same `SubagentCompleted` hook they already fire.
+pub struct WebhookHooks {
+    inner: Option<SharedHookDispatcher>,
+    webhooks: Arc<WebhookDispatcher>,
+}
+
+impl WebhookHooks {
+    /// Wrap `inner`, forwarding every hook to it.
+    pub fn new(inner: Option<SharedHookDispatcher>, webhooks: Arc<WebhookDispatcher>) -> Self {
+        Self { inner, webhooks }
+    }
+}
+
+#[async_trait::async_trait]
+impl HookDispatch for WebhookHooks {
+    async fn dispatch_pre_tool_use(&self, tool_name: &str, params: &Value) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => inner.dispatch_pre_tool_use(tool_name, params).await,
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_post_tool_use(
+        &self,
+        tool_name: &str,
+        params: &Value,
+        result: &Value,
+    ) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => {
+                inner
+                    .dispatch_post_tool_use(tool_name, params, result)
+                    .await
+            }
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_session_start(&self, session_id: &str) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => inner.dispatch_session_start(session_id).await,
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_session_end(&self, session_id: &str, turn_count: usize) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => inner.dispatch_session_end(session_id, turn_count).await,
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_stop(&self, response: &str) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => inner.dispatch_stop(response).await,
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_subagent_started(
+        &self,
+        parent_session_id: &str,
+        subagent_name: &str,
+        task_preview: &str,
+    ) -> HookOutcome {
+        match &self.inner {
+            Some(inner) => {
+                inner
+                    .dispatch_subagent_started(parent_session_id, subagent_name, task_preview)
+                    .await
+            }
+            None => HookOutcome::Allow,
+        }
+    }
+
+    async fn dispatch_subagent_completed(
+        &self,
+        parent_session_id: &str,
+        subagent_name: &str,
+        result_preview: &str,
+        duration_ms: u64,
+        success: bool,
+    ) -> HookOutcome {
+        self.webhooks.emit(WebhookEvent::subagent_completed(
+            parent_session_id,
+            subagent_name,
+            result_preview,
+            duration_ms,
+            success,
+        ));
+        match &self.inner {
+            Some(inner) => {
+                inner
+                    .dispatch_subagent_completed(
+                        parent_session_id,
+                        subagent_name,
+                        result_preview,
+                        duration_ms,
+                        success,
+                    )
+                    .await
+            }
+            None => HookOutcome::Allow,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arawn_domain::NewWebhook;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast_retry(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    fn dispatcher_for(server: &MockServer, max_attempts: u32) -> (WebhookDispatcher, String) {
+        let store = Arc::new(WebhookStore::open_in_memory().unwrap());
+        let webhook = store
+            .create(NewWebhook {
+                url: format!("{}/hook", server.uri()),
+                secret: Some("s3cret".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        (
+            WebhookDispatcher::new(store).with_retry(fast_retry(max_attempts)),
+            webhook.id,
+        )
+    }
+
+    async fn wait_for_requests(server: &MockServer, count: usize) {
+        for _ in 0..200 {
+            if server.received_requests().await.unwrap_or_default().len() >= count {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("expected {} webhook requests", count);
+    }
+
+    #[test]
+    fn test_sign() {
+        let signature = sign("secret", 1_700_000_000, r#"{"a":1}"#);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("secret", 1_700_000_000, r#"{"a":1}"#));
+        assert_ne!(signature, sign("other", 1_700_000_000, r#"{"a":1}"#));
+        assert_ne!(signature, sign("secret", 1_700_000_001, r#"{"a":1}"#));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let retry = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        };
+        assert_eq!(retry.backoff(1), Duration::from_secs(1));
+        assert_eq!(retry.backoff(2), Duration::from_secs(2));
+        assert_eq!(retry.backoff(3), Duration::from_secs(4));
+        assert_eq!(retry.backoff(4), Duration::from_secs(5));
+        assert_eq!(retry.backoff(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_disk_pressure_event_scope() {
+        let mut pressure = DiskPressureEvent {
+            level: arawn_domain::PressureLevel::Warning,
+            scope: "total".to_string(),
+            usage_mb: 900.0,
+            limit_mb: 1000.0,
+            timestamp: Utc::now(),
+        };
+        assert_eq!(WebhookEvent::disk_pressure(&pressure).workstream_id, None);
+        pressure.scope = "ws1".to_string();
+        let event = WebhookEvent::disk_pressure(&pressure);
+        assert_eq!(event.workstream_id.as_deref(), Some("ws1"));
+        assert_eq!(event.data["level"], "warning");
+    }
+
+    #[test]
+    fn test_turn_events() {
+        let mut turn = Turn::new("list files");
+        turn.tool_calls.push(arawn_domain::ToolCall {
+            id: "call_1".to_string(),
+            name: "shell".to_string(),
+            arguments: json!({"command": "ls"}),
+        });
+        turn.tool_calls.push(arawn_domain::ToolCall {
+            id: "call_2".to_string(),
+            name: "read_file".to_string(),
+            arguments: json!({}),
+        });
+        turn.tool_results.push(arawn_domain::ToolResultRecord {
+            tool_call_id: "call_1".to_string(),
+            success: true,
+            content: "a.txt".to_string(),
+        });
+        turn.tool_results.push(arawn_domain::ToolResultRecord {
+            tool_call_id: "call_2".to_string(),
+            success: false,
+            content: "x".repeat(2000),
+        });
+
+        let completed = WebhookEvent::turn_completed("s1", Some("ws1"), &turn);
+        assert_eq!(completed.data["tool_calls"], 2);
+        assert_eq!(completed.data["failed_tools"], 1);
+
+        let failures = WebhookEvent::tool_failures("s1", Some("ws1"), &turn);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].kind, WebhookEventKind::ToolFailed);
+        assert_eq!(failures[0].data["tool"], "read_file");
+        assert_eq!(
+            failures[0].data["error"].as_str().unwrap().len(),
+            ERROR_PREVIEW_CHARS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emit_delivers_signed_event() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header(EVENT_HEADER, "session_ended"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let (dispatcher, _) = dispatcher_for(&server, 3);
+
+        let event = WebhookEvent::session_ended("s1", Some("ws1"), 4);
+        let event_id = event.id.clone();
+        dispatcher.emit(event);
+        wait_for_requests(&server, 1).await;
+
+        let request = &server.received_requests().await.unwrap()[0];
+        let body = String::from_utf8(request.body.clone()).unwrap();
+        let timestamp: i64 = request.headers[TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            request.headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("s3cret", timestamp, &body)
+        );
+        assert_eq!(request.headers[DELIVERY_HEADER].to_str().unwrap(), event_id);
+
+        let payload: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["type"], "session_ended");
+        assert_eq!(payload["workstream_id"], "ws1");
+        assert_eq!(payload["data"]["turn_count"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_then_dead_lettered() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        let (dispatcher, webhook_id) = dispatcher_for(&server, 3);
+
+        dispatcher.emit(WebhookEvent::memory_stored(None, "m1", "fact"));
+        wait_for_requests(&server, 3).await;
+
+        let mut dead = Vec::new();
+        for _ in 0..200 {
+            dead = dispatcher.store().dead_letters(None, 10).unwrap();
+            if !dead.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].subscription_id, webhook_id);
+        assert_eq!(dead[0].attempts, 3);
+        assert_eq!(dead[0].last_status, Some(500));
+        assert_eq!(dead[0].event, WebhookEventKind::MemoryStored);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+
+        // Redelivery to a recovered endpoint clears the dead letter
+        server.reset().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        dispatcher.redeliver(&dead[0]).await.unwrap();
+        assert!(
+            dispatcher
+                .store()
+                .dead_letters(None, 10)
+                .unwrap()
+                .is_empty()
+        );
+        let request = &server.received_requests().await.unwrap()[0];
+        assert_eq!(
+            request.headers[DELIVERY_HEADER].to_str().unwrap(),
+            dead[0].event_id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_emit_respects_workstream_filter() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let store = Arc::new(WebhookStore::open_in_memory().unwrap());
+        store
+            .create(NewWebhook {
+                url: server.uri(),
+                workstreams: vec!["ws1".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+        let dispatcher = WebhookDispatcher::new(store);
+
+        dispatcher.emit(WebhookEvent::session_ended("s1", Some("ws2"), 1));
+        dispatcher.emit(WebhookEvent::session_ended("s2", None, 1));
+        dispatcher.emit(WebhookEvent::session_ended("s3", Some("ws1"), 1));
+        wait_for_requests(&server, 1).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let payload: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(payload["data"]["session_id"], "s3");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_hooks_emit_subagent_completed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header(EVENT_HEADER, "subagent_completed"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let (dispatcher, _) = dispatcher_for(&server, 1);
+        let hooks = WebhookHooks::new(None, Arc::new(dispatcher));
+
+        let outcome = hooks
+            .dispatch_subagent_completed("parent", "researcher", "done", 1200, true)
+            .await;
+        assert!(matches!(outcome, HookOutcome::Allow));
+        assert!(hooks.is_empty());
+        wait_for_requests(&server, 1).await;
+
+        let payload: Value =
+            serde_json::from_slice(&server.received_requests().await.unwrap()[0].body).unwrap();
+        assert_eq!(payload["data"]["subagent"], "researcher");
+        assert_eq!(payload["data"]["success"], true);
+    }
+}
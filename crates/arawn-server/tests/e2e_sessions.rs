@@ -1,7 +1,7 @@
 //! E2E tests for the sessions endpoints.
 //!
 //! These tests exercise POST/GET/PATCH/DELETE /api/v1/sessions
-//! and GET /api/v1/sessions/:id/messages.
+//! GET /api/v1/sessions/:id/messages and POST /api/v1/sessions/:id/fork.
 
 mod common;
 
@@ -941,6 +941,98 @@ async fn scenario_reassign_session_nonexistent_workstream() -> Result<()> {
     Ok(())
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Scenario: Fork a session from an earlier turn
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn scenario_fork_session_from_earlier_turn() -> Result<()> {
+    let server = TestServerBuilder::new()
+        .with_workstreams()
+        .with_text_responses(vec!["First answer".into(), "Second answer".into()])
+        .build()
+        .await?;
+
+    let chat_resp = server
+        .post("/api/v1/chat")
+        .json(&json!({"message": "First question"}))
+        .send()
+        .await?;
+    let chat_body: serde_json::Value = chat_resp.json().await?;
+    let session_id = chat_body["session_id"].as_str().unwrap().to_string();
+
+    let chat_resp = server
+        .post("/api/v1/chat")
+        .json(&json!({"message": "Second question", "session_id": session_id}))
+        .send()
+        .await?;
+    assert_eq!(chat_resp.status().as_u16(), 200);
+
+    // Fork keeping only the first turn
+    let resp = server
+        .post(&format!("/api/v1/sessions/{}/fork", session_id))
+        .json(&json!({"turns": 1}))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let fork: serde_json::Value = resp.json().await?;
+    let fork_id = fork["session_id"].as_str().unwrap();
+    assert_ne!(fork_id, session_id);
+    assert_eq!(fork["parent_session_id"], session_id);
+    assert_eq!(fork["messages_copied"], 2);
+    assert!(fork["forked_from_message_id"].as_str().is_some());
+
+    // The fork holds the first exchange only
+    let resp = server
+        .get(&format!("/api/v1/sessions/{}/messages", fork_id))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: serde_json::Value = resp.json().await?;
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["messages"][0]["content"], "First question");
+
+    // The original is untouched
+    let resp = server
+        .get(&format!("/api/v1/sessions/{}/messages", session_id))
+        .send()
+        .await?;
+    let body: serde_json::Value = resp.json().await?;
+    assert_eq!(body["count"], 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn scenario_fork_session_rejects_bad_fork_points() -> Result<()> {
+    let server = TestServerBuilder::new().with_workstreams().build().await?;
+
+    let chat_resp = server
+        .post("/api/v1/chat")
+        .json(&json!({"message": "Hello"}))
+        .send()
+        .await?;
+    let chat_body: serde_json::Value = chat_resp.json().await?;
+    let session_id = chat_body["session_id"].as_str().unwrap();
+
+    let resp = server
+        .post(&format!("/api/v1/sessions/{}/fork", session_id))
+        .json(&json!({"turns": 1, "message_id": "abc"}))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = server
+        .post(&format!("/api/v1/sessions/{}/fork", session_id))
+        .json(&json!({"message_id": "does-not-exist"}))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 404);
+
+    Ok(())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Scenario: Session list summary fields are correct
 // ─────────────────────────────────────────────────────────────────────────────
@@ -719,6 +719,27 @@ async fn scenario_cleanup_no_directory_manager_returns_503() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn scenario_snapshots_no_directory_manager_returns_503() -> Result<()> {
+    let server = TestServerBuilder::new().with_workstreams().build().await?;
+    let ws_id = create_workstream(&server, "No DirMgr Snapshots").await;
+
+    let resp = server
+        .get(&format!("/api/v1/workstreams/{}/snapshots", ws_id))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 503);
+
+    let resp = server
+        .post(&format!("/api/v1/workstreams/{}/snapshots", ws_id))
+        .json(&json!({}))
+        .send()
+        .await?;
+    assert_eq!(resp.status().as_u16(), 503);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn scenario_compress_no_compressor_returns_503() -> Result<()> {
     let server = TestServerBuilder::new().with_workstreams().build().await?;
@@ -106,6 +106,7 @@ async fn test_ws_subscribe_gets_ownership() -> Result<()> {
             session_id: sid,
             owner,
             reconnect_token,
+            ..
         } => {
             assert_eq!(sid, session_id);
             assert!(owner, "First subscriber should be the owner");
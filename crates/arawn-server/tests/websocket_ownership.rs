@@ -395,3 +395,165 @@ async fn test_ws_owner_can_chat() -> Result<()> {
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// 9. Observers see the owner's turns but cannot chat
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_ws_observer_receives_relayed_turn() -> Result<()> {
+    let server = TestServer::start_with_responses(vec!["R1".into()]).await?;
+    let url = server.ws_url();
+    let session_id = uuid::Uuid::new_v4().to_string();
+
+    let mut ws_a = TestWsClient::connect(&url).await?;
+    ws_a.authenticate("test-token").await?;
+    ws_a.subscribe(&session_id).await?;
+
+    // Observer gets the roster in its ack
+    let mut ws_o = TestWsClient::connect(&url).await?;
+    ws_o.authenticate("test-token").await?;
+    let observer_id = match ws_o.observe(&session_id).await? {
+        WsServerMessage::SubscribeAck {
+            owner,
+            observer,
+            connection_id,
+            viewers,
+            ..
+        } => {
+            assert!(!owner, "Observer should not own the session");
+            assert!(observer, "Ack should confirm observer mode");
+            let roles: Vec<_> = viewers.iter().map(|v| v.role.as_str()).collect();
+            assert_eq!(roles, vec!["owner", "observer"]);
+            connection_id
+        }
+        other => panic!("Expected SubscribeAck, got: {:?}", other),
+    };
+
+    // The owner is told someone joined
+    match ws_a.recv().await? {
+        WsServerMessage::Roster { viewers, .. } => {
+            assert_eq!(viewers.len(), 2);
+            assert_eq!(viewers[1].connection_id, observer_id);
+        }
+        other => panic!("Expected Roster, got: {:?}", other),
+    }
+
+    // The owner's turn is relayed to the observer
+    ws_a.chat("hello", Some(&session_id), None).await?;
+    let first = ws_o.recv().await?;
+    assert!(
+        matches!(&first, WsServerMessage::UserMessage { message, .. } if message == "hello"),
+        "Observer should see the user's message first, got: {:?}",
+        first
+    );
+    let mut saw_chunk = false;
+    loop {
+        match ws_o.recv().await? {
+            WsServerMessage::ChatChunk { done: true, .. } | WsServerMessage::Error { .. } => break,
+            WsServerMessage::ChatChunk { chunk, .. } => saw_chunk |= chunk == "R1",
+            WsServerMessage::SessionCreated { .. } => {
+                panic!("SessionCreated is only sent to the sender")
+            }
+            _ => continue,
+        }
+    }
+    assert!(saw_chunk, "Observer should see the owner's response");
+
+    // Observers are read-only
+    let msgs = ws_o.chat("me too", Some(&session_id), None).await?;
+    assert!(
+        msgs.iter().any(
+            |m| matches!(m, WsServerMessage::Error { code, .. } if code == "session_not_owned")
+        ),
+        "Observer chat should be rejected"
+    );
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// 10. Owner hands the session to an observer
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_ws_handoff_to_observer() -> Result<()> {
+    let server = TestServer::start_with_responses(vec!["R1".into(), "R2".into()]).await?;
+    let url = server.ws_url();
+    let session_id = uuid::Uuid::new_v4().to_string();
+
+    let mut ws_a = TestWsClient::connect(&url).await?;
+    ws_a.authenticate("test-token").await?;
+    ws_a.subscribe(&session_id).await?;
+
+    let mut ws_b = TestWsClient::connect(&url).await?;
+    ws_b.authenticate("test-token").await?;
+    let b_id = match ws_b.observe(&session_id).await? {
+        WsServerMessage::SubscribeAck { connection_id, .. } => connection_id,
+        other => panic!("Expected SubscribeAck, got: {:?}", other),
+    };
+
+    // Only the owner can hand off
+    let denied = ws_b.handoff(&session_id, &b_id).await?;
+    assert!(
+        matches!(&denied, WsServerMessage::Error { code, .. } if code == "session_not_owned"),
+        "Non-owner handoff should be rejected, got: {:?}",
+        denied
+    );
+
+    // Unknown targets are rejected
+    let unknown = ws_a
+        .handoff(&session_id, &uuid::Uuid::new_v4().to_string())
+        .await?;
+    assert!(
+        matches!(&unknown, WsServerMessage::Error { code, .. } if code == "invalid_viewer"),
+        "Handoff to an unknown connection should fail, got: {:?}",
+        unknown
+    );
+
+    let released = ws_a.handoff(&session_id, &b_id).await?;
+    assert!(
+        matches!(
+            released,
+            WsServerMessage::OwnershipChanged { owner: false, .. }
+        ),
+        "Old owner should be told it gave up ownership"
+    );
+
+    // The new owner is notified with a fresh reconnect token
+    loop {
+        match ws_b.recv().await? {
+            WsServerMessage::OwnershipChanged {
+                owner,
+                reconnect_token,
+                ..
+            } => {
+                assert!(owner);
+                assert!(reconnect_token.is_some());
+                break;
+            }
+            WsServerMessage::Roster { .. } => continue,
+            other => panic!("Expected OwnershipChanged, got: {:?}", other),
+        }
+    }
+
+    // The old owner is now a reader
+    let msgs_a = ws_a.chat("still mine?", Some(&session_id), None).await?;
+    assert!(
+        msgs_a.iter().any(
+            |m| matches!(m, WsServerMessage::Error { code, .. } if code == "session_not_owned")
+        ),
+        "Old owner should no longer be able to chat"
+    );
+
+    // ...and the new owner can chat
+    let msgs_b = ws_b.chat("my turn", Some(&session_id), None).await?;
+    assert!(
+        msgs_b
+            .iter()
+            .any(|m| matches!(m, WsServerMessage::ChatChunk { done: true, .. })),
+        "New owner should be able to chat"
+    );
+
+    Ok(())
+}
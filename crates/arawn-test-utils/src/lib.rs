@@ -2,12 +2,13 @@
 //!
 //! Provides common infrastructure for integration and unit tests
 //! across all crates: test servers, WebSocket clients, fixtures,
-//! and streaming mock backends.
+//! streaming mock backends, and replay of captured sessions.
 
 pub mod assertions;
 pub mod fixtures;
 pub mod mock_backend;
 pub mod mock_tools;
+pub mod replay;
 pub mod server;
 pub mod sse;
 pub mod ws_client;
@@ -19,6 +20,7 @@ pub use mock_backend::{
 pub use mock_tools::{
     EchoTool, FailTool, LargeOutputTool, MockReadFileTool, SlowTool, mock_tool_registry,
 };
+pub use replay::ReplaySession;
 pub use server::TestServer;
 pub use sse::{SseEvent, collect_sse_events, events_of_type, reconstruct_text};
 pub use ws_client::TestWsClient;
@@ -0,0 +1,116 @@
+//! Replay captured sessions as regression tests.
+//!
+//! A session run with `[logging.capture] enabled = true` leaves JSONL files
+//! under `~/.config/arawn/captures/`. Copy the relevant file into a test's
+//! fixtures and drive an agent against it:
+//!
+//! ```rust,ignore
+//! use arawn_test_utils::ReplaySession;
+//!
+//! let replay = ReplaySession::load("tests/fixtures/read-file-session.jsonl");
+//! let agent = Agent::builder()
+//!     .with_shared_backend(replay.backend())
+//!     .with_tools(tools)
+//!     .build()?;
+//! agent.turn(&mut session, "Summarize README.md", None).await?;
+//! replay.assert_consumed();
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+
+use arawn_llm::{ReplayBackend, SharedBackend};
+
+/// A loaded capture served through a [`ReplayBackend`].
+pub struct ReplaySession {
+    backend: Arc<ReplayBackend>,
+}
+
+impl ReplaySession {
+    /// Load a capture file or directory, panicking with the path on failure.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let backend = ReplayBackend::from_path(path)
+            .unwrap_or_else(|e| panic!("Failed to load capture {}: {}", path.display(), e));
+        Self::from_backend(backend)
+    }
+
+    /// Wrap an already configured replay backend.
+    pub fn from_backend(backend: ReplayBackend) -> Self {
+        Self {
+            backend: Arc::new(backend),
+        }
+    }
+
+    /// The backend to hand to an agent.
+    pub fn backend(&self) -> SharedBackend {
+        self.backend.clone()
+    }
+
+    /// Number of recorded calls not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.backend.remaining()
+    }
+
+    /// Assert every recorded call was replayed.
+    ///
+    /// Fewer calls than recorded usually means the agent loop stopped early.
+    pub fn assert_consumed(&self) {
+        let remaining = self.remaining();
+        assert_eq!(
+            remaining, 0,
+            "{} recorded LLM call(s) were never replayed",
+            remaining
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestFixtures;
+    use arawn_domain::{Agent, ToolRegistry};
+    use arawn_llm::{CaptureLog, CaptureLogConfig, CapturingBackend, Redactor};
+
+    #[tokio::test]
+    async fn test_captured_session_replays() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = Arc::new(
+            CaptureLog::new(
+                CaptureLogConfig {
+                    path: Some(dir.path().to_path_buf()),
+                    retention_days: 1,
+                },
+                Redactor::new(),
+            )
+            .unwrap(),
+        );
+
+        // Record a turn against a mock backend
+        let recording = CapturingBackend::new(
+            Arc::new(TestFixtures::mock_backend(&["Recorded answer"])),
+            log,
+        );
+        let agent = Agent::builder()
+            .with_backend(recording)
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        let mut session = TestFixtures::session();
+        agent.turn(&mut session, "Hello", None).await.unwrap();
+
+        // Replay it without the original backend
+        let replay = ReplaySession::load(dir.path());
+        assert_eq!(replay.remaining(), 1);
+        let agent = Agent::builder()
+            .with_shared_backend(replay.backend())
+            .with_tools(ToolRegistry::new())
+            .build()
+            .unwrap();
+        let mut session = TestFixtures::session();
+        let response = agent.turn(&mut session, "Hello", None).await.unwrap();
+
+        assert_eq!(response.text, "Recorded answer");
+        replay.assert_consumed();
+    }
+}
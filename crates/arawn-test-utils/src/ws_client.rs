@@ -60,6 +60,26 @@ pub enum WsServerMessage {
         owner: bool,
         #[serde(skip_serializing_if = "Option::is_none")]
         reconnect_token: Option<String>,
+        #[serde(default)]
+        connection_id: String,
+        #[serde(default)]
+        observer: bool,
+        #[serde(default)]
+        viewers: Vec<WsViewer>,
+    },
+    Roster {
+        session_id: String,
+        viewers: Vec<WsViewer>,
+    },
+    OwnershipChanged {
+        session_id: String,
+        owner: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reconnect_token: Option<String>,
+    },
+    UserMessage {
+        session_id: String,
+        message: String,
     },
     ContextInfo {
         session_id: String,
@@ -83,6 +103,15 @@ pub enum WsServerMessage {
     Unknown,
 }
 
+/// A connection listed in a session roster.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WsViewer {
+    pub connection_id: String,
+    /// `owner`, `reader` or `observer`.
+    pub role: String,
+    pub identity: String,
+}
+
 impl TestWsClient {
     /// Connect to a WebSocket server at the given URL.
     pub async fn connect(url: &str) -> Result<Self> {
@@ -116,7 +145,7 @@ impl TestWsClient {
             "session_id": session_id
         });
         self.send_json(&msg).await?;
-        self.recv().await
+        self.recv_reply().await
     }
 
     /// Subscribe with a reconnect token.
@@ -131,7 +160,29 @@ impl TestWsClient {
             "reconnect_token": token
         });
         self.send_json(&msg).await?;
-        self.recv().await
+        self.recv_reply().await
+    }
+
+    /// Subscribe to a session as a read-only observer.
+    pub async fn observe(&mut self, session_id: &str) -> Result<WsServerMessage> {
+        let msg = serde_json::json!({
+            "type": "subscribe",
+            "session_id": session_id,
+            "observe": true
+        });
+        self.send_json(&msg).await?;
+        self.recv_reply().await
+    }
+
+    /// Hand ownership of a session to another subscribed connection.
+    pub async fn handoff(&mut self, session_id: &str, to: &str) -> Result<WsServerMessage> {
+        let msg = serde_json::json!({
+            "type": "handoff",
+            "session_id": session_id,
+            "to": to
+        });
+        self.send_json(&msg).await?;
+        self.recv_reply().await
     }
 
     /// Send a chat message and collect all response messages until done.
@@ -159,7 +210,7 @@ impl TestWsClient {
     pub async fn ping(&mut self) -> Result<()> {
         let msg = serde_json::json!({"type": "ping"});
         self.send_json(&msg).await?;
-        let resp = self.recv().await?;
+        let resp = self.recv_reply().await?;
         match resp {
             WsServerMessage::Pong => Ok(()),
             other => bail!("Expected Pong, got: {:?}", other),
@@ -226,6 +277,17 @@ impl TestWsClient {
         }
     }
 
+    /// Receive the reply to a request, skipping roster updates pushed because
+    /// other connections joined or left a shared session.
+    async fn recv_reply(&mut self) -> Result<WsServerMessage> {
+        loop {
+            match self.recv().await? {
+                WsServerMessage::Roster { .. } => continue,
+                msg => return Ok(msg),
+            }
+        }
+    }
+
     /// Try to receive a message with a short timeout. Returns None on timeout.
     pub async fn try_recv(&mut self, wait: Duration) -> Result<Option<WsServerMessage>> {
         match timeout(wait, self.recv()).await {
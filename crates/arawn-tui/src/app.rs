@@ -15,13 +15,17 @@ use crate::events::{Event, EventHandler};
 use crate::input::InputState;
 use crate::logs::LogBuffer;
 use crate::palette::{ActionId, CommandPalette};
-use crate::protocol::ServerMessage;
+use crate::plugins::PluginPanel;
+use crate::protocol::{ServerMessage, SessionViewer};
+use crate::search::MessageSearch;
 use crate::sessions::{SessionList, SessionSummary};
 use crate::sidebar::{Sidebar, SidebarSection, WorkstreamEntry};
 use crate::ui;
 use crate::ui::CommandPopup;
 use anyhow::Result;
-use arawn_client::{ArawnClient, CreateWorkstreamRequest, UpdateWorkstreamRequest};
+use arawn_client::{
+    ArawnClient, CreateWorkstreamRequest, TemplateInfo, UpdateWorkstreamRequest, WorkstreamSettings,
+};
 use chrono::{DateTime, Utc};
 
 /// Pending async actions to be executed in the main loop.
@@ -43,6 +47,27 @@ pub enum PendingAction {
     FetchSessionMessages(String),
     /// Move a session to a different workstream (session_id, new_workstream_id).
     MoveSessionToWorkstream(String, String),
+    /// Show or edit a workstream's agent settings (workstream_id, `key=value` args).
+    WorkstreamSettings(String, String),
+    /// Fork a session after its first N turns and send a new message there
+    /// (session_id, turns_to_keep, message).
+    ForkAndResubmit(String, usize, String),
+    /// Search message history across workstreams.
+    SearchMessages(String),
+    /// Fetch the plugin list for the plugin panel.
+    LoadPlugins,
+    /// Enable or disable a plugin (plugin_id, enabled).
+    SetPluginEnabled(String, bool),
+    /// Update a plugin subscription and reload it.
+    SyncPlugin(String),
+    /// Merge a workstream into another (source_id, target_id, conflict policy).
+    MergeWorkstream(String, String, Option<String>),
+    /// Move a session into a new workstream (workstream_id, session_id, title).
+    SplitSession(String, String, String),
+    /// List available workstream templates.
+    ListTemplates,
+    /// Create a workstream from a template (template, title, params).
+    CreateFromTemplate(String, String, Vec<(String, String)>),
 }
 
 /// Input mode determines what the input field is being used for.
@@ -55,6 +80,8 @@ pub enum InputMode {
     NewWorkstream,
     /// Renaming a workstream - stores the workstream ID.
     RenameWorkstream(String),
+    /// Editing an earlier user message - stores the number of turns before it.
+    EditMessage(usize),
 }
 use crossterm::event::{KeyCode, KeyModifiers};
 
@@ -130,6 +157,10 @@ pub struct App {
     pub sessions: SessionList,
     /// Command palette state.
     pub palette: CommandPalette,
+    /// Message history search overlay state.
+    pub message_search: MessageSearch,
+    /// Plugin panel state.
+    pub plugin_panel: PluginPanel,
     /// Context name (for display in header).
     pub context_name: Option<String>,
     /// Log buffer for capturing and displaying logs.
@@ -150,6 +181,8 @@ pub struct App {
     pub show_tool_pane: bool,
     /// Pending async actions to process.
     pending_actions: Vec<PendingAction>,
+    /// Text to scroll to once the next session history load completes.
+    pending_jump: Option<String>,
     /// Command autocomplete popup.
     pub command_popup: CommandPopup,
     /// Whether a command is currently executing.
@@ -170,6 +203,8 @@ pub struct App {
     /// Whether the current session is owned by this client (can send Chat).
     /// When false, the client is in read-only mode.
     pub is_session_owner: bool,
+    /// Connections subscribed to the current session, owner first.
+    pub session_viewers: Vec<SessionViewer>,
     /// Pending delete confirmation for workstream (id, name).
     /// Set on first 'd' press, cleared on second 'd' (executes delete) or any other action.
     pub pending_delete_workstream: Option<(String, String)>,
@@ -324,6 +359,8 @@ impl App {
             chat_auto_scroll: true,
             sessions: SessionList::new(),
             palette: CommandPalette::new(),
+            message_search: MessageSearch::new(),
+            plugin_panel: PluginPanel::new(),
             context_name: None,
             log_buffer,
             log_scroll: 0,
@@ -334,6 +371,7 @@ impl App {
             selected_tool_index: None,
             show_tool_pane: false,
             pending_actions: Vec::new(),
+            pending_jump: None,
             command_popup: CommandPopup::new(),
             command_executing: false,
             command_progress: None,
@@ -343,6 +381,7 @@ impl App {
             show_usage_popup: false,
             reconnect_tokens: std::collections::HashMap::new(),
             is_session_owner: true, // Default to owner until told otherwise
+            session_viewers: Vec::new(),
             pending_delete_workstream: None,
             pending_delete_session: None,
             panel_areas: PanelAreas::default(),
@@ -434,7 +473,11 @@ impl App {
         for action in actions {
             match action {
                 PendingAction::CreateWorkstream(title) => {
-                    self.do_create_workstream(&title).await;
+                    self.do_create_workstream(CreateWorkstreamRequest {
+                        title,
+                        ..Default::default()
+                    })
+                    .await;
                 }
                 PendingAction::RenameWorkstream(id, new_title) => {
                     self.do_rename_workstream(&id, &new_title).await;
@@ -463,18 +506,50 @@ impl App {
                     self.do_move_session_to_workstream(&session_id, &workstream_id)
                         .await;
                 }
+                PendingAction::WorkstreamSettings(workstream_id, args) => {
+                    self.do_workstream_settings(&workstream_id, &args).await;
+                }
+                PendingAction::ForkAndResubmit(session_id, turns, message) => {
+                    self.do_fork_and_resubmit(&session_id, turns, message).await;
+                }
+                PendingAction::SearchMessages(query) => {
+                    self.do_search_messages(&query).await;
+                }
+                PendingAction::LoadPlugins => {
+                    self.do_load_plugins().await;
+                }
+                PendingAction::SetPluginEnabled(plugin_id, enabled) => {
+                    self.do_set_plugin_enabled(&plugin_id, enabled).await;
+                }
+                PendingAction::SyncPlugin(plugin_id) => {
+                    self.do_sync_plugin(&plugin_id).await;
+                }
+                PendingAction::MergeWorkstream(source_id, target_id, on_conflict) => {
+                    self.do_merge_workstream(&source_id, &target_id, on_conflict)
+                        .await;
+                }
+                PendingAction::SplitSession(workstream_id, session_id, title) => {
+                    self.do_split_session(&workstream_id, &session_id, &title)
+                        .await;
+                }
+                PendingAction::ListTemplates => {
+                    self.do_list_templates().await;
+                }
+                PendingAction::CreateFromTemplate(template, title, params) => {
+                    self.do_create_workstream(CreateWorkstreamRequest {
+                        title,
+                        template: Some(template),
+                        params: params.into_iter().collect(),
+                        ..Default::default()
+                    })
+                    .await;
+                }
             }
         }
     }
 
     /// Create a workstream via API.
-    async fn do_create_workstream(&mut self, title: &str) {
-        let request = CreateWorkstreamRequest {
-            title: title.to_string(),
-            default_model: None,
-            tags: vec![],
-        };
-
+    async fn do_create_workstream(&mut self, request: CreateWorkstreamRequest) {
         match self.api.workstreams().create(request).await {
             Ok(workstream) => {
                 tracing::info!(
@@ -482,7 +557,19 @@ impl App {
                     workstream.title,
                     workstream.id
                 );
-                self.status_message = Some(format!("Created workstream: {}", workstream.title));
+                self.status_message = Some(match workstream.template {
+                    Some(ref applied) if !applied.warnings.is_empty() => format!(
+                        "Created workstream: {} ({} template warning(s): {})",
+                        workstream.title,
+                        applied.warnings.len(),
+                        applied.warnings.join("; ")
+                    ),
+                    Some(ref applied) => format!(
+                        "Created workstream: {} from {}",
+                        workstream.title, applied.name
+                    ),
+                    None => format!("Created workstream: {}", workstream.title),
+                });
 
                 // Add to sidebar and switch to it
                 self.sidebar.workstreams.push(WorkstreamEntry {
@@ -513,6 +600,7 @@ impl App {
             summary: None,
             default_model: None,
             tags: None,
+            settings: None,
         };
 
         match self.api.workstreams().update(id, request).await {
@@ -667,8 +755,12 @@ impl App {
                     .collect();
                 self.messages.replace_from_vec(chat_messages);
 
-                // Scroll to bottom to show latest messages
+                // Scroll to a search hit if one was selected, otherwise
+                // to the bottom to show latest messages
                 self.chat_auto_scroll = true;
+                if let Some(needle) = self.pending_jump.take() {
+                    self.scroll_to_text(&needle);
+                }
 
                 tracing::info!(
                     "Loaded {} messages for session {}",
@@ -684,6 +776,247 @@ impl App {
         }
     }
 
+    /// Search message history via API.
+    async fn do_search_messages(&mut self, query: &str) {
+        match self.api.search().messages(query).await {
+            Ok(response) => {
+                self.message_search
+                    .set_results(response.hits, response.total);
+            }
+            Err(e) => {
+                tracing::warn!("Message search failed: {}", e);
+                self.message_search
+                    .set_error(format!("Search failed: {}", e));
+            }
+        }
+    }
+
+    /// Fetch the plugin list via API.
+    async fn do_load_plugins(&mut self) {
+        match self.api.plugins().list().await {
+            Ok(response) => self.plugin_panel.set_plugins(response.plugins),
+            Err(e) => {
+                tracing::warn!("Failed to list plugins: {}", e);
+                self.plugin_panel
+                    .set_error(format!("Failed to list plugins: {}", e));
+            }
+        }
+    }
+
+    /// Enable or disable a plugin via API.
+    async fn do_set_plugin_enabled(&mut self, plugin_id: &str, enabled: bool) {
+        let plugins = self.api.plugins();
+        let result = if enabled {
+            plugins.enable(plugin_id).await
+        } else {
+            plugins.disable(plugin_id).await
+        };
+        match result {
+            Ok(plugin) => {
+                self.status_message = Some(format!(
+                    "{} plugin {}",
+                    if enabled { "Enabled" } else { "Disabled" },
+                    plugin.name
+                ));
+                self.plugin_panel.update_plugin(plugin);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to update plugin {}: {}", plugin_id, e);
+                self.plugin_panel
+                    .set_error(format!("Failed to update {}: {}", plugin_id, e));
+            }
+        }
+    }
+
+    /// Sync a plugin subscription via API.
+    async fn do_sync_plugin(&mut self, plugin_id: &str) {
+        match self.api.plugins().sync(plugin_id).await {
+            Ok(response) => {
+                self.status_message = Some(format!(
+                    "Synced plugin {}: {}",
+                    response.plugin.name,
+                    response.result.action.replace('_', " ")
+                ));
+                self.plugin_panel.update_plugin(response.plugin);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to sync plugin {}: {}", plugin_id, e);
+                self.plugin_panel
+                    .set_error(format!("Failed to sync {}: {}", plugin_id, e));
+            }
+        }
+    }
+
+    /// Show a workstream's agent settings, or apply `key=value` edits to them.
+    async fn do_workstream_settings(&mut self, workstream_id: &str, args: &str) {
+        let workstream = match self.api.workstreams().get(workstream_id).await {
+            Ok(ws) => ws,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to load settings: {}", e));
+                return;
+            }
+        };
+
+        let mut settings = workstream.settings;
+        if !args.trim().is_empty() {
+            if let Err(e) = apply_settings_args(&mut settings, args) {
+                self.status_message = Some(e);
+                return;
+            }
+            let request = UpdateWorkstreamRequest {
+                settings: Some(settings),
+                ..Default::default()
+            };
+            match self.api.workstreams().update(workstream_id, request).await {
+                Ok(ws) => {
+                    settings = ws.settings;
+                    self.status_message = Some("Workstream settings updated".to_string());
+                }
+                Err(e) => {
+                    self.status_message = Some(format!("Failed to update settings: {}", e));
+                    return;
+                }
+            }
+        }
+
+        self.push_message(ChatMessage {
+            is_user: false,
+            content: format_settings(&workstream.title, &settings),
+            streaming: false,
+        });
+    }
+
+    /// Fork a session before an edited message and send the new text there.
+    ///
+    /// The original session keeps its history; the fork becomes current.
+    async fn do_fork_and_resubmit(&mut self, session_id: &str, turns: usize, message: String) {
+        use arawn_client::ForkSessionRequest;
+
+        let request = ForkSessionRequest {
+            turns: Some(turns),
+            ..Default::default()
+        };
+        let fork = match self.api.sessions().fork(session_id, request).await {
+            Ok(fork) => fork,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to fork session: {}", e));
+                return;
+            }
+        };
+
+        // Load the fork's history now rather than via the queued fetch, so the
+        // resubmitted message lands after it
+        self.switch_to_session(&fork.session_id);
+        self.pending_actions
+            .retain(|a| !matches!(a, PendingAction::FetchSessionMessages(_)));
+        self.do_fetch_session_messages(&fork.session_id).await;
+        self.status_message = Some(format!("Forked session after {} turn(s)", turns));
+
+        self.push_message(ChatMessage {
+            is_user: true,
+            content: message.clone(),
+            streaming: false,
+        });
+        self.tools.clear();
+        self.chat_auto_scroll = true;
+
+        if let Err(e) = self.ws_client.send_chat(
+            message,
+            Some(fork.session_id.clone()),
+            self.workstream_id.clone(),
+        ) {
+            self.status_message = Some(format!("Failed to send: {}", e));
+            return;
+        }
+        self.waiting = true;
+    }
+
+    /// Merge a workstream into another via API and follow the current
+    /// session into the target.
+    async fn do_merge_workstream(
+        &mut self,
+        source_id: &str,
+        target_id: &str,
+        on_conflict: Option<String>,
+    ) {
+        use arawn_client::MergeWorkstreamRequest;
+
+        let request = MergeWorkstreamRequest {
+            target: target_id.to_string(),
+            on_conflict,
+        };
+        match self.api.workstreams().merge(source_id, request).await {
+            Ok(result) => {
+                let session_id = self.session_id.clone();
+                self.refresh_sidebar_data().await;
+                self.switch_to_workstream(&result.workstream.title);
+                if let Some(id) = session_id {
+                    self.switch_to_session(&id);
+                }
+
+                let mut status = format!(
+                    "Merged into {}: {} session(s), {} message(s), {} file(s)",
+                    result.workstream.title,
+                    result.session_ids.len(),
+                    result.messages_moved,
+                    result.files_moved + result.files_renamed + result.files_overwritten,
+                );
+                if result.files_skipped > 0 {
+                    status.push_str(&format!(", {} skipped", result.files_skipped));
+                }
+                self.status_message = Some(status);
+            }
+            Err(e) => {
+                tracing::error!("Failed to merge workstream: {}", e);
+                self.status_message = Some(format!("Failed to merge: {}", e));
+            }
+        }
+    }
+
+    /// Show the available workstream templates in chat.
+    async fn do_list_templates(&mut self) {
+        match self.api.templates().list().await {
+            Ok(response) => {
+                let content = format_templates(&response.templates);
+                self.push_message(ChatMessage {
+                    is_user: false,
+                    content,
+                    streaming: false,
+                });
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to list templates: {}", e));
+            }
+        }
+    }
+
+    /// Move a session into a new workstream via API.
+    async fn do_split_session(&mut self, workstream_id: &str, session_id: &str, title: &str) {
+        use arawn_client::SplitWorkstreamRequest;
+
+        let request = SplitWorkstreamRequest {
+            session_ids: vec![session_id.to_string()],
+            title: title.to_string(),
+            tags: Vec::new(),
+        };
+        match self.api.workstreams().split(workstream_id, request).await {
+            Ok(result) => {
+                // The session stays current; it just lives somewhere new
+                self.workstream = result.workstream.title.clone();
+                self.workstream_id = Some(result.workstream.id.clone());
+                self.status_message = Some(format!(
+                    "Moved session to new workstream {}",
+                    result.workstream.title
+                ));
+                self.refresh_sidebar_data().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to split session: {}", e);
+                self.status_message = Some(format!("Failed to split: {}", e));
+            }
+        }
+    }
+
     /// Move a session to a different workstream via API.
     async fn do_move_session_to_workstream(&mut self, session_id: &str, workstream_id: &str) {
         use arawn_client::UpdateSessionRequest;
@@ -905,6 +1238,15 @@ impl App {
                         };
                     self.status_message = Some(format!("/{}: {}", command, result_str));
 
+                    // A fork becomes the current session
+                    if command == "fork"
+                        && let Some(id) = result.get("session_id").and_then(|v| v.as_str())
+                    {
+                        self.switch_to_session(id);
+                        self.status_message = Some("Switched to forked session".to_string());
+                        return;
+                    }
+
                     // Add as system message in chat
                     self.push_message(ChatMessage {
                         is_user: false,
@@ -994,9 +1336,12 @@ impl App {
                 session_id,
                 owner,
                 reconnect_token,
+                viewers,
+                ..
             } => {
                 // Update ownership state
                 self.is_session_owner = owner;
+                self.session_viewers = viewers;
 
                 // Store reconnect token if we're the owner
                 if let Some(token) = reconnect_token {
@@ -1010,6 +1355,49 @@ impl App {
                     self.status_message = Some("Read-only mode".to_string());
                 }
             }
+
+            ServerMessage::Roster {
+                session_id,
+                viewers,
+            } => {
+                if self.session_id.as_deref() == Some(session_id.as_str()) {
+                    self.session_viewers = viewers;
+                }
+            }
+
+            ServerMessage::OwnershipChanged {
+                session_id,
+                owner,
+                reconnect_token,
+            } => {
+                if let Some(token) = reconnect_token {
+                    self.reconnect_tokens.insert(session_id.clone(), token);
+                } else if !owner {
+                    self.reconnect_tokens.remove(&session_id);
+                }
+                if self.session_id.as_deref() == Some(session_id.as_str()) {
+                    self.is_session_owner = owner;
+                    self.status_message = Some(if owner {
+                        "Session handed to you".to_string()
+                    } else {
+                        "Read-only mode: session handed off".to_string()
+                    });
+                }
+            }
+
+            ServerMessage::UserMessage {
+                session_id,
+                message,
+            } => {
+                // Another client's turn on the session we're watching
+                if self.session_id.as_deref() == Some(session_id.as_str()) {
+                    self.push_message(ChatMessage {
+                        is_user: true,
+                        content: message,
+                        streaming: false,
+                    });
+                }
+            }
         }
     }
 
@@ -1047,6 +1435,10 @@ impl App {
                     self.open_sessions_panel();
                     return;
                 }
+                KeyCode::Char('f') => {
+                    self.focus.push_overlay(FocusTarget::MessageSearch);
+                    return;
+                }
                 KeyCode::Char('w') => {
                     // Toggle sidebar open/closed
                     if self.sidebar.is_open() {
@@ -1105,6 +1497,8 @@ impl App {
             FocusTarget::Sessions => self.handle_sessions_key(key),
             FocusTarget::CommandPalette => self.handle_palette_key(key),
             FocusTarget::Workstreams => self.handle_overlay_key(key),
+            FocusTarget::MessageSearch => self.handle_search_key(key),
+            FocusTarget::Plugins => self.handle_plugins_key(key),
             FocusTarget::ToolPane => self.handle_tool_pane_key(key),
             FocusTarget::Logs => self.handle_logs_key(key),
         }
@@ -1255,6 +1649,21 @@ impl App {
                             self.input_mode = InputMode::Chat;
                             self.status_message = None;
                         }
+                        InputMode::EditMessage(turns) => {
+                            if self.waiting || self.command_executing {
+                                return;
+                            }
+                            let turns = *turns;
+                            let Some(session_id) = self.session_id.clone() else {
+                                self.input_mode = InputMode::Chat;
+                                return;
+                            };
+                            let message = self.input.submit();
+                            self.pending_actions
+                                .push(PendingAction::ForkAndResubmit(session_id, turns, message));
+                            self.input_mode = InputMode::Chat;
+                            self.status_message = Some("Forking session...".to_string());
+                        }
                     }
                 }
             }
@@ -1438,6 +1847,48 @@ impl App {
                 return;
             }
 
+            if cmd.name.eq_ignore_ascii_case("edit") {
+                self.start_edit_message(&cmd.args);
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("settings") {
+                match self.workstream_id.clone() {
+                    Some(id) => self
+                        .pending_actions
+                        .push(PendingAction::WorkstreamSettings(id, cmd.args.clone())),
+                    None => {
+                        self.status_message = Some("No workstream selected".to_string());
+                    }
+                }
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("merge") {
+                self.start_merge(&cmd.args);
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("split") {
+                self.start_split(&cmd.args);
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("plugins") {
+                self.open_plugins_panel();
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("templates") {
+                self.pending_actions.push(PendingAction::ListTemplates);
+                return;
+            }
+
+            if cmd.name.eq_ignore_ascii_case("template") {
+                self.start_template(&cmd.args);
+                return;
+            }
+
             // Check read-only mode for server commands
             if !self.is_session_owner {
                 self.status_message = Some("Read-only mode: cannot run commands".to_string());
@@ -1460,6 +1911,148 @@ impl App {
         }
     }
 
+    /// Queue a merge of the current workstream into the one named in `args`.
+    ///
+    /// A trailing `rename`, `overwrite` or `skip` sets the file conflict policy.
+    fn start_merge(&mut self, args: &str) {
+        let Some(source_id) = self.workstream_id.clone() else {
+            self.status_message = Some("No workstream selected".to_string());
+            return;
+        };
+
+        let mut target = args.trim();
+        let mut on_conflict = None;
+        if let Some((rest, last)) = target.rsplit_once(' ')
+            && matches!(last, "rename" | "overwrite" | "skip")
+        {
+            target = rest.trim_end();
+            on_conflict = Some(last.to_string());
+        }
+        if target.is_empty() {
+            self.status_message =
+                Some("Usage: /merge <workstream> [rename|overwrite|skip]".to_string());
+            return;
+        }
+
+        let Some(target_id) = self
+            .sidebar
+            .workstreams
+            .iter()
+            .find(|ws| ws.id == target || ws.name.eq_ignore_ascii_case(target))
+            .map(|ws| ws.id.clone())
+        else {
+            self.status_message = Some(format!("Unknown workstream: {}", target));
+            return;
+        };
+        if target_id == source_id {
+            self.status_message = Some("Cannot merge a workstream into itself".to_string());
+            return;
+        }
+
+        self.pending_actions.push(PendingAction::MergeWorkstream(
+            source_id,
+            target_id,
+            on_conflict,
+        ));
+    }
+
+    /// Queue a move of the current session into a new workstream titled `args`.
+    fn start_split(&mut self, args: &str) {
+        let title = args.trim();
+        if title.is_empty() {
+            self.status_message = Some("Usage: /split <new workstream title>".to_string());
+            return;
+        }
+        match (self.workstream_id.clone(), self.session_id.clone()) {
+            (Some(workstream_id), Some(session_id)) => {
+                self.pending_actions.push(PendingAction::SplitSession(
+                    workstream_id,
+                    session_id,
+                    title.to_string(),
+                ));
+            }
+            _ => {
+                self.status_message = Some("No session selected".to_string());
+            }
+        }
+    }
+
+    /// Queue creation of a workstream from a template.
+    ///
+    /// `args` is `<template> <title words> [key=value ...]`.
+    fn start_template(&mut self, args: &str) {
+        let mut words = args.split_whitespace();
+        let template = words.next().unwrap_or_default().to_string();
+        let mut title = Vec::new();
+        let mut params = Vec::new();
+        for word in words {
+            match word.split_once('=') {
+                Some((key, value)) if !key.is_empty() => {
+                    params.push((key.to_string(), value.to_string()));
+                }
+                _ => title.push(word),
+            }
+        }
+        if template.is_empty() || title.is_empty() {
+            self.status_message =
+                Some("Usage: /template <name> <title> [key=value ...]".to_string());
+            return;
+        }
+
+        self.pending_actions.push(PendingAction::CreateFromTemplate(
+            template,
+            title.join(" "),
+            params,
+        ));
+    }
+
+    /// Load an earlier user message into the input for editing.
+    ///
+    /// `args` is the 1-based position of the message among this session's
+    /// user messages; empty means the most recent one. Submitting the edit
+    /// forks the session just before that message.
+    fn start_edit_message(&mut self, args: &str) {
+        if self.session_id.is_none() {
+            self.status_message = Some("No session to edit".to_string());
+            return;
+        }
+        if !self.is_session_owner {
+            self.status_message = Some("Read-only mode: cannot edit messages".to_string());
+            return;
+        }
+
+        let user_messages: Vec<&str> = self
+            .messages
+            .iter()
+            .filter(|m| m.is_user)
+            .map(|m| m.content.as_str())
+            .collect();
+        let index = match args.trim() {
+            "" => user_messages.len().checked_sub(1),
+            n => n
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .filter(|i| *i < user_messages.len()),
+        };
+        let Some(index) = index else {
+            self.status_message = Some(format!(
+                "No such message (session has {} user message(s))",
+                user_messages.len()
+            ));
+            return;
+        };
+
+        let text = user_messages[index].to_string();
+        self.input.clear();
+        self.input.set_text(&text);
+        self.input_mode = InputMode::EditMessage(index);
+        self.status_message = Some(format!(
+            "Editing message {} - Enter to fork and resend (Esc to cancel)",
+            index + 1
+        ));
+    }
+
     /// Build command arguments JSON from parsed command.
     fn build_command_args(&self, cmd: &crate::input::ParsedCommand) -> serde_json::Value {
         let mut args = serde_json::json!({});
@@ -1492,6 +2085,20 @@ impl App {
         let mut text = String::from("**Available Commands:**\n\n");
         text.push_str("/compact - Compact session history by summarizing older turns\n");
         text.push_str("  Options: --force, -f (force compaction even if not needed)\n\n");
+        text.push_str("/settings - Show this workstream's agent settings\n");
+        text.push_str("  Edit: /settings key=value ... (model, profile, files, allow, deny,\n");
+        text.push_str("  memory, network; lists are comma-separated, empty clears;\n");
+        text.push_str("  prompt=... takes the rest of the line)\n\n");
+        text.push_str("/edit [N] - Edit your Nth message (default: last) and resend it\n");
+        text.push_str("  in a fork of this session; the original is kept\n\n");
+        text.push_str("/fork - Fork this session into a new one\n\n");
+        text.push_str("/merge <workstream> [rename|overwrite|skip] - Merge this workstream\n");
+        text.push_str("  into another and archive it; the option decides file clashes\n\n");
+        text.push_str("/split <title> - Move this session into a new workstream\n\n");
+        text.push_str("/plugins - Manage plugins: enable, disable and sync them\n\n");
+        text.push_str("/templates - List workstream templates\n\n");
+        text.push_str("/template <name> <title> [key=value ...] - Create a workstream\n");
+        text.push_str("  from a template, filling in its parameters\n\n");
         text.push_str("/help - Show this help message\n");
         text
     }
@@ -1621,6 +2228,144 @@ impl App {
         }
     }
 
+    /// Handle message search overlay key events.
+    fn handle_search_key(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.focus.pop_overlay();
+            }
+            KeyCode::Enter => {
+                // Run the query if it changed, otherwise jump to the selected hit
+                if self.message_search.is_stale() {
+                    let query = self.message_search.query().trim().to_string();
+                    if !query.is_empty() {
+                        self.message_search.start();
+                        self.pending_actions
+                            .push(PendingAction::SearchMessages(query));
+                    }
+                } else if let Some(hit) = self.message_search.selected_hit().cloned() {
+                    self.focus.pop_overlay();
+                    self.jump_to_hit(&hit);
+                }
+            }
+            KeyCode::Up => {
+                self.message_search.select_prev();
+            }
+            KeyCode::Down => {
+                self.message_search.select_next();
+            }
+            KeyCode::Char(c) => {
+                self.message_search.query_push(c);
+            }
+            KeyCode::Backspace => {
+                self.message_search.query_pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the plugin panel and fetch the plugin list.
+    fn open_plugins_panel(&mut self) {
+        self.plugin_panel.start_loading();
+        self.pending_actions.push(PendingAction::LoadPlugins);
+        self.focus.push_overlay(FocusTarget::Plugins);
+    }
+
+    /// Handle plugin panel key events.
+    fn handle_plugins_key(&mut self, key: crossterm::event::KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.focus.pop_overlay();
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.plugin_panel.select_prev();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.plugin_panel.select_next();
+            }
+            KeyCode::Char('r') => {
+                self.plugin_panel.start_loading();
+                self.pending_actions.push(PendingAction::LoadPlugins);
+            }
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('s')
+                if self.plugin_panel.busy().is_none() =>
+            {
+                let Some(plugin) = self.plugin_panel.selected_plugin() else {
+                    return;
+                };
+                let id = plugin.id.clone();
+                let action = if key.code == KeyCode::Char('s') {
+                    PendingAction::SyncPlugin(id.clone())
+                } else {
+                    PendingAction::SetPluginEnabled(id.clone(), !plugin.enabled)
+                };
+                self.plugin_panel.start_change(id);
+                self.pending_actions.push(action);
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the session containing a search hit and scroll to the message.
+    fn jump_to_hit(&mut self, hit: &arawn_client::MessageSearchHit) {
+        let Some(session_id) = hit.session_id.clone() else {
+            self.status_message = Some("That message is not part of a session".to_string());
+            return;
+        };
+
+        if self.workstream_id.as_deref() != Some(hit.workstream_id.as_str()) {
+            let name = self
+                .sidebar
+                .workstreams
+                .iter()
+                .find(|ws| ws.id == hit.workstream_id)
+                .map(|ws| ws.name.clone())
+                .unwrap_or_else(|| hit.workstream_title.clone());
+            self.switch_to_workstream(&name);
+            if self.workstream_id.is_none() {
+                self.workstream_id = Some(hit.workstream_id.clone());
+                self.pending_actions
+                    .push(PendingAction::FetchWorkstreamSessions(
+                        hit.workstream_id.clone(),
+                    ));
+            }
+        }
+
+        self.pending_jump = crate::search::jump_text(&hit.snippet);
+        self.switch_to_session(&session_id);
+    }
+
+    /// Scroll the chat so the first message containing `needle` is at the top.
+    fn scroll_to_text(&mut self, needle: &str) {
+        let Some(index) = self
+            .messages
+            .iter()
+            .position(|m| m.content.contains(needle))
+        else {
+            self.status_message = Some("Match not found in session history".to_string());
+            return;
+        };
+
+        // Mirrors the chat view: one line per user message, one per line of
+        // assistant text, and a blank line between messages
+        let offset: usize = self
+            .messages
+            .iter()
+            .take(index)
+            .map(|m| {
+                let lines = if m.is_user {
+                    1
+                } else {
+                    m.content.lines().count()
+                };
+                lines + 1
+            })
+            .sum();
+        self.chat_scroll = offset;
+        self.chat_auto_scroll = false;
+        self.status_message = Some("Jumped to search match".to_string());
+    }
+
     /// Execute a palette action.
     fn execute_action(&mut self, action_id: ActionId) {
         match action_id {
@@ -1662,6 +2407,12 @@ impl App {
                 self.status_message =
                     Some("New workstream: Enter name (Esc to cancel)".to_string());
             }
+            ActionId::SearchMessages => {
+                self.focus.push_overlay(FocusTarget::MessageSearch);
+            }
+            ActionId::PluginsManage => {
+                self.open_plugins_panel();
+            }
             ActionId::ViewToggleToolPane => {
                 self.focus.toggle(FocusTarget::ToolPane);
             }
@@ -1687,6 +2438,7 @@ impl App {
 
         // Reset ownership state - will be updated by SubscribeAck
         self.is_session_owner = false;
+        self.session_viewers.clear();
 
         // Now clear current messages and tools
         self.messages.clear();
@@ -2254,6 +3006,8 @@ impl App {
             chat_auto_scroll: true,
             sessions: SessionList::new(),
             palette: CommandPalette::new(),
+            message_search: MessageSearch::new(),
+            plugin_panel: PluginPanel::new(),
             context_name: None,
             log_buffer: LogBuffer::new(),
             log_scroll: 0,
@@ -2264,6 +3018,7 @@ impl App {
             selected_tool_index: None,
             show_tool_pane: false,
             pending_actions: Vec::new(),
+            pending_jump: None,
             command_popup: CommandPopup::new(),
             command_executing: false,
             command_progress: None,
@@ -2273,6 +3028,7 @@ impl App {
             show_usage_popup: false,
             reconnect_tokens: std::collections::HashMap::new(),
             is_session_owner: true,
+            session_viewers: Vec::new(),
             pending_delete_workstream: None,
             pending_delete_session: None,
             panel_areas: PanelAreas::default(),
@@ -2281,6 +3037,126 @@ impl App {
     }
 }
 
+/// Apply `/settings` arguments (`key=value` pairs) to a settings record.
+///
+/// List values are comma-separated and an empty value clears the field.
+/// `prompt=` must come last and takes the rest of the line.
+fn apply_settings_args(settings: &mut WorkstreamSettings, args: &str) -> Result<(), String> {
+    fn opt(value: &str) -> Option<String> {
+        (!value.is_empty()).then(|| value.to_string())
+    }
+    fn list(value: &str) -> Vec<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    let (pairs, prompt) = match args.find("prompt=") {
+        Some(idx) => (&args[..idx], Some(args[idx + "prompt=".len()..].trim())),
+        None => (args, None),
+    };
+
+    for part in pairs.split_whitespace() {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got '{}'", part))?;
+        match key {
+            "model" => settings.model = opt(value),
+            "profile" => settings.profile = opt(value),
+            "files" => settings.bootstrap_files = list(value),
+            "allow" => settings.allowed_tools = list(value),
+            "deny" => settings.denied_tools = list(value),
+            "memory" => match value {
+                "all" | "session" | "none" => settings.memory_scope = Some(value.to_string()),
+                _ => return Err("memory must be one of: all, session, none".to_string()),
+            },
+            "network" => settings.network_domains = list(value),
+            other => return Err(format!("Unknown setting '{}'", other)),
+        }
+    }
+
+    if let Some(prompt) = prompt {
+        settings.system_prompt = opt(prompt);
+    }
+    Ok(())
+}
+
+/// Render a workstream's settings for display in the chat pane.
+fn format_settings(title: &str, settings: &WorkstreamSettings) -> String {
+    fn or_default(value: &Option<String>) -> &str {
+        value.as_deref().unwrap_or("(default)")
+    }
+    fn or_all(values: &[String], empty: &str) -> String {
+        if values.is_empty() {
+            empty.to_string()
+        } else {
+            values.join(", ")
+        }
+    }
+
+    let mut text = format!("**Settings for {}:**\n\n", title);
+    text.push_str(&format!("model: {}\n", or_default(&settings.model)));
+    text.push_str(&format!("profile: {}\n", or_default(&settings.profile)));
+    text.push_str(&format!(
+        "prompt: {}\n",
+        or_default(&settings.system_prompt)
+    ));
+    text.push_str(&format!(
+        "files: {}\n",
+        or_all(&settings.bootstrap_files, "(none)")
+    ));
+    text.push_str(&format!(
+        "allow: {}\n",
+        or_all(&settings.allowed_tools, "(all)")
+    ));
+    text.push_str(&format!(
+        "deny: {}\n",
+        or_all(&settings.denied_tools, "(none)")
+    ));
+    text.push_str(&format!(
+        "memory: {}\n",
+        settings.memory_scope.as_deref().unwrap_or("all")
+    ));
+    text.push_str(&format!(
+        "network: {}\n",
+        or_all(&settings.network_domains, "(none)")
+    ));
+    text
+}
+
+/// Render the available workstream templates for display in the chat pane.
+fn format_templates(templates: &[TemplateInfo]) -> String {
+    if templates.is_empty() {
+        return "No workstream templates found.".to_string();
+    }
+
+    let mut text = String::from("**Workstream templates:**\n\n");
+    for t in templates {
+        text.push_str(&t.name);
+        if let Some(ref description) = t.description {
+            text.push_str(&format!(" - {}", description));
+        }
+        text.push('\n');
+        for p in &t.params {
+            let detail = match (&p.default, p.required) {
+                (Some(default), _) => format!(" (default: {})", default),
+                (None, true) => " (required)".to_string(),
+                (None, false) => String::new(),
+            };
+            text.push_str(&format!("  {}{}", p.name, detail));
+            if let Some(ref description) = p.description {
+                text.push_str(&format!(" - {}", description));
+            }
+            text.push('\n');
+        }
+    }
+    text.push_str("\nUse /template <name> <title> [key=value ...] to create one.\n");
+    text
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2412,6 +3288,9 @@ mod tests {
             session_id: "s1".to_string(),
             owner: true,
             reconnect_token: Some("tok".to_string()),
+            connection_id: "c1".to_string(),
+            observer: false,
+            viewers: Vec::new(),
         });
 
         assert!(app.is_session_owner);
@@ -2421,6 +3300,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_handoff_and_relayed_messages() {
+        use crate::protocol::ViewerRole;
+
+        let mut app = App::test_new();
+        app.session_id = Some("s1".to_string());
+        app.is_session_owner = false;
+
+        app.handle_server_message(ServerMessage::Roster {
+            session_id: "s1".to_string(),
+            viewers: vec![SessionViewer {
+                connection_id: "c1".to_string(),
+                role: ViewerRole::Owner,
+                identity: "user:alice".to_string(),
+            }],
+        });
+        assert_eq!(app.session_viewers.len(), 1);
+
+        // Another client's message shows up as a user message
+        app.handle_server_message(ServerMessage::UserMessage {
+            session_id: "s1".to_string(),
+            message: "hi".to_string(),
+        });
+        assert!(
+            app.messages
+                .last()
+                .is_some_and(|m| m.is_user && m.content == "hi")
+        );
+
+        // Messages for other sessions are ignored
+        app.handle_server_message(ServerMessage::UserMessage {
+            session_id: "s2".to_string(),
+            message: "elsewhere".to_string(),
+        });
+        assert_eq!(app.messages.len(), 1);
+
+        app.handle_server_message(ServerMessage::OwnershipChanged {
+            session_id: "s1".to_string(),
+            owner: true,
+            reconnect_token: Some("tok".to_string()),
+        });
+        assert!(app.is_session_owner);
+        assert_eq!(
+            app.reconnect_tokens.get("s1").map(String::as_str),
+            Some("tok")
+        );
+
+        app.handle_server_message(ServerMessage::OwnershipChanged {
+            session_id: "s1".to_string(),
+            owner: false,
+            reconnect_token: None,
+        });
+        assert!(!app.is_session_owner);
+        assert!(!app.reconnect_tokens.contains_key("s1"));
+    }
+
     #[tokio::test]
     async fn test_subscribe_ack_reader() {
         let mut app = App::test_new();
@@ -2429,6 +3364,9 @@ mod tests {
             session_id: "s1".to_string(),
             owner: false,
             reconnect_token: None,
+            connection_id: "c2".to_string(),
+            observer: false,
+            viewers: Vec::new(),
         });
 
         assert!(!app.is_session_owner);
@@ -3010,6 +3948,8 @@ mod tests {
             chat_auto_scroll: true,
             sessions: SessionList::new(),
             palette: CommandPalette::new(),
+            message_search: MessageSearch::new(),
+            plugin_panel: PluginPanel::new(),
             context_name: None,
             log_buffer: LogBuffer::new(),
             log_scroll: 0,
@@ -3020,6 +3960,7 @@ mod tests {
             selected_tool_index: None,
             show_tool_pane: false,
             pending_actions: Vec::new(),
+            pending_jump: None,
             command_popup: CommandPopup::new(),
             command_executing: false,
             command_progress: None,
@@ -3029,6 +3970,7 @@ mod tests {
             show_usage_popup: false,
             reconnect_tokens: std::collections::HashMap::new(),
             is_session_owner: true,
+            session_viewers: Vec::new(),
             pending_delete_workstream: None,
             pending_delete_session: None,
             panel_areas: PanelAreas::default(),
@@ -3144,6 +4086,9 @@ mod tests {
                 session_id: "sess-42".to_string(),
                 owner: true,
                 reconnect_token: Some("tok-new".to_string()),
+                connection_id: "c1".to_string(),
+                observer: false,
+                viewers: Vec::new(),
             })
             .unwrap();
         if let Some(msg) = app.ws_client.try_recv() {
@@ -3269,4 +4214,378 @@ mod tests {
             "No status message when not waiting"
         );
     }
+
+    // ── Workstream Settings ──────────────────────────────────────────
+
+    #[test]
+    fn test_apply_settings_args() {
+        let mut settings = WorkstreamSettings {
+            model: Some("old-model".to_string()),
+            ..Default::default()
+        };
+        apply_settings_args(
+            &mut settings,
+            "model= profile=fast deny=shell,web_fetch memory=session prompt=Be terse. Cite files.",
+        )
+        .unwrap();
+
+        assert!(settings.model.is_none());
+        assert_eq!(settings.profile.as_deref(), Some("fast"));
+        assert_eq!(settings.denied_tools, vec!["shell", "web_fetch"]);
+        assert_eq!(settings.memory_scope.as_deref(), Some("session"));
+        assert_eq!(
+            settings.system_prompt.as_deref(),
+            Some("Be terse. Cite files.")
+        );
+
+        assert!(apply_settings_args(&mut settings, "memory=workstream").is_err());
+        assert!(apply_settings_args(&mut settings, "colour=blue").is_err());
+        assert!(apply_settings_args(&mut settings, "profile").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_settings_command_queues_action() {
+        let mut app = App::test_new();
+        app.workstream_id = Some("ws-1".to_string());
+        app.input.set_text("/settings profile=fast");
+        app.send_command();
+
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::WorkstreamSettings(
+                    "ws-1".to_string(),
+                    "profile=fast".to_string()
+                ))
+        );
+    }
+
+    // ── Merge and Split ──────────────────────────────────────────────
+
+    fn app_with_workstreams() -> App {
+        let mut app = App::test_new();
+        for (id, name) in [("ws-1", "Current"), ("ws-2", "Big Project")] {
+            app.sidebar.workstreams.push(WorkstreamEntry {
+                id: id.to_string(),
+                name: name.to_string(),
+                session_count: 0,
+                is_current: id == "ws-1",
+                is_scratch: false,
+                usage_bytes: None,
+                limit_bytes: None,
+                state: "active".to_string(),
+            });
+        }
+        app.workstream_id = Some("ws-1".to_string());
+        app
+    }
+
+    #[tokio::test]
+    async fn test_merge_command_resolves_target_and_policy() {
+        let mut app = app_with_workstreams();
+        app.input.set_text("/merge big project skip");
+        app.send_command();
+
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::MergeWorkstream(
+                    "ws-1".to_string(),
+                    "ws-2".to_string(),
+                    Some("skip".to_string())
+                ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_command_rejects_unknown_or_self() {
+        let mut app = app_with_workstreams();
+        for input in ["/merge nowhere", "/merge Current", "/merge"] {
+            app.input.set_text(input);
+            app.send_command();
+        }
+        assert!(app.pending_actions.is_empty());
+        assert!(app.status_message.unwrap().starts_with("Usage"));
+    }
+
+    #[tokio::test]
+    async fn test_split_command_queues_current_session() {
+        let mut app = app_with_workstreams();
+        app.input.set_text("/split Side quest");
+        app.send_command();
+        assert!(app.pending_actions.is_empty());
+
+        app.session_id = Some("sess-1".to_string());
+        app.input.set_text("/split Side quest");
+        app.send_command();
+        assert!(app.pending_actions.contains(&PendingAction::SplitSession(
+            "ws-1".to_string(),
+            "sess-1".to_string(),
+            "Side quest".to_string()
+        )));
+    }
+
+    // ── Templates ────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_template_command_parses_title_and_params() {
+        let mut app = App::test_new();
+        app.input
+            .set_text("/template research Fusion power topic=tokamaks depth=deep");
+        app.send_command();
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::CreateFromTemplate(
+                    "research".to_string(),
+                    "Fusion power".to_string(),
+                    vec![
+                        ("topic".to_string(), "tokamaks".to_string()),
+                        ("depth".to_string(), "deep".to_string()),
+                    ]
+                ))
+        );
+
+        app.input.set_text("/template research topic=x");
+        app.send_command();
+        assert_eq!(app.pending_actions.len(), 1);
+        assert!(app.status_message.as_deref().unwrap().starts_with("Usage"));
+
+        app.input.set_text("/templates");
+        app.send_command();
+        assert!(app.pending_actions.contains(&PendingAction::ListTemplates));
+    }
+
+    #[test]
+    fn test_format_templates() {
+        let templates: Vec<TemplateInfo> = serde_json::from_value(serde_json::json!([{
+            "name": "research",
+            "description": "Research project",
+            "params": [
+                { "name": "topic", "required": true },
+                { "name": "depth", "default": "shallow", "required": false }
+            ]
+        }]))
+        .unwrap();
+
+        let text = format_templates(&templates);
+        assert!(text.contains("research - Research project"));
+        assert!(text.contains("topic (required)"));
+        assert!(text.contains("depth (default: shallow)"));
+        assert_eq!(format_templates(&[]), "No workstream templates found.");
+    }
+
+    // ── Edit and Resubmit ────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_edit_command_forks_before_message() {
+        let mut app = App::test_new();
+        app.session_id = Some("sess-1".to_string());
+        app.is_session_owner = true;
+        for (is_user, content) in [
+            (true, "first"),
+            (false, "reply one"),
+            (true, "second"),
+            (false, "reply two"),
+        ] {
+            app.push_message(ChatMessage {
+                is_user,
+                content: content.to_string(),
+                streaming: false,
+            });
+        }
+
+        // Defaults to the last user message
+        app.input.set_text("/edit");
+        app.send_command();
+        assert_eq!(app.input_mode, InputMode::EditMessage(1));
+        assert_eq!(app.input.content(), "second");
+
+        app.input.set_text("/edit 1");
+        app.send_command();
+        assert_eq!(app.input_mode, InputMode::EditMessage(0));
+        assert_eq!(app.input.content(), "first");
+
+        app.input.set_text("first, rephrased");
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.input_mode, InputMode::Chat);
+        assert!(app.input.is_empty());
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::ForkAndResubmit(
+                    "sess-1".to_string(),
+                    0,
+                    "first, rephrased".to_string()
+                ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edit_command_rejects_unknown_message() {
+        let mut app = App::test_new();
+        app.session_id = Some("sess-1".to_string());
+        app.is_session_owner = true;
+        app.push_message(ChatMessage {
+            is_user: true,
+            content: "only".to_string(),
+            streaming: false,
+        });
+
+        app.input.set_text("/edit 3");
+        app.send_command();
+        assert_eq!(app.input_mode, InputMode::Chat);
+        assert!(app.pending_actions.is_empty());
+        assert!(
+            app.status_message
+                .as_deref()
+                .unwrap()
+                .contains("No such message")
+        );
+    }
+
+    // ── Message Search ───────────────────────────────────────────────
+
+    fn search_hit(workstream_id: &str, session_id: &str) -> arawn_client::MessageSearchHit {
+        arawn_client::MessageSearchHit {
+            message_id: "m1".to_string(),
+            workstream_id: workstream_id.to_string(),
+            workstream_title: "Ops".to_string(),
+            session_id: Some(session_id.to_string()),
+            role: "assistant".to_string(),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            snippet: "…rotate the **certificates** weekly…".to_string(),
+            score: 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_f_search_queues_query() {
+        let mut app = App::test_new();
+        app.handle_key(key_mod(KeyCode::Char('f'), KeyModifiers::CONTROL));
+        assert_eq!(app.focus.current(), FocusTarget::MessageSearch);
+
+        // Enter with an empty query does nothing
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.pending_actions.is_empty());
+
+        for c in "certs".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.message_search.is_loading());
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::SearchMessages("certs".to_string()))
+        );
+
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.focus.current(), FocusTarget::Input);
+    }
+
+    #[tokio::test]
+    async fn test_search_enter_jumps_to_hit() {
+        let mut app = App::test_new();
+        app.workstream_id = Some("ws-current".to_string());
+        app.focus.push_overlay(FocusTarget::MessageSearch);
+        app.message_search.query_push('c');
+        app.message_search.start();
+        app.message_search
+            .set_results(vec![search_hit("ws-ops", "sess-7")], 1);
+
+        app.handle_key(key(KeyCode::Enter));
+
+        assert_eq!(app.focus.current(), FocusTarget::Input);
+        assert_eq!(app.workstream_id.as_deref(), Some("ws-ops"));
+        assert_eq!(app.session_id.as_deref(), Some("sess-7"));
+        assert_eq!(
+            app.pending_jump.as_deref(),
+            Some("rotate the certificates weekly")
+        );
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::FetchSessionMessages("sess-7".to_string()))
+        );
+    }
+
+    // ── Plugins ──────────────────────────────────────────────────────
+
+    fn plugin_info(id: &str, enabled: bool) -> arawn_client::PluginInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "source": "github",
+            "enabled": enabled,
+            "loaded": enabled,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_plugins_command_opens_panel() {
+        let mut app = App::test_new();
+        app.input.set_text("/plugins");
+        app.send_command();
+
+        assert_eq!(app.focus.current(), FocusTarget::Plugins);
+        assert!(app.plugin_panel.is_loading());
+        assert!(app.pending_actions.contains(&PendingAction::LoadPlugins));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_panel_keys_queue_changes() {
+        let mut app = App::test_new();
+        app.focus.push_overlay(FocusTarget::Plugins);
+        app.plugin_panel.set_plugins(vec![
+            plugin_info("journal", true),
+            plugin_info("lint", false),
+        ]);
+
+        app.handle_key(key(KeyCode::Enter));
+        assert!(
+            app.pending_actions
+                .contains(&PendingAction::SetPluginEnabled(
+                    "journal".to_string(),
+                    false
+                ))
+        );
+        assert_eq!(app.plugin_panel.busy(), Some("journal"));
+
+        // Further changes wait for the one in flight
+        app.pending_actions.clear();
+        app.handle_key(key(KeyCode::Down));
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.pending_actions.is_empty());
+
+        app.plugin_panel
+            .update_plugin(plugin_info("journal", false));
+        app.handle_key(key(KeyCode::Enter));
+        app.handle_key(key(KeyCode::Char('s')));
+        assert_eq!(
+            app.pending_actions,
+            vec![PendingAction::SetPluginEnabled("lint".to_string(), true)]
+        );
+
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.focus.current(), FocusTarget::Input);
+    }
+
+    #[tokio::test]
+    async fn test_scroll_to_text_positions_message() {
+        let mut app = App::test_new();
+        for (is_user, content) in [
+            (true, "hello"),
+            (false, "line one\nline two"),
+            (true, "how do we rotate the certificates weekly?"),
+        ] {
+            app.push_message(ChatMessage {
+                is_user,
+                content: content.to_string(),
+                streaming: false,
+            });
+        }
+
+        app.scroll_to_text("rotate the certificates weekly");
+        assert!(!app.chat_auto_scroll);
+        assert_eq!(app.chat_scroll, 5);
+
+        app.scroll_to_text("never said");
+        assert!(app.status_message.as_deref().unwrap().contains("not found"));
+    }
 }
@@ -137,6 +137,7 @@ impl WsClient {
             .send(ClientMessage::Subscribe {
                 session_id,
                 reconnect_token,
+                observe: false,
             })
             .context("Failed to subscribe to session")
     }
@@ -21,6 +21,10 @@ pub enum FocusTarget {
     Sessions,
     /// Workstreams list overlay.
     Workstreams,
+    /// Message history search overlay (Ctrl+F).
+    MessageSearch,
+    /// Plugin panel overlay.
+    Plugins,
 }
 
 impl FocusTarget {
@@ -31,7 +35,11 @@ impl FocusTarget {
     pub fn is_overlay(&self) -> bool {
         matches!(
             self,
-            FocusTarget::CommandPalette | FocusTarget::Sessions | FocusTarget::Workstreams
+            FocusTarget::CommandPalette
+                | FocusTarget::Sessions
+                | FocusTarget::Workstreams
+                | FocusTarget::MessageSearch
+                | FocusTarget::Plugins
         )
     }
 
@@ -50,6 +58,8 @@ impl FocusTarget {
             FocusTarget::CommandPalette => "Command Palette",
             FocusTarget::Sessions => "Sessions",
             FocusTarget::Workstreams => "Workstreams",
+            FocusTarget::MessageSearch => "Search",
+            FocusTarget::Plugins => "Plugins",
         }
     }
 }
@@ -355,6 +365,8 @@ mod tests {
         assert!(FocusTarget::CommandPalette.is_overlay());
         assert!(FocusTarget::Sessions.is_overlay());
         assert!(FocusTarget::Workstreams.is_overlay());
+        assert!(FocusTarget::MessageSearch.is_overlay());
+        assert!(FocusTarget::Plugins.is_overlay());
         assert!(!FocusTarget::Input.is_overlay());
         assert!(!FocusTarget::Sidebar.is_overlay());
     }
@@ -10,7 +10,9 @@ pub mod focus;
 pub mod input;
 pub mod logs;
 pub mod palette;
+pub mod plugins;
 pub mod protocol;
+pub mod search;
 pub mod sessions;
 pub mod sidebar;
 pub mod ui;
@@ -24,6 +24,10 @@ pub enum ActionId {
     // Workstreams
     WorkstreamsSwitch,
     WorkstreamsCreate,
+    // Search
+    SearchMessages,
+    // Plugins
+    PluginsManage,
     // View
     ViewToggleToolPane,
     // App
@@ -85,6 +89,18 @@ pub const DEFAULT_ACTIONS: &[Action] = &[
         "Workstreams",
         None,
     ),
+    Action::new(
+        ActionId::SearchMessages,
+        "Search: Message history...",
+        "Search",
+        Some("Ctrl+F"),
+    ),
+    Action::new(
+        ActionId::PluginsManage,
+        "Plugins: Manage...",
+        "Plugins",
+        None,
+    ),
     Action::new(
         ActionId::ViewToggleToolPane,
         "View: Toggle tool pane",
@@ -339,7 +355,7 @@ mod tests {
             }
         }
 
-        // Should have at least Sessions, Workstreams, View, App categories
-        assert!(categories_seen.len() >= 4);
+        // Should have at least Sessions, Workstreams, Search, View, App categories
+        assert!(categories_seen.len() >= 5);
     }
 }
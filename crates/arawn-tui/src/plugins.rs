@@ -0,0 +1,154 @@
+//! Plugin panel state.
+
+use arawn_client::PluginInfo;
+
+/// State for the plugin panel overlay.
+///
+/// The list is fetched when the panel opens; enable, disable and sync
+/// replace the affected entry with the server's response.
+#[derive(Debug, Clone, Default)]
+pub struct PluginPanel {
+    /// Plugins, sorted by name.
+    plugins: Vec<PluginInfo>,
+    /// Currently selected plugin index.
+    selected: usize,
+    /// Whether the list is being fetched.
+    loading: bool,
+    /// ID of the plugin with a change in flight.
+    busy: Option<String>,
+    /// Error from the last request.
+    error: Option<String>,
+}
+
+impl PluginPanel {
+    /// Create an empty panel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the list as being fetched.
+    pub fn start_loading(&mut self) {
+        self.loading = true;
+        self.error = None;
+    }
+
+    /// Check if the list is being fetched.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Store a freshly fetched list, keeping the selection on the same plugin.
+    pub fn set_plugins(&mut self, plugins: Vec<PluginInfo>) {
+        let selected_id = self.selected_plugin().map(|p| p.id.clone());
+        self.plugins = plugins;
+        self.selected = selected_id
+            .and_then(|id| self.plugins.iter().position(|p| p.id == id))
+            .unwrap_or(0);
+        self.loading = false;
+    }
+
+    /// Replace a plugin's entry after a change.
+    pub fn update_plugin(&mut self, plugin: PluginInfo) {
+        match self.plugins.iter_mut().find(|p| p.id == plugin.id) {
+            Some(existing) => *existing = plugin,
+            None => self.plugins.push(plugin),
+        }
+        self.busy = None;
+    }
+
+    /// Mark a change to a plugin as in flight.
+    pub fn start_change(&mut self, id: impl Into<String>) {
+        self.busy = Some(id.into());
+        self.error = None;
+    }
+
+    /// ID of the plugin with a change in flight.
+    pub fn busy(&self) -> Option<&str> {
+        self.busy.as_deref()
+    }
+
+    /// Record a failed request.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.loading = false;
+        self.busy = None;
+        self.error = Some(error.into());
+    }
+
+    /// Get the error from the last request, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Get the plugins.
+    pub fn plugins(&self) -> &[PluginInfo] {
+        &self.plugins
+    }
+
+    /// Get the selected index.
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// Get the selected plugin (if any).
+    pub fn selected_plugin(&self) -> Option<&PluginInfo> {
+        self.plugins.get(self.selected)
+    }
+
+    /// Move selection up.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Move selection down.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.plugins.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(id: &str, enabled: bool) -> PluginInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id.split('@').next().unwrap(),
+            "source": "directory",
+            "enabled": enabled,
+            "loaded": enabled,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_panel_keeps_selection_and_updates_entries() {
+        let mut panel = PluginPanel::new();
+        panel.start_loading();
+        assert!(panel.is_loading());
+        panel.set_plugins(vec![plugin("a@local", true), plugin("b@local", true)]);
+        assert!(!panel.is_loading());
+
+        panel.select_next();
+        panel.select_next();
+        assert_eq!(panel.selected_plugin().unwrap().id, "b@local");
+
+        // A refresh that adds a plugin before the selection keeps it selected
+        panel.set_plugins(vec![
+            plugin("a@local", true),
+            plugin("aa@local", true),
+            plugin("b@local", true),
+        ]);
+        assert_eq!(panel.selected_index(), 2);
+
+        panel.start_change("b@local");
+        assert_eq!(panel.busy(), Some("b@local"));
+        panel.update_plugin(plugin("b@local", false));
+        assert!(panel.busy().is_none());
+        assert!(!panel.selected_plugin().unwrap().enabled);
+
+        panel.set_error("boom");
+        assert_eq!(panel.error(), Some("boom"));
+    }
+}
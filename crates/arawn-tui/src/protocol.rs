@@ -26,6 +26,9 @@ pub enum ClientMessage {
         /// Reconnect token for reclaiming ownership after disconnect.
         #[serde(skip_serializing_if = "Option::is_none")]
         reconnect_token: Option<String>,
+        /// Watch the session read-only without claiming ownership.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        observe: bool,
     },
     /// Unsubscribe from session updates.
     Unsubscribe {
@@ -52,6 +55,36 @@ pub enum ClientMessage {
         #[serde(default)]
         args: serde_json::Value,
     },
+    /// Hand ownership of a session to another subscribed connection.
+    Handoff {
+        /// Session ID to hand off.
+        session_id: String,
+        /// Connection ID of the new owner.
+        to: String,
+    },
+}
+
+/// How a connection participates in a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewerRole {
+    /// Can send chat messages.
+    Owner,
+    /// Subscribed without ownership.
+    Reader,
+    /// Watching read-only.
+    Observer,
+}
+
+/// A connection subscribed to a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionViewer {
+    /// Connection ID.
+    pub connection_id: String,
+    /// The connection's role.
+    pub role: ViewerRole,
+    /// Who is connected, e.g. `user:alice`.
+    pub identity: String,
 }
 
 /// Messages from server to client.
@@ -126,6 +159,39 @@ pub enum ServerMessage {
         /// Only present if this connection is the owner.
         #[serde(skip_serializing_if = "Option::is_none")]
         reconnect_token: Option<String>,
+        /// This connection's ID, used as a handoff target.
+        #[serde(default)]
+        connection_id: String,
+        /// Whether this connection subscribed as a read-only observer.
+        #[serde(default)]
+        observer: bool,
+        /// The session's viewers, including this connection.
+        #[serde(default)]
+        viewers: Vec<SessionViewer>,
+    },
+    /// The connections subscribed to a session changed.
+    Roster {
+        /// Session ID.
+        session_id: String,
+        /// Current viewers, owner first.
+        viewers: Vec<SessionViewer>,
+    },
+    /// Ownership of a session was handed to or taken from this connection.
+    OwnershipChanged {
+        /// Session ID.
+        session_id: String,
+        /// Whether this connection now owns the session.
+        owner: bool,
+        /// Reconnect token, present when ownership was gained.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reconnect_token: Option<String>,
+    },
+    /// A message the session owner sent, relayed to other viewers.
+    UserMessage {
+        /// Session ID.
+        session_id: String,
+        /// The message content.
+        message: String,
     },
     /// Command execution progress.
     CommandProgress {
@@ -0,0 +1,203 @@
+//! Message history search overlay state.
+
+use arawn_client::MessageSearchHit;
+
+/// State for the message search overlay (Ctrl+F).
+///
+/// Typing edits the query; results are fetched from the server when the
+/// query is submitted, so they may lag behind the text until Enter is pressed.
+#[derive(Debug, Clone, Default)]
+pub struct MessageSearch {
+    /// Current query text.
+    query: String,
+    /// Query the current results belong to.
+    searched: Option<String>,
+    /// Hits for the last search.
+    hits: Vec<MessageSearchHit>,
+    /// Total matches reported by the server.
+    total: usize,
+    /// Currently selected hit index.
+    selected: usize,
+    /// Whether a search is in flight.
+    loading: bool,
+    /// Error from the last search.
+    error: Option<String>,
+}
+
+impl MessageSearch {
+    /// Create an empty search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the query text.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Add a character to the query.
+    pub fn query_push(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    /// Remove the last character from the query.
+    pub fn query_pop(&mut self) {
+        self.query.pop();
+    }
+
+    /// Whether the query differs from the one the results were fetched for.
+    pub fn is_stale(&self) -> bool {
+        self.searched.as_deref() != Some(self.query.trim())
+    }
+
+    /// Check if a search is in flight.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Mark a search as started for the current query.
+    pub fn start(&mut self) {
+        self.loading = true;
+        self.error = None;
+        self.searched = Some(self.query.trim().to_string());
+    }
+
+    /// Store results for a completed search.
+    pub fn set_results(&mut self, hits: Vec<MessageSearchHit>, total: usize) {
+        self.hits = hits;
+        self.total = total;
+        self.selected = 0;
+        self.loading = false;
+    }
+
+    /// Record a failed search.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.hits.clear();
+        self.total = 0;
+        self.selected = 0;
+        self.loading = false;
+        self.error = Some(error.into());
+    }
+
+    /// Get the error from the last search, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Whether a search has been run for any query.
+    pub fn has_searched(&self) -> bool {
+        self.searched.is_some()
+    }
+
+    /// Get the hits from the last search.
+    pub fn hits(&self) -> &[MessageSearchHit] {
+        &self.hits
+    }
+
+    /// Total matches across all pages.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the selected index.
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    /// Get the selected hit (if any).
+    pub fn selected_hit(&self) -> Option<&MessageSearchHit> {
+        self.hits.get(self.selected)
+    }
+
+    /// Move selection up.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Move selection down.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.hits.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Clear the query and results.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Extract text from a search snippet that can be located in the full message.
+///
+/// Strips the `**` highlight markers and returns the longest fragment between
+/// the `…` elisions, or `None` if the snippet has no usable text.
+pub fn jump_text(snippet: &str) -> Option<String> {
+    snippet
+        .replace("**", "")
+        .split('…')
+        .map(str::trim)
+        .max_by_key(|s| s.len())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(id: &str) -> MessageSearchHit {
+        MessageSearchHit {
+            message_id: id.to_string(),
+            workstream_id: "ws-1".to_string(),
+            workstream_title: "Ops".to_string(),
+            session_id: Some("s1".to_string()),
+            role: "user".to_string(),
+            timestamp: "2026-03-01T10:00:00Z".to_string(),
+            snippet: "rotate the **certificates**".to_string(),
+            score: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_search_staleness_and_navigation() {
+        let mut search = MessageSearch::new();
+        assert!(search.is_stale());
+        assert!(!search.has_searched());
+
+        for c in "certs ".chars() {
+            search.query_push(c);
+        }
+        search.start();
+        assert!(search.is_loading());
+        assert!(!search.is_stale());
+
+        search.set_results(vec![hit("m1"), hit("m2")], 5);
+        assert_eq!(search.total(), 5);
+        assert_eq!(search.selected_hit().unwrap().message_id, "m1");
+
+        search.select_next();
+        search.select_next();
+        assert_eq!(search.selected_index(), 1);
+        search.select_prev();
+        search.select_prev();
+        assert_eq!(search.selected_index(), 0);
+
+        search.query_pop();
+        search.query_pop();
+        assert!(search.is_stale());
+
+        search.set_error("boom");
+        assert!(search.hits().is_empty());
+        assert_eq!(search.error(), Some("boom"));
+    }
+
+    #[test]
+    fn test_jump_text() {
+        assert_eq!(
+            jump_text("…we should **rotate** the certs… and then").as_deref(),
+            Some("we should rotate the certs")
+        );
+        assert_eq!(jump_text("**deploy**").as_deref(), Some("deploy"));
+        assert_eq!(jump_text("…"), None);
+    }
+}
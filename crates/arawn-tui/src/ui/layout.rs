@@ -12,6 +12,8 @@ use crate::ui::chat::render_chat;
 use crate::ui::input::{calculate_input_height, render_input as render_input_area};
 use crate::ui::logs::{render_logs_footer, render_logs_panel};
 use crate::ui::palette::render_palette_overlay as render_palette;
+use crate::ui::plugins::render_plugins_overlay;
+use crate::ui::search::render_search_overlay;
 use crate::ui::sessions::render_sessions_overlay as render_sessions;
 use crate::ui::sidebar::{SIDEBAR_HINT_WIDTH, SIDEBAR_WIDTH, render_sidebar};
 use crate::ui::tools::{render_tool_pane, render_tool_pane_footer};
@@ -123,6 +125,8 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         FocusTarget::Sessions => render_sessions_overlay(app, frame, area),
         FocusTarget::Workstreams => render_workstreams_overlay(app, frame, area),
         FocusTarget::CommandPalette => render_command_palette(app, frame, area),
+        FocusTarget::MessageSearch => render_search_overlay(&app.message_search, frame, area),
+        FocusTarget::Plugins => render_plugins_overlay(&app.plugin_panel, frame, area),
         _ => {}
     }
 
@@ -258,7 +262,7 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
             section
         )
     } else {
-        "^K palette │ ^W sidebar │ ^E tools │ ^L logs │ ^Q quit".to_string()
+        "^K palette │ ^F search │ ^W sidebar │ ^E tools │ ^L logs │ ^Q quit".to_string()
     };
 
     // Build right side with context info and connection status
@@ -271,6 +275,14 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
         right_spans.push(Span::raw(" "));
     }
 
+    // Show who else is on the session
+    if app.session_viewers.len() > 1 {
+        right_spans.push(Span::styled(
+            format!("{} viewing ", app.session_viewers.len()),
+            Style::default().fg(theme::TEXT_SECONDARY),
+        ));
+    }
+
     // Show connection status if not connected
     match app.connection_status {
         ConnectionStatus::Connected => {}
@@ -6,6 +6,8 @@ pub mod input;
 mod layout;
 pub mod logs;
 pub mod palette;
+pub mod plugins;
+pub mod search;
 pub mod sessions;
 pub mod sidebar;
 pub mod theme;
@@ -0,0 +1,235 @@
+//! Plugin panel overlay rendering.
+
+use super::theme;
+use crate::plugins::PluginPanel;
+use arawn_client::PluginInfo;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Render the plugin panel overlay.
+pub fn render_plugins_overlay(panel: &PluginPanel, frame: &mut Frame, area: Rect) {
+    // Create centered overlay (70% width, 70% height)
+    let overlay_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" plugins ")
+        .borders(Borders::ALL)
+        .border_style(theme::border_focused());
+
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Min(3),    // List
+        Constraint::Length(1), // Separator
+        Constraint::Length(5), // Details
+        Constraint::Length(1), // Footer
+    ])
+    .split(inner);
+
+    render_list(panel, frame, chunks[0]);
+    render_separator(frame, chunks[1]);
+    render_details(panel, frame, chunks[2]);
+    render_footer(frame, chunks[3]);
+}
+
+/// Render the plugin list, one line per plugin.
+fn render_list(panel: &PluginPanel, frame: &mut Frame, area: Rect) {
+    let mut lines = Vec::new();
+
+    if let Some(error) = panel.error() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            theme::warning_banner(),
+        )));
+    }
+
+    if panel.is_loading() {
+        lines.push(Line::from(Span::styled(
+            "  Loading plugins...",
+            theme::empty_state(),
+        )));
+    } else if panel.plugins().is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No plugins installed",
+            theme::empty_state(),
+        )));
+    } else {
+        // Keep the selection visible when the list is taller than the area
+        let per_page = (area.height as usize).saturating_sub(lines.len()).max(1);
+        let first = panel.selected_index().saturating_sub(per_page - 1);
+        for (i, plugin) in panel
+            .plugins()
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(per_page)
+        {
+            let is_busy = panel.busy() == Some(plugin.id.as_str());
+            lines.push(format_plugin(plugin, i == panel.selected_index(), is_busy));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Format a plugin's list line: state marker, name, version, source and counts.
+fn format_plugin(plugin: &PluginInfo, is_selected: bool, is_busy: bool) -> Line<'static> {
+    let prefix = if is_selected {
+        Span::styled(" > ", Style::default().fg(theme::ACCENT))
+    } else {
+        Span::raw("   ")
+    };
+    let (marker, marker_style) = if is_busy {
+        ("…", Style::default().fg(theme::ACCENT))
+    } else if plugin.error.is_some() {
+        ("!", Style::default().fg(theme::ERR))
+    } else if plugin.enabled {
+        ("●", Style::default().fg(theme::OK))
+    } else {
+        ("○", theme::list_item_dim())
+    };
+    let name_style = if is_selected {
+        theme::selected()
+    } else if plugin.enabled {
+        theme::list_item()
+    } else {
+        theme::list_item_dim()
+    };
+
+    let mut counts = Vec::new();
+    for (n, what) in [
+        (plugin.skills.len(), "skills"),
+        (plugin.agents.len(), "agents"),
+        (plugin.hooks, "hooks"),
+        (plugin.mcp_servers.len(), "mcp"),
+    ] {
+        if n > 0 {
+            counts.push(format!("{} {}", n, what));
+        }
+    }
+
+    let mut spans = vec![
+        prefix,
+        Span::styled(format!("{} ", marker), marker_style),
+        Span::styled(plugin.name.clone(), name_style),
+    ];
+    if let Some(ref version) = plugin.version {
+        spans.push(Span::styled(
+            format!(" v{}", version),
+            theme::list_item_dim(),
+        ));
+    }
+    spans.push(Span::styled(
+        format!("  {}", plugin.source),
+        theme::list_item_dim(),
+    ));
+    if !counts.is_empty() {
+        spans.push(Span::styled(
+            format!(" · {}", counts.join(", ")),
+            theme::list_item_dim(),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Render details of the selected plugin: description, origin and problems.
+fn render_details(panel: &PluginPanel, frame: &mut Frame, area: Rect) {
+    let Some(plugin) = panel.selected_plugin() else {
+        return;
+    };
+
+    let mut lines = Vec::new();
+    if let Some(ref description) = plugin.description {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", description),
+            theme::list_item(),
+        )));
+    }
+
+    let origin = match (&plugin.repo, &plugin.url, &plugin.path) {
+        (Some(repo), _, _) => repo.clone(),
+        (None, Some(url), _) => url.clone(),
+        (None, None, Some(path)) => path.clone(),
+        (None, None, None) => plugin.id.clone(),
+    };
+    let mut origin_line = format!("  {}", origin);
+    if let Some(ref git_ref) = plugin.git_ref {
+        origin_line.push_str(&format!(" @ {}", git_ref));
+    }
+    if let Some(ref commit) = plugin.commit {
+        origin_line.push_str(&format!(" ({})", commit.get(..8).unwrap_or(commit)));
+    }
+    lines.push(Line::from(Span::styled(
+        origin_line,
+        theme::list_item_dim(),
+    )));
+
+    if let Some(ref error) = plugin.error {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(theme::ERR),
+        )));
+    }
+    for warning in plugin.capabilities.iter().flat_map(|c| c.warnings.iter()) {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", warning),
+            Style::default().fg(theme::WARN),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// Render a separator line.
+fn render_separator(frame: &mut Frame, area: Rect) {
+    let sep = Paragraph::new(Line::from(Span::styled(
+        "─".repeat(area.width as usize),
+        theme::separator(),
+    )));
+    frame.render_widget(sep, area);
+}
+
+/// Render the footer with keyboard hints.
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let spans = vec![
+        Span::styled("  ↑↓", theme::key_hint()),
+        Span::styled(" navigate", theme::key_desc()),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("enter", theme::key_hint()),
+        Span::styled(" enable/disable", theme::key_desc()),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("s", theme::key_hint()),
+        Span::styled(" sync", theme::key_desc()),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("r", theme::key_hint()),
+        Span::styled(" refresh", theme::key_desc()),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("esc", theme::key_hint()),
+        Span::styled(" close", theme::key_desc()),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Create a centered rectangle within the given area.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(area);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
@@ -0,0 +1,195 @@
+//! Message search overlay rendering.
+
+use super::theme;
+use crate::search::MessageSearch;
+use arawn_client::MessageSearchHit;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Render the message search overlay.
+pub fn render_search_overlay(search: &MessageSearch, frame: &mut Frame, area: Rect) {
+    // Create centered overlay (70% width, 70% height)
+    let overlay_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" search messages ")
+        .borders(Borders::ALL)
+        .border_style(theme::border_focused());
+
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Search
+        Constraint::Length(1), // Separator
+        Constraint::Min(3),    // Results
+        Constraint::Length(1), // Footer
+    ])
+    .split(inner);
+
+    render_search_box(search, frame, chunks[0]);
+    render_separator(frame, chunks[1]);
+    render_results(search, frame, chunks[2]);
+    render_footer(search, frame, chunks[3]);
+}
+
+/// Render the query box.
+fn render_search_box(search: &MessageSearch, frame: &mut Frame, area: Rect) {
+    let query = search.query();
+    let line = if query.is_empty() {
+        Line::from(Span::styled(
+            " > search past conversations...",
+            theme::search_prompt(),
+        ))
+    } else {
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(theme::ACCENT)),
+            Span::styled(query.to_string(), theme::user_text()),
+        ])
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// Render a separator line.
+fn render_separator(frame: &mut Frame, area: Rect) {
+    let sep = Paragraph::new(Line::from(Span::styled(
+        "─".repeat(area.width as usize),
+        theme::separator(),
+    )));
+    frame.render_widget(sep, area);
+}
+
+/// Render the result list, two lines per hit.
+fn render_results(search: &MessageSearch, frame: &mut Frame, area: Rect) {
+    let mut lines = Vec::new();
+
+    if search.is_loading() {
+        lines.push(Line::from(Span::styled(
+            "  Searching...",
+            theme::empty_state(),
+        )));
+    } else if let Some(error) = search.error() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            theme::warning_banner(),
+        )));
+    } else if !search.has_searched() {
+        lines.push(Line::from(Span::styled(
+            "  Type a query and press Enter",
+            theme::empty_state(),
+        )));
+    } else if search.hits().is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No matching messages",
+            theme::empty_state(),
+        )));
+    } else {
+        // Keep the selection visible when the list is taller than the area
+        let per_page = (area.height as usize / 2).max(1);
+        let first = search.selected_index().saturating_sub(per_page - 1);
+        for (i, hit) in search.hits().iter().enumerate().skip(first).take(per_page) {
+            let is_selected = i == search.selected_index();
+            lines.push(format_hit_header(hit, is_selected));
+            lines.push(format_snippet(hit, area.width as usize));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Format the workstream / role / time line for a hit.
+fn format_hit_header(hit: &MessageSearchHit, is_selected: bool) -> Line<'static> {
+    let prefix = if is_selected {
+        Span::styled(" > ", Style::default().fg(theme::ACCENT))
+    } else {
+        Span::raw("   ")
+    };
+    let title_style = if is_selected {
+        theme::selected()
+    } else {
+        theme::list_item()
+    };
+    let when = hit.timestamp.get(..16).unwrap_or(&hit.timestamp);
+
+    Line::from(vec![
+        prefix,
+        Span::styled(hit.workstream_title.clone(), title_style),
+        Span::styled(
+            format!("  {} · {}", hit.role, when.replace('T', " ")),
+            theme::list_item_dim(),
+        ),
+    ])
+}
+
+/// Format the snippet line, rendering `**term**` markers as bold.
+fn format_snippet(hit: &MessageSearchHit, width: usize) -> Line<'static> {
+    let flat = hit.snippet.replace('\n', " ");
+    let mut spans = vec![Span::raw("     ")];
+    let mut remaining = width.saturating_sub(6);
+    for (i, part) in flat.split("**").enumerate() {
+        if remaining == 0 {
+            break;
+        }
+        let text: String = part.chars().take(remaining).collect();
+        remaining -= text.chars().count();
+        let style = if i % 2 == 1 {
+            theme::list_item().add_modifier(Modifier::BOLD)
+        } else {
+            theme::list_item_dim()
+        };
+        spans.push(Span::styled(text, style));
+    }
+    Line::from(spans)
+}
+
+/// Render the footer with keyboard hints and the match count.
+fn render_footer(search: &MessageSearch, frame: &mut Frame, area: Rect) {
+    let mut spans = vec![
+        Span::styled("  ↑↓", theme::key_hint()),
+        Span::styled(" navigate", theme::key_desc()),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("enter", theme::key_hint()),
+        Span::styled(
+            if search.is_stale() {
+                " search"
+            } else {
+                " jump"
+            },
+            theme::key_desc(),
+        ),
+        Span::styled(" │ ", theme::separator()),
+        Span::styled("esc", theme::key_hint()),
+        Span::styled(" close", theme::key_desc()),
+    ];
+    if search.has_searched() && !search.is_loading() {
+        spans.push(Span::styled(" │ ", theme::separator()));
+        spans.push(Span::styled(
+            format!("{} of {}", search.hits().len(), search.total()),
+            theme::key_desc(),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Create a centered rectangle within the given area.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(area);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
@@ -0,0 +1,186 @@
+//! Search over past conversation history.
+//!
+//! Defines the [`MessageSearcher`] trait through which the agent recalls
+//! earlier conversations across workstreams, along with the query and result
+//! types shared by the server and CLI. The trait lives here so both
+//! `arawn-agent` (consumer) and `arawn-workstream` (full-text index) can
+//! reference it without circular dependencies.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Default number of hits returned by a search.
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Errors from message search.
+#[derive(Debug, Error)]
+pub enum MessageSearchError {
+    /// The query has no searchable terms.
+    #[error("Search query is empty")]
+    EmptyQuery,
+
+    /// The search index is not available.
+    #[error("Message search is not available")]
+    Unavailable,
+
+    /// The index failed to read or update.
+    #[error("Search index error: {0}")]
+    Index(String),
+}
+
+/// A full-text query over message history, with optional facet filters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchQuery {
+    /// Words to match. All terms must appear; a trailing `*` matches a prefix.
+    pub text: String,
+    /// Only messages in this workstream.
+    #[serde(default)]
+    pub workstream_id: Option<String>,
+    /// Only messages in this session.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Only messages with one of these roles (e.g. `user`, `assistant`).
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// Only messages in workstreams carrying one of these tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only messages at or after this time.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages at or before this time.
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of hits.
+    pub limit: usize,
+    /// Number of hits to skip.
+    #[serde(default)]
+    pub offset: usize,
+}
+
+impl MessageSearchQuery {
+    /// Create a query for the given text with no filters.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            workstream_id: None,
+            session_id: None,
+            roles: Vec::new(),
+            tags: Vec::new(),
+            since: None,
+            until: None,
+            limit: DEFAULT_SEARCH_LIMIT,
+            offset: 0,
+        }
+    }
+
+    /// Restrict to a workstream.
+    pub fn with_workstream(mut self, workstream_id: impl Into<String>) -> Self {
+        self.workstream_id = Some(workstream_id.into());
+        self
+    }
+
+    /// Restrict to a session.
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Restrict to a role. May be called more than once.
+    pub fn with_role(mut self, role: impl Into<String>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    /// Restrict to workstreams with a tag. May be called more than once.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Restrict to a time window.
+    pub fn with_time_range(
+        mut self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    /// Set the page size and offset.
+    pub fn with_page(mut self, limit: usize, offset: usize) -> Self {
+        self.limit = limit;
+        self.offset = offset;
+        self
+    }
+}
+
+/// A message matching a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchHit {
+    pub message_id: String,
+    pub workstream_id: String,
+    pub workstream_title: String,
+    pub session_id: Option<String>,
+    pub role: String,
+    pub timestamp: DateTime<Utc>,
+    /// Excerpt around the match, with matched terms wrapped in `**`.
+    pub snippet: String,
+    /// Relevance score; higher is better.
+    pub score: f64,
+}
+
+/// Hit counts per facet value across all matches, before paging.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageSearchFacets {
+    pub roles: BTreeMap<String, usize>,
+    pub workstreams: BTreeMap<String, usize>,
+}
+
+/// A page of search results.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageSearchResults {
+    pub hits: Vec<MessageSearchHit>,
+    /// Total matches across all pages.
+    pub total: usize,
+    pub facets: MessageSearchFacets,
+}
+
+/// Full-text search over conversation history.
+pub trait MessageSearcher: Send + Sync {
+    /// Run a query, bringing the index up to date first.
+    fn search_messages(
+        &self,
+        query: &MessageSearchQuery,
+    ) -> Result<MessageSearchResults, MessageSearchError>;
+}
+
+/// Type alias for a shared message searcher.
+pub type SharedMessageSearcher = Arc<dyn MessageSearcher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_builder() {
+        let query = MessageSearchQuery::new("deploy")
+            .with_workstream("ws-1")
+            .with_role("user")
+            .with_role("assistant")
+            .with_tag("infra")
+            .with_page(5, 10);
+        assert_eq!(query.text, "deploy");
+        assert_eq!(query.workstream_id.as_deref(), Some("ws-1"));
+        assert_eq!(query.roles, vec!["user", "assistant"]);
+        assert_eq!(query.tags, vec!["infra"]);
+        assert_eq!((query.limit, query.offset), (5, 10));
+        assert_eq!(MessageSearchQuery::new("x").limit, DEFAULT_SEARCH_LIMIT);
+    }
+}
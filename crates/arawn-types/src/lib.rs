@@ -3,8 +3,12 @@
 pub mod config;
 pub mod delegation;
 pub mod fs_gate;
+pub mod history;
 pub mod hooks;
 pub mod secret_resolver;
+pub mod settings;
+pub mod skills;
+pub mod usage;
 
 pub use delegation::{
     DelegationOutcome, SharedSubagentSpawner, SubagentInfo, SubagentResult, SubagentSpawner,
@@ -12,6 +16,10 @@ pub use delegation::{
 pub use fs_gate::{
     FsGate, FsGateError, FsGateResolver, GATED_TOOLS, SandboxOutput, SharedFsGate, is_gated_tool,
 };
+pub use history::{
+    DEFAULT_SEARCH_LIMIT, MessageSearchError, MessageSearchFacets, MessageSearchHit,
+    MessageSearchQuery, MessageSearchResults, MessageSearcher, SharedMessageSearcher,
+};
 pub use hooks::{
     HookAction, HookDef, HookDispatch, HookEvent, HookMatcherGroup, HookOutcome, HookType,
     HooksConfig, SharedHookDispatcher,
@@ -20,6 +28,9 @@ pub use secret_resolver::{
     SecretResolver, SharedSecretResolver, contains_secret_handle, extract_secret_name,
     resolve_handles_in_json, resolve_handles_in_string,
 };
+pub use settings::{AgentSettings, MemoryScope};
+pub use skills::{ExpandedSkill, SharedSkillExpander, SkillExpander};
+pub use usage::{BudgetStatus, SharedUsageRecorder, UsageEntry, UsageError, UsageRecorder};
 
 pub use config::{
     AgentConfigProvider, ConfigProvider, HasAgentConfig, HasRateLimitConfig, HasSessionConfig,
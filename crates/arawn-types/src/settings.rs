@@ -0,0 +1,164 @@
+//! Per-workstream agent settings.
+//!
+//! An [`AgentSettings`] record is stored on each workstream and copied into
+//! the metadata of every session that runs in it. The agent reads it at the
+//! start of a turn to pick the model and LLM profile, extend the system
+//! prompt, restrict the tool set and scope active recall. It lives here so
+//! `arawn-workstream` (storage), `arawn-agent` (consumer) and the server can
+//! share it without circular dependencies.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Which memories active recall may draw from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// Recall from every indexed session (default).
+    #[default]
+    All,
+    /// Recall only memories indexed from the current session.
+    Session,
+    /// Disable active recall.
+    None,
+}
+
+impl MemoryScope {
+    /// The scope's config/API name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Session => "session",
+            Self::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for MemoryScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MemoryScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "session" => Ok(Self::Session),
+            "none" | "off" => Ok(Self::None),
+            other => Err(format!(
+                "Unknown memory scope '{}'. Valid: all, session, none",
+                other
+            )),
+        }
+    }
+}
+
+/// Agent settings attached to a workstream and inherited by its sessions.
+///
+/// Every field is optional; the default value leaves the agent's global
+/// configuration untouched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentSettings {
+    /// Model identifier sent to the backend instead of the agent default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// LLM profile (`[llm.<name>]`) used as the routing hint for the router.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Extra instructions appended to the system prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Files, relative to the workstream's `production/` directory, whose
+    /// contents are appended to the system prompt.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap_files: Vec<String>,
+    /// Tools the agent may use. Empty allows every registered tool.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    /// Tools the agent may never use. Takes precedence over `allowed_tools`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub denied_tools: Vec<String>,
+    /// Which memories active recall may draw from.
+    pub memory_scope: MemoryScope,
+    /// Domains sandboxed shell commands may reach. Empty means no network.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub network_domains: Vec<String>,
+}
+
+impl AgentSettings {
+    /// Whether these settings change nothing.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the allow/deny lists restrict the tool set at all.
+    pub fn restricts_tools(&self) -> bool {
+        !self.allowed_tools.is_empty() || !self.denied_tools.is_empty()
+    }
+
+    /// Whether the tool `name` may be used under these settings.
+    pub fn allows_tool(&self, name: &str) -> bool {
+        if self.denied_tools.iter().any(|t| t == name) {
+            return false;
+        }
+        self.allowed_tools.is_empty() || self.allowed_tools.iter().any(|t| t == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_tool() {
+        let open = AgentSettings::default();
+        assert!(open.allows_tool("shell"));
+        assert!(!open.restricts_tools());
+
+        let settings = AgentSettings {
+            allowed_tools: vec!["shell".into(), "file_read".into()],
+            denied_tools: vec!["shell".into()],
+            ..Default::default()
+        };
+        assert!(settings.restricts_tools());
+        assert!(!settings.allows_tool("shell"));
+        assert!(settings.allows_tool("file_read"));
+        assert!(!settings.allows_tool("web_fetch"));
+    }
+
+    #[test]
+    fn test_serde_omits_unset_fields() {
+        let settings = AgentSettings {
+            profile: Some("fast".into()),
+            memory_scope: MemoryScope::Session,
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"profile": "fast", "memory_scope": "session"})
+        );
+
+        let parsed: AgentSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, settings);
+        let empty: AgentSettings = serde_json::from_str("{}").unwrap();
+        assert!(empty.is_default());
+    }
+
+    #[test]
+    fn test_memory_scope_from_str() {
+        assert_eq!("all".parse::<MemoryScope>().unwrap(), MemoryScope::All);
+        assert_eq!(
+            "Session".parse::<MemoryScope>().unwrap(),
+            MemoryScope::Session
+        );
+        assert_eq!("off".parse::<MemoryScope>().unwrap(), MemoryScope::None);
+        assert!("workstream".parse::<MemoryScope>().is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! Skill expansion trait.
+//!
+//! Skills are plugin-provided prompt templates invoked with `/skill args`
+//! or `/plugin:skill args`. The registry lives in `arawn-plugin`; the trait
+//! is defined here so `arawn-agent` can expand invocations without
+//! depending on it.
+
+use std::sync::Arc;
+
+/// A user message rewritten by a skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedSkill {
+    /// Qualified skill name (`plugin:skill`).
+    pub name: String,
+    /// The rendered skill body, sent in place of the user's message.
+    pub prompt: String,
+    /// LLM routing hint to apply while the skill runs.
+    pub routing_hint: Option<String>,
+}
+
+/// Expands skill invocations in user messages.
+pub trait SkillExpander: Send + Sync {
+    /// Expand `message` if it invokes a known skill.
+    ///
+    /// Returns `None` for ordinary messages and unknown skills.
+    fn expand(&self, message: &str) -> Option<ExpandedSkill>;
+}
+
+/// Shared skill expander type.
+pub type SharedSkillExpander = Arc<dyn SkillExpander>;
@@ -0,0 +1,118 @@
+//! Token usage accounting and spend limits.
+//!
+//! Defines the [`UsageRecorder`] trait through which the agent reports the
+//! token usage and cost of each LLM call, and checks workstream spend limits
+//! before making the next one. The trait lives here so both `arawn-agent`
+//! (consumer) and `arawn-workstream` (SQLite ledger) can reference it without
+//! circular dependencies.
+
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors from usage recording.
+#[derive(Debug, Error)]
+pub enum UsageError {
+    /// The usage store failed to read or write.
+    #[error("Usage storage error: {0}")]
+    Storage(String),
+}
+
+/// Token usage and cost of a single LLM call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageEntry {
+    /// Session the call was made in.
+    pub session_id: String,
+    /// Workstream the session belongs to, if any.
+    pub workstream_id: Option<String>,
+    /// LLM profile that served the call (e.g. `default`, `fast`).
+    pub profile: String,
+    /// Model identifier sent to the backend.
+    pub model: String,
+    /// Input tokens billed.
+    pub input_tokens: u32,
+    /// Output tokens billed.
+    pub output_tokens: u32,
+    /// Tokens written to the prompt cache.
+    pub cache_creation_input_tokens: u32,
+    /// Tokens read from the prompt cache.
+    pub cache_read_input_tokens: u32,
+    /// Cost of the call in USD (0 when the model has no configured price).
+    pub cost_usd: f64,
+}
+
+/// Spend state of a workstream relative to its configured limits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BudgetStatus {
+    /// No limit configured, or spend is below the soft limit.
+    WithinLimit,
+    /// Spend reached the soft limit — calls continue but a warning is raised.
+    SoftLimitExceeded { spent_usd: f64, limit_usd: f64 },
+    /// Spend reached the hard limit — no further LLM calls are made.
+    HardLimitExceeded { spent_usd: f64, limit_usd: f64 },
+}
+
+impl BudgetStatus {
+    /// Whether the hard limit has been reached.
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self, Self::HardLimitExceeded { .. })
+    }
+}
+
+impl fmt::Display for BudgetStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WithinLimit => write!(f, "within limit"),
+            Self::SoftLimitExceeded {
+                spent_usd,
+                limit_usd,
+            } => write!(f, "spent ${:.2} of ${:.2} soft limit", spent_usd, limit_usd),
+            Self::HardLimitExceeded {
+                spent_usd,
+                limit_usd,
+            } => write!(f, "spent ${:.2} of ${:.2} hard limit", spent_usd, limit_usd),
+        }
+    }
+}
+
+/// Sink for per-call usage that also enforces spend limits.
+pub trait UsageRecorder: Send + Sync {
+    /// Record the usage of one LLM call.
+    fn record(&self, entry: &UsageEntry) -> Result<(), UsageError>;
+
+    /// Current spend state of a workstream for the active limit period.
+    fn budget_status(&self, workstream_id: &str) -> BudgetStatus;
+}
+
+/// Type alias for a shared usage recorder.
+pub type SharedUsageRecorder = Arc<dyn UsageRecorder>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_status_display() {
+        let status = BudgetStatus::HardLimitExceeded {
+            spent_usd: 12.345,
+            limit_usd: 10.0,
+        };
+        assert!(status.is_exhausted());
+        assert_eq!(status.to_string(), "spent $12.35 of $10.00 hard limit");
+        assert!(!BudgetStatus::WithinLimit.is_exhausted());
+    }
+
+    #[test]
+    fn test_budget_status_serde() {
+        let status = BudgetStatus::SoftLimitExceeded {
+            spent_usd: 5.0,
+            limit_usd: 4.0,
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["status"], "soft_limit_exceeded");
+        assert_eq!(json["limit_usd"], 4.0);
+    }
+}
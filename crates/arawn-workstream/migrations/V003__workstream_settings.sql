@@ -0,0 +1,2 @@
+-- Per-workstream agent settings (JSON-encoded AgentSettings)
+ALTER TABLE workstreams ADD COLUMN settings TEXT;
@@ -0,0 +1,4 @@
+-- Lineage for forked sessions: the session a fork was copied from and the
+-- last message it kept.
+ALTER TABLE sessions ADD COLUMN parent_session_id TEXT;
+ALTER TABLE sessions ADD COLUMN forked_from_message_id TEXT;
@@ -0,0 +1,18 @@
+-- Full-text index over message content, rebuilt incrementally from the JSONL
+-- files (which remain the source of truth).
+CREATE VIRTUAL TABLE message_search USING fts5(
+    content,
+    message_id    UNINDEXED,
+    workstream_id UNINDEXED,
+    session_id    UNINDEXED,
+    role          UNINDEXED,
+    timestamp     UNINDEXED,
+    tokenize = 'porter unicode61'
+);
+
+-- How far into each workstream's messages.jsonl the index has read.
+CREATE TABLE message_search_state (
+    workstream_id TEXT    PRIMARY KEY,
+    byte_offset   INTEGER NOT NULL,
+    indexed_at    TEXT    NOT NULL
+);
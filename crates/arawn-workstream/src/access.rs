@@ -0,0 +1,517 @@
+//! Named API tokens and resource ownership.
+//!
+//! Tokens are stored as SHA-256 hashes; the secret is only shown once, when
+//! the token is created. Each token belongs to a user and carries a set of
+//! [`TokenScope`]s. Ownership of workstreams, sessions, notes and memories is
+//! recorded in a side table keyed by [`ResourceKind`] and ID, so records that
+//! are created lazily (like sessions) can be claimed up front.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::store::parse_dt;
+use crate::{Result, WorkstreamError};
+
+/// Prefix of every token secret, so leaked tokens are easy to spot.
+pub const TOKEN_PREFIX: &str = "arawn_";
+
+// ── Scopes ──────────────────────────────────────────────────────────────
+
+/// What a token is allowed to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenScope {
+    /// Read-only API access.
+    Read,
+    /// Chat and modify the user's own data.
+    Chat,
+    /// Everything, including every user's data.
+    Admin,
+    /// Add, remove and (dis)connect MCP servers.
+    McpManage,
+}
+
+impl TokenScope {
+    /// All scopes, in display order.
+    pub const ALL: [TokenScope; 4] = [Self::Read, Self::Chat, Self::Admin, Self::McpManage];
+
+    /// Scope name as used in the CLI and API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Chat => "chat",
+            Self::Admin => "admin",
+            Self::McpManage => "mcp-manage",
+        }
+    }
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::Read),
+            "chat" => Ok(Self::Chat),
+            "admin" => Ok(Self::Admin),
+            "mcp-manage" => Ok(Self::McpManage),
+            other => Err(format!(
+                "invalid scope '{}' (expected read, chat, admin or mcp-manage)",
+                other
+            )),
+        }
+    }
+}
+
+/// Whether `scopes` grant `scope` (`admin` grants everything).
+pub fn scopes_allow(scopes: &[TokenScope], scope: TokenScope) -> bool {
+    scopes.contains(&TokenScope::Admin) || scopes.contains(&scope)
+}
+
+// ── Tokens ──────────────────────────────────────────────────────────────
+
+/// A named API token (without its secret).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    /// Human-readable token name, unique across tokens.
+    pub name: String,
+    /// User the token acts as; owns what the token creates.
+    pub user: String,
+    pub scopes: Vec<TokenScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    /// Whether the token is neither revoked nor expired at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > now)
+    }
+
+    /// Whether the token grants `scope`.
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        scopes_allow(&self.scopes, scope)
+    }
+}
+
+/// Kind of resource an owner is recorded for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Workstream,
+    Session,
+    Note,
+    Memory,
+}
+
+impl ResourceKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Workstream => "workstream",
+            Self::Session => "session",
+            Self::Note => "note",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn generate_secret() -> String {
+    format!(
+        "{}{}{}",
+        TOKEN_PREFIX,
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+// ── Store ───────────────────────────────────────────────────────────────
+
+/// SQLite-backed store of API tokens and resource owners.
+///
+/// Thread-safe via internal `Mutex<Connection>`.
+pub struct AccessStore {
+    conn: Mutex<Connection>,
+}
+
+impl AccessStore {
+    /// Open (or create) the access database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::init(conn)
+    }
+
+    /// Open an in-memory store (for testing).
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS api_tokens (
+                id            TEXT PRIMARY KEY,
+                name          TEXT NOT NULL UNIQUE,
+                user          TEXT NOT NULL,
+                scopes        TEXT NOT NULL,
+                secret_hash   TEXT NOT NULL UNIQUE,
+                created_at    TEXT NOT NULL,
+                expires_at    TEXT,
+                revoked_at    TEXT,
+                last_used_at  TEXT
+            );
+            CREATE TABLE IF NOT EXISTS resource_owners (
+                kind          TEXT NOT NULL,
+                resource_id   TEXT NOT NULL,
+                owner         TEXT NOT NULL,
+                PRIMARY KEY (kind, resource_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_resource_owners_owner
+                ON resource_owners (kind, owner);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Create a token and return it with its secret.
+    ///
+    /// The secret is not stored and cannot be recovered later.
+    pub fn create_token(
+        &self,
+        name: &str,
+        user: &str,
+        scopes: &[TokenScope],
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(ApiToken, String)> {
+        if name.trim().is_empty() || user.trim().is_empty() {
+            return Err(WorkstreamError::InvalidOperation(
+                "token name and user must not be empty".to_string(),
+            ));
+        }
+        if scopes.is_empty() {
+            return Err(WorkstreamError::InvalidOperation(
+                "a token needs at least one scope".to_string(),
+            ));
+        }
+
+        let conn = self.conn.lock();
+        let exists: bool = conn
+            .query_row(
+                "SELECT 1 FROM api_tokens WHERE name = ?1",
+                params![name],
+                |_| Ok(true),
+            )
+            .optional()?
+            .unwrap_or(false);
+        if exists {
+            return Err(WorkstreamError::InvalidOperation(format!(
+                "a token named '{}' already exists",
+                name
+            )));
+        }
+
+        let mut scopes = scopes.to_vec();
+        scopes.sort_by_key(|s| TokenScope::ALL.iter().position(|a| a == s));
+        scopes.dedup();
+
+        let token = ApiToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            user: user.to_string(),
+            scopes,
+            created_at: Utc::now(),
+            expires_at,
+            revoked_at: None,
+            last_used_at: None,
+        };
+        let secret = generate_secret();
+        conn.execute(
+            "INSERT INTO api_tokens (id, name, user, scopes, secret_hash, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                token.id,
+                token.name,
+                token.user,
+                serde_json::to_string(&token.scopes)?,
+                hash_secret(&secret),
+                token.created_at.to_rfc3339(),
+                token.expires_at.map(|t| t.to_rfc3339()),
+            ],
+        )?;
+        Ok((token, secret))
+    }
+
+    /// All tokens, revoked and expired ones included, oldest first.
+    pub fn list_tokens(&self) -> Result<Vec<ApiToken>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, user, scopes, created_at, expires_at, revoked_at, last_used_at
+             FROM api_tokens ORDER BY created_at, name",
+        )?;
+        let rows = stmt.query_map([], row_to_token)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// Revoke a token by ID or name.
+    pub fn revoke_token(&self, id_or_name: &str) -> Result<ApiToken> {
+        let conn = self.conn.lock();
+        let changed = conn.execute(
+            "UPDATE api_tokens SET revoked_at = ?2
+             WHERE (id = ?1 OR name = ?1) AND revoked_at IS NULL",
+            params![id_or_name, Utc::now().to_rfc3339()],
+        )?;
+        let token = conn
+            .query_row(
+                "SELECT id, name, user, scopes, created_at, expires_at, revoked_at, last_used_at
+                 FROM api_tokens WHERE id = ?1 OR name = ?1",
+                params![id_or_name],
+                row_to_token,
+            )
+            .optional()?
+            .ok_or_else(|| WorkstreamError::NotFound(format!("token {}", id_or_name)))?;
+        if changed == 0 {
+            return Err(WorkstreamError::InvalidOperation(format!(
+                "token '{}' is already revoked",
+                token.name
+            )));
+        }
+        Ok(token)
+    }
+
+    /// Look up the active token for a secret, recording its use.
+    ///
+    /// Returns `None` for unknown, revoked and expired tokens.
+    pub fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>> {
+        if !secret.starts_with(TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let conn = self.conn.lock();
+        let token = conn
+            .query_row(
+                "SELECT id, name, user, scopes, created_at, expires_at, revoked_at, last_used_at
+                 FROM api_tokens WHERE secret_hash = ?1",
+                params![hash_secret(secret)],
+                row_to_token,
+            )
+            .optional()?;
+        let now = Utc::now();
+        match token {
+            Some(mut token) if token.is_active(now) => {
+                conn.execute(
+                    "UPDATE api_tokens SET last_used_at = ?2 WHERE id = ?1",
+                    params![token.id, now.to_rfc3339()],
+                )?;
+                token.last_used_at = Some(now);
+                Ok(Some(token))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // ── Ownership ───────────────────────────────────────────────────────
+
+    /// Record `owner` as the owner of a resource, replacing any previous owner.
+    pub fn set_owner(&self, kind: ResourceKind, id: &str, owner: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO resource_owners (kind, resource_id, owner) VALUES (?1, ?2, ?3)
+             ON CONFLICT (kind, resource_id) DO UPDATE SET owner = excluded.owner",
+            params![kind.as_str(), id, owner],
+        )?;
+        Ok(())
+    }
+
+    /// The owner of a resource, if one was recorded.
+    pub fn owner(&self, kind: ResourceKind, id: &str) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT owner FROM resource_owners WHERE kind = ?1 AND resource_id = ?2",
+                params![kind.as_str(), id],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// IDs of the resources of `kind` owned by `owner`.
+    pub fn owned(&self, kind: ResourceKind, owner: &str) -> Result<HashSet<String>> {
+        let conn = self.conn.lock();
+        let mut stmt =
+            conn.prepare("SELECT resource_id FROM resource_owners WHERE kind = ?1 AND owner = ?2")?;
+        let rows = stmt.query_map(params![kind.as_str(), owner], |row| row.get(0))?;
+        Ok(rows.collect::<std::result::Result<HashSet<_>, _>>()?)
+    }
+
+    /// Forget the owner of a deleted resource.
+    pub fn clear_owner(&self, kind: ResourceKind, id: &str) -> Result<()> {
+        self.conn.lock().execute(
+            "DELETE FROM resource_owners WHERE kind = ?1 AND resource_id = ?2",
+            params![kind.as_str(), id],
+        )?;
+        Ok(())
+    }
+}
+
+fn row_to_token(row: &rusqlite::Row<'_>) -> rusqlite::Result<ApiToken> {
+    let scopes: String = row.get(3)?;
+    let optional_dt = |idx: usize| -> rusqlite::Result<Option<DateTime<Utc>>> {
+        Ok(row.get::<_, Option<String>>(idx)?.map(|s| parse_dt(&s)))
+    };
+    Ok(ApiToken {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        user: row.get(2)?,
+        scopes: serde_json::from_str(&scopes).unwrap_or_default(),
+        created_at: parse_dt(&row.get::<_, String>(4)?),
+        expires_at: optional_dt(5)?,
+        revoked_at: optional_dt(6)?,
+        last_used_at: optional_dt(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_scope_parsing() {
+        for scope in TokenScope::ALL {
+            assert_eq!(scope.as_str().parse::<TokenScope>().unwrap(), scope);
+        }
+        assert!("write".parse::<TokenScope>().is_err());
+        assert!(scopes_allow(&[TokenScope::Admin], TokenScope::McpManage));
+        assert!(!scopes_allow(&[TokenScope::Read], TokenScope::Chat));
+    }
+
+    #[test]
+    fn test_create_and_authenticate() {
+        let store = AccessStore::open_in_memory().unwrap();
+        let (token, secret) = store
+            .create_token(
+                "laptop",
+                "alice",
+                &[TokenScope::Chat, TokenScope::Read, TokenScope::Chat],
+                None,
+            )
+            .unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.scopes, vec![TokenScope::Read, TokenScope::Chat]);
+
+        let found = store.authenticate(&secret).unwrap().unwrap();
+        assert_eq!(found.id, token.id);
+        assert_eq!(found.user, "alice");
+        assert!(found.last_used_at.is_some());
+
+        assert!(store.authenticate("arawn_wrong").unwrap().is_none());
+        assert!(store.authenticate("not-a-token").unwrap().is_none());
+
+        // The secret itself is never stored
+        let conn = store.conn.lock();
+        let stored: String = conn
+            .query_row("SELECT secret_hash FROM api_tokens", [], |r| r.get(0))
+            .unwrap();
+        assert_ne!(stored, secret);
+    }
+
+    #[test]
+    fn test_create_validation() {
+        let store = AccessStore::open_in_memory().unwrap();
+        store
+            .create_token("ci", "bot", &[TokenScope::Read], None)
+            .unwrap();
+        assert!(
+            store
+                .create_token("ci", "bot", &[TokenScope::Read], None)
+                .is_err()
+        );
+        assert!(store.create_token("other", "bot", &[], None).is_err());
+        assert!(
+            store
+                .create_token("", "bot", &[TokenScope::Read], None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_revoke_and_expiry() {
+        let store = AccessStore::open_in_memory().unwrap();
+        let (_, secret) = store
+            .create_token("a", "alice", &[TokenScope::Read], None)
+            .unwrap();
+        let revoked = store.revoke_token("a").unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert!(store.authenticate(&secret).unwrap().is_none());
+        assert!(store.revoke_token("a").is_err());
+        assert!(matches!(
+            store.revoke_token("missing"),
+            Err(WorkstreamError::NotFound(_))
+        ));
+
+        let (_, expired) = store
+            .create_token(
+                "b",
+                "bob",
+                &[TokenScope::Read],
+                Some(Utc::now() - Duration::hours(1)),
+            )
+            .unwrap();
+        assert!(store.authenticate(&expired).unwrap().is_none());
+
+        let tokens = store.list_tokens().unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens.iter().all(|t| !t.is_active(Utc::now())));
+    }
+
+    #[test]
+    fn test_ownership() {
+        let store = AccessStore::open_in_memory().unwrap();
+        store
+            .set_owner(ResourceKind::Workstream, "ws1", "alice")
+            .unwrap();
+        store
+            .set_owner(ResourceKind::Workstream, "ws2", "bob")
+            .unwrap();
+        store.set_owner(ResourceKind::Note, "ws1", "bob").unwrap();
+
+        assert_eq!(
+            store.owner(ResourceKind::Workstream, "ws1").unwrap(),
+            Some("alice".to_string())
+        );
+        assert_eq!(store.owner(ResourceKind::Session, "ws1").unwrap(), None);
+        assert_eq!(
+            store.owned(ResourceKind::Workstream, "bob").unwrap(),
+            HashSet::from(["ws2".to_string()])
+        );
+
+        store
+            .set_owner(ResourceKind::Workstream, "ws1", "bob")
+            .unwrap();
+        assert_eq!(
+            store.owned(ResourceKind::Workstream, "bob").unwrap().len(),
+            2
+        );
+        store.clear_owner(ResourceKind::Workstream, "ws1").unwrap();
+        assert_eq!(store.owner(ResourceKind::Workstream, "ws1").unwrap(), None);
+    }
+}
@@ -1,7 +1,8 @@
 //! Scheduled cleanup tasks for workstream management.
 //!
-//! Provides functions for cleaning up inactive scratch sessions and monitoring
-//! disk pressure. These are designed to be used with cloacina's scheduler.
+//! Provides functions for cleaning up inactive scratch sessions, pruning
+//! production snapshots and monitoring disk pressure. These are designed to be
+//! used with cloacina's scheduler.
 
 use std::sync::Arc;
 
@@ -10,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
 use crate::WorkstreamManager;
-use crate::directory::{DirectoryManager, SCRATCH_WORKSTREAM};
+use crate::directory::{DirectoryManager, SCRATCH_WORKSTREAM, SnapshotRetention};
 
 /// Configuration for cleanup tasks.
 #[derive(Debug, Clone)]
@@ -21,6 +22,10 @@ pub struct CleanupConfig {
     pub total_usage_warning_bytes: u64,
     /// Per-workstream disk usage warning threshold in bytes.
     pub workstream_usage_warning_bytes: u64,
+    /// Snapshot retention applied to every workstream.
+    pub snapshot_retention: SnapshotRetention,
+    /// Snapshots kept for a workstream that is under disk pressure.
+    pub pressure_snapshot_keep: usize,
     /// Dry run mode - log actions but don't delete anything.
     pub dry_run: bool,
 }
@@ -31,6 +36,8 @@ impl Default for CleanupConfig {
             scratch_cleanup_days: 7,
             total_usage_warning_bytes: 10 * 1024 * 1024 * 1024, // 10 GB
             workstream_usage_warning_bytes: 2 * 1024 * 1024 * 1024, // 2 GB
+            snapshot_retention: SnapshotRetention::default(),
+            pressure_snapshot_keep: 3,
             dry_run: false,
         }
     }
@@ -51,6 +58,21 @@ pub struct CleanupResult {
     pub dry_run: bool,
 }
 
+/// Result of a snapshot pruning pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotCleanupResult {
+    /// Number of workstreams checked.
+    pub workstreams_checked: usize,
+    /// Number of snapshots removed.
+    pub snapshots_removed: usize,
+    /// Total bytes reclaimed from snapshot stores.
+    pub bytes_reclaimed: u64,
+    /// Workstreams pruned with the tighter disk-pressure retention.
+    pub pressured_workstreams: Vec<String>,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+}
+
 /// Disk pressure alert levels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -370,6 +392,87 @@ pub fn check_disk_pressure(
     result
 }
 
+/// Prune production snapshots across workstreams.
+///
+/// Every workstream gets `config.snapshot_retention`. Workstreams named in a
+/// pressure event from `pressure`, or all of them when total usage is
+/// critical, keep only `config.pressure_snapshot_keep` snapshots.
+pub fn prune_snapshots(
+    dir_manager: &DirectoryManager,
+    workstream_manager: &WorkstreamManager,
+    config: &CleanupConfig,
+    pressure: Option<&DiskPressureResult>,
+) -> SnapshotCleanupResult {
+    let mut result = SnapshotCleanupResult {
+        workstreams_checked: 0,
+        snapshots_removed: 0,
+        bytes_reclaimed: 0,
+        pressured_workstreams: Vec::new(),
+        dry_run: config.dry_run,
+    };
+
+    let workstreams = match workstream_manager.list_workstreams() {
+        Ok(ws) => ws,
+        Err(e) => {
+            warn!("Failed to list workstreams for snapshot pruning: {}", e);
+            return result;
+        }
+    };
+
+    let events = pressure.map(|p| p.events.as_slice()).unwrap_or_default();
+    let total_critical = events
+        .iter()
+        .any(|e| e.scope == "total" && e.level == PressureLevel::Critical);
+
+    for ws in workstreams {
+        if !dir_manager.workstream_exists(&ws.id) {
+            continue;
+        }
+        result.workstreams_checked += 1;
+
+        let pressured = total_critical || events.iter().any(|e| e.scope == ws.id);
+        let mut retention = config.snapshot_retention.clone();
+        if pressured {
+            retention.keep_last = retention.keep_last.min(config.pressure_snapshot_keep);
+            result.pressured_workstreams.push(ws.id.clone());
+        }
+
+        match dir_manager.prune_snapshots(&ws.id, &retention, config.dry_run) {
+            Ok(pruned) => {
+                if !pruned.removed.is_empty() {
+                    info!(
+                        workstream_id = %ws.id,
+                        removed = pruned.removed.len(),
+                        bytes = pruned.freed_bytes,
+                        pressured,
+                        dry_run = config.dry_run,
+                        "Pruned workstream snapshots"
+                    );
+                }
+                result.snapshots_removed += pruned.removed.len();
+                result.bytes_reclaimed += pruned.freed_bytes;
+            }
+            Err(e) => {
+                warn!(
+                    workstream_id = %ws.id,
+                    error = %e,
+                    "Failed to prune snapshots"
+                );
+            }
+        }
+    }
+
+    info!(
+        workstreams_checked = result.workstreams_checked,
+        snapshots_removed = result.snapshots_removed,
+        bytes_reclaimed = result.bytes_reclaimed,
+        dry_run = config.dry_run,
+        "Snapshot pruning completed"
+    );
+
+    result
+}
+
 /// Cleanup task context for cloacina integration.
 ///
 /// This struct holds references needed by cleanup tasks and can be stored
@@ -407,6 +510,17 @@ impl CleanupContext {
     pub fn run_disk_pressure_check(&self) -> DiskPressureResult {
         check_disk_pressure(&self.dir_manager, &self.workstream_manager, &self.config)
     }
+
+    /// Check disk pressure, then prune snapshots accordingly.
+    pub fn run_snapshot_prune(&self) -> SnapshotCleanupResult {
+        let pressure = self.run_disk_pressure_check();
+        prune_snapshots(
+            &self.dir_manager,
+            &self.workstream_manager,
+            &self.config,
+            Some(&pressure),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -623,6 +737,55 @@ mod tests {
         assert!(result.events.is_empty());
     }
 
+    #[test]
+    fn test_prune_snapshots_tightens_under_pressure() {
+        use crate::directory::SnapshotTrigger;
+
+        let dir = tempdir().unwrap();
+        let dir_manager = DirectoryManager::new(dir.path());
+        let ws_manager = test_workstream_manager(dir.path());
+        let ws = ws_manager.create_workstream("Docs", None, &[]).unwrap();
+        dir_manager.create_workstream(&ws.id).unwrap();
+
+        let prod = dir_manager.production_path(&ws.id);
+        for i in 0..5 {
+            std::fs::write(prod.join("a.md"), format!("v{i}")).unwrap();
+            dir_manager
+                .create_snapshot(&ws.id, SnapshotTrigger::Manual, None)
+                .unwrap();
+        }
+
+        let config = CleanupConfig {
+            snapshot_retention: SnapshotRetention {
+                keep_last: 4,
+                max_age_days: None,
+            },
+            pressure_snapshot_keep: 2,
+            ..Default::default()
+        };
+
+        let result = prune_snapshots(&dir_manager, &ws_manager, &config, None);
+        assert_eq!(result.workstreams_checked, 1);
+        assert_eq!(result.snapshots_removed, 1);
+        assert!(result.pressured_workstreams.is_empty());
+
+        let pressure = DiskPressureResult {
+            total_usage_bytes: 0,
+            workstream_usage: vec![],
+            events: vec![DiskPressureEvent::new(
+                PressureLevel::Warning,
+                &ws.id,
+                3000.0,
+                2000.0,
+            )],
+            timestamp: Utc::now(),
+        };
+        let result = prune_snapshots(&dir_manager, &ws_manager, &config, Some(&pressure));
+        assert_eq!(result.snapshots_removed, 2);
+        assert_eq!(result.pressured_workstreams, vec![ws.id.clone()]);
+        assert_eq!(dir_manager.list_snapshots(&ws.id).unwrap().len(), 2);
+    }
+
     #[test]
     fn test_workstream_usage_struct() {
         let usage = WorkstreamUsage {
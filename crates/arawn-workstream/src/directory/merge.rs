@@ -0,0 +1,256 @@
+//! Merging one workstream's files into another.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{
+    DirectoryError, DirectoryManager, DirectoryResult, FileConflictPolicy, FileMergeResult,
+    SnapshotTrigger,
+};
+
+impl DirectoryManager {
+    /// Moves the files in `from`'s `production/` and `work/` directories to
+    /// the same relative paths in `to`.
+    ///
+    /// Files that already exist in `to` are handled according to `policy`;
+    /// skipped files stay in `from`. When files are about to land in `to`'s
+    /// `production/`, a snapshot of it is taken first (see
+    /// [`DirectoryManager::snapshot_before_change`]). Directories emptied by
+    /// the move are removed.
+    ///
+    /// # Errors
+    ///
+    /// - `DirectoryError::InvalidName` if either workstream name is invalid.
+    /// - `DirectoryError::Io` if a file cannot be moved.
+    pub fn merge_files(
+        &self,
+        from: &str,
+        to: &str,
+        policy: FileConflictPolicy,
+    ) -> DirectoryResult<FileMergeResult> {
+        for name in [from, to] {
+            if !Self::is_valid_name(name) {
+                return Err(DirectoryError::InvalidName(name.to_string()));
+            }
+        }
+
+        let mut result = FileMergeResult::default();
+        if !self.workstream_exists(from) {
+            return Ok(result);
+        }
+        self.create_workstream(to)?;
+
+        for (src_root, dest_root, is_production) in [
+            (self.production_path(from), self.production_path(to), true),
+            (self.work_path(from), self.work_path(to), false),
+        ] {
+            let mut files = Vec::new();
+            if src_root.is_dir() {
+                collect_files(&src_root, &src_root, &mut files)?;
+            }
+            if files.is_empty() {
+                continue;
+            }
+
+            if is_production && let Err(e) = self.snapshot_before_change(to, SnapshotTrigger::Merge)
+            {
+                tracing::warn!(
+                    workstream = %to,
+                    error = %e,
+                    "Failed to snapshot production before merge"
+                );
+            }
+
+            for rel in files {
+                let src = src_root.join(&rel);
+                let original = dest_root.join(&rel);
+                let dest = if !original.exists() {
+                    result.moved += 1;
+                    original
+                } else {
+                    match policy {
+                        FileConflictPolicy::Skip => {
+                            result.skipped += 1;
+                            continue;
+                        }
+                        FileConflictPolicy::Overwrite if original.is_file() => {
+                            result.overwritten += 1;
+                            original
+                        }
+                        // A directory in the way can't be overwritten by a file
+                        FileConflictPolicy::Overwrite | FileConflictPolicy::Rename => {
+                            result.renamed += 1;
+                            Self::resolve_conflict(&original)
+                        }
+                    }
+                };
+
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                move_file(&src, &dest)?;
+            }
+
+            remove_empty_dirs(&src_root);
+        }
+
+        tracing::info!(
+            from = %from,
+            to = %to,
+            policy = %policy,
+            moved = result.moved,
+            renamed = result.renamed,
+            overwritten = result.overwritten,
+            skipped = result.skipped,
+            "Merged workstream files"
+        );
+
+        Ok(result)
+    }
+}
+
+/// Collects regular files under `dir` as paths relative to `root`, sorted.
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) -> DirectoryResult<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if file_type.is_file() {
+            out.push(path.strip_prefix(root).unwrap_or(&path).to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Moves a file, falling back to copy and delete across filesystems.
+fn move_file(src: &Path, dest: &Path) -> DirectoryResult<()> {
+    if fs::rename(src, dest).is_err() {
+        fs::copy(src, dest)?;
+        fs::remove_file(src)?;
+    }
+    Ok(())
+}
+
+/// Removes empty subdirectories of `dir`, keeping `dir` itself.
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            let _ = fs::remove_dir(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::super::{FileConflictPolicy, SnapshotTrigger};
+    use super::DirectoryManager;
+
+    fn setup() -> (tempfile::TempDir, DirectoryManager) {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DirectoryManager::new(dir.path());
+        manager.create_workstream("source").unwrap();
+        manager.create_workstream("target").unwrap();
+        (dir, manager)
+    }
+
+    fn write(manager: &DirectoryManager, ws: &str, rel: &str, content: &str) {
+        let path = manager.production_path(ws).join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_merge_files_moves_production_and_work() {
+        let (_dir, manager) = setup();
+        write(&manager, "source", "docs/a.md", "a");
+        fs::write(manager.work_path("source").join("notes.txt"), "n").unwrap();
+
+        let result = manager
+            .merge_files("source", "target", FileConflictPolicy::Rename)
+            .unwrap();
+
+        assert_eq!(result.moved, 2);
+        let prod = manager.production_path("target");
+        assert_eq!(fs::read_to_string(prod.join("docs/a.md")).unwrap(), "a");
+        assert!(manager.work_path("target").join("notes.txt").exists());
+        // Emptied source directories are cleaned up
+        assert!(!manager.production_path("source").join("docs").exists());
+    }
+
+    #[test]
+    fn test_merge_files_conflict_policies() {
+        for (policy, expected_target, source_kept) in [
+            (FileConflictPolicy::Rename, "target", false),
+            (FileConflictPolicy::Overwrite, "source", false),
+            (FileConflictPolicy::Skip, "target", true),
+        ] {
+            let (_dir, manager) = setup();
+            write(&manager, "source", "a.md", "source");
+            write(&manager, "target", "a.md", "target");
+
+            let result = manager.merge_files("source", "target", policy).unwrap();
+
+            let prod = manager.production_path("target");
+            assert_eq!(
+                fs::read_to_string(prod.join("a.md")).unwrap(),
+                expected_target
+            );
+            assert_eq!(
+                manager.production_path("source").join("a.md").exists(),
+                source_kept
+            );
+            match policy {
+                FileConflictPolicy::Rename => {
+                    assert_eq!(result.renamed, 1);
+                    assert_eq!(fs::read_to_string(prod.join("a(1).md")).unwrap(), "source");
+                }
+                FileConflictPolicy::Overwrite => assert_eq!(result.overwritten, 1),
+                FileConflictPolicy::Skip => assert_eq!(result.skipped, 1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_files_snapshots_target_production() {
+        let (_dir, manager) = setup();
+        write(&manager, "target", "a.md", "before");
+        write(&manager, "source", "a.md", "after");
+
+        manager
+            .merge_files("source", "target", FileConflictPolicy::Overwrite)
+            .unwrap();
+
+        let snapshots = manager.list_snapshots("target").unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].trigger, SnapshotTrigger::Merge);
+    }
+
+    #[test]
+    fn test_merge_files_missing_source_is_noop() {
+        let (_dir, manager) = setup();
+        let result = manager
+            .merge_files("absent", "target", FileConflictPolicy::Rename)
+            .unwrap();
+        assert_eq!(result, Default::default());
+    }
+
+    #[test]
+    fn test_conflict_policy_parse() {
+        assert_eq!(
+            "Overwrite".parse::<FileConflictPolicy>().unwrap(),
+            FileConflictPolicy::Overwrite
+        );
+        assert!("merge".parse::<FileConflictPolicy>().is_err());
+        assert_eq!(FileConflictPolicy::default().to_string(), "rename");
+    }
+}
@@ -9,7 +9,8 @@
 //! ~/.arawn/workstreams/
 //! ├── scratch/sessions/<session-id>/work/   # Isolated per-session
 //! ├── <workstream>/production/              # Shared deliverables
-//! └── <workstream>/work/                    # Shared working area
+//! ├── <workstream>/work/                    # Shared working area
+//! └── <workstream>/.snapshots/              # Versioned copies of production/
 //! ```
 //!
 //! # Access Rules
@@ -21,6 +22,8 @@
 
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Errors that can occur during directory operations.
@@ -65,6 +68,14 @@ pub enum DirectoryError {
     /// Session work directory does not exist.
     #[error("Session work directory does not exist: {0}")]
     SessionWorkNotFound(String),
+
+    /// Snapshot does not exist.
+    #[error("Snapshot not found: {0}")]
+    SnapshotNotFound(String),
+
+    /// Snapshot manifest or object is unreadable.
+    #[error("Corrupt snapshot {id}: {reason}")]
+    CorruptSnapshot { id: String, reason: String },
 }
 
 /// Result type for directory operations.
@@ -112,6 +123,176 @@ pub struct AttachResult {
     pub allowed_paths: Vec<PathBuf>,
 }
 
+/// What caused a snapshot to be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotTrigger {
+    /// Requested by a user or API call.
+    Manual,
+    /// Taken automatically before a file promotion.
+    Promote,
+    /// Taken automatically before restoring another snapshot.
+    Restore,
+    /// Taken automatically before another workstream's files are merged in.
+    Merge,
+}
+
+impl std::fmt::Display for SnapshotTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotTrigger::Manual => write!(f, "manual"),
+            SnapshotTrigger::Promote => write!(f, "promote"),
+            SnapshotTrigger::Restore => write!(f, "restore"),
+            SnapshotTrigger::Merge => write!(f, "merge"),
+        }
+    }
+}
+
+/// What to do when a merged file already exists in the target workstream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileConflictPolicy {
+    /// Keep both, giving the incoming file a suffix (e.g. `file(1).txt`).
+    #[default]
+    Rename,
+    /// Replace the target's file with the incoming one.
+    Overwrite,
+    /// Keep the target's file and leave the incoming one in the source.
+    Skip,
+}
+
+impl std::fmt::Display for FileConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileConflictPolicy::Rename => write!(f, "rename"),
+            FileConflictPolicy::Overwrite => write!(f, "overwrite"),
+            FileConflictPolicy::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+impl std::str::FromStr for FileConflictPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "rename" => Ok(FileConflictPolicy::Rename),
+            "overwrite" => Ok(FileConflictPolicy::Overwrite),
+            "skip" => Ok(FileConflictPolicy::Skip),
+            other => Err(format!(
+                "unknown conflict policy '{other}' (expected rename, overwrite or skip)"
+            )),
+        }
+    }
+}
+
+/// Result of merging one workstream's files into another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileMergeResult {
+    /// Files moved without a conflict.
+    pub moved: usize,
+    /// Files moved under a new name because the target already had one.
+    pub renamed: usize,
+    /// Target files replaced by incoming ones.
+    pub overwritten: usize,
+    /// Incoming files left in the source because of a conflict.
+    pub skipped: usize,
+}
+
+/// A file recorded in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Path relative to `production/`, with `/` separators.
+    pub path: String,
+    /// SHA-256 of the file contents (hex).
+    pub hash: String,
+    /// File size in bytes.
+    pub bytes: u64,
+}
+
+/// A point-in-time copy of a workstream's `production/` directory.
+///
+/// File contents live in a content-addressed object store shared by all
+/// snapshots of the workstream, so unchanged files cost nothing extra.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Snapshot ID (sortable by creation time).
+    pub id: String,
+    /// When the snapshot was taken.
+    pub created_at: DateTime<Utc>,
+    /// What caused the snapshot.
+    pub trigger: SnapshotTrigger,
+    /// Optional user-supplied label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Files in the snapshot, sorted by path.
+    pub files: Vec<SnapshotFile>,
+}
+
+impl Snapshot {
+    /// Total size of the snapshotted files in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.bytes).sum()
+    }
+}
+
+/// Differences between two states of `production/`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Paths present only in the newer state.
+    pub added: Vec<String>,
+    /// Paths present only in the older state.
+    pub removed: Vec<String>,
+    /// Paths whose contents changed.
+    pub modified: Vec<String>,
+}
+
+impl SnapshotDiff {
+    /// Whether the two states are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Result of restoring a snapshot.
+#[derive(Debug, Clone)]
+pub struct RestoreResult {
+    /// Number of files written back to `production/`.
+    pub files_restored: usize,
+    /// Number of files removed because the snapshot did not contain them.
+    pub files_removed: usize,
+    /// Snapshot of the pre-restore state, if `production/` had any files.
+    pub safety_snapshot: Option<String>,
+}
+
+/// How many snapshots to keep per workstream.
+#[derive(Debug, Clone)]
+pub struct SnapshotRetention {
+    /// Keep at most this many of the newest snapshots.
+    pub keep_last: usize,
+    /// Drop snapshots older than this many days. The newest snapshot is
+    /// always kept.
+    pub max_age_days: Option<i64>,
+}
+
+impl Default for SnapshotRetention {
+    fn default() -> Self {
+        Self {
+            keep_last: 20,
+            max_age_days: Some(30),
+        }
+    }
+}
+
+/// Result of pruning a workstream's snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotPruneResult {
+    /// IDs of the snapshots removed (or that would be, in a dry run).
+    pub removed: Vec<String>,
+    /// Bytes freed from the object store.
+    pub freed_bytes: u64,
+}
+
 /// Usage statistics for a single session.
 #[derive(Debug, Clone)]
 pub struct SessionUsage {
@@ -148,6 +329,8 @@ pub struct UsageStats {
     pub production_bytes: u64,
     /// Disk usage of the work/ directory in bytes.
     pub work_bytes: u64,
+    /// Disk usage of stored snapshots in bytes.
+    pub snapshot_bytes: u64,
     /// Per-session usage (only populated for scratch workstream).
     pub sessions: Vec<SessionUsage>,
     /// Total disk usage in bytes.
@@ -188,10 +371,15 @@ const WORK_DIR: &str = "work";
 /// Subdirectory for scratch sessions.
 const SESSIONS_DIR: &str = "sessions";
 
+/// Subdirectory for production snapshots.
+const SNAPSHOTS_DIR: &str = ".snapshots";
+
 mod clone;
 mod manager;
+mod merge;
 mod operations;
 mod session;
+mod snapshot;
 mod usage;
 
 // Re-export the main manager type and its associated types
@@ -3,14 +3,17 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::{DirectoryError, DirectoryManager, DirectoryResult, ExportResult, PromoteResult};
+use super::{
+    DirectoryError, DirectoryManager, DirectoryResult, ExportResult, PromoteResult, SnapshotTrigger,
+};
 
 impl DirectoryManager {
     /// Promotes a file from `work/` to `production/`.
     ///
     /// This moves a file from the workstream's work directory to its production
     /// directory. If a file already exists at the destination, a conflict suffix
-    /// is appended (e.g., `file(1).txt`, `file(2).txt`).
+    /// is appended (e.g., `file(1).txt`, `file(2).txt`). A snapshot of
+    /// `production/` is taken first (see [`DirectoryManager::snapshot_before_change`]).
     ///
     /// # Arguments
     ///
@@ -76,6 +79,15 @@ impl DirectoryManager {
             return Err(DirectoryError::NotAFile(src_full));
         }
 
+        // Keep the pre-promote state recoverable
+        if let Err(e) = self.snapshot_before_change(workstream, SnapshotTrigger::Promote) {
+            tracing::warn!(
+                workstream = %workstream,
+                error = %e,
+                "Failed to snapshot production before promote"
+            );
+        }
+
         // Create destination directory if needed
         if let Some(parent) = original_dest.parent() {
             fs::create_dir_all(parent)?;
@@ -116,7 +128,7 @@ impl DirectoryManager {
     ///
     /// Given `file.txt`, tries `file(1).txt`, `file(2).txt`, etc.
     /// until finding a path that doesn't exist.
-    fn resolve_conflict(path: &Path) -> PathBuf {
+    pub(super) fn resolve_conflict(path: &Path) -> PathBuf {
         let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
         let extension = path.extension().and_then(|s| s.to_str());
         let parent = path.parent().unwrap_or(Path::new(""));
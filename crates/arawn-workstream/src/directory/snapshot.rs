@@ -0,0 +1,657 @@
+//! Content-addressed snapshots of `production/`.
+//!
+//! Each workstream keeps its snapshots next to its other directories:
+//!
+//! ```text
+//! <workstream>/.snapshots/
+//! ├── objects/<aa>/<rest-of-sha256>   # File contents, stored once
+//! └── manifests/<snapshot-id>.json    # File list of one snapshot
+//! ```
+//!
+//! Objects are shared by all snapshots of a workstream, so taking a snapshot
+//! only costs the files that changed since the previous one.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::{
+    DirectoryError, DirectoryManager, DirectoryResult, RestoreResult, SNAPSHOTS_DIR, Snapshot,
+    SnapshotDiff, SnapshotFile, SnapshotPruneResult, SnapshotRetention, SnapshotTrigger,
+};
+
+/// Subdirectory holding file contents by hash.
+const OBJECTS_DIR: &str = "objects";
+
+/// Subdirectory holding one JSON manifest per snapshot.
+const MANIFESTS_DIR: &str = "manifests";
+
+impl DirectoryManager {
+    /// Returns the snapshot store path for a workstream.
+    pub fn snapshots_path(&self, workstream: &str) -> PathBuf {
+        self.workstream_path(workstream).join(SNAPSHOTS_DIR)
+    }
+
+    /// Takes a snapshot of the workstream's `production/` directory.
+    ///
+    /// # Errors
+    ///
+    /// - `DirectoryError::InvalidName` if the workstream name is invalid.
+    /// - `DirectoryError::WorkstreamNotFound` if the workstream doesn't exist.
+    /// - `DirectoryError::Io` if reading or storing files fails.
+    pub fn create_snapshot(
+        &self,
+        workstream: &str,
+        trigger: SnapshotTrigger,
+        label: Option<&str>,
+    ) -> DirectoryResult<Snapshot> {
+        self.check_snapshot_workstream(workstream)?;
+
+        let files = self.scan_production(workstream)?;
+        self.store_objects(workstream, &files)?;
+
+        let created_at = Utc::now();
+        let snapshot = Snapshot {
+            id: format!(
+                "{}-{}",
+                created_at.format("%Y%m%dT%H%M%S%3fZ"),
+                &uuid::Uuid::new_v4().simple().to_string()[..8]
+            ),
+            created_at,
+            trigger,
+            label: label.map(str::to_string),
+            files,
+        };
+
+        let manifests = self.snapshots_path(workstream).join(MANIFESTS_DIR);
+        fs::create_dir_all(&manifests)?;
+        let json = serde_json::to_vec_pretty(&snapshot).map_err(io::Error::other)?;
+        fs::write(manifests.join(format!("{}.json", snapshot.id)), json)?;
+
+        tracing::info!(
+            workstream = %workstream,
+            snapshot = %snapshot.id,
+            trigger = %trigger,
+            files = snapshot.files.len(),
+            bytes = snapshot.total_bytes(),
+            "Created production snapshot"
+        );
+
+        Ok(snapshot)
+    }
+
+    /// Snapshots `production/` ahead of an automatic change.
+    ///
+    /// Returns `None` when `production/` is empty. When nothing changed
+    /// since the latest snapshot, that snapshot is returned instead of
+    /// recording a duplicate.
+    pub fn snapshot_before_change(
+        &self,
+        workstream: &str,
+        trigger: SnapshotTrigger,
+    ) -> DirectoryResult<Option<Snapshot>> {
+        self.check_snapshot_workstream(workstream)?;
+
+        let current = self.scan_production(workstream)?;
+        if current.is_empty() {
+            return Ok(None);
+        }
+        if let Some(latest) = self.list_snapshots(workstream)?.into_iter().next()
+            && latest.files == current
+        {
+            return Ok(Some(latest));
+        }
+
+        self.create_snapshot(workstream, trigger, None).map(Some)
+    }
+
+    /// Lists a workstream's snapshots, newest first.
+    ///
+    /// Unreadable manifests are skipped with a warning.
+    pub fn list_snapshots(&self, workstream: &str) -> DirectoryResult<Vec<Snapshot>> {
+        self.check_snapshot_workstream(workstream)?;
+
+        let manifests = self.snapshots_path(workstream).join(MANIFESTS_DIR);
+        if !manifests.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&manifests)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            match Self::read_manifest(&path) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "Skipping unreadable snapshot manifest"
+                ),
+            }
+        }
+
+        snapshots.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+        Ok(snapshots)
+    }
+
+    /// Loads a single snapshot.
+    ///
+    /// # Errors
+    ///
+    /// - `DirectoryError::SnapshotNotFound` if no snapshot has this ID.
+    /// - `DirectoryError::CorruptSnapshot` if its manifest can't be parsed.
+    pub fn get_snapshot(&self, workstream: &str, id: &str) -> DirectoryResult<Snapshot> {
+        self.check_snapshot_workstream(workstream)?;
+
+        let valid_id = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        let path = self
+            .snapshots_path(workstream)
+            .join(MANIFESTS_DIR)
+            .join(format!("{id}.json"));
+        if !valid_id || !path.is_file() {
+            return Err(DirectoryError::SnapshotNotFound(id.to_string()));
+        }
+
+        Self::read_manifest(&path)
+    }
+
+    /// Compares a snapshot with another snapshot or with the current state.
+    ///
+    /// With `against` set, the diff describes the changes from `id` to
+    /// `against`; otherwise from `id` to the current `production/`.
+    pub fn diff_snapshot(
+        &self,
+        workstream: &str,
+        id: &str,
+        against: Option<&str>,
+    ) -> DirectoryResult<SnapshotDiff> {
+        let old = self.get_snapshot(workstream, id)?.files;
+        let new = match against {
+            Some(other) => self.get_snapshot(workstream, other)?.files,
+            None => self.scan_production(workstream)?,
+        };
+        Ok(diff_files(&old, &new))
+    }
+
+    /// Restores `production/` to the state recorded in a snapshot.
+    ///
+    /// The current state is snapshotted first, so a restore can itself be
+    /// undone. Files not present in the snapshot are removed.
+    ///
+    /// # Errors
+    ///
+    /// - `DirectoryError::SnapshotNotFound` if no snapshot has this ID.
+    /// - `DirectoryError::CorruptSnapshot` if any of its objects are missing.
+    pub fn restore_snapshot(&self, workstream: &str, id: &str) -> DirectoryResult<RestoreResult> {
+        let target = self.get_snapshot(workstream, id)?;
+
+        // Check everything is restorable before touching production/
+        for file in &target.files {
+            if !is_safe_relative(&file.path) {
+                return Err(DirectoryError::CorruptSnapshot {
+                    id: id.to_string(),
+                    reason: format!("invalid path {}", file.path),
+                });
+            }
+            if !self.object_path(workstream, &file.hash).is_file() {
+                return Err(DirectoryError::CorruptSnapshot {
+                    id: id.to_string(),
+                    reason: format!("missing contents of {}", file.path),
+                });
+            }
+        }
+
+        let safety_snapshot = self
+            .snapshot_before_change(workstream, SnapshotTrigger::Restore)?
+            .map(|s| s.id);
+
+        let prod_path = self.production_path(workstream);
+        let current: BTreeMap<String, String> = self
+            .scan_production(workstream)?
+            .into_iter()
+            .map(|f| (f.path, f.hash))
+            .collect();
+
+        let mut files_restored = 0;
+        for file in &target.files {
+            if current.get(&file.path) == Some(&file.hash) {
+                continue;
+            }
+            let dest = prod_path.join(&file.path);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.object_path(workstream, &file.hash), &dest)?;
+            files_restored += 1;
+        }
+
+        let wanted: HashSet<&str> = target.files.iter().map(|f| f.path.as_str()).collect();
+        let mut files_removed = 0;
+        for path in current.keys() {
+            if !wanted.contains(path.as_str()) {
+                fs::remove_file(prod_path.join(path))?;
+                files_removed += 1;
+            }
+        }
+        if files_removed > 0 {
+            remove_empty_dirs(&prod_path)?;
+        }
+
+        tracing::info!(
+            workstream = %workstream,
+            snapshot = %id,
+            files_restored,
+            files_removed,
+            safety_snapshot = ?safety_snapshot,
+            "Restored production snapshot"
+        );
+
+        Ok(RestoreResult {
+            files_restored,
+            files_removed,
+            safety_snapshot,
+        })
+    }
+
+    /// Removes old snapshots according to a retention policy.
+    ///
+    /// Objects no longer referenced by a remaining snapshot are deleted.
+    /// With `dry_run`, nothing is deleted but the result reports what would be.
+    pub fn prune_snapshots(
+        &self,
+        workstream: &str,
+        retention: &SnapshotRetention,
+        dry_run: bool,
+    ) -> DirectoryResult<SnapshotPruneResult> {
+        let snapshots = self.list_snapshots(workstream)?;
+        let cutoff = retention
+            .max_age_days
+            .map(|days| Utc::now() - Duration::days(days));
+
+        let (removed, kept): (Vec<_>, Vec<_>) =
+            snapshots.into_iter().enumerate().partition(|(i, s)| {
+                *i >= retention.keep_last
+                    || (*i > 0 && cutoff.is_some_and(|cutoff| s.created_at < cutoff))
+            });
+        if removed.is_empty() {
+            return Ok(SnapshotPruneResult::default());
+        }
+
+        let still_referenced: HashSet<&str> = kept
+            .iter()
+            .flat_map(|(_, s)| s.files.iter().map(|f| f.hash.as_str()))
+            .collect();
+        let mut orphaned: BTreeMap<&str, u64> = BTreeMap::new();
+        for (_, snapshot) in &removed {
+            for file in &snapshot.files {
+                if !still_referenced.contains(file.hash.as_str()) {
+                    orphaned.insert(file.hash.as_str(), file.bytes);
+                }
+            }
+        }
+
+        let result = SnapshotPruneResult {
+            removed: removed.iter().map(|(_, s)| s.id.clone()).collect(),
+            freed_bytes: orphaned.values().sum(),
+        };
+        if dry_run {
+            return Ok(result);
+        }
+
+        let manifests = self.snapshots_path(workstream).join(MANIFESTS_DIR);
+        for (_, snapshot) in &removed {
+            fs::remove_file(manifests.join(format!("{}.json", snapshot.id)))?;
+        }
+        for hash in orphaned.keys() {
+            let path = self.object_path(workstream, hash);
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        tracing::info!(
+            workstream = %workstream,
+            removed = result.removed.len(),
+            freed_bytes = result.freed_bytes,
+            "Pruned production snapshots"
+        );
+
+        Ok(result)
+    }
+
+    /// Validates the workstream for snapshot operations.
+    fn check_snapshot_workstream(&self, workstream: &str) -> DirectoryResult<()> {
+        if !Self::is_valid_name(workstream) {
+            return Err(DirectoryError::InvalidName(workstream.to_string()));
+        }
+        if !self.workstream_exists(workstream) {
+            return Err(DirectoryError::WorkstreamNotFound(workstream.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Hashes every regular file under `production/`, sorted by path.
+    fn scan_production(&self, workstream: &str) -> DirectoryResult<Vec<SnapshotFile>> {
+        let prod_path = self.production_path(workstream);
+        if !prod_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = Vec::new();
+        for entry in WalkDir::new(&prod_path).follow_links(false) {
+            let entry =
+                entry.map_err(|e| DirectoryError::Io(std::io::Error::other(e.to_string())))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry
+                .path()
+                .strip_prefix(&prod_path)
+                .unwrap_or(entry.path())
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let mut hasher = Sha256::new();
+            let mut file = fs::File::open(entry.path())?;
+            let bytes = io::copy(&mut file, &mut hasher)?;
+
+            files.push(SnapshotFile {
+                path: relative,
+                hash: hex::encode(hasher.finalize()),
+                bytes,
+            });
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Copies any file contents not yet in the object store.
+    fn store_objects(&self, workstream: &str, files: &[SnapshotFile]) -> DirectoryResult<()> {
+        let prod_path = self.production_path(workstream);
+        for file in files {
+            let object = self.object_path(workstream, &file.hash);
+            if object.exists() {
+                continue;
+            }
+            let dir = object.parent().expect("object paths have a parent");
+            fs::create_dir_all(dir)?;
+
+            // Write then rename so a crash never leaves a truncated object
+            let tmp = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+            fs::copy(prod_path.join(&file.path), &tmp)?;
+            fs::rename(&tmp, &object)?;
+        }
+        Ok(())
+    }
+
+    /// Path of the stored object for a content hash.
+    fn object_path(&self, workstream: &str, hash: &str) -> PathBuf {
+        let (prefix, rest) = hash.split_at(2.min(hash.len()));
+        self.snapshots_path(workstream)
+            .join(OBJECTS_DIR)
+            .join(prefix)
+            .join(rest)
+    }
+
+    fn read_manifest(path: &Path) -> DirectoryResult<Snapshot> {
+        let data = fs::read(path)?;
+        serde_json::from_slice(&data).map_err(|e| DirectoryError::CorruptSnapshot {
+            id: path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+/// Compares two sorted file lists.
+fn diff_files(old: &[SnapshotFile], new: &[SnapshotFile]) -> SnapshotDiff {
+    let old: BTreeMap<&str, &str> = old
+        .iter()
+        .map(|f| (f.path.as_str(), f.hash.as_str()))
+        .collect();
+    let new: BTreeMap<&str, &str> = new
+        .iter()
+        .map(|f| (f.path.as_str(), f.hash.as_str()))
+        .collect();
+
+    let mut diff = SnapshotDiff::default();
+    for (path, hash) in &new {
+        match old.get(path) {
+            None => diff.added.push(path.to_string()),
+            Some(old_hash) if old_hash != hash => diff.modified.push(path.to_string()),
+            Some(_) => {}
+        }
+    }
+    for path in old.keys() {
+        if !new.contains_key(path) {
+            diff.removed.push(path.to_string());
+        }
+    }
+    diff
+}
+
+/// Whether a manifest path stays inside `production/`.
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Removes directories left empty under `root` (but not `root` itself).
+fn remove_empty_dirs(root: &Path) -> io::Result<()> {
+    for entry in WalkDir::new(root)
+        .min_depth(1)
+        .contents_first(true)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+    {
+        if entry.file_type().is_dir() && fs::read_dir(entry.path())?.next().is_none() {
+            fs::remove_dir(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::super::{DirectoryError, SnapshotRetention, SnapshotTrigger};
+    use super::DirectoryManager;
+
+    fn setup() -> (tempfile::TempDir, DirectoryManager) {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = DirectoryManager::new(dir.path());
+        manager.create_workstream("docs").unwrap();
+        (dir, manager)
+    }
+
+    fn write_prod(manager: &DirectoryManager, path: &str, content: &str) {
+        let full = manager.production_path("docs").join(path);
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(full, content).unwrap();
+    }
+
+    fn object_count(manager: &DirectoryManager) -> usize {
+        walkdir::WalkDir::new(manager.snapshots_path("docs").join("objects"))
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .count()
+    }
+
+    #[test]
+    fn test_snapshot_dedups_unchanged_files() {
+        let (_dir, manager) = setup();
+        write_prod(&manager, "a.md", "alpha");
+        write_prod(&manager, "nested/b.md", "beta");
+
+        let first = manager
+            .create_snapshot("docs", SnapshotTrigger::Manual, Some("v1"))
+            .unwrap();
+        assert_eq!(first.files.len(), 2);
+        assert_eq!(first.files[1].path, "nested/b.md");
+        assert_eq!(first.total_bytes(), 9);
+        assert_eq!(object_count(&manager), 2);
+
+        write_prod(&manager, "a.md", "alpha v2");
+        let second = manager
+            .create_snapshot("docs", SnapshotTrigger::Manual, None)
+            .unwrap();
+        // Only the changed file adds an object
+        assert_eq!(object_count(&manager), 3);
+
+        let listed = manager.list_snapshots("docs").unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].id, second.id);
+        assert_eq!(listed[1].label.as_deref(), Some("v1"));
+
+        // Usage counts the snapshot store
+        assert!(manager.get_usage("docs").unwrap().snapshot_bytes > 0);
+    }
+
+    #[test]
+    fn test_snapshot_before_change_skips_duplicates() {
+        let (_dir, manager) = setup();
+        assert!(
+            manager
+                .snapshot_before_change("docs", SnapshotTrigger::Promote)
+                .unwrap()
+                .is_none()
+        );
+
+        write_prod(&manager, "a.md", "alpha");
+        let first = manager
+            .snapshot_before_change("docs", SnapshotTrigger::Promote)
+            .unwrap()
+            .unwrap();
+        let again = manager
+            .snapshot_before_change("docs", SnapshotTrigger::Promote)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.id, again.id);
+        assert_eq!(manager.list_snapshots("docs").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_promote_takes_snapshot() {
+        let (_dir, manager) = setup();
+        write_prod(&manager, "report.md", "original");
+        fs::write(manager.work_path("docs").join("draft.md"), "draft").unwrap();
+
+        manager
+            .promote("docs", Path::new("draft.md"), Path::new("draft.md"))
+            .unwrap();
+
+        let snapshots = manager.list_snapshots("docs").unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].trigger, SnapshotTrigger::Promote);
+        assert_eq!(snapshots[0].files.len(), 1);
+        assert_eq!(snapshots[0].files[0].path, "report.md");
+    }
+
+    #[test]
+    fn test_diff_and_restore() {
+        let (_dir, manager) = setup();
+        write_prod(&manager, "keep.md", "same");
+        write_prod(&manager, "edit.md", "before");
+        write_prod(&manager, "gone.md", "deleted later");
+        let snap = manager
+            .create_snapshot("docs", SnapshotTrigger::Manual, None)
+            .unwrap();
+
+        write_prod(&manager, "edit.md", "after");
+        write_prod(&manager, "extra/new.md", "new");
+        fs::remove_file(manager.production_path("docs").join("gone.md")).unwrap();
+
+        let diff = manager.diff_snapshot("docs", &snap.id, None).unwrap();
+        assert_eq!(diff.added, vec!["extra/new.md"]);
+        assert_eq!(diff.removed, vec!["gone.md"]);
+        assert_eq!(diff.modified, vec!["edit.md"]);
+
+        let result = manager.restore_snapshot("docs", &snap.id).unwrap();
+        assert_eq!(result.files_restored, 2);
+        assert_eq!(result.files_removed, 1);
+        let safety = result.safety_snapshot.unwrap();
+
+        let prod = manager.production_path("docs");
+        assert_eq!(fs::read_to_string(prod.join("edit.md")).unwrap(), "before");
+        assert_eq!(
+            fs::read_to_string(prod.join("gone.md")).unwrap(),
+            "deleted later"
+        );
+        assert!(!prod.join("extra").exists());
+        assert!(
+            manager
+                .diff_snapshot("docs", &snap.id, None)
+                .unwrap()
+                .is_empty()
+        );
+
+        // The pre-restore state is still available
+        let undo = manager
+            .diff_snapshot("docs", &snap.id, Some(&safety))
+            .unwrap();
+        assert_eq!(undo.added, vec!["extra/new.md"]);
+    }
+
+    #[test]
+    fn test_get_snapshot_not_found() {
+        let (_dir, manager) = setup();
+        for id in ["missing", "../../etc/passwd", ""] {
+            assert!(matches!(
+                manager.get_snapshot("docs", id),
+                Err(DirectoryError::SnapshotNotFound(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_prune_snapshots() {
+        let (_dir, manager) = setup();
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            write_prod(&manager, "a.md", &format!("version {i}"));
+            ids.push(
+                manager
+                    .create_snapshot("docs", SnapshotTrigger::Manual, None)
+                    .unwrap()
+                    .id,
+            );
+        }
+        let retention = SnapshotRetention {
+            keep_last: 2,
+            max_age_days: None,
+        };
+
+        let dry = manager.prune_snapshots("docs", &retention, true).unwrap();
+        assert_eq!(dry.removed, vec![ids[1].clone(), ids[0].clone()]);
+        assert_eq!(manager.list_snapshots("docs").unwrap().len(), 4);
+
+        let result = manager.prune_snapshots("docs", &retention, false).unwrap();
+        assert_eq!(result.removed.len(), 2);
+        assert_eq!(result.freed_bytes, 18);
+        assert_eq!(manager.list_snapshots("docs").unwrap().len(), 2);
+        assert_eq!(object_count(&manager), 2);
+
+        // Nothing left to prune
+        let again = manager.prune_snapshots("docs", &retention, false).unwrap();
+        assert!(again.removed.is_empty());
+    }
+}
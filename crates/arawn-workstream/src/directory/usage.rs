@@ -5,7 +5,7 @@ use std::path::{Path, PathBuf};
 
 use super::{
     DirectoryError, DirectoryManager, DirectoryResult, ManualCleanupResult, PRODUCTION_DIR,
-    SCRATCH_WORKSTREAM, SESSIONS_DIR, SessionUsage, UsageStats, WORK_DIR,
+    SCRATCH_WORKSTREAM, SESSIONS_DIR, SNAPSHOTS_DIR, SessionUsage, UsageStats, WORK_DIR,
 };
 
 impl DirectoryManager {
@@ -107,11 +107,15 @@ impl DirectoryManager {
             (work_bytes, Vec::new())
         };
 
-        let total_bytes = production_bytes + work_bytes;
+        // Snapshots are not user-visible but count towards disk pressure
+        let snapshot_bytes = Self::dir_size(&ws_path.join(SNAPSHOTS_DIR)).unwrap_or(0);
+
+        let total_bytes = production_bytes + work_bytes + snapshot_bytes;
 
         Ok(UsageStats {
             production_bytes,
             work_bytes,
+            snapshot_bytes,
             sessions,
             total_bytes,
             warnings,
@@ -528,6 +532,7 @@ mod tests {
         let stats = UsageStats {
             production_bytes: 1_048_576, // 1 MB
             work_bytes: 524_288,         // 0.5 MB
+            snapshot_bytes: 0,
             sessions: vec![],
             total_bytes: 1_572_864, // 1.5 MB
             warnings: vec![],
@@ -11,6 +11,9 @@ pub enum WorkstreamError {
     #[error("Workstream not found: {0}")]
     NotFound(String),
 
+    #[error("Invalid operation: {0}")]
+    InvalidOperation(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -26,6 +26,8 @@ pub struct WorkstreamFsGate {
     working_dir: PathBuf,
     /// The allowed paths for sandbox write access.
     allowed_paths: Vec<PathBuf>,
+    /// Domains shell commands may reach (empty = no network).
+    allowed_domains: Vec<String>,
 }
 
 impl WorkstreamFsGate {
@@ -73,8 +75,17 @@ impl WorkstreamFsGate {
             sandbox_manager: sandbox,
             working_dir,
             allowed_paths,
+            allowed_domains: Vec::new(),
         }
     }
+
+    /// Allow sandboxed shell commands to reach these domains.
+    ///
+    /// Taken from the workstream's `network_domains` setting.
+    pub fn with_allowed_domains(mut self, domains: Vec<String>) -> Self {
+        self.allowed_domains = domains;
+        self
+    }
 }
 
 #[async_trait]
@@ -143,6 +154,7 @@ impl FsGate for WorkstreamFsGate {
 
         let mut config = arawn_sandbox::SandboxConfig::default()
             .with_write_paths(self.allowed_paths.clone())
+            .with_allowed_domains(self.allowed_domains.clone())
             .with_working_dir(&self.working_dir);
 
         if let Some(t) = timeout {
@@ -3,6 +3,7 @@
 //! Provides persistent conversational contexts (workstreams) with JSONL message
 //! history as the source of truth and SQLite as an operational cache layer.
 
+pub mod access;
 pub mod cleanup;
 pub mod compression;
 pub mod context;
@@ -10,25 +11,33 @@ pub mod directory;
 pub mod error;
 pub mod fs_gate;
 pub mod manager;
+pub mod merge;
 pub mod message_store;
 pub mod path_validator;
 pub mod scratch;
+pub mod search;
 pub mod session;
 pub mod session_loader;
 pub mod storage;
 pub mod store;
 pub mod types;
+pub mod usage;
 pub mod watcher;
+pub mod webhooks;
 
+pub use access::{AccessStore, ApiToken, ResourceKind, TOKEN_PREFIX, TokenScope, scopes_allow};
 pub use compression::{Compressor, CompressorConfig};
 pub use context::{AssembledContext, ContextAssembler, ContextMessage, ContextRole};
 pub use directory::{
     AttachResult, CloneResult, DirectoryError, DirectoryManager, DirectoryResult, ExportResult,
-    ManualCleanupResult, PromoteResult, SCRATCH_WORKSTREAM, SessionUsage, UsageStats,
+    FileConflictPolicy, FileMergeResult, ManualCleanupResult, PromoteResult, RestoreResult,
+    SCRATCH_WORKSTREAM, SessionUsage, Snapshot, SnapshotDiff, SnapshotFile, SnapshotPruneResult,
+    SnapshotRetention, SnapshotTrigger, UsageStats,
 };
 pub use error::{Result, WorkstreamError};
 pub use fs_gate::WorkstreamFsGate;
 pub use manager::{WorkstreamConfig, WorkstreamManager};
+pub use merge::{MergeResult, SplitResult};
 pub use message_store::MessageStore;
 pub use path_validator::{PathError, PathResult, PathValidator};
 pub use scratch::{SCRATCH_ID, ScratchManager};
@@ -41,8 +50,16 @@ pub use storage::{MessageStorage, WorkstreamStorage};
 #[cfg(test)]
 pub use storage::{MockMessageStorage, MockWorkstreamStorage};
 pub use store::WorkstreamStore;
-pub use types::{MessageRole, WorkstreamMessage};
+pub use types::{ForkPoint, MessageRole, WorkstreamMessage};
+pub use usage::{
+    LimitPeriod, SpendLimit, SpendLimits, UsageGroupBy, UsageLedger, UsageQuery, UsageReport,
+    UsageRow,
+};
 pub use watcher::{
     DEFAULT_DEBOUNCE_MS, DEFAULT_POLL_INTERVAL_SECS, FileWatcher, FileWatcherConfig, FsAction,
     FsChangeEvent, WatcherError, WatcherHandle, WatcherResult,
 };
+pub use webhooks::{
+    DeadLetter, NewWebhook, WEBHOOK_SECRET_PREFIX, WebhookEventKind, WebhookStore,
+    WebhookSubscription, WebhookUpdate,
+};
@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
+use arawn_types::AgentSettings;
+
 use crate::directory::DirectoryManager;
 use crate::message_store::MessageStore;
 use crate::scratch::{SCRATCH_ID, ScratchManager};
 use crate::session::SessionManager;
 use crate::store::{Session, Workstream, WorkstreamStore};
-use crate::types::{MessageRole, WorkstreamMessage};
+use crate::types::{ForkPoint, MessageRole, WorkstreamMessage};
 use crate::{Result, WorkstreamError};
 
 /// Configuration for the workstream manager.
@@ -150,6 +152,14 @@ impl WorkstreamManager {
         self.store.get_workstream(id)
     }
 
+    /// Replace a workstream's agent settings.
+    ///
+    /// Sessions pick the new settings up on their next turn.
+    pub fn update_settings(&self, id: &str, settings: &AgentSettings) -> Result<Workstream> {
+        self.store.update_settings(id, settings)?;
+        self.store.get_workstream(id)
+    }
+
     /// Update tags for a workstream.
     pub fn set_tags(&self, workstream_id: &str, tags: &[String]) -> Result<()> {
         // Verify the workstream exists
@@ -263,6 +273,70 @@ impl WorkstreamManager {
         self.store.list_sessions(workstream_id)
     }
 
+    /// Fork a session.
+    ///
+    /// Creates a new session in the same workstream whose history is a
+    /// prefix of `session_id`'s, cut at `point`, and records the parent
+    /// session and last copied message as lineage. Returns the new session
+    /// and the number of messages copied.
+    ///
+    /// Sessions persisted only through their message log get a session record
+    /// here so the lineage always points at a known parent.
+    pub fn fork_session(
+        &self,
+        workstream_id: &str,
+        session_id: &str,
+        point: &ForkPoint,
+    ) -> Result<(Session, usize)> {
+        let messages = self
+            .message_store
+            .read_for_session(workstream_id, session_id)?;
+        if messages.is_empty() && self.store.get_session(session_id).is_err() {
+            return Err(WorkstreamError::NotFound(format!("session {}", session_id)));
+        }
+        if workstream_id == SCRATCH_ID {
+            self.scratch_manager().ensure_scratch()?;
+        }
+        self.store
+            .create_session_with_id(session_id, workstream_id)?;
+
+        let cut = match point {
+            ForkPoint::Message(id) => {
+                messages
+                    .iter()
+                    .position(|m| &m.id == id)
+                    .ok_or_else(|| WorkstreamError::NotFound(format!("message {}", id)))?
+                    + 1
+            }
+            ForkPoint::Turns(n) => messages
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| m.role == MessageRole::User)
+                .nth(*n)
+                .map(|(i, _)| i)
+                .unwrap_or(messages.len()),
+            ForkPoint::End => messages.len(),
+        };
+        let prefix = &messages[..cut];
+
+        let fork = self.store.create_forked_session(
+            workstream_id,
+            session_id,
+            prefix.last().map(|m| m.id.as_str()),
+        )?;
+        let copied = self
+            .message_store
+            .copy_to_session(workstream_id, &fork.id, prefix)?;
+
+        tracing::info!(
+            parent_session_id = %session_id,
+            session_id = %fork.id,
+            messages_copied = copied,
+            "Forked session"
+        );
+        Ok((fork, copied))
+    }
+
     /// Move a session to a different workstream.
     pub fn reassign_session(&self, session_id: &str, new_workstream_id: &str) -> Result<Session> {
         tracing::info!(
@@ -497,4 +571,63 @@ mod tests {
         let scratch_msgs = mgr.get_messages(SCRATCH_ID).unwrap();
         assert!(scratch_msgs.is_empty());
     }
+
+    #[test]
+    fn test_fork_session() {
+        let (_dir, mgr) = test_manager();
+        let ws = mgr.create_workstream("Forks", None, &[]).unwrap();
+        let sid = "parent-session";
+
+        let mut ids = Vec::new();
+        for (role, content) in [
+            (MessageRole::User, "first"),
+            (MessageRole::Assistant, "reply one"),
+            (MessageRole::User, "second"),
+            (MessageRole::Assistant, "reply two"),
+        ] {
+            let msg = mgr
+                .send_message(Some(&ws.id), Some(sid), role, content, None)
+                .unwrap();
+            ids.push(msg.id);
+        }
+
+        // Fork before the second user message (edit-and-resubmit)
+        let (fork, copied) = mgr.fork_session(&ws.id, sid, &ForkPoint::Turns(1)).unwrap();
+        assert_eq!(copied, 2);
+        assert_eq!(fork.workstream_id, ws.id);
+        assert_eq!(fork.parent_session_id.as_deref(), Some(sid));
+        assert_eq!(fork.forked_from_message_id.as_ref(), Some(&ids[1]));
+
+        let history = mgr
+            .message_store()
+            .read_for_session(&ws.id, &fork.id)
+            .unwrap();
+        let contents: Vec<_> = history.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["first", "reply one"]);
+        assert!(history.iter().all(|m| !ids.contains(&m.id)));
+
+        // Lineage survives a reload; the parent is untouched
+        let stored = mgr.store().get_session(&fork.id).unwrap();
+        assert_eq!(stored.parent_session_id.as_deref(), Some(sid));
+        let parent = mgr.message_store().read_for_session(&ws.id, sid).unwrap();
+        assert_eq!(parent.len(), 4);
+
+        // Fork through a specific message, and the whole session
+        let (_, copied) = mgr
+            .fork_session(&ws.id, sid, &ForkPoint::Message(ids[2].clone()))
+            .unwrap();
+        assert_eq!(copied, 3);
+        let (_, copied) = mgr.fork_session(&ws.id, sid, &ForkPoint::End).unwrap();
+        assert_eq!(copied, 4);
+
+        let err = mgr
+            .fork_session(&ws.id, sid, &ForkPoint::Message("missing".into()))
+            .unwrap_err();
+        assert!(matches!(err, WorkstreamError::NotFound(_)));
+
+        let err = mgr
+            .fork_session(&ws.id, "no-such-session", &ForkPoint::End)
+            .unwrap_err();
+        assert!(matches!(err, WorkstreamError::NotFound(_)));
+    }
 }
@@ -0,0 +1,362 @@
+//! Merging workstreams together and splitting sessions out of one.
+//!
+//! Both operations move whole sessions: their records, their messages (the
+//! JSONL files are rewritten with messages interleaved by timestamp) and,
+//! because memories are keyed by session, the memories extracted from them.
+//! A merge also moves tags and files and archives the source.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::directory::{DirectoryError, FileConflictPolicy, FileMergeResult};
+use crate::manager::WorkstreamManager;
+use crate::scratch::SCRATCH_ID;
+use crate::store::Workstream;
+use crate::{Result, WorkstreamError};
+
+/// Outcome of merging one workstream into another.
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    /// The target workstream after the merge.
+    pub workstream: Workstream,
+    /// IDs of the sessions moved from the source.
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    pub messages_moved: usize,
+    /// What happened to the source's files; `None` without a directory manager.
+    pub files: Option<FileMergeResult>,
+}
+
+/// Outcome of splitting sessions out into a new workstream.
+#[derive(Debug, Clone)]
+pub struct SplitResult {
+    /// The newly created workstream.
+    pub workstream: Workstream,
+    /// IDs of the sessions moved into it.
+    pub session_ids: Vec<String>,
+    /// Number of messages moved.
+    pub messages_moved: usize,
+}
+
+impl WorkstreamManager {
+    /// Merge `source_id` into `target_id` and archive the source.
+    ///
+    /// Moves every session and message, adds the source's tags to the
+    /// target, and moves `production/` and `work/` files, resolving clashes
+    /// with `on_conflict`. The source keeps its record (archived) and any
+    /// files skipped because of a conflict.
+    pub fn merge_workstreams(
+        &self,
+        source_id: &str,
+        target_id: &str,
+        on_conflict: FileConflictPolicy,
+    ) -> Result<MergeResult> {
+        if source_id == target_id {
+            return Err(WorkstreamError::InvalidOperation(
+                "Cannot merge a workstream into itself".to_string(),
+            ));
+        }
+        if source_id == SCRATCH_ID || target_id == SCRATCH_ID {
+            return Err(WorkstreamError::InvalidOperation(
+                "The scratch workstream cannot be merged; promote it first".to_string(),
+            ));
+        }
+        for ws in [
+            self.get_workstream(source_id)?,
+            self.get_workstream(target_id)?,
+        ] {
+            if ws.state == "archived" {
+                return Err(WorkstreamError::InvalidOperation(format!(
+                    "Workstream '{}' is archived",
+                    ws.title
+                )));
+            }
+        }
+
+        let session_ids: Vec<String> = self.known_sessions(source_id)?.into_iter().collect();
+
+        let messages_moved = self
+            .message_store()
+            .transfer_messages(source_id, target_id, None)?;
+        self.store().reassign_sessions(source_id, target_id)?;
+        self.store().merge_tags(source_id, target_id)?;
+
+        let files = match self.directory_manager() {
+            Some(dm) => Some(
+                dm.merge_files(source_id, target_id, on_conflict)
+                    .map_err(directory_error)?,
+            ),
+            None => None,
+        };
+
+        for id in [source_id, target_id] {
+            self.store().reset_message_index(id)?;
+        }
+        self.archive_workstream(source_id)?;
+        let workstream = self.update_workstream(target_id, None, None, None)?;
+
+        tracing::info!(
+            source_id = %source_id,
+            target_id = %target_id,
+            sessions = session_ids.len(),
+            messages = messages_moved,
+            "Merged workstream"
+        );
+
+        Ok(MergeResult {
+            workstream,
+            session_ids,
+            messages_moved,
+            files,
+        })
+    }
+
+    /// Move the given sessions out of `source_id` into a new workstream.
+    ///
+    /// The new workstream gets `title` and `tags` and inherits the source's
+    /// default model and agent settings. Files stay with the source, since
+    /// named workstreams share them across sessions.
+    pub fn split_workstream(
+        &self,
+        source_id: &str,
+        session_ids: &[String],
+        title: &str,
+        tags: &[String],
+    ) -> Result<SplitResult> {
+        if source_id == SCRATCH_ID {
+            return Err(WorkstreamError::InvalidOperation(
+                "The scratch workstream cannot be split; promote it or move sessions instead"
+                    .to_string(),
+            ));
+        }
+        let selected: HashSet<String> = session_ids.iter().cloned().collect();
+        if selected.is_empty() {
+            return Err(WorkstreamError::InvalidOperation(
+                "No sessions selected to split".to_string(),
+            ));
+        }
+
+        let source = self.get_workstream(source_id)?;
+        let known = self.known_sessions(source_id)?;
+        if let Some(missing) = selected.iter().find(|id| !known.contains(*id)) {
+            return Err(WorkstreamError::NotFound(format!("session {}", missing)));
+        }
+
+        let mut workstream =
+            self.create_workstream(title, source.default_model.as_deref(), tags)?;
+        if !source.settings.is_default() {
+            workstream = self.update_settings(&workstream.id, &source.settings)?;
+        }
+
+        let messages_moved =
+            self.message_store()
+                .transfer_messages(source_id, &workstream.id, Some(&selected))?;
+        let mut moved: Vec<String> = selected.into_iter().collect();
+        moved.sort();
+        for id in &moved {
+            if self.store().get_session(id).is_ok() {
+                self.store().reassign_session(id, &workstream.id)?;
+            } else {
+                // Known only from its messages; give it a record in its new home
+                self.store().create_session_with_id(id, &workstream.id)?;
+            }
+        }
+
+        for id in [source_id, workstream.id.as_str()] {
+            self.store().reset_message_index(id)?;
+        }
+
+        tracing::info!(
+            source_id = %source_id,
+            workstream_id = %workstream.id,
+            sessions = moved.len(),
+            messages = messages_moved,
+            "Split sessions into new workstream"
+        );
+
+        Ok(SplitResult {
+            workstream,
+            session_ids: moved,
+            messages_moved,
+        })
+    }
+
+    /// Sessions of a workstream, from its records and its message history.
+    fn known_sessions(&self, workstream_id: &str) -> Result<BTreeSet<String>> {
+        let mut ids: BTreeSet<String> = self
+            .list_sessions(workstream_id)?
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        ids.extend(
+            self.message_store()
+                .read_all(workstream_id)?
+                .into_iter()
+                .filter_map(|m| m.session_id),
+        );
+        Ok(ids)
+    }
+}
+
+fn directory_error(e: DirectoryError) -> WorkstreamError {
+    match e {
+        DirectoryError::Io(e) => WorkstreamError::Io(e),
+        other => WorkstreamError::InvalidOperation(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::directory::DirectoryManager;
+    use crate::message_store::MessageStore;
+    use crate::store::WorkstreamStore;
+    use crate::types::MessageRole;
+    use arawn_types::AgentSettings;
+
+    fn test_manager() -> (tempfile::TempDir, WorkstreamManager) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = WorkstreamStore::open_in_memory().unwrap();
+        let msg_store = MessageStore::new(dir.path());
+        let mgr = WorkstreamManager::from_parts(store, msg_store, 30)
+            .with_directory_manager(DirectoryManager::new(dir.path().join("files")));
+        (dir, mgr)
+    }
+
+    fn send(mgr: &WorkstreamManager, ws: &str, session: &str, content: &str) {
+        mgr.send_message(Some(ws), Some(session), MessageRole::User, content, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_merge_workstreams() {
+        let (_dir, mgr) = test_manager();
+        let source = mgr
+            .create_workstream("Source", None, &["a".into(), "shared".into()])
+            .unwrap();
+        let target = mgr
+            .create_workstream("Target", None, &["shared".into()])
+            .unwrap();
+        send(&mgr, &target.id, "t1", "first");
+        send(&mgr, &source.id, "s1", "second");
+        send(&mgr, &target.id, "t1", "third");
+
+        let dm = mgr.directory_manager().unwrap();
+        std::fs::write(dm.production_path(&source.id).join("report.md"), "r").unwrap();
+
+        let result = mgr
+            .merge_workstreams(&source.id, &target.id, FileConflictPolicy::Rename)
+            .unwrap();
+
+        assert_eq!(result.workstream.id, target.id);
+        assert_eq!(result.session_ids, vec!["s1"]);
+        assert_eq!(result.messages_moved, 1);
+        assert_eq!(result.files.unwrap().moved, 1);
+
+        let contents: Vec<_> = mgr
+            .get_messages(&target.id)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(contents, vec!["first", "second", "third"]);
+        assert!(mgr.get_messages(&source.id).unwrap().is_empty());
+
+        let sessions = mgr.list_sessions(&target.id).unwrap();
+        assert_eq!(sessions.len(), 2);
+
+        let mut tags = mgr.get_tags(&target.id).unwrap();
+        tags.sort();
+        assert_eq!(tags, vec!["a", "shared"]);
+        assert!(dm.production_path(&target.id).join("report.md").exists());
+        assert_eq!(mgr.get_workstream(&source.id).unwrap().state, "archived");
+
+        // The archived source can't take part in another merge
+        let err = mgr
+            .merge_workstreams(&source.id, &target.id, FileConflictPolicy::Rename)
+            .unwrap_err();
+        assert!(matches!(err, WorkstreamError::InvalidOperation(_)));
+    }
+
+    #[test]
+    fn test_merge_rejects_same_and_scratch() {
+        let (_dir, mgr) = test_manager();
+        let ws = mgr.create_workstream("Only", None, &[]).unwrap();
+        mgr.send_message(None, None, MessageRole::User, "hi", None)
+            .unwrap();
+
+        for (from, to) in [
+            (ws.id.as_str(), ws.id.as_str()),
+            (SCRATCH_ID, ws.id.as_str()),
+        ] {
+            let err = mgr
+                .merge_workstreams(from, to, FileConflictPolicy::Rename)
+                .unwrap_err();
+            assert!(matches!(err, WorkstreamError::InvalidOperation(_)));
+        }
+        let err = mgr
+            .merge_workstreams(&ws.id, "missing", FileConflictPolicy::Rename)
+            .unwrap_err();
+        assert!(matches!(err, WorkstreamError::NotFound(_)));
+    }
+
+    #[test]
+    fn test_split_workstream() {
+        let (_dir, mgr) = test_manager();
+        let source = mgr.create_workstream("Mixed", Some("sonnet"), &[]).unwrap();
+        mgr.update_settings(
+            &source.id,
+            &AgentSettings {
+                system_prompt: Some("Be brief".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        send(&mgr, &source.id, "keep", "stays");
+        send(&mgr, &source.id, "move", "goes");
+        send(&mgr, &source.id, "keep", "stays too");
+
+        let result = mgr
+            .split_workstream(&source.id, &["move".into()], "Spun off", &["new".into()])
+            .unwrap();
+
+        let new_id = &result.workstream.id;
+        assert_eq!(result.workstream.title, "Spun off");
+        assert_eq!(result.workstream.default_model.as_deref(), Some("sonnet"));
+        assert_eq!(
+            result.workstream.settings.system_prompt.as_deref(),
+            Some("Be brief")
+        );
+        assert_eq!(result.session_ids, vec!["move"]);
+        assert_eq!(result.messages_moved, 1);
+        assert_eq!(mgr.get_tags(new_id).unwrap(), vec!["new"]);
+
+        let moved = mgr.get_messages(new_id).unwrap();
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].workstream_id, *new_id);
+        assert_eq!(mgr.get_messages(&source.id).unwrap().len(), 2);
+        assert_eq!(
+            mgr.store().get_session("move").unwrap().workstream_id,
+            *new_id
+        );
+        assert_eq!(
+            mgr.store().get_session("keep").unwrap().workstream_id,
+            source.id
+        );
+    }
+
+    #[test]
+    fn test_split_rejects_unknown_or_empty_selection() {
+        let (_dir, mgr) = test_manager();
+        let ws = mgr.create_workstream("Mixed", None, &[]).unwrap();
+        send(&mgr, &ws.id, "s1", "hello");
+
+        let err = mgr
+            .split_workstream(&ws.id, &["nope".into()], "New", &[])
+            .unwrap_err();
+        assert!(matches!(err, WorkstreamError::NotFound(_)));
+        let err = mgr.split_workstream(&ws.id, &[], "New", &[]).unwrap_err();
+        assert!(matches!(err, WorkstreamError::InvalidOperation(_)));
+        // Nothing was created by the failed attempts
+        assert_eq!(mgr.list_workstreams().unwrap().len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -153,6 +154,138 @@ impl MessageStore {
         Ok(())
     }
 
+    /// Copy messages into another session of the same workstream.
+    ///
+    /// Each copy gets a fresh id and `session_id`; content, timestamps and
+    /// metadata are kept. Returns the number of messages copied.
+    pub fn copy_to_session(
+        &self,
+        workstream_id: &str,
+        session_id: &str,
+        messages: &[WorkstreamMessage],
+    ) -> Result<usize> {
+        if messages.is_empty() {
+            return Ok(0);
+        }
+
+        let dir = self.workstream_dir(workstream_id);
+        fs::create_dir_all(&dir)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("messages.jsonl"))?;
+
+        for msg in messages {
+            let copy = WorkstreamMessage {
+                id: Uuid::new_v4().to_string(),
+                workstream_id: workstream_id.to_string(),
+                session_id: Some(session_id.to_string()),
+                ..msg.clone()
+            };
+            let mut line = serde_json::to_string(&copy)?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+        }
+        file.sync_all()?;
+
+        Ok(messages.len())
+    }
+
+    /// Append messages exactly as given, keeping their ids and timestamps.
+    ///
+    /// Used when importing an archive. Returns the number of messages written.
+    pub fn append_messages(
+        &self,
+        workstream_id: &str,
+        messages: &[WorkstreamMessage],
+    ) -> Result<usize> {
+        let dir = self.workstream_dir(workstream_id);
+        fs::create_dir_all(&dir)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("messages.jsonl"))?;
+
+        for msg in messages {
+            let mut line = serde_json::to_string(msg)?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+        }
+        file.sync_all()?;
+
+        Ok(messages.len())
+    }
+
+    /// Move messages into another workstream's history.
+    ///
+    /// Takes the messages of `from_workstream` whose session is in `sessions`
+    /// (all of them when `None`) and interleaves them into `to_workstream`'s
+    /// history by timestamp, keeping each file's own order for ties. Both
+    /// files are rewritten through a temporary file and a rename, target
+    /// first. Returns the number of messages moved.
+    pub fn transfer_messages(
+        &self,
+        from_workstream: &str,
+        to_workstream: &str,
+        sessions: Option<&HashSet<String>>,
+    ) -> Result<usize> {
+        let (moved, kept): (Vec<_>, Vec<_>) = self
+            .read_all(from_workstream)?
+            .into_iter()
+            .partition(|m| match sessions {
+                Some(ids) => m.session_id.as_ref().is_some_and(|id| ids.contains(id)),
+                None => true,
+            });
+        if moved.is_empty() {
+            return Ok(0);
+        }
+        let count = moved.len();
+
+        let existing = self.read_all(to_workstream)?;
+        let mut merged = Vec::with_capacity(existing.len() + count);
+        let mut incoming = moved
+            .into_iter()
+            .map(|msg| WorkstreamMessage {
+                workstream_id: to_workstream.to_string(),
+                ..msg
+            })
+            .peekable();
+        for msg in existing {
+            while let Some(next) = incoming.next_if(|m| m.timestamp < msg.timestamp) {
+                merged.push(next);
+            }
+            merged.push(msg);
+        }
+        merged.extend(incoming);
+
+        self.rewrite(to_workstream, &merged)?;
+        if kept.is_empty() {
+            self.delete_all(from_workstream)?;
+        } else {
+            self.rewrite(from_workstream, &kept)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Replace a workstream's history with `messages`.
+    fn rewrite(&self, workstream_id: &str, messages: &[WorkstreamMessage]) -> Result<()> {
+        let dir = self.workstream_dir(workstream_id);
+        fs::create_dir_all(&dir)?;
+        let tmp = dir.join("messages.jsonl.tmp");
+
+        let mut file = fs::File::create(&tmp)?;
+        for msg in messages {
+            let mut line = serde_json::to_string(msg)?;
+            line.push('\n');
+            file.write_all(line.as_bytes())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, dir.join("messages.jsonl"))?;
+
+        Ok(())
+    }
+
     /// Delete all messages for a workstream.
     pub fn delete_all(&self, workstream_id: &str) -> Result<()> {
         let path = self.jsonl_path(workstream_id);
@@ -471,6 +604,90 @@ mod tests {
         assert!(path.ends_with("workstreams/my-ws"));
     }
 
+    #[test]
+    fn test_append_messages_keeps_ids() {
+        let (_dir, source) = temp_store();
+        source
+            .append("ws-1", Some("s-1"), MessageRole::User, "hi", None)
+            .unwrap();
+        source
+            .append("ws-1", Some("s-1"), MessageRole::Assistant, "hello", None)
+            .unwrap();
+        let original = source.read_all("ws-1").unwrap();
+
+        let (_dir2, target) = temp_store();
+        assert_eq!(target.append_messages("ws-1", &original).unwrap(), 2);
+
+        let copied = target.read_all("ws-1").unwrap();
+        assert_eq!(copied.len(), 2);
+        assert_eq!(copied[0].id, original[0].id);
+        assert_eq!(copied[1].timestamp, original[1].timestamp);
+    }
+
+    #[test]
+    fn test_transfer_messages_interleaves_by_timestamp() {
+        let (_dir, store) = temp_store();
+        let at = |secs: i64| DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap();
+        let msg = |id: &str, ws: &str, session: &str, secs| WorkstreamMessage {
+            id: id.to_string(),
+            workstream_id: ws.to_string(),
+            session_id: Some(session.to_string()),
+            role: MessageRole::User,
+            content: id.to_string(),
+            timestamp: at(secs),
+            metadata: None,
+        };
+        store
+            .append_messages(
+                "dest",
+                &[msg("d1", "dest", "s0", 10), msg("d2", "dest", "s0", 30)],
+            )
+            .unwrap();
+        store
+            .append_messages(
+                "src",
+                &[
+                    msg("a1", "src", "s1", 5),
+                    msg("b1", "src", "s2", 20),
+                    msg("a2", "src", "s1", 30),
+                    msg("b2", "src", "s2", 40),
+                ],
+            )
+            .unwrap();
+
+        // Split out one session
+        let only_s1: HashSet<String> = ["s1".to_string()].into();
+        assert_eq!(
+            store
+                .transfer_messages("src", "dest", Some(&only_s1))
+                .unwrap(),
+            2
+        );
+        let ids = |ws| {
+            store
+                .read_all(ws)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("dest"), vec!["a1", "d1", "d2", "a2"]);
+        assert_eq!(ids("src"), vec!["b1", "b2"]);
+        assert!(
+            store
+                .read_all("dest")
+                .unwrap()
+                .iter()
+                .all(|m| m.workstream_id == "dest")
+        );
+
+        // Move the rest; the source file goes away
+        assert_eq!(store.transfer_messages("src", "dest", None).unwrap(), 2);
+        assert_eq!(ids("dest"), vec!["a1", "d1", "b1", "d2", "a2", "b2"]);
+        assert!(!store.jsonl_path("src").exists());
+        assert_eq!(store.transfer_messages("src", "dest", None).unwrap(), 0);
+    }
+
     #[test]
     fn test_jsonl_path() {
         let (_dir, store) = temp_store();
@@ -0,0 +1,510 @@
+//! Full-text search over workstream message history.
+//!
+//! Messages are indexed into an FTS5 table in the workstream database. The
+//! JSONL files remain the source of truth: the index records how many bytes
+//! of each `messages.jsonl` it has read and picks up from there, so keeping
+//! it current costs one `stat` per workstream plus whatever was appended.
+//! A file that shrank or disappeared (e.g. after a merge moved its messages)
+//! is re-indexed from scratch.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+use arawn_types::{
+    MessageSearchError, MessageSearchFacets, MessageSearchHit, MessageSearchQuery,
+    MessageSearchResults, MessageSearcher,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{OptionalExtension, params, params_from_iter, types::Value};
+
+use crate::manager::WorkstreamManager;
+use crate::store::{WorkstreamStore, parse_dt};
+use crate::types::WorkstreamMessage;
+use crate::{Result, WorkstreamError};
+
+/// Marker placed around matched terms in snippets.
+const HIGHLIGHT: &str = "**";
+
+/// Approximate number of tokens in a snippet.
+const SNIPPET_TOKENS: i64 = 16;
+
+impl WorkstreamStore {
+    /// Index any messages appended to `path` since the last call.
+    ///
+    /// Returns the number of messages added to the index.
+    pub fn sync_message_index(&self, workstream_id: &str, path: &Path) -> Result<usize> {
+        let mut conn = self.conn();
+        let offset: u64 = conn
+            .query_row(
+                "SELECT byte_offset FROM message_search_state WHERE workstream_id = ?1",
+                params![workstream_id],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()?
+            .map(|o| o.max(0) as u64)
+            .unwrap_or(0);
+
+        let len = match fs::metadata(path) {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if offset > 0 {
+                    let tx = conn.transaction()?;
+                    clear_index(&tx, workstream_id)?;
+                    tx.commit()?;
+                }
+                return Ok(0);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if len == offset {
+            return Ok(0);
+        }
+        let start = if len < offset { 0 } else { offset };
+
+        let mut reader = BufReader::new(fs::File::open(path)?);
+        reader.seek(SeekFrom::Start(start))?;
+        let mut consumed = start;
+        let mut messages = Vec::new();
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let n = reader.read_until(b'\n', &mut line)?;
+            // Stop at EOF or at a line that is still being written.
+            if n == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            consumed += n as u64;
+            let text = String::from_utf8_lossy(&line);
+            if text.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<WorkstreamMessage>(&text) {
+                Ok(msg) => messages.push(msg),
+                Err(e) => tracing::warn!(
+                    workstream_id = %workstream_id,
+                    error = %e,
+                    "Skipping unreadable message while indexing"
+                ),
+            }
+        }
+
+        let tx = conn.transaction()?;
+        if start == 0 {
+            clear_index(&tx, workstream_id)?;
+        }
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO message_search
+                     (content, message_id, workstream_id, session_id, role, timestamp)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for msg in messages.iter().filter(|m| !m.content.trim().is_empty()) {
+                insert.execute(params![
+                    msg.content,
+                    msg.id,
+                    workstream_id,
+                    msg.session_id,
+                    msg.role.as_str(),
+                    format_ts(&msg.timestamp),
+                ])?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO message_search_state (workstream_id, byte_offset, indexed_at)
+             VALUES (?1, ?2, ?3)",
+            params![workstream_id, consumed as i64, Utc::now().to_rfc3339()],
+        )?;
+        tx.commit()?;
+
+        Ok(messages.len())
+    }
+
+    /// Drop one workstream from the index so the next sync re-reads its file.
+    ///
+    /// Needed after a file is rewritten in place, since the index only
+    /// notices appends and truncation.
+    pub fn reset_message_index(&self, workstream_id: &str) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        clear_index(&tx, workstream_id)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Drop the whole index so the next sync rebuilds it.
+    pub fn clear_message_index(&self) -> Result<()> {
+        let conn = self.conn();
+        conn.execute_batch("DELETE FROM message_search; DELETE FROM message_search_state;")?;
+        Ok(())
+    }
+
+    /// Search indexed messages. Does not sync the index first.
+    pub fn search_messages(&self, query: &MessageSearchQuery) -> Result<MessageSearchResults> {
+        let Some(expr) = match_expression(&query.text) else {
+            return Ok(MessageSearchResults::default());
+        };
+
+        let mut filters = String::new();
+        let mut args: Vec<Value> = vec![Value::Text(expr)];
+        if let Some(ref ws) = query.workstream_id {
+            args.push(Value::Text(ws.clone()));
+            filters.push_str(&format!(
+                " AND message_search.workstream_id = ?{}",
+                args.len()
+            ));
+        }
+        if let Some(ref session) = query.session_id {
+            args.push(Value::Text(session.clone()));
+            filters.push_str(&format!(" AND message_search.session_id = ?{}", args.len()));
+        }
+        if !query.roles.is_empty() {
+            let placeholders = push_all(&mut args, &query.roles);
+            filters.push_str(&format!(" AND message_search.role IN ({placeholders})"));
+        }
+        if !query.tags.is_empty() {
+            let placeholders = push_all(&mut args, &query.tags);
+            filters.push_str(&format!(
+                " AND message_search.workstream_id IN
+                    (SELECT workstream_id FROM workstream_tags WHERE tag IN ({placeholders}))"
+            ));
+        }
+        if let Some(since) = query.since {
+            args.push(Value::Text(format_ts(&since)));
+            filters.push_str(&format!(" AND message_search.timestamp >= ?{}", args.len()));
+        }
+        if let Some(until) = query.until {
+            args.push(Value::Text(format_ts(&until)));
+            filters.push_str(&format!(" AND message_search.timestamp <= ?{}", args.len()));
+        }
+
+        let conn = self.conn();
+        let from = format!("FROM message_search WHERE message_search MATCH ?1{filters}");
+
+        let mut facets = MessageSearchFacets::default();
+        for (column, counts) in [
+            ("role", &mut facets.roles),
+            ("workstream_id", &mut facets.workstreams),
+        ] {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT message_search.{column}, COUNT(*) {from} GROUP BY message_search.{column}"
+            ))?;
+            let rows = stmt.query_map(params_from_iter(args.iter()), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?;
+            for row in rows {
+                let (value, count) = row?;
+                counts.insert(value, count as usize);
+            }
+        }
+        let total = facets.roles.values().sum();
+
+        let limit_idx = args.len() + 1;
+        args.push(Value::Integer(query.limit as i64));
+        args.push(Value::Integer(query.offset as i64));
+        let mut stmt = conn.prepare(&format!(
+            "SELECT message_search.message_id, message_search.workstream_id,
+                    COALESCE(w.title, message_search.workstream_id),
+                    message_search.session_id, message_search.role, message_search.timestamp,
+                    snippet(message_search, 0, '{HIGHLIGHT}', '{HIGHLIGHT}', '…', {SNIPPET_TOKENS}),
+                    bm25(message_search)
+             FROM message_search
+             LEFT JOIN workstreams w ON w.id = message_search.workstream_id
+             WHERE message_search MATCH ?1{filters}
+             ORDER BY bm25(message_search)
+             LIMIT ?{limit_idx} OFFSET ?{}",
+            limit_idx + 1
+        ))?;
+        let hits = stmt
+            .query_map(params_from_iter(args.iter()), |row| {
+                Ok(MessageSearchHit {
+                    message_id: row.get(0)?,
+                    workstream_id: row.get(1)?,
+                    workstream_title: row.get(2)?,
+                    session_id: row.get(3)?,
+                    role: row.get(4)?,
+                    timestamp: parse_dt(&row.get::<_, String>(5)?),
+                    snippet: row.get(6)?,
+                    score: -row.get::<_, f64>(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(MessageSearchResults {
+            hits,
+            total,
+            facets,
+        })
+    }
+}
+
+impl WorkstreamManager {
+    /// Bring the search index up to date with every workstream's JSONL file.
+    ///
+    /// Returns the number of newly indexed messages.
+    pub fn sync_message_index(&self) -> Result<usize> {
+        let mut indexed = 0;
+        for ws in self.list_all_workstreams()? {
+            indexed += self
+                .store()
+                .sync_message_index(&ws.id, &self.message_store().jsonl_path(&ws.id))?;
+        }
+        Ok(indexed)
+    }
+
+    /// Rebuild the search index from scratch. Returns the number of messages indexed.
+    pub fn rebuild_message_index(&self) -> Result<usize> {
+        self.store().clear_message_index()?;
+        self.sync_message_index()
+    }
+
+    /// Search message history across workstreams, syncing the index first.
+    pub fn search_messages(&self, query: &MessageSearchQuery) -> Result<MessageSearchResults> {
+        match query.workstream_id {
+            Some(ref id) => {
+                self.store()
+                    .sync_message_index(id, &self.message_store().jsonl_path(id))?;
+            }
+            None => {
+                self.sync_message_index()?;
+            }
+        }
+        self.store().search_messages(query)
+    }
+}
+
+impl MessageSearcher for WorkstreamManager {
+    fn search_messages(
+        &self,
+        query: &MessageSearchQuery,
+    ) -> std::result::Result<MessageSearchResults, MessageSearchError> {
+        if match_expression(&query.text).is_none() {
+            return Err(MessageSearchError::EmptyQuery);
+        }
+        WorkstreamManager::search_messages(self, query)
+            .map_err(|e: WorkstreamError| MessageSearchError::Index(e.to_string()))
+    }
+}
+
+// ── Helpers ─────────────────────────────────────────────────────────
+
+fn clear_index(conn: &rusqlite::Connection, workstream_id: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM message_search WHERE workstream_id = ?1",
+        params![workstream_id],
+    )?;
+    conn.execute(
+        "DELETE FROM message_search_state WHERE workstream_id = ?1",
+        params![workstream_id],
+    )?;
+    Ok(())
+}
+
+/// Fixed-width RFC 3339 so timestamps compare correctly as text.
+fn format_ts(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Turn free text into an FTS5 expression that matches every term.
+///
+/// Each word is quoted so punctuation can't be read as query syntax. A
+/// trailing `*` is kept as a prefix match. Returns `None` if there is
+/// nothing to search for.
+fn match_expression(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .filter_map(|word| {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(stem) => (stem, true),
+                None => (word, false),
+            };
+            let word = word.trim_matches('"');
+            if !word.chars().any(char::is_alphanumeric) {
+                return None;
+            }
+            let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+            Some(if prefix { format!("{quoted}*") } else { quoted })
+        })
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Append values as query parameters, returning their placeholders.
+fn push_all(args: &mut Vec<Value>, values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| {
+            args.push(Value::Text(v.clone()));
+            format!("?{}", args.len())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::WorkstreamConfig;
+    use crate::types::MessageRole;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn test_manager() -> (TempDir, WorkstreamManager) {
+        let dir = TempDir::new().unwrap();
+        let config = WorkstreamConfig {
+            db_path: dir.path().join("test.db"),
+            data_dir: dir.path().join("data"),
+            session_timeout_minutes: 30,
+        };
+        let mgr = WorkstreamManager::new(&config).unwrap();
+        (dir, mgr)
+    }
+
+    #[test]
+    fn test_match_expression() {
+        assert_eq!(
+            match_expression("deploy  k8s-cluster").as_deref(),
+            Some("\"deploy\" \"k8s-cluster\"")
+        );
+        assert_eq!(match_expression("roll*").as_deref(), Some("\"roll\"*"));
+        assert_eq!(match_expression("  - * \"\" "), None);
+    }
+
+    #[test]
+    fn test_search_across_workstreams_with_facets() {
+        let (_dir, mgr) = test_manager();
+        let infra = mgr
+            .create_workstream("Infra", None, &["ops".to_string()])
+            .unwrap();
+        let blog = mgr.create_workstream("Blog", None, &[]).unwrap();
+        mgr.send_message(
+            Some(&infra.id),
+            None,
+            MessageRole::User,
+            "How do we deploy the database migrations?",
+            None,
+        )
+        .unwrap();
+        mgr.send_message(
+            Some(&infra.id),
+            None,
+            MessageRole::Assistant,
+            "Deploying migrations runs before the app starts.",
+            None,
+        )
+        .unwrap();
+        mgr.send_message(
+            Some(&blog.id),
+            None,
+            MessageRole::User,
+            "Draft a post about our deploy pipeline",
+            None,
+        )
+        .unwrap();
+
+        let results = mgr
+            .search_messages(&MessageSearchQuery::new("deploy"))
+            .unwrap();
+        assert_eq!(results.total, 3);
+        assert_eq!(results.facets.workstreams[&infra.id], 2);
+        assert_eq!(results.facets.roles["user"], 2);
+        assert!(results.hits.iter().any(|h| h.snippet.contains("**")));
+        assert!(
+            results
+                .hits
+                .iter()
+                .any(|h| h.workstream_title == "Blog" && h.role == "user")
+        );
+
+        let tagged = mgr
+            .search_messages(&MessageSearchQuery::new("deploy").with_tag("ops"))
+            .unwrap();
+        assert_eq!(tagged.total, 2);
+
+        let assistant = mgr
+            .search_messages(&MessageSearchQuery::new("migrations").with_role("assistant"))
+            .unwrap();
+        assert_eq!(assistant.total, 1);
+        assert_eq!(assistant.hits[0].workstream_id, infra.id);
+
+        let future = mgr
+            .search_messages(
+                &MessageSearchQuery::new("deploy")
+                    .with_time_range(Some(Utc::now() + chrono::Duration::hours(1)), None),
+            )
+            .unwrap();
+        assert_eq!(future.total, 0);
+
+        let paged = mgr
+            .search_messages(&MessageSearchQuery::new("deploy").with_page(1, 1))
+            .unwrap();
+        assert_eq!(paged.hits.len(), 1);
+        assert_eq!(paged.total, 3);
+    }
+
+    #[test]
+    fn test_index_is_incremental_and_handles_rewrites() {
+        let (_dir, mgr) = test_manager();
+        let ws = mgr.create_workstream("Notes", None, &[]).unwrap();
+        mgr.send_message(Some(&ws.id), None, MessageRole::User, "alpha", None)
+            .unwrap();
+        assert_eq!(mgr.sync_message_index().unwrap(), 1);
+        assert_eq!(mgr.sync_message_index().unwrap(), 0);
+
+        mgr.send_message(Some(&ws.id), None, MessageRole::User, "beta", None)
+            .unwrap();
+        assert_eq!(mgr.sync_message_index().unwrap(), 1);
+
+        // A partially written line is left for the next sync.
+        let path = mgr.message_store().jsonl_path(&ws.id);
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"id\":\"partial").unwrap();
+        assert_eq!(mgr.sync_message_index().unwrap(), 0);
+
+        // A file that shrinks is re-indexed from the start.
+        let first_line = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .to_string();
+        fs::write(&path, format!("{first_line}\n")).unwrap();
+        assert_eq!(mgr.sync_message_index().unwrap(), 1);
+        let results = mgr
+            .search_messages(&MessageSearchQuery::new("beta"))
+            .unwrap();
+        assert_eq!(results.total, 0);
+
+        // A removed file drops its messages.
+        fs::remove_file(&path).unwrap();
+        mgr.sync_message_index().unwrap();
+        let results = mgr
+            .search_messages(&MessageSearchQuery::new("alpha"))
+            .unwrap();
+        assert_eq!(results.total, 0);
+    }
+
+    #[test]
+    fn test_rebuild_index() {
+        let (_dir, mgr) = test_manager();
+        let ws = mgr.create_workstream("Notes", None, &[]).unwrap();
+        mgr.send_message(Some(&ws.id), None, MessageRole::User, "gamma", None)
+            .unwrap();
+        mgr.sync_message_index().unwrap();
+        assert_eq!(mgr.rebuild_message_index().unwrap(), 1);
+        let results = mgr
+            .search_messages(&MessageSearchQuery::new("gamma"))
+            .unwrap();
+        assert_eq!(results.total, 1);
+    }
+
+    #[test]
+    fn test_searcher_rejects_empty_query() {
+        let (_dir, mgr) = test_manager();
+        let searcher: &dyn MessageSearcher = &mgr;
+        assert!(matches!(
+            searcher.search_messages(&MessageSearchQuery::new("  ")),
+            Err(MessageSearchError::EmptyQuery)
+        ));
+    }
+}
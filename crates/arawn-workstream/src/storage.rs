@@ -16,6 +16,7 @@
 //!     └── MockMessageStorage      - In-memory mock for testing
 //! ```
 
+use arawn_types::AgentSettings;
 use chrono::{DateTime, Utc};
 
 use crate::Result;
@@ -56,6 +57,9 @@ pub trait WorkstreamStorage: Send + Sync {
         default_model: Option<&str>,
     ) -> Result<()>;
 
+    /// Replace a workstream's agent settings.
+    fn update_settings(&self, id: &str, settings: &AgentSettings) -> Result<()>;
+
     // ── Tag Operations ──────────────────────────────────────────────────
 
     /// Set tags for a workstream (replaces existing tags).
@@ -159,6 +163,7 @@ impl WorkstreamStorage for MockWorkstreamStorage {
             is_scratch,
             state: "active".to_string(),
             default_model: default_model.map(String::from),
+            settings: AgentSettings::default(),
             created_at: now,
             updated_at: now,
         };
@@ -219,6 +224,16 @@ impl WorkstreamStorage for MockWorkstreamStorage {
         }
     }
 
+    fn update_settings(&self, id: &str, settings: &AgentSettings) -> Result<()> {
+        let mut map = self.workstreams.lock().unwrap();
+        let ws = map
+            .get_mut(id)
+            .ok_or_else(|| crate::WorkstreamError::NotFound(id.to_string()))?;
+        ws.settings = settings.clone();
+        ws.updated_at = Utc::now();
+        Ok(())
+    }
+
     fn set_tags(&self, workstream_id: &str, tags: &[String]) -> Result<()> {
         // Verify workstream exists
         if !self.workstreams.lock().unwrap().contains_key(workstream_id) {
@@ -260,6 +275,8 @@ impl WorkstreamStorage for MockWorkstreamStorage {
             turn_count: Some(0),
             summary: None,
             compressed: false,
+            parent_session_id: None,
+            forked_from_message_id: None,
         };
 
         self.sessions
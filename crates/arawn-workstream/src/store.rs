@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use arawn_types::AgentSettings;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use rusqlite::{Connection, OptionalExtension, params};
@@ -23,10 +24,25 @@ pub struct Workstream {
     pub is_scratch: bool,
     pub state: String,
     pub default_model: Option<String>,
+    /// Agent settings inherited by every session in this workstream.
+    #[serde(default)]
+    pub settings: AgentSettings,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Workstream {
+    /// Settings to apply to a session, with `default_model` as the model
+    /// fallback when the settings don't name one.
+    pub fn agent_settings(&self) -> AgentSettings {
+        let mut settings = self.settings.clone();
+        if settings.model.is_none() {
+            settings.model = self.default_model.clone();
+        }
+        settings
+    }
+}
+
 /// A turn batch within a workstream.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -37,6 +53,12 @@ pub struct Session {
     pub turn_count: Option<i32>,
     pub summary: Option<String>,
     pub compressed: bool,
+    /// Session this one was forked from, if any.
+    #[serde(default)]
+    pub parent_session_id: Option<String>,
+    /// Last message copied from the parent when forking.
+    #[serde(default)]
+    pub forked_from_message_id: Option<String>,
 }
 
 /// Thin repository over SQLite for workstream operational data.
@@ -80,7 +102,7 @@ impl WorkstreamStore {
     }
 
     /// Lock the connection for use.
-    fn conn(&self) -> parking_lot::MutexGuard<'_, Connection> {
+    pub(crate) fn conn(&self) -> parking_lot::MutexGuard<'_, Connection> {
         self.conn.lock()
     }
 
@@ -113,6 +135,7 @@ impl WorkstreamStore {
             is_scratch,
             state: "active".to_string(),
             default_model: default_model.map(String::from),
+            settings: AgentSettings::default(),
             created_at: now,
             updated_at: now,
         })
@@ -121,21 +144,10 @@ impl WorkstreamStore {
     pub fn get_workstream(&self, id: &str) -> Result<Workstream> {
         self.conn()
             .query_row(
-                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at
+                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at, settings
                  FROM workstreams WHERE id = ?1",
                 params![id],
-                |row| {
-                    Ok(Workstream {
-                        id: row.get(0)?,
-                        title: row.get(1)?,
-                        summary: row.get(2)?,
-                        is_scratch: row.get::<_, i32>(3)? != 0,
-                        state: row.get(4)?,
-                        default_model: row.get(5)?,
-                        created_at: parse_dt(&row.get::<_, String>(6)?),
-                        updated_at: parse_dt(&row.get::<_, String>(7)?),
-                    })
-                },
+                row_to_workstream,
             )
             .optional()?
             .ok_or_else(|| WorkstreamError::NotFound(id.to_string()))
@@ -147,7 +159,7 @@ impl WorkstreamStore {
         if let Some(state) = state_filter {
             let conn = self.conn();
             let mut stmt = conn.prepare(
-                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at
+                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at, settings
                  FROM workstreams WHERE state = ?1 ORDER BY updated_at DESC",
             )?;
             let iter = stmt.query_map(params![state], row_to_workstream)?;
@@ -157,7 +169,7 @@ impl WorkstreamStore {
         } else {
             let conn = self.conn();
             let mut stmt = conn.prepare(
-                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at
+                "SELECT id, title, summary, is_scratch, state, default_model, created_at, updated_at, settings
                  FROM workstreams ORDER BY updated_at DESC",
             )?;
             let iter = stmt.query_map([], row_to_workstream)?;
@@ -221,6 +233,23 @@ impl WorkstreamStore {
         Ok(())
     }
 
+    /// Replace a workstream's agent settings.
+    pub fn update_settings(&self, id: &str, settings: &AgentSettings) -> Result<()> {
+        let json = if settings.is_default() {
+            None
+        } else {
+            Some(serde_json::to_string(settings)?)
+        };
+        let updated = self.conn().execute(
+            "UPDATE workstreams SET settings = ?1, updated_at = ?2 WHERE id = ?3",
+            params![json, Utc::now().to_rfc3339(), id],
+        )?;
+        if updated == 0 {
+            return Err(WorkstreamError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
     // ── Bulk reassignment (for scratch promotion) ─────────────────
 
     /// Move all sessions from one workstream to another.
@@ -241,6 +270,26 @@ impl WorkstreamStore {
         Ok(())
     }
 
+    /// Add one workstream's tags to another and remove them from the first.
+    ///
+    /// Unlike [`reassign_tags`](Self::reassign_tags), tags the target already
+    /// has are not duplicated.
+    pub fn merge_tags(&self, from_id: &str, to_id: &str) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR IGNORE INTO workstream_tags (workstream_id, tag)
+             SELECT ?1, tag FROM workstream_tags WHERE workstream_id = ?2",
+            params![to_id, from_id],
+        )?;
+        tx.execute(
+            "DELETE FROM workstream_tags WHERE workstream_id = ?1",
+            params![from_id],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
     // ── Tags ────────────────────────────────────────────────────────
 
     pub fn set_tags(&self, workstream_id: &str, tags: &[String]) -> Result<()> {
@@ -317,13 +366,51 @@ impl WorkstreamStore {
             turn_count: None,
             summary: None,
             compressed: false,
+            parent_session_id: None,
+            forked_from_message_id: None,
+        })
+    }
+
+    /// Create a session that records the session it was forked from.
+    pub fn create_forked_session(
+        &self,
+        workstream_id: &str,
+        parent_session_id: &str,
+        forked_from_message_id: Option<&str>,
+    ) -> Result<Session> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        self.conn().execute(
+            "INSERT INTO sessions (id, workstream_id, started_at, parent_session_id, forked_from_message_id)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id,
+                workstream_id,
+                now.to_rfc3339(),
+                parent_session_id,
+                forked_from_message_id
+            ],
+        )?;
+
+        Ok(Session {
+            id,
+            workstream_id: workstream_id.to_string(),
+            started_at: now,
+            ended_at: None,
+            turn_count: None,
+            summary: None,
+            compressed: false,
+            parent_session_id: Some(parent_session_id.to_string()),
+            forked_from_message_id: forked_from_message_id.map(String::from),
         })
     }
 
     pub fn get_session(&self, id: &str) -> Result<Session> {
         self.conn()
             .query_row(
-                "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed
+                "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed,
+                        parent_session_id, forked_from_message_id
                  FROM sessions WHERE id = ?1",
                 params![id],
                 row_to_session,
@@ -336,7 +423,8 @@ impl WorkstreamStore {
         Ok(self
             .conn()
             .query_row(
-                "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed
+                "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed,
+                        parent_session_id, forked_from_message_id
                  FROM sessions WHERE workstream_id = ?1 AND ended_at IS NULL
                  ORDER BY started_at DESC LIMIT 1",
                 params![workstream_id],
@@ -439,7 +527,8 @@ impl WorkstreamStore {
     pub fn list_sessions(&self, workstream_id: &str) -> Result<Vec<Session>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed
+            "SELECT id, workstream_id, started_at, ended_at, turn_count, summary, compressed,
+                        parent_session_id, forked_from_message_id
              FROM sessions WHERE workstream_id = ?1 ORDER BY started_at DESC",
         )?;
         let iter = stmt.query_map(params![workstream_id], row_to_session)?;
@@ -450,6 +539,69 @@ impl WorkstreamStore {
         Ok(sessions)
     }
 
+    // ── Import ──────────────────────────────────────────────────────
+
+    /// Insert a workstream with its tags and sessions exactly as given.
+    ///
+    /// Used when importing an archive. Runs in a single transaction, so a
+    /// conflicting ID leaves nothing behind.
+    pub fn import_workstream(
+        &self,
+        ws: &Workstream,
+        tags: &[String],
+        sessions: &[Session],
+    ) -> Result<()> {
+        let settings = if ws.settings.is_default() {
+            None
+        } else {
+            Some(serde_json::to_string(&ws.settings)?)
+        };
+
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO workstreams (id, title, summary, is_scratch, state, default_model, created_at, updated_at, settings)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                ws.id,
+                ws.title,
+                ws.summary,
+                ws.is_scratch as i32,
+                ws.state,
+                ws.default_model,
+                ws.created_at.to_rfc3339(),
+                ws.updated_at.to_rfc3339(),
+                settings
+            ],
+        )?;
+        for tag in tags {
+            tx.execute(
+                "INSERT INTO workstream_tags (workstream_id, tag) VALUES (?1, ?2)",
+                params![ws.id, tag],
+            )?;
+        }
+        for s in sessions {
+            tx.execute(
+                "INSERT INTO sessions (id, workstream_id, started_at, ended_at, turn_count, summary,
+                                       compressed, parent_session_id, forked_from_message_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    s.id,
+                    ws.id,
+                    s.started_at.to_rfc3339(),
+                    s.ended_at.map(|t| t.to_rfc3339()),
+                    s.turn_count,
+                    s.summary,
+                    s.compressed as i32,
+                    s.parent_session_id,
+                    s.forked_from_message_id
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     // ── Scratch ─────────────────────────────────────────────────────
 
     /// Ensure the well-known scratch workstream exists, creating it if missing.
@@ -464,7 +616,7 @@ impl WorkstreamStore {
 
 // ── Helpers ─────────────────────────────────────────────────────────
 
-fn parse_dt(s: &str) -> DateTime<Utc> {
+pub(crate) fn parse_dt(s: &str) -> DateTime<Utc> {
     DateTime::parse_from_rfc3339(s)
         .map(|dt| dt.with_timezone(&Utc))
         .unwrap_or_else(|e| {
@@ -485,11 +637,22 @@ fn row_to_workstream(row: &rusqlite::Row<'_>) -> rusqlite::Result<Workstream> {
         is_scratch: row.get::<_, i32>(3)? != 0,
         state: row.get(4)?,
         default_model: row.get(5)?,
+        settings: parse_settings(row.get::<_, Option<String>>(8)?.as_deref()),
         created_at: parse_dt(&row.get::<_, String>(6)?),
         updated_at: parse_dt(&row.get::<_, String>(7)?),
     })
 }
 
+fn parse_settings(json: Option<&str>) -> AgentSettings {
+    let Some(json) = json else {
+        return AgentSettings::default();
+    };
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to parse workstream settings, using defaults");
+        AgentSettings::default()
+    })
+}
+
 fn row_to_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<Session> {
     Ok(Session {
         id: row.get(0)?,
@@ -499,6 +662,8 @@ fn row_to_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<Session> {
         turn_count: row.get(4)?,
         summary: row.get(5)?,
         compressed: row.get::<_, i32>(6)? != 0,
+        parent_session_id: row.get(7)?,
+        forked_from_message_id: row.get(8)?,
     })
 }
 
@@ -535,6 +700,10 @@ impl crate::storage::WorkstreamStorage for WorkstreamStore {
         WorkstreamStore::update_workstream(self, id, title, summary, state, default_model)
     }
 
+    fn update_settings(&self, id: &str, settings: &AgentSettings) -> Result<()> {
+        WorkstreamStore::update_settings(self, id, settings)
+    }
+
     fn set_tags(&self, workstream_id: &str, tags: &[String]) -> Result<()> {
         WorkstreamStore::set_tags(self, workstream_id, tags)
     }
@@ -616,6 +785,44 @@ mod tests {
         assert!(archived.is_empty());
     }
 
+    #[test]
+    fn test_workstream_settings_roundtrip() {
+        let store = test_store();
+        let ws = store
+            .create_workstream("Research", Some("claude-sonnet"), false)
+            .unwrap();
+        assert!(ws.settings.is_default());
+        assert_eq!(ws.agent_settings().model.as_deref(), Some("claude-sonnet"));
+
+        let settings = AgentSettings {
+            profile: Some("fast".to_string()),
+            denied_tools: vec!["shell".to_string()],
+            memory_scope: arawn_types::MemoryScope::Session,
+            ..Default::default()
+        };
+        store.update_settings(&ws.id, &settings).unwrap();
+
+        let fetched = store.get_workstream(&ws.id).unwrap();
+        assert_eq!(fetched.settings, settings);
+        assert_eq!(
+            fetched.agent_settings().model.as_deref(),
+            Some("claude-sonnet")
+        );
+        let listed = store.list_workstreams(None).unwrap();
+        assert_eq!(listed[0].settings, settings);
+
+        // Clearing stores NULL and reads back as defaults
+        store
+            .update_settings(&ws.id, &AgentSettings::default())
+            .unwrap();
+        assert!(store.get_workstream(&ws.id).unwrap().settings.is_default());
+
+        assert!(matches!(
+            store.update_settings("missing", &settings),
+            Err(WorkstreamError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_tags() {
         let store = test_store();
@@ -677,4 +884,30 @@ mod tests {
         let err = store.get_workstream("nonexistent").unwrap_err();
         assert!(matches!(err, WorkstreamError::NotFound(_)));
     }
+
+    #[test]
+    fn test_import_workstream_roundtrip() {
+        let source = test_store();
+        let ws = source.create_workstream("Imported", None, false).unwrap();
+        let mut session = source.create_session(&ws.id).unwrap();
+        source.end_session(&session.id, 4).unwrap();
+        session = source.get_session(&session.id).unwrap();
+
+        let target = test_store();
+        target
+            .import_workstream(&ws, &["rust".to_string()], std::slice::from_ref(&session))
+            .unwrap();
+
+        let imported = target.get_workstream(&ws.id).unwrap();
+        assert_eq!(imported.title, "Imported");
+        assert_eq!(target.get_tags(&ws.id).unwrap(), vec!["rust"]);
+        let sessions = target.list_sessions(&ws.id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].turn_count, Some(4));
+
+        // A second import of the same IDs fails and leaves the first intact
+        let err = target.import_workstream(&ws, &[], &[]);
+        assert!(err.is_err());
+        assert_eq!(target.list_sessions(&ws.id).unwrap().len(), 1);
+    }
 }
@@ -36,6 +36,17 @@ impl std::fmt::Display for MessageRole {
     }
 }
 
+/// Where a fork stops copying its parent session's history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkPoint {
+    /// Copy up to and including the message with this id.
+    Message(String),
+    /// Copy the first `n` turns (everything before the `n+1`th user message).
+    Turns(usize),
+    /// Copy the whole session.
+    End,
+}
+
 /// A single message in a workstream's conversation history.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkstreamMessage {
@@ -0,0 +1,574 @@
+//! Persistent token usage and cost ledger.
+//!
+//! Every LLM call made by the agent is aggregated into one row per
+//! `(day, session, workstream, profile, model)`, so the ledger stays small no
+//! matter how many calls are made. Reports group the rows along a single
+//! dimension, and [`UsageLedger`] implements [`UsageRecorder`] so the agent
+//! can check workstream spend limits before each call.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use arawn_types::{BudgetStatus, UsageEntry, UsageError, UsageRecorder};
+use chrono::{Datelike, NaiveDate, Utc};
+use parking_lot::Mutex;
+use rusqlite::{Connection, params, params_from_iter};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Workstream key used for calls made outside any workstream.
+const NO_WORKSTREAM: &str = "";
+
+// ── Limits ──────────────────────────────────────────────────────────────
+
+/// Window over which spend is summed when checking limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LimitPeriod {
+    /// The current UTC day.
+    Day,
+    /// The current UTC calendar month.
+    #[default]
+    Month,
+    /// All recorded usage.
+    Total,
+}
+
+impl LimitPeriod {
+    /// First day included in the period containing `today` (`None` = unbounded).
+    pub fn start(&self, today: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Day => Some(today),
+            Self::Month => today.with_day(1),
+            Self::Total => None,
+        }
+    }
+}
+
+/// Soft and hard spend limits in USD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpendLimit {
+    /// Spend at which a warning is raised.
+    pub soft_usd: Option<f64>,
+    /// Spend at which the agent stops making LLM calls.
+    pub hard_usd: Option<f64>,
+}
+
+impl SpendLimit {
+    /// Create a limit with optional soft and hard thresholds.
+    pub fn new(soft_usd: Option<f64>, hard_usd: Option<f64>) -> Self {
+        Self { soft_usd, hard_usd }
+    }
+
+    /// Whether neither threshold is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.soft_usd.is_none() && self.hard_usd.is_none()
+    }
+
+    /// Evaluate spend against this limit.
+    pub fn status(&self, spent_usd: f64) -> BudgetStatus {
+        if let Some(limit_usd) = self.hard_usd
+            && spent_usd >= limit_usd
+        {
+            return BudgetStatus::HardLimitExceeded {
+                spent_usd,
+                limit_usd,
+            };
+        }
+        if let Some(limit_usd) = self.soft_usd
+            && spent_usd >= limit_usd
+        {
+            return BudgetStatus::SoftLimitExceeded {
+                spent_usd,
+                limit_usd,
+            };
+        }
+        BudgetStatus::WithinLimit
+    }
+}
+
+/// Spend limits for all workstreams, with per-workstream overrides.
+#[derive(Debug, Clone, Default)]
+pub struct SpendLimits {
+    period: LimitPeriod,
+    default: SpendLimit,
+    workstreams: HashMap<String, SpendLimit>,
+}
+
+impl SpendLimits {
+    /// Create limits summed over the given period, with no thresholds set.
+    pub fn new(period: LimitPeriod) -> Self {
+        Self {
+            period,
+            ..Default::default()
+        }
+    }
+
+    /// Set the limit applied to workstreams without an override.
+    pub fn with_default(mut self, limit: SpendLimit) -> Self {
+        self.default = limit;
+        self
+    }
+
+    /// Override the limit for one workstream.
+    pub fn with_workstream(mut self, workstream_id: impl Into<String>, limit: SpendLimit) -> Self {
+        self.workstreams.insert(workstream_id.into(), limit);
+        self
+    }
+
+    /// The period spend is summed over.
+    pub fn period(&self) -> LimitPeriod {
+        self.period
+    }
+
+    /// Effective limit for a workstream; unset override fields fall back to the default.
+    pub fn limit_for(&self, workstream_id: &str) -> SpendLimit {
+        match self.workstreams.get(workstream_id) {
+            Some(limit) => SpendLimit {
+                soft_usd: limit.soft_usd.or(self.default.soft_usd),
+                hard_usd: limit.hard_usd.or(self.default.hard_usd),
+            },
+            None => self.default,
+        }
+    }
+}
+
+// ── Reports ─────────────────────────────────────────────────────────────
+
+/// Dimension a usage report is grouped by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageGroupBy {
+    #[default]
+    Day,
+    Session,
+    Workstream,
+    Profile,
+    Model,
+}
+
+impl UsageGroupBy {
+    fn column(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Session => "session_id",
+            Self::Workstream => "workstream_id",
+            Self::Profile => "profile",
+            Self::Model => "model",
+        }
+    }
+}
+
+impl FromStr for UsageGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "session" => Ok(Self::Session),
+            "workstream" => Ok(Self::Workstream),
+            "profile" => Ok(Self::Profile),
+            "model" => Ok(Self::Model),
+            other => Err(format!(
+                "invalid group '{}' (expected day, session, workstream, profile or model)",
+                other
+            )),
+        }
+    }
+}
+
+/// Filters and grouping for a usage report.
+#[derive(Debug, Clone, Default)]
+pub struct UsageQuery {
+    pub workstream_id: Option<String>,
+    pub session_id: Option<String>,
+    pub profile: Option<String>,
+    /// First day included (inclusive).
+    pub since: Option<NaiveDate>,
+    /// Last day included (inclusive).
+    pub until: Option<NaiveDate>,
+    pub group_by: UsageGroupBy,
+}
+
+/// Aggregated usage for one group (or the report total).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageRow {
+    /// Group value (day, session ID, ...); `total` for the report total.
+    pub key: String,
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Result of a usage query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub group_by: UsageGroupBy,
+    pub rows: Vec<UsageRow>,
+    pub total: UsageRow,
+}
+
+// ── Ledger ──────────────────────────────────────────────────────────────
+
+/// SQLite-backed usage ledger.
+///
+/// Thread-safe via internal `Mutex<Connection>`.
+pub struct UsageLedger {
+    conn: Mutex<Connection>,
+    limits: SpendLimits,
+}
+
+impl UsageLedger {
+    /// Open (or create) the ledger database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::init(conn)
+    }
+
+    /// Open an in-memory ledger (for testing).
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS usage_ledger (
+                day                          TEXT    NOT NULL,
+                session_id                   TEXT    NOT NULL,
+                workstream_id                TEXT    NOT NULL,
+                profile                      TEXT    NOT NULL,
+                model                        TEXT    NOT NULL,
+                calls                        INTEGER NOT NULL DEFAULT 0,
+                input_tokens                 INTEGER NOT NULL DEFAULT 0,
+                output_tokens                INTEGER NOT NULL DEFAULT 0,
+                cache_creation_input_tokens  INTEGER NOT NULL DEFAULT 0,
+                cache_read_input_tokens      INTEGER NOT NULL DEFAULT 0,
+                cost_usd                     REAL    NOT NULL DEFAULT 0,
+                PRIMARY KEY (day, session_id, workstream_id, profile, model)
+            );
+            CREATE INDEX IF NOT EXISTS idx_usage_workstream_day
+                ON usage_ledger (workstream_id, day);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            limits: SpendLimits::default(),
+        })
+    }
+
+    /// Set the spend limits enforced through [`UsageRecorder::budget_status`].
+    pub fn with_limits(mut self, limits: SpendLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The configured spend limits.
+    pub fn limits(&self) -> &SpendLimits {
+        &self.limits
+    }
+
+    /// Add an entry to the ledger row for `day`.
+    pub fn record_on(&self, day: NaiveDate, entry: &UsageEntry) -> Result<()> {
+        self.conn.lock().execute(
+            "INSERT INTO usage_ledger (day, session_id, workstream_id, profile, model, calls,
+                 input_tokens, output_tokens, cache_creation_input_tokens,
+                 cache_read_input_tokens, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT (day, session_id, workstream_id, profile, model) DO UPDATE SET
+                 calls = calls + 1,
+                 input_tokens = input_tokens + excluded.input_tokens,
+                 output_tokens = output_tokens + excluded.output_tokens,
+                 cache_creation_input_tokens =
+                     cache_creation_input_tokens + excluded.cache_creation_input_tokens,
+                 cache_read_input_tokens =
+                     cache_read_input_tokens + excluded.cache_read_input_tokens,
+                 cost_usd = cost_usd + excluded.cost_usd",
+            params![
+                day.to_string(),
+                entry.session_id,
+                entry.workstream_id.as_deref().unwrap_or(NO_WORKSTREAM),
+                entry.profile,
+                entry.model,
+                entry.input_tokens,
+                entry.output_tokens,
+                entry.cache_creation_input_tokens,
+                entry.cache_read_input_tokens,
+                entry.cost_usd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Total spend of a workstream from `since` (inclusive) onwards.
+    pub fn spend_since(&self, workstream_id: &str, since: Option<NaiveDate>) -> Result<f64> {
+        let since = since.map(|d| d.to_string()).unwrap_or_default();
+        let spent = self.conn.lock().query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0) FROM usage_ledger
+             WHERE workstream_id = ?1 AND day >= ?2",
+            params![workstream_id, since],
+            |row| row.get(0),
+        )?;
+        Ok(spent)
+    }
+
+    /// Spend state of a workstream for the limit period containing `today`.
+    pub fn budget_status_on(&self, workstream_id: &str, today: NaiveDate) -> Result<BudgetStatus> {
+        let limit = self.limits.limit_for(workstream_id);
+        if limit.is_unlimited() {
+            return Ok(BudgetStatus::WithinLimit);
+        }
+        let spent = self.spend_since(workstream_id, self.limits.period.start(today))?;
+        Ok(limit.status(spent))
+    }
+
+    /// Aggregate usage matching the query, grouped along one dimension.
+    pub fn report(&self, query: &UsageQuery) -> Result<UsageReport> {
+        let mut clauses = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+        let mut filter = |clause: &str, value: String| {
+            values.push(value);
+            clauses.push(format!("{} ?{}", clause, values.len()));
+        };
+        if let Some(ref ws) = query.workstream_id {
+            filter("workstream_id =", ws.clone());
+        }
+        if let Some(ref session) = query.session_id {
+            filter("session_id =", session.clone());
+        }
+        if let Some(ref profile) = query.profile {
+            filter("profile =", profile.clone());
+        }
+        if let Some(since) = query.since {
+            filter("day >=", since.to_string());
+        }
+        if let Some(until) = query.until {
+            filter("day <=", until.to_string());
+        }
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let column = query.group_by.column();
+        let sql = format!(
+            "SELECT {column}, SUM(calls), SUM(input_tokens), SUM(output_tokens),
+                    SUM(cache_creation_input_tokens), SUM(cache_read_input_tokens), SUM(cost_usd)
+             FROM usage_ledger {where_clause}
+             GROUP BY {column} ORDER BY {column}"
+        );
+
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map(params_from_iter(values.iter()), |row| {
+                Ok(UsageRow {
+                    key: row.get(0)?,
+                    calls: row.get::<_, i64>(1)? as u64,
+                    input_tokens: row.get::<_, i64>(2)? as u64,
+                    output_tokens: row.get::<_, i64>(3)? as u64,
+                    cache_creation_input_tokens: row.get::<_, i64>(4)? as u64,
+                    cache_read_input_tokens: row.get::<_, i64>(5)? as u64,
+                    cost_usd: row.get(6)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut total = UsageRow {
+            key: "total".to_string(),
+            ..Default::default()
+        };
+        for row in &rows {
+            total.calls += row.calls;
+            total.input_tokens += row.input_tokens;
+            total.output_tokens += row.output_tokens;
+            total.cache_creation_input_tokens += row.cache_creation_input_tokens;
+            total.cache_read_input_tokens += row.cache_read_input_tokens;
+            total.cost_usd += row.cost_usd;
+        }
+
+        Ok(UsageReport {
+            group_by: query.group_by,
+            rows,
+            total,
+        })
+    }
+}
+
+impl UsageRecorder for UsageLedger {
+    fn record(&self, entry: &UsageEntry) -> std::result::Result<(), UsageError> {
+        self.record_on(Utc::now().date_naive(), entry)
+            .map_err(|e| UsageError::Storage(e.to_string()))
+    }
+
+    fn budget_status(&self, workstream_id: &str) -> BudgetStatus {
+        // Fail open: a broken ledger must not take the agent down with it
+        self.budget_status_on(workstream_id, Utc::now().date_naive())
+            .unwrap_or_else(|e| {
+                tracing::warn!(workstream_id, error = %e, "Failed to read workstream spend");
+                BudgetStatus::WithinLimit
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn entry(session: &str, ws: Option<&str>, profile: &str, cost: f64) -> UsageEntry {
+        UsageEntry {
+            session_id: session.to_string(),
+            workstream_id: ws.map(String::from),
+            profile: profile.to_string(),
+            model: "model-a".to_string(),
+            input_tokens: 100,
+            output_tokens: 20,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: 5,
+            cost_usd: cost,
+        }
+    }
+
+    #[test]
+    fn test_record_aggregates_per_key() {
+        let ledger = UsageLedger::open_in_memory().unwrap();
+        let d = day("2026-03-01");
+        ledger
+            .record_on(d, &entry("s1", Some("ws"), "default", 0.5))
+            .unwrap();
+        ledger
+            .record_on(d, &entry("s1", Some("ws"), "default", 0.25))
+            .unwrap();
+
+        let report = ledger.report(&UsageQuery::default()).unwrap();
+        assert_eq!(report.rows.len(), 1);
+        let row = &report.rows[0];
+        assert_eq!(row.key, "2026-03-01");
+        assert_eq!(row.calls, 2);
+        assert_eq!(row.input_tokens, 200);
+        assert_eq!(row.output_tokens, 40);
+        assert_eq!(row.cache_read_input_tokens, 10);
+        assert!((row.cost_usd - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_report_group_and_filter() {
+        let ledger = UsageLedger::open_in_memory().unwrap();
+        ledger
+            .record_on(day("2026-03-01"), &entry("s1", Some("ws-a"), "fast", 0.1))
+            .unwrap();
+        ledger
+            .record_on(
+                day("2026-03-02"),
+                &entry("s2", Some("ws-a"), "quality", 1.0),
+            )
+            .unwrap();
+        ledger
+            .record_on(day("2026-03-02"), &entry("s3", Some("ws-b"), "fast", 0.2))
+            .unwrap();
+        ledger
+            .record_on(day("2026-03-03"), &entry("s4", None, "fast", 0.3))
+            .unwrap();
+
+        let by_profile = ledger
+            .report(&UsageQuery {
+                group_by: UsageGroupBy::Profile,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_profile.rows.len(), 2);
+        assert_eq!(by_profile.rows[0].key, "fast");
+        assert_eq!(by_profile.rows[0].calls, 3);
+        assert!((by_profile.total.cost_usd - 1.6).abs() < 1e-9);
+
+        let ws_a = ledger
+            .report(&UsageQuery {
+                workstream_id: Some("ws-a".to_string()),
+                since: Some(day("2026-03-02")),
+                group_by: UsageGroupBy::Session,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(ws_a.rows.len(), 1);
+        assert_eq!(ws_a.rows[0].key, "s2");
+
+        let by_ws = ledger
+            .report(&UsageQuery {
+                until: Some(day("2026-03-03")),
+                group_by: UsageGroupBy::Workstream,
+                ..Default::default()
+            })
+            .unwrap();
+        let keys: Vec<_> = by_ws.rows.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["", "ws-a", "ws-b"]);
+    }
+
+    #[test]
+    fn test_budget_status_uses_period() {
+        let limits = SpendLimits::new(LimitPeriod::Month)
+            .with_default(SpendLimit::new(Some(1.0), Some(2.0)))
+            .with_workstream("big", SpendLimit::new(None, Some(10.0)));
+        let ledger = UsageLedger::open_in_memory().unwrap().with_limits(limits);
+
+        // Last month's spend does not count towards this month
+        ledger
+            .record_on(day("2026-02-28"), &entry("s1", Some("ws"), "default", 5.0))
+            .unwrap();
+        let today = day("2026-03-15");
+        assert_eq!(
+            ledger.budget_status_on("ws", today).unwrap(),
+            BudgetStatus::WithinLimit
+        );
+
+        ledger
+            .record_on(day("2026-03-01"), &entry("s1", Some("ws"), "default", 1.5))
+            .unwrap();
+        assert!(matches!(
+            ledger.budget_status_on("ws", today).unwrap(),
+            BudgetStatus::SoftLimitExceeded { .. }
+        ));
+
+        ledger
+            .record_on(day("2026-03-10"), &entry("s1", Some("ws"), "default", 1.0))
+            .unwrap();
+        assert!(ledger.budget_status_on("ws", today).unwrap().is_exhausted());
+
+        // Override keeps the default soft limit but raises the hard one
+        ledger
+            .record_on(day("2026-03-10"), &entry("s2", Some("big"), "default", 3.0))
+            .unwrap();
+        assert!(matches!(
+            ledger.budget_status_on("big", today).unwrap(),
+            BudgetStatus::SoftLimitExceeded { limit_usd, .. } if limit_usd == 1.0
+        ));
+    }
+
+    #[test]
+    fn test_group_by_from_str() {
+        assert_eq!("profile".parse::<UsageGroupBy>(), Ok(UsageGroupBy::Profile));
+        assert!("week".parse::<UsageGroupBy>().is_err());
+    }
+
+    #[test]
+    fn test_open_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.db");
+        {
+            let ledger = UsageLedger::open(&path).unwrap();
+            ledger
+                .record(&entry("s1", Some("ws"), "default", 0.5))
+                .unwrap();
+        }
+        let ledger = UsageLedger::open(&path).unwrap();
+        assert!((ledger.spend_since("ws", None).unwrap() - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,699 @@
+//! Outbound webhook subscriptions and dead letters.
+//!
+//! A [`WebhookSubscription`] names a URL, the [`WebhookEventKind`]s it wants
+//! and, optionally, the workstreams it cares about. Its secret is stored in
+//! plain text because the server needs it to sign every delivery. Deliveries
+//! that still fail after every retry are kept as [`DeadLetter`]s so they can
+//! be inspected and redelivered.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use crate::store::parse_dt;
+use crate::{Result, WorkstreamError};
+
+/// Prefix of generated signing secrets.
+pub const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+// ── Event kinds ─────────────────────────────────────────────────────────
+
+/// Kind of event a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// A session was closed.
+    SessionEnded,
+    /// An agent turn finished and was saved.
+    TurnCompleted,
+    /// A tool call in a turn failed.
+    ToolFailed,
+    /// A background subagent finished.
+    SubagentCompleted,
+    /// A workflow run finished.
+    WorkflowFinished,
+    /// Disk usage crossed a warning threshold.
+    DiskPressure,
+    /// A memory was stored.
+    MemoryStored,
+}
+
+impl WebhookEventKind {
+    /// All event kinds.
+    pub const ALL: [WebhookEventKind; 7] = [
+        Self::SessionEnded,
+        Self::TurnCompleted,
+        Self::ToolFailed,
+        Self::SubagentCompleted,
+        Self::WorkflowFinished,
+        Self::DiskPressure,
+        Self::MemoryStored,
+    ];
+
+    /// Event name as used in payloads and the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SessionEnded => "session_ended",
+            Self::TurnCompleted => "turn_completed",
+            Self::ToolFailed => "tool_failed",
+            Self::SubagentCompleted => "subagent_completed",
+            Self::WorkflowFinished => "workflow_finished",
+            Self::DiskPressure => "disk_pressure",
+            Self::MemoryStored => "memory_stored",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WebhookEventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("unknown webhook event '{}'", s))
+    }
+}
+
+// ── Records ─────────────────────────────────────────────────────────────
+
+/// A persisted webhook subscription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    /// Endpoint deliveries are POSTed to.
+    pub url: String,
+    /// HMAC-SHA256 signing secret.
+    pub secret: String,
+    /// Events delivered; empty means every event.
+    pub events: Vec<WebhookEventKind>,
+    /// Workstreams whose events are delivered; empty means every event,
+    /// including those not tied to a workstream.
+    pub workstreams: Vec<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// Whether an event of `kind` in `workstream` should be delivered.
+    ///
+    /// Subscriptions filtered by workstream never receive events that aren't
+    /// tied to one.
+    pub fn matches(&self, kind: WebhookEventKind, workstream: Option<&str>) -> bool {
+        self.enabled
+            && (self.events.is_empty() || self.events.contains(&kind))
+            && (self.workstreams.is_empty()
+                || workstream.is_some_and(|ws| self.workstreams.iter().any(|w| w == ws)))
+    }
+}
+
+/// Fields of a new subscription.
+#[derive(Debug, Clone, Default)]
+pub struct NewWebhook {
+    pub url: String,
+    /// Signing secret; generated when `None`.
+    pub secret: Option<String>,
+    pub events: Vec<WebhookEventKind>,
+    pub workstreams: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// Changes to an existing subscription; `None` leaves a field unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookUpdate {
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEventKind>>,
+    pub workstreams: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// A delivery that failed after every retry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub id: String,
+    pub subscription_id: String,
+    /// ID of the event that failed to deliver.
+    pub event_id: String,
+    pub event: WebhookEventKind,
+    /// The exact JSON body that was sent.
+    pub payload: String,
+    pub attempts: u32,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    pub last_status: Option<u16>,
+    pub last_error: String,
+    pub created_at: DateTime<Utc>,
+}
+
+fn generate_secret() -> String {
+    format!("{}{}", WEBHOOK_SECRET_PREFIX, uuid::Uuid::new_v4().simple())
+}
+
+fn validate_url(url: &str) -> Result<()> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(WorkstreamError::InvalidOperation(format!(
+            "webhook URL must start with http:// or https:// (got '{}')",
+            url
+        )))
+    }
+}
+
+fn normalize_events(mut events: Vec<WebhookEventKind>) -> Vec<WebhookEventKind> {
+    events.sort_by_key(|e| WebhookEventKind::ALL.iter().position(|a| a == e));
+    events.dedup();
+    events
+}
+
+// ── Store ───────────────────────────────────────────────────────────────
+
+/// SQLite-backed store of webhook subscriptions and dead letters.
+///
+/// Thread-safe via internal `Mutex<Connection>`.
+pub struct WebhookStore {
+    conn: Mutex<Connection>,
+}
+
+impl WebhookStore {
+    /// Open (or create) the webhook database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        Self::init(conn)
+    }
+
+    /// Open an in-memory store (for testing).
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "PRAGMA foreign_keys=ON;
+            CREATE TABLE IF NOT EXISTS webhooks (
+                id            TEXT PRIMARY KEY,
+                url           TEXT NOT NULL,
+                secret        TEXT NOT NULL,
+                events        TEXT NOT NULL,
+                workstreams   TEXT NOT NULL,
+                description   TEXT,
+                enabled       INTEGER NOT NULL DEFAULT 1,
+                created_at    TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+                id               TEXT PRIMARY KEY,
+                subscription_id  TEXT NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+                event_id         TEXT NOT NULL,
+                event            TEXT NOT NULL,
+                payload          TEXT NOT NULL,
+                attempts         INTEGER NOT NULL,
+                last_status      INTEGER,
+                last_error       TEXT NOT NULL,
+                created_at       TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_subscription
+                ON webhook_dead_letters (subscription_id, created_at);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    // ── Subscriptions ───────────────────────────────────────────────────
+
+    /// Create a subscription.
+    pub fn create(&self, new: NewWebhook) -> Result<WebhookSubscription> {
+        validate_url(&new.url)?;
+        if new.secret.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            return Err(WorkstreamError::InvalidOperation(
+                "webhook secret must not be empty".to_string(),
+            ));
+        }
+
+        let webhook = WebhookSubscription {
+            id: uuid::Uuid::new_v4().to_string(),
+            url: new.url,
+            secret: new.secret.unwrap_or_else(generate_secret),
+            events: normalize_events(new.events),
+            workstreams: new.workstreams,
+            description: new.description,
+            enabled: true,
+            created_at: Utc::now(),
+        };
+        self.conn.lock().execute(
+            "INSERT INTO webhooks (id, url, secret, events, workstreams, description, enabled, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7)",
+            params![
+                webhook.id,
+                webhook.url,
+                webhook.secret,
+                serde_json::to_string(&webhook.events)?,
+                serde_json::to_string(&webhook.workstreams)?,
+                webhook.description,
+                webhook.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(webhook)
+    }
+
+    /// All subscriptions, oldest first.
+    pub fn list(&self) -> Result<Vec<WebhookSubscription>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, url, secret, events, workstreams, description, enabled, created_at
+             FROM webhooks ORDER BY created_at, id",
+        )?;
+        let rows = stmt.query_map([], row_to_webhook)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// A subscription by ID.
+    pub fn get(&self, id: &str) -> Result<Option<WebhookSubscription>> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT id, url, secret, events, workstreams, description, enabled, created_at
+                 FROM webhooks WHERE id = ?1",
+                params![id],
+                row_to_webhook,
+            )
+            .optional()?)
+    }
+
+    /// Subscriptions that should receive an event of `kind` in `workstream`.
+    pub fn subscribers(
+        &self,
+        kind: WebhookEventKind,
+        workstream: Option<&str>,
+    ) -> Result<Vec<WebhookSubscription>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|w| w.matches(kind, workstream))
+            .collect())
+    }
+
+    /// Apply `update` to a subscription and return the result.
+    pub fn update(&self, id: &str, update: WebhookUpdate) -> Result<WebhookSubscription> {
+        let mut webhook = self
+            .get(id)?
+            .ok_or_else(|| WorkstreamError::NotFound(format!("webhook {}", id)))?;
+        if let Some(url) = update.url {
+            validate_url(&url)?;
+            webhook.url = url;
+        }
+        if let Some(events) = update.events {
+            webhook.events = normalize_events(events);
+        }
+        if let Some(workstreams) = update.workstreams {
+            webhook.workstreams = workstreams;
+        }
+        if let Some(description) = update.description {
+            webhook.description = Some(description);
+        }
+        if let Some(enabled) = update.enabled {
+            webhook.enabled = enabled;
+        }
+
+        self.conn.lock().execute(
+            "UPDATE webhooks SET url = ?2, events = ?3, workstreams = ?4, description = ?5,
+                 enabled = ?6
+             WHERE id = ?1",
+            params![
+                webhook.id,
+                webhook.url,
+                serde_json::to_string(&webhook.events)?,
+                serde_json::to_string(&webhook.workstreams)?,
+                webhook.description,
+                webhook.enabled,
+            ],
+        )?;
+        Ok(webhook)
+    }
+
+    /// Delete a subscription and its dead letters.
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let deleted = self
+            .conn
+            .lock()
+            .execute("DELETE FROM webhooks WHERE id = ?1", params![id])?;
+        if deleted == 0 {
+            return Err(WorkstreamError::NotFound(format!("webhook {}", id)));
+        }
+        Ok(())
+    }
+
+    // ── Dead letters ────────────────────────────────────────────────────
+
+    /// Record a delivery that failed after every retry.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_dead_letter(
+        &self,
+        subscription_id: &str,
+        event_id: &str,
+        event: WebhookEventKind,
+        payload: &str,
+        attempts: u32,
+        last_status: Option<u16>,
+        last_error: &str,
+    ) -> Result<DeadLetter> {
+        let dead = DeadLetter {
+            id: uuid::Uuid::new_v4().to_string(),
+            subscription_id: subscription_id.to_string(),
+            event_id: event_id.to_string(),
+            event,
+            payload: payload.to_string(),
+            attempts,
+            last_status,
+            last_error: last_error.to_string(),
+            created_at: Utc::now(),
+        };
+        self.conn.lock().execute(
+            "INSERT INTO webhook_dead_letters
+                 (id, subscription_id, event_id, event, payload, attempts, last_status, last_error, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                dead.id,
+                dead.subscription_id,
+                dead.event_id,
+                dead.event.as_str(),
+                dead.payload,
+                dead.attempts,
+                dead.last_status,
+                dead.last_error,
+                dead.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(dead)
+    }
+
+    /// Dead letters, newest first, optionally for one subscription.
+    pub fn dead_letters(
+        &self,
+        subscription_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DeadLetter>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, subscription_id, event_id, event, payload, attempts, last_status,
+                    last_error, created_at
+             FROM webhook_dead_letters
+             WHERE ?1 IS NULL OR subscription_id = ?1
+             ORDER BY created_at DESC, id
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![subscription_id, limit as i64], row_to_dead_letter)?;
+        Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    /// A dead letter by ID.
+    pub fn dead_letter(&self, id: &str) -> Result<Option<DeadLetter>> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT id, subscription_id, event_id, event, payload, attempts, last_status,
+                        last_error, created_at
+                 FROM webhook_dead_letters WHERE id = ?1",
+                params![id],
+                row_to_dead_letter,
+            )
+            .optional()?)
+    }
+
+    /// Delete a dead letter (e.g. after a successful redelivery).
+    pub fn delete_dead_letter(&self, id: &str) -> Result<()> {
+        let deleted = self.conn.lock().execute(
+            "DELETE FROM webhook_dead_letters WHERE id = ?1",
+            params![id],
+        )?;
+        if deleted == 0 {
+            return Err(WorkstreamError::NotFound(format!("dead letter {}", id)));
+        }
+        Ok(())
+    }
+}
+
+fn row_to_webhook(row: &rusqlite::Row<'_>) -> rusqlite::Result<WebhookSubscription> {
+    let events: String = row.get(3)?;
+    let workstreams: String = row.get(4)?;
+    Ok(WebhookSubscription {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        secret: row.get(2)?,
+        events: serde_json::from_str(&events).unwrap_or_default(),
+        workstreams: serde_json::from_str(&workstreams).unwrap_or_default(),
+        description: row.get(5)?,
+        enabled: row.get(6)?,
+        created_at: parse_dt(&row.get::<_, String>(7)?),
+    })
+}
+
+fn row_to_dead_letter(row: &rusqlite::Row<'_>) -> rusqlite::Result<DeadLetter> {
+    let event: String = row.get(3)?;
+    Ok(DeadLetter {
+        id: row.get(0)?,
+        subscription_id: row.get(1)?,
+        event_id: row.get(2)?,
+        event: event.parse().map_err(|e: String| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, e.into())
+        })?,
+        payload: row.get(4)?,
+        attempts: row.get(5)?,
+        last_status: row.get(6)?,
+        last_error: row.get(7)?,
+        created_at: parse_dt(&row.get::<_, String>(8)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_webhook(url: &str) -> NewWebhook {
+        NewWebhook {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_event_kind_parsing() {
+        for kind in WebhookEventKind::ALL {
+            assert_eq!(kind.as_str().parse::<WebhookEventKind>().unwrap(), kind);
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::json!(kind.as_str())
+            );
+        }
+        assert!("session_started".parse::<WebhookEventKind>().is_err());
+    }
+
+    #[test]
+    fn test_create_and_get() {
+        let store = WebhookStore::open_in_memory().unwrap();
+        let created = store
+            .create(NewWebhook {
+                url: "https://example.com/hook".to_string(),
+                events: vec![
+                    WebhookEventKind::MemoryStored,
+                    WebhookEventKind::SessionEnded,
+                    WebhookEventKind::MemoryStored,
+                ],
+                workstreams: vec!["ws1".to_string()],
+                description: Some("ci".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(created.secret.starts_with(WEBHOOK_SECRET_PREFIX));
+        assert!(created.enabled);
+        assert_eq!(
+            created.events,
+            vec![
+                WebhookEventKind::SessionEnded,
+                WebhookEventKind::MemoryStored
+            ]
+        );
+
+        assert_eq!(store.get(&created.id).unwrap().unwrap(), created);
+        assert!(store.get("missing").unwrap().is_none());
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_create_validation() {
+        let store = WebhookStore::open_in_memory().unwrap();
+        assert!(store.create(new_webhook("ftp://example.com")).is_err());
+        assert!(
+            store
+                .create(NewWebhook {
+                    url: "http://localhost:9000".to_string(),
+                    secret: Some("  ".to_string()),
+                    ..Default::default()
+                })
+                .is_err()
+        );
+        let custom = store
+            .create(NewWebhook {
+                url: "http://localhost:9000".to_string(),
+                secret: Some("s3cret".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(custom.secret, "s3cret");
+    }
+
+    #[test]
+    fn test_subscribers_filtering() {
+        let store = WebhookStore::open_in_memory().unwrap();
+        let all = store.create(new_webhook("http://a")).unwrap();
+        let filtered = store
+            .create(NewWebhook {
+                url: "http://b".to_string(),
+                events: vec![WebhookEventKind::ToolFailed],
+                workstreams: vec!["ws1".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let ids = |kind, ws| -> Vec<String> {
+            store
+                .subscribers(kind, ws)
+                .unwrap()
+                .into_iter()
+                .map(|w| w.id)
+                .collect()
+        };
+        assert_eq!(
+            ids(WebhookEventKind::ToolFailed, Some("ws1")),
+            vec![all.id.clone(), filtered.id.clone()]
+        );
+        assert_eq!(
+            ids(WebhookEventKind::ToolFailed, Some("ws2")),
+            vec![all.id.clone()]
+        );
+        assert_eq!(
+            ids(WebhookEventKind::ToolFailed, None),
+            vec![all.id.clone()]
+        );
+        assert_eq!(
+            ids(WebhookEventKind::SessionEnded, Some("ws1")),
+            vec![all.id.clone()]
+        );
+
+        store
+            .update(
+                &all.id,
+                WebhookUpdate {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(ids(WebhookEventKind::DiskPressure, None).is_empty());
+    }
+
+    #[test]
+    fn test_update_and_delete() {
+        let store = WebhookStore::open_in_memory().unwrap();
+        let webhook = store.create(new_webhook("http://a")).unwrap();
+
+        let updated = store
+            .update(
+                &webhook.id,
+                WebhookUpdate {
+                    url: Some("https://b".to_string()),
+                    events: Some(vec![WebhookEventKind::TurnCompleted]),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(updated.url, "https://b");
+        assert_eq!(updated.events, vec![WebhookEventKind::TurnCompleted]);
+        assert_eq!(updated.secret, webhook.secret);
+        assert_eq!(store.get(&webhook.id).unwrap().unwrap(), updated);
+
+        assert!(
+            store
+                .update(
+                    &webhook.id,
+                    WebhookUpdate {
+                        url: Some("nope".to_string()),
+                        ..Default::default()
+                    },
+                )
+                .is_err()
+        );
+        assert!(matches!(
+            store.update("missing", WebhookUpdate::default()),
+            Err(WorkstreamError::NotFound(_))
+        ));
+
+        store.delete(&webhook.id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(matches!(
+            store.delete(&webhook.id),
+            Err(WorkstreamError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_dead_letters() {
+        let store = WebhookStore::open_in_memory().unwrap();
+        let a = store.create(new_webhook("http://a")).unwrap();
+        let b = store.create(new_webhook("http://b")).unwrap();
+
+        let dead = store
+            .record_dead_letter(
+                &a.id,
+                "evt1",
+                WebhookEventKind::SessionEnded,
+                "{}",
+                5,
+                Some(500),
+                "HTTP 500",
+            )
+            .unwrap();
+        store
+            .record_dead_letter(
+                &b.id,
+                "evt2",
+                WebhookEventKind::DiskPressure,
+                "{}",
+                5,
+                None,
+                "connection refused",
+            )
+            .unwrap();
+
+        assert_eq!(store.dead_letters(None, 10).unwrap().len(), 2);
+        assert_eq!(store.dead_letters(None, 1).unwrap().len(), 1);
+        assert_eq!(
+            store.dead_letters(Some(&a.id), 10).unwrap(),
+            vec![dead.clone()]
+        );
+        assert_eq!(store.dead_letter(&dead.id).unwrap().unwrap(), dead);
+
+        store.delete_dead_letter(&dead.id).unwrap();
+        assert!(store.dead_letter(&dead.id).unwrap().is_none());
+        assert!(store.delete_dead_letter(&dead.id).is_err());
+
+        // Deleting a subscription drops its dead letters
+        store.delete(&b.id).unwrap();
+        assert!(store.dead_letters(None, 10).unwrap().is_empty());
+    }
+}
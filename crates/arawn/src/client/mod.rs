@@ -6,6 +6,7 @@
 use anyhow::Result;
 use futures::{SinkExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::pin::Pin;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
@@ -125,6 +126,198 @@ pub struct LogFilesResponse {
     pub files: Vec<LogFileInfo>,
 }
 
+/// Aggregated usage for one group in a usage report.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageRow {
+    pub key: String,
+    pub calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Workstream spend against its configured limits.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkstreamBudget {
+    pub workstream_id: String,
+    pub period: String,
+    pub spent_usd: f64,
+    #[serde(default)]
+    pub soft_limit_usd: Option<f64>,
+    #[serde(default)]
+    pub hard_limit_usd: Option<f64>,
+    pub status: String,
+}
+
+/// Token usage and cost report.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub group_by: String,
+    pub rows: Vec<UsageRow>,
+    pub total: UsageRow,
+    #[serde(default)]
+    pub budget: Option<WorkstreamBudget>,
+}
+
+/// Filters for message history search.
+#[derive(Debug, Default, Clone)]
+pub struct MessageSearchFilters {
+    pub workstream: Option<String>,
+    pub session: Option<String>,
+    pub roles: Vec<String>,
+    pub tags: Vec<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// A message matching a history search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchHit {
+    pub message_id: String,
+    pub workstream_id: String,
+    pub workstream_title: String,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    pub role: String,
+    pub timestamp: String,
+    pub snippet: String,
+    pub score: f64,
+}
+
+/// Match counts per role and workstream.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageSearchFacets {
+    #[serde(default)]
+    pub roles: std::collections::BTreeMap<String, usize>,
+    #[serde(default)]
+    pub workstreams: std::collections::BTreeMap<String, usize>,
+}
+
+/// A page of message history search results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchResults {
+    pub query: String,
+    pub hits: Vec<MessageSearchHit>,
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+    #[serde(default)]
+    pub facets: MessageSearchFacets,
+}
+
+/// A production directory snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub created_at: String,
+    pub trigger: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+/// Snapshot list response.
+#[derive(Debug, Deserialize)]
+struct SnapshotListResponse {
+    snapshots: Vec<SnapshotInfo>,
+}
+
+/// Create snapshot request.
+#[derive(Debug, Serialize)]
+struct CreateSnapshotRequest {
+    label: Option<String>,
+}
+
+/// Differences between a snapshot and a newer state.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+/// Result of restoring a snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotRestore {
+    pub files_restored: usize,
+    pub files_removed: usize,
+    #[serde(default)]
+    pub safety_snapshot_id: Option<String>,
+}
+
+/// A parameter a workstream template asks for.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateParamInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// An available workstream template.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub plugin: Option<String>,
+    #[serde(default)]
+    pub params: Vec<TemplateParamInfo>,
+}
+
+/// Template list response.
+#[derive(Debug, Deserialize)]
+struct TemplateListResponse {
+    templates: Vec<TemplateInfo>,
+}
+
+/// Create workstream request.
+#[derive(Debug, Serialize)]
+pub struct CreateWorkstreamRequest {
+    pub title: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, String>,
+}
+
+/// What a template applied to a new workstream.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateApplied {
+    pub name: String,
+    pub files: usize,
+    pub notes: usize,
+    #[serde(default)]
+    pub workflows: Vec<String>,
+    #[serde(default)]
+    pub repos: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// A newly created workstream.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedWorkstream {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub template: Option<TemplateApplied>,
+}
+
 /// Notes list response.
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)] // Pagination fields used by serde deserialization
@@ -625,6 +818,266 @@ impl Client {
         Ok(result)
     }
 
+    /// Get an aggregated token usage and cost report.
+    pub async fn usage_report(
+        &self,
+        group_by: &str,
+        workstream: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<UsageReport> {
+        let mut url = self.base_url.join("/api/v1/usage")?;
+
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("group_by", group_by);
+            if let Some(ws) = workstream {
+                query.append_pair("workstream", ws);
+            }
+            if let Some(s) = since {
+                query.append_pair("since", s);
+            }
+        }
+
+        let mut request = self.http.get(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            anyhow::bail!("Usage accounting is not enabled on the server");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let result: UsageReport = response.json().await?;
+        Ok(result)
+    }
+
+    /// Full-text search over message history across workstreams.
+    pub async fn search_messages(
+        &self,
+        query: &str,
+        filters: &MessageSearchFilters,
+    ) -> Result<MessageSearchResults> {
+        let mut url = self.base_url.join("/api/v1/search/messages")?;
+
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("q", query);
+            if let Some(ref ws) = filters.workstream {
+                pairs.append_pair("workstream", ws);
+            }
+            if let Some(ref session) = filters.session {
+                pairs.append_pair("session", session);
+            }
+            if !filters.roles.is_empty() {
+                pairs.append_pair("role", &filters.roles.join(","));
+            }
+            if !filters.tags.is_empty() {
+                pairs.append_pair("tag", &filters.tags.join(","));
+            }
+            if let Some(ref since) = filters.since {
+                pairs.append_pair("since", since);
+            }
+            if let Some(ref until) = filters.until {
+                pairs.append_pair("until", until);
+            }
+            if let Some(limit) = filters.limit {
+                pairs.append_pair("limit", &limit.to_string());
+            }
+        }
+
+        let mut request = self.http.get(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            anyhow::bail!("Workstreams are not enabled on the server");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let result: MessageSearchResults = response.json().await?;
+        Ok(result)
+    }
+
+    /// List available workstream templates.
+    pub async fn list_templates(&self) -> Result<Vec<TemplateInfo>> {
+        let url = self.base_url.join("/api/v1/templates")?;
+
+        let mut request = self.http.get(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            anyhow::bail!("Workstream templates are not configured on the server");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let result: TemplateListResponse = response.json().await?;
+        Ok(result.templates)
+    }
+
+    /// Create a workstream, optionally from a template.
+    pub async fn create_workstream(
+        &self,
+        request: &CreateWorkstreamRequest,
+    ) -> Result<CreatedWorkstream> {
+        let url = self.base_url.join("/api/v1/workstreams")?;
+
+        let mut http_request = self.http.post(url).json(request);
+
+        if let Some(ref token) = self.token {
+            http_request = http_request.bearer_auth(token);
+        }
+
+        let response = http_request.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let message = response
+                .json::<serde_json::Value>()
+                .await
+                .ok()
+                .and_then(|v| v["message"].as_str().map(str::to_string))
+                .unwrap_or_else(|| status.to_string());
+            anyhow::bail!("Failed to create workstream: {}", message);
+        }
+
+        let workstream: CreatedWorkstream = response.json().await?;
+        Ok(workstream)
+    }
+
+    /// List a workstream's production snapshots, newest first.
+    pub async fn list_snapshots(&self, workstream: &str) -> Result<Vec<SnapshotInfo>> {
+        let url = self
+            .base_url
+            .join(&format!("/api/v1/workstreams/{}/snapshots", workstream))?;
+
+        let mut request = self.http.get(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let result: SnapshotListResponse = response.json().await?;
+        Ok(result.snapshots)
+    }
+
+    /// Snapshot a workstream's production directory.
+    pub async fn create_snapshot(
+        &self,
+        workstream: &str,
+        label: Option<&str>,
+    ) -> Result<SnapshotInfo> {
+        let url = self
+            .base_url
+            .join(&format!("/api/v1/workstreams/{}/snapshots", workstream))?;
+
+        let mut request = self.http.post(url).json(&CreateSnapshotRequest {
+            label: label.map(str::to_string),
+        });
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let snapshot: SnapshotInfo = response.json().await?;
+        Ok(snapshot)
+    }
+
+    /// Diff a snapshot against current production or another snapshot.
+    pub async fn diff_snapshot(
+        &self,
+        workstream: &str,
+        snapshot_id: &str,
+        against: Option<&str>,
+    ) -> Result<SnapshotDiff> {
+        let mut url = self.base_url.join(&format!(
+            "/api/v1/workstreams/{}/snapshots/{}/diff",
+            workstream, snapshot_id
+        ))?;
+
+        if let Some(other) = against {
+            url.query_pairs_mut().append_pair("against", other);
+        }
+
+        let mut request = self.http.get(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Snapshot not found: {}", snapshot_id);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let diff: SnapshotDiff = response.json().await?;
+        Ok(diff)
+    }
+
+    /// Restore a workstream's production directory from a snapshot.
+    pub async fn restore_snapshot(
+        &self,
+        workstream: &str,
+        snapshot_id: &str,
+    ) -> Result<SnapshotRestore> {
+        let url = self.base_url.join(&format!(
+            "/api/v1/workstreams/{}/snapshots/{}/restore",
+            workstream, snapshot_id
+        ))?;
+
+        let mut request = self.http.post(url);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Snapshot not found: {}", snapshot_id);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Server returned error: {}", response.status());
+        }
+
+        let result: SnapshotRestore = response.json().await?;
+        Ok(result)
+    }
+
     /// Delete a session.
     #[allow(dead_code)]
     pub async fn delete_session(&self, session_id: &str) -> Result<()> {
@@ -1,7 +1,11 @@
 //! Auth command - authentication management.
 
 use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use clap::{Args, Subcommand};
+use console::Style;
+
+use arawn_workstream::{AccessStore, TokenScope};
 
 use super::Context;
 use super::output;
@@ -12,6 +16,10 @@ use super::output;
   arawn auth login                  Authenticate via OAuth
   arawn auth status                 Show current auth state
   arawn auth token --generate       Generate a new API token
+  arawn auth token create ci --user alice --scope read --expires 30
+                                    Create a named, scoped API token
+  arawn auth token list             List named API tokens
+  arawn auth token revoke ci        Revoke a named API token
   arawn auth logout                 Clear stored tokens")]
 pub struct AuthArgs {
     #[command(subcommand)]
@@ -29,11 +37,44 @@ pub enum AuthCommand {
     /// Clear stored OAuth tokens
     Logout,
 
-    /// Generate or show API token (for server auth)
+    /// Generate or show API token (for server auth), or manage named tokens
     Token {
         /// Generate a new token
         #[arg(long)]
         generate: bool,
+
+        #[command(subcommand)]
+        command: Option<TokenCommand>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TokenCommand {
+    /// Create a named API token (the secret is shown once)
+    Create {
+        /// Unique token name
+        name: String,
+
+        /// User the token acts as (defaults to the token name)
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Scope to grant: read, chat, admin or mcp-manage (repeatable)
+        #[arg(long = "scope", value_parser = clap::value_parser!(TokenScope), default_values = ["read", "chat"])]
+        scopes: Vec<TokenScope>,
+
+        /// Expiry, as a number of days or a YYYY-MM-DD date
+        #[arg(long, value_parser = parse_expiry)]
+        expires: Option<DateTime<Utc>>,
+    },
+
+    /// List named API tokens
+    List,
+
+    /// Revoke a named API token by ID or name
+    Revoke {
+        /// Token ID or name
+        id_or_name: String,
     },
 }
 
@@ -43,7 +84,11 @@ pub async fn run(args: AuthArgs, ctx: &Context) -> Result<()> {
         AuthCommand::Login => cmd_login(ctx).await,
         AuthCommand::Status => cmd_status(ctx).await,
         AuthCommand::Logout => cmd_logout().await,
-        AuthCommand::Token { generate } => cmd_token(generate, ctx).await,
+        AuthCommand::Token {
+            command: Some(command),
+            ..
+        } => cmd_named_token(command, ctx),
+        AuthCommand::Token { generate, .. } => cmd_token(generate, ctx).await,
     }
 }
 
@@ -218,6 +263,108 @@ async fn cmd_token(generate: bool, _ctx: &Context) -> Result<()> {
     Ok(())
 }
 
+fn cmd_named_token(command: TokenCommand, ctx: &Context) -> Result<()> {
+    let data_dir = arawn_config::xdg_config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?;
+    std::fs::create_dir_all(&data_dir)?;
+    let store = AccessStore::open(&data_dir.join("access.db"))
+        .map_err(|e| anyhow::anyhow!("Failed to open access store: {}", e))?;
+
+    match command {
+        TokenCommand::Create {
+            name,
+            user,
+            scopes,
+            expires,
+        } => {
+            let user = user.unwrap_or_else(|| name.clone());
+            let (token, secret) = store
+                .create_token(&name, &user, &scopes, expires)
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            if ctx.json_output {
+                let mut value = serde_json::to_value(&token)?;
+                value["secret"] = serde_json::Value::String(secret);
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            } else {
+                output::success(format!("Created token '{}' for {}", token.name, token.user));
+                output::kv("ID", &token.id);
+                output::kv("Scopes", format_scopes(&token.scopes));
+                if let Some(at) = token.expires_at {
+                    output::kv("Expires", at.format("%Y-%m-%d %H:%M UTC"));
+                }
+                println!();
+                println!("  {}", secret);
+                println!();
+                output::hint("Store this secret now; it cannot be shown again.");
+            }
+        }
+        TokenCommand::List => {
+            let tokens = store
+                .list_tokens()
+                .map_err(|e| anyhow::anyhow!("Failed to list tokens: {}", e))?;
+            if ctx.json_output {
+                println!("{}", serde_json::to_string_pretty(&tokens)?);
+                return Ok(());
+            }
+            println!();
+            output::header("API Tokens");
+            if tokens.is_empty() {
+                println!("  No tokens found.");
+            }
+            let now = Utc::now();
+            for token in &tokens {
+                let status = if token.revoked_at.is_some() {
+                    "revoked"
+                } else if !token.is_active(now) {
+                    "expired"
+                } else {
+                    "active"
+                };
+                println!(
+                    "  {} {} user={} scopes={} {}",
+                    Style::new().cyan().apply_to(&token.name),
+                    Style::new().dim().apply_to(&token.id),
+                    token.user,
+                    format_scopes(&token.scopes),
+                    status,
+                );
+            }
+            println!();
+        }
+        TokenCommand::Revoke { id_or_name } => {
+            let token = store
+                .revoke_token(&id_or_name)
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            output::success(format!("Revoked token '{}'", token.name));
+        }
+    }
+
+    Ok(())
+}
+
+fn format_scopes(scopes: &[TokenScope]) -> String {
+    scopes
+        .iter()
+        .map(TokenScope::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse `--expires` as a number of days from now or a `YYYY-MM-DD` date.
+fn parse_expiry(s: &str) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(days) = s.trim_end_matches('d').parse::<i64>() {
+        if days <= 0 {
+            return Err("expiry must be at least one day".to_string());
+        }
+        return Ok(Utc::now() + Duration::days(days));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+        .ok_or_else(|| format!("invalid expiry '{}' (expected days or YYYY-MM-DD)", s))
+}
+
 /// Build an OAuthConfig applying any `[oauth]` overrides from arawn config.
 fn build_oauth_config() -> arawn_oauth::OAuthConfig {
     let base = arawn_oauth::OAuthConfig::default();
@@ -397,12 +397,13 @@ fn parse_backend(s: &str) -> Result<Backend> {
     match s.to_lowercase().as_str() {
         "anthropic" => Ok(Backend::Anthropic),
         "openai" => Ok(Backend::Openai),
+        "gemini" | "google" => Ok(Backend::Gemini),
         "groq" => Ok(Backend::Groq),
         "ollama" => Ok(Backend::Ollama),
         "custom" => Ok(Backend::Custom),
         "claude-oauth" | "claudeoauth" => Ok(Backend::ClaudeOauth),
         other => Err(anyhow::anyhow!(
-            "Unknown backend '{}'. Valid: anthropic, openai, groq, ollama, custom, claude-oauth",
+            "Unknown backend '{}'. Valid: anthropic, openai, gemini, groq, ollama, custom, claude-oauth",
             other
         )),
     }
@@ -186,7 +186,7 @@ async fn run_remote(args: LogsArgs, ctx: &Context) -> Result<()> {
                             println!(
                                 "  {} {}",
                                 Style::new().cyan().apply_to(&f.name),
-                                Style::new().dim().apply_to(format_size(f.size)),
+                                Style::new().dim().apply_to(output::format_size(f.size)),
                             );
                         }
                     }
@@ -229,16 +229,6 @@ async fn run_remote(args: LogsArgs, ctx: &Context) -> Result<()> {
     Ok(())
 }
 
-fn format_size(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{}B", bytes)
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1}KB", bytes as f64 / 1024.0)
-    } else {
-        format!("{:.1}MB", bytes as f64 / (1024.0 * 1024.0))
-    }
-}
-
 fn print_log_line(line: &str) {
     // Strip ANSI escape codes for cleaner output, then print
     let stripped = strip_ansi_escapes(line);
@@ -310,7 +310,7 @@ async fn cmd_export(output: Option<String>, ctx: &Context) -> Result<()> {
 }
 
 /// Open the memory store at the default data directory.
-fn open_memory_store() -> Result<arawn_memory::MemoryStore> {
+pub(crate) fn open_memory_store() -> Result<arawn_memory::MemoryStore> {
     let data_dir = arawn_config::xdg_config_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
     let db_path = data_dir.join("memory.db");
@@ -319,28 +319,38 @@ fn open_memory_store() -> Result<arawn_memory::MemoryStore> {
 }
 
 /// Build an EmbedderSpec from EmbeddingConfig (same logic as start.rs).
-fn build_embedder_spec(config: &arawn_config::EmbeddingConfig) -> arawn_llm::EmbedderSpec {
+pub(crate) fn build_embedder_spec(
+    config: &arawn_config::EmbeddingConfig,
+) -> arawn_llm::EmbedderSpec {
     use arawn_config::EmbeddingProvider;
 
     let provider = match config.provider {
         EmbeddingProvider::Local => "local",
         EmbeddingProvider::OpenAi => "openai",
+        EmbeddingProvider::Gemini => "gemini",
         EmbeddingProvider::Mock => "mock",
     };
 
     let openai_config = config.openai.as_ref();
+    let gemini_config = config.gemini.as_ref();
     let local_config = config.local.as_ref();
 
     // Resolve OpenAI API key: config → env var
     let openai_api_key = openai_config
         .and_then(|c| c.api_key.clone())
         .or_else(|| std::env::var("OPENAI_API_KEY").ok());
+    let gemini_api_key = gemini_config
+        .and_then(|c| c.api_key.clone())
+        .or_else(|| std::env::var("GEMINI_API_KEY").ok());
 
     arawn_llm::EmbedderSpec {
         provider: provider.to_string(),
         openai_api_key,
         openai_model: openai_config.map(|c| c.model.clone()),
         openai_base_url: openai_config.and_then(|c| c.base_url.clone()),
+        gemini_api_key,
+        gemini_model: gemini_config.map(|c| c.model.clone()),
+        gemini_base_url: gemini_config.and_then(|c| c.base_url.clone()),
         local_model_path: local_config.and_then(|c| c.model_path.clone()),
         local_tokenizer_path: local_config.and_then(|c| c.tokenizer_path.clone()),
         dimensions: config.dimensions,
@@ -12,11 +12,13 @@ pub mod notes;
 pub mod output;
 pub mod plugin;
 pub mod repl;
+pub mod search;
 pub mod secrets;
 pub mod session;
 pub mod start;
 pub mod status;
 pub mod tui;
+pub mod workstream;
 
 use console::Style;
 
@@ -60,6 +60,17 @@ pub fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Format a byte count as B, KB, or MB.
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{}B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1}KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1}MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
 /// Truncate a multiline string, preserving indentation on continuation.
 pub fn truncate_multiline(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -3,8 +3,13 @@
 //! Provides CLI subcommands for managing plugin subscriptions:
 //! - `arawn plugin add` - Subscribe to a plugin
 //! - `arawn plugin update` - Update subscribed plugins
+//! - `arawn plugin outdated` - Show plugins with newer commits than the lockfile
 //! - `arawn plugin remove` - Unsubscribe from a plugin
 //! - `arawn plugin list` - List all plugins
+//!
+//! `add` and `update` record resolved commits and content hashes in
+//! `arawn-plugins.lock` in the current directory; `update --locked` installs
+//! exactly what the lockfile pins.
 
 use std::path::PathBuf;
 
@@ -12,7 +17,10 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 
 use arawn_config::PluginSubscription;
-use arawn_plugin::{PluginManager, SubscriptionManager, SyncAction};
+use arawn_plugin::lockfile::short_commit;
+use arawn_plugin::{
+    LOCKFILE_NAME, PluginManager, SubscriptionManager, SyncAction, SyncMode, parse_source,
+};
 
 use super::Context;
 use super::output;
@@ -25,6 +33,8 @@ use super::output;
   arawn plugin add https://git.example.com/plugin.git
   arawn plugin update                Update all plugins
   arawn plugin update my-plugin      Update a specific plugin
+  arawn plugin update --locked       Install the commits pinned in arawn-plugins.lock
+  arawn plugin outdated              Show plugins with newer upstream commits
   arawn plugin remove my-plugin
   arawn plugin list")]
 pub struct PluginArgs {
@@ -40,6 +50,9 @@ pub enum PluginCommand {
     /// Update subscribed plugins (all if no name given)
     Update(UpdateArgs),
 
+    /// Show subscribed plugins whose upstream ref moved past the locked commit
+    Outdated,
+
     /// Unsubscribe and remove a plugin
     Remove(RemoveArgs),
 
@@ -50,7 +63,7 @@ pub enum PluginCommand {
 /// Arguments for `arawn plugin add`.
 #[derive(Args, Debug)]
 pub struct AddArgs {
-    /// Plugin source: GitHub shorthand (owner/repo) or full git URL
+    /// Plugin source: GitHub shorthand (owner/repo), full git URL, or local path
     pub source: String,
 
     /// Git ref (branch, tag, or commit) to checkout
@@ -67,6 +80,11 @@ pub struct AddArgs {
 pub struct UpdateArgs {
     /// Plugin name to update (updates all if not specified)
     pub name: Option<String>,
+
+    /// Check out the commits recorded in arawn-plugins.lock instead of the
+    /// latest, failing if a plugin is unlocked or its content does not match
+    #[arg(long)]
+    pub locked: bool,
 }
 
 /// Arguments for `arawn plugin remove`.
@@ -101,31 +119,12 @@ pub async fn run(args: PluginArgs, ctx: &Context) -> Result<()> {
     match args.command {
         PluginCommand::Add(add_args) => run_add(add_args, ctx).await,
         PluginCommand::Update(update_args) => run_update(update_args, ctx).await,
+        PluginCommand::Outdated => run_outdated(ctx).await,
         PluginCommand::Remove(remove_args) => run_remove(remove_args, ctx).await,
         PluginCommand::List(list_args) => run_list(list_args, ctx).await,
     }
 }
 
-/// Parse a source string into a PluginSubscription.
-fn parse_source(source: &str, git_ref: Option<String>) -> PluginSubscription {
-    // Check if it's a GitHub shorthand (owner/repo)
-    if !source.contains("://") && !source.starts_with("git@") {
-        // Treat as GitHub shorthand
-        let mut sub = PluginSubscription::github(source);
-        if let Some(r) = git_ref {
-            sub = sub.with_ref(r);
-        }
-        sub
-    } else {
-        // Full URL
-        let mut sub = PluginSubscription::url(source);
-        if let Some(r) = git_ref {
-            sub = sub.with_ref(r);
-        }
-        sub
-    }
-}
-
 /// Run `arawn plugin add`.
 async fn run_add(args: AddArgs, ctx: &Context) -> Result<()> {
     let subscription = parse_source(&args.source, args.r#ref);
@@ -146,7 +145,8 @@ async fn run_add(args: AddArgs, ctx: &Context) -> Result<()> {
         None
     };
 
-    let mut manager = SubscriptionManager::new(Vec::new(), project_dir.as_deref())?;
+    let mut manager = SubscriptionManager::new(Vec::new(), project_dir.as_deref())?
+        .with_lockfile_path(lockfile_path()?)?;
 
     // Add subscription to appropriate config
     if args.project {
@@ -176,16 +176,37 @@ async fn run_add(args: AddArgs, ctx: &Context) -> Result<()> {
         SyncAction::Skipped => {
             println!("Skipped (local path): {}", sub_id);
         }
-        SyncAction::CloneFailed | SyncAction::UpdateFailed => {
+        SyncAction::CloneFailed | SyncAction::UpdateFailed | SyncAction::IntegrityFailed => {
             let err = result.error.unwrap_or_else(|| "unknown error".to_string());
             tracing::error!("Failed to clone plugin: {}", err);
             return Err(anyhow::anyhow!("Clone failed: {}", err));
         }
     }
 
+    // Pin the cloned commit
+    match manager.lock_subscription(&subscription) {
+        Ok(Some(locked)) => {
+            manager.save_lockfile()?;
+            if ctx.verbose {
+                println!(
+                    "  Locked at {} in {}",
+                    short_commit(&locked.commit),
+                    manager.lockfile_path().display()
+                );
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to lock plugin {}: {}", sub_id, e),
+    }
+
     Ok(())
 }
 
+/// Path of the lockfile managed by the CLI (in the current directory).
+fn lockfile_path() -> Result<PathBuf> {
+    Ok(std::env::current_dir()?.join(LOCKFILE_NAME))
+}
+
 /// Run `arawn plugin update`.
 async fn run_update(args: UpdateArgs, ctx: &Context) -> Result<()> {
     // Load config to get subscriptions
@@ -193,8 +214,9 @@ async fn run_update(args: UpdateArgs, ctx: &Context) -> Result<()> {
     let plugins_cfg = loaded.config.plugins.clone().unwrap_or_default();
 
     let project_dir = std::env::current_dir().ok();
-    let manager =
-        SubscriptionManager::new(plugins_cfg.subscriptions.clone(), project_dir.as_deref())?;
+    let mut manager =
+        SubscriptionManager::new(plugins_cfg.subscriptions.clone(), project_dir.as_deref())?
+            .with_lock_mode(plugins_cfg.lock);
 
     let subscriptions = manager.all_subscriptions();
 
@@ -220,9 +242,19 @@ async fn run_update(args: UpdateArgs, ctx: &Context) -> Result<()> {
         return Ok(());
     }
 
-    println!("Updating {} plugin(s)...", to_update.len());
+    let mode = if args.locked {
+        println!(
+            "Installing {} plugin(s) from {}...",
+            to_update.len(),
+            manager.lockfile_path().display()
+        );
+        SyncMode::Locked
+    } else {
+        println!("Updating {} plugin(s)...", to_update.len());
+        SyncMode::Latest
+    };
 
-    let results = manager.sync_all_async().await;
+    let results = manager.sync_all_with_mode_async(mode).await;
 
     // Filter results to only show the ones we're updating
     let update_ids: std::collections::HashSet<_> = to_update.iter().map(|s| s.id()).collect();
@@ -257,10 +289,42 @@ async fn run_update(args: UpdateArgs, ctx: &Context) -> Result<()> {
                     println!("  Skipped: {}", result.subscription_id);
                 }
             }
-            SyncAction::CloneFailed | SyncAction::UpdateFailed => {
+            SyncAction::CloneFailed | SyncAction::UpdateFailed | SyncAction::IntegrityFailed => {
                 failed += 1;
                 let err = result.error.as_deref().unwrap_or("unknown error");
-                tracing::error!("Plugin sync failed: {} - {}", result.subscription_id, err);
+                tracing::error!(
+                    "Plugin sync failed: {} ({}) - {}",
+                    result.subscription_id,
+                    result.action,
+                    err
+                );
+            }
+        }
+    }
+
+    // Record the new commits and content hashes
+    if mode == SyncMode::Latest {
+        let synced: std::collections::HashSet<_> = filtered_results
+            .iter()
+            .filter(|r| matches!(r.action, SyncAction::Cloned | SyncAction::Updated))
+            .map(|r| r.subscription_id.clone())
+            .collect();
+        let mut relocked = 0;
+        for sub in to_update.iter().filter(|s| synced.contains(&s.id())) {
+            match manager.lock_subscription(sub) {
+                Ok(Some(_)) => relocked += 1,
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to lock plugin {}: {}", sub.id(), e),
+            }
+        }
+        if relocked > 0 {
+            manager.save_lockfile()?;
+            if ctx.verbose {
+                println!(
+                    "  Locked {} plugin(s) in {}",
+                    relocked,
+                    manager.lockfile_path().display()
+                );
             }
         }
     }
@@ -285,7 +349,8 @@ async fn run_remove(args: RemoveArgs, ctx: &Context) -> Result<()> {
         None
     };
 
-    let mut manager = SubscriptionManager::new(Vec::new(), project_dir.as_deref())?;
+    let mut manager = SubscriptionManager::new(Vec::new(), project_dir.as_deref())?
+        .with_lockfile_path(lockfile_path()?)?;
 
     // Find the subscription by name or ID
     let all_subs = manager.all_subscriptions();
@@ -332,6 +397,13 @@ async fn run_remove(args: RemoveArgs, ctx: &Context) -> Result<()> {
         println!("Removed from global config: {}", sub_id);
     }
 
+    if manager.unlock_subscription(&sub_id) {
+        manager.save_lockfile()?;
+        if ctx.verbose {
+            println!("Removed from {}", manager.lockfile_path().display());
+        }
+    }
+
     // Delete cached files if requested
     if args.delete_cache {
         let cache_dir = manager.cache_dir_for(sub);
@@ -347,6 +419,84 @@ async fn run_remove(args: RemoveArgs, ctx: &Context) -> Result<()> {
     Ok(())
 }
 
+/// Run `arawn plugin outdated`.
+async fn run_outdated(ctx: &Context) -> Result<()> {
+    let loaded = arawn_config::load_config(None)?;
+    let plugins_cfg = loaded.config.plugins.clone().unwrap_or_default();
+
+    let project_dir = std::env::current_dir().ok();
+    let manager =
+        SubscriptionManager::new(plugins_cfg.subscriptions.clone(), project_dir.as_deref())?;
+
+    let report = manager.outdated();
+
+    if ctx.json_output {
+        use serde_json::json;
+        let entries: Vec<_> = report
+            .iter()
+            .map(|p| {
+                json!({
+                    "name": p.subscription_id,
+                    "ref": p.git_ref,
+                    "locked": p.locked,
+                    "latest": p.latest,
+                    "outdated": p.is_outdated(),
+                    "error": p.error,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if report.is_empty() {
+        println!("No subscribed plugins found.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<30} {:<15} {:<14} {:<14} STATUS",
+        "ID", "REF", "LOCKED", "LATEST"
+    );
+    println!("{}", "─".repeat(85));
+
+    let mut outdated = 0;
+    for p in &report {
+        let status = if let Some(ref err) = p.error {
+            tracing::warn!("Failed to query {}: {}", p.subscription_id, err);
+            "error"
+        } else if p.locked.is_none() {
+            "unlocked"
+        } else if p.is_outdated() {
+            "outdated"
+        } else {
+            "up to date"
+        };
+        if p.is_outdated() {
+            outdated += 1;
+        }
+
+        println!(
+            "{:<30} {:<15} {:<14} {:<14} {}",
+            output::truncate(&p.subscription_id, 30),
+            output::truncate(&p.git_ref, 15),
+            p.locked.as_deref().map(short_commit).unwrap_or("-"),
+            p.latest.as_deref().map(short_commit).unwrap_or("-"),
+            status
+        );
+    }
+
+    if outdated > 0 {
+        println!();
+        println!(
+            "{} plugin(s) can be updated with `arawn plugin update`.",
+            outdated
+        );
+    }
+
+    Ok(())
+}
+
 /// Run `arawn plugin list`.
 async fn run_list(args: ListArgs, ctx: &Context) -> Result<()> {
     // Load config
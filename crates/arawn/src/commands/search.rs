@@ -0,0 +1,128 @@
+//! Search command - full-text search over conversation history.
+
+use anyhow::Result;
+use clap::Args;
+use console::Style;
+
+use super::Context;
+use super::output;
+use crate::client::{Client, MessageSearchFilters};
+
+/// Arguments for the search command.
+#[derive(Args, Debug)]
+#[command(after_help = "\x1b[1mExamples:\x1b[0m
+  arawn search \"database migration\"              Search every workstream
+  arawn search deploy* -w <ws> --role user       Prefix match, one workstream, user messages
+  arawn search certificates --tag infra --since 2026-01-01")]
+pub struct SearchArgs {
+    /// Words to search for (all must match; end a word with * for a prefix)
+    #[arg(required = true)]
+    pub query: Vec<String>,
+
+    /// Only search this workstream
+    #[arg(short, long)]
+    pub workstream: Option<String>,
+
+    /// Only search this session
+    #[arg(short, long)]
+    pub session: Option<String>,
+
+    /// Only match these roles (user, assistant, system, tool_use, tool_result)
+    #[arg(short, long = "role", value_delimiter = ',')]
+    pub roles: Vec<String>,
+
+    /// Only search workstreams with this tag
+    #[arg(short, long = "tag", value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Earliest message (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Latest message (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Maximum results to show
+    #[arg(short, long, default_value = "20")]
+    pub limit: usize,
+}
+
+/// Run the search command.
+pub async fn run(args: SearchArgs, ctx: &Context) -> Result<()> {
+    let client = Client::new(&ctx.server_url)?;
+    let query = args.query.join(" ");
+    let filters = MessageSearchFilters {
+        workstream: args.workstream,
+        session: args.session,
+        roles: args.roles,
+        tags: args.tags,
+        since: args.since,
+        until: args.until,
+        limit: Some(args.limit),
+    };
+
+    match client.search_messages(&query, &filters).await {
+        Ok(results) => {
+            if ctx.json_output {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+                return Ok(());
+            }
+
+            output::header(&format!("Search: \"{}\"", query));
+            if results.hits.is_empty() {
+                output::hint("No matching messages");
+                return Ok(());
+            }
+
+            let dim = Style::new().dim();
+            let cyan = Style::new().cyan();
+            for hit in &results.hits {
+                let when = hit.timestamp.get(..16).unwrap_or(&hit.timestamp);
+                println!(
+                    "  {} {} {}",
+                    cyan.apply_to(&hit.workstream_title),
+                    dim.apply_to(format!("· {} · {}", hit.role, when.replace('T', " "))),
+                    dim.apply_to(&hit.message_id),
+                );
+                println!("    {}", highlight(&output::truncate(&hit.snippet, 200)));
+                println!();
+            }
+
+            let roles: Vec<String> = results
+                .facets
+                .roles
+                .iter()
+                .map(|(role, n)| format!("{} {}", n, role))
+                .collect();
+            output::hint(format!(
+                "Showing {} of {} matches ({}) across {} workstream(s)",
+                results.hits.len(),
+                results.total,
+                roles.join(", "),
+                results.facets.workstreams.len()
+            ));
+        }
+        Err(e) => {
+            super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+        }
+    }
+
+    Ok(())
+}
+
+/// Render `**term**` markers from the server as bold text.
+fn highlight(snippet: &str) -> String {
+    let bold = Style::new().bold().yellow();
+    snippet
+        .split("**")
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                bold.apply_to(part).to_string()
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
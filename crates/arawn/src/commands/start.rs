@@ -11,15 +11,23 @@ use clap::Args;
 
 use arawn_agent::{
     Agent, IndexerConfig, McpToolAdapter, PromptMode, SessionIndexer, SystemPromptBuilder, Tool,
-    ToolRegistry, tools,
+    ToolRegistry, UsageMeter, tools,
 };
 #[cfg(feature = "gliner")]
 use arawn_agent::{GlinerEngine, NerConfig};
 use arawn_config::EmbeddingProvider;
-use arawn_config::{self, Backend, LlmConfig, ResolvedLlm};
+use arawn_config::{
+    self, Backend, InboundHookAuth, InboundHookConfig, LlmConfig, PluginLockMode, ResolvedLlm,
+};
+use arawn_domain::services::config::{mcp_server_config, recall_config, tool_output_overrides};
+use arawn_domain::{
+    BackendFactory, ConfigService, PluginService, TemplateRegistry, WorkflowService,
+};
 use arawn_llm::{
-    AnthropicBackend, AnthropicConfig, ApiKeyProvider, EmbedderSpec, OpenAiBackend, OpenAiConfig,
-    SharedBackend,
+    AnthropicBackend, AnthropicConfig, ApiKeyProvider, EmbedderSpec, GeminiBackend, GeminiConfig,
+    LlmClassifier, LlmRouter, ModelPricing, OpenAiBackend, OpenAiConfig, PricingTable,
+    PromptToolBackend, ReloadableBackend, RouteCondition, RouteProfile, RoutingRule, SharedBackend,
+    ToolPromptFormat,
 };
 use arawn_mcp::{McpManager, McpServerConfig};
 use arawn_memory::{MemoryStore, init_vector_extension};
@@ -29,9 +37,14 @@ use arawn_pipeline::{
     CatalogEntry, PipelineConfig, PipelineEngine, RuntimeCatalog, RuntimeCategory, WorkflowEvent,
     WorkflowLoader, build_executor_factory,
 };
-use arawn_plugin::{HookDispatcher, PluginManager, PluginWatcher, SubscriptionManager, SyncAction};
+use arawn_plugin::{PluginManager, PluginWatcher, SubscriptionManager, SyncAction};
+use arawn_server::hooks::{HookAuth, HookTarget, InboundHook, InboundHooks};
+use arawn_server::webhooks::{RetryPolicy, WebhookDispatcher, WebhookEvent, WebhookHooks};
 use arawn_server::{AppState, Server, ServerConfig};
-use arawn_workstream::{WorkstreamConfig as WsConfig, WorkstreamFsGate, WorkstreamManager};
+use arawn_workstream::{
+    AccessStore, DirectoryManager, LimitPeriod, SpendLimit, SpendLimits, UsageLedger, WebhookStore,
+    WorkstreamConfig as WsConfig, WorkstreamFsGate, WorkstreamManager,
+};
 use tokio::sync::RwLock;
 
 use super::Context;
@@ -39,7 +52,7 @@ use super::Context;
 /// Arguments for the start command.
 ///
 /// CLI arguments override config file values.
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[command(after_help = "\x1b[1mExamples:\x1b[0m
   arawn start                       Start with config file defaults
   arawn start -p 9090               Start on port 9090
@@ -158,12 +171,23 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     }
 
-    let backend = create_backend(&resolved, config.oauth.as_ref()).await?;
+    // Each profile's backend can be swapped when the config is reloaded
+    let mut reloadable: HashMap<String, Arc<ReloadableBackend>> = HashMap::new();
+    let default_backend = Arc::new(ReloadableBackend::new(
+        create_backend(&resolved, config.oauth.as_ref()).await?,
+        resolved.model.clone(),
+    ));
+    reloadable.insert("default".to_string(), default_backend.clone());
+    let reload_backend = backend_factory(&args);
+    let mut backend: SharedBackend = default_backend;
 
     // ── Create named backends from profiles ──────────────────────────────
 
     let mut backends: HashMap<String, SharedBackend> = HashMap::new();
     backends.insert("default".to_string(), backend.clone());
+    let mut profile_models: HashMap<String, String> = HashMap::new();
+    profile_models.insert("default".to_string(), resolved.model.clone());
+    let mut api_keys: Vec<String> = resolved.api_key.iter().cloned().collect();
 
     for (name, llm_config) in &config.llm_profiles {
         match resolve_profile(name, llm_config) {
@@ -176,7 +200,14 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
                                 name, profile_resolved.backend, profile_resolved.model
                             );
                         }
+                        let profile_backend = Arc::new(ReloadableBackend::new(
+                            profile_backend,
+                            profile_resolved.model.clone(),
+                        ));
+                        reloadable.insert(name.clone(), profile_backend.clone());
                         backends.insert(name.clone(), profile_backend);
+                        profile_models.insert(name.clone(), profile_resolved.model.clone());
+                        api_keys.extend(profile_resolved.api_key.clone());
                     }
                     Err(e) => {
                         tracing::warn!("failed to create backend '{}': {}", name, e);
@@ -189,6 +220,91 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     }
 
+    // ── Full request capture ([logging.capture]) ────────────────────────
+
+    if let Some(capture_cfg) = config
+        .logging
+        .as_ref()
+        .map(|l| &l.capture)
+        .filter(|c| c.enabled)
+    {
+        let mut redactor = arawn_llm::Redactor::new();
+        for key in api_keys {
+            redactor = redactor.with_secret(key);
+        }
+        for pattern in &capture_cfg.redact_patterns {
+            match redactor.clone().with_pattern(pattern) {
+                Ok(r) => redactor = r,
+                Err(e) => tracing::warn!("invalid capture redact pattern '{}': {}", pattern, e),
+            }
+        }
+        let log_config = arawn_llm::CaptureLogConfig {
+            path: capture_cfg.path.clone(),
+            retention_days: capture_cfg.retention_days,
+        };
+        match arawn_llm::CaptureLog::new(log_config.clone(), redactor) {
+            Ok(log) => {
+                let log = Arc::new(log);
+                for shared in backends.values_mut() {
+                    *shared = Arc::new(arawn_llm::CapturingBackend::new(
+                        shared.clone(),
+                        log.clone(),
+                    ));
+                }
+                backend = backends["default"].clone();
+                if ctx.verbose {
+                    println!(
+                        "Capturing LLM calls to {}",
+                        log_config.resolved_path().display()
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("Failed to initialize capture log: {}", e),
+        }
+    }
+
+    // ── Metrics ([telemetry]) ───────────────────────────────────────────
+
+    let telemetry_cfg = config.telemetry.clone().unwrap_or_default();
+    let metrics_handle = if telemetry_cfg.metrics {
+        match arawn_server::metrics::install_recorder() {
+            Ok(handle) => {
+                for (profile, shared) in backends.iter_mut() {
+                    *shared = Arc::new(arawn_llm::MeteredBackend::new(
+                        shared.clone(),
+                        profile.clone(),
+                    ));
+                }
+                backend = backends["default"].clone();
+                Some(handle)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to install metrics recorder: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // ── LLM router ──────────────────────────────────────────────────────
+
+    let router = match config.routing {
+        Some(ref routing_cfg) if routing_cfg.enabled => {
+            let router = build_router(routing_cfg, &backends, &profile_models)?;
+            if ctx.verbose {
+                println!(
+                    "LLM routing: default '{}', {} rule(s), profiles: {}",
+                    router.default_profile(),
+                    router.rules().len(),
+                    router.profile_names().join(", ")
+                );
+            }
+            Some(Arc::new(router))
+        }
+        _ => None,
+    };
+
     if ctx.verbose && backends.len() > 1 {
         println!(
             "Available backends: {}",
@@ -351,6 +467,45 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     };
 
+    // ── Usage ledger (token accounting and spend limits) ───────────────
+
+    let usage_cfg = config.usage.clone().unwrap_or_default();
+    let usage_ledger: Option<Arc<UsageLedger>> = if usage_cfg.enabled {
+        let usage_db_path = usage_cfg
+            .database
+            .clone()
+            .map(|p| if p.is_relative() { data_dir.join(p) } else { p })
+            .unwrap_or_else(|| data_dir.join("usage.db"));
+        match UsageLedger::open(&usage_db_path) {
+            Ok(ledger) => {
+                if ctx.verbose {
+                    println!(
+                        "Usage ledger: {} ({} priced model(s))",
+                        usage_db_path.display(),
+                        usage_cfg.pricing.len()
+                    );
+                }
+                Some(Arc::new(ledger.with_limits(build_spend_limits(&usage_cfg))))
+            }
+            Err(e) => {
+                tracing::warn!("failed to open usage ledger: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let usage_meter = usage_ledger.as_ref().map(|ledger| {
+        let profile = config
+            .agent
+            .get("default")
+            .and_then(|a| a.llm.clone())
+            .unwrap_or_else(|| "default".to_string());
+        UsageMeter::new(ledger.clone())
+            .with_pricing(build_pricing_table(&usage_cfg))
+            .with_profile(profile)
+    });
+
     // ── Build agent ─────────────────────────────────────────────────────
 
     // Get tool configuration
@@ -376,6 +531,11 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         ..Default::default()
     };
 
+    // The workstream manager is created after the agent, so tools and the
+    // filesystem gate reach it through a slot filled in once it exists.
+    let late_workstreams: Arc<std::sync::OnceLock<Arc<WorkstreamManager>>> =
+        Arc::new(std::sync::OnceLock::new());
+
     let mut tool_registry = ToolRegistry::new();
     tool_registry.register(tools::ShellTool::with_config(shell_config));
     tool_registry.register(tools::FileReadTool::new());
@@ -388,32 +548,21 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         Some(store) => tool_registry.register(tools::MemorySearchTool::with_store(store.clone())),
         None => tool_registry.register(tools::MemorySearchTool::new()),
     }
-
-    // Wire per-tool output config overrides from [tools.output]
-    use arawn_agent::OutputConfig;
-    let output_cfg = &tools_cfg.output;
-    if let Some(v) = output_cfg.shell {
-        tool_registry.set_output_config("shell", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("bash", OutputConfig::with_max_size(v));
-    }
-    if let Some(v) = output_cfg.file_read {
-        tool_registry.set_output_config("file_read", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("read_file", OutputConfig::with_max_size(v));
-    }
-    if let Some(v) = output_cfg.web_fetch {
-        tool_registry.set_output_config("web_fetch", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("fetch", OutputConfig::with_max_size(v));
-    }
-    if let Some(v) = output_cfg.search {
-        tool_registry.set_output_config("grep", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("glob", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("search", OutputConfig::with_max_size(v));
-        tool_registry.set_output_config("memory_search", OutputConfig::with_max_size(v));
+    tool_registry.register(tools::ConversationSearchTool::new(Arc::new(
+        LateMessageSearcher(Arc::clone(&late_workstreams)),
+    )));
+
+    // Wire per-tool output config overrides from [tools.output] (config
+    // reloads replace them through the same handle)
+    let output_overrides = tool_registry.output_overrides();
+    for (name, output) in tool_output_overrides(&tools_cfg.output) {
+        tool_registry.set_output_config(name, output);
     }
 
     // Register workflow tool if pipeline is enabled.
     // Uses a labeled block so fallback failures skip tool registration
     // without aborting the entire server startup.
+    let mut workflow_service: Option<WorkflowService> = None;
     if let Some(ref engine) = pipeline_engine {
         let pipeline_tools: Option<(Arc<ScriptExecutor>, Arc<RwLock<RuntimeCatalog>>)> = 'pipeline: {
             let runtimes_dir = resolve_path(None, "runtimes");
@@ -489,6 +638,12 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
                 register_builtin_runtimes(src_dir, &executor, &catalog, ctx.verbose).await;
             }
 
+            workflow_service = Some(WorkflowService::new(
+                engine.clone(),
+                pipeline_workflow_dir.clone(),
+                executor.clone(),
+                catalog.clone(),
+            ));
             tool_registry.register(tools::CatalogTool::new(catalog.clone(), executor.clone()));
             tool_registry.register(tools::WorkflowTool::new(
                 engine.clone(),
@@ -649,207 +804,211 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
     // ── Plugin system ────────────────────────────────────────────────────
 
     let plugins_cfg = config.plugins.clone().unwrap_or_default();
-    let plugin_prompts: Vec<(String, String)> = Vec::new();
-    let mut hook_dispatcher = HookDispatcher::new();
-    // Collect agent configs from plugins for delegate tool
-    let mut plugin_agent_configs: HashMap<String, arawn_plugin::PluginAgentConfig> = HashMap::new();
-    let mut plugin_agent_sources: HashMap<String, String> = HashMap::new();
-    let _watcher_handle: Option<arawn_plugin::WatcherHandle> = if plugins_cfg.enabled {
-        // Build plugin directories: defaults + any user-configured dirs
-        let mut plugin_dirs: Vec<PathBuf> = Vec::new();
-        if let Some(config_dir) = dirs::config_dir() {
-            plugin_dirs.push(config_dir.join("arawn").join("plugins"));
-        }
-        plugin_dirs.push(PathBuf::from("./plugins"));
-        plugin_dirs.extend(plugins_cfg.dirs.clone());
-
-        // Sync subscribed plugins (clone/update from git)
-        if !plugins_cfg.subscriptions.is_empty() {
+    // Hooks, skills, subagents and prompt fragments of the enabled plugins.
+    // The agent is built against its shared handles; the plugin service
+    // swaps their contents as plugins are enabled, disabled and reloaded.
+    let plugin_runtime = arawn_plugin::PluginRuntime::new();
+    let mut plugin_template_dirs: Vec<(String, PathBuf)> = Vec::new();
+    // MCP servers declared by plugins, registered alongside the configured ones
+    let mut plugin_mcp_servers: Vec<McpServerConfig> = Vec::new();
+    let (plugin_watcher, plugin_service): (Option<Arc<PluginWatcher>>, Option<PluginService>) =
+        if plugins_cfg.enabled {
+            // Build plugin directories: defaults + any user-configured dirs
+            let mut plugin_dirs: Vec<PathBuf> = Vec::new();
+            if let Some(config_dir) = dirs::config_dir() {
+                plugin_dirs.push(config_dir.join("arawn").join("plugins"));
+            }
+            plugin_dirs.push(PathBuf::from("./plugins"));
+            plugin_dirs.extend(plugins_cfg.dirs.clone());
+
+            // Subscriptions from arawn.toml and the runtime plugins.json files
             let workspace_dir = workspace.as_deref();
-            match SubscriptionManager::new(plugins_cfg.subscriptions.clone(), workspace_dir) {
-                Ok(sub_manager) => {
-                    // Check if auto-update is enabled
-                    let should_update =
-                        plugins_cfg.auto_update && !SubscriptionManager::is_auto_update_disabled();
+            let sub_manager =
+                match SubscriptionManager::new(plugins_cfg.subscriptions.clone(), workspace_dir) {
+                    Ok(sub_manager) => Some(sub_manager.with_lock_mode(plugins_cfg.lock)),
+                    Err(e) => {
+                        tracing::warn!("failed to load plugin subscriptions: {}", e);
+                        None
+                    }
+                };
 
-                    if should_update {
-                        if ctx.verbose {
-                            println!(
-                                "Syncing {} subscribed plugin(s)...",
-                                sub_manager.all_subscriptions().len()
-                            );
-                        }
+            // Sync subscribed plugins (clone/update from git)
+            if let Some(ref sub_manager) = sub_manager
+                && !sub_manager.all_subscriptions().is_empty()
+            {
+                // Check if auto-update is enabled
+                let should_update =
+                    plugins_cfg.auto_update && !SubscriptionManager::is_auto_update_disabled();
 
-                        let results = sub_manager.sync_all_async().await;
+                if should_update {
+                    if ctx.verbose {
+                        println!(
+                            "Syncing {} subscribed plugin(s)...",
+                            sub_manager.all_subscriptions().len()
+                        );
+                    }
 
-                        // Log results
-                        for result in &results {
-                            match result.action {
-                                SyncAction::Cloned => {
-                                    if ctx.verbose {
-                                        println!("  Cloned: {}", result.subscription_id);
-                                    }
-                                }
-                                SyncAction::Updated => {
-                                    if ctx.verbose {
-                                        println!("  Updated: {}", result.subscription_id);
-                                    }
+                    let results = sub_manager.sync_all_async().await;
+
+                    // Log results
+                    for result in &results {
+                        match result.action {
+                            SyncAction::Cloned => {
+                                if ctx.verbose {
+                                    println!("  Cloned: {}", result.subscription_id);
                                 }
-                                SyncAction::Skipped => {
-                                    // Silent unless verbose
-                                    if ctx.verbose {
-                                        println!("  Skipped: {}", result.subscription_id);
-                                    }
+                            }
+                            SyncAction::Updated => {
+                                if ctx.verbose {
+                                    println!("  Updated: {}", result.subscription_id);
                                 }
-                                SyncAction::CloneFailed | SyncAction::UpdateFailed => {
-                                    let err = result.error.as_deref().unwrap_or("unknown error");
-                                    tracing::warn!(
-                                        " {} {}: {}",
-                                        result.action,
-                                        result.subscription_id,
-                                        err
-                                    );
+                            }
+                            SyncAction::Skipped => {
+                                // Silent unless verbose
+                                if ctx.verbose {
+                                    println!("  Skipped: {}", result.subscription_id);
                                 }
                             }
+                            SyncAction::CloneFailed
+                            | SyncAction::UpdateFailed
+                            | SyncAction::IntegrityFailed => {
+                                let err = result.error.as_deref().unwrap_or("unknown error");
+                                tracing::warn!(
+                                    " {} {}: {}",
+                                    result.action,
+                                    result.subscription_id,
+                                    err
+                                );
+                            }
                         }
+                    }
 
-                        // Summary
-                        let cloned = results
-                            .iter()
-                            .filter(|r| r.action == SyncAction::Cloned)
-                            .count();
-                        let updated = results
-                            .iter()
-                            .filter(|r| r.action == SyncAction::Updated)
-                            .count();
-                        let failed = results.iter().filter(|r| r.is_failure()).count();
+                    // Summary
+                    let cloned = results
+                        .iter()
+                        .filter(|r| r.action == SyncAction::Cloned)
+                        .count();
+                    let updated = results
+                        .iter()
+                        .filter(|r| r.action == SyncAction::Updated)
+                        .count();
+                    let failed = results.iter().filter(|r| r.is_failure()).count();
 
-                        if ctx.verbose || failed > 0 {
-                            println!(
-                                "Plugin sync: {} cloned, {} updated, {} failed",
-                                cloned, updated, failed
+                    if ctx.verbose || failed > 0 {
+                        println!(
+                            "Plugin sync: {} cloned, {} updated, {} failed",
+                            cloned, updated, failed
+                        );
+                    }
+                } else if ctx.verbose {
+                    println!("Plugin auto-update: disabled");
+                }
+
+                // Report plugins whose checkout no longer matches the lockfile
+                if plugins_cfg.lock != PluginLockMode::Off {
+                    for (id, check) in sub_manager.verify_all() {
+                        if !check.is_ok() {
+                            tracing::warn!(
+                                "plugin {} does not match {}: {}",
+                                id,
+                                arawn_plugin::LOCKFILE_NAME,
+                                check
                             );
                         }
-                    } else if ctx.verbose {
-                        println!("Plugin auto-update: disabled");
                     }
-
-                    // Add synced plugin directories
-                    plugin_dirs.extend(sub_manager.plugin_dirs());
-                }
-                Err(e) => {
-                    tracing::warn!("failed to load plugin subscriptions: {}", e);
                 }
+
+                // Watch disabled subscriptions too so they can be enabled later
+                // (strict mode skips mismatches)
+                plugin_dirs.extend(sub_manager.known_plugin_dirs());
             }
-        }
 
-        let manager = PluginManager::new(plugin_dirs);
-        let watcher = PluginWatcher::new(manager);
-        let _events = watcher.load_initial().await;
+            let watcher = Arc::new(PluginWatcher::new(PluginManager::new(plugin_dirs)));
+            let service = match sub_manager {
+                Some(sub_manager) => {
+                    let service =
+                        PluginService::new(watcher.clone(), plugin_runtime.clone(), sub_manager);
+                    service.load_initial().await;
+                    Some(service)
+                }
+                None => {
+                    // Without subscriptions plugins can't be managed, but the
+                    // ones in plugin directories still load
+                    watcher.load_initial().await;
+                    plugin_runtime.apply(&watcher.state().read().await.plugins());
+                    None
+                }
+            };
 
-        // Register plugin CLI tools, hooks, and collect prompt fragments
-        {
-            let state = watcher.state();
-            let st = state.read().await;
+            // Collect template directories and MCP servers, and report what loaded
+            {
+                let state = watcher.state();
+                let st = state.read().await;
 
-            for plugin in st.plugins() {
-                // Register hooks from this plugin
-                if let Some(ref hooks_config) = plugin.hooks_config {
-                    hook_dispatcher.register_from_config(hooks_config, &plugin.plugin_dir);
+                for plugin in st.plugins() {
                     if ctx.verbose {
-                        let hook_count =
-                            hooks_config.hooks.values().map(|v| v.len()).sum::<usize>();
-                        if hook_count > 0 {
+                        if let Some(ref hooks_config) = plugin.hooks_config {
+                            let hook_count =
+                                hooks_config.hooks.values().map(|v| v.len()).sum::<usize>();
+                            if hook_count > 0 {
+                                println!(
+                                    "  Plugin '{}': {} hook(s) registered",
+                                    plugin.manifest.name, hook_count
+                                );
+                            }
+                        }
+                        for loaded_agent in &plugin.agent_configs {
                             println!(
-                                "  Plugin '{}': {} hook(s) registered",
-                                plugin.manifest.name, hook_count
+                                "  Plugin '{}': agent '{}' registered",
+                                plugin.manifest.name, loaded_agent.config.agent.name
                             );
                         }
                     }
-                }
 
-                // Collect agent configs for delegate tool
-                for loaded_agent in &plugin.agent_configs {
-                    plugin_agent_configs.insert(
-                        loaded_agent.config.agent.name.clone(),
-                        loaded_agent.config.clone(),
-                    );
-                    plugin_agent_sources.insert(
-                        loaded_agent.config.agent.name.clone(),
-                        plugin.manifest.name.clone(),
-                    );
-                    if ctx.verbose {
-                        println!(
-                            "  Plugin '{}': agent '{}' registered",
-                            plugin.manifest.name, loaded_agent.config.agent.name
-                        );
+                    // Collect workstream template directories
+                    for dir in plugin.manifest.templates_paths(&plugin.plugin_dir) {
+                        plugin_template_dirs.push((plugin.manifest.name.clone(), dir));
                     }
-                }
 
-                // Note: CLI tools (commands/) and prompt fragments are not yet implemented
-                // in the Claude plugin format migration. Those will be handled in future tasks.
-            }
-
-            if ctx.verbose || !st.is_empty() {
-                println!(
-                    "Plugins: {} loaded ({})",
-                    st.len(),
-                    st.plugins()
-                        .iter()
-                        .map(|p| p.manifest.name.as_str())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                );
-            }
+                    // Collect MCP servers declared by this plugin
+                    for server in &plugin.mcp_servers {
+                        if ctx.verbose {
+                            println!(
+                                "  Plugin '{}': MCP server '{}' registered",
+                                plugin.manifest.name, server.name
+                            );
+                        }
+                        plugin_mcp_servers.push(server.clone());
+                    }
+                }
 
-            if !hook_dispatcher.is_empty() && ctx.verbose {
-                println!("Hooks: {} total registered", hook_dispatcher.len());
-            }
-        }
+                if ctx.verbose || !st.is_empty() {
+                    println!(
+                        "Plugins: {} loaded ({})",
+                        st.len(),
+                        st.plugins()
+                            .iter()
+                            .map(|p| p.manifest.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
 
-        // Start hot-reload watcher if enabled
-        if plugins_cfg.hot_reload {
-            match watcher.watch() {
-                Ok((mut rx, handle)) => {
-                    // Spawn background task to log reload events
-                    tokio::spawn(async move {
-                        while let Some(event) = rx.recv().await {
-                            match event {
-                                arawn_plugin::PluginEvent::Reloaded { name, .. } => {
-                                    tracing::info!(plugin = %name, "plugin reloaded");
-                                }
-                                arawn_plugin::PluginEvent::Removed { name, .. } => {
-                                    tracing::info!(plugin = %name, "plugin removed");
-                                }
-                                arawn_plugin::PluginEvent::Error { plugin_dir, error } => {
-                                    tracing::warn!(
-                                        dir = %plugin_dir.display(),
-                                        error = %error,
-                                        "plugin reload failed"
-                                    );
-                                }
-                            }
-                        }
-                    });
-                    Some(handle)
+                if plugin_runtime.hook_count() > 0 && ctx.verbose {
+                    println!("Hooks: {} total registered", plugin_runtime.hook_count());
                 }
-                Err(e) => {
-                    tracing::warn!("failed to start plugin watcher: {}", e);
-                    None
+                if plugin_runtime.skill_count() > 0 && ctx.verbose {
+                    println!("Skills: {} registered", plugin_runtime.skill_count());
                 }
             }
+
+            (Some(watcher), service)
         } else {
             if ctx.verbose {
-                println!("Plugin hot-reload: disabled");
+                println!("Plugin system: disabled");
             }
-            None
-        }
-    } else {
-        if ctx.verbose {
-            println!("Plugin system: disabled");
-        }
-        None
-    };
+            (None, None)
+        };
 
     // ── MCP (Model Context Protocol) servers ────────────────────────────────
 
@@ -865,48 +1024,17 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         None
     };
 
-    // Connect pre-configured servers if any
+    // Connect pre-configured and plugin-declared servers if any
     if let Some(ref mut manager) = mcp_manager {
-        if !mcp_cfg.servers.is_empty() {
+        if !mcp_cfg.servers.is_empty() || !plugin_mcp_servers.is_empty() {
             // Convert config entries to McpServerConfig
-            let enabled_servers: Vec<McpServerConfig> = mcp_cfg
+            let mut enabled_servers: Vec<McpServerConfig> = mcp_cfg
                 .servers
                 .iter()
                 .filter(|s| s.enabled)
-                .filter_map(|entry| {
-                    if entry.is_http() {
-                        // HTTP transport
-                        let url = match &entry.url {
-                            Some(u) => u.clone(),
-                            None => {
-                                tracing::warn!(
-                                    " MCP server '{}' is HTTP but has no URL, skipping",
-                                    entry.name
-                                );
-                                return None;
-                            }
-                        };
-                        let mut config = McpServerConfig::http(&entry.name, &url);
-                        for (k, v) in entry.header_tuples() {
-                            config = config.with_header(k, v);
-                        }
-                        if let Some(timeout) = entry.timeout_secs {
-                            config = config.with_timeout(std::time::Duration::from_secs(timeout));
-                        }
-                        if let Some(retries) = entry.retries {
-                            config = config.with_retries(retries);
-                        }
-                        Some(config)
-                    } else {
-                        // Stdio transport
-                        Some(
-                            McpServerConfig::new(&entry.name, &entry.command)
-                                .with_args(entry.args.clone())
-                                .with_env(entry.env_tuples()),
-                        )
-                    }
-                })
+                .filter_map(mcp_server_config)
                 .collect();
+            enabled_servers.append(&mut plugin_mcp_servers);
 
             if enabled_servers.is_empty() {
                 if ctx.verbose {
@@ -978,16 +1106,107 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     };
 
-    // ── Hook dispatcher (shared between agent and subagent spawner) ─────────
+    let shared_mcp_manager: Option<arawn_server::state::SharedMcpManager> =
+        mcp_manager.take().map(|m| Arc::new(RwLock::new(m)));
 
-    // Create the shared hook dispatcher early so it can be used by both
-    // the agent and the subagent spawner for background execution events
-    let shared_hook_dispatcher: Option<arawn_types::SharedHookDispatcher> =
-        if !hook_dispatcher.is_empty() {
-            Some(Arc::new(hook_dispatcher))
-        } else {
+    // Plugin-declared MCP servers follow plugin reloads, enables and disables
+    if let (Some(watcher), Some(mcp)) = (&plugin_watcher, &shared_mcp_manager) {
+        watcher.set_mcp_manager(mcp.clone());
+    }
+
+    // Start plugin hot-reload watcher (after MCP so plugin servers follow reloads)
+    let _watcher_handle: Option<arawn_plugin::WatcherHandle> = match plugin_watcher {
+        Some(watcher) if plugins_cfg.hot_reload => match watcher.watch() {
+            Ok((mut rx, handle)) => {
+                // Apply reloads to the running agent
+                let service = plugin_service.clone();
+                let runtime = plugin_runtime.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = rx.recv().await {
+                        match &event {
+                            arawn_plugin::PluginEvent::Reloaded { name, .. } => {
+                                tracing::info!(plugin = %name, "plugin reloaded");
+                            }
+                            arawn_plugin::PluginEvent::Removed { name, .. } => {
+                                tracing::info!(plugin = %name, "plugin removed");
+                            }
+                            arawn_plugin::PluginEvent::Error { plugin_dir, error } => {
+                                tracing::warn!(
+                                    dir = %plugin_dir.display(),
+                                    error = %error,
+                                    "plugin reload failed"
+                                );
+                            }
+                        }
+                        match service {
+                            Some(ref service) => service.handle_event(&event).await,
+                            None => runtime.apply(&watcher.state().read().await.plugins()),
+                        }
+                    }
+                });
+                Some(handle)
+            }
+            Err(e) => {
+                tracing::warn!("failed to start plugin watcher: {}", e);
+                None
+            }
+        },
+        Some(_) => {
+            if ctx.verbose {
+                println!("Plugin hot-reload: disabled");
+            }
             None
-        };
+        }
+        None => None,
+    };
+
+    // ── Hook dispatcher (shared between agent and subagent spawner) ─────────
+
+    // The plugin runtime's dispatcher always runs the current plugins' hooks,
+    // so it is shared by both the agent and the subagent spawner for
+    // background execution events
+    let shared_hook_dispatcher: Option<arawn_types::SharedHookDispatcher> = plugins_cfg
+        .enabled
+        .then(|| plugin_runtime.hook_dispatcher());
+
+    // ── Webhooks (outbound event delivery) ──────────────────────────────────
+
+    let webhooks_cfg = config.webhooks.clone().unwrap_or_default();
+    let webhooks: Option<Arc<WebhookDispatcher>> = if webhooks_cfg.enabled {
+        let webhooks_db_path = data_dir.join("webhooks.db");
+        match WebhookStore::open(&webhooks_db_path) {
+            Ok(store) => {
+                if ctx.verbose {
+                    println!("Webhooks: {}", webhooks_db_path.display());
+                }
+                Some(Arc::new(
+                    WebhookDispatcher::new(Arc::new(store))
+                        .with_retry(RetryPolicy {
+                            max_attempts: webhooks_cfg.max_attempts,
+                            initial_backoff: Duration::from_secs(webhooks_cfg.initial_backoff_secs),
+                            max_backoff: Duration::from_secs(webhooks_cfg.max_backoff_secs),
+                        })
+                        .with_timeout(Duration::from_secs(webhooks_cfg.timeout_secs)),
+                ))
+            }
+            Err(e) => {
+                tracing::warn!("failed to open webhook store: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Background subagents report completion through the SubagentCompleted
+    // hook, so the spawner's dispatcher also emits webhooks
+    let spawner_hook_dispatcher: Option<arawn_types::SharedHookDispatcher> = match &webhooks {
+        Some(webhooks) => Some(Arc::new(WebhookHooks::new(
+            shared_hook_dispatcher.clone(),
+            Arc::clone(webhooks),
+        ))),
+        None => shared_hook_dispatcher.clone(),
+    };
 
     // ── Explore tool (RLM exploration agent) ────────────────────────────────
 
@@ -1033,14 +1252,14 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
 
     // ── Delegate tool (subagent delegation) ────────────────────────────────
 
-    // Create delegate tool if any plugin agents are defined
-    if !plugin_agent_configs.is_empty() {
+    // Plugin subagents come and go with their plugins, so the delegate tool is
+    // registered whenever the plugin system is on
+    if plugins_cfg.enabled {
         let parent_tools = Arc::new(tool_registry);
-        let mut spawner = arawn_plugin::PluginSubagentSpawner::with_sources(
+        let mut spawner = arawn_plugin::PluginSubagentSpawner::with_shared_agents(
             parent_tools.clone(),
             backend.clone(),
-            plugin_agent_configs,
-            plugin_agent_sources,
+            plugin_runtime.agents(),
         );
 
         // Wire default max_iterations from [agent.default] config
@@ -1049,12 +1268,23 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
 
         // Wire hook dispatcher for background subagent events
-        if let Some(ref dispatcher) = shared_hook_dispatcher {
+        if let Some(ref dispatcher) = spawner_hook_dispatcher {
             spawner = spawner.with_hook_dispatcher(dispatcher.clone());
         }
 
-        // Create a new mutable registry and copy tools from the Arc'd one
-        let mut new_registry = ToolRegistry::new();
+        // Subagents share the router so their routing hints take effect
+        if let Some(ref router) = router {
+            spawner = spawner.with_router(router.clone());
+        }
+
+        // Subagent LLM calls count towards usage too
+        if let Some(ref meter) = usage_meter {
+            spawner = spawner.with_usage_meter(meter.clone());
+        }
+
+        // Create a new mutable registry and copy tools from the Arc'd one,
+        // keeping the shared output overrides
+        let mut new_registry = ToolRegistry::new().with_output_overrides(output_overrides.clone());
         for name in parent_tools.names() {
             if let Some(tool) = parent_tools.get(name) {
                 new_registry.register_arc(tool);
@@ -1065,11 +1295,7 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         if ctx.verbose {
             println!(
                 "Delegate tool: {} subagent(s) available",
-                new_registry
-                    .names()
-                    .iter()
-                    .filter(|n| *n != &"delegate")
-                    .count()
+                plugin_runtime.agent_count()
             );
         }
 
@@ -1102,7 +1328,6 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
     let mut builder = Agent::builder()
         .with_shared_backend(backend)
         .with_tools(tool_registry)
-        .with_plugin_prompts(plugin_prompts)
         .with_prompt_builder(prompt_builder)
         .with_model(&resolved.model);
 
@@ -1116,11 +1341,49 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         builder = builder.with_max_tokens(max_tok);
     }
 
+    // Wire per-request LLM routing
+    if let Some(ref router) = router {
+        builder = builder.with_router(router.clone());
+        if let Some(hint) = agent_profile.and_then(|a| a.routing_hint.clone()) {
+            builder = builder.with_routing_hint(hint);
+        }
+    }
+
+    // Wire usage accounting and spend limits ([usage])
+    if let Some(meter) = usage_meter {
+        builder = builder.with_usage_meter(meter);
+    }
+
+    // Wire structured interaction logging ([logging.interactions])
+    if let Some(interactions_cfg) = config
+        .logging
+        .as_ref()
+        .map(|l| &l.interactions)
+        .filter(|i| i.enabled)
+    {
+        let logger_config = arawn_llm::interaction_log::InteractionLogConfig {
+            enabled: true,
+            path: interactions_cfg.path.clone(),
+            retention_days: interactions_cfg.retention_days,
+        };
+        match arawn_llm::interaction_log::InteractionLogger::new(logger_config) {
+            Ok(logger) => builder = builder.with_interaction_logger(Arc::new(logger)),
+            Err(e) => tracing::warn!("Failed to initialize interaction log: {}", e),
+        }
+    }
+
     // Wire up hook dispatcher to the agent
     if let Some(ref dispatcher) = shared_hook_dispatcher {
         builder = builder.with_hook_dispatcher(dispatcher.clone());
     }
 
+    // Wire plugin skills (`/plugin:skill args`) and prompt fragments
+    if plugins_cfg.enabled {
+        builder = builder
+            .with_skill_expander(plugin_runtime.skill_expander())
+            .with_live_plugin_prompts(plugin_runtime.prompts());
+    }
+
     if let Some(ref ws) = workspace {
         if ctx.verbose {
             println!("Workspace: {}", ws.display());
@@ -1155,7 +1418,9 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     }
 
-    // Wire filesystem gate resolver for workstream-scoped tool execution
+    // Wire filesystem gate resolver for workstream-scoped tool execution.
+    // The resolver reads per-workstream sandbox settings through the
+    // late-filled workstream slot.
     {
         use arawn_workstream::DirectoryManager;
 
@@ -1189,15 +1454,26 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
                 }
             };
 
+        let late_workstreams = Arc::clone(&late_workstreams);
         let resolver: arawn_types::FsGateResolver =
             Arc::new(move |session_id: &str, workstream_id: &str| {
                 let gate: Arc<dyn arawn_types::FsGate> = match &sandbox {
-                    Some(sandbox) => Arc::new(WorkstreamFsGate::new(
-                        &dm,
-                        Arc::clone(sandbox),
-                        workstream_id,
-                        session_id,
-                    )),
+                    Some(sandbox) => {
+                        let network_domains = late_workstreams
+                            .get()
+                            .and_then(|mgr| mgr.get_workstream(workstream_id).ok())
+                            .map(|ws| ws.settings.network_domains)
+                            .unwrap_or_default();
+                        Arc::new(
+                            WorkstreamFsGate::new(
+                                &dm,
+                                Arc::clone(sandbox),
+                                workstream_id,
+                                session_id,
+                            )
+                            .with_allowed_domains(network_domains),
+                        )
+                    }
                     None => Arc::new(WorkstreamFsGate::path_only(&dm, workstream_id, session_id)),
                 };
                 Some(gate)
@@ -1206,7 +1482,10 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         builder = builder.with_fs_gate_resolver(resolver);
     }
 
-    // Wire memory store and embedder for active recall
+    // Wire memory store and embedder for active recall; [memory.recall] is
+    // shared so config reloads apply from the next turn
+    let live_recall = Arc::new(std::sync::RwLock::new(recall_config(&memory_cfg.recall)));
+    builder = builder.with_live_recall_config(live_recall.clone());
     if let Some(ref store) = memory_store {
         builder = builder.with_memory_store(store.clone());
         if ctx.verbose {
@@ -1392,8 +1671,60 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
     if let Some(dispatcher) = shared_hook_dispatcher {
         app_state = app_state.with_hook_dispatcher(dispatcher);
     }
-    if let Some(manager) = mcp_manager.take() {
-        app_state = app_state.with_mcp_manager(manager);
+    if let Some(ref manager) = shared_mcp_manager {
+        app_state = app_state.with_shared_mcp_manager(manager.clone());
+    }
+    if let Some(ledger) = usage_ledger {
+        app_state = app_state.with_usage_ledger(ledger);
+    }
+    if let Some(handle) = metrics_handle {
+        app_state = app_state.with_metrics(handle);
+    }
+    if let Some(ref webhooks) = webhooks {
+        app_state = app_state.with_webhooks(Arc::clone(webhooks));
+        if let Some(ref engine) = pipeline_engine {
+            forward_workflow_runs(engine, Arc::clone(webhooks));
+        }
+    }
+    if let Some(workflows) = workflow_service {
+        app_state = app_state.with_workflows(workflows);
+    }
+    if let Some(plugins) = plugin_service {
+        app_state = app_state.with_plugins(plugins);
+    }
+
+    // Config updates from PUT/PATCH /api/v1/config and SIGHUP
+    let mut config_service = ConfigService::new(loaded.config.clone(), loaded.sources.clone())
+        .with_backends(reloadable, reload_backend)
+        .with_tool_output(output_overrides)
+        .with_recall(live_recall);
+    if let Some(ref manager) = shared_mcp_manager {
+        config_service = config_service.with_mcp_manager(manager.clone());
+    }
+    #[cfg(unix)]
+    spawn_sighup_reload(config_service.clone());
+    app_state = app_state.with_config_service(config_service);
+
+    let inbound_hooks = build_inbound_hooks(&webhooks_cfg.inbound);
+    if !inbound_hooks.is_empty() {
+        if ctx.verbose {
+            let mut names: Vec<_> = inbound_hooks.keys().map(String::as_str).collect();
+            names.sort_unstable();
+            println!("Inbound hooks: {}", names.join(", "));
+        }
+        app_state = app_state.with_inbound_hooks(inbound_hooks);
+    }
+
+    // ── Access store (named API tokens and resource ownership) ───────────
+    let access_db_path = data_dir.join("access.db");
+    match AccessStore::open(&access_db_path) {
+        Ok(store) => {
+            if ctx.verbose {
+                println!("Access store: {}", access_db_path.display());
+            }
+            app_state = app_state.with_access_store(Arc::new(store));
+        }
+        Err(e) => tracing::warn!("failed to open access store: {}", e),
     }
 
     // ── Workstream manager ────────────────────────────────────────────────
@@ -1410,7 +1741,11 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         session_timeout_minutes: ws_cfg.session_timeout_minutes,
     };
 
-    match WorkstreamManager::new(&ws_config) {
+    // Same root as the filesystem gate, so production/ and work/ are
+    // created (and scaffolded from templates) where tools look for them
+    match WorkstreamManager::new(&ws_config).map(|mgr| {
+        mgr.with_directory_manager(arawn_workstream::DirectoryManager::new(&ws_config.data_dir))
+    }) {
         Ok(mgr) => {
             // Seed test data if requested
             if args.seed {
@@ -1418,6 +1753,25 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
             }
 
             app_state = app_state.with_workstreams(mgr);
+            if let Some(mgr) = app_state.workstreams() {
+                let _ = late_workstreams.set(Arc::clone(mgr));
+                if let Some(ref webhooks) = webhooks
+                    && webhooks_cfg.disk_check_interval_secs > 0
+                {
+                    let paths = config.paths.clone().unwrap_or_default();
+                    spawn_disk_pressure_webhooks(
+                        Arc::new(DirectoryManager::new(&ws_config.data_dir)),
+                        Arc::clone(mgr),
+                        arawn_workstream::cleanup::CleanupConfig {
+                            total_usage_warning_bytes: paths.total_warning_bytes(),
+                            workstream_usage_warning_bytes: paths.workstream_warning_bytes(),
+                            ..Default::default()
+                        },
+                        Duration::from_secs(webhooks_cfg.disk_check_interval_secs),
+                        Arc::clone(webhooks),
+                    );
+                }
+            }
             if ctx.verbose {
                 println!(
                     "Workstreams: db={}, data={}",
@@ -1431,6 +1785,23 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
         }
     }
 
+    // ── Workstream templates ─────────────────────────────────────────────
+    let templates_dir = resolve_path(ws_cfg.templates_dir.clone(), "templates");
+    if let Err(e) = std::fs::create_dir_all(&templates_dir) {
+        tracing::warn!("failed to create templates directory: {}", e);
+    }
+    let mut templates = TemplateRegistry::new().with_dir(&templates_dir);
+    for (plugin, dir) in plugin_template_dirs {
+        templates = templates.with_plugin_dir(&plugin, dir);
+    }
+    if pipeline_cfg.enabled {
+        templates = templates.with_workflow_dir(&pipeline_workflow_dir);
+    }
+    app_state = app_state.with_templates(templates);
+    if ctx.verbose {
+        println!("Workstream templates: {}", templates_dir.display());
+    }
+
     // Apply session cache configuration (must be after workstreams so cache is recreated with manager)
     let session_cfg = config.session.clone().unwrap_or_default();
     app_state = app_state.with_session_config(&session_cfg);
@@ -1496,11 +1867,11 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
     }
 
     // Shutdown MCP servers
-    if let Some(ref mut manager) = mcp_manager {
+    if let Some(ref manager) = shared_mcp_manager {
         if ctx.verbose {
             println!("Shutting down MCP servers...");
         }
-        if let Err(e) = manager.shutdown_all() {
+        if let Err(e) = manager.write().await.shutdown_all() {
             tracing::warn!("MCP shutdown error: {}", e);
         }
     }
@@ -1508,6 +1879,176 @@ pub async fn run(args: StartArgs, ctx: &Context) -> Result<()> {
     Ok(())
 }
 
+/// Build the `/hooks/{name}` triggers from `[webhooks.inbound]`, skipping
+/// hooks without a secret or without exactly one target.
+fn build_inbound_hooks(configs: &HashMap<String, InboundHookConfig>) -> InboundHooks {
+    let mut hooks = InboundHooks::new();
+    for (name, cfg) in configs {
+        let Some(secret) = cfg.resolve_secret() else {
+            tracing::warn!(hook = %name, "Inbound hook has no secret; skipping");
+            continue;
+        };
+        let target = match (&cfg.workflow, &cfg.workstream) {
+            (Some(workflow), None) => HookTarget::Workflow(workflow.clone()),
+            (None, Some(workstream)) => HookTarget::Workstream(workstream.clone()),
+            _ => {
+                tracing::warn!(
+                    hook = %name,
+                    "Inbound hook needs exactly one of `workflow` and `workstream`; skipping"
+                );
+                continue;
+            }
+        };
+        let mut auth = match cfg.auth {
+            InboundHookAuth::Hmac => HookAuth::hmac(secret),
+            InboundHookAuth::Token => HookAuth::token(secret),
+        };
+        if let Some(ref header) = cfg.header {
+            auth = auth.with_header(header.clone());
+        }
+        let mut hook = InboundHook::new(name.clone(), auth, target);
+        for (key, pointer) in &cfg.context {
+            hook = hook.with_context(key.clone(), pointer.clone());
+        }
+        if let Some(ref message) = cfg.message {
+            hook = hook.with_message(message.clone());
+        }
+        hooks.insert(name.clone(), hook);
+    }
+    hooks
+}
+
+/// Emit a `workflow_finished` webhook for every finished pipeline run.
+fn forward_workflow_runs(engine: &PipelineEngine, webhooks: Arc<WebhookDispatcher>) {
+    let mut runs = engine.subscribe_runs();
+    tokio::spawn(async move {
+        loop {
+            match runs.recv().await {
+                Ok(run) => webhooks.emit(WebhookEvent::workflow_finished(
+                    &run.workflow,
+                    run.execution_id.as_deref().unwrap_or_default(),
+                    run.status,
+                    run.duration_ms,
+                    run.error.as_deref(),
+                )),
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "workflow_finished webhooks dropped");
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Read the config files again on SIGHUP and apply what changed.
+#[cfg(unix)]
+fn spawn_sighup_reload(service: ConfigService) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            tracing::warn!("Failed to listen for SIGHUP, config reload disabled: {}", e);
+            return;
+        }
+    };
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            match service.reload().await {
+                Ok(update) => {
+                    tracing::info!(
+                        changed = update.changed.len(),
+                        applied = ?update.applied,
+                        "Config reloaded"
+                    );
+                    if !update.restart_required.is_empty() {
+                        tracing::warn!(
+                            keys = ?update.restart_required,
+                            "Config changes need a restart to take effect"
+                        );
+                    }
+                    for warning in &update.warnings {
+                        tracing::warn!("{}", warning);
+                    }
+                }
+                Err(e) => tracing::error!("Config reload failed, keeping current config: {}", e),
+            }
+        }
+    });
+}
+
+/// Build the factory config reloads use to recreate a profile's backend,
+/// applying the same CLI overrides to the default profile as at startup.
+fn backend_factory(args: &StartArgs) -> BackendFactory {
+    let args = Arc::new(args.clone());
+    Arc::new(move |name: String, config: arawn_config::ArawnConfig| {
+        let args = Arc::clone(&args);
+        Box::pin(async move {
+            let resolved = if name == "default" {
+                resolve_with_cli_overrides(&config, &args)
+            } else {
+                let llm = config
+                    .llm_profiles
+                    .get(&name)
+                    .ok_or_else(|| format!("profile '{}' not found", name))?;
+                resolve_profile(&name, llm)
+            }
+            .map_err(|e| e.to_string())?;
+            let backend = create_backend(&resolved, config.oauth.as_ref())
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok((backend, resolved.model))
+        })
+    })
+}
+
+/// Periodically check disk usage and emit `disk_pressure` webhooks.
+///
+/// Only changes are reported: a scope that stays above its threshold is
+/// reported once, and again if its level changes.
+fn spawn_disk_pressure_webhooks(
+    dir_manager: Arc<DirectoryManager>,
+    workstreams: Arc<WorkstreamManager>,
+    cleanup_config: arawn_workstream::cleanup::CleanupConfig,
+    interval: Duration,
+    webhooks: Arc<WebhookDispatcher>,
+) {
+    use arawn_workstream::cleanup::{PressureLevel, check_disk_pressure};
+
+    tokio::spawn(async move {
+        let mut reported: HashMap<String, PressureLevel> = HashMap::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let (dm, wm, cfg) = (
+                Arc::clone(&dir_manager),
+                Arc::clone(&workstreams),
+                cleanup_config.clone(),
+            );
+            let result = match tokio::task::spawn_blocking(move || {
+                check_disk_pressure(&dm, &wm, &cfg)
+            })
+            .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::warn!("disk pressure check failed: {}", e);
+                    continue;
+                }
+            };
+
+            let mut current = HashMap::new();
+            for event in &result.events {
+                if reported.get(&event.scope) != Some(&event.level) {
+                    webhooks.emit(WebhookEvent::disk_pressure(event));
+                }
+                current.insert(event.scope.clone(), event.level);
+            }
+            reported = current;
+        }
+    });
+}
+
 /// Resolve LLM config, applying CLI overrides on top of config file values.
 fn resolve_with_cli_overrides(
     config: &arawn_config::ArawnConfig,
@@ -1539,6 +2080,7 @@ fn resolve_with_cli_overrides(
                 resolved_from: arawn_config::ResolvedFrom::GlobalDefault,
                 retry_max: None,
                 retry_backoff_ms: None,
+                tool_format: None,
             }
         }
     };
@@ -1571,10 +2113,28 @@ fn make_api_key_provider(backend: Backend, config_value: Option<String>) -> ApiK
     })
 }
 
-/// Create an LLM backend from a resolved config.
+/// Create an LLM backend from a resolved config, wrapping it for
+/// prompt-based tool calling when the profile sets `tool_format`.
 async fn create_backend(
     resolved: &ResolvedLlm,
     oauth_overrides: Option<&arawn_config::OAuthConfigOverride>,
+) -> Result<SharedBackend> {
+    let backend = create_provider_backend(resolved, oauth_overrides).await?;
+    let Some(tool_format) = resolved.tool_format else {
+        return Ok(backend);
+    };
+    let format = match tool_format {
+        arawn_config::ToolFormat::Json => ToolPromptFormat::Json,
+        arawn_config::ToolFormat::Xml => ToolPromptFormat::Xml,
+        arawn_config::ToolFormat::Hermes => ToolPromptFormat::Hermes,
+    };
+    Ok(Arc::new(PromptToolBackend::new(backend, format)))
+}
+
+/// Create an LLM backend from a resolved config.
+async fn create_provider_backend(
+    resolved: &ResolvedLlm,
+    oauth_overrides: Option<&arawn_config::OAuthConfigOverride>,
 ) -> Result<SharedBackend> {
     match resolved.backend {
         Backend::Anthropic => {
@@ -1605,6 +2165,22 @@ async fn create_backend(
             }
             Ok(Arc::new(OpenAiBackend::new(config)?))
         }
+        Backend::Gemini => {
+            let provider = make_api_key_provider(resolved.backend, resolved.api_key.clone());
+            let mut config = GeminiConfig::new("placeholder");
+            config.api_key = provider;
+            if let Some(ref base_url) = resolved.base_url {
+                config = config.with_base_url(base_url);
+            }
+            config = config.with_model(&resolved.model);
+            if let Some(max) = resolved.retry_max {
+                config = config.with_max_retries(max);
+            }
+            if let Some(ms) = resolved.retry_backoff_ms {
+                config = config.with_retry_backoff(Duration::from_millis(ms));
+            }
+            Ok(Arc::new(GeminiBackend::new(config)?))
+        }
         Backend::Groq => {
             let provider = make_api_key_provider(resolved.backend, resolved.api_key.clone());
             let mut config = OpenAiConfig::groq("placeholder");
@@ -1712,12 +2288,13 @@ fn parse_backend(s: &str) -> Result<Backend> {
     match s.to_lowercase().as_str() {
         "anthropic" => Ok(Backend::Anthropic),
         "openai" => Ok(Backend::Openai),
+        "gemini" | "google" => Ok(Backend::Gemini),
         "groq" => Ok(Backend::Groq),
         "ollama" => Ok(Backend::Ollama),
         "custom" => Ok(Backend::Custom),
         "claude-oauth" | "claudeoauth" => Ok(Backend::ClaudeOauth),
         other => Err(anyhow::anyhow!(
-            "Unknown backend '{}'. Valid: anthropic, openai, groq, ollama, custom, claude-oauth",
+            "Unknown backend '{}'. Valid: anthropic, openai, gemini, groq, ollama, custom, claude-oauth",
             other
         )),
     }
@@ -1773,14 +2350,114 @@ fn resolve_profile(name: &str, llm_config: &LlmConfig) -> Result<ResolvedLlm> {
         },
         retry_max: llm_config.retry_max,
         retry_backoff_ms: llm_config.retry_backoff_ms,
+        tool_format: llm_config.tool_format,
     })
 }
 
+/// Build the LLM router from `[routing]` config and the created profile backends.
+///
+/// Rules and classifier labels referencing profiles that failed to load are
+/// rejected, so misconfigured routing fails at startup rather than per request.
+fn build_router(
+    routing: &arawn_config::RoutingConfig,
+    backends: &HashMap<String, SharedBackend>,
+    models: &HashMap<String, String>,
+) -> Result<LlmRouter> {
+    let profile = |name: &str| -> Result<RouteProfile> {
+        match (backends.get(name), models.get(name)) {
+            (Some(backend), Some(model)) => {
+                Ok(RouteProfile::new(name, backend.clone(), model.clone()))
+            }
+            _ => Err(anyhow::anyhow!(
+                "routing references unknown or unavailable LLM profile '{}'",
+                name
+            )),
+        }
+    };
+
+    let default_name = routing.default.as_deref().unwrap_or("default");
+    let mut router = LlmRouter::new(profile(default_name)?);
+
+    for (i, rule) in routing.rules.iter().enumerate() {
+        router = router
+            .with_profile(profile(&rule.profile)?)
+            .with_rule(RoutingRule::new(
+                rule.name
+                    .clone()
+                    .unwrap_or_else(|| format!("rule-{}", i + 1)),
+                RouteCondition {
+                    min_prompt_tokens: rule.min_prompt_tokens,
+                    max_prompt_tokens: rule.max_prompt_tokens,
+                    has_tools: rule.has_tools,
+                    min_iteration: rule.min_iteration,
+                    max_iteration: rule.max_iteration,
+                    hint: rule.hint.clone(),
+                },
+                rule.profile.clone(),
+            ));
+    }
+
+    // Any loaded profile can be selected directly by a routing hint
+    for name in backends.keys() {
+        if let Ok(p) = profile(name) {
+            router = router.with_profile(p);
+        }
+    }
+
+    if let Some(ref classifier_cfg) = routing.classifier {
+        let runner = profile(&classifier_cfg.profile)?;
+        let mut classifier = LlmClassifier::new(runner.backend, runner.model);
+        for label in &classifier_cfg.labels {
+            router = router.with_profile(profile(&label.profile)?);
+            classifier = classifier.with_label(&label.name, &label.description, &label.profile);
+        }
+        router = router
+            .with_classifier(Arc::new(classifier))
+            .with_min_confidence(classifier_cfg.min_confidence);
+    }
+
+    router.validate()?;
+    Ok(router)
+}
+
+/// Build the pricing table from `[usage.pricing]`.
+fn build_pricing_table(usage: &arawn_config::UsageConfig) -> PricingTable {
+    usage
+        .pricing
+        .iter()
+        .fold(PricingTable::new(), |table, (model, price)| {
+            let mut pricing = ModelPricing::new(price.input, price.output);
+            pricing.cache_write_per_mtok = price.cache_write;
+            pricing.cache_read_per_mtok = price.cache_read;
+            table.with_model(model, pricing)
+        })
+}
+
+/// Build workstream spend limits from `[usage]`.
+fn build_spend_limits(usage: &arawn_config::UsageConfig) -> SpendLimits {
+    let period = match usage.period {
+        arawn_config::SpendPeriod::Day => LimitPeriod::Day,
+        arawn_config::SpendPeriod::Month => LimitPeriod::Month,
+        arawn_config::SpendPeriod::Total => LimitPeriod::Total,
+    };
+    usage.workstreams.iter().fold(
+        SpendLimits::new(period)
+            .with_default(SpendLimit::new(usage.soft_limit_usd, usage.hard_limit_usd)),
+        |limits, (workstream_id, limit)| {
+            limits.with_workstream(
+                workstream_id,
+                SpendLimit::new(limit.soft_limit_usd, limit.hard_limit_usd),
+            )
+        },
+    )
+}
+
 /// Build an `EmbedderSpec` from the application's `EmbeddingConfig`.
 fn build_embedder_spec(config: &arawn_config::EmbeddingConfig) -> EmbedderSpec {
     let provider = match config.provider {
         EmbeddingProvider::Local => "local",
         EmbeddingProvider::OpenAi => "openai",
+        EmbeddingProvider::Gemini => "gemini",
         EmbeddingProvider::Mock => "mock",
     };
 
@@ -1799,6 +2476,14 @@ fn build_embedder_spec(config: &arawn_config::EmbeddingConfig) -> EmbedderSpec {
         })
         .unwrap_or((None, None, None));
 
+    let gemini = config.gemini.clone().unwrap_or_default();
+    let gemini_api_key = gemini.api_key.clone().or_else(|| {
+        std::env::var("GEMINI_API_KEY").ok().or_else(|| {
+            arawn_config::secrets::resolve_api_key(&arawn_config::Backend::Gemini, None)
+                .map(|r| r.value)
+        })
+    });
+
     let (local_model_path, local_tokenizer_path, local_model_url, local_tokenizer_url) = config
         .local
         .as_ref()
@@ -1817,6 +2502,9 @@ fn build_embedder_spec(config: &arawn_config::EmbeddingConfig) -> EmbedderSpec {
         openai_api_key,
         openai_model,
         openai_base_url,
+        gemini_api_key,
+        gemini_model: Some(gemini.model),
+        gemini_base_url: gemini.base_url,
         local_model_path,
         local_tokenizer_path,
         dimensions: Some(config.effective_dimensions()),
@@ -1829,6 +2517,7 @@ fn default_model(backend: &Backend) -> String {
     match backend {
         Backend::Anthropic | Backend::ClaudeOauth => "claude-sonnet-4-20250514".to_string(),
         Backend::Openai => "gpt-4o".to_string(),
+        Backend::Gemini => "gemini-2.5-flash".to_string(),
         Backend::Groq => "llama-3.1-70b-versatile".to_string(),
         Backend::Ollama => "llama3.2".to_string(),
         Backend::Custom => "default".to_string(),
@@ -2015,3 +2704,19 @@ fn seed_test_data(manager: &WorkstreamManager, verbose: bool) {
         println!("Seed: no new workstreams created (already seeded)");
     }
 }
+
+/// Message search over a workstream manager that is created after the agent.
+struct LateMessageSearcher(Arc<std::sync::OnceLock<Arc<WorkstreamManager>>>);
+
+impl arawn_types::MessageSearcher for LateMessageSearcher {
+    fn search_messages(
+        &self,
+        query: &arawn_types::MessageSearchQuery,
+    ) -> std::result::Result<arawn_types::MessageSearchResults, arawn_types::MessageSearchError>
+    {
+        match self.0.get() {
+            Some(mgr) => arawn_types::MessageSearcher::search_messages(mgr.as_ref(), query),
+            None => Err(arawn_types::MessageSearchError::Unavailable),
+        }
+    }
+}
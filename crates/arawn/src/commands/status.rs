@@ -1,4 +1,7 @@
 //! Status command - shows server status and resource usage.
+//!
+//! With `--usage`, also reports LLM token usage and cost from the server's
+//! usage ledger.
 
 use anyhow::Result;
 use clap::Args;
@@ -7,15 +10,34 @@ use serde::Serialize;
 
 use super::Context;
 use super::output;
-use crate::client::Client;
+use crate::client::{Client, UsageReport};
 
 /// Arguments for the status command.
 #[derive(Args, Debug)]
 #[command(after_help = "\x1b[1mExamples:\x1b[0m
   arawn status                      Check default server
   arawn status --json               Machine-readable output
-  arawn status --server http://remote:8080")]
-pub struct StatusArgs {}
+  arawn status --server http://remote:8080
+  arawn status --usage              Token usage and cost by day
+  arawn status --usage --group-by profile --since 2026-01-01
+  arawn status --usage --workstream my-project")]
+pub struct StatusArgs {
+    /// Show LLM token usage and cost
+    #[arg(long)]
+    pub usage: bool,
+
+    /// Group usage by day, session, workstream, profile or model
+    #[arg(long, default_value = "day", requires = "usage")]
+    pub group_by: String,
+
+    /// Only show usage for this workstream (includes spend limits)
+    #[arg(long, requires = "usage")]
+    pub workstream: Option<String>,
+
+    /// Only show usage since this date (YYYY-MM-DD)
+    #[arg(long, requires = "usage")]
+    pub since: Option<String>,
+}
 
 /// Status response for JSON output.
 #[derive(Debug, Serialize)]
@@ -23,19 +45,36 @@ struct StatusOutput {
     running: bool,
     version: Option<String>,
     server_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<UsageReport>,
 }
 
 /// Run the status command.
-pub async fn run(_args: StatusArgs, ctx: &Context) -> Result<()> {
+pub async fn run(args: StatusArgs, ctx: &Context) -> Result<()> {
     let client = Client::new(&ctx.server_url)?;
 
     match client.health().await {
         Ok(health) => {
+            let usage = if args.usage {
+                Some(
+                    client
+                        .usage_report(
+                            &args.group_by,
+                            args.workstream.as_deref(),
+                            args.since.as_deref(),
+                        )
+                        .await?,
+                )
+            } else {
+                None
+            };
+
             if ctx.json_output {
                 let output = StatusOutput {
                     running: true,
                     version: Some(health.version.clone()),
                     server_url: ctx.server_url.clone(),
+                    usage,
                 };
                 println!("{}", serde_json::to_string_pretty(&output)?);
             } else {
@@ -45,6 +84,10 @@ pub async fn run(_args: StatusArgs, ctx: &Context) -> Result<()> {
                 output::kv("Version", &health.version);
                 output::kv("Server", &ctx.server_url);
                 println!();
+
+                if let Some(report) = usage {
+                    print_usage(&report);
+                }
             }
         }
         Err(e) => {
@@ -53,6 +96,7 @@ pub async fn run(_args: StatusArgs, ctx: &Context) -> Result<()> {
                     running: false,
                     version: None,
                     server_url: ctx.server_url.clone(),
+                    usage: None,
                 };
                 println!("{}", serde_json::to_string_pretty(&output)?);
             } else {
@@ -75,3 +119,64 @@ pub async fn run(_args: StatusArgs, ctx: &Context) -> Result<()> {
 
     Ok(())
 }
+
+/// Print a usage report as a table.
+fn print_usage(report: &UsageReport) {
+    output::header(&format!("Usage by {}", report.group_by));
+
+    if report.rows.is_empty() {
+        output::hint("  No usage recorded");
+        println!();
+    } else {
+        let dim = Style::new().dim();
+        println!(
+            "  {}",
+            dim.apply_to(format!(
+                "{:<28} {:>7} {:>12} {:>12} {:>10}",
+                report.group_by, "calls", "input", "output", "cost"
+            ))
+        );
+        for row in &report.rows {
+            let key = if row.key.is_empty() { "-" } else { &row.key };
+            println!(
+                "  {:<28} {:>7} {:>12} {:>12} {:>10}",
+                output::truncate(key, 28),
+                row.calls,
+                row.input_tokens,
+                row.output_tokens,
+                format!("${:.4}", row.cost_usd),
+            );
+        }
+        let total = &report.total;
+        println!(
+            "  {}",
+            Style::new().bold().apply_to(format!(
+                "{:<28} {:>7} {:>12} {:>12} {:>10}",
+                "total",
+                total.calls,
+                total.input_tokens,
+                total.output_tokens,
+                format!("${:.4}", total.cost_usd),
+            ))
+        );
+        println!();
+    }
+
+    if let Some(ref budget) = report.budget {
+        let limit = |v: Option<f64>| v.map_or("none".to_string(), |v| format!("${:.2}", v));
+        let status = match budget.status.as_str() {
+            "hard_limit_exceeded" => Style::new().red().apply_to("● hard limit reached"),
+            "soft_limit_exceeded" => Style::new().yellow().apply_to("● soft limit reached"),
+            _ => Style::new().green().apply_to("● within limit"),
+        };
+        output::header(&format!("Budget ({})", budget.workstream_id));
+        output::kv("Status", status);
+        output::kv(
+            "Spent",
+            format!("${:.2} (period: {})", budget.spent_usd, budget.period),
+        );
+        output::kv("Soft limit", limit(budget.soft_limit_usd));
+        output::kv("Hard limit", limit(budget.hard_limit_usd));
+        println!();
+    }
+}
@@ -0,0 +1,511 @@
+//! Workstream command - create workstreams from templates, manage production
+//! snapshots and move workstreams between machines.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use arawn_domain::{ImportOptions, WorkstreamArchiver};
+use arawn_workstream::{DirectoryManager, WorkstreamConfig, WorkstreamManager};
+use clap::{Args, Subcommand};
+use console::Style;
+
+use super::Context;
+use super::output;
+use crate::client::{Client, CreateWorkstreamRequest};
+
+/// Arguments for the workstream command.
+#[derive(Args, Debug)]
+#[command(after_help = "\x1b[1mExamples:\x1b[0m
+  arawn workstream templates                            List workstream templates
+  arawn workstream create \"Fusion\" -t research -p topic=tokamaks
+                                                        Scaffold a workstream from a template
+  arawn workstream snapshot list <ws>                   List production snapshots
+  arawn workstream snapshot create <ws> -l \"pre-edit\"   Take a snapshot now
+  arawn workstream snapshot diff <ws> <id>              Compare a snapshot with production
+  arawn workstream snapshot restore <ws> <id>           Roll production back to a snapshot
+  arawn workstream export <ws> -o ws.tar.zst            Bundle a workstream into an archive
+  arawn workstream import ws.tar.zst --new-ids          Import a copy alongside the original")]
+pub struct WorkstreamArgs {
+    #[command(subcommand)]
+    pub command: WorkstreamCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkstreamCommands {
+    /// List available workstream templates
+    Templates,
+
+    /// Create a workstream, optionally from a template
+    Create {
+        /// Workstream title
+        title: String,
+
+        /// Template to scaffold the workstream from
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Template parameter as key=value (repeatable)
+        #[arg(short, long = "param", value_name = "KEY=VALUE", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+
+        /// Tag to add (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+
+    /// Manage snapshots of a workstream's production directory
+    #[command(subcommand)]
+    Snapshot(SnapshotCommands),
+
+    /// Export a workstream, its history, memories, notes and files to an archive
+    Export {
+        /// Workstream ID
+        workstream: String,
+
+        /// Archive path (defaults to <workstream>.tar.zst)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import a workstream archive
+    Import {
+        /// Archive path
+        file: PathBuf,
+
+        /// Assign fresh IDs instead of failing on conflicts
+        #[arg(long)]
+        new_ids: bool,
+
+        /// Title for the imported workstream
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Re-embed memories if the archive came from a different embedder
+        #[arg(long)]
+        reembed: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommands {
+    /// List snapshots, newest first
+    List {
+        /// Workstream ID
+        workstream: String,
+    },
+
+    /// Take a snapshot of the production directory
+    Create {
+        /// Workstream ID
+        workstream: String,
+
+        /// Label to identify the snapshot
+        #[arg(short, long)]
+        label: Option<String>,
+    },
+
+    /// Show what changed since a snapshot
+    Diff {
+        /// Workstream ID
+        workstream: String,
+
+        /// Snapshot ID
+        id: String,
+
+        /// Compare against another snapshot instead of current production
+        #[arg(long)]
+        against: Option<String>,
+    },
+
+    /// Restore the production directory from a snapshot
+    Restore {
+        /// Workstream ID
+        workstream: String,
+
+        /// Snapshot ID
+        id: String,
+    },
+}
+
+/// Run the workstream command.
+pub async fn run(args: WorkstreamArgs, ctx: &Context) -> Result<()> {
+    match args.command {
+        WorkstreamCommands::Templates => cmd_templates(ctx).await,
+        WorkstreamCommands::Create {
+            title,
+            template,
+            params,
+            tags,
+        } => {
+            let request = CreateWorkstreamRequest {
+                title,
+                tags,
+                template,
+                params: params.into_iter().collect(),
+            };
+            cmd_create(&request, ctx).await
+        }
+        WorkstreamCommands::Snapshot(cmd) => run_snapshot(cmd, ctx).await,
+        WorkstreamCommands::Export { workstream, output } => {
+            cmd_export(&workstream, output, ctx).await
+        }
+        WorkstreamCommands::Import {
+            file,
+            new_ids,
+            title,
+            reembed,
+        } => {
+            let options = ImportOptions {
+                new_ids,
+                title,
+                reembed,
+            };
+            cmd_import(&file, &options, ctx).await
+        }
+    }
+}
+
+fn parse_param(s: &str) -> std::result::Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{s}'")),
+    }
+}
+
+async fn cmd_templates(ctx: &Context) -> Result<()> {
+    let client = Client::new(&ctx.server_url)?;
+    let dim = Style::new().dim();
+
+    match client.list_templates().await {
+        Ok(templates) => {
+            if ctx.json_output {
+                println!("{}", serde_json::to_string_pretty(&templates)?);
+            } else {
+                output::header("Workstream templates");
+
+                if templates.is_empty() {
+                    output::hint("No templates found");
+                }
+                for t in &templates {
+                    println!(
+                        "  {} {}",
+                        Style::new().cyan().apply_to(&t.name),
+                        dim.apply_to(t.description.as_deref().unwrap_or_default())
+                    );
+                    for p in &t.params {
+                        let detail = match (&p.default, p.required) {
+                            (Some(default), _) => format!("(default: {default})"),
+                            (None, true) => "(required)".to_string(),
+                            (None, false) => String::new(),
+                        };
+                        println!(
+                            "      {:<16} {} {}",
+                            p.name,
+                            p.description.as_deref().unwrap_or_default(),
+                            dim.apply_to(detail)
+                        );
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_create(request: &CreateWorkstreamRequest, ctx: &Context) -> Result<()> {
+    let client = Client::new(&ctx.server_url)?;
+
+    match client.create_workstream(request).await {
+        Ok(ws) => {
+            if ctx.json_output {
+                println!("{}", serde_json::to_string_pretty(&ws)?);
+            } else {
+                output::success(format!(
+                    "Created {} {}",
+                    ws.title,
+                    Style::new().dim().apply_to(&ws.id)
+                ));
+                if let Some(ref applied) = ws.template {
+                    output::kv("Template", &applied.name);
+                    output::kv("Files", applied.files);
+                    output::kv("Notes", applied.notes);
+                    if !applied.workflows.is_empty() {
+                        output::kv("Workflows", applied.workflows.join(", "));
+                    }
+                    if !applied.repos.is_empty() {
+                        output::kv("Repos", applied.repos.join(", "));
+                    }
+                    for warning in &applied.warnings {
+                        output::hint(warning);
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_export(workstream: &str, output: Option<PathBuf>, ctx: &Context) -> Result<()> {
+    let path = output.unwrap_or_else(|| PathBuf::from(format!("{workstream}.tar.zst")));
+    let archiver = open_archiver(false).await?;
+
+    let file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let manifest = match archiver.export(workstream, std::io::BufWriter::new(file)) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            return Err(e.into());
+        }
+    };
+
+    if ctx.json_output {
+        println!("{}", serde_json::to_string_pretty(&manifest)?);
+    } else {
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        output::success(format!(
+            "Exported {} to {} ({})",
+            manifest.title,
+            path.display(),
+            output::format_size(size)
+        ));
+        let c = &manifest.counts;
+        output::kv("Sessions", c.sessions);
+        output::kv("Messages", c.messages);
+        output::kv(
+            "Memories",
+            format!("{} ({} embedded)", c.memories, c.embeddings),
+        );
+        output::kv("Notes", c.notes);
+        output::kv("Files", c.files);
+    }
+    Ok(())
+}
+
+async fn cmd_import(file: &std::path::Path, options: &ImportOptions, ctx: &Context) -> Result<()> {
+    let archiver = open_archiver(options.reembed).await?;
+    let reader =
+        std::fs::File::open(file).with_context(|| format!("Failed to open {}", file.display()))?;
+    let report = archiver
+        .import(std::io::BufReader::new(reader), options)
+        .await?;
+
+    if ctx.json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        output::success(format!(
+            "Imported {} as {}",
+            report.title,
+            Style::new().dim().apply_to(&report.workstream_id)
+        ));
+        let c = &report.imported;
+        output::kv("Sessions", c.sessions);
+        output::kv("Messages", c.messages);
+        output::kv(
+            "Memories",
+            format!("{} ({} embedded)", c.memories, c.embeddings),
+        );
+        output::kv("Notes", c.notes);
+        output::kv("Files", c.files);
+        if report.memories_skipped > 0 || report.notes_skipped > 0 {
+            output::hint(format!(
+                "Skipped {} memories and {} notes that already exist",
+                report.memories_skipped, report.notes_skipped
+            ));
+        }
+        if report.memories_without_embedding > 0 {
+            output::hint(format!(
+                "{} memories have no embedding; rerun with --reembed or run `arawn memory reindex`",
+                report.memories_without_embedding
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Open the local workstream and memory stores (same paths as `arawn start`).
+///
+/// The embedder is only built when re-embedding was requested.
+async fn open_archiver(with_embedder: bool) -> Result<WorkstreamArchiver> {
+    let config = arawn_config::load_config(None)?.config;
+    let data_dir = arawn_config::xdg_config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    let resolve = |p: PathBuf| if p.is_relative() { data_dir.join(p) } else { p };
+
+    let ws_cfg = config.workstream.clone().unwrap_or_default();
+    let ws_data_dir = ws_cfg
+        .data_dir
+        .map(resolve)
+        .unwrap_or_else(|| data_dir.join("workstreams"));
+    let ws_config = WorkstreamConfig {
+        db_path: ws_cfg
+            .database
+            .map(resolve)
+            .unwrap_or_else(|| data_dir.join("workstreams.db")),
+        data_dir: ws_data_dir.clone(),
+        session_timeout_minutes: ws_cfg.session_timeout_minutes,
+    };
+    let manager = WorkstreamManager::new(&ws_config)?
+        .with_directory_manager(DirectoryManager::new(&ws_data_dir));
+
+    arawn_memory::init_vector_extension();
+    let memory = super::memory::open_memory_store()?;
+
+    let embedder = if with_embedder {
+        let embedding_config = config.embedding.clone().unwrap_or_default();
+        let spec = super::memory::build_embedder_spec(&embedding_config);
+        Some(
+            arawn_llm::build_embedder(&spec)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to build embedder: {e}"))?,
+        )
+    } else {
+        None
+    };
+
+    // Use the vector table the store already has; fall back to the embedder
+    // for a store that has never been started.
+    match arawn_domain::EmbedderInfo::from_store(&memory)? {
+        Some(info) => memory.init_vectors(info.dimensions, &info.provider)?,
+        None => {
+            if let Some(ref embedder) = embedder {
+                memory.init_vectors(embedder.dimensions(), embedder.name())?;
+            }
+        }
+    }
+
+    let mut archiver =
+        WorkstreamArchiver::new(Arc::new(manager)).with_memory_store(Arc::new(memory));
+    if let Some(embedder) = embedder {
+        archiver = archiver.with_embedder(embedder);
+    }
+    Ok(archiver)
+}
+
+async fn run_snapshot(cmd: SnapshotCommands, ctx: &Context) -> Result<()> {
+    let client = Client::new(&ctx.server_url)?;
+    let dim = Style::new().dim();
+
+    match cmd {
+        SnapshotCommands::List { workstream } => match client.list_snapshots(&workstream).await {
+            Ok(snapshots) => {
+                if ctx.json_output {
+                    println!("{}", serde_json::to_string_pretty(&snapshots)?);
+                } else {
+                    output::header(&format!("Snapshots: {}", workstream));
+
+                    if snapshots.is_empty() {
+                        output::hint("No snapshots found");
+                    } else {
+                        for s in &snapshots {
+                            let label = s
+                                .label
+                                .as_deref()
+                                .map(|l| format!(" \"{}\"", l))
+                                .unwrap_or_default();
+                            println!(
+                                "  {} {:<8} {} files, {}{}",
+                                Style::new().cyan().apply_to(&s.id),
+                                s.trigger,
+                                s.file_count,
+                                output::format_size(s.total_bytes),
+                                dim.apply_to(label),
+                            );
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+            }
+        },
+        SnapshotCommands::Create { workstream, label } => {
+            match client.create_snapshot(&workstream, label.as_deref()).await {
+                Ok(snapshot) => {
+                    if ctx.json_output {
+                        println!("{}", serde_json::to_string_pretty(&snapshot)?);
+                    } else {
+                        output::success(format!(
+                            "Snapshot created: {} ({} files)",
+                            dim.apply_to(&snapshot.id),
+                            snapshot.file_count
+                        ));
+                    }
+                }
+                Err(e) => {
+                    super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+                }
+            }
+        }
+        SnapshotCommands::Diff {
+            workstream,
+            id,
+            against,
+        } => match client
+            .diff_snapshot(&workstream, &id, against.as_deref())
+            .await
+        {
+            Ok(diff) => {
+                if ctx.json_output {
+                    println!("{}", serde_json::to_string_pretty(&diff)?);
+                } else {
+                    output::header(&format!(
+                        "{} → {}",
+                        id,
+                        against.as_deref().unwrap_or("production")
+                    ));
+
+                    if diff.added.is_empty() && diff.removed.is_empty() && diff.modified.is_empty()
+                    {
+                        output::hint("No changes");
+                    } else {
+                        let green = Style::new().green();
+                        let red = Style::new().red();
+                        let yellow = Style::new().yellow();
+                        for path in &diff.added {
+                            println!("  {} {}", green.apply_to("+"), path);
+                        }
+                        for path in &diff.removed {
+                            println!("  {} {}", red.apply_to("-"), path);
+                        }
+                        for path in &diff.modified {
+                            println!("  {} {}", yellow.apply_to("~"), path);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+            }
+        },
+        SnapshotCommands::Restore { workstream, id } => {
+            match client.restore_snapshot(&workstream, &id).await {
+                Ok(result) => {
+                    if ctx.json_output {
+                        println!("{}", serde_json::to_string_pretty(&result)?);
+                    } else {
+                        output::success(format!(
+                            "Restored {}: {} files written, {} removed",
+                            id, result.files_restored, result.files_removed
+                        ));
+                        if let Some(ref safety) = result.safety_snapshot_id {
+                            output::hint(format!("Previous state saved as {}", safety));
+                        }
+                    }
+                }
+                Err(e) => {
+                    super::print_cli_error(&e, &ctx.server_url, ctx.verbose);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -7,10 +7,11 @@ use clap::{Parser, Subcommand};
 
 mod client;
 mod commands;
+mod telemetry;
 
 use commands::{
-    agent, ask, auth, chat, config, logs, mcp, memory, notes, plugin, secrets, session, start,
-    status, tui,
+    agent, ask, auth, chat, config, logs, mcp, memory, notes, plugin, search, secrets, session,
+    start, status, tui, workstream,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -69,6 +70,9 @@ pub enum Commands {
     /// Note management
     Notes(notes::NotesArgs),
 
+    /// Search conversation history across workstreams
+    Search(search::SearchArgs),
+
     /// Configuration management
     Config(config::ConfigArgs),
 
@@ -95,6 +99,9 @@ pub enum Commands {
 
     /// Launch Terminal UI
     Tui(tui::TuiArgs),
+
+    /// Workstream management (snapshots, export and import)
+    Workstream(workstream::WorkstreamArgs),
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -539,8 +546,9 @@ mod tests {
         let cli = Cli::try_parse_from(["arawn", "auth", "token"]).unwrap();
         match cli.command {
             Commands::Auth(args) => match args.command {
-                auth::AuthCommand::Token { generate } => {
+                auth::AuthCommand::Token { generate, command } => {
                     assert!(!generate);
+                    assert!(command.is_none());
                 }
                 _ => panic!("Expected Token"),
             },
@@ -553,8 +561,9 @@ mod tests {
         let cli = Cli::try_parse_from(["arawn", "auth", "token", "--generate"]).unwrap();
         match cli.command {
             Commands::Auth(args) => match args.command {
-                auth::AuthCommand::Token { generate } => {
+                auth::AuthCommand::Token { generate, command } => {
                     assert!(generate);
+                    assert!(command.is_none());
                 }
                 _ => panic!("Expected Token"),
             },
@@ -562,6 +571,111 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_auth_token_create() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "auth",
+            "token",
+            "create",
+            "ci",
+            "--user",
+            "alice",
+            "--scope",
+            "read",
+            "--scope",
+            "mcp-manage",
+            "--expires",
+            "30",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Auth(args) => match args.command {
+                auth::AuthCommand::Token {
+                    command:
+                        Some(auth::TokenCommand::Create {
+                            name,
+                            user,
+                            scopes,
+                            expires,
+                        }),
+                    ..
+                } => {
+                    assert_eq!(name, "ci");
+                    assert_eq!(user.as_deref(), Some("alice"));
+                    assert_eq!(
+                        scopes,
+                        vec![
+                            arawn_workstream::TokenScope::Read,
+                            arawn_workstream::TokenScope::McpManage
+                        ]
+                    );
+                    assert!(expires.is_some());
+                }
+                _ => panic!("Expected Token create"),
+            },
+            _ => panic!("Expected Auth command"),
+        }
+    }
+
+    #[test]
+    fn test_auth_token_create_defaults() {
+        let cli = Cli::try_parse_from(["arawn", "auth", "token", "create", "laptop"]).unwrap();
+        match cli.command {
+            Commands::Auth(args) => match args.command {
+                auth::AuthCommand::Token {
+                    command:
+                        Some(auth::TokenCommand::Create {
+                            user,
+                            scopes,
+                            expires,
+                            ..
+                        }),
+                    ..
+                } => {
+                    assert!(user.is_none());
+                    assert_eq!(
+                        scopes,
+                        vec![
+                            arawn_workstream::TokenScope::Read,
+                            arawn_workstream::TokenScope::Chat
+                        ]
+                    );
+                    assert!(expires.is_none());
+                }
+                _ => panic!("Expected Token create"),
+            },
+            _ => panic!("Expected Auth command"),
+        }
+    }
+
+    #[test]
+    fn test_auth_token_create_rejects_bad_scope_and_expiry() {
+        assert!(
+            Cli::try_parse_from(["arawn", "auth", "token", "create", "x", "--scope", "root"])
+                .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["arawn", "auth", "token", "create", "x", "--expires", "soon"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_auth_token_revoke() {
+        let cli = Cli::try_parse_from(["arawn", "auth", "token", "revoke", "ci"]).unwrap();
+        match cli.command {
+            Commands::Auth(args) => match args.command {
+                auth::AuthCommand::Token {
+                    command: Some(auth::TokenCommand::Revoke { id_or_name }),
+                    ..
+                } => assert_eq!(id_or_name, "ci"),
+                _ => panic!("Expected Token revoke"),
+            },
+            _ => panic!("Expected Auth command"),
+        }
+    }
+
     #[test]
     fn test_auth_missing_subcommand() {
         let result = Cli::try_parse_from(["arawn", "auth"]);
@@ -607,6 +721,160 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── Workstream Subcommand ───────────────────────────────────────
+
+    #[test]
+    fn test_workstream_snapshot_create_with_label() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "workstream",
+            "snapshot",
+            "create",
+            "docs",
+            "--label",
+            "before rewrite",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Workstream(args) => match args.command {
+                workstream::WorkstreamCommands::Snapshot(
+                    workstream::SnapshotCommands::Create { workstream, label },
+                ) => {
+                    assert_eq!(workstream, "docs");
+                    assert_eq!(label.as_deref(), Some("before rewrite"));
+                }
+                _ => panic!("Expected Snapshot Create"),
+            },
+            _ => panic!("Expected Workstream command"),
+        }
+    }
+
+    #[test]
+    fn test_workstream_snapshot_diff_against() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "workstream",
+            "snapshot",
+            "diff",
+            "docs",
+            "snap-a",
+            "--against",
+            "snap-b",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Workstream(args) => match args.command {
+                workstream::WorkstreamCommands::Snapshot(workstream::SnapshotCommands::Diff {
+                    workstream,
+                    id,
+                    against,
+                }) => {
+                    assert_eq!(workstream, "docs");
+                    assert_eq!(id, "snap-a");
+                    assert_eq!(against.as_deref(), Some("snap-b"));
+                }
+                _ => panic!("Expected Snapshot Diff"),
+            },
+            _ => panic!("Expected Workstream command"),
+        }
+    }
+
+    #[test]
+    fn test_search_with_filters() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "search",
+            "database",
+            "migration",
+            "-w",
+            "ops",
+            "--role",
+            "user,assistant",
+            "--tag",
+            "infra",
+            "--since",
+            "2026-01-01",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Search(args) => {
+                assert_eq!(args.query, vec!["database", "migration"]);
+                assert_eq!(args.workstream.as_deref(), Some("ops"));
+                assert_eq!(args.roles, vec!["user", "assistant"]);
+                assert_eq!(args.tags, vec!["infra"]);
+                assert_eq!(args.since.as_deref(), Some("2026-01-01"));
+                assert_eq!(args.limit, 20);
+            }
+            _ => panic!("Expected Search command"),
+        }
+    }
+
+    #[test]
+    fn test_search_requires_query() {
+        assert!(Cli::try_parse_from(["arawn", "search"]).is_err());
+    }
+
+    #[test]
+    fn test_workstream_export() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "workstream",
+            "export",
+            "docs",
+            "-o",
+            "docs.tar.zst",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Workstream(args) => match args.command {
+                workstream::WorkstreamCommands::Export { workstream, output } => {
+                    assert_eq!(workstream, "docs");
+                    assert_eq!(output, Some(std::path::PathBuf::from("docs.tar.zst")));
+                }
+                _ => panic!("Expected Export"),
+            },
+            _ => panic!("Expected Workstream command"),
+        }
+    }
+
+    #[test]
+    fn test_workstream_import_flags() {
+        let cli = Cli::try_parse_from([
+            "arawn",
+            "workstream",
+            "import",
+            "docs.tar.zst",
+            "--new-ids",
+            "--title",
+            "Docs copy",
+            "--reembed",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Workstream(args) => match args.command {
+                workstream::WorkstreamCommands::Import {
+                    file,
+                    new_ids,
+                    title,
+                    reembed,
+                } => {
+                    assert_eq!(file, std::path::PathBuf::from("docs.tar.zst"));
+                    assert!(new_ids);
+                    assert_eq!(title.as_deref(), Some("Docs copy"));
+                    assert!(reembed);
+                }
+                _ => panic!("Expected Import"),
+            },
+            _ => panic!("Expected Workstream command"),
+        }
+    }
+
+    #[test]
+    fn test_workstream_snapshot_restore_missing_id() {
+        let result = Cli::try_parse_from(["arawn", "workstream", "snapshot", "restore", "docs"]);
+        assert!(result.is_err());
+    }
+
     // ── Global Flags ────────────────────────────────────────────────
 
     #[test]
@@ -662,6 +930,14 @@ async fn run() -> Result<()> {
 
     use tracing_subscriber::prelude::*;
 
+    // OTLP trace export ([telemetry]) only applies to the server
+    let tracer_provider = match &cli.command {
+        Commands::Start(args) => {
+            telemetry::tracer_provider(args.config.as_deref(), args.workspace.as_deref())
+        }
+        _ => None,
+    };
+
     if is_tui {
         // TUI mode: tracing is set up by the TUI itself with a log buffer
         // Don't initialize here - the TUI will handle it
@@ -681,6 +957,11 @@ async fn run() -> Result<()> {
                         "arawn=trace,arawn_agent=trace,arawn_llm=trace,arawn_server=trace,arawn_oauth=trace,arawn_config=trace,info"
                     )),
             )
+            .with(tracer_provider.as_ref().map(|provider| {
+                tracing_opentelemetry::layer()
+                    .with_tracer(telemetry::tracer(provider))
+                    .with_filter(tracing_subscriber::EnvFilter::new(filter))
+            }))
             .init();
     }
 
@@ -695,7 +976,7 @@ async fn run() -> Result<()> {
     };
 
     // Dispatch to command handlers
-    match cli.command {
+    let result = match cli.command {
         Commands::Start(args) => start::run(args, &ctx).await,
         Commands::Status(args) => status::run(args, &ctx).await,
         Commands::Ask(args) => ask::run(args, &ctx).await,
@@ -710,6 +991,16 @@ async fn run() -> Result<()> {
         Commands::Secrets(args) => secrets::run(args).await,
         Commands::Session(args) => session::run(args, &ctx).await,
         Commands::Logs(args) => logs::run(args, &ctx).await,
+        Commands::Search(args) => search::run(args, &ctx).await,
         Commands::Tui(args) => tui::run(args, &ctx).await,
+        Commands::Workstream(args) => workstream::run(args, &ctx).await,
+    };
+
+    // Flush spans still buffered for export
+    if let Some(provider) = tracer_provider
+        && let Err(e) = provider.shutdown()
+    {
+        eprintln!("Warning: failed to flush traces: {e}");
     }
+    result
 }
@@ -0,0 +1,68 @@
+//! OpenTelemetry trace export for `arawn start`.
+//!
+//! When `[telemetry] otlp_endpoint` (or `OTEL_EXPORTER_OTLP_ENDPOINT`) is
+//! set, spans are exported over OTLP/HTTP: the HTTP request span from the
+//! server, the `agent_turn` span under it, and the `llm_call` and `tool`
+//! spans under the turn.
+
+use std::path::Path;
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+
+/// Environment variables the OTLP exporter reads its endpoint from.
+const OTLP_ENDPOINT_VARS: &[&str] = &[
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+];
+
+/// Build a tracer provider exporting to the configured OTLP endpoint.
+///
+/// Reads the same config `arawn start` does (`--config`, or discovery from
+/// `--workspace`). Returns `None` when no endpoint is configured. The
+/// provider must be shut down before exit so buffered spans are flushed.
+pub fn tracer_provider(
+    config: Option<&Path>,
+    workspace: Option<&Path>,
+) -> Option<SdkTracerProvider> {
+    let loaded = match config {
+        Some(path) => arawn_config::load_config_file(path).ok(),
+        None => arawn_config::load_config(workspace).ok().map(|l| l.config),
+    };
+    let telemetry = loaded.and_then(|c| c.telemetry).unwrap_or_default();
+
+    let mut builder = SpanExporter::builder().with_http();
+    if let Some(endpoint) = &telemetry.otlp_endpoint {
+        builder = builder.with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')));
+    } else if !OTLP_ENDPOINT_VARS
+        .iter()
+        .any(|var| std::env::var(var).is_ok_and(|v| !v.is_empty()))
+    {
+        return None;
+    }
+
+    let exporter = match builder.build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("Warning: failed to create OTLP exporter: {e}");
+            return None;
+        }
+    };
+    Some(
+        SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(telemetry.service_name)
+                    .build(),
+            )
+            .build(),
+    )
+}
+
+/// Tracer used by the `tracing` → OpenTelemetry bridge.
+pub fn tracer(provider: &SdkTracerProvider) -> SdkTracer {
+    provider.tracer("arawn")
+}